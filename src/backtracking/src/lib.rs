@@ -0,0 +1,118 @@
+//! Backtracking solvers for puzzles on a grid that record their steps so that they can be visualized.
+pub mod n_queens;
+pub mod sudoku;
+
+pub use n_queens::*;
+pub use sudoku::*;
+
+use std::fmt;
+
+/// The cells of a puzzle by row and column, where `None` is an empty cell.
+pub type Grid<T> = Vec<Vec<Option<T>>>;
+
+/// A step of a backtracking search on a [`Grid`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BacktrackStep<T> {
+    /// Consider putting a value in a cell: `(row, column, value)`
+    Try(usize, usize, T),
+    /// The value can't go in the cell because of the value in another cell: `(row, column, value, (conflict_row, conflict_column))`
+    Conflict(usize, usize, T, (usize, usize)),
+    /// Put the value in the cell and continue with the next cell: `(row, column, value)`
+    Place(usize, usize, T),
+    /// Take the value back out of the cell, since none of the values after it led to a solution: `(row, column, value)`
+    Backtrack(usize, usize, T),
+}
+
+impl<T> BacktrackStep<T> {
+    /// Gets the row and column of the cell that the step is about.
+    pub fn cell(&self) -> (usize, usize) {
+        match self {
+            BacktrackStep::Try(row, column, _)
+            | BacktrackStep::Conflict(row, column, _, _)
+            | BacktrackStep::Place(row, column, _)
+            | BacktrackStep::Backtrack(row, column, _) => (*row, *column),
+        }
+    }
+    /// Converts the value of the step, like into a label to draw it with.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> BacktrackStep<U> {
+        match self {
+            BacktrackStep::Try(row, column, value) => BacktrackStep::Try(row, column, f(value)),
+            BacktrackStep::Conflict(row, column, value, conflict) => {
+                BacktrackStep::Conflict(row, column, f(value), conflict)
+            }
+            BacktrackStep::Place(row, column, value) => BacktrackStep::Place(row, column, f(value)),
+            BacktrackStep::Backtrack(row, column, value) => {
+                BacktrackStep::Backtrack(row, column, f(value))
+            }
+        }
+    }
+}
+
+/// The rows and columns are counted from 1, like on a printed board.
+impl<T: fmt::Display> fmt::Display for BacktrackStep<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BacktrackStep::Try(row, column, value) => write!(
+                f,
+                "Trying {} at row {}, column {}",
+                value,
+                row + 1,
+                column + 1
+            ),
+            BacktrackStep::Conflict(row, column, value, (conflict_row, conflict_column)) => write!(
+                f,
+                "Can't put {} at row {}, column {}: it conflicts with row {}, column {}",
+                value,
+                row + 1,
+                column + 1,
+                conflict_row + 1,
+                conflict_column + 1
+            ),
+            BacktrackStep::Place(row, column, value) => write!(
+                f,
+                "Placing {} at row {}, column {}",
+                value,
+                row + 1,
+                column + 1
+            ),
+            BacktrackStep::Backtrack(row, column, value) => write!(
+                f,
+                "Backtracking: removing {} from row {}, column {}",
+                value,
+                row + 1,
+                column + 1
+            ),
+        }
+    }
+}
+
+/// Runs recorded steps on a grid. Only placing and backtracking change it.
+pub fn run_backtrack_steps<T: Clone>(grid: &mut Grid<T>, steps: &[BacktrackStep<T>]) {
+    for step in steps {
+        match step {
+            BacktrackStep::Place(row, column, value) => grid[*row][*column] = Some(value.clone()),
+            BacktrackStep::Backtrack(row, column, _) => grid[*row][*column] = None,
+            BacktrackStep::Try(..) | BacktrackStep::Conflict(..) => (),
+        }
+    }
+}
+
+/// How a backtracking search ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolveOutcome {
+    Solved,
+    /// Every value was tried in every cell without finding a solution.
+    NoSolution,
+    /// The search was stopped after the most steps it was allowed to take.
+    StepLimitReached,
+}
+
+impl fmt::Display for SolveOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveOutcome::Solved => write!(f, "solved"),
+            SolveOutcome::NoSolution => write!(f, "no solution"),
+            SolveOutcome::StepLimitReached => write!(f, "step limit reached"),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+
+use crate::{BacktrackStep, Grid, SolveOutcome};
+
+/// A queen on the board of the N-Queens problem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Queen;
+
+impl fmt::Display for Queen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a queen")
+    }
+}
+
+/// Places `n` queens on an `n` by `n` board so that no two queens are on the same row, column or diagonal.\
+/// The queens are placed row by row, trying the columns from left to right, and the search stops after `max_steps` steps.
+/// Returns the board, which has the solution if one was found.
+///
+/// ## Example
+///
+/// ```rust
+/// use backtracking::{solve_n_queens, SolveOutcome};
+///
+/// let mut steps = vec![];
+/// let (board, outcome) = solve_n_queens(4, 1000, &mut steps);
+///
+/// assert_eq!(outcome, SolveOutcome::Solved);
+/// assert_eq!(board.iter().flatten().flatten().count(), 4);
+/// ```
+pub fn solve_n_queens(
+    n: usize,
+    max_steps: usize,
+    steps: &mut Vec<BacktrackStep<Queen>>,
+) -> (Grid<Queen>, SolveOutcome) {
+    let mut grid = vec![vec![None; n]; n];
+    // The column of the queen on each row that has one
+    let mut columns = Vec::with_capacity(n);
+    let max_len = steps.len() + max_steps;
+    let outcome = place_queens(n, &mut columns, max_len, steps);
+    for (row, column) in columns.into_iter().enumerate() {
+        grid[row][column] = Some(Queen);
+    }
+    (grid, outcome)
+}
+
+fn place_queens(
+    n: usize,
+    columns: &mut Vec<usize>,
+    max_len: usize,
+    steps: &mut Vec<BacktrackStep<Queen>>,
+) -> SolveOutcome {
+    let row = columns.len();
+    if row == n {
+        return SolveOutcome::Solved;
+    }
+
+    for column in 0..n {
+        if steps.len() >= max_len {
+            return SolveOutcome::StepLimitReached;
+        }
+        steps.push(BacktrackStep::Try(row, column, Queen));
+
+        // Queens attack along their column and both diagonals
+        let conflict = columns
+            .iter()
+            .enumerate()
+            .find(|(other_row, other_column)| {
+                **other_column == column || other_column.abs_diff(column) == row - other_row
+            });
+        if let Some((other_row, other_column)) = conflict {
+            steps.push(BacktrackStep::Conflict(
+                row,
+                column,
+                Queen,
+                (other_row, *other_column),
+            ));
+            continue;
+        }
+
+        steps.push(BacktrackStep::Place(row, column, Queen));
+        columns.push(column);
+        match place_queens(n, columns, max_len, steps) {
+            SolveOutcome::NoSolution => {
+                columns.pop();
+                steps.push(BacktrackStep::Backtrack(row, column, Queen));
+            }
+            outcome => return outcome,
+        }
+    }
+    SolveOutcome::NoSolution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_backtrack_steps;
+
+    #[test]
+    fn solutions_have_no_attacking_queens() {
+        for n in 1..=10 {
+            let mut steps = vec![];
+            let (board, outcome) = solve_n_queens(n, usize::MAX, &mut steps);
+            // There are no solutions for 2 and 3 queens
+            if n == 2 || n == 3 {
+                assert_eq!(outcome, SolveOutcome::NoSolution);
+                assert!(board.iter().flatten().all(Option::is_none));
+                continue;
+            }
+            assert_eq!(outcome, SolveOutcome::Solved);
+
+            let queens = (0..n)
+                .map(|row| board[row].iter().position(Option::is_some).unwrap())
+                .collect::<Vec<usize>>();
+            for (row, column) in queens.iter().enumerate() {
+                for (other_row, other_column) in queens.iter().enumerate().skip(row + 1) {
+                    assert_ne!(column, other_column);
+                    assert_ne!(column.abs_diff(*other_column), other_row - row);
+                }
+            }
+
+            let mut replayed = vec![vec![None; n]; n];
+            run_backtrack_steps(&mut replayed, &steps);
+            assert_eq!(replayed, board);
+        }
+    }
+
+    #[test]
+    fn stops_at_the_step_limit() {
+        let mut steps = vec![];
+        let (_, outcome) = solve_n_queens(8, 50, &mut steps);
+        assert_eq!(outcome, SolveOutcome::StepLimitReached);
+        // The last candidate may still record its result after reaching the limit
+        assert!(steps.len() <= 52);
+    }
+}
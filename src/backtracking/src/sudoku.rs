@@ -0,0 +1,255 @@
+use std::{error::Error, fmt};
+
+use crate::{BacktrackStep, Grid, SolveOutcome};
+
+/// A puzzle with the given digits filled in, which is solved in about 80 000 steps.
+pub const EXAMPLE_SUDOKU: &str =
+    "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+
+/// Why a sudoku couldn't be read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SudokuError {
+    /// A sudoku has 81 cells, but there were this many.
+    WrongLength(usize),
+    /// A character that isn't a digit or `.` at an index: `(character, index)`
+    InvalidCharacter(char, usize),
+    /// Two given digits are the same on a row, column or box: `((row, column), (other_row, other_column))`
+    ConflictingGivens((usize, usize), (usize, usize)),
+}
+impl fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SudokuError::WrongLength(len) => write!(f, "sudoku has {} cells instead of 81", len),
+            SudokuError::InvalidCharacter(character, index) => {
+                write!(f, "invalid character '{}' at cell {}", character, index + 1)
+            }
+            SudokuError::ConflictingGivens((row, column), (other_row, other_column)) => write!(
+                f,
+                "digits at row {}, column {} and row {}, column {} conflict",
+                row + 1,
+                column + 1,
+                other_row + 1,
+                other_column + 1
+            ),
+        }
+    }
+}
+impl Error for SudokuError {}
+
+/// Reads a sudoku from its 81 cells row by row, where the given digits are `1` to `9` and the empty cells are `.` or `0`.
+/// Whitespace is skipped, so the rows can be on their own lines.
+///
+/// ## Example
+///
+/// ```rust
+/// use backtracking::{parse_sudoku, SudokuError, EXAMPLE_SUDOKU};
+///
+/// let grid = parse_sudoku(EXAMPLE_SUDOKU).unwrap();
+/// assert_eq!(grid[0][0], Some(5));
+/// assert_eq!(grid[0][2], None);
+///
+/// assert_eq!(parse_sudoku("123"), Err(SudokuError::WrongLength(3)));
+/// ```
+pub fn parse_sudoku(text: &str) -> Result<Grid<u8>, SudokuError> {
+    let mut cells = Vec::with_capacity(81);
+    for (index, character) in text.chars().filter(|c| !c.is_whitespace()).enumerate() {
+        match character {
+            '.' | '0' => cells.push(None),
+            '1'..='9' => cells.push(Some(character as u8 - b'0')),
+            _ => return Err(SudokuError::InvalidCharacter(character, index)),
+        }
+    }
+    if cells.len() != 81 {
+        return Err(SudokuError::WrongLength(cells.len()));
+    }
+
+    let grid = cells.chunks(9).map(<[Option<u8>]>::to_vec).collect();
+    match sudoku_conflict(&grid) {
+        Some((cell, other_cell)) => Err(SudokuError::ConflictingGivens(cell, other_cell)),
+        None => Ok(grid),
+    }
+}
+
+/// Writes a sudoku as its 81 cells row by row in the format read by [`parse_sudoku`].
+pub fn sudoku_to_string(grid: &Grid<u8>) -> String {
+    grid.iter()
+        .flatten()
+        .map(|cell| match cell {
+            Some(digit) => (b'0' + digit) as char,
+            None => '.',
+        })
+        .collect()
+}
+
+/// Finds a cell on the same row, column or 3x3 box as `(row, column)` that already has `digit`.
+fn find_digit(grid: &Grid<u8>, row: usize, column: usize, digit: u8) -> Option<(usize, usize)> {
+    let (box_row, box_column) = (row / 3 * 3, column / 3 * 3);
+    (0..9)
+        .map(|i| (row, i))
+        .chain((0..9).map(|i| (i, column)))
+        .chain((0..9).map(|i| (box_row + i / 3, box_column + i % 3)))
+        .find(|&(other_row, other_column)| {
+            (other_row, other_column) != (row, column)
+                && grid[other_row][other_column] == Some(digit)
+        })
+}
+
+/// Finds the first two cells that have the same digit on a row, column or 3x3 box.
+pub fn sudoku_conflict(grid: &Grid<u8>) -> Option<((usize, usize), (usize, usize))> {
+    (0..81).find_map(|i| {
+        let (row, column) = (i / 9, i % 9);
+        grid[row][column]
+            .and_then(|digit| find_digit(grid, row, column, digit))
+            .map(|other_cell| ((row, column), other_cell))
+    })
+}
+
+/// Fills the empty cells of a sudoku so that every row, column and 3x3 box has the digits 1 to 9 once.\
+/// The cells are filled row by row, trying the digits from 1 up, and the search stops after `max_steps` steps.
+/// A sudoku whose given digits conflict has no solution and takes no steps.
+///
+/// ## Example
+///
+/// ```rust
+/// use backtracking::{parse_sudoku, solve_sudoku, SolveOutcome, EXAMPLE_SUDOKU};
+///
+/// let mut grid = parse_sudoku(EXAMPLE_SUDOKU).unwrap();
+/// let mut steps = vec![];
+///
+/// assert_eq!(solve_sudoku(&mut grid, 100_000, &mut steps), SolveOutcome::Solved);
+/// assert!(grid.iter().flatten().all(Option::is_some));
+/// ```
+pub fn solve_sudoku(
+    grid: &mut Grid<u8>,
+    max_steps: usize,
+    steps: &mut Vec<BacktrackStep<u8>>,
+) -> SolveOutcome {
+    if sudoku_conflict(grid).is_some() {
+        return SolveOutcome::NoSolution;
+    }
+    let empty_cells = (0..81)
+        .map(|i| (i / 9, i % 9))
+        .filter(|&(row, column)| grid[row][column].is_none())
+        .collect::<Vec<(usize, usize)>>();
+    let max_len = steps.len() + max_steps;
+    fill_cells(grid, &empty_cells, max_len, steps)
+}
+
+fn fill_cells(
+    grid: &mut Grid<u8>,
+    empty_cells: &[(usize, usize)],
+    max_len: usize,
+    steps: &mut Vec<BacktrackStep<u8>>,
+) -> SolveOutcome {
+    let Some((&(row, column), rest)) = empty_cells.split_first() else {
+        return SolveOutcome::Solved;
+    };
+
+    for digit in 1..=9 {
+        if steps.len() >= max_len {
+            return SolveOutcome::StepLimitReached;
+        }
+        steps.push(BacktrackStep::Try(row, column, digit));
+
+        if let Some(conflict) = find_digit(grid, row, column, digit) {
+            steps.push(BacktrackStep::Conflict(row, column, digit, conflict));
+            continue;
+        }
+
+        steps.push(BacktrackStep::Place(row, column, digit));
+        grid[row][column] = Some(digit);
+        match fill_cells(grid, rest, max_len, steps) {
+            SolveOutcome::NoSolution => {
+                grid[row][column] = None;
+                steps.push(BacktrackStep::Backtrack(row, column, digit));
+            }
+            outcome => return outcome,
+        }
+    }
+    SolveOutcome::NoSolution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_backtrack_steps;
+
+    #[test]
+    fn parses_and_writes_sudokus() {
+        let grid = parse_sudoku(EXAMPLE_SUDOKU).unwrap();
+        assert_eq!(sudoku_to_string(&grid), EXAMPLE_SUDOKU);
+
+        let with_lines = EXAMPLE_SUDOKU
+            .as_bytes()
+            .chunks(9)
+            .map(|row| std::str::from_utf8(row).unwrap().replace('.', "0"))
+            .collect::<Vec<String>>()
+            .join("\n");
+        assert_eq!(parse_sudoku(&with_lines), Ok(grid));
+
+        assert_eq!(
+            parse_sudoku(&EXAMPLE_SUDOKU.replacen('.', "x", 1)),
+            Err(SudokuError::InvalidCharacter('x', 2))
+        );
+        // The 5 on the first row is given again at its end
+        assert_eq!(
+            parse_sudoku(&format!(
+                "{}5{}",
+                &EXAMPLE_SUDOKU[..8],
+                &EXAMPLE_SUDOKU[9..]
+            )),
+            Err(SudokuError::ConflictingGivens((0, 0), (0, 8)))
+        );
+        assert_eq!(
+            SudokuError::WrongLength(80).to_string(),
+            "sudoku has 80 cells instead of 81"
+        );
+    }
+
+    #[test]
+    fn solutions_keep_the_givens_and_have_no_conflicts() {
+        let givens = parse_sudoku(EXAMPLE_SUDOKU).unwrap();
+        let mut grid = givens.clone();
+        let mut steps = vec![];
+        assert_eq!(
+            solve_sudoku(&mut grid, usize::MAX, &mut steps),
+            SolveOutcome::Solved
+        );
+        assert_eq!(sudoku_conflict(&grid), None);
+        for (row, column) in (0..81).map(|i| (i / 9, i % 9)) {
+            assert!(grid[row][column].is_some());
+            if givens[row][column].is_some() {
+                assert_eq!(grid[row][column], givens[row][column]);
+            }
+        }
+
+        let mut replayed = givens;
+        run_backtrack_steps(&mut replayed, &steps);
+        assert_eq!(replayed, grid);
+        assert!(steps
+            .iter()
+            .any(|step| matches!(step, BacktrackStep::Backtrack(..))));
+    }
+
+    #[test]
+    fn unsolvable_sudokus_have_no_solution() {
+        // The first cell can't have any digit, since its row and column have all of them
+        let mut grid =
+            parse_sudoku(&format!(".12345678{}{}", "9........", ".".repeat(63))).unwrap();
+        let mut steps = vec![];
+        assert_eq!(
+            solve_sudoku(&mut grid, usize::MAX, &mut steps),
+            SolveOutcome::NoSolution
+        );
+        assert_eq!(steps.len(), 18);
+
+        let mut conflicting = vec![vec![None; 9]; 9];
+        conflicting[0][0] = Some(1);
+        conflicting[4][0] = Some(1);
+        assert_eq!(
+            solve_sudoku(&mut conflicting, usize::MAX, &mut steps),
+            SolveOutcome::NoSolution
+        );
+        assert_eq!(steps.len(), 18);
+    }
+}
@@ -0,0 +1,6 @@
+use algorust::workers::sort_worker::SortWorker;
+use yew_agent::Threaded;
+
+fn main() {
+    SortWorker::register();
+}
@@ -0,0 +1,135 @@
+use backtracking::BacktrackStep;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+/// How the squares of a board are told apart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardStyle {
+    /// Alternating light and dark squares.
+    Chessboard,
+    /// Thick lines between the 3x3 boxes.
+    Sudoku,
+}
+
+/// A cell that was edited and the digit typed in it, or `None` when it was cleared.
+pub type CellEdit = ((usize, usize), Option<u8>);
+
+#[derive(Properties, PartialEq)]
+pub struct BoardViewProps {
+    /// The labels of the values in the cells by row and column.
+    pub cells: Vec<Vec<Option<String>>>,
+    pub style: BoardStyle,
+    /// The cells that were filled in before solving, which are drawn in bold.
+    #[prop_or_default]
+    pub givens: Vec<(usize, usize)>,
+    /// The step that was just taken, whose cell is highlighted with the label of its value.
+    #[prop_or_default]
+    pub step: Option<BacktrackStep<String>>,
+    /// Makes the cells editable.
+    #[prop_or_default]
+    pub on_edit: Option<Callback<CellEdit>>,
+}
+
+/// Draws the board of a backtracking search. The value being tried is drawn faded in its cell,
+/// a conflicting value is linked to the cell it conflicts with and a value taken back out by backtracking is drawn in red.
+#[function_component(BoardView)]
+pub fn board_view(props: &BoardViewProps) -> Html {
+    let BoardViewProps {
+        cells,
+        style,
+        givens,
+        step,
+        on_edit,
+    } = props;
+
+    let step_cell = step.as_ref().map(BacktrackStep::cell);
+    let conflict_cell = match step {
+        Some(BacktrackStep::Conflict(_, _, _, conflict)) => Some(*conflict),
+        _ => None,
+    };
+
+    let rows = cells
+        .iter()
+        .enumerate()
+        .map(|(row, row_cells)| {
+            row_cells
+                .iter()
+                .enumerate()
+                .map(|(column, cell)| {
+                    let is_step = step_cell == Some((row, column));
+                    // The value of the step is shown even when it isn't in the grid
+                    let (label, step_class) = match step {
+                        Some(BacktrackStep::Try(_, _, value)) if is_step => {
+                            (Some(value), Some("trying"))
+                        }
+                        Some(BacktrackStep::Conflict(_, _, value, _)) if is_step => {
+                            (Some(value), Some("conflict"))
+                        }
+                        Some(BacktrackStep::Place(..)) if is_step => {
+                            (cell.as_ref(), Some("placed"))
+                        }
+                        Some(BacktrackStep::Backtrack(_, _, value)) if is_step => {
+                            (Some(value), Some("backtracked"))
+                        }
+                        _ => (cell.as_ref(), None),
+                    };
+                    let class = classes!(
+                        "board-cell",
+                        step_class,
+                        (conflict_cell == Some((row, column))).then_some("conflicting"),
+                        givens.contains(&(row, column)).then_some("given"),
+                        (*style == BoardStyle::Chessboard && (row + column) % 2 == 1)
+                            .then_some("dark"),
+                        (*style == BoardStyle::Sudoku
+                            && column % 3 == 2
+                            && column + 1 < row_cells.len())
+                        .then_some("box-right"),
+                        (*style == BoardStyle::Sudoku && row % 3 == 2 && row + 1 < cells.len())
+                            .then_some("box-bottom"),
+                    );
+
+                    let content = match on_edit {
+                        Some(on_edit) => {
+                            let on_edit = on_edit.clone();
+                            let oninput = Callback::from(move |event: InputEvent| {
+                                let input: HtmlInputElement = event.target_unchecked_into();
+                                // The last typed character replaces the digit that was there
+                                let digit = input
+                                    .value()
+                                    .chars()
+                                    .last()
+                                    .and_then(|character| character.to_digit(10))
+                                    .filter(|digit| *digit != 0)
+                                    .map(|digit| digit as u8);
+                                input.set_value(
+                                    &digit.map(|digit| digit.to_string()).unwrap_or_default(),
+                                );
+                                on_edit.emit(((row, column), digit));
+                            });
+                            html! {
+                                <input
+                                    type="text"
+                                    inputmode="numeric"
+                                    aria-label={format!("Row {}, column {}", row + 1, column + 1)}
+                                    value={label.cloned().unwrap_or_default()}
+                                    {oninput}
+                                />
+                            }
+                        }
+                        None => html! { label.cloned().unwrap_or_default() },
+                    };
+                    html! { <div {class}>{ content }</div> }
+                })
+                .collect::<Html>()
+        })
+        .collect::<Html>();
+
+    html! {
+        <div
+            class={classes!("board-view", (*style == BoardStyle::Sudoku).then_some("sudoku"))}
+            style={format!("--size: {}", cells.len())}
+        >
+            { rows }
+        </div>
+    }
+}
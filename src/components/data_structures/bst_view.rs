@@ -0,0 +1,111 @@
+use data_structures::{BinarySearchTree, BstStep};
+use std::fmt::Display;
+use yew::prelude::*;
+
+/// The horizontal distance between the columns of the nodes in SVG units.
+const COLUMN_WIDTH: f64 = 40.0;
+/// The vertical distance between the levels of the tree in SVG units.
+const LEVEL_HEIGHT: f64 = 60.0;
+const NODE_RADIUS: f64 = 15.0;
+
+#[derive(Properties, PartialEq)]
+pub struct BstViewProps<T: 'static + Clone + Ord + Display> {
+    pub tree: BinarySearchTree<T>,
+    /// The step that was just taken, whose node is highlighted.
+    #[prop_or_default]
+    pub step: Option<BstStep<T>>,
+    /// The nodes that the current operation has visited so far.
+    #[prop_or_default]
+    pub visited: Vec<T>,
+}
+
+/// Draws a binary search tree with every node in its own column in sorted order and every level of the tree in its own row.\
+/// The nodes are keyed by their values, so they slide to their new places when the tree changes.
+#[function_component(BstView)]
+pub fn bst_view<T: 'static + Clone + Ord + Display>(props: &BstViewProps<T>) -> Html {
+    let layout = props.tree.layout();
+    let position = |column: usize, depth: usize| {
+        (
+            (column as f64 + 0.5) * COLUMN_WIDTH,
+            (depth as f64 + 0.5) * LEVEL_HEIGHT,
+        )
+    };
+    let positions: Vec<(f64, f64)> = layout
+        .iter()
+        .map(|node| position(node.column, node.depth))
+        .collect();
+
+    let edges = layout
+        .iter()
+        .zip(&positions)
+        .filter_map(|(node, (x, y))| {
+            let parent = node.parent.as_ref()?;
+            // The nodes are in sorted order, so the parent can be found with a binary search
+            let parent_index = layout
+                .binary_search_by(|other| other.value.cmp(parent))
+                .ok()?;
+            let (parent_x, parent_y) = positions[parent_index];
+            Some(html! {
+                <line
+                    class="bst-edge"
+                    x1={parent_x.to_string()}
+                    y1={parent_y.to_string()}
+                    x2={x.to_string()}
+                    y2={y.to_string()}
+                />
+            })
+        })
+        .collect::<Html>();
+
+    let nodes = layout
+        .iter()
+        .zip(&positions)
+        .map(|(node, (x, y))| {
+            let current = props.step.as_ref().and_then(|step| match step {
+                BstStep::Replace(_, successor) if *successor == node.value => Some("replaced"),
+                BstStep::Visit(value) if *value == node.value => Some("current"),
+                BstStep::Found(value) if *value == node.value => Some("found"),
+                BstStep::Insert(value) if *value == node.value => Some("inserted"),
+                BstStep::Output(value) if *value == node.value => Some("output"),
+                _ => None,
+            });
+            let class = classes!(
+                "bst-node",
+                props.visited.contains(&node.value).then_some("visited"),
+                current,
+            );
+            html! {
+                <g
+                    key={node.value.to_string()}
+                    {class}
+                    style={format!("transform: translate({}px, {}px)", x, y)}
+                >
+                    <circle r={NODE_RADIUS.to_string()} />
+                    <text text-anchor="middle" dominant-baseline="central">{ node.value.to_string() }</text>
+                </g>
+            }
+        })
+        .collect::<Html>();
+
+    let width = (layout.len().max(1) as f64) * COLUMN_WIDTH;
+    let height = (props.tree.height().max(1) as f64) * LEVEL_HEIGHT;
+
+    html! {
+        <div class="bst-view">
+            {
+                if layout.is_empty() {
+                    html! { <p class="bst-note">{ "The tree is empty. Insert some values to grow it." }</p> }
+                } else {
+                    html! {}
+                }
+            }
+            <svg
+                xmlns="http://www.w3.org/2000/svg"
+                viewBox={format!("0 0 {} {}", width, height)}
+            >
+                { edges }
+                { nodes }
+            </svg>
+        </div>
+    }
+}
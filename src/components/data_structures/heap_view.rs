@@ -0,0 +1,146 @@
+use yew::prelude::*;
+
+/// The most items that the heap view draws, since every level of the tree doubles its width.
+pub const HEAP_VIEW_ITEM_LIMIT: usize = 63;
+
+/// The horizontal distance between the nodes on the lowest level of the tree in SVG units.
+const COLUMN_WIDTH: f64 = 40.0;
+/// The vertical distance between the levels of the tree in SVG units.
+const LEVEL_HEIGHT: f64 = 60.0;
+const NODE_RADIUS: f64 = 15.0;
+
+#[derive(Properties, PartialEq)]
+pub struct HeapViewProps {
+    /// The labels of the items of the array in order.
+    pub items: Vec<String>,
+    /// How many of the first items are in the heap. The items after it are only shown in the array.
+    pub heap_len: usize,
+    /// The indices to highlight in both views, like the items of a swap.
+    #[prop_or_default]
+    pub highlighted: Vec<usize>,
+}
+
+/// Draws the array of a binary heap next to the tree that it stores, where the children of the item at `i` are at `2i + 1` and `2i + 2`.\
+/// Hovering an item in either view highlights it in the other one too.
+#[function_component(HeapView)]
+pub fn heap_view(props: &HeapViewProps) -> Html {
+    let hovered = use_state_eq(|| None::<usize>);
+
+    if props.items.len() > HEAP_VIEW_ITEM_LIMIT {
+        return html! {
+            <p class="heap-note">
+                { format!("The heap is only drawn for up to {} items.", HEAP_VIEW_ITEM_LIMIT) }
+            </p>
+        };
+    }
+
+    let heap_len = props.heap_len.min(props.items.len());
+    let classes_of = |i: usize| {
+        classes!(
+            props.highlighted.contains(&i).then_some("highlighted"),
+            (*hovered == Some(i)).then_some("hovered"),
+            (i >= heap_len).then_some("outside-heap"),
+        )
+    };
+    let hover = |i: usize| {
+        let hovered = hovered.clone();
+        Callback::from(move |_: MouseEvent| hovered.set(Some(i)))
+    };
+    let unhover = {
+        let hovered = hovered.clone();
+        Callback::from(move |_: MouseEvent| hovered.set(None))
+    };
+
+    let cells = props
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            html! {
+                <div
+                    class={classes!("heap-cell", classes_of(i))}
+                    onmouseenter={hover(i)}
+                    onmouseleave={unhover.clone()}
+                >
+                    <span class="heap-cell-value">{ item }</span>
+                    <span class="heap-cell-index">{ i }</span>
+                </div>
+            }
+        })
+        .collect::<Html>();
+
+    let levels = level_of(heap_len.max(1) - 1) + 1;
+    let width = (1 << (levels - 1)) as f64 * COLUMN_WIDTH;
+    let position = |i: usize| {
+        let level = level_of(i);
+        let nodes_on_level = 1 << level;
+        let index_on_level = i + 1 - nodes_on_level;
+        (
+            (index_on_level as f64 + 0.5) * width / nodes_on_level as f64,
+            (level as f64 + 0.5) * LEVEL_HEIGHT,
+        )
+    };
+
+    let edges = (1..heap_len)
+        .map(|i| {
+            let (parent_x, parent_y) = position((i - 1) / 2);
+            let (x, y) = position(i);
+            html! {
+                <line
+                    class="heap-edge"
+                    x1={parent_x.to_string()}
+                    y1={parent_y.to_string()}
+                    x2={x.to_string()}
+                    y2={y.to_string()}
+                />
+            }
+        })
+        .collect::<Html>();
+
+    let nodes = props.items[..heap_len]
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let (x, y) = position(i);
+            html! {
+                <g
+                    class={classes!("heap-node", classes_of(i))}
+                    transform={format!("translate({} {})", x, y)}
+                    onmouseenter={hover(i)}
+                    onmouseleave={unhover.clone()}
+                >
+                    <circle r={NODE_RADIUS.to_string()} />
+                    <text text-anchor="middle" dominant-baseline="central">{ item }</text>
+                </g>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="heap-view">
+            <div class="heap-array">{ cells }</div>
+            <div class="heap-tree">
+                {
+                    if heap_len == 0 {
+                        html! { <p class="heap-note">{ "The heap is empty." }</p> }
+                    } else {
+                        html! {
+                            <svg
+                                xmlns="http://www.w3.org/2000/svg"
+                                viewBox={format!("0 0 {} {}", width, levels as f64 * LEVEL_HEIGHT)}
+                            >
+                                { edges }
+                                { nodes }
+                            </svg>
+                        }
+                    }
+                }
+            </div>
+        </div>
+    }
+}
+
+/// Gets the level of the item at an index in the tree, where the root is on level 0.
+fn level_of(index: usize) -> u32 {
+    (index + 1).ilog2()
+}
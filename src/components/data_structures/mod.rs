@@ -0,0 +1,2 @@
+pub mod bst_view;
+pub mod heap_view;
@@ -0,0 +1,130 @@
+use string_algorithms::{alignment, DpStep, DpTable, Neighbor};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct DpTableViewProps {
+    /// The string along the rows of the table.
+    pub a: Vec<char>,
+    /// The string along the columns of the table.
+    pub b: Vec<char>,
+    pub table: DpTable,
+    /// The step that was just taken, whose cell is highlighted along with the neighbor it was computed from.
+    #[prop_or_default]
+    pub step: Option<DpStep>,
+}
+
+fn arrow(neighbor: Neighbor) -> &'static str {
+    match neighbor {
+        Neighbor::Diagonal => "↖",
+        Neighbor::Up => "↑",
+        Neighbor::Left => "←",
+    }
+}
+
+/// Draws a dynamic programming table with `a` down its side and `b` along its top.
+/// Every filled cell has an arrow to the neighbor it was computed from, and the traced path is lined up below the table once it has started.
+#[function_component(DpTableView)]
+pub fn dp_table_view(props: &DpTableViewProps) -> Html {
+    let DpTableViewProps { a, b, table, step } = props;
+
+    let (current, source) = match step {
+        Some(DpStep::Fill(row, column, _, from)) => (
+            Some((*row, *column)),
+            from.map(|neighbor| neighbor.of((*row, *column))),
+        ),
+        Some(DpStep::Trace(row, column)) => (Some((*row, *column)), None),
+        None => (None, None),
+    };
+    // The characters of the current cell are highlighted in the headers
+    let (current_row, current_column) = current.unzip();
+
+    let header = b
+        .iter()
+        .enumerate()
+        .map(|(i, character)| {
+            let class = classes!(
+                "dp-header",
+                (current_column == Some(i + 1)).then_some("current")
+            );
+            html! { <th {class}>{ character }</th> }
+        })
+        .collect::<Html>();
+
+    let rows = (0..=a.len())
+        .map(|row| {
+            let label = if row == 0 { "ε".to_string() } else { a[row - 1].to_string() };
+            let cells = (0..=b.len())
+                .map(|column| {
+                    let cell = (row, column);
+                    let class = classes!(
+                        "dp-cell",
+                        (current == Some(cell)).then_some("current"),
+                        (source == Some(cell)).then_some("source"),
+                        table.path.contains(&cell).then_some("path"),
+                    );
+                    html! {
+                        <td {class}>
+                            <span class="dp-arrow">
+                                { table.from[row][column].map(arrow).unwrap_or_default() }
+                            </span>
+                            { table.values[row][column].map(|value| value.to_string()).unwrap_or_default() }
+                        </td>
+                    }
+                })
+                .collect::<Html>();
+            let class = classes!("dp-header", (current_row == Some(row)).then_some("current"));
+            html! {
+                <tr>
+                    <th {class}>{ label }</th>
+                    { cells }
+                </tr>
+            }
+        })
+        .collect::<Html>();
+
+    // The alignment is only known up to the traced part of the path
+    let columns = alignment(a, b, &table.path);
+    let gap = |character: Option<char>| character.unwrap_or('-').to_string();
+    // The characters of `a` are on the top row and the ones of `b` on the bottom one
+    let aligned = |top: bool| {
+        columns
+            .iter()
+            .map(|column| {
+                let class = classes!(
+                    "dp-aligned",
+                    if column.0.is_some() && column.0 == column.1 {
+                        "matched"
+                    } else {
+                        "mismatched"
+                    }
+                );
+                html! { <span {class}>{ gap(if top { column.0 } else { column.1 }) }</span> }
+            })
+            .collect::<Html>()
+    };
+
+    html! {
+        <div class="dp-table-view">
+            <table class="dp-table">
+                <tr>
+                    <th />
+                    <th class={classes!("dp-header", (current_column == Some(0)).then_some("current"))}>{ "ε" }</th>
+                    { header }
+                </tr>
+                { rows }
+            </table>
+            {
+                if columns.is_empty() {
+                    html! {}
+                } else {
+                    html! {
+                        <div class="dp-alignment">
+                            <div class="dp-alignment-row">{ aligned(true) }</div>
+                            <div class="dp-alignment-row">{ aligned(false) }</div>
+                        </div>
+                    }
+                }
+            }
+        </div>
+    }
+}
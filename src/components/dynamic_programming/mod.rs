@@ -0,0 +1 @@
+pub mod dp_table_view;
@@ -0,0 +1,159 @@
+use geometry::{HullState, Point};
+use web_sys::Element;
+use yew::prelude::*;
+
+/// The size of the unit square that the points are in, in SVG units.
+const VIEW_SIZE: f64 = 100.0;
+/// The margin around the unit square so that the points on its edges are drawn whole.
+const VIEW_PADDING: f64 = 3.0;
+const POINT_RADIUS: f64 = 1.2;
+
+#[derive(Properties, PartialEq)]
+pub struct PointCanvasProps {
+    /// The points in the unit square, where `y` grows upwards.
+    pub points: Vec<Point>,
+    /// What the hull algorithm has built so far.
+    pub state: HullState,
+    /// Whether the hull is complete, which closes its last edge.
+    #[prop_or_default]
+    pub finished: bool,
+    /// Called with the clicked point of the unit square.
+    pub on_add: Callback<Point>,
+}
+
+/// Draws points and the convex hull being built around them. The candidate edge is dashed, and the points that were discarded
+/// from the hull or rejected as the end of the candidate edge are drawn in red. Clicking the canvas adds a point.
+#[function_component(PointCanvas)]
+pub fn point_canvas(props: &PointCanvasProps) -> Html {
+    let svg_ref = use_node_ref();
+    let PointCanvasProps {
+        points,
+        state,
+        finished,
+        on_add,
+    } = props;
+
+    // The SVG is drawn with `y` growing downwards
+    let to_view = |point: Point| (point.x * VIEW_SIZE, (1.0 - point.y) * VIEW_SIZE);
+
+    let onclick = {
+        let svg_ref = svg_ref.clone();
+        let on_add = on_add.clone();
+
+        Callback::from(move |e: MouseEvent| {
+            let Some(svg) = svg_ref.cast::<Element>() else {
+                return;
+            };
+            let (width, height) = (svg.client_width() as f64, svg.client_height() as f64);
+            if width <= 0.0 || height <= 0.0 {
+                return;
+            }
+            let view_size = VIEW_SIZE + 2.0 * VIEW_PADDING;
+            let x = (e.offset_x() as f64 / width * view_size - VIEW_PADDING) / VIEW_SIZE;
+            let y = (e.offset_y() as f64 / height * view_size - VIEW_PADDING) / VIEW_SIZE;
+            on_add.emit(Point::new(x.clamp(0.0, 1.0), 1.0 - y.clamp(0.0, 1.0)));
+        })
+    };
+
+    let hull_points = state
+        .hull
+        .iter()
+        .map(|i| {
+            let (x, y) = to_view(points[*i]);
+            format!("{},{}", x, y)
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+    let hull = if *finished {
+        html! { <polygon class="hull" points={hull_points} /> }
+    } else {
+        html! { <polyline class="hull" points={hull_points} /> }
+    };
+
+    let candidate = match state.candidate {
+        Some((from, to)) => {
+            let ((x1, y1), (x2, y2)) = (to_view(points[from]), to_view(points[to]));
+            html! {
+                <line
+                    class="candidate"
+                    x1={x1.to_string()}
+                    y1={y1.to_string()}
+                    x2={x2.to_string()}
+                    y2={y2.to_string()}
+                />
+            }
+        }
+        None => html! {},
+    };
+
+    let circles = points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let (x, y) = to_view(*point);
+            let class = classes!(
+                "point",
+                state.hull.contains(&i).then_some("on-hull"),
+                (state.hull.first() == Some(&i)).then_some("start"),
+                state.discarded.contains(&i).then_some("discarded"),
+                (state.rejected == Some(i)).then_some("rejected"),
+                (state.candidate.map(|(_, to)| to) == Some(i)).then_some("candidate-end"),
+            );
+            html! {
+                <circle {class} cx={x.to_string()} cy={y.to_string()} r={POINT_RADIUS.to_string()} />
+            }
+        })
+        .collect::<Html>();
+
+    // Only the points that the narration is about are numbered, so that the labels don't cover the other points
+    let mut labeled = state
+        .hull
+        .first()
+        .copied()
+        .into_iter()
+        .chain(
+            state
+                .candidate
+                .map(|(from, to)| [from, to])
+                .into_iter()
+                .flatten(),
+        )
+        .chain(state.rejected)
+        .collect::<Vec<usize>>();
+    labeled.sort_unstable();
+    labeled.dedup();
+    let labels = labeled
+        .iter()
+        .map(|i| {
+            let (x, y) = to_view(points[*i]);
+            html! {
+                <text class="point-label" x={(x + POINT_RADIUS * 1.5).to_string()} y={(y - POINT_RADIUS * 1.5).to_string()}>
+                    { i }
+                </text>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="point-canvas">
+            <svg
+                ref={svg_ref}
+                xmlns="http://www.w3.org/2000/svg"
+                viewBox={format!(
+                    "{} {} {} {}",
+                    -VIEW_PADDING,
+                    -VIEW_PADDING,
+                    VIEW_SIZE + 2.0 * VIEW_PADDING,
+                    VIEW_SIZE + 2.0 * VIEW_PADDING
+                )}
+                {onclick}
+            >
+                <rect class="point-canvas-area" width={VIEW_SIZE.to_string()} height={VIEW_SIZE.to_string()} />
+                { hull }
+                { candidate }
+                { circles }
+                { labels }
+            </svg>
+        </div>
+    }
+}
@@ -4,15 +4,21 @@ use yew::prelude::*;
 pub struct ButtonProps {
     pub title: String,
     pub onclick: Callback<MouseEvent>,
+    #[prop_or_default]
+    pub disabled: bool,
 }
 
 #[function_component(Button)]
 pub fn button(props: &ButtonProps) -> Html {
-    let ButtonProps { title, onclick } = props.clone();
+    let ButtonProps {
+        title,
+        onclick,
+        disabled,
+    } = props.clone();
 
     html! {
         <div class="input button">
-            <button {onclick}>{title}</button>
+            <button {onclick} {disabled}>{title}</button>
         </div>
     }
 }
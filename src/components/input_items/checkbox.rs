@@ -7,6 +7,8 @@ pub struct CheckboxProps {
     pub title: String,
     pub value: bool,
     pub oninput: Callback<InputEvent>,
+    #[prop_or_default]
+    pub disabled: bool,
 }
 
 #[function_component(Checkbox)]
@@ -15,12 +17,13 @@ pub fn checkbox(props: &CheckboxProps) -> Html {
         title,
         value,
         oninput,
+        disabled,
     } = props.clone();
     let id = input_title_to_id(&title);
 
     html! {
         <div class="input checkbox">
-            <input id={id.clone()} type="checkbox" checked={value} {oninput} />
+            <input id={id.clone()} type="checkbox" checked={value} {oninput} {disabled} />
             <label for={id}>{title}</label>
         </div>
     }
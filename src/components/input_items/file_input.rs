@@ -0,0 +1,59 @@
+use crate::components::input_items::input_title_to_id;
+
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct FileInputProps {
+    pub title: String,
+    /// The file types that can be chosen, e.g. `".json"`.
+    pub accept: String,
+    /// An error message shown below the input, if any.
+    #[prop_or_default]
+    pub error: Option<String>,
+    /// Called with the contents of the chosen file.
+    pub onload: Callback<String>,
+}
+
+#[function_component(FileInput)]
+pub fn file_input(props: &FileInputProps) -> Html {
+    let FileInputProps {
+        title,
+        accept,
+        error,
+        onload,
+    } = props.clone();
+    let id = input_title_to_id(&title);
+    let onchange = move |event: Event| {
+        let el: HtmlInputElement = event.target_unchecked_into();
+        if let Some(file) = el.files().and_then(|files| files.get(0)) {
+            let onload = onload.clone();
+            spawn_local(async move {
+                if let Some(text) = JsFuture::from(file.text())
+                    .await
+                    .ok()
+                    .and_then(|text| text.as_string())
+                {
+                    onload.emit(text);
+                }
+            });
+        }
+        // Otherwise choosing the same file again wouldn't load it again
+        el.set_value("");
+    };
+
+    html! {
+        <div class="input file-input">
+            <label for={id.clone()}>{title.to_string()}</label>
+            <input {id} type="file" {accept} {onchange} />
+            {
+                if let Some(error) = error {
+                    html! { <span class="input-error">{error}</span> }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
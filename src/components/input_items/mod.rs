@@ -1,12 +1,18 @@
 mod button;
 mod checkbox;
+mod file_input;
 mod number_input;
+mod playback_time_input;
 mod select_input;
+mod text_input;
 
 pub use button::Button;
 pub use checkbox::Checkbox;
+pub use file_input::FileInput;
 pub use number_input::{FloatInput, IntInput};
+pub use playback_time_input::PlaybackTimeInput;
 pub use select_input::SelectInput;
+pub use text_input::TextInput;
 
 use regex::Regex;
 
@@ -0,0 +1,31 @@
+use crate::{components::input_items::FloatInput, hooks::use_settings::use_settings};
+
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct PlaybackTimeInputProps {
+    #[prop_or_else(|| "Playback time (seconds)".to_string())]
+    pub title: String,
+}
+
+/// An input for the playback time in the settings, which is shared by the pages that don't have their own playback time.
+#[function_component(PlaybackTimeInput)]
+pub fn playback_time_input(props: &PlaybackTimeInputProps) -> Html {
+    let settings = use_settings();
+
+    let oninput = {
+        let settings = settings.clone();
+        Callback::from(move |playback_time| {
+            settings.update(|settings| settings.playback_time = playback_time)
+        })
+    };
+
+    html! {
+        <FloatInput<f32>
+            title={props.title.clone()}
+            value={settings.playback_time}
+            {oninput}
+            min={0.0}
+        />
+    }
+}
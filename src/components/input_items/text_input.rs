@@ -0,0 +1,51 @@
+use crate::components::input_items::input_title_to_id;
+
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct TextInputProps {
+    pub title: String,
+    pub value: String,
+    #[prop_or_default]
+    pub placeholder: Option<String>,
+    /// An error message shown below the input, if any.
+    #[prop_or_default]
+    pub error: Option<String>,
+    pub oninput: Callback<String>,
+}
+
+#[function_component(TextInput)]
+pub fn text_input(props: &TextInputProps) -> Html {
+    let TextInputProps {
+        title,
+        value,
+        placeholder,
+        error,
+        oninput,
+    } = props.clone();
+    let id = input_title_to_id(&title);
+    let oninput = move |event: InputEvent| {
+        let el: HtmlInputElement = event.target_unchecked_into();
+        oninput.emit(el.value());
+    };
+
+    html! {
+        <div class="input text-input">
+            <label for={id.clone()}>{title.to_string()}</label>
+            <input {id}
+                type="text"
+                placeholder={placeholder.unwrap_or_else(|| title.to_string())}
+                {value}
+                {oninput}
+            />
+            {
+                if let Some(error) = error {
+                    html! { <span class="input-error">{error}</span> }
+                } else {
+                    html! {}
+                }
+            }
+        </div>
+    }
+}
@@ -5,5 +5,10 @@ pub mod raw_html;
 pub mod sidebar;
 pub mod step_slider;
 
+pub mod backtracking;
+pub mod data_structures;
+pub mod dynamic_programming;
+pub mod geometry;
 pub mod pathfinding;
 pub mod sorting;
+pub mod strings;
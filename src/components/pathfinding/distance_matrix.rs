@@ -0,0 +1,249 @@
+use pathfinding::{
+    graph::{AdjacencyList, AllPairsShortestPaths, NegativeCycle},
+    Coord, Edge,
+};
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use yew::prelude::*;
+use yew_hooks::use_size;
+
+use crate::{components::step_slider::StepSlider, hooks::use_color_scheme::ColorScheme};
+
+/// The most open cells that the matrix is computed for, as Floyd-Warshall takes cubic time and the matrix quadratic space.
+pub const DISTANCE_MATRIX_VERTEX_LIMIT: usize = 150;
+
+/// The paths between every pair of cells, or the negative cycle that makes them undefined.
+type FloydWarshallResult<E> = Result<Rc<AllPairsShortestPaths<Coord, E>>, NegativeCycle<Coord>>;
+
+#[derive(Properties, PartialEq)]
+pub struct DistanceMatrixProps<E: 'static + Edge> {
+    pub graph: AdjacencyList<Coord, E>,
+    /// How long it should take to play all passes in seconds.
+    #[prop_or(10.0)]
+    pub playback_time: f32,
+}
+
+/// The all-pairs distances of Floyd-Warshall as a heatmap, from cheap paths in blue to expensive paths in red.\
+/// The slider steps through the passes, highlighting the row and column of the vertex that the paths may go through in the pass
+/// and outlining the cells that got cheaper.
+#[function_component(DistanceMatrix)]
+pub fn distance_matrix<E: 'static + Edge>(props: &DistanceMatrixProps<E>) -> Html {
+    let color_scheme = use_context::<ColorScheme>().expect("no color scheme context found");
+    let paths: UseStateHandle<Option<FloydWarshallResult<E>>> = use_state(|| None);
+    let pass_count = use_state(|| 0);
+    let hovered_cell: UseStateHandle<Option<(usize, usize)>> = use_state(|| None);
+    let canvas_ref = use_node_ref();
+    let canvas_container_ref = use_node_ref();
+    let canvas_container_size = use_size(canvas_container_ref.clone());
+    let canvas_size = canvas_container_size.0.min(canvas_container_size.1);
+
+    {
+        let paths = paths.clone();
+        let pass_count = pass_count.clone();
+
+        use_effect_with_deps(
+            move |graph| {
+                if graph.vertex_count() <= DISTANCE_MATRIX_VERTEX_LIMIT {
+                    let result = graph.floyd_warshall().map(Rc::new);
+                    pass_count.set(result.as_ref().map_or(0, |paths| paths.passes.len()));
+                    paths.set(Some(result));
+                } else {
+                    paths.set(None);
+                }
+                || ()
+            },
+            props.graph.clone(),
+        );
+    }
+
+    {
+        let canvas_ref = canvas_ref.clone();
+
+        use_effect_with_deps(
+            move |(paths, pass_count, _, color_scheme)| {
+                if let (Some(Ok(paths)), Some(canvas)) =
+                    (paths, canvas_ref.cast::<HtmlCanvasElement>())
+                {
+                    let ctx: CanvasRenderingContext2d = canvas
+                        .get_context("2d")
+                        .unwrap()
+                        .unwrap()
+                        .dyn_into()
+                        .unwrap();
+                    draw(
+                        &ctx,
+                        canvas.width() as f64,
+                        paths,
+                        *pass_count,
+                        *color_scheme,
+                    );
+                }
+                || ()
+            },
+            ((*paths).clone(), *pass_count, canvas_size, color_scheme),
+        );
+    }
+
+    let paths = match &*paths {
+        Some(Ok(paths)) => Rc::clone(paths),
+        Some(Err(negative_cycle)) => {
+            return html! {
+                <p class="distance-matrix-note">{ format!("The distances aren't defined, as the {}", negative_cycle) }</p>
+            }
+        }
+        None => {
+            return html! {
+                <p class="distance-matrix-note">
+                    { format!("The distance matrix is only shown for grids with at most {} open cells.", DISTANCE_MATRIX_VERTEX_LIMIT) }
+                </p>
+            }
+        }
+    };
+
+    let on_change_pass = {
+        let pass_count = pass_count.clone();
+        Callback::from(move |index| pass_count.set(index))
+    };
+
+    let onmousemove = {
+        let hovered_cell = hovered_cell.clone();
+        let vertex_count = paths.vertices.len();
+
+        Callback::from(move |e: MouseEvent| {
+            let cell_size = canvas_size as f64 / vertex_count.max(1) as f64;
+            let (column, row) = (
+                (e.offset_x() as f64 / cell_size) as usize,
+                (e.offset_y() as f64 / cell_size) as usize,
+            );
+            if row < vertex_count && column < vertex_count {
+                hovered_cell.set(Some((row, column)));
+            } else {
+                hovered_cell.set(None);
+            }
+        })
+    };
+
+    let onmouseleave = {
+        let hovered_cell = hovered_cell.clone();
+        Callback::from(move |_| hovered_cell.set(None))
+    };
+
+    let hovered_distance = (*hovered_cell).map(|(from, to)| {
+        let distance = paths.distances_after(*pass_count)[from][to];
+        format!(
+            "{} to {}: {}",
+            paths.vertices[from],
+            paths.vertices[to],
+            distance.and_then(|distance| distance.to_f64()).map_or(
+                "not reachable".to_string(),
+                |distance| format!("{:.1}", distance)
+            )
+        )
+    });
+
+    html! {
+        <div class="distance-matrix">
+            <div ref={canvas_container_ref} class="distance-matrix-container">
+                <canvas
+                    ref={canvas_ref}
+                    width={canvas_size.to_string()}
+                    height={canvas_size.to_string()}
+                    {onmousemove}
+                    {onmouseleave}
+                ></canvas>
+            </div>
+            <p class="distance-matrix-note">
+                { hovered_distance.unwrap_or_else(|| "Hover over a cell to see the distance between its row and column.".to_string()) }
+            </p>
+            <StepSlider
+                label={
+                    if *pass_count == 0 {
+                        format!("Edges only (0/{})", paths.passes.len())
+                    } else {
+                        format!("Paths through {} ({}/{})", paths.vertices[*pass_count - 1], *pass_count, paths.passes.len())
+                    }
+                }
+                active_step_index={*pass_count}
+                max={paths.passes.len()}
+                on_change={on_change_pass}
+                playback_time={props.playback_time}
+            />
+        </div>
+    }
+}
+
+/// Gets the color of a distance, where `fraction` is 0 for the cheapest distance and 1 for the most expensive one.
+fn heat_color(fraction: f64) -> String {
+    format!("hsl({}, 70%, 50%)", 240.0 - 240.0 * fraction)
+}
+
+fn draw<E: Edge>(
+    ctx: &CanvasRenderingContext2d,
+    size: f64,
+    paths: &AllPairsShortestPaths<Coord, E>,
+    pass_count: usize,
+    color_scheme: ColorScheme,
+) {
+    ctx.clear_rect(0.0, 0.0, size, size);
+
+    let vertex_count = paths.vertices.len();
+    let cell_size = size / vertex_count.max(1) as f64;
+    let distances = paths.distances_after(pass_count);
+
+    // The colors are scaled by the final distances so that they don't shift while the passes play
+    let (min, max) = paths
+        .distances
+        .iter()
+        .flatten()
+        .flatten()
+        .filter_map(|distance| distance.to_f64())
+        .fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min, max), distance| (min.min(distance), max.max(distance)),
+        );
+    let range = (max - min).max(f64::EPSILON);
+
+    for (from, row) in distances.iter().enumerate() {
+        for (to, distance) in row.iter().enumerate() {
+            if let Some(distance) = distance.and_then(|distance| distance.to_f64()) {
+                ctx.set_fill_style(&JsValue::from_str(&heat_color(
+                    ((distance - min) / range).clamp(0.0, 1.0),
+                )));
+                ctx.fill_rect(
+                    to as f64 * cell_size,
+                    from as f64 * cell_size,
+                    cell_size,
+                    cell_size,
+                );
+            }
+        }
+    }
+
+    if pass_count == 0 {
+        return;
+    }
+    let via = pass_count - 1;
+    let highlight_color = match color_scheme {
+        ColorScheme::Light => "rgba(0, 0, 0, 0.2)",
+        ColorScheme::Dark => "rgba(255, 255, 255, 0.2)",
+    };
+    ctx.set_fill_style(&JsValue::from_str(highlight_color));
+    ctx.fill_rect(0.0, via as f64 * cell_size, size, cell_size);
+    ctx.fill_rect(via as f64 * cell_size, 0.0, cell_size, size);
+
+    let outline_color = match color_scheme {
+        ColorScheme::Light => "#333333",
+        ColorScheme::Dark => "#dddddd",
+    };
+    ctx.set_stroke_style(&JsValue::from_str(outline_color));
+    ctx.set_line_width((cell_size / 6.0).clamp(0.5, 2.0));
+    for update in &paths.passes[via] {
+        ctx.stroke_rect(
+            update.to as f64 * cell_size,
+            update.from as f64 * cell_size,
+            cell_size,
+            cell_size,
+        );
+    }
+}
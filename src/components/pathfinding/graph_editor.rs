@@ -0,0 +1,444 @@
+use pathfinding::{
+    graph::{AdjacencyList, ForceLayout, GraphEdit},
+    Coord, Edge, VertexState,
+};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+use web_sys::Element;
+use yew::prelude::*;
+use yew_hooks::{use_interval, use_update};
+
+use crate::components::input_items::*;
+
+/// The most vertices that the editor lays out, as every pair of vertices pushes each other on every step of the layout.
+pub const GRAPH_EDITOR_VERTEX_LIMIT: usize = 1000;
+
+/// The size of the unit square of the layout in SVG units.
+const VIEW_SIZE: f64 = 100.0;
+/// The margin around the unit square so that the vertices on its edges are drawn whole.
+const VIEW_PADDING: f64 = 3.0;
+const VERTEX_RADIUS: f64 = 1.2;
+/// How far from a vertex a click still hits it, in the units of the layout.
+const HIT_RADIUS: f64 = 0.025;
+
+const SIMULATION_INTERVAL_MS: u32 = 30;
+/// The furthest that a vertex can move on a step of the layout right after the graph changed.
+const START_TEMPERATURE: f64 = 0.05;
+/// The layout is stopped once it has cooled below this.
+const MIN_TEMPERATURE: f64 = 0.0005;
+/// The layout is also stopped once no vertex moves further than this on a step, as it has settled.
+const SETTLED_DISTANCE: f64 = 0.0001;
+const COOLING: f64 = 0.95;
+
+/// The graph of the page along with a version that the page increments whenever it changes the graph,
+/// so that the editor can tell when the graph changed without cloning or comparing it.
+#[derive(Clone)]
+pub struct VersionedGraph<E: Clone> {
+    pub graph: Rc<RefCell<AdjacencyList<Coord, E>>>,
+    pub version: u32,
+}
+
+impl<E: Clone> PartialEq for VersionedGraph<E> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.graph, &other.graph) && self.version == other.version
+    }
+}
+
+/// What clicking a vertex in the editor does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum EditorTool {
+    /// Clicking two vertices adds an edge between them, or removes it if there is one.
+    #[default]
+    Connect,
+    Start,
+    End,
+}
+
+impl EditorTool {
+    const ALL: [EditorTool; 3] = [EditorTool::Connect, EditorTool::Start, EditorTool::End];
+
+    fn name(&self) -> &'static str {
+        match self {
+            EditorTool::Connect => "Add or remove edges",
+            EditorTool::Start => "Select start",
+            EditorTool::End => "Select end",
+        }
+    }
+}
+
+/// The vertex that is being dragged, and whether it has moved, since a press and release without moving is a click.
+struct EditorDrag {
+    vertex: Coord,
+    moved: bool,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct GraphEditorProps<E: 'static + Edge> {
+    pub graph: VersionedGraph<E>,
+    /// The state of every vertex at the shown step of the search.
+    pub vertex_states: BTreeMap<Coord, VertexState>,
+    pub path: Vec<Coord>,
+    pub start: Coord,
+    pub end: Coord,
+    /// Called with the edit that adds or removes an edge, which is applied by the parent so that it can be undone.
+    pub on_edit: Callback<GraphEdit<Coord, E>>,
+    pub on_select_start: Callback<Coord>,
+    pub on_select_end: Callback<Coord>,
+    #[prop_or_default]
+    pub can_undo: bool,
+    #[prop_or_default]
+    pub can_redo: bool,
+    pub on_undo: Callback<()>,
+    pub on_redo: Callback<()>,
+}
+
+/// The graph drawn as vertices and edges with a force-directed layout instead of as a grid.\
+/// The vertices can be dragged around while the layout keeps running, and clicking two vertices adds or removes the edge
+/// between them. Clicking a vertex can also make it the start or the end of the search.
+#[function_component(GraphEditor)]
+pub fn graph_editor<E: 'static + Edge>(props: &GraphEditorProps<E>) -> Html {
+    let layout = use_mut_ref(|| ForceLayout::new(&props.graph.graph.borrow(), 0));
+    let temperature = use_mut_ref(|| START_TEMPERATURE);
+    let running = use_state_eq(|| true);
+    let update = use_update();
+    let drag = use_mut_ref(|| None::<EditorDrag>);
+    // The first vertex of the edge that is being added or removed
+    let selected: UseStateHandle<Option<Coord>> = use_state_eq(|| None);
+    let tool = use_state_eq(EditorTool::default);
+    let new_edge_weight = use_state_eq(|| 1.0);
+    let svg_ref = use_node_ref();
+
+    {
+        let layout = layout.clone();
+        let temperature = temperature.clone();
+        let running = running.clone();
+
+        use_effect_with_deps(
+            move |graph: &VersionedGraph<E>| {
+                let graph = graph.graph.borrow();
+                layout.borrow_mut().set_graph(&graph);
+                *temperature.borrow_mut() = START_TEMPERATURE;
+                running.set(graph.vertex_count() <= GRAPH_EDITOR_VERTEX_LIMIT);
+                || ()
+            },
+            props.graph.clone(),
+        );
+    }
+
+    {
+        let layout = layout.clone();
+        let temperature = temperature.clone();
+        let interval_ms = if *running { SIMULATION_INTERVAL_MS } else { 0 };
+        let running = running.clone();
+
+        use_interval(
+            move || {
+                let mut temperature = temperature.borrow_mut();
+                let furthest = layout.borrow_mut().step(*temperature);
+                *temperature *= COOLING;
+                if *temperature < MIN_TEMPERATURE || furthest < SETTLED_DISTANCE {
+                    running.set(false);
+                }
+                update();
+            },
+            interval_ms,
+        );
+    }
+
+    let graph = props.graph.graph.borrow();
+    if graph.vertex_count() > GRAPH_EDITOR_VERTEX_LIMIT {
+        return html! {
+            <p class="graph-editor-note">
+                { format!("The graph editor is only shown for graphs with at most {} vertices.", GRAPH_EDITOR_VERTEX_LIMIT) }
+            </p>
+        };
+    }
+
+    // Converts the position of the mouse into the units of the layout
+    let to_layout = {
+        let svg_ref = svg_ref.clone();
+
+        move |e: &MouseEvent| {
+            let svg = svg_ref.cast::<Element>()?;
+            let (width, height) = (svg.client_width() as f64, svg.client_height() as f64);
+            if width <= 0.0 || height <= 0.0 {
+                return None;
+            }
+            let view_size = VIEW_SIZE + 2.0 * VIEW_PADDING;
+            Some((
+                (e.offset_x() as f64 / width * view_size - VIEW_PADDING) / VIEW_SIZE,
+                (e.offset_y() as f64 / height * view_size - VIEW_PADDING) / VIEW_SIZE,
+            ))
+        }
+    };
+
+    // Reheats the layout so that the rest of the graph makes room for a dragged vertex
+    let reheat = {
+        let temperature = temperature.clone();
+        let running = running.clone();
+
+        move || {
+            let mut temperature = temperature.borrow_mut();
+            *temperature = temperature.max(START_TEMPERATURE / 2.0);
+            running.set(true);
+        }
+    };
+
+    let onmousedown = {
+        let layout = layout.clone();
+        let drag = drag.clone();
+        let to_layout = to_layout.clone();
+
+        Callback::from(move |e: MouseEvent| {
+            if let Some(vertex) =
+                to_layout(&e).and_then(|point| layout.borrow().vertex_at(point, HIT_RADIUS))
+            {
+                *drag.borrow_mut() = Some(EditorDrag {
+                    vertex,
+                    moved: false,
+                });
+            }
+        })
+    };
+
+    let onmousemove = {
+        let layout = layout.clone();
+        let drag = drag.clone();
+        let reheat = reheat.clone();
+
+        Callback::from(move |e: MouseEvent| {
+            if let (Some(drag), Some(point)) = (drag.borrow_mut().as_mut(), to_layout(&e)) {
+                layout.borrow_mut().pin(&drag.vertex, point);
+                drag.moved = true;
+                reheat();
+            }
+        })
+    };
+
+    let onmouseup = {
+        let graph = Rc::clone(&props.graph.graph);
+        let (start, end) = (props.start, props.end);
+        let on_edit = props.on_edit.clone();
+        let on_select_start = props.on_select_start.clone();
+        let on_select_end = props.on_select_end.clone();
+        let layout = layout.clone();
+        let drag = drag.clone();
+        let selected = selected.clone();
+        let tool = *tool;
+        let new_edge_weight = *new_edge_weight;
+        let reheat = reheat.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            let vertex = match drag.borrow_mut().take() {
+                Some(EditorDrag {
+                    vertex,
+                    moved: true,
+                }) => {
+                    layout.borrow_mut().unpin(&vertex);
+                    reheat();
+                    return;
+                }
+                Some(EditorDrag { vertex, .. }) => vertex,
+                None => {
+                    // Clicking the background cancels the edge that was started
+                    selected.set(None);
+                    return;
+                }
+            };
+
+            match tool {
+                EditorTool::Connect => match *selected {
+                    Some(first) if first != vertex => {
+                        let connected = {
+                            let graph = graph.borrow();
+                            graph.is_neighbor(&first, &vertex) || graph.is_neighbor(&vertex, &first)
+                        };
+                        if connected {
+                            on_edit.emit(GraphEdit::RemoveEdgeUndirected(first, vertex));
+                        } else {
+                            on_edit.emit(GraphEdit::AddEdgeUndirected(
+                                first,
+                                vertex,
+                                E::from(new_edge_weight).unwrap(),
+                            ));
+                        }
+                        selected.set(None);
+                    }
+                    Some(_) => selected.set(None),
+                    None => selected.set(Some(vertex)),
+                },
+                EditorTool::Start if vertex != end => on_select_start.emit(vertex),
+                EditorTool::End if vertex != start => on_select_end.emit(vertex),
+                _ => {}
+            }
+        })
+    };
+
+    // The mouse button may be released outside of the editor, which ends the drag
+    let onmouseleave = {
+        let layout = layout.clone();
+        let drag = drag.clone();
+
+        Callback::from(move |_: MouseEvent| {
+            if let Some(EditorDrag { vertex, .. }) = drag.borrow_mut().take() {
+                layout.borrow_mut().unpin(&vertex);
+                reheat();
+            }
+        })
+    };
+
+    let change_tool = {
+        let tool = tool.clone();
+        let selected = selected.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(new_tool) = EditorTool::ALL
+                .iter()
+                .copied()
+                .find(|tool| tool.name() == name)
+            {
+                tool.set(new_tool);
+                selected.set(None);
+            }
+        })
+    };
+
+    let change_new_edge_weight = {
+        let new_edge_weight = new_edge_weight.clone();
+        Callback::from(move |weight| new_edge_weight.set(weight))
+    };
+
+    let positions = layout.borrow().positions();
+    let position = |vertex: &Coord| {
+        let (x, y) = positions.get(vertex).copied().unwrap_or_default();
+        (x * VIEW_SIZE, y * VIEW_SIZE)
+    };
+    let path_edges: BTreeSet<(Coord, Coord)> = props
+        .path
+        .windows(2)
+        .flat_map(|pair| [(pair[0], pair[1]), (pair[1], pair[0])])
+        .collect();
+
+    let edges = graph
+        .edges_iter()
+        .filter(|(from, to, _)| from != to)
+        .filter_map(|(from, to, _)| {
+            let undirected = graph.is_neighbor(to, from);
+            if undirected && to < from {
+                return None;
+            }
+            let ((x1, y1), (x2, y2)) = (position(from), position(to));
+            let class = classes!(
+                "editor-edge",
+                (!undirected).then_some("directed"),
+                path_edges.contains(&(*from, *to)).then_some("path"),
+            );
+            Some(html! {
+                <line
+                    {class}
+                    x1={x1.to_string()}
+                    y1={y1.to_string()}
+                    x2={x2.to_string()}
+                    y2={y2.to_string()}
+                    marker-end={(!undirected).then_some("url(#editorArrow)")}
+                />
+            })
+        })
+        .collect::<Html>();
+
+    let path_vertices: BTreeSet<&Coord> = props.path.iter().collect();
+    let vertices = graph
+        .ordered_vertices()
+        .map(|vertex| {
+            let (x, y) = position(vertex);
+            let state = if *vertex == props.start {
+                "start"
+            } else if *vertex == props.end {
+                "end"
+            } else if path_vertices.contains(vertex) {
+                "path"
+            } else {
+                match props.vertex_states.get(vertex) {
+                    Some(VertexState::Frontier) => "frontier",
+                    Some(VertexState::NewVisited) => "new-visited",
+                    Some(VertexState::Visited) => "visited",
+                    _ => "",
+                }
+            };
+            let class = classes!(
+                "editor-vertex",
+                state,
+                (*selected == Some(*vertex)).then_some("selected"),
+            );
+            html! {
+                <circle {class} cx={x.to_string()} cy={y.to_string()} r={VERTEX_RADIUS.to_string()}>
+                    <title>{ vertex.to_string() }</title>
+                </circle>
+            }
+        })
+        .collect::<Html>();
+
+    let hint = match (*tool, *selected) {
+        (EditorTool::Connect, Some(first)) => format!(
+            "Click another vertex to add or remove its edge to {}, or the background to cancel.",
+            first
+        ),
+        (EditorTool::Connect, None) => {
+            "Drag the vertices to move them, or click two vertices to add or remove the edge between them.".to_string()
+        }
+        (EditorTool::Start, _) => "Click a vertex to start the search from it.".to_string(),
+        (EditorTool::End, _) => "Click a vertex to search for a path to it.".to_string(),
+    };
+
+    let view_box = format!(
+        "{} {} {} {}",
+        -VIEW_PADDING,
+        -VIEW_PADDING,
+        VIEW_SIZE + 2.0 * VIEW_PADDING,
+        VIEW_SIZE + 2.0 * VIEW_PADDING
+    );
+
+    html! {
+        <div class="graph-editor">
+            <div class="graph-editor-controls">
+                <SelectInput
+                    title="Click action"
+                    options={EditorTool::ALL.iter().map(|tool| tool.name().to_string()).collect::<Vec<String>>()}
+                    selected_value={tool.name().to_string()}
+                    onchange={change_tool}
+                />
+                <FloatInput<f64>
+                    title="New edge weight"
+                    value={*new_edge_weight}
+                    oninput={change_new_edge_weight}
+                    min={0.0}
+                />
+                <Button title="Undo" onclick={props.on_undo.reform(|_| ())} disabled={!props.can_undo} />
+                <Button title="Redo" onclick={props.on_redo.reform(|_| ())} disabled={!props.can_redo} />
+            </div>
+            <div class="graph-editor-container">
+                <svg
+                    ref={svg_ref}
+                    xmlns="http://www.w3.org/2000/svg"
+                    viewBox={view_box}
+                    {onmousedown}
+                    {onmousemove}
+                    {onmouseup}
+                    {onmouseleave}
+                >
+                    <defs>
+                        <marker id="editorArrow" viewBox="0 0 10 10" refX="16" refY="5" markerWidth="4" markerHeight="4" orient="auto-start-reverse">
+                            <path d="M 0 0 L 10 5 L 0 10 z" />
+                        </marker>
+                    </defs>
+                    { edges }
+                    { vertices }
+                </svg>
+            </div>
+            <p class="graph-editor-note">{ hint }</p>
+        </div>
+    }
+}
@@ -1,9 +1,15 @@
+mod distance_matrix;
+mod graph_editor;
 mod path_grid;
 mod pathfinding_controls;
+mod random_graph_controls;
 mod wall_controls;
 
 pub mod toolbar;
 
+pub use distance_matrix::{DistanceMatrix, DISTANCE_MATRIX_VERTEX_LIMIT};
+pub use graph_editor::{GraphEditor, VersionedGraph, GRAPH_EDITOR_VERTEX_LIMIT};
 pub use path_grid::PathGrid;
 pub use pathfinding_controls::PathfindingControls;
+pub use random_graph_controls::RandomGraphControls;
 pub use wall_controls::WallControls;
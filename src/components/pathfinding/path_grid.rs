@@ -24,6 +24,8 @@ pub struct PathGridConfig {
     color_start: String,
     color_end: String,
     color_wall: String,
+    color_mud: String,
+    color_frontier: String,
     color_new_visited: String,
     color_not_visited: String,
     color_visited: String,
@@ -36,6 +38,8 @@ pub struct PathGridProps {
     pub height: usize,
     pub graph: Rc<RefCell<BTreeMap<Coord, VertexState>>>,
     pub walls: Rc<RefCell<BTreeSet<Coord>>>,
+    /// The cells that cost more to cross.
+    pub mud: Rc<RefCell<BTreeSet<Coord>>>,
     pub path: Option<Rc<RefCell<Vec<Coord>>>>,
     pub start: Coord,
     pub end: Coord,
@@ -50,6 +54,7 @@ pub fn path_grid(props: &PathGridProps) -> Html {
         height,
         graph,
         walls,
+        mud,
         path,
         on_click_cell,
         on_draw_end,
@@ -59,6 +64,8 @@ pub fn path_grid(props: &PathGridProps) -> Html {
         color_start: "#00ff66".to_string(),
         color_end: "#ff4500".to_string(),
         color_wall: "#cccccc".to_string(),
+        color_mud: "#8b5a2b".to_string(),
+        color_frontier: "#99e6cc".to_string(),
         color_new_visited: "#00bbff".to_string(),
         color_not_visited: "".to_string(),
         color_visited: "#0066ff".to_string(),
@@ -104,37 +111,27 @@ pub fn path_grid(props: &PathGridProps) -> Html {
 
                     ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
 
-                    ctx.begin_path();
-                    ctx.set_fill_style(&config.color_visited.as_str().into());
-                    for (vertex, _) in graph
-                        .borrow()
-                        .iter()
-                        .filter(|(_, state)| **state == VertexState::Visited)
-                    {
-                        ctx.rect(
-                            vertex.x as f64 * cell_width,
-                            vertex.y as f64 * cell_height,
-                            cell_width,
-                            cell_height,
-                        );
-                    }
-                    ctx.fill();
-
-                    ctx.begin_path();
-                    ctx.set_fill_style(&config.color_new_visited.as_str().into());
-                    for (vertex, _) in graph
-                        .borrow()
-                        .iter()
-                        .filter(|(_, state)| **state == VertexState::NewVisited)
-                    {
-                        ctx.rect(
-                            vertex.x as f64 * cell_width,
-                            vertex.y as f64 * cell_height,
-                            cell_width,
-                            cell_height,
-                        );
+                    for (state, color) in [
+                        (VertexState::Frontier, &config.color_frontier),
+                        (VertexState::Visited, &config.color_visited),
+                        (VertexState::NewVisited, &config.color_new_visited),
+                    ] {
+                        ctx.begin_path();
+                        ctx.set_fill_style(&color.as_str().into());
+                        for (vertex, _) in graph
+                            .borrow()
+                            .iter()
+                            .filter(|(_, vertex_state)| **vertex_state == state)
+                        {
+                            ctx.rect(
+                                vertex.x as f64 * cell_width,
+                                vertex.y as f64 * cell_height,
+                                cell_width,
+                                cell_height,
+                            );
+                        }
+                        ctx.fill();
                     }
-                    ctx.fill();
                 }
             }
         }
@@ -203,6 +200,7 @@ pub fn path_grid(props: &PathGridProps) -> Html {
 
     let draw_walls = {
         let walls = walls.clone();
+        let mud = mud.clone();
         let canvas = wall_canvas.clone();
         let ctx = wall_ctx.clone();
 
@@ -216,6 +214,21 @@ pub fn path_grid(props: &PathGridProps) -> Html {
 
                     ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
 
+                    // The mud is see-through so that the search can be seen under it
+                    ctx.begin_path();
+                    ctx.set_global_alpha(0.6);
+                    ctx.set_fill_style(&config.color_mud.as_str().into());
+                    for vertex in mud.borrow().iter() {
+                        ctx.rect(
+                            vertex.x as f64 * cell_width,
+                            vertex.y as f64 * cell_height,
+                            cell_width,
+                            cell_height,
+                        );
+                    }
+                    ctx.fill();
+                    ctx.set_global_alpha(1.0);
+
                     ctx.begin_path();
                     ctx.set_fill_style(&config.color_wall.as_str().into());
                     for vertex in walls.borrow().iter() {
@@ -309,7 +322,7 @@ pub fn path_grid(props: &PathGridProps) -> Html {
                 draw_walls();
                 || ()
             },
-            walls.borrow().clone(),
+            (walls.borrow().clone(), mud.borrow().clone()),
         );
     }
 
@@ -395,7 +408,15 @@ pub fn path_grid(props: &PathGridProps) -> Html {
                 style={format!("z-index: 4; aspect-ratio: {} / {};", width, height, )}
                 width={background_canvas_size.0.to_string()}
                 height={background_canvas_size.1.to_string()}
-                onmouseup={move |_| on_draw_end.emit(())}
+                onmouseup={
+                    let on_draw_end = on_draw_end.clone();
+                    move |_| on_draw_end.emit(())
+                }
+                // Dragging off of the grid ends the drag, since the mouse button may be released outside of it
+                onmouseleave={
+                    let on_draw_end = on_draw_end.clone();
+                    move |_| on_draw_end.emit(())
+                }
                 onmousedown={onmouseover.clone()}
                 onmousemove={onmouseover}
             >
@@ -1,6 +1,6 @@
 use std::{cell::RefCell, rc::Rc};
 
-use pathfinding::{Coord, Edge};
+use pathfinding::{heuristics::Heuristic, Coord, Edge};
 use yew::prelude::*;
 use yew_router::{history::History, hooks::use_history};
 
@@ -38,8 +38,7 @@ pub fn pathfinding_controls<E: 'static + Edge>(props: &PathfindingControlsProps<
         let on_update_config = on_update_config.clone();
 
         Callback::from(move |graph_width| {
-            if graph_width > 1 && graph_width != config.borrow().graph_width {
-                config.borrow_mut().graph_width = graph_width;
+            if config.borrow_mut().set_graph_width(graph_width) {
                 on_update_config.emit(());
             }
         })
@@ -50,8 +49,23 @@ pub fn pathfinding_controls<E: 'static + Edge>(props: &PathfindingControlsProps<
         let on_update_config = on_update_config.clone();
 
         Callback::from(move |graph_height| {
-            if graph_height > 1 && graph_height != config.borrow().graph_height {
-                config.borrow_mut().graph_height = graph_height;
+            if config.borrow_mut().set_graph_height(graph_height) {
+                on_update_config.emit(());
+            }
+        })
+    };
+
+    let change_heuristic = {
+        let config = config.clone();
+        let on_update_config = on_update_config.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(heuristic) = Heuristic::ALL
+                .iter()
+                .copied()
+                .find(|heuristic| heuristic.name() == name)
+            {
+                config.borrow_mut().heuristic = heuristic;
                 on_update_config.emit(());
             }
         })
@@ -61,8 +75,7 @@ pub fn pathfinding_controls<E: 'static + Edge>(props: &PathfindingControlsProps<
         let config = config.clone();
 
         Callback::from(move |_| {
-            let move_diagonally = config.borrow().move_diagonally;
-            config.borrow_mut().move_diagonally = !move_diagonally;
+            config.borrow_mut().toggle_move_diagonally();
             on_update_config.emit(());
         })
     };
@@ -89,6 +102,20 @@ pub fn pathfinding_controls<E: 'static + Edge>(props: &PathfindingControlsProps<
                 selected_value={config.borrow().algorithm.name.to_string()}
                 onchange={change_algorithm}
             />
+            {
+                if config.borrow().algorithm.uses_heuristic {
+                    html! {
+                        <SelectInput
+                            title="Heuristic"
+                            options={Heuristic::ALL.iter().map(|heuristic| heuristic.name().to_string()).collect::<Vec<String>>()}
+                            selected_value={config.borrow().heuristic.name().to_string()}
+                            onchange={change_heuristic}
+                        />
+                    }
+                } else {
+                    html! {}
+                }
+            }
             <IntInput<usize>
                 title="Graph width"
                 value={config.borrow().graph_width}
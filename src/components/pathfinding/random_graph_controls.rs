@@ -0,0 +1,106 @@
+use std::{cell::RefCell, rc::Rc};
+
+use pathfinding::{graph::RandomGraphKind, Edge};
+use yew::prelude::*;
+
+use crate::{components::input_items::*, pages::pathfinding::PathfindingConfig};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct RandomGraphControlsProps<E: 'static + Edge> {
+    pub config: Rc<RefCell<PathfindingConfig<E>>>,
+    pub on_generate_graph: Callback<()>,
+}
+
+#[function_component(RandomGraphControls)]
+pub fn random_graph_controls<E: 'static + Edge>(props: &RandomGraphControlsProps<E>) -> Html {
+    let RandomGraphControlsProps {
+        config,
+        on_generate_graph,
+    } = props.clone();
+    // Kept in a state so that the edge probability is shown or hidden when the kind changes
+    let random_graph = use_state_eq(|| config.borrow().random_graph);
+
+    let change_random_graph = {
+        let config = config.clone();
+        let random_graph = random_graph.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(kind) = RandomGraphKind::ALL
+                .iter()
+                .copied()
+                .find(|kind| kind.name() == name)
+            {
+                config.borrow_mut().random_graph = kind;
+                random_graph.set(kind);
+            }
+        })
+    };
+
+    let change_edge_probability = {
+        let config = config.clone();
+
+        Callback::from(move |edge_probability: f64| {
+            config.borrow_mut().edge_probability = edge_probability.clamp(0.0, 1.0);
+        })
+    };
+
+    let change_min_weight = {
+        let config = config.clone();
+
+        Callback::from(move |min_weight| {
+            config.borrow_mut().min_weight = min_weight;
+        })
+    };
+
+    let change_max_weight = {
+        let config = config.clone();
+
+        Callback::from(move |max_weight| {
+            config.borrow_mut().max_weight = max_weight;
+        })
+    };
+
+    let on_generate_graph = Callback::from(move |_| {
+        on_generate_graph.emit(());
+    });
+
+    html! {
+        <div class="random-graph-controls">
+            <SelectInput
+                title="Graph kind"
+                options={RandomGraphKind::ALL.iter().map(|kind| kind.name().to_string()).collect::<Vec<String>>()}
+                selected_value={random_graph.name().to_string()}
+                onchange={change_random_graph}
+            />
+            {
+                if random_graph.uses_edge_probability() {
+                    html! {
+                        <FloatInput<f64>
+                            title="Edge probability"
+                            value={config.borrow().edge_probability}
+                            oninput={change_edge_probability}
+                            min={0.0}
+                            max={1.0}
+                            step={0.01}
+                        />
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            <FloatInput<f64>
+                title="Min weight"
+                value={config.borrow().min_weight}
+                oninput={change_min_weight}
+                min={0.0}
+            />
+            <FloatInput<f64>
+                title="Max weight"
+                value={config.borrow().max_weight}
+                oninput={change_max_weight}
+                min={0.0}
+            />
+            <Button title="Generate graph" onclick={on_generate_graph} />
+        </div>
+    }
+}
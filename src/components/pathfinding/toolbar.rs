@@ -6,6 +6,7 @@ pub enum PathTool {
     Start,
     End,
     Wall,
+    Mud,
 }
 
 #[derive(Clone, PartialEq)]
@@ -20,6 +21,12 @@ pub struct PathToolbarProps {
     #[prop_or(PathTool::Wall)]
     pub active_tool: PathTool,
     pub on_tool_change: Callback<PathTool>,
+    #[prop_or_default]
+    pub can_undo: bool,
+    #[prop_or_default]
+    pub can_redo: bool,
+    pub on_undo: Callback<()>,
+    pub on_redo: Callback<()>,
 }
 
 #[function_component(PathToolbar)]
@@ -31,6 +38,11 @@ pub fn path_toolbar(props: &PathToolbarProps) -> Html {
                 description: "Draw walls".to_string(),
                 icon: html! { { "▣" } },
             },
+            PathToolButton {
+                tool: PathTool::Mud,
+                description: "Draw mud, which costs more to cross".to_string(),
+                icon: html! { <span style="color: #8b5a2b">{ "▣" }</span> },
+            },
             PathToolButton {
                 tool: PathTool::Start,
                 description: "Set the path's starting point".to_string(),
@@ -64,6 +76,22 @@ pub fn path_toolbar(props: &PathToolbarProps) -> Html {
                     }
                 }).collect::<Html>()
             }
+            <div class="history-buttons">
+                <button
+                    class="toolbar-button"
+                    onclick={props.on_undo.reform(|_| ())}
+                    disabled={!props.can_undo}
+                    title="Undo (Ctrl+Z)">
+                    { "↶" }
+                </button>
+                <button
+                    class="toolbar-button"
+                    onclick={props.on_redo.reform(|_| ())}
+                    disabled={!props.can_redo}
+                    title="Redo (Ctrl+Shift+Z)">
+                    { "↷" }
+                </button>
+            </div>
         </div>
     }
 }
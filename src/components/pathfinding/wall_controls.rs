@@ -1,17 +1,24 @@
+use std::{cell::RefCell, rc::Rc};
+
+use pathfinding::{maze_generation::MazeAlgorithm, Edge};
 use yew::prelude::*;
 
-use crate::components::input_items::*;
+use crate::{components::input_items::*, pages::pathfinding::PathfindingConfig};
 
 #[derive(Properties, Clone, PartialEq)]
-pub struct WallControlsProps {
+pub struct WallControlsProps<E: 'static + Edge> {
+    pub config: Rc<RefCell<PathfindingConfig<E>>>,
     pub on_clear_walls: Callback<()>,
+    pub on_clear_mud: Callback<()>,
     pub on_generate_maze: Callback<()>,
 }
 
 #[function_component(WallControls)]
-pub fn pathfinding_controls(props: &WallControlsProps) -> Html {
+pub fn wall_controls<E: 'static + Edge>(props: &WallControlsProps<E>) -> Html {
     let WallControlsProps {
+        config,
         on_clear_walls,
+        on_clear_mud,
         on_generate_maze,
     } = props.clone();
 
@@ -19,6 +26,24 @@ pub fn pathfinding_controls(props: &WallControlsProps) -> Html {
         on_clear_walls.emit(());
     });
 
+    let on_clear_mud = Callback::from(move |_| {
+        on_clear_mud.emit(());
+    });
+
+    let change_maze_algorithm = {
+        let config = config.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(maze_algorithm) = MazeAlgorithm::ALL
+                .iter()
+                .copied()
+                .find(|maze_algorithm| maze_algorithm.name() == name)
+            {
+                config.borrow_mut().maze_algorithm = maze_algorithm;
+            }
+        })
+    };
+
     let on_generate_maze = Callback::from(move |_| {
         on_generate_maze.emit(());
     });
@@ -26,6 +51,13 @@ pub fn pathfinding_controls(props: &WallControlsProps) -> Html {
     html! {
         <div class="wall-controls">
             <Button title="Clear walls" onclick={on_clear_walls} />
+            <Button title="Clear mud" onclick={on_clear_mud} />
+            <SelectInput
+                title="Maze algorithm"
+                options={MazeAlgorithm::ALL.iter().map(|maze_algorithm| maze_algorithm.name().to_string()).collect::<Vec<String>>()}
+                selected_value={config.borrow().maze_algorithm.name().to_string()}
+                onchange={change_maze_algorithm}
+            />
             <Button title="Generate maze" onclick={on_generate_maze} />
         </div>
     }
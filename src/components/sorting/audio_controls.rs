@@ -13,6 +13,8 @@ pub struct AudioConfig {
     pub min_frequency: f32,
     pub max_frequency: f32,
     pub note_duration: Duration,
+    /// The volume of the notes, from 0.0 (silent) to 1.0.
+    pub volume: f32,
 }
 impl Default for AudioConfig {
     fn default() -> Self {
@@ -22,6 +24,7 @@ impl Default for AudioConfig {
             min_frequency: 50.0,
             max_frequency: 800.0,
             note_duration: Duration::from_millis(200),
+            volume: 1.0,
         }
     }
 }
@@ -95,6 +98,7 @@ pub fn audio_controls(props: &AudioControlsProps) -> Html {
 
     let change_max_frequency = {
         let config = config.clone();
+        let update_config = update_config.clone();
 
         Callback::from(move |max_frequency| {
             update_config.emit((
@@ -107,6 +111,20 @@ pub fn audio_controls(props: &AudioControlsProps) -> Html {
         })
     };
 
+    let change_volume = {
+        let config = config.clone();
+
+        Callback::from(move |volume: f32| {
+            update_config.emit((
+                AudioConfig {
+                    volume: volume.clamp(0.0, 1.0),
+                    ..config.clone()
+                },
+                false,
+            ))
+        })
+    };
+
     html! {
         <div class="audio-controls">
             <Checkbox title="Audio enabled" value={config.enabled} oninput={toggle_audio} />
@@ -116,6 +134,13 @@ pub fn audio_controls(props: &AudioControlsProps) -> Html {
                 selected_value={sound_types.clone().iter().find(|(_, val)| **val == config.sound_type).unwrap().0.to_owned()}
                 onchange={change_sound_type}
             />
+            <FloatInput<f32>
+                title="Volume"
+                value={props.config.volume}
+                oninput={change_volume}
+                min={0.0}
+                max={1.0}
+            />
             <FloatInput<f32>
                 title="Minimum frequency"
                 value={props.config.min_frequency}
@@ -0,0 +1,159 @@
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use yew::prelude::*;
+use yew_hooks::use_size;
+
+use crate::hooks::use_color_scheme::ColorScheme;
+
+/// The space around the plot for the labels of the axes, in pixels: `(left, bottom)`.
+const AXIS_MARGIN: (f64, f64) = (60.0, 30.0);
+/// How many labeled ticks each axis has.
+const TICKS: usize = 5;
+
+#[derive(Properties, PartialEq)]
+pub struct BenchmarkChartProps {
+    pub sizes: Vec<usize>,
+    /// The name of each algorithm and the time in milliseconds that it took for each size, or `None` for the sizes that it skipped.
+    /// There can be fewer times than sizes while the benchmark is still running.
+    pub series: Vec<(String, Vec<Option<f64>>)>,
+}
+
+/// A line chart of the time that each algorithm took against the input size, with a legend below it.
+#[function_component(BenchmarkChart)]
+pub fn benchmark_chart(props: &BenchmarkChartProps) -> Html {
+    let color_scheme = use_context::<ColorScheme>().expect("no color scheme context found");
+    let canvas_ref = use_node_ref();
+    let canvas_container_ref = use_node_ref();
+    let canvas_container_size = use_size(canvas_container_ref.clone());
+
+    {
+        let canvas_ref = canvas_ref.clone();
+
+        use_effect_with_deps(
+            move |(sizes, series, _, color_scheme)| {
+                if let Some(canvas) = canvas_ref.cast::<HtmlCanvasElement>() {
+                    let ctx: CanvasRenderingContext2d = canvas
+                        .get_context("2d")
+                        .unwrap()
+                        .unwrap()
+                        .dyn_into()
+                        .unwrap();
+                    draw(
+                        &ctx,
+                        (canvas.width() as f64, canvas.height() as f64),
+                        sizes,
+                        series,
+                        *color_scheme,
+                    );
+                }
+                || ()
+            },
+            (
+                props.sizes.clone(),
+                props.series.clone(),
+                canvas_container_size,
+                color_scheme,
+            ),
+        );
+    }
+
+    html! {
+        <div class="benchmark-chart">
+            <div ref={canvas_container_ref} class="benchmark-chart-container">
+                <canvas
+                    ref={canvas_ref}
+                    width={canvas_container_size.0.to_string()}
+                    height={canvas_container_size.1.to_string()}
+                ></canvas>
+            </div>
+            <ul class="benchmark-legend">
+                {
+                    for props.series.iter().enumerate().map(|(i, (name, times))| html! {
+                        <li>
+                            <span class="benchmark-legend-color" style={format!("background-color: {}", series_color(i, props.series.len()))}></span>
+                            { name }
+                            {
+                                if times.contains(&None) {
+                                    " (skipped for the largest inputs)"
+                                } else {
+                                    ""
+                                }
+                            }
+                        </li>
+                    })
+                }
+            </ul>
+        </div>
+    }
+}
+
+/// Gets a color for the series at `index`, with the hues spread evenly around the color wheel.
+fn series_color(index: usize, count: usize) -> String {
+    format!("hsl({}, 70%, 50%)", index * 360 / count.max(1))
+}
+
+fn draw(
+    ctx: &CanvasRenderingContext2d,
+    (width, height): (f64, f64),
+    sizes: &[usize],
+    series: &[(String, Vec<Option<f64>>)],
+    color_scheme: ColorScheme,
+) {
+    ctx.clear_rect(0.0, 0.0, width, height);
+
+    let max_size = sizes.iter().copied().max().unwrap_or(0) as f64;
+    let max_time = series
+        .iter()
+        .flat_map(|(_, times)| times.iter().flatten())
+        .fold(0.0, |max: f64, time| max.max(*time));
+    let (left, bottom) = AXIS_MARGIN;
+    let (plot_width, plot_height) = (width - left, height - bottom);
+    let x = |size: f64| left + size / max_size.max(1.0) * plot_width;
+    // The top of the plot is left empty so that the top label fits
+    let y = |time: f64| plot_height - time / max_time.max(f64::EPSILON) * plot_height * 0.95;
+
+    let text_color = match color_scheme {
+        ColorScheme::Light => "#333333",
+        ColorScheme::Dark => "#dddddd",
+    };
+    ctx.set_stroke_style(&JsValue::from_str(text_color));
+    ctx.set_fill_style(&JsValue::from_str(text_color));
+    ctx.set_line_width(1.0);
+    ctx.begin_path();
+    ctx.move_to(left, 0.0);
+    ctx.line_to(left, plot_height);
+    ctx.line_to(width, plot_height);
+    ctx.stroke();
+
+    for tick in 0..=TICKS {
+        let fraction = tick as f64 / TICKS as f64;
+        let size = max_size * fraction;
+        let time = max_time * fraction;
+
+        ctx.set_text_align("center");
+        ctx.fill_text(&format!("{:.0}", size), x(size), height - 10.0)
+            .unwrap();
+        ctx.set_text_align("right");
+        ctx.fill_text(&format!("{:.1} ms", time), left - 5.0, y(time) + 4.0)
+            .unwrap();
+    }
+
+    ctx.set_line_width(2.0);
+    for (i, (_, times)) in series.iter().enumerate() {
+        ctx.set_stroke_style(&JsValue::from_str(&series_color(i, series.len())));
+        ctx.begin_path();
+        let mut drawing = false;
+        for (size, time) in sizes.iter().zip(times) {
+            match time {
+                Some(time) if drawing => ctx.line_to(x(*size as f64), y(*time)),
+                Some(time) => {
+                    ctx.move_to(x(*size as f64), y(*time));
+                    drawing = true;
+                }
+                // A skipped size breaks the line
+                None => drawing = false,
+            }
+        }
+        ctx.stroke();
+    }
+}
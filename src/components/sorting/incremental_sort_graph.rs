@@ -0,0 +1,165 @@
+use std::ops::Range;
+
+use sorting::{SortCommand, TotalF64};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use yew::prelude::*;
+use yew_hooks::use_size;
+
+use crate::{
+    hooks::use_color_scheme::ColorScheme,
+    utils::{
+        palette::Palette,
+        sort_renderer::{dirty_indices, step_indices},
+    },
+};
+
+#[derive(Properties, PartialEq)]
+pub struct IncrementalSortGraphProps {
+    pub items: Vec<TotalF64>,
+    pub step: Vec<SortCommand<TotalF64>>,
+    pub palette: Palette,
+}
+
+/// What was drawn on the canvas last, so that the next step only has to redraw what changed.
+struct DrawnGraph {
+    items: Vec<TotalF64>,
+    highlighted: Vec<usize>,
+    size: (f64, f64),
+    /// The base color and the highlight color.
+    colors: (&'static str, &'static str),
+    max_val: f64,
+}
+
+/// A sort graph for large inputs that only redraws the bars that changed since the previous step, unlike [`SortGraph`](super::sort_graph::SortGraph).\
+/// Everything is redrawn when the size, the colors or the largest value change, since those affect every bar.
+#[function_component(IncrementalSortGraph)]
+pub fn incremental_sort_graph(props: &IncrementalSortGraphProps) -> Html {
+    let app_color_scheme = use_context::<ColorScheme>().expect("no color scheme context found");
+    let canvas_ref = use_node_ref();
+    let canvas_container_ref = use_node_ref();
+    let canvas_container_size = use_size(canvas_container_ref.clone());
+    let drawn = use_mut_ref(|| None);
+
+    {
+        let canvas_ref = canvas_ref.clone();
+        let colors = (
+            props.palette.base_color(app_color_scheme),
+            props.palette.highlight_color(app_color_scheme),
+        );
+
+        use_effect_with_deps(
+            move |(items, step, _, colors)| {
+                if let Some(canvas) = canvas_ref.cast::<HtmlCanvasElement>() {
+                    let ctx: CanvasRenderingContext2d = canvas
+                        .get_context("2d")
+                        .unwrap()
+                        .unwrap()
+                        .dyn_into()
+                        .unwrap();
+                    let next = DrawnGraph {
+                        items: items.clone(),
+                        highlighted: step_indices(step),
+                        size: (canvas.width() as f64, canvas.height() as f64),
+                        colors: *colors,
+                        max_val: items
+                            .iter()
+                            .map(|val| val.0)
+                            .filter(|val| val.is_finite())
+                            .fold(0.0, f64::max),
+                    };
+                    draw(&ctx, drawn.borrow().as_ref(), &next);
+                    *drawn.borrow_mut() = Some(next);
+                }
+                || ()
+            },
+            (
+                props.items.clone(),
+                props.step.clone(),
+                canvas_container_size,
+                colors,
+            ),
+        );
+    }
+
+    html! {
+        <div ref={canvas_container_ref} class="sort-graph-container">
+            <canvas
+                ref={canvas_ref}
+                class="sort-graph"
+                width={canvas_container_size.0.to_string()}
+                height={canvas_container_size.1.to_string()}
+            ></canvas>
+        </div>
+    }
+}
+
+/// Draws `next` over `prev`, only redrawing the pixel columns of the bars that changed if the rest of the graph is still the same.
+fn draw(ctx: &CanvasRenderingContext2d, prev: Option<&DrawnGraph>, next: &DrawnGraph) {
+    let (canvas_width, canvas_height) = next.size;
+    let len = next.items.len();
+
+    let prev = match prev {
+        Some(prev)
+            if prev.size == next.size
+                && prev.colors == next.colors
+                && prev.max_val == next.max_val
+                && prev.items.len() == len =>
+        {
+            prev
+        }
+        _ => {
+            ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
+            draw_bars(ctx, next, 0..len);
+            return;
+        }
+    };
+
+    let width = canvas_width / len as f64;
+    for i in dirty_indices(
+        &prev.items,
+        &next.items,
+        &prev.highlighted,
+        &next.highlighted,
+    ) {
+        // A bar can be thinner than a pixel, in which case the bars that share its pixels are redrawn too
+        let left = (width * i as f64).floor();
+        let right = (width * (i + 1) as f64).ceil();
+        let first = (left / width).floor() as usize;
+        let last = ((right / width).ceil() as usize).min(len);
+
+        ctx.save();
+        ctx.begin_path();
+        ctx.rect(left, 0.0, right - left, canvas_height);
+        ctx.clip();
+        ctx.clear_rect(left, 0.0, right - left, canvas_height);
+        draw_bars(ctx, next, first..last);
+        ctx.restore();
+    }
+}
+
+fn draw_bars(ctx: &CanvasRenderingContext2d, graph: &DrawnGraph, indices: Range<usize>) {
+    let (canvas_width, canvas_height) = graph.size;
+    let width = canvas_width / graph.items.len() as f64;
+    let margin = width * 0.1;
+    // Remove margin when it's small enough to avoid problem where some bars have a tiny margin and some don't.
+    let margin = if margin < 0.5 { 0.0 } else { margin };
+
+    for i in indices {
+        let color = if graph.highlighted.contains(&i) {
+            graph.colors.1
+        } else {
+            graph.colors.0
+        };
+        // Values that can't be drawn as a bar, like NaN or negative values, get no height
+        let height = graph.items[i].0.max(0.0) / graph.max_val * canvas_height;
+
+        ctx.set_fill_style(&JsValue::from_str(color));
+        ctx.fill_rect(
+            width * i as f64 + margin * 0.5,
+            canvas_height - height,
+            width - margin,
+            height,
+        );
+    }
+}
@@ -1,3 +1,8 @@
 pub mod audio_controls;
+pub mod benchmark_chart;
+pub mod incremental_sort_graph;
+pub mod race_grid;
+pub mod race_leaderboard;
 pub mod sort_controls;
 pub mod sort_graph;
+pub mod sort_stats;
@@ -0,0 +1,17 @@
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct RaceGridProps {
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Lays out the lanes of a race in a grid of two columns, so that up to four of them fit on the screen at once.
+#[function_component(RaceGrid)]
+pub fn race_grid(props: &RaceGridProps) -> Html {
+    html! {
+        <div class="race-grid">
+            { for props.children.iter() }
+        </div>
+    }
+}
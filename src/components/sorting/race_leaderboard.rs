@@ -0,0 +1,45 @@
+use yew::prelude::*;
+
+/// How an algorithm is doing in the race, for a row of the [`RaceLeaderboard`].
+#[derive(Clone, PartialEq)]
+pub struct RaceStanding {
+    pub algorithm: String,
+    /// The place that the algorithm finished in, or `None` if it's still sorting.
+    pub place: Option<usize>,
+    /// The swaps and sets played so far.
+    pub operations: usize,
+    /// The swaps and sets that the algorithm does in total.
+    pub total_operations: usize,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct RaceLeaderboardProps {
+    pub standings: Vec<RaceStanding>,
+}
+
+/// Lists the racing algorithms with the finished ones first, in the order that they finished.
+#[function_component(RaceLeaderboard)]
+pub fn race_leaderboard(props: &RaceLeaderboardProps) -> Html {
+    let mut standings = props.standings.clone();
+    // The algorithms that are still sorting keep their order after the finished ones
+    standings.sort_by_key(|standing| standing.place.unwrap_or(usize::MAX));
+
+    html! {
+        <table class="race-leaderboard">
+            <tr>
+                <th>{"Place"}</th>
+                <th>{"Algorithm"}</th>
+                <th>{"Operations"}</th>
+            </tr>
+            {
+                for standings.iter().map(|standing| html! {
+                    <tr>
+                        <td>{ standing.place.map_or_else(|| "-".to_string(), |place| place.to_string()) }</td>
+                        <th>{ &standing.algorithm }</th>
+                        <td>{ format!("{} of {}", standing.operations, standing.total_operations) }</td>
+                    </tr>
+                })
+            }
+        </table>
+    }
+}
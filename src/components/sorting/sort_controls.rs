@@ -1,16 +1,31 @@
+use std::rc::Rc;
+
+use sorting::{count_inversions, TotalF64};
+use web_sys::window;
 use yew::prelude::*;
 use yew_router::{history::History, hooks::use_history};
 
 use crate::{
     components::input_items::*,
-    pages::sorting::{get_sorting_algorithms, SortConfig, SortingRoute},
-    utils::{gen_u32_vec, knuth_shuffle},
+    pages::sorting::{
+        algorithm_slug, get_sorting_algorithms, SortConfig, SortingAlgorithm, SortingRoute,
+        MAX_INPUT_LEN, MAX_RACERS,
+    },
+    utils::{
+        gen_with_inversions, input_distribution::InputDistribution, palette::Palette,
+        parse_i32_list, shuffle, sort_renderer::SortRenderer,
+        sort_visualization::SortVisualization,
+    },
 };
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct SortControlsProps {
     pub config: SortConfig,
-    pub update_input: Callback<Vec<u32>>,
+    /// The current input, used for shuffling it without changing its values.
+    pub input: Rc<Vec<TotalF64>>,
+    pub update_input: Callback<Vec<TotalF64>>,
+    /// Restores the input that the current sort started from and replays the sort from the first step.
+    pub reset_input: Callback<()>,
     pub update_config: Callback<(SortConfig, bool)>,
 }
 
@@ -18,12 +33,47 @@ pub struct SortControlsProps {
 pub fn sort_controls(props: &SortControlsProps) -> Html {
     let SortControlsProps {
         config,
+        input,
         update_input,
+        reset_input,
         update_config,
     } = props.clone();
 
     let history = use_history().unwrap();
 
+    // Bucket sort only sorts whole numbers, so it can't race on floating-point inputs
+    let racer_options = get_sorting_algorithms()
+        .into_values()
+        .filter(|algorithm| *algorithm != config.sorting_algorithm && !algorithm.whole_numbers_only)
+        .collect::<Vec<SortingAlgorithm>>();
+    // Once there are as many racers as fit in the grid, only the chosen ones can be unchecked
+    let racers_full = config.race_algorithms.len() >= MAX_RACERS;
+    let toggle_racer = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        move |racer: SortingAlgorithm| {
+            let config = config.clone();
+            let update_config = update_config.clone();
+
+            Callback::from(move |_| {
+                let mut race_algorithms = config.race_algorithms.clone();
+                if race_algorithms.contains(&racer) {
+                    race_algorithms.retain(|algorithm| *algorithm != racer);
+                } else {
+                    race_algorithms.push(racer.clone());
+                }
+                update_config.emit((
+                    SortConfig {
+                        race_algorithms,
+                        ..config.clone()
+                    },
+                    false,
+                ));
+            })
+        }
+    };
+
     let algorithm_names = use_state_eq(|| {
         get_sorting_algorithms()
             .values()
@@ -31,11 +81,93 @@ pub fn sort_controls(props: &SortControlsProps) -> Html {
             .collect::<Vec<String>>()
     });
 
+    let custom_input = use_state(String::new);
+    let custom_input_error = use_state(|| None::<String>);
+
     let gen_input = {
         let config = config.clone();
+        let update_config = update_config.clone();
 
+        // A new seed, so that the new input can be shared through the URL as well
         Callback::from(move |_e: MouseEvent| {
-            update_input.emit(knuth_shuffle(gen_u32_vec(config.input_len)));
+            update_config.emit((
+                SortConfig {
+                    seed: rand::random(),
+                    ..config.clone()
+                },
+                true,
+            ));
+        })
+    };
+    let gen_mountain_input = {
+        let config = config.clone();
+        let update_input = update_input.clone();
+
+        Callback::from(move |_e: MouseEvent| {
+            update_input.emit(config.gen_mountain_input());
+        })
+    };
+    let show_worst_case = {
+        let config = config.clone();
+        let update_input = update_input.clone();
+
+        Callback::from(move |_e: MouseEvent| {
+            update_input.emit(config.sorting_algorithm.worst_case_input(config.input_len));
+        })
+    };
+    let inversions = count_inversions(&input);
+    let change_inversions = {
+        let config = config.clone();
+        let update_input = update_input.clone();
+
+        Callback::from(move |target_inversions| {
+            // Start the numbers from 1 like the other generated inputs
+            let items = gen_with_inversions(config.input_len, target_inversions, rand::random())
+                .into_iter()
+                .map(|item| TotalF64::from(item + 1))
+                .collect();
+            update_input.emit(items);
+        })
+    };
+    let shuffle_input = {
+        let update_input = update_input.clone();
+
+        Callback::from(move |_e: MouseEvent| {
+            let mut items = (*input).clone();
+            shuffle(&mut items, rand::random());
+            update_input.emit(items);
+        })
+    };
+    let change_custom_input = {
+        let config = config.clone();
+        let custom_input = custom_input.clone();
+        let custom_input_error = custom_input_error.clone();
+
+        Callback::from(move |value: String| {
+            match parse_i32_list(&value) {
+                Ok(items) if items.is_empty() => custom_input_error.set(None),
+                // Algorithms like counting sort convert the numbers to unsigned ones, which would change negative numbers
+                Ok(items)
+                    if config.sorting_algorithm.whole_numbers_only
+                        && items.iter().any(|item| *item < 0) =>
+                {
+                    custom_input_error.set(Some(format!(
+                        "{} only sorts numbers that aren't negative",
+                        config.sorting_algorithm.name
+                    )))
+                }
+                Ok(items) => {
+                    custom_input_error.set(None);
+                    update_input.emit(
+                        items
+                            .into_iter()
+                            .map(|item| TotalF64::from(f64::from(item)))
+                            .collect(),
+                    );
+                }
+                Err(error) => custom_input_error.set(Some(error.to_string())),
+            }
+            custom_input.set(value);
         })
     };
     let change_input_len = {
@@ -43,7 +175,10 @@ pub fn sort_controls(props: &SortControlsProps) -> Html {
         let update_config = update_config.clone();
 
         Callback::from(move |input_len| {
-            if input_len > 1 && input_len != config.input_len {
+            if (2..=MAX_INPUT_LEN).contains(&input_len)
+                && input_len != config.input_len
+                && confirm_large_run(&config, input_len)
+            {
                 update_config.emit((
                     SortConfig {
                         input_len,
@@ -54,6 +189,198 @@ pub fn sort_controls(props: &SortControlsProps) -> Html {
             }
         })
     };
+    let change_distribution = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(distribution) = InputDistribution::ALL
+                .iter()
+                .copied()
+                .find(|distribution| distribution.name() == name)
+            {
+                update_config.emit((
+                    SortConfig {
+                        distribution,
+                        ..config.clone()
+                    },
+                    true,
+                ));
+            }
+        })
+    };
+    let change_presortedness = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |presortedness: f64| {
+            if (0.0..=1.0).contains(&presortedness) {
+                update_config.emit((
+                    SortConfig {
+                        presortedness,
+                        ..config.clone()
+                    },
+                    true,
+                ));
+            }
+        })
+    };
+    let toggle_float_input = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |_| {
+            update_config.emit((
+                SortConfig {
+                    float_range: match config.float_range {
+                        Some(_) => None,
+                        None => Some((0.0, 1.0)),
+                    },
+                    ..config.clone()
+                },
+                true,
+            ));
+        })
+    };
+    let change_float_min = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |min: f64| {
+            if let Some((_, max)) = config.float_range {
+                if min >= 0.0 && min < max {
+                    update_config.emit((
+                        SortConfig {
+                            float_range: Some((min, max)),
+                            ..config.clone()
+                        },
+                        true,
+                    ));
+                }
+            }
+        })
+    };
+    let change_float_max = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |max: f64| {
+            if let Some((min, _)) = config.float_range {
+                if max > min {
+                    update_config.emit((
+                        SortConfig {
+                            float_range: Some((min, max)),
+                            ..config.clone()
+                        },
+                        true,
+                    ));
+                }
+            }
+        })
+    };
+    let change_visualization = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(visualization) = SortVisualization::ALL
+                .iter()
+                .copied()
+                .find(|visualization| visualization.name() == name)
+            {
+                update_config.emit((
+                    SortConfig {
+                        visualization,
+                        ..config.clone()
+                    },
+                    false,
+                ));
+            }
+        })
+    };
+    let change_palette = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(palette) = Palette::ALL
+                .iter()
+                .copied()
+                .find(|palette| palette.name() == name)
+            {
+                update_config.emit((
+                    SortConfig {
+                        palette,
+                        ..config.clone()
+                    },
+                    false,
+                ));
+            }
+        })
+    };
+    let change_renderer = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(renderer) = SortRenderer::ALL
+                .iter()
+                .copied()
+                .find(|renderer| renderer.name() == name)
+            {
+                update_config.emit((
+                    SortConfig {
+                        renderer,
+                        ..config.clone()
+                    },
+                    false,
+                ));
+            }
+        })
+    };
+    let toggle_inversions = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |_| {
+            update_config.emit((
+                SortConfig {
+                    highlight_inversions: !config.highlight_inversions,
+                    ..config.clone()
+                },
+                false,
+            ));
+        })
+    };
+    let toggle_stability_demo = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        // The demo needs an input with many equal values, so the input is regenerated
+        Callback::from(move |_| {
+            update_config.emit((
+                SortConfig {
+                    stability_demo: !config.stability_demo,
+                    ..config.clone()
+                },
+                true,
+            ));
+        })
+    };
+    let toggle_proportional_playback = {
+        let config = config.clone();
+        let update_config = update_config.clone();
+
+        Callback::from(move |_| {
+            update_config.emit((
+                SortConfig {
+                    proportional_playback: !config.proportional_playback,
+                    ..config.clone()
+                },
+                false,
+            ));
+        })
+    };
     let change_playback_time = {
         let config = config.clone();
 
@@ -69,31 +396,161 @@ pub fn sort_controls(props: &SortControlsProps) -> Html {
     };
     let change_algorithm = Callback::from(move |algorithm: String| {
         history.push(SortingRoute::SortingAlgorithm {
-            algorithm: algorithm.replace(' ', "-").to_lowercase(),
+            algorithm: algorithm_slug(&algorithm),
         });
     });
 
     html! {
         <div class="sort-controls">
             <Button title="Generate input" onclick={gen_input} />
+            <Button title="Mountain input" onclick={gen_mountain_input} />
+            <Button title="Shuffle input" onclick={shuffle_input} />
+            <Button title="Reset" onclick={reset_input.reform(|_: MouseEvent| ())} />
+            <Button title="Show worst case" onclick={show_worst_case} />
+            <TextInput
+                title="Custom input"
+                value={(*custom_input).clone()}
+                placeholder={"e.g. 5, 3, 8, 1".to_string()}
+                error={(*custom_input_error).clone()}
+                oninput={change_custom_input}
+            />
             <IntInput<usize>
                 title="Input length"
                 value={props.config.input_len}
                 oninput={change_input_len}
                 min={2}
+                max={MAX_INPUT_LEN}
+            />
+            <SelectInput
+                title="Input distribution"
+                options={InputDistribution::ALL.iter().map(|distribution| distribution.name().to_string()).collect::<Vec<String>>()}
+                selected_value={config.distribution.name().to_string()}
+                onchange={change_distribution}
+            />
+            {
+                // Only random inputs can be more or less sorted
+                if config.distribution == InputDistribution::Random {
+                    html! {
+                        <FloatInput<f64>
+                            title="Presortedness"
+                            value={props.config.presortedness}
+                            oninput={change_presortedness}
+                            min={0.0}
+                            max={1.0}
+                        />
+                    }
+                } else {
+                    html! {}
+                }
+            }
+            <IntInput<usize>
+                title="Inversions"
+                value={inversions}
+                oninput={change_inversions}
+                min={0}
+                max={config.input_len * config.input_len.saturating_sub(1) / 2}
             />
+            <Checkbox
+                title="Highlight inversions"
+                value={config.highlight_inversions}
+                oninput={toggle_inversions}
+            />
+            <Checkbox
+                title="Stability demo"
+                value={config.stability_demo}
+                oninput={toggle_stability_demo}
+            />
+            <Checkbox
+                title="Floating-point input"
+                value={config.float_range.is_some()}
+                oninput={toggle_float_input}
+            />
+            {
+                if let Some((min, max)) = config.float_range {
+                    html! {
+                        <>
+                            <FloatInput<f64>
+                                title="Minimum value"
+                                value={min}
+                                oninput={change_float_min}
+                                min={0.0}
+                            />
+                            <FloatInput<f64>
+                                title="Maximum value"
+                                value={max}
+                                oninput={change_float_max}
+                                min={0.0}
+                            />
+                        </>
+                    }
+                } else {
+                    html! {}
+                }
+            }
             <FloatInput<f32>
                 title="Playback time (seconds)"
                 value={props.config.playback_time}
                 oninput={change_playback_time}
                 min={0.0}
             />
+            <Checkbox
+                title="Scale playback time to steps"
+                value={config.proportional_playback}
+                oninput={toggle_proportional_playback}
+            />
             <SelectInput
                 title="Algorithm"
                 options={(*algorithm_names).clone()}
-                selected_value={config.sorting_algorithm.name}
+                selected_value={config.sorting_algorithm.name.clone()}
                 onchange={change_algorithm}
             />
+            {
+                for racer_options.into_iter().map(|racer| html! {
+                    <Checkbox
+                        title={format!("Race {}", racer.name.to_lowercase())}
+                        value={config.race_algorithms.contains(&racer)}
+                        oninput={toggle_racer(racer.clone())}
+                        disabled={racers_full && !config.race_algorithms.contains(&racer)}
+                    />
+                })
+            }
+            <SelectInput
+                title="Visualization"
+                options={SortVisualization::ALL.iter().map(|visualization| visualization.name().to_string()).collect::<Vec<String>>()}
+                selected_value={config.visualization.name().to_string()}
+                onchange={change_visualization}
+            />
+            <SelectInput
+                title="Palette"
+                options={Palette::ALL.iter().map(|palette| palette.name().to_string()).collect::<Vec<String>>()}
+                selected_value={config.palette.name().to_string()}
+                onchange={change_palette}
+            />
+            <SelectInput
+                title="Renderer"
+                options={SortRenderer::ALL.iter().map(|renderer| renderer.name().to_string()).collect::<Vec<String>>()}
+                selected_value={config.renderer.name().to_string()}
+                onchange={change_renderer}
+            />
         </div>
     }
 }
+
+/// Runs with more estimated steps than this ask for confirmation first, since sorting and animating them can freeze the page.
+const LARGE_RUN_STEPS: usize = 1_000_000;
+
+/// Asks whether to go ahead with an input of length `input_len` if the selected algorithm is estimated to take a huge number of steps for it.
+fn confirm_large_run(config: &SortConfig, input_len: usize) -> bool {
+    let complexity = config.sorting_algorithm.complexity;
+    let estimated_steps = complexity.estimate(input_len);
+    if estimated_steps <= LARGE_RUN_STEPS {
+        return true;
+    }
+    window()
+        .unwrap()
+        .confirm_with_message(&format!(
+            "{} is {} on average, so sorting {} items takes around {} steps. Continue?",
+            config.sorting_algorithm.name, complexity, input_len, estimated_steps
+        ))
+        .unwrap_or(false)
+}
@@ -1,4 +1,6 @@
-use sorting::SortCommand;
+use std::f64::consts::TAU;
+
+use sorting::{SortCommand, TotalF64};
 
 // std::time isn't supported on WASM platforms
 use instant::{Duration, Instant};
@@ -10,7 +12,14 @@ use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 use yew::prelude::*;
 use yew_hooks::use_size;
 
-use crate::hooks::use_color_scheme::ColorScheme;
+use crate::{
+    hooks::use_color_scheme::ColorScheme,
+    utils::{
+        palette::Palette,
+        sort_renderer::step_indices,
+        sort_visualization::{item_angle, value_color, SortVisualization},
+    },
+};
 
 #[wasm_bindgen]
 extern "C" {
@@ -22,8 +31,18 @@ const UPDATE_RATE: Duration = Duration::from_millis(50);
 
 #[derive(Properties, PartialEq)]
 pub struct SortGraphProps {
-    pub items: UseStateHandle<Vec<u32>>,
-    pub step: UseStateHandle<Vec<SortCommand<u32>>>,
+    pub items: Vec<TotalF64>,
+    pub step: Vec<SortCommand<TotalF64>>,
+    pub palette: Palette,
+    #[prop_or_default]
+    pub visualization: SortVisualization,
+    /// Pairs of indices whose bars are connected by a faint line, like the inversions that are still left.
+    /// They're only drawn over [`SortVisualization::Bars`].
+    #[prop_or_default]
+    pub inversions: Vec<(usize, usize)>,
+    /// A color for every bar that isn't changed by the step, used instead of the palette's base color when it's not empty.
+    #[prop_or_default]
+    pub colors: Vec<String>,
 }
 
 #[derive(Clone, PartialEq)]
@@ -45,29 +64,24 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
     let _render_timeout_closure: UseStateHandle<Option<Closure<dyn FnMut()>>> = use_state(|| None);
 
     let prev_draw = use_state_eq(Instant::now);
-    let graph_color_scheme = match app_color_scheme {
-        ColorScheme::Light => SortGraphConfig {
-            color_changed: "#059ada".to_string(),
-            color_unchanged: "#7abc05".to_string(),
-        },
-        ColorScheme::Dark => SortGraphConfig {
-            color_changed: "#00aaff".to_string(),
-            color_unchanged: "#adff2f".to_string(),
-        },
+    let graph_color_scheme = SortGraphConfig {
+        color_changed: props.palette.highlight_color(app_color_scheme).to_string(),
+        color_unchanged: props.palette.base_color(app_color_scheme).to_string(),
     };
 
     let draw_bars = {
         let items = props.items.clone();
         let ctx = ctx.clone();
 
-        move |indices: &[usize], max_val: u32, width: f64, canvas_height: f64| {
+        move |indices: &[usize], max_val: f64, width: f64, canvas_height: f64| {
             if let Some(ctx) = ctx.as_ref() {
                 ctx.begin_path();
                 for i in indices.iter() {
                     let i = *i;
-                    let val = items[i] as f64;
+                    // Values that can't be drawn as a bar, like NaN or negative values, get no height
+                    let val = items[i].0.max(0.0);
                     let x = (width * i as f64) + width * 0.5;
-                    let height = val / max_val as f64 * canvas_height;
+                    let height = val / max_val * canvas_height;
                     ctx.move_to(x, canvas_height);
                     ctx.line_to(x, canvas_height - height);
                 }
@@ -80,6 +94,9 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
     let draw = {
         let items = props.items.clone();
         let step = props.step.clone();
+        let inversions = props.inversions.clone();
+        let colors = props.colors.clone();
+        let visualization = props.visualization;
         let canvas = canvas.clone();
         let ctx = ctx.clone();
 
@@ -88,10 +105,26 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
                 if let Some(ctx) = ctx.as_ref() {
                     let canvas_width = canvas.width() as f64;
                     let canvas_height = canvas.height() as f64;
-                    let max_val = match items.iter().max() {
-                        Some(val) => *val,
-                        None => 0,
-                    };
+
+                    if visualization != SortVisualization::Bars {
+                        ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
+                        draw_shapes(
+                            ctx,
+                            visualization,
+                            &items,
+                            &step_indices(&step),
+                            &colors,
+                            &graph_color_scheme,
+                            (canvas_width, canvas_height),
+                        );
+                        return;
+                    }
+
+                    let max_val = items
+                        .iter()
+                        .map(|val| val.0)
+                        .filter(|val| val.is_finite())
+                        .fold(0.0, f64::max);
                     let width = canvas_width / items.len() as f64;
                     let margin = width * 0.1;
                     // Remove margin when it's small enough to avoid problem where some bars have a tiny margin and some don't.
@@ -102,6 +135,7 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
                             !step.iter().any(|command| match command {
                                 SortCommand::Swap(from, to) => from == i || to == i,
                                 SortCommand::Set(index, _) => index == i,
+                                SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => false,
                             })
                         })
                         .to_owned()
@@ -110,8 +144,15 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
                     ctx.clear_rect(0.0, 0.0, canvas_width, canvas_height);
                     ctx.set_line_width(width - margin);
 
-                    set_stroke_style(ctx, graph_color_scheme.color_unchanged);
-                    draw_bars(&unchanged_indices, max_val, width, canvas_height);
+                    if colors.is_empty() {
+                        set_stroke_style(ctx, graph_color_scheme.color_unchanged);
+                        draw_bars(&unchanged_indices, max_val, width, canvas_height);
+                    } else {
+                        for i in unchanged_indices {
+                            set_stroke_style(ctx, colors[i].clone());
+                            draw_bars(&[i], max_val, width, canvas_height);
+                        }
+                    }
 
                     set_stroke_style(ctx, graph_color_scheme.color_changed);
                     draw_bars(
@@ -120,6 +161,7 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
                             .map(|command| match command.to_owned() {
                                 SortCommand::Swap(from, to) => vec![from, to],
                                 SortCommand::Set(index, _) => vec![index],
+                                SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => vec![],
                             })
                             .collect::<Vec<Vec<usize>>>()
                             .concat(),
@@ -127,6 +169,26 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
                         width,
                         canvas_height,
                     );
+
+                    if !inversions.is_empty() {
+                        let top = |i: usize| {
+                            let val = items[i].0.max(0.0);
+                            (
+                                (width * i as f64) + width * 0.5,
+                                canvas_height - val / max_val * canvas_height,
+                            )
+                        };
+                        ctx.set_line_width(1.0);
+                        ctx.set_global_alpha(0.3);
+                        ctx.begin_path();
+                        for (i, j) in inversions.iter() {
+                            let (from, to) = (top(*i), top(*j));
+                            ctx.move_to(from.0, from.1);
+                            ctx.line_to(to.0, to.1);
+                        }
+                        ctx.stroke();
+                        ctx.set_global_alpha(1.0);
+                    }
                 }
             }
         }
@@ -175,7 +237,12 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
                 };
                 || ()
             },
-            (props.items.clone(), props.step.clone()),
+            (
+                props.items.clone(),
+                props.step.clone(),
+                props.inversions.clone(),
+                props.colors.clone(),
+            ),
         );
     }
 
@@ -184,7 +251,12 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
             draw.clone()();
             || ()
         },
-        (canvas_container_size, app_color_scheme),
+        (
+            canvas_container_size,
+            app_color_scheme,
+            props.palette,
+            props.visualization,
+        ),
     );
 
     html! {
@@ -199,6 +271,72 @@ pub fn sort_graph(props: &SortGraphProps) -> Html {
     }
 }
 
+/// Draws the items as the shapes of a [`SortVisualization`] other than [`SortVisualization::Bars`].
+fn draw_shapes(
+    ctx: &CanvasRenderingContext2d,
+    visualization: SortVisualization,
+    items: &[TotalF64],
+    highlighted: &[usize],
+    colors: &[String],
+    graph_color_scheme: &SortGraphConfig,
+    (canvas_width, canvas_height): (f64, f64),
+) {
+    let len = items.len();
+    let max_val = items
+        .iter()
+        .map(|val| val.0)
+        .filter(|val| val.is_finite())
+        .fold(0.0, f64::max);
+    // Values that can't be drawn, like NaN or negative values, are drawn like the smallest value
+    let value = |i: usize| items[i].0.max(0.0) / max_val;
+
+    match visualization {
+        SortVisualization::ColorWheel => {
+            let (center_x, center_y) = (canvas_width * 0.5, canvas_height * 0.5);
+            let radius = canvas_width.min(canvas_height) * 0.5;
+            for i in 0..len {
+                let color = if highlighted.contains(&i) {
+                    graph_color_scheme.color_changed.clone()
+                } else {
+                    value_color(value(i))
+                };
+                ctx.set_fill_style(&JsValue::from_str(&color));
+                ctx.begin_path();
+                ctx.move_to(center_x, center_y);
+                ctx.arc(
+                    center_x,
+                    center_y,
+                    radius,
+                    item_angle(i, len),
+                    item_angle(i + 1, len),
+                )
+                .unwrap();
+                ctx.close_path();
+                ctx.fill();
+            }
+        }
+        _ => {
+            // The dots get smaller for longer inputs, but not too small to see
+            let radius = (canvas_width.min(canvas_height) / len as f64).clamp(1.5, 6.0);
+            for i in 0..len {
+                let color = if highlighted.contains(&i) {
+                    &graph_color_scheme.color_changed
+                } else if colors.is_empty() {
+                    &graph_color_scheme.color_unchanged
+                } else {
+                    &colors[i]
+                };
+                let (x, y) =
+                    visualization.dot_position(i, len, value(i), (canvas_width, canvas_height));
+                ctx.set_fill_style(&JsValue::from_str(color));
+                ctx.begin_path();
+                ctx.arc(x, y, radius, 0.0, TAU).unwrap();
+                ctx.fill();
+            }
+        }
+    }
+}
+
 fn set_stroke_style(ctx: &CanvasRenderingContext2d, stroke_style: String) {
     ctx.set_stroke_style(&JsValue::from_str(&stroke_style));
 }
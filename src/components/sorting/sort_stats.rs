@@ -0,0 +1,57 @@
+use sorting::SortStats;
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct SortStatsPanelProps {
+    /// The work done by the steps up to the active step.
+    pub so_far: SortStats,
+    /// The work done by the whole run.
+    pub total: SortStats,
+    /// Whether the comparisons were counted at every step. Comparisons aren't part of the steps,
+    /// so an imported run only knows them and the reads that they cause for the whole run.
+    #[prop_or(true)]
+    pub comparisons_so_far: bool,
+}
+
+/// Shows how many comparisons, swaps, writes and reads the algorithm did.
+#[function_component(SortStatsPanel)]
+pub fn sort_stats_panel(props: &SortStatsPanelProps) -> Html {
+    let SortStatsPanelProps {
+        so_far,
+        total,
+        comparisons_so_far,
+    } = props.clone();
+    let rows = [
+        (
+            "Comparisons",
+            Some(so_far.comparisons).filter(|_| comparisons_so_far),
+            total.comparisons,
+        ),
+        ("Swaps", Some(so_far.swaps), total.swaps),
+        ("Writes", Some(so_far.writes), total.writes),
+        (
+            "Reads",
+            Some(so_far.reads).filter(|_| comparisons_so_far),
+            total.reads,
+        ),
+    ];
+
+    html! {
+        <table class="sort-stats">
+            <tr>
+                <th></th>
+                <th>{"So far"}</th>
+                <th>{"Total"}</th>
+            </tr>
+            {
+                for rows.iter().map(|(name, so_far, total)| html! {
+                    <tr>
+                        <th>{ name }</th>
+                        <td>{ so_far.map_or_else(|| "-".to_string(), |so_far| so_far.to_string()) }</td>
+                        <td>{ total }</td>
+                    </tr>
+                })
+            }
+        </table>
+    }
+}
@@ -90,6 +90,26 @@ pub fn step_slider(props: &StepSliderProps) -> Html {
         })
     };
 
+    // Moves a single step back or forward, pausing the playback so that the step can be looked at.
+    let step_by = {
+        let on_change = on_change.clone();
+        let interval_ms = interval_ms.clone();
+
+        move |forward: bool| {
+            let on_change = on_change.clone();
+            let interval_ms = interval_ms.clone();
+
+            Callback::from(move |_| {
+                interval_ms.set(0);
+                if forward {
+                    on_change.emit((active_step_index + 1).min(max));
+                } else {
+                    on_change.emit(active_step_index.saturating_sub(1));
+                }
+            })
+        }
+    };
+
     let play_step_player = {
         let interval_ms = interval_ms.clone();
 
@@ -124,7 +144,9 @@ pub fn step_slider(props: &StepSliderProps) -> Html {
                 }
             }
             <div class="step-slider">
+                { step_button(step_by(false), false, disabled) }
                 { playback_button(on_click_playback_button, playing) }
+                { step_button(step_by(true), true, disabled) }
                 <input
                     type="range"
                     id={format!("stepSlider{}", label.clone())}
@@ -139,6 +161,26 @@ pub fn step_slider(props: &StepSliderProps) -> Html {
     }
 }
 
+fn step_button(onclick: Callback<MouseEvent>, forward: bool, disabled: bool) -> Html {
+    // The back button is the forward button mirrored horizontally
+    let transform = if forward {
+        ""
+    } else {
+        "scale(-1 1) translate(-600 0)"
+    };
+
+    html! {
+        <button aria-label={if forward { "Step forward" } else { "Step back" }} {onclick} {disabled}>
+            <svg width="600" height="586" viewBox="0 0 600 586" fill="none" xmlns="http://www.w3.org/2000/svg">
+                <g transform={transform}>
+                    <path d="M412.125 267.275L60.4349 20.2609C40.4391 8.26348 15 22.6669 15 45.9857V540.014C15 563.333 40.4391 577.737 60.4348 565.739L412.125 318.725C431.546 307.073 431.546 278.927 412.125 267.275Z" fill="var(--text-color)" stroke="var(--text-color)" stroke-width="30" stroke-linejoin="round"/>
+                    <rect x="475" y="15" width="110" height="556" rx="30" fill="var(--text-color)"/>
+                </g>
+            </svg>
+        </button>
+    }
+}
+
 fn playback_button(onclick: Callback<MouseEvent>, playing: bool) -> Html {
     html! {
         <button aria-label="Step playback button" {onclick}>
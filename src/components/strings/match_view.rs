@@ -0,0 +1,133 @@
+use string_algorithms::{MatchState, MatchStep};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct MatchViewProps {
+    pub text: Vec<char>,
+    pub pattern: Vec<char>,
+    /// What the search has found out so far.
+    pub state: MatchState,
+    /// The step that was just taken, whose characters are highlighted.
+    #[prop_or_default]
+    pub step: Option<MatchStep>,
+}
+
+/// Draws the pattern under the text where the search has lined it up, with the compared characters marked as matched or mismatched.\
+/// The failure function of KMP and the hashes of Rabin-Karp are drawn below them once the search has computed them.
+#[function_component(MatchView)]
+pub fn match_view(props: &MatchViewProps) -> Html {
+    let MatchViewProps {
+        text,
+        pattern,
+        state,
+        step,
+    } = props;
+    let shift = state.shift.unwrap_or_default();
+    let compared = |pattern_index: usize| {
+        state
+            .compared
+            .iter()
+            .find(|(index, _)| *index == pattern_index)
+            .map(|(_, matched)| if *matched { "matched" } else { "mismatched" })
+    };
+    let is_current = |text_index: Option<usize>, pattern_index: Option<usize>| match step {
+        Some(MatchStep::Compare(step_text_index, step_pattern_index, _)) => {
+            text_index == Some(*step_text_index) || pattern_index == Some(*step_pattern_index)
+        }
+        Some(MatchStep::Failure(step_pattern_index, _)) => {
+            pattern_index == Some(*step_pattern_index)
+        }
+        _ => false,
+    };
+
+    let indices = (0..text.len())
+        .map(|i| html! { <span class="match-index">{ i }</span> })
+        .collect::<Html>();
+
+    let text_cells = text
+        .iter()
+        .enumerate()
+        .map(|(i, character)| {
+            let in_window = state.shift.is_some() && (shift..shift + pattern.len()).contains(&i);
+            let found = state
+                .matches
+                .iter()
+                .any(|found| (*found..found + pattern.len()).contains(&i));
+            let class = classes!(
+                "match-cell",
+                in_window.then_some("window"),
+                in_window.then(|| compared(i - shift)).flatten(),
+                found.then_some("found"),
+                is_current(Some(i), None).then_some("current"),
+            );
+            html! { <span {class}>{ character }</span> }
+        })
+        .collect::<Html>();
+
+    let pattern_cells = pattern
+        .iter()
+        .enumerate()
+        .map(|(i, character)| {
+            let class = classes!(
+                "match-cell",
+                compared(i),
+                state.shift.is_none().then_some("unaligned"),
+                (state.shift.is_some() && is_current(None, Some(i))).then_some("current"),
+            );
+            html! {
+                <span {class} style={format!("grid-column: {}", shift + i + 1)}>{ character }</span>
+            }
+        })
+        .collect::<Html>();
+
+    let failure = if state.failure.is_empty() {
+        html! {}
+    } else {
+        let failure_cells = pattern
+            .iter()
+            .enumerate()
+            .map(|(i, character)| {
+                let class = classes!(
+                    "match-cell",
+                    (state.shift.is_none() && is_current(None, Some(i))).then_some("current"),
+                );
+                html! {
+                    <span {class}>
+                        { character }
+                        <span class="failure-value">
+                            { state.failure.get(i).map(|length| length.to_string()).unwrap_or_default() }
+                        </span>
+                    </span>
+                }
+            })
+            .collect::<Html>();
+        html! {
+            <div class="failure-function">
+                <span>{ "Failure function" }</span>
+                <div class="match-row" style={format!("--columns: {}", pattern.len())}>{ failure_cells }</div>
+            </div>
+        }
+    };
+
+    let hashes = match state.hashes {
+        Some((window_hash, pattern_hash)) => html! {
+            <p class={classes!("match-hashes", (window_hash == pattern_hash).then_some("hit"))}>
+                { format!("Window hash {}, pattern hash {}", window_hash, pattern_hash) }
+            </p>
+        },
+        None => html! {},
+    };
+
+    let columns = format!("--columns: {}", text.len().max(1));
+    html! {
+        <div class="match-view">
+            <div class="match-grid">
+                <div class="match-row" style={columns.clone()}>{ indices }</div>
+                <div class="match-row" style={columns.clone()}>{ text_cells }</div>
+                <div class="match-row" style={columns}>{ pattern_cells }</div>
+            </div>
+            { hashes }
+            { failure }
+        </div>
+    }
+}
@@ -0,0 +1,519 @@
+//! A binary search tree that records its operations so that they can be visualized.
+use std::fmt;
+
+/// The order in which a traversal of a [`BinarySearchTree`] outputs the values of the nodes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TraversalOrder {
+    /// The left subtree, then the node, then the right subtree, which gives the values in sorted order.
+    #[default]
+    InOrder,
+    /// The node before its subtrees, e.g. for copying the tree.
+    PreOrder,
+    /// The subtrees before the node, e.g. for deleting the tree.
+    PostOrder,
+}
+
+impl TraversalOrder {
+    pub const ALL: [TraversalOrder; 3] = [
+        TraversalOrder::InOrder,
+        TraversalOrder::PreOrder,
+        TraversalOrder::PostOrder,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TraversalOrder::InOrder => "In-order",
+            TraversalOrder::PreOrder => "Pre-order",
+            TraversalOrder::PostOrder => "Post-order",
+        }
+    }
+}
+
+/// A step of an operation on a [`BinarySearchTree`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum BstStep<T> {
+    /// Compare with a node on the way down the tree, or arrive at a node during a traversal: `(node_value)`
+    Visit(T),
+    /// The searched value is in the tree: `(value)`
+    Found(T),
+    /// The search ran out of nodes, so the value isn't in the tree: `(value)`
+    NotFound(T),
+    /// Add a leaf: `(value)`
+    Insert(T),
+    /// Remove a node with at most one child, which takes its place: `(value)`
+    Remove(T),
+    /// Remove a node with two children by moving the value of its in-order successor into it: `(value, successor)`
+    Replace(T, T),
+    /// Output a value during a traversal: `(value)`
+    Output(T),
+}
+
+impl<T> BstStep<T> {
+    /// Gets the value of the node that the step is about. For [`BstStep::Replace`], this is the node whose value is replaced.
+    pub fn value(&self) -> &T {
+        match self {
+            BstStep::Visit(value)
+            | BstStep::Found(value)
+            | BstStep::NotFound(value)
+            | BstStep::Insert(value)
+            | BstStep::Remove(value)
+            | BstStep::Replace(value, _)
+            | BstStep::Output(value) => value,
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for BstStep<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BstStep::Visit(value) => write!(f, "Visiting {}", value),
+            BstStep::Found(value) => write!(f, "Found {}", value),
+            BstStep::NotFound(value) => write!(f, "{} is not in the tree", value),
+            BstStep::Insert(value) => write!(f, "Inserting {} as a leaf", value),
+            BstStep::Remove(value) => write!(f, "Removing {}", value),
+            BstStep::Replace(value, successor) => write!(
+                f,
+                "Replacing {} with its in-order successor {}",
+                value, successor
+            ),
+            BstStep::Output(value) => write!(f, "Output {}", value),
+        }
+    }
+}
+
+/// Runs recorded tree operations on a tree, like `run_heap_steps` of the sorting crate does for heaps.
+/// Only the steps that change the tree do something, and they aren't recorded again.
+pub fn run_bst_steps<T: Clone + Ord>(tree: &mut BinarySearchTree<T>, steps: &[BstStep<T>]) {
+    let recorded = tree.steps.len();
+    for step in steps {
+        match step {
+            BstStep::Insert(value) => {
+                tree.insert(value.clone());
+            }
+            BstStep::Remove(value) | BstStep::Replace(value, _) => {
+                tree.remove(value);
+            }
+            _ => {}
+        }
+    }
+    tree.steps.truncate(recorded);
+}
+
+/// Where a node of a [`BinarySearchTree`] is drawn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeLayout<T> {
+    pub value: T,
+    /// The index of the node in sorted order, so that every node gets its own column and no edges cross.
+    pub column: usize,
+    /// The number of edges from the root to the node.
+    pub depth: usize,
+    pub parent: Option<T>,
+}
+
+type Link<T> = Option<Box<Node<T>>>;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Node<T> {
+    value: T,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+/// A binary search tree without duplicates, where the values in the left subtree of every node are smaller
+/// than the node's value and the values in its right subtree are larger.
+///
+/// The tree isn't balanced, so inserting values in sorted order makes it a list.
+/// Every node that an operation visits is recorded as a step, so the operations can be replayed with [`run_bst_steps`]
+/// to visualize how searching, inserting, deleting and traversing work.
+///
+/// ## Example
+///
+/// ```rust
+/// use data_structures::{run_bst_steps, BinarySearchTree, TraversalOrder};
+///
+/// let mut tree = BinarySearchTree::new();
+/// for value in [5, 2, 8, 1] {
+///     tree.insert(value);
+/// }
+/// assert!(tree.remove(&5));
+/// assert_eq!(tree.traverse(TraversalOrder::PreOrder), vec![8, 2, 1]);
+///
+/// let mut replayed = BinarySearchTree::new();
+/// run_bst_steps(&mut replayed, tree.steps());
+/// assert_eq!(replayed.values(), tree.values());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinarySearchTree<T: Clone + Ord> {
+    root: Link<T>,
+    len: usize,
+    steps: Vec<BstStep<T>>,
+}
+
+impl<T: Clone + Ord> Default for BinarySearchTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Ord> BinarySearchTree<T> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            steps: vec![],
+        }
+    }
+    /// Adds `value` as a new leaf where the search for it ends, or returns `false` if it's already in the tree.
+    pub fn insert(&mut self, value: T) -> bool {
+        let inserted = insert(&mut self.root, value, &mut self.steps);
+        if inserted {
+            self.len += 1;
+        }
+        inserted
+    }
+    /// Removes `value`, or returns `false` if it isn't in the tree.\
+    /// A node with two children takes the value of its in-order successor, the smallest node of its right subtree,
+    /// which is removed instead since it has at most one child.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let removed = remove(&mut self.root, value, &mut self.steps);
+        if removed {
+            self.len -= 1;
+        }
+        removed
+    }
+    /// Searches for `value` from the root, recording the nodes that it's compared with. See [`BinarySearchTree::contains`]
+    /// for searching without recording.
+    pub fn search(&mut self, value: &T) -> bool {
+        let found = find(&mut self.root, value, &mut self.steps).is_some();
+        self.steps.push(if found {
+            BstStep::Found(value.clone())
+        } else {
+            BstStep::NotFound(value.clone())
+        });
+        found
+    }
+    /// Gets the values in the given `order`, recording every node when it's first arrived at and when its value is output.
+    pub fn traverse(&mut self, order: TraversalOrder) -> Vec<T> {
+        let mut values = vec![];
+        traverse(&self.root, order, &mut values, &mut self.steps);
+        values
+    }
+    pub fn contains(&self, value: &T) -> bool {
+        let mut link = &self.root;
+        while let Some(node) = link {
+            link = match value.cmp(&node.value) {
+                std::cmp::Ordering::Less => &node.left,
+                std::cmp::Ordering::Greater => &node.right,
+                std::cmp::Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+    /// Gets the values in sorted order without recording anything.
+    pub fn values(&self) -> Vec<T> {
+        let mut values = vec![];
+        traverse(
+            &self.root,
+            TraversalOrder::InOrder,
+            &mut values,
+            &mut vec![],
+        );
+        values
+    }
+    pub fn root(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.value)
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Gets the number of levels of the tree, which is 0 for an empty tree and 1 for just the root.
+    pub fn height(&self) -> usize {
+        fn height<T>(link: &Link<T>) -> usize {
+            link.as_ref()
+                .map_or(0, |node| 1 + height(&node.left).max(height(&node.right)))
+        }
+        height(&self.root)
+    }
+    /// Positions the nodes for drawing the tree, with the root at the top and the values growing from left to right.
+    /// The nodes are listed in sorted order.
+    pub fn layout(&self) -> Vec<NodeLayout<T>> {
+        fn layout<T: Clone>(
+            link: &Link<T>,
+            depth: usize,
+            parent: Option<&T>,
+            nodes: &mut Vec<NodeLayout<T>>,
+        ) {
+            if let Some(node) = link {
+                layout(&node.left, depth + 1, Some(&node.value), nodes);
+                nodes.push(NodeLayout {
+                    value: node.value.clone(),
+                    column: nodes.len(),
+                    depth,
+                    parent: parent.cloned(),
+                });
+                layout(&node.right, depth + 1, Some(&node.value), nodes);
+            }
+        }
+        let mut nodes = vec![];
+        layout(&self.root, 0, None, &mut nodes);
+        nodes
+    }
+    /// Gets the steps of every operation so far.
+    pub fn steps(&self) -> &[BstStep<T>] {
+        &self.steps
+    }
+    /// Removes and returns the recorded steps, e.g. after they have been visualized.
+    pub fn take_steps(&mut self) -> Vec<BstStep<T>> {
+        std::mem::take(&mut self.steps)
+    }
+}
+
+/// Finds the link that holds `value`, or the empty link where it would be added, recording the nodes on the way.
+fn find<'a, T: Clone + Ord>(
+    mut link: &'a mut Link<T>,
+    value: &T,
+    steps: &mut Vec<BstStep<T>>,
+) -> &'a mut Link<T> {
+    loop {
+        let ordering = match link {
+            Some(node) => {
+                steps.push(BstStep::Visit(node.value.clone()));
+                value.cmp(&node.value)
+            }
+            None => return link,
+        };
+        if ordering == std::cmp::Ordering::Equal {
+            return link;
+        }
+        let node = link.as_mut().unwrap();
+        link = if ordering == std::cmp::Ordering::Less {
+            &mut node.left
+        } else {
+            &mut node.right
+        };
+    }
+}
+
+fn insert<T: Clone + Ord>(root: &mut Link<T>, value: T, steps: &mut Vec<BstStep<T>>) -> bool {
+    let link = find(root, &value, steps);
+    if link.is_some() {
+        steps.push(BstStep::Found(value));
+        return false;
+    }
+    *link = Some(Box::new(Node {
+        value: value.clone(),
+        left: None,
+        right: None,
+    }));
+    steps.push(BstStep::Insert(value));
+    true
+}
+
+fn remove<T: Clone + Ord>(root: &mut Link<T>, value: &T, steps: &mut Vec<BstStep<T>>) -> bool {
+    let link = find(root, value, steps);
+    let node = match link.take() {
+        Some(node) => node,
+        None => {
+            steps.push(BstStep::NotFound(value.clone()));
+            return false;
+        }
+    };
+
+    match (node.left, node.right) {
+        (Some(left), Some(right)) => {
+            let mut right = Some(right);
+            let successor = take_min(&mut right, steps);
+            steps.push(BstStep::Replace(node.value, successor.clone()));
+            *link = Some(Box::new(Node {
+                value: successor,
+                left: Some(left),
+                right,
+            }));
+        }
+        (child, None) | (None, child) => {
+            steps.push(BstStep::Remove(node.value));
+            *link = child;
+        }
+    }
+    true
+}
+
+/// Removes the smallest node of a non-empty subtree and returns its value, recording the nodes on the way down to it.
+/// The right child of the smallest node takes its place.
+fn take_min<T: Clone>(mut link: &mut Link<T>, steps: &mut Vec<BstStep<T>>) -> T {
+    loop {
+        let node = link.as_mut().unwrap();
+        steps.push(BstStep::Visit(node.value.clone()));
+        if node.left.is_none() {
+            break;
+        }
+        link = &mut link.as_mut().unwrap().left;
+    }
+    let node = link.take().unwrap();
+    *link = node.right;
+    node.value
+}
+
+fn traverse<T: Clone>(
+    link: &Link<T>,
+    order: TraversalOrder,
+    values: &mut Vec<T>,
+    steps: &mut Vec<BstStep<T>>,
+) {
+    let node = match link {
+        Some(node) => node,
+        None => return,
+    };
+    let output = |values: &mut Vec<T>, steps: &mut Vec<BstStep<T>>| {
+        values.push(node.value.clone());
+        steps.push(BstStep::Output(node.value.clone()));
+    };
+
+    steps.push(BstStep::Visit(node.value.clone()));
+    if order == TraversalOrder::PreOrder {
+        output(values, steps);
+    }
+    traverse(&node.left, order, values, steps);
+    if order == TraversalOrder::InOrder {
+        output(values, steps);
+    }
+    traverse(&node.right, order, values, steps);
+    if order == TraversalOrder::PostOrder {
+        output(values, steps);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_search_tree<T: Clone + Ord>(tree: &BinarySearchTree<T>) -> bool {
+        let values = tree.values();
+        values.len() == tree.len() && values.windows(2).all(|pair| pair[0] < pair[1])
+    }
+
+    ///       5
+    ///     /   \
+    ///    2     8
+    ///   / \   /
+    ///  1   4 7
+    ///     /
+    ///    3
+    fn tree() -> BinarySearchTree<i32> {
+        let mut tree = BinarySearchTree::new();
+        for value in [5, 2, 8, 1, 4, 7, 3] {
+            assert!(tree.insert(value));
+        }
+        tree.take_steps();
+        tree
+    }
+
+    #[test]
+    fn traversal_orders() {
+        let mut tree = tree();
+        assert_eq!(
+            tree.traverse(TraversalOrder::InOrder),
+            vec![1, 2, 3, 4, 5, 7, 8]
+        );
+        assert_eq!(
+            tree.traverse(TraversalOrder::PreOrder),
+            vec![5, 2, 1, 4, 3, 8, 7]
+        );
+        assert_eq!(
+            tree.traverse(TraversalOrder::PostOrder),
+            vec![1, 3, 4, 2, 7, 8, 5]
+        );
+        // Every node is visited and output once per traversal
+        assert_eq!(tree.steps().len(), 3 * 2 * 7);
+        assert_eq!(tree.height(), 4);
+    }
+
+    #[test]
+    fn insert_search_and_remove_record_their_paths() {
+        let mut tree = tree();
+
+        assert!(!tree.insert(4));
+        assert!(tree.search(&3));
+        assert!(!tree.search(&6));
+        assert_eq!(
+            tree.take_steps(),
+            vec![
+                BstStep::Visit(5),
+                BstStep::Visit(2),
+                BstStep::Visit(4),
+                BstStep::Found(4),
+                BstStep::Visit(5),
+                BstStep::Visit(2),
+                BstStep::Visit(4),
+                BstStep::Visit(3),
+                BstStep::Found(3),
+                BstStep::Visit(5),
+                BstStep::Visit(8),
+                BstStep::Visit(7),
+                BstStep::NotFound(6),
+            ]
+        );
+
+        // 2 has two children, so its successor 3 moves into it
+        assert!(tree.remove(&2));
+        assert_eq!(
+            tree.take_steps(),
+            vec![
+                BstStep::Visit(5),
+                BstStep::Visit(2),
+                BstStep::Visit(4),
+                BstStep::Visit(3),
+                BstStep::Replace(2, 3),
+            ]
+        );
+        assert!(tree.remove(&8));
+        assert!(!tree.remove(&8));
+        assert!(is_search_tree(&tree));
+        assert_eq!(tree.values(), vec![1, 3, 4, 5, 7]);
+        assert_eq!(tree.traverse(TraversalOrder::PreOrder), vec![5, 3, 1, 4, 7]);
+    }
+
+    #[test]
+    fn replaying_steps_gives_the_same_tree() {
+        let mut tree = BinarySearchTree::new();
+        // A fixed pseudo-random sequence with repeats
+        let values: Vec<i32> = (0..60).map(|i| (i * 37 + 11) % 41).collect();
+        for (i, value) in values.iter().enumerate() {
+            if i % 3 == 2 {
+                tree.remove(&values[i / 2]);
+            } else {
+                tree.insert(*value);
+            }
+            assert!(is_search_tree(&tree));
+        }
+        tree.traverse(TraversalOrder::PostOrder);
+
+        let mut replayed = BinarySearchTree::new();
+        run_bst_steps(&mut replayed, tree.steps());
+        replayed.steps = tree.steps.clone();
+        assert_eq!(replayed, tree);
+    }
+
+    #[test]
+    fn layout_columns_are_sorted() {
+        let layout = tree().layout();
+        assert_eq!(
+            layout.iter().map(|node| node.value).collect::<Vec<i32>>(),
+            vec![1, 2, 3, 4, 5, 7, 8]
+        );
+        assert!(layout.iter().enumerate().all(|(i, node)| node.column == i));
+        assert_eq!(
+            layout[2],
+            NodeLayout {
+                value: 3,
+                column: 2,
+                depth: 3,
+                parent: Some(4)
+            }
+        );
+        assert_eq!(layout[4].parent, None);
+    }
+}
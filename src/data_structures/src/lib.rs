@@ -0,0 +1,2 @@
+pub mod binary_search_tree;
+pub use binary_search_tree::*;
@@ -0,0 +1,66 @@
+use crate::{orientation, HullStep, Orientation, Point};
+
+/// Sorts the points by their angle around the lowest one and walks through them in that order,
+/// removing points from the end of the hull for as long as the hull would turn clockwise at them.\
+/// This takes *O(n log n)* time for *n* points, which is dominated by the sort.
+///
+/// ## Example
+///
+/// ```rust
+/// use geometry::{graham_scan, Point};
+///
+/// let points = [
+///     Point::new(0.0, 0.0),
+///     Point::new(1.0, 1.0),
+///     Point::new(2.0, 0.0),
+///     Point::new(1.0, 3.0),
+/// ];
+///
+/// assert_eq!(graham_scan(&points, &mut vec![]), vec![0, 2, 3]);
+/// ```
+pub fn graham_scan(points: &[Point], steps: &mut Vec<HullStep>) -> Vec<usize> {
+    let Some(start) = (0..points.len()).min_by(|a, b| {
+        let (a, b) = (points[*a], points[*b]);
+        a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+    }) else {
+        return vec![];
+    };
+    steps.push(HullStep::Start(start));
+    let pivot = points[start];
+
+    // Copies of the start have no angle around it
+    let mut order = (0..points.len())
+        .filter(|i| points[*i] != pivot)
+        .collect::<Vec<usize>>();
+    let angle = |point: Point| (point.y - pivot.y).atan2(point.x - pivot.x);
+    order.sort_by(|a, b| {
+        let (a, b) = (points[*a], points[*b]);
+        angle(a).total_cmp(&angle(b)).then(
+            pivot
+                .distance_squared(a)
+                .total_cmp(&pivot.distance_squared(b)),
+        )
+    });
+    steps.push(HullStep::Sort(order.clone()));
+
+    let mut hull = vec![start];
+    for i in order {
+        while hull.len() >= 2 {
+            let (previous, last) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+            steps.push(HullStep::Candidate(last, i));
+            if orientation(points[previous], points[last], points[i])
+                == Orientation::CounterClockwise
+            {
+                break;
+            }
+            steps.push(HullStep::Discard(last));
+            hull.pop();
+        }
+        if hull.len() == 1 {
+            steps.push(HullStep::Candidate(start, i));
+        }
+        steps.push(HullStep::Push(i));
+        hull.push(i);
+    }
+    hull
+}
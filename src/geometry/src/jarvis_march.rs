@@ -0,0 +1,61 @@
+use crate::{orientation, HullStep, Orientation, Point};
+
+/// Wraps the points like a gift, starting from the leftmost one and always picking the next point so that every other point is on the left of the edge to it.\
+/// This takes *O(nh)* time for *n* points, where *h* is the number of points on the hull.
+///
+/// ## Example
+///
+/// ```rust
+/// use geometry::{jarvis_march, Point};
+///
+/// let points = [
+///     Point::new(0.0, 0.0),
+///     Point::new(1.0, 1.0),
+///     Point::new(2.0, 0.0),
+///     Point::new(1.0, 3.0),
+/// ];
+///
+/// assert_eq!(jarvis_march(&points, &mut vec![]), vec![0, 2, 3]);
+/// ```
+pub fn jarvis_march(points: &[Point], steps: &mut Vec<HullStep>) -> Vec<usize> {
+    let Some(start) = (0..points.len()).min_by(|a, b| {
+        let (a, b) = (points[*a], points[*b]);
+        a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y))
+    }) else {
+        return vec![];
+    };
+    steps.push(HullStep::Start(start));
+
+    let mut hull = vec![start];
+    let mut current = start;
+    // The hull can't have more points than there are, even if rounding makes the points look like they turn both ways
+    while hull.len() < points.len() {
+        // Copies of the current point don't make an edge with it
+        let mut others = (0..points.len()).filter(|i| points[*i] != points[current]);
+        let Some(mut best) = others.next() else {
+            break;
+        };
+        steps.push(HullStep::Candidate(current, best));
+
+        for i in others {
+            let turn = orientation(points[current], points[best], points[i]);
+            // A point on the right of the edge, or further along it, makes a better edge
+            let further = points[current].distance_squared(points[i])
+                > points[current].distance_squared(points[best]);
+            if turn == Orientation::Clockwise || (turn == Orientation::Collinear && further) {
+                best = i;
+                steps.push(HullStep::Candidate(current, best));
+            } else {
+                steps.push(HullStep::Reject(i));
+            }
+        }
+
+        if points[best] == points[start] {
+            break;
+        }
+        steps.push(HullStep::Push(best));
+        hull.push(best);
+        current = best;
+    }
+    hull
+}
@@ -0,0 +1,248 @@
+//! Computational geometry algorithms on points in the plane that record their steps so that they can be visualized.
+pub mod graham_scan;
+pub mod jarvis_march;
+
+pub use graham_scan::*;
+pub use jarvis_march::*;
+
+use std::fmt;
+
+/// A point in the plane, where `y` grows upwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+    pub fn distance_squared(&self, other: Point) -> f64 {
+        (self.x - other.x).powi(2) + (self.y - other.y).powi(2)
+    }
+}
+
+/// Which way a path through three points turns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    /// The points are on the same line.
+    Collinear,
+}
+
+/// Tells which way the path from `a` through `b` to `c` turns from the sign of the cross product of `b - a` and `c - a`.
+///
+/// ## Example
+///
+/// ```rust
+/// use geometry::{orientation, Orientation, Point};
+///
+/// let (a, b) = (Point::new(0.0, 0.0), Point::new(1.0, 0.0));
+///
+/// assert_eq!(orientation(a, b, Point::new(1.0, 1.0)), Orientation::CounterClockwise);
+/// assert_eq!(orientation(a, b, Point::new(1.0, -1.0)), Orientation::Clockwise);
+/// assert_eq!(orientation(a, b, Point::new(2.0, 0.0)), Orientation::Collinear);
+/// ```
+pub fn orientation(a: Point, b: Point, c: Point) -> Orientation {
+    let cross = (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+    if cross > 0.0 {
+        Orientation::CounterClockwise
+    } else if cross < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+/// A step of building a convex hull, where the points are referred to by their indices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HullStep {
+    /// The hull starts from a point that is surely on it: `(index)`
+    Start(usize),
+    /// Graham scan visits the other points in this order, by their angle around the start.
+    Sort(Vec<usize>),
+    /// Consider the edge from a point of the hull to another point: `(from, to)`
+    Candidate(usize, usize),
+    /// Add a point to the end of the hull: `(index)`
+    Push(usize),
+    /// Graham scan removes the last point of the hull, since the hull would turn clockwise at it: `(index)`
+    Discard(usize),
+    /// Gift wrapping keeps the candidate edge, since a point is on its left: `(index)`
+    Reject(usize),
+}
+
+impl fmt::Display for HullStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HullStep::Start(index) => write!(f, "Starting the hull from point {}", index),
+            HullStep::Sort(order) => write!(
+                f,
+                "Sorting the other {} points by their angle around the start",
+                order.len()
+            ),
+            HullStep::Candidate(from, to) => {
+                write!(f, "Trying the edge from point {} to point {}", from, to)
+            }
+            HullStep::Push(index) => write!(f, "Adding point {} to the hull", index),
+            HullStep::Discard(index) => write!(
+                f,
+                "Removing point {} from the hull, since the hull would turn clockwise at it",
+                index
+            ),
+            HullStep::Reject(index) => write!(
+                f,
+                "Point {} is on the left of the edge, so the edge is kept",
+                index
+            ),
+        }
+    }
+}
+
+/// What a convex hull algorithm has built after some of its steps.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HullState {
+    /// The points of the hull so far, in counterclockwise order.
+    pub hull: Vec<usize>,
+    /// The order that Graham scan visits the points in, once it has sorted them.
+    pub order: Vec<usize>,
+    /// The edge being considered.
+    pub candidate: Option<(usize, usize)>,
+    /// The points that Graham scan has removed from the hull.
+    pub discarded: Vec<usize>,
+    /// The point that gift wrapping last rejected, until the next candidate.
+    pub rejected: Option<usize>,
+}
+
+impl HullState {
+    /// Plays `steps` from the start of building a hull.
+    pub fn after(steps: &[HullStep]) -> Self {
+        let mut state = Self::default();
+        for step in steps {
+            match step {
+                HullStep::Start(index) => state.hull = vec![*index],
+                HullStep::Sort(order) => state.order = order.clone(),
+                HullStep::Candidate(from, to) => {
+                    state.candidate = Some((*from, *to));
+                    state.rejected = None;
+                }
+                HullStep::Push(index) => {
+                    state.hull.push(*index);
+                    state.candidate = None;
+                }
+                HullStep::Discard(index) => {
+                    state.hull.pop();
+                    state.discarded.push(*index);
+                }
+                HullStep::Reject(index) => state.rejected = Some(*index),
+            }
+        }
+        state
+    }
+}
+
+/// Finds the indices of the points on the convex hull of `points` in counterclockwise order, recording its steps.\
+/// Points on the edges of the hull are left out.
+pub type HullFn = fn(&[Point], &mut Vec<HullStep>) -> Vec<usize>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HullAlgorithm {
+    GrahamScan,
+    JarvisMarch,
+}
+
+impl HullAlgorithm {
+    pub const ALL: [HullAlgorithm; 2] = [HullAlgorithm::GrahamScan, HullAlgorithm::JarvisMarch];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HullAlgorithm::GrahamScan => "Graham scan",
+            HullAlgorithm::JarvisMarch => "Gift wrapping (Jarvis march)",
+        }
+    }
+    pub fn hull_fn(&self) -> HullFn {
+        match self {
+            HullAlgorithm::GrahamScan => graham_scan,
+            HullAlgorithm::JarvisMarch => jarvis_march,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_points(seed: &mut u64, count: usize) -> Vec<Point> {
+        let mut random = || {
+            *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            // Coarse coordinates make collinear and repeated points common
+            ((*seed >> 33) % 20) as f64
+        };
+        (0..count).map(|_| Point::new(random(), random())).collect()
+    }
+
+    #[test]
+    fn algorithms_find_the_same_convex_hull() {
+        let mut seed = 3;
+        for count in 3..60 {
+            let points = random_points(&mut seed, count);
+            let mut hulls = HullAlgorithm::ALL.iter().map(|algorithm| {
+                let mut steps = vec![];
+                let hull = algorithm.hull_fn()(&points, &mut steps);
+                assert_eq!(HullState::after(&steps).hull, hull, "{}", algorithm.name());
+                hull
+            });
+            let hull = hulls.next().unwrap();
+
+            // Every point is inside or on the hull, which only turns counterclockwise
+            if hull.len() >= 3 {
+                for (i, from) in hull.iter().enumerate() {
+                    let to = hull[(i + 1) % hull.len()];
+                    let next = hull[(i + 2) % hull.len()];
+                    assert_eq!(
+                        orientation(points[*from], points[to], points[next]),
+                        Orientation::CounterClockwise
+                    );
+                    for point in &points {
+                        assert_ne!(
+                            orientation(points[*from], points[to], *point),
+                            Orientation::Clockwise
+                        );
+                    }
+                }
+            }
+
+            // The algorithms can start from different points and pick different copies of a repeated point
+            let corners = |hull: &[usize]| {
+                let mut corners = hull
+                    .iter()
+                    .map(|i| (points[*i].x as i64, points[*i].y as i64))
+                    .collect::<Vec<_>>();
+                corners.sort_unstable();
+                corners
+            };
+            for other_hull in hulls {
+                assert_eq!(corners(&other_hull), corners(&hull));
+            }
+        }
+    }
+
+    #[test]
+    fn points_on_edges_are_left_out() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.0, 1.0),
+        ];
+        for algorithm in HullAlgorithm::ALL {
+            let mut hull = algorithm.hull_fn()(&points, &mut vec![]);
+            hull.sort_unstable();
+            assert_eq!(hull, vec![0, 2, 3, 5], "{}", algorithm.name());
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod use_color_scheme;
 pub mod use_mouse_pos;
+pub mod use_settings;
 pub mod use_sort_audio;
@@ -1,6 +1,6 @@
 use std::fmt::Display;
 
-use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+use gloo_storage::{LocalStorage, Storage};
 use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
@@ -23,44 +23,67 @@ impl Display for ColorScheme {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
-pub enum ColorSchemeMode {
+/// The theme chosen by the user. Every theme is drawn with the colors of a [`ColorScheme`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    /// Follows the color scheme that the browser prefers.
     #[default]
     Auto,
     Light,
     Dark,
+    /// The dark color scheme with pure black and white and brighter accents.
+    HighContrast,
 }
 
-pub fn use_color_scheme() -> ColorScheme {
-    let app_color_scheme = use_state_eq(|| ColorScheme::Light);
+impl Theme {
+    pub const ALL: [Theme; 4] = [Theme::Auto, Theme::Light, Theme::Dark, Theme::HighContrast];
 
-    use_effect_with_deps(
-        move |()| {
-            let mode: Result<ColorScheme, StorageError> =
-                LocalStorage::get("app-color-scheme-mode");
-            if mode.is_err() {
-                LocalStorage::set("app-color-scheme-mode", ColorSchemeMode::default()).unwrap();
-            }
-            || ()
-        },
-        (),
-    );
+    pub fn name(&self) -> &'static str {
+        match self {
+            Theme::Auto => "Auto",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+            Theme::HighContrast => "High contrast",
+        }
+    }
+    pub fn color_scheme(&self, preferred: ColorScheme) -> ColorScheme {
+        match self {
+            Theme::Auto => preferred,
+            Theme::Light => ColorScheme::Light,
+            Theme::Dark | Theme::HighContrast => ColorScheme::Dark,
+        }
+    }
+    /// The classes of the body that select the CSS variables of the theme.
+    pub fn class_name(&self, color_scheme: ColorScheme) -> String {
+        match self {
+            Theme::HighContrast => "dark high-contrast".to_string(),
+            _ => color_scheme.to_string().to_lowercase(),
+        }
+    }
+    /// The theme that the theme button switches to: light, dark and high contrast in turn.
+    pub fn next(&self, color_scheme: ColorScheme) -> Theme {
+        match (self, color_scheme) {
+            (Theme::HighContrast, _) => Theme::Light,
+            (_, ColorScheme::Light) => Theme::Dark,
+            (_, ColorScheme::Dark) => Theme::HighContrast,
+        }
+    }
+}
+
+/// Gets the color scheme of `theme`, following the color scheme that the browser prefers for [`Theme::Auto`].
+pub fn use_color_scheme(theme: Theme) -> ColorScheme {
+    let app_color_scheme = use_state_eq(|| ColorScheme::Light);
 
     {
         let app_color_scheme = app_color_scheme.clone();
 
         use_effect_with_deps(
-            move |(mode, preferred)| {
-                app_color_scheme.set(match mode {
-                    ColorSchemeMode::Auto => *preferred,
-                    ColorSchemeMode::Light => ColorScheme::Light,
-                    ColorSchemeMode::Dark => ColorScheme::Dark,
-                });
-
+            move |(theme, preferred)| {
+                app_color_scheme.set(theme.color_scheme(*preferred));
                 || ()
             },
             (
-                LocalStorage::get("app-color-scheme-mode").unwrap_or_default(),
+                theme,
                 LocalStorage::get("preferred-color-scheme").unwrap_or(ColorScheme::Light),
             ),
         );
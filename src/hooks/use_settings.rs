@@ -0,0 +1,110 @@
+use std::ops::Deref;
+
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::{
+    hooks::use_color_scheme::Theme,
+    pages::{pathfinding::PathfindingSettings, sorting::SortConfig},
+};
+
+/// The localStorage key of the settings.
+const SETTINGS_KEY: &str = "app-settings";
+/// The localStorage key of the color scheme mode that was saved before the settings, which is read once to keep the chosen theme.
+const LEGACY_THEME_KEY: &str = "app-color-scheme-mode";
+
+/// The settings that are shared by every page and kept between visits.
+/// Settings that are missing from the saved ones, like ones added in a later version, get their default values.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub theme: Theme,
+    /// How long the playback of steps takes in seconds on the pages that don't have their own playback time.
+    pub playback_time: f32,
+    /// The config of the sorting page. The input length, distribution and seed in the URL take precedence.
+    pub sort: SortConfig,
+    pub pathfinding: PathfindingSettings,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            playback_time: 10.0,
+            sort: SortConfig::default(),
+            pathfinding: PathfindingSettings::default(),
+        }
+    }
+}
+impl Settings {
+    /// Loads the saved settings, or the default ones if there are none or they can't be read.
+    pub fn load() -> Self {
+        LocalStorage::get(SETTINGS_KEY).unwrap_or_else(|_| Self {
+            theme: LocalStorage::get(LEGACY_THEME_KEY).unwrap_or_default(),
+            ..Self::default()
+        })
+    }
+    pub fn save(&self) {
+        if LocalStorage::set(SETTINGS_KEY, self).is_ok() {
+            LocalStorage::delete(LEGACY_THEME_KEY);
+        }
+    }
+}
+
+/// A handle to the settings in the settings context. Updating them renders every component that uses them.
+#[derive(Clone, PartialEq)]
+pub struct SettingsHandle(UseStateHandle<Settings>);
+impl Deref for SettingsHandle {
+    type Target = Settings;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl SettingsHandle {
+    pub fn update(&self, update: impl FnOnce(&mut Settings)) {
+        let mut settings = (*self.0).clone();
+        update(&mut settings);
+        self.0.set(settings);
+    }
+}
+
+/// Loads the settings and saves them whenever they change. The handle should be provided to the rest of the app in a
+/// [`ContextProvider`], where [`use_settings`] gets it from.
+pub fn use_settings_provider() -> SettingsHandle {
+    let settings = use_state_eq(Settings::load);
+
+    use_effect_with_deps(
+        |settings| {
+            settings.save();
+            || ()
+        },
+        (*settings).clone(),
+    );
+
+    SettingsHandle(settings)
+}
+
+pub fn use_settings() -> SettingsHandle {
+    use_context::<SettingsHandle>().expect("no settings context found")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_settings_get_default_values() {
+        let settings: Settings = serde_json::from_str(
+            r#"{ "theme": "HighContrast", "pathfinding": { "graph_width": 12 } }"#,
+        )
+        .unwrap();
+        assert_eq!(settings.theme, Theme::HighContrast);
+        assert_eq!(settings.playback_time, Settings::default().playback_time);
+        assert_eq!(settings.pathfinding.graph_width, 12);
+        assert_eq!(
+            settings.pathfinding.graph_height,
+            PathfindingSettings::default().graph_height
+        );
+    }
+}
@@ -2,15 +2,15 @@ use std::rc::Rc;
 
 use crate::{
     components::sorting::audio_controls::AudioConfig,
-    utils::audio::{Note, Synth},
+    utils::audio::{value_to_frequency, Note, Synth},
 };
 
-use sorting::SortCommand;
+use sorting::{SortCommand, TotalF64};
 use yew::prelude::*;
 
 pub fn use_sort_audio(
-    items: UseStateHandle<Vec<u32>>,
-    step: UseStateHandle<Vec<SortCommand<u32>>>,
+    items: UseStateHandle<Vec<TotalF64>>,
+    step: UseStateHandle<Vec<SortCommand<TotalF64>>>,
     config: AudioConfig,
 ) {
     let synth = use_mut_ref(Synth::new);
@@ -22,20 +22,25 @@ pub fn use_sort_audio(
             let mut notes: Vec<Note> = vec![];
 
             let ctx = Rc::clone(&synth.borrow().ctx);
+            let min = items.iter().min().map_or(0.0, |val| val.0) as f32;
+            let max = items.iter().max().map_or(0.0, |val| val.0) as f32;
 
             for command in step.iter() {
                 let val = match command {
                     SortCommand::Swap(_, to) => items[*to],
                     SortCommand::Set(index, _) => items[*index],
-                } as f32;
-                let ratio = val / *items.iter().max().unwrap() as f32;
+                    SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => continue,
+                }
+                .0 as f32;
                 let frequency =
-                    config.min_frequency + (config.max_frequency - config.min_frequency) * ratio;
+                    value_to_frequency(val, min, max, config.min_frequency, config.max_frequency);
 
                 notes.push(Note::new(&ctx, frequency, config.sound_type));
             }
 
-            synth.borrow_mut().play(notes, config.note_duration);
+            synth
+                .borrow_mut()
+                .play(notes, config.note_duration, config.volume);
         }
         || ()
     });
@@ -0,0 +1,43 @@
+extern crate backtracking;
+extern crate data_structures;
+extern crate geometry;
+extern crate pathfinding;
+extern crate sorting;
+extern crate string_algorithms;
+
+pub mod components;
+pub mod hooks;
+pub mod pages;
+pub mod utils;
+pub mod workers;
+
+use yew_router::prelude::*;
+
+#[derive(Clone, Debug, Routable, PartialEq)]
+pub enum Route {
+    #[at("/")]
+    Home,
+    #[at("/sorting")]
+    Sorting,
+    // Without this, subroutes don't seem to be recognized even though they are defined in pages::sorting
+    #[at("/sorting/:algorithm")]
+    SortingAlgorithm,
+    #[at("/pathfinding")]
+    Pathfinding,
+    #[at("/pathfinding/:algorithm")]
+    PathfindingAlgorithm,
+    #[at("/searching")]
+    Searching,
+    #[at("/data-structures")]
+    DataStructures,
+    #[at("/strings")]
+    Strings,
+    #[at("/backtracking")]
+    Backtracking,
+    #[at("/dynamic-programming")]
+    DynamicProgramming,
+    #[at("/geometry")]
+    Geometry,
+    #[at("/benchmark")]
+    Benchmark,
+}
@@ -1,34 +1,15 @@
-extern crate pathfinding;
-extern crate sorting;
-
-mod components;
-mod hooks;
-mod pages;
-mod utils;
-
-use gloo_storage::{LocalStorage, Storage};
-use hooks::use_color_scheme::{use_color_scheme, ColorScheme, ColorSchemeMode};
+use algorust::{
+    hooks::{
+        use_color_scheme::{use_color_scheme, ColorScheme, Theme},
+        use_settings::{use_settings_provider, SettingsHandle},
+    },
+    pages, Route,
+};
 use wasm_bindgen::JsCast;
 use web_sys::{window, HtmlMetaElement};
 use yew::prelude::*;
-use yew_hooks::use_update;
 use yew_router::prelude::*;
 
-#[derive(Clone, Debug, Routable, PartialEq)]
-enum Route {
-    #[at("/")]
-    Home,
-    #[at("/sorting")]
-    Sorting,
-    // Without this, subroutes don't seem to be recognized even though they are defined in pages::sorting
-    #[at("/sorting/:algorithm")]
-    SortingAlgorithm,
-    #[at("/pathfinding")]
-    Pathfinding,
-    #[at("/pathfinding/:algorithm")]
-    PathfindingAlgorithm,
-}
-
 fn switch(routes: &Route) -> Html {
     match routes {
         Route::Home => html! {
@@ -46,91 +27,115 @@ fn switch(routes: &Route) -> Html {
         Route::PathfindingAlgorithm => html! {
             <Switch<pages::pathfinding::PathfindingRoute> render={Switch::render(pages::pathfinding::switch_pathfinding)} />
         },
+        Route::Searching => html! {
+            <pages::searching::SearchingPage />
+        },
+        Route::DataStructures => html! {
+            <pages::data_structures::DataStructuresPage />
+        },
+        Route::Strings => html! {
+            <pages::strings::StringsPage />
+        },
+        Route::Backtracking => html! {
+            <pages::backtracking::BacktrackingPage />
+        },
+        Route::DynamicProgramming => html! {
+            <pages::dynamic_programming::DynamicProgrammingPage />
+        },
+        Route::Geometry => html! {
+            <pages::geometry::GeometryPage />
+        },
+        Route::Benchmark => html! {
+            <pages::benchmark::BenchmarkPage />
+        },
     }
 }
 
 #[function_component(App)]
 fn app() -> Html {
-    let color_scheme = use_color_scheme();
-    let update = use_update();
+    let settings = use_settings_provider();
+    let theme = settings.theme;
+    let color_scheme = use_color_scheme(theme);
 
-    let set_color_scheme = move |color_scheme: ColorScheme| {
+    let set_theme = move |theme: Theme, color_scheme: ColorScheme| {
         let document = window().unwrap().document().unwrap();
-        // Set body class according to color scheme
+        // Set body class according to theme
         document
             .body()
             .unwrap()
-            .set_class_name(&color_scheme.to_string().to_lowercase());
+            .set_class_name(&theme.class_name(color_scheme));
         // Set PWA theme color
         if let Ok(meta) = document.query_selector("meta[name=theme-color]") {
             meta.unwrap()
                 .dyn_into::<HtmlMetaElement>()
                 .unwrap()
-                .set_content(match color_scheme {
-                    ColorScheme::Light => "#d3dbde",
-                    ColorScheme::Dark => "#161b1d",
+                .set_content(match (theme, color_scheme) {
+                    (Theme::HighContrast, _) => "#000000",
+                    (_, ColorScheme::Light) => "#d3dbde",
+                    (_, ColorScheme::Dark) => "#161b1d",
                 });
         }
     };
 
     use_effect_with_deps(
-        move |_| {
-            set_color_scheme(color_scheme);
+        move |(theme, color_scheme)| {
+            set_theme(*theme, *color_scheme);
             || ()
         },
-        color_scheme,
+        (theme, color_scheme),
     );
 
     let toggle_theme = {
+        let settings = settings.clone();
+
         Callback::from(move |_| {
-            let next_color_scheme = match LocalStorage::get("app-color-scheme-mode").unwrap() {
-                ColorSchemeMode::Auto => match LocalStorage::get("preferred-color-scheme").unwrap()
-                {
-                    ColorScheme::Light => ColorScheme::Dark,
-                    ColorScheme::Dark => ColorScheme::Light,
-                },
-                ColorSchemeMode::Light => ColorScheme::Dark,
-                ColorSchemeMode::Dark => ColorScheme::Light,
-            };
-            LocalStorage::set("app-color-scheme-mode", next_color_scheme.to_string()).unwrap();
-            set_color_scheme(next_color_scheme);
-            update();
+            settings.update(|settings| settings.theme = theme.next(color_scheme));
         })
     };
 
     html! {
         <BrowserRouter>
-            <ContextProvider<ColorScheme> context={color_scheme}>
-                <div class="top-bar">
-                    <div class="page-links">
-                        <Link<Route> to={Route::Home}>{ "Home" }</Link<Route>>
-                        <Link<Route> to={Route::Sorting}>{ "Sorting" }</Link<Route>>
-                        <Link<Route> to={Route::Pathfinding}>{ "Pathfinding" }</Link<Route>>
-                    </div>
-                    <div class="other-links">
-                        <button onclick={toggle_theme}>{
-                            match color_scheme {
-                                ColorScheme::Light => "☀️",
-                                ColorScheme::Dark => "🌙"
-                            }
-                        }</button>
-                        <a href="https://github.com/Jondolf/rust-algorithms" target="_blank" aria-label="Link to this website's GitHub repository (opens in a new window)">
-                            <img
-                                src={
-                                    match color_scheme {
-                                        ColorScheme::Light => "/assets/images/GitHub-Mark-64px.png",
-                                        ColorScheme::Dark => "/assets/images/GitHub-Mark-Light-64px.png",
-                                    }
+            <ContextProvider<SettingsHandle> context={settings}>
+                <ContextProvider<ColorScheme> context={color_scheme}>
+                    <div class="top-bar">
+                        <div class="page-links">
+                            <Link<Route> to={Route::Home}>{ "Home" }</Link<Route>>
+                            <Link<Route> to={Route::Sorting}>{ "Sorting" }</Link<Route>>
+                            <Link<Route> to={Route::Pathfinding}>{ "Pathfinding" }</Link<Route>>
+                            <Link<Route> to={Route::Searching}>{ "Searching" }</Link<Route>>
+                            <Link<Route> to={Route::DataStructures}>{ "Data structures" }</Link<Route>>
+                            <Link<Route> to={Route::Strings}>{ "Strings" }</Link<Route>>
+                            <Link<Route> to={Route::Backtracking}>{ "Backtracking" }</Link<Route>>
+                            <Link<Route> to={Route::DynamicProgramming}>{ "Dynamic programming" }</Link<Route>>
+                            <Link<Route> to={Route::Geometry}>{ "Geometry" }</Link<Route>>
+                            <Link<Route> to={Route::Benchmark}>{ "Benchmark" }</Link<Route>>
+                        </div>
+                        <div class="other-links">
+                            <button onclick={toggle_theme} title={format!("Theme: {}", theme.name())}>{
+                                match (theme, color_scheme) {
+                                    (Theme::HighContrast, _) => "◐",
+                                    (_, ColorScheme::Light) => "☀️",
+                                    (_, ColorScheme::Dark) => "🌙"
                                 }
-                                alt="GitHub logo"
-                                width="40"
-                                height="40"
-                            />
-                        </a>
+                            }</button>
+                            <a href="https://github.com/Jondolf/rust-algorithms" target="_blank" aria-label="Link to this website's GitHub repository (opens in a new window)">
+                                <img
+                                    src={
+                                        match color_scheme {
+                                            ColorScheme::Light => "/assets/images/GitHub-Mark-64px.png",
+                                            ColorScheme::Dark => "/assets/images/GitHub-Mark-Light-64px.png",
+                                        }
+                                    }
+                                    alt="GitHub logo"
+                                    width="40"
+                                    height="40"
+                                />
+                            </a>
+                        </div>
                     </div>
-                </div>
-                <Switch<Route> render={Switch::render(switch)} />
-            </ContextProvider<ColorScheme>>
+                    <Switch<Route> render={Switch::render(switch)} />
+                </ContextProvider<ColorScheme>>
+            </ContextProvider<SettingsHandle>>
         </BrowserRouter>
     }
 }
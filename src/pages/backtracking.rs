@@ -0,0 +1,338 @@
+use backtracking::{
+    parse_sudoku, run_backtrack_steps, solve_n_queens, solve_sudoku, sudoku_to_string,
+    BacktrackStep, Grid, Queen, SolveOutcome, EXAMPLE_SUDOKU,
+};
+use yew::prelude::*;
+use yew_hooks::use_title;
+
+use crate::{
+    components::{
+        backtracking::board_view::{BoardStyle, BoardView, CellEdit},
+        collapsible::Collapsible,
+        input_items::{Button, IntInput, PlaybackTimeInput, SelectInput, TextInput},
+        sidebar::Sidebar,
+        step_slider::StepSlider,
+    },
+    hooks::use_settings::use_settings,
+};
+
+/// The most steps that a search takes before it is stopped, so that hard puzzles don't freeze the page.
+const STEP_LIMIT: usize = 100_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Puzzle {
+    NQueens,
+    Sudoku,
+}
+impl Puzzle {
+    pub const ALL: [Puzzle; 2] = [Puzzle::NQueens, Puzzle::Sudoku];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Puzzle::NQueens => "N-Queens",
+            Puzzle::Sudoku => "Sudoku",
+        }
+    }
+}
+
+/// The steps of solving a puzzle and how the search ended, kept along with the puzzle so that it is only solved again
+/// once the puzzle changes.
+struct Solution<P, T> {
+    puzzle: P,
+    steps: Vec<BacktrackStep<T>>,
+    outcome: SolveOutcome,
+}
+
+/// Returns the kept solution of `puzzle`, or solves it with `solve` if the kept solution is of another puzzle.
+fn solve_once<'a, P: Clone + PartialEq, T>(
+    solution: &'a mut Option<Solution<P, T>>,
+    puzzle: &P,
+    solve: impl FnOnce(&P, &mut Vec<BacktrackStep<T>>) -> SolveOutcome,
+) -> &'a Solution<P, T> {
+    if solution
+        .as_ref()
+        .is_some_and(|solution| solution.puzzle != *puzzle)
+    {
+        *solution = None;
+    }
+    solution.get_or_insert_with(|| {
+        let mut steps = vec![];
+        let outcome = solve(puzzle, &mut steps);
+        Solution {
+            puzzle: puzzle.clone(),
+            steps,
+            outcome,
+        }
+    })
+}
+
+/// Labels the values of a grid for the board view.
+fn labels<T>(grid: &Grid<T>, label: impl Fn(&T) -> String) -> Vec<Vec<Option<String>>> {
+    grid.iter()
+        .map(|row| row.iter().map(|cell| cell.as_ref().map(&label)).collect())
+        .collect()
+}
+
+/// Counts how many values were placed and taken back out in steps.
+fn count_steps<T>(steps: &[BacktrackStep<T>]) -> (usize, usize) {
+    steps
+        .iter()
+        .fold((0, 0), |(placed, backtracked), step| match step {
+            BacktrackStep::Place(..) => (placed + 1, backtracked),
+            BacktrackStep::Backtrack(..) => (placed, backtracked + 1),
+            _ => (placed, backtracked),
+        })
+}
+
+/// Solves N-Queens or a sudoku typed in by the user with backtracking and plays back every value it tries and takes back.
+#[function_component(BacktrackingPage)]
+pub fn backtracking_page() -> Html {
+    let puzzle = use_state_eq(|| Puzzle::NQueens);
+    let queens = use_state_eq(|| 8);
+    let sudoku = use_state_eq(|| parse_sudoku(EXAMPLE_SUDOKU).unwrap());
+    let sudoku_text = use_state_eq(|| EXAMPLE_SUDOKU.to_string());
+    let step_index = use_state(|| 0);
+    let settings = use_settings();
+    // Solving can take up to STEP_LIMIT steps, so the steps are kept for every render until the puzzle changes
+    let queens_solution = use_mut_ref(|| None::<Solution<usize, Queen>>);
+    let sudoku_solution = use_mut_ref(|| None::<Solution<Grid<u8>, u8>>);
+
+    use_title(format!("{} - Backtracking", puzzle.name()));
+
+    let change_puzzle = {
+        let puzzle = puzzle.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(new_puzzle) = Puzzle::ALL
+                .iter()
+                .copied()
+                .find(|puzzle| puzzle.name() == name)
+            {
+                puzzle.set(new_puzzle);
+                step_index.set(0);
+            }
+        })
+    };
+
+    let change_queens = {
+        let queens = queens.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |new_queens| {
+            queens.set(new_queens);
+            step_index.set(0);
+        })
+    };
+
+    // The grid only changes once the text is a valid sudoku, so that it can be typed a cell at a time
+    let change_sudoku_text = {
+        let sudoku = sudoku.clone();
+        let sudoku_text = sudoku_text.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |new_text: String| {
+            if let Ok(new_sudoku) = parse_sudoku(&new_text) {
+                sudoku.set(new_sudoku);
+                step_index.set(0);
+            }
+            sudoku_text.set(new_text);
+        })
+    };
+
+    let edit_sudoku = {
+        let sudoku = sudoku.clone();
+        let sudoku_text = sudoku_text.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |((row, column), digit): CellEdit| {
+            let mut new_sudoku = (*sudoku).clone();
+            new_sudoku[row][column] = digit;
+            sudoku_text.set(sudoku_to_string(&new_sudoku));
+            sudoku.set(new_sudoku);
+            step_index.set(0);
+        })
+    };
+
+    let set_sudoku = |text: String| {
+        let sudoku = sudoku.clone();
+        let sudoku_text = sudoku_text.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |_| {
+            sudoku.set(parse_sudoku(&text).unwrap());
+            sudoku_text.set(text.clone());
+            step_index.set(0);
+        })
+    };
+
+    let on_change_step = {
+        let step_index = step_index.clone();
+        Callback::from(move |index| step_index.set(index))
+    };
+
+    // Only the shown steps are labeled and narrated, since a sudoku can take tens of thousands of steps
+    let (cells, givens, step, step_narration, counts, step_count, outcome) = match *puzzle {
+        Puzzle::NQueens => {
+            let mut solution = queens_solution.borrow_mut();
+            let Solution { steps, outcome, .. } =
+                solve_once(&mut solution, &*queens, |n, steps| {
+                    solve_n_queens(*n, STEP_LIMIT, steps).1
+                });
+            let shown_steps = &steps[..(*step_index).min(steps.len())];
+            let mut board = vec![vec![None; *queens]; *queens];
+            run_backtrack_steps(&mut board, shown_steps);
+            let step = shown_steps.last();
+            (
+                labels(&board, |_| "♛".to_string()),
+                vec![],
+                step.map(|step| step.clone().map(|_| "♛".to_string())),
+                step.map(ToString::to_string),
+                count_steps(shown_steps),
+                steps.len(),
+                *outcome,
+            )
+        }
+        Puzzle::Sudoku => {
+            let mut solution = sudoku_solution.borrow_mut();
+            let Solution { steps, outcome, .. } =
+                solve_once(&mut solution, &*sudoku, |sudoku, steps| {
+                    solve_sudoku(&mut sudoku.clone(), STEP_LIMIT, steps)
+                });
+            let shown_steps = &steps[..(*step_index).min(steps.len())];
+            let mut grid = (*sudoku).clone();
+            run_backtrack_steps(&mut grid, shown_steps);
+            let givens = (0..81)
+                .map(|i| (i / 9, i % 9))
+                .filter(|&(row, column)| sudoku[row][column].is_some())
+                .collect();
+            let step = shown_steps.last();
+            (
+                labels(&grid, u8::to_string),
+                givens,
+                step.map(|step| step.clone().map(|digit| digit.to_string())),
+                step.map(ToString::to_string),
+                count_steps(shown_steps),
+                steps.len(),
+                *outcome,
+            )
+        }
+    };
+    let shown_step_count = (*step_index).min(step_count);
+    let (placed, backtracked) = counts;
+
+    let sudoku_error = parse_sudoku(&sudoku_text)
+        .err()
+        .map(|error| format!("Not a valid sudoku: {}", error));
+
+    // The outcome is told once every step has been played
+    let narration = match step_narration {
+        Some(step_narration) if shown_step_count < step_count => step_narration,
+        _ if shown_step_count == 0 && step_count > 0 => match *puzzle {
+            Puzzle::NQueens => format!("Placing {} queens row by row", *queens),
+            Puzzle::Sudoku => {
+                "Type digits in the grid or paste a puzzle, then play the steps".to_string()
+            }
+        },
+        _ => match outcome {
+            SolveOutcome::Solved => format!("Solved in {} steps", step_count),
+            SolveOutcome::NoSolution => "There is no solution".to_string(),
+            SolveOutcome::StepLimitReached => {
+                format!(
+                    "Stopped after {} steps without finding a solution",
+                    step_count
+                )
+            }
+        },
+    };
+
+    let config = match *puzzle {
+        Puzzle::NQueens => html! {
+            <IntInput<usize>
+                title="Queens"
+                value={*queens}
+                oninput={change_queens}
+                min={1}
+                max={16}
+            />
+        },
+        Puzzle::Sudoku => html! {
+            <>
+                <TextInput
+                    title="Puzzle"
+                    value={(*sudoku_text).clone()}
+                    placeholder="81 digits, with . or 0 for empty cells"
+                    error={sudoku_error}
+                    oninput={change_sudoku_text}
+                />
+                <Button title="Load example" onclick={set_sudoku(EXAMPLE_SUDOKU.to_string())} />
+                <Button title="Clear" onclick={set_sudoku(".".repeat(81))} />
+            </>
+        },
+    };
+
+    html! {
+        <div class="page" id="Backtracking">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <SelectInput
+                        title="Puzzle"
+                        options={Puzzle::ALL.iter().map(|puzzle| puzzle.name().to_string()).collect::<Vec<String>>()}
+                        selected_value={puzzle.name().to_string()}
+                        onchange={change_puzzle}
+                    />
+                    { config }
+                    <PlaybackTimeInput />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <span>{ format!("Placed: {}, backtracked: {}", placed, backtracked) }</span>
+                <BoardView
+                    {cells}
+                    style={if *puzzle == Puzzle::Sudoku { BoardStyle::Sudoku } else { BoardStyle::Chessboard }}
+                    {givens}
+                    {step}
+                    on_edit={(*puzzle == Puzzle::Sudoku && *step_index == 0).then_some(edit_sudoku)}
+                />
+                <p class="step-narration">{ narration }</p>
+                <StepSlider
+                    label={format!("Steps ({}/{})", shown_step_count, step_count)}
+                    active_step_index={*step_index}
+                    max={step_count}
+                    on_change={on_change_step}
+                    playback_time={settings.playback_time}
+                />
+            </main>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puzzles_are_solved_again_only_when_they_change() {
+        let mut solution = None;
+        let mut solves = 0;
+        let mut solve = |n: &usize, steps: &mut Vec<BacktrackStep<Queen>>| {
+            solves += 1;
+            solve_n_queens(*n, STEP_LIMIT, steps).1
+        };
+
+        let steps = solve_once(&mut solution, &4, &mut solve).steps.clone();
+        assert_eq!(solve_once(&mut solution, &4, &mut solve).steps, steps);
+        assert_eq!(
+            solve_once(&mut solution, &4, &mut solve).outcome,
+            SolveOutcome::Solved
+        );
+        assert_eq!(
+            solve_once(&mut solution, &3, &mut solve).outcome,
+            SolveOutcome::NoSolution
+        );
+        assert_eq!(solves, 2);
+    }
+}
@@ -0,0 +1,172 @@
+use yew::prelude::*;
+use yew_hooks::{use_interval, use_title};
+
+use crate::{
+    components::{
+        collapsible::Collapsible,
+        input_items::{Button, Checkbox, IntInput},
+        sidebar::Sidebar,
+        sorting::benchmark_chart::BenchmarkChart,
+    },
+    pages::sorting::{get_sorting_algorithms, SortingAlgorithm},
+    utils::benchmark::{benchmark_sizes, Benchmark, MAX_BENCHMARK_STEPS},
+};
+
+#[derive(Clone, PartialEq)]
+struct BenchmarkConfig {
+    /// The largest input size of the sweep.
+    max_len: usize,
+    /// How many evenly spaced input sizes are measured.
+    sizes: usize,
+    algorithms: Vec<SortingAlgorithm>,
+}
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            max_len: 2000,
+            sizes: 10,
+            algorithms: get_sorting_algorithms().into_values().collect(),
+        }
+    }
+}
+
+/// Times the sorting algorithms without visualizing them and charts the time against the input size,
+/// which shows how their running times actually grow.
+#[function_component(BenchmarkPage)]
+pub fn benchmark_page() -> Html {
+    let config = use_state(BenchmarkConfig::default);
+    let benchmark = use_state(|| Benchmark::new(vec![], vec![], 0));
+    let running = use_state_eq(|| false);
+
+    use_title("Benchmark - Sorting algorithms".to_string());
+
+    {
+        let benchmark = benchmark.clone();
+        let millis = if *running { 1 } else { 0 };
+        let running = running.clone();
+
+        // One measurement per tick, so the chart updates and the page stays responsive between them
+        use_interval(
+            move || {
+                let mut next = (*benchmark).clone();
+                if !next.measure_next() {
+                    running.set(false);
+                }
+                benchmark.set(next);
+            },
+            millis,
+        );
+    }
+
+    let toggle_running = {
+        let config = config.clone();
+        let benchmark = benchmark.clone();
+        let running = running.clone();
+
+        Callback::from(move |_| {
+            if !*running {
+                benchmark.set(Benchmark::new(
+                    config.algorithms.clone(),
+                    benchmark_sizes(config.max_len, config.sizes),
+                    rand::random(),
+                ));
+            }
+            running.set(!*running);
+        })
+    };
+    let change_max_len = {
+        let config = config.clone();
+
+        Callback::from(move |max_len| {
+            config.set(BenchmarkConfig {
+                max_len,
+                ..(*config).clone()
+            })
+        })
+    };
+    let change_sizes = {
+        let config = config.clone();
+
+        Callback::from(move |sizes| {
+            config.set(BenchmarkConfig {
+                sizes,
+                ..(*config).clone()
+            })
+        })
+    };
+    let toggle_algorithm = {
+        let config = config.clone();
+
+        move |algorithm: SortingAlgorithm| {
+            let config = config.clone();
+
+            Callback::from(move |_| {
+                // Keep the order of the algorithm list so that the colors don't depend on the order that they were checked in
+                let algorithms = get_sorting_algorithms()
+                    .into_values()
+                    .filter(|other| (*other == algorithm) != config.algorithms.contains(other))
+                    .collect();
+                config.set(BenchmarkConfig {
+                    algorithms,
+                    ..(*config).clone()
+                })
+            })
+        }
+    };
+
+    let series = benchmark
+        .algorithms
+        .iter()
+        .zip(&benchmark.times)
+        .map(|(algorithm, times)| (algorithm.name.clone(), times.clone()))
+        .collect::<Vec<(String, Vec<Option<f64>>)>>();
+
+    html! {
+        <div class="page" id="Benchmark">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <Button title={if *running { "Stop" } else { "Run benchmark" }} onclick={toggle_running} />
+                    <IntInput<usize>
+                        title="Largest input"
+                        value={config.max_len}
+                        oninput={change_max_len}
+                        min={1}
+                    />
+                    <IntInput<usize>
+                        title="Input sizes"
+                        value={config.sizes}
+                        oninput={change_sizes}
+                        min={1}
+                    />
+                    {
+                        for get_sorting_algorithms().into_values().map(|algorithm| html! {
+                            <Checkbox
+                                title={algorithm.name.clone()}
+                                value={config.algorithms.contains(&algorithm)}
+                                oninput={toggle_algorithm(algorithm.clone())}
+                            />
+                        })
+                    }
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <span>
+                    {
+                        if benchmark.is_empty() {
+                            "Run the benchmark to time the algorithms on random inputs of increasing size".to_string()
+                        } else {
+                            format!("{} of {} measurements done", benchmark.measured(), benchmark.len())
+                        }
+                    }
+                </span>
+                <span class="benchmark-note">
+                    { format!("Algorithms that would take more than {} steps for an input are skipped for it", MAX_BENCHMARK_STEPS) }
+                </span>
+                <BenchmarkChart sizes={benchmark.sizes.clone()} {series} />
+            </main>
+        </div>
+    }
+}
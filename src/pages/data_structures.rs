@@ -0,0 +1,424 @@
+use data_structures::{run_bst_steps, BinarySearchTree, BstStep, TraversalOrder};
+use rand::Rng;
+use sorting::{run_heap_steps, BinaryHeapVisual, HeapOrder, HeapStep};
+use yew::prelude::*;
+use yew_hooks::use_title;
+
+use crate::{
+    components::{
+        collapsible::Collapsible,
+        data_structures::{bst_view::BstView, heap_view::HeapView},
+        input_items::{Button, IntInput, PlaybackTimeInput, SelectInput},
+        sidebar::Sidebar,
+        step_slider::StepSlider,
+    },
+    hooks::use_settings::use_settings,
+};
+
+/// The largest value of the random batches, so that the values fit in the nodes.
+const MAX_RANDOM_VALUE: i32 = 99;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataStructure {
+    BinarySearchTree,
+    BinaryHeap,
+}
+impl DataStructure {
+    pub const ALL: [DataStructure; 2] =
+        [DataStructure::BinarySearchTree, DataStructure::BinaryHeap];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DataStructure::BinarySearchTree => "Binary search tree",
+            DataStructure::BinaryHeap => "Binary heap",
+        }
+    }
+}
+
+/// Data structures whose operations are played back step by step.\
+/// Each operation starts from the state that the previous one left, so the slider only covers the latest operation.
+#[function_component(DataStructuresPage)]
+pub fn data_structures_page() -> Html {
+    let data_structure = use_state_eq(|| DataStructure::BinarySearchTree);
+
+    use_title(format!("{} - Data structures", data_structure.name()));
+
+    let tree = use_mut_ref(BinarySearchTree::<i32>::new);
+    // The tree before the latest operation, which its steps are replayed on
+    let tree_before = use_mut_ref(BinarySearchTree::<i32>::new);
+    let tree_steps = use_mut_ref(Vec::<BstStep<i32>>::new);
+    let heap = use_mut_ref(BinaryHeapVisual::<i32>::new_min);
+    // The array of the heap before the latest operation, which its steps are replayed on
+    let heap_before = use_mut_ref(Vec::<i32>::new);
+    let heap_steps = use_mut_ref(Vec::<Vec<HeapStep<i32>>>::new);
+    let step_index = use_state(|| 0);
+    let value = use_state_eq(|| 50);
+    let batch_size = use_state_eq(|| 10);
+    let traversal_order = use_state_eq(TraversalOrder::default);
+    let heap_order = use_state_eq(|| HeapOrder::Min);
+    let settings = use_settings();
+
+    // Runs `operation` on the tree and plays its steps from the start
+    let run_tree_operation = {
+        let tree = tree.clone();
+        let tree_before = tree_before.clone();
+        let tree_steps = tree_steps.clone();
+        let step_index = step_index.clone();
+
+        move |operation: &dyn Fn(&mut BinarySearchTree<i32>)| {
+            let mut tree = tree.borrow_mut();
+            *tree_before.borrow_mut() = tree.clone();
+            operation(&mut tree);
+            *tree_steps.borrow_mut() = tree.take_steps();
+            step_index.set(0);
+        }
+    };
+
+    // Runs `operation` on the heap and plays its steps from the start
+    let run_heap_operation = {
+        let heap = heap.clone();
+        let heap_before = heap_before.clone();
+        let heap_steps = heap_steps.clone();
+        let step_index = step_index.clone();
+
+        move |operation: &dyn Fn(&mut BinaryHeapVisual<i32>)| {
+            let mut heap = heap.borrow_mut();
+            *heap_before.borrow_mut() = heap.as_slice().to_vec();
+            operation(&mut heap);
+            *heap_steps.borrow_mut() = heap.take_steps();
+            step_index.set(0);
+        }
+    };
+
+    let insert = {
+        let run_tree_operation = run_tree_operation.clone();
+        let value = *value;
+
+        Callback::from(move |_| {
+            run_tree_operation(&|tree| {
+                tree.insert(value);
+            })
+        })
+    };
+
+    let remove = {
+        let run_tree_operation = run_tree_operation.clone();
+        let value = *value;
+
+        Callback::from(move |_| {
+            run_tree_operation(&|tree| {
+                tree.remove(&value);
+            })
+        })
+    };
+
+    let search = {
+        let run_tree_operation = run_tree_operation.clone();
+        let value = *value;
+
+        Callback::from(move |_| {
+            run_tree_operation(&|tree| {
+                tree.search(&value);
+            })
+        })
+    };
+
+    let insert_random_batch = {
+        let run_tree_operation = run_tree_operation.clone();
+        let batch_size = *batch_size;
+
+        Callback::from(move |_| {
+            let values = random_batch(batch_size);
+            run_tree_operation(&|tree| {
+                for value in &values {
+                    tree.insert(*value);
+                }
+            })
+        })
+    };
+
+    let traverse = {
+        let run_tree_operation = run_tree_operation.clone();
+        let traversal_order = *traversal_order;
+
+        Callback::from(move |_| {
+            run_tree_operation(&|tree| {
+                tree.traverse(traversal_order);
+            })
+        })
+    };
+
+    let clear_tree =
+        Callback::from(move |_| run_tree_operation(&|tree| *tree = BinarySearchTree::new()));
+
+    let push = {
+        let run_heap_operation = run_heap_operation.clone();
+        let value = *value;
+
+        Callback::from(move |_| run_heap_operation(&|heap| heap.push(value)))
+    };
+
+    let pop = {
+        let run_heap_operation = run_heap_operation.clone();
+
+        Callback::from(move |_| {
+            run_heap_operation(&|heap| {
+                heap.pop();
+            })
+        })
+    };
+
+    let push_random_batch = {
+        let run_heap_operation = run_heap_operation.clone();
+        let batch_size = *batch_size;
+
+        Callback::from(move |_| {
+            let values = random_batch(batch_size);
+            run_heap_operation(&|heap| {
+                for value in &values {
+                    heap.push(*value);
+                }
+            })
+        })
+    };
+
+    let clear_heap = {
+        let run_heap_operation = run_heap_operation.clone();
+        let heap_order = *heap_order;
+
+        Callback::from(move |_| {
+            run_heap_operation(&|heap| *heap = BinaryHeapVisual::new(heap_order))
+        })
+    };
+
+    let change_data_structure = {
+        let data_structure = data_structure.clone();
+        let tree_steps = tree_steps.clone();
+        let heap_steps = heap_steps.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(structure) = DataStructure::ALL
+                .iter()
+                .copied()
+                .find(|structure| structure.name() == name)
+            {
+                // Show where the latest operation of the other structure ended
+                step_index.set(match structure {
+                    DataStructure::BinarySearchTree => tree_steps.borrow().len(),
+                    DataStructure::BinaryHeap => heap_steps.borrow().len(),
+                });
+                data_structure.set(structure);
+            }
+        })
+    };
+
+    let change_value = {
+        let value = value.clone();
+        Callback::from(move |new_value| value.set(new_value))
+    };
+
+    let change_batch_size = {
+        let batch_size = batch_size.clone();
+        Callback::from(move |new_batch_size| batch_size.set(new_batch_size))
+    };
+
+    let change_traversal_order = {
+        let traversal_order = traversal_order.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(order) = TraversalOrder::ALL
+                .iter()
+                .copied()
+                .find(|order| order.name() == name)
+            {
+                traversal_order.set(order);
+            }
+        })
+    };
+
+    // Rebuilds the heap in the new order, which plays back how heapify works
+    let change_heap_order = {
+        let heap_order = heap_order.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(order) = HeapOrder::ALL
+                .iter()
+                .copied()
+                .find(|order| order.name() == name)
+            {
+                run_heap_operation(&|heap| {
+                    *heap = BinaryHeapVisual::heapify(heap.as_slice().to_vec(), order)
+                });
+                heap_order.set(order);
+            }
+        })
+    };
+
+    let on_change_step = {
+        let step_index = step_index.clone();
+        Callback::from(move |index| step_index.set(index))
+    };
+
+    let (view, narration, steps_len) = match *data_structure {
+        DataStructure::BinarySearchTree => {
+            // The tree after the steps up to the slider, and what the current operation has visited and output so far
+            let steps = tree_steps.borrow();
+            let shown_steps = &steps[..(*step_index).min(steps.len())];
+            let mut shown_tree = tree_before.borrow().clone();
+            run_bst_steps(&mut shown_tree, shown_steps);
+            let mut visited = vec![];
+            let mut output = vec![];
+            for step in shown_steps {
+                match step {
+                    BstStep::Visit(value) => visited.push(*value),
+                    BstStep::Output(value) => output.push(value.to_string()),
+                    // Each insertion of a batch starts from the root again
+                    _ => visited.clear(),
+                }
+            }
+            let step = shown_steps.last().cloned();
+            let narration = match &step {
+                Some(step) => step.to_string(),
+                None => format!(
+                    "{} values, height {}",
+                    tree.borrow().len(),
+                    tree.borrow().height()
+                ),
+            };
+
+            let view = html! {
+                <>
+                    <BstView<i32> tree={shown_tree} {step} {visited} />
+                    {
+                        if output.is_empty() {
+                            html! {}
+                        } else {
+                            html! { <p class="bst-output">{ format!("Output: {}", output.join(", ")) }</p> }
+                        }
+                    }
+                </>
+            };
+            (view, narration, steps.len())
+        }
+        DataStructure::BinaryHeap => {
+            let steps = heap_steps.borrow();
+            let shown_steps = &steps[..(*step_index).min(steps.len())];
+            let mut items = heap_before.borrow().clone();
+            run_heap_steps(&mut items, shown_steps);
+            let step = shown_steps.last().cloned().unwrap_or_default();
+            let highlighted = step
+                .iter()
+                .flat_map(|command| match command {
+                    HeapStep::Push(_) => vec![items.len() - 1],
+                    HeapStep::Swap(from, to) => vec![*from, *to],
+                    HeapStep::Pop => vec![],
+                })
+                .collect::<Vec<usize>>();
+            let narration = if step.is_empty() {
+                match heap.borrow().peek() {
+                    Some(root) => format!("{} items, the root is {}", heap.borrow().len(), root),
+                    None => "0 items".to_string(),
+                }
+            } else {
+                step.iter()
+                    .map(|command| command.to_string())
+                    .collect::<Vec<String>>()
+                    .join("; ")
+            };
+
+            let view = html! {
+                <HeapView
+                    heap_len={items.len()}
+                    items={items.iter().map(|item| item.to_string()).collect::<Vec<String>>()}
+                    {highlighted}
+                />
+            };
+            (view, narration, steps.len())
+        }
+    };
+
+    html! {
+        <div class="page" id="DataStructures">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <SelectInput
+                        title="Data structure"
+                        options={DataStructure::ALL.iter().map(|structure| structure.name().to_string()).collect::<Vec<String>>()}
+                        selected_value={data_structure.name().to_string()}
+                        onchange={change_data_structure}
+                    />
+                    <IntInput<i32>
+                        title="Value"
+                        value={*value}
+                        oninput={change_value}
+                    />
+                    <IntInput<usize>
+                        title="Batch size"
+                        value={*batch_size}
+                        oninput={change_batch_size}
+                        min={1}
+                    />
+                    <PlaybackTimeInput />
+                </Collapsible>
+
+                {
+                    match *data_structure {
+                        DataStructure::BinarySearchTree => html! {
+                            <Collapsible title="Operations" open={true} class="config-section">
+                                <Button title="Insert" onclick={insert} />
+                                <Button title="Delete" onclick={remove} />
+                                <Button title="Search" onclick={search} />
+                                <Button title="Insert random batch" onclick={insert_random_batch} />
+                                <SelectInput
+                                    title="Traversal order"
+                                    options={TraversalOrder::ALL.iter().map(|order| order.name().to_string()).collect::<Vec<String>>()}
+                                    selected_value={traversal_order.name().to_string()}
+                                    onchange={change_traversal_order}
+                                />
+                                <Button title="Traverse" onclick={traverse} />
+                                <Button title="Clear" onclick={clear_tree} />
+                            </Collapsible>
+                        },
+                        DataStructure::BinaryHeap => html! {
+                            <Collapsible title="Operations" open={true} class="config-section">
+                                <SelectInput
+                                    title="Heap order"
+                                    options={HeapOrder::ALL.iter().map(|order| order.name().to_string()).collect::<Vec<String>>()}
+                                    selected_value={heap_order.name().to_string()}
+                                    onchange={change_heap_order}
+                                />
+                                <Button title="Push" onclick={push} />
+                                <Button title="Pop root" onclick={pop} />
+                                <Button title="Push random batch" onclick={push_random_batch} />
+                                <Button title="Clear" onclick={clear_heap} />
+                            </Collapsible>
+                        },
+                    }
+                }
+            </Sidebar>
+
+            <main>
+                { view }
+                <p class="step-narration">{ narration }</p>
+                <StepSlider
+                    label={format!("Steps ({}/{})", *step_index, steps_len)}
+                    active_step_index={*step_index}
+                    max={steps_len}
+                    on_change={on_change_step}
+                    playback_time={settings.playback_time}
+                />
+            </main>
+        </div>
+    }
+}
+
+/// Generates `len` random values for inserting at once.
+fn random_batch(len: usize) -> Vec<i32> {
+    let mut rng = rand::thread_rng();
+    (0..len)
+        .map(|_| rng.gen_range(0..=MAX_RANDOM_VALUE))
+        .collect()
+}
@@ -0,0 +1,146 @@
+use string_algorithms::{alignment, DpProblem, DpStep, DpTable};
+use yew::prelude::*;
+use yew_hooks::use_title;
+
+use crate::{
+    components::{
+        collapsible::Collapsible,
+        dynamic_programming::dp_table_view::DpTableView,
+        input_items::{PlaybackTimeInput, SelectInput, TextInput},
+        sidebar::Sidebar,
+        step_slider::StepSlider,
+    },
+    hooks::use_settings::use_settings,
+};
+
+/// The longest strings that are compared, so that their table fits on the page.
+const MAX_STRING_LEN: usize = 16;
+
+/// Fills the dynamic programming table of two strings cell by cell and traces the optimal alignment back through it.
+#[function_component(DynamicProgrammingPage)]
+pub fn dynamic_programming_page() -> Html {
+    let problem = use_state_eq(|| DpProblem::EditDistance);
+    let a = use_state_eq(|| "kitten".to_string());
+    let b = use_state_eq(|| "sitting".to_string());
+    let step_index = use_state(|| 0);
+    let settings = use_settings();
+
+    use_title(format!("{} - Dynamic programming", problem.name()));
+
+    let change_problem = {
+        let problem = problem.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(new_problem) = DpProblem::ALL
+                .iter()
+                .copied()
+                .find(|problem| problem.name() == name)
+            {
+                problem.set(new_problem);
+                step_index.set(0);
+            }
+        })
+    };
+
+    let change_string = |string: UseStateHandle<String>| {
+        let step_index = step_index.clone();
+
+        Callback::from(move |new_string: String| {
+            string.set(new_string);
+            step_index.set(0);
+        })
+    };
+
+    let on_change_step = {
+        let step_index = step_index.clone();
+        Callback::from(move |index| step_index.set(index))
+    };
+
+    let a_chars = a.chars().take(MAX_STRING_LEN).collect::<Vec<char>>();
+    let b_chars = b.chars().take(MAX_STRING_LEN).collect::<Vec<char>>();
+    let length_error = |string: &str| {
+        (string.chars().count() > MAX_STRING_LEN)
+            .then(|| format!("Only the first {} characters are used", MAX_STRING_LEN))
+    };
+
+    let mut steps = vec![];
+    let answer = problem.dp_fn()(&a_chars, &b_chars, &mut steps);
+    let shown_steps = &steps[..(*step_index).min(steps.len())];
+    let table = DpTable::after(a_chars.len(), b_chars.len(), shown_steps);
+    let step = shown_steps.last().copied();
+
+    // The characters of a cell are named, since the step only knows its row and column
+    let narration = match step {
+        Some(step) if shown_steps.len() < steps.len() => match step {
+            DpStep::Fill(row, column, ..) if row > 0 && column > 0 => format!(
+                "Comparing '{}' and '{}': {}",
+                a_chars[row - 1],
+                b_chars[column - 1],
+                step
+            ),
+            _ => step.to_string(),
+        },
+        _ if shown_steps.is_empty() => {
+            format!(
+                "Filling the table row by row to find the {}",
+                problem.name().to_lowercase()
+            )
+        }
+        _ => match *problem {
+            DpProblem::EditDistance => format!("The edit distance is {}", answer),
+            DpProblem::LongestCommonSubsequence => {
+                let subsequence = alignment(&a_chars, &b_chars, &table.path)
+                    .into_iter()
+                    .filter_map(|(a_char, b_char)| a_char.filter(|_| a_char == b_char))
+                    .collect::<String>();
+                format!(
+                    "The longest common subsequence is \"{}\" with length {}",
+                    subsequence, answer
+                )
+            }
+        },
+    };
+
+    html! {
+        <div class="page" id="DynamicProgramming">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <SelectInput
+                        title="Problem"
+                        options={DpProblem::ALL.iter().map(|problem| problem.name().to_string()).collect::<Vec<String>>()}
+                        selected_value={problem.name().to_string()}
+                        onchange={change_problem}
+                    />
+                    <TextInput
+                        title="First string"
+                        value={(*a).clone()}
+                        error={length_error(&a)}
+                        oninput={change_string(a.clone())}
+                    />
+                    <TextInput
+                        title="Second string"
+                        value={(*b).clone()}
+                        error={length_error(&b)}
+                        oninput={change_string(b.clone())}
+                    />
+                    <PlaybackTimeInput />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <DpTableView a={a_chars} b={b_chars} {table} {step} />
+                <p class="step-narration">{ narration }</p>
+                <StepSlider
+                    label={format!("Steps ({}/{})", shown_steps.len(), steps.len())}
+                    active_step_index={*step_index}
+                    max={steps.len()}
+                    on_change={on_change_step}
+                    playback_time={settings.playback_time}
+                />
+            </main>
+        </div>
+    }
+}
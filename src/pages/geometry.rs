@@ -0,0 +1,147 @@
+use geometry::{HullAlgorithm, HullState, Point};
+use rand::Rng;
+use yew::prelude::*;
+use yew_hooks::use_title;
+
+use crate::{
+    components::{
+        collapsible::Collapsible,
+        geometry::point_canvas::PointCanvas,
+        input_items::{Button, IntInput, PlaybackTimeInput, SelectInput},
+        sidebar::Sidebar,
+        step_slider::StepSlider,
+    },
+    hooks::use_settings::use_settings,
+};
+
+/// Scatters `count` random points, keeping them away from the edges of the unit square.
+fn random_points(count: usize) -> Vec<Point> {
+    let mut rng = rand::thread_rng();
+    (0..count)
+        .map(|_| Point::new(rng.gen_range(0.05..0.95), rng.gen_range(0.05..0.95)))
+        .collect()
+}
+
+/// Builds the convex hull of points that the user scatters or generates and plays back how the hull grows.
+#[function_component(GeometryPage)]
+pub fn geometry_page() -> Html {
+    let algorithm = use_state_eq(|| HullAlgorithm::GrahamScan);
+    let count = use_state_eq(|| 30);
+    let points = use_state_eq(|| random_points(30));
+    let step_index = use_state(|| 0);
+    let settings = use_settings();
+
+    use_title(format!("{} - Convex hull", algorithm.name()));
+
+    let change_algorithm = {
+        let algorithm = algorithm.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(new_algorithm) = HullAlgorithm::ALL
+                .iter()
+                .copied()
+                .find(|algorithm| algorithm.name() == name)
+            {
+                algorithm.set(new_algorithm);
+                step_index.set(0);
+            }
+        })
+    };
+
+    let change_count = {
+        let count = count.clone();
+        Callback::from(move |new_count| count.set(new_count))
+    };
+
+    let randomize_points = {
+        let points = points.clone();
+        let step_index = step_index.clone();
+        let count = *count;
+
+        Callback::from(move |_| {
+            points.set(random_points(count));
+            step_index.set(0);
+        })
+    };
+
+    let clear_points = {
+        let points = points.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |_| {
+            points.set(vec![]);
+            step_index.set(0);
+        })
+    };
+
+    let add_point = {
+        let points = points.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |point| {
+            let mut new_points = (*points).clone();
+            new_points.push(point);
+            points.set(new_points);
+            step_index.set(0);
+        })
+    };
+
+    let on_change_step = {
+        let step_index = step_index.clone();
+        Callback::from(move |index| step_index.set(index))
+    };
+
+    let mut steps = vec![];
+    let hull = algorithm.hull_fn()(&points, &mut steps);
+    let shown_steps = &steps[..(*step_index).min(steps.len())];
+    let state = HullState::after(shown_steps);
+    let finished = shown_steps.len() == steps.len();
+
+    // The result is told once every step has been played
+    let narration = match shown_steps.last() {
+        Some(step) if !finished => step.to_string(),
+        _ if points.is_empty() => "Click the canvas to add points".to_string(),
+        _ if !finished => "Press play to build the hull".to_string(),
+        _ => format!("The hull has {} of the {} points", hull.len(), points.len()),
+    };
+
+    html! {
+        <div class="page" id="Geometry">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <SelectInput
+                        title="Algorithm"
+                        options={HullAlgorithm::ALL.iter().map(|algorithm| algorithm.name().to_string()).collect::<Vec<String>>()}
+                        selected_value={algorithm.name().to_string()}
+                        onchange={change_algorithm}
+                    />
+                    <IntInput<usize>
+                        title="Point count"
+                        value={*count}
+                        oninput={change_count}
+                        min={1}
+                        max={500}
+                    />
+                    <Button title="Randomize points" onclick={randomize_points} />
+                    <Button title="Clear points" onclick={clear_points} />
+                    <PlaybackTimeInput />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <PointCanvas points={(*points).clone()} {state} {finished} on_add={add_point} />
+                <p class="step-narration">{ narration }</p>
+                <StepSlider
+                    label={format!("Steps ({}/{})", shown_steps.len(), steps.len())}
+                    active_step_index={*step_index}
+                    max={steps.len()}
+                    on_change={on_change_step}
+                    playback_time={settings.playback_time}
+                />
+            </main>
+        </div>
+    }
+}
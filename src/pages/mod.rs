@@ -1,3 +1,10 @@
+pub mod backtracking;
+pub mod benchmark;
+pub mod data_structures;
+pub mod dynamic_programming;
+pub mod geometry;
 pub mod home;
 pub mod pathfinding;
+pub mod searching;
 pub mod sorting;
+pub mod strings;
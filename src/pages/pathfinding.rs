@@ -1,28 +1,45 @@
-use crate::components::{
-    algo_desc::AlgoDesc,
-    collapsible::Collapsible,
-    pathfinding::{toolbar::*, *},
-    sidebar::Sidebar,
-    step_slider::StepSlider,
+use crate::{
+    components::{
+        algo_desc::AlgoDesc,
+        collapsible::Collapsible,
+        input_items::{Button, FileInput, SelectInput},
+        pathfinding::{toolbar::*, *},
+        sidebar::Sidebar,
+        step_slider::StepSlider,
+    },
+    hooks::use_settings::{use_settings, SettingsHandle},
+    utils::{
+        download_file,
+        grid_history::{CellsEdit, GridHistory, MAX_HISTORY_DEPTH},
+        wall_painting::WallPainter,
+    },
 };
 use pathfinding::{
     generate_graph,
-    graph::AdjacencyList,
-    maze_generation::{recursive_division, MazeGenerationResult, MazeGenerationStep},
-    pathfinding_algorithms, run_pathfinding, Coord, Edge, PathfindingResult, PathfindingStep,
-    PathfindingSteps, Vertex, VertexState,
+    graph::{AdjacencyList, GraphEdit, GraphFormat, RandomGraphKind},
+    heuristics::Heuristic,
+    maze_generation::{MazeAlgorithm, MazeGenerationResult, MazeGenerationStep},
+    pathfinding_algorithms,
+    playback::SearchPlayback,
+    run_pathfinding, Coord, Edge, PathfindingResult, PathfindingSteps, Vertex, VertexState,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
     rc::Rc,
 };
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 use yew::prelude::*;
-use yew_hooks::use_title;
+use yew_hooks::{use_event_with_window, use_title};
 use yew_router::prelude::*;
 
 pub type EdgeType = f32;
 
+/// How much more it costs to enter a mud cell than an empty cell.
+pub const MUD_WEIGHT: f64 = 4.0;
+
 type PathfindingFunc<V, E> =
     fn(AdjacencyList<V, E>, V, V, PathfindingSteps<V>) -> PathfindingResult<V, E>;
 
@@ -30,12 +47,15 @@ type PathfindingFunc<V, E> =
 pub struct PathfindingAlgorithm<V: Vertex, E: Edge> {
     pub name: String,
     find_path: PathfindingFunc<V, E>,
+    /// Whether the search is guided by the [`Heuristic`] of the config, in which case it's run with A* instead of `find_path`.
+    pub uses_heuristic: bool,
 }
 impl<V: Vertex, E: Edge> PathfindingAlgorithm<V, E> {
     pub fn new(name: &str, find_path: PathfindingFunc<V, E>) -> Self {
         Self {
             name: name.to_string(),
             find_path,
+            uses_heuristic: false,
         }
     }
     /// Finds a path from `start` to `end`.
@@ -49,11 +69,36 @@ impl<V: Vertex, E: Edge> PathfindingAlgorithm<V, E> {
         run_pathfinding(graph, start, end, self.find_path)
     }
 }
+impl<E: Edge> PathfindingAlgorithm<Coord, E> {
+    /// Finds a path from `start` to `end` and splits the search into frames for animating the exploration and the path separately.
+    /// The `heuristic` is only used if the algorithm [uses one](PathfindingAlgorithm::uses_heuristic).
+    pub fn search_playback(
+        &self,
+        graph: &AdjacencyList<Coord, E>,
+        start: Coord,
+        end: Coord,
+        heuristic: Heuristic,
+    ) -> SearchPlayback<Coord> {
+        let result = if self.uses_heuristic {
+            pathfinding_algorithms::a_star_with_heuristic(
+                graph.clone(),
+                start,
+                end,
+                |vertex| heuristic.estimate(vertex, &end),
+                PathfindingSteps::new(vec![]),
+            )
+        } else {
+            (self.find_path)(graph.clone(), start, end, PathfindingSteps::new(vec![]))
+        };
+        SearchPlayback::from_result(result)
+    }
+}
 impl<E: Edge> Default for PathfindingAlgorithm<Coord, E> {
     fn default() -> Self {
         Self {
             name: String::from("Dijkstra"),
             find_path: pathfinding_algorithms::dijkstra::<Coord, E>,
+            uses_heuristic: false,
         }
     }
 }
@@ -64,12 +109,19 @@ pub fn get_pathfinding_algorithms<V: Vertex, E: Edge>(
     BTreeMap::from([
         (
             "a*",
-            PathfindingAlgorithm::new("A*", pathfinding_algorithms::a_star::<V, E>),
+            PathfindingAlgorithm {
+                uses_heuristic: true,
+                ..PathfindingAlgorithm::new("A*", pathfinding_algorithms::a_star::<V, E>)
+            },
         ),
         (
             "dijkstra",
             PathfindingAlgorithm::new("Dijkstra", pathfinding_algorithms::dijkstra::<V, E>),
         ),
+        (
+            "bfs",
+            PathfindingAlgorithm::new("BFS", pathfinding_algorithms::bfs::<V, E>),
+        ),
         (
             "dfs",
             PathfindingAlgorithm::new("DFS", pathfinding_algorithms::dfs::<V, E>),
@@ -110,6 +162,18 @@ pub struct PathfindingConfig<E: Edge> {
     pub graph_width: usize,
     pub graph_height: usize,
     pub move_diagonally: bool,
+    /// The heuristic of the algorithms that [use one](PathfindingAlgorithm::uses_heuristic).
+    pub heuristic: Heuristic,
+    /// The algorithm that generates the mazes of the "Generate maze" button.
+    pub maze_algorithm: MazeAlgorithm,
+    /// The kind of graph that the "Generate graph" button generates.
+    pub random_graph: RandomGraphKind,
+    /// The chance of each edge of the random graphs that [leave their edges to chance](RandomGraphKind::uses_edge_probability).
+    pub edge_probability: f64,
+    /// The smallest weight of the edges of the random graphs.
+    pub min_weight: f64,
+    /// The largest weight of the edges of the random graphs.
+    pub max_weight: f64,
     pub playback_time: f32,
 }
 impl<E: Edge> Default for PathfindingConfig<E> {
@@ -119,10 +183,103 @@ impl<E: Edge> Default for PathfindingConfig<E> {
             graph_width: 25,
             graph_height: 25,
             move_diagonally: false,
+            heuristic: Heuristic::default(),
+            maze_algorithm: MazeAlgorithm::default(),
+            random_graph: RandomGraphKind::default(),
+            edge_probability: 0.01,
+            min_weight: 1.0,
+            max_weight: 5.0,
             playback_time: 5.0,
         }
     }
 }
+impl<E: Edge> PathfindingConfig<E> {
+    /// Sets the parts of the config that are in the `settings`, ignoring a heuristic, maze algorithm
+    /// or random graph kind that doesn't exist anymore.
+    pub fn apply_settings(&mut self, settings: &PathfindingSettings) {
+        self.set_graph_width(settings.graph_width);
+        self.set_graph_height(settings.graph_height);
+        self.move_diagonally = settings.move_diagonally;
+        if let Some(heuristic) = Heuristic::ALL
+            .iter()
+            .copied()
+            .find(|heuristic| heuristic.name() == settings.heuristic)
+        {
+            self.heuristic = heuristic;
+        }
+        if let Some(maze_algorithm) = MazeAlgorithm::ALL
+            .iter()
+            .copied()
+            .find(|maze_algorithm| maze_algorithm.name() == settings.maze_algorithm)
+        {
+            self.maze_algorithm = maze_algorithm;
+        }
+        if let Some(kind) = RandomGraphKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.name() == settings.random_graph)
+        {
+            self.random_graph = kind;
+        }
+        self.edge_probability = settings.edge_probability.clamp(0.0, 1.0);
+        self.min_weight = settings.min_weight;
+        self.max_weight = settings.max_weight;
+        self.playback_time = settings.playback_time;
+    }
+    /// Sets the width of the grid and returns `true` if it changed. Grids narrower than 2 cells are ignored.
+    pub fn set_graph_width(&mut self, graph_width: usize) -> bool {
+        let changed = graph_width > 1 && graph_width != self.graph_width;
+        if changed {
+            self.graph_width = graph_width;
+        }
+        changed
+    }
+    /// Sets the height of the grid and returns `true` if it changed. Grids shorter than 2 cells are ignored.
+    pub fn set_graph_height(&mut self, graph_height: usize) -> bool {
+        let changed = graph_height > 1 && graph_height != self.graph_height;
+        if changed {
+            self.graph_height = graph_height;
+        }
+        changed
+    }
+    pub fn toggle_move_diagonally(&mut self) {
+        self.move_diagonally = !self.move_diagonally;
+    }
+    /// Generates the grid graph described by the config, leaving out the `walls`.
+    /// Entering a `mud` cell costs [`MUD_WEIGHT`] more than entering an empty cell.
+    pub fn generate_graph(
+        &self,
+        walls: &BTreeSet<Coord>,
+        mud: &BTreeSet<Coord>,
+    ) -> AdjacencyList<Coord, E> {
+        let mut graph = generate_graph(
+            self.graph_width,
+            self.graph_height,
+            self.move_diagonally,
+            walls,
+        );
+        let mud_weight = E::from(MUD_WEIGHT).unwrap();
+        for (from, to, weight) in graph.edges() {
+            if mud.contains(&to) {
+                graph.add_edge_directed(from, to, weight + mud_weight);
+            }
+        }
+        graph
+    }
+    /// Generates a random graph of the [kind](PathfindingConfig::random_graph) of the config whose vertices are the cells of the grid.
+    /// The weights are drawn from between the min and the max weight, even if the min is larger.
+    pub fn generate_random_graph(&self, seed: u64) -> AdjacencyList<Coord, E> {
+        let weights = self.min_weight.min(self.max_weight)..=self.min_weight.max(self.max_weight);
+        self.random_graph.generate(
+            self.graph_width,
+            self.graph_height,
+            self.move_diagonally,
+            self.edge_probability,
+            weights,
+            seed,
+        )
+    }
+}
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct PathfindingPageProps {
@@ -130,13 +287,63 @@ pub struct PathfindingPageProps {
     pub algorithm: String,
 }
 
+/// The parts of a [`PathfindingConfig`] that are kept in the settings. The algorithm is left out, since it's in the URL.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PathfindingSettings {
+    pub graph_width: usize,
+    pub graph_height: usize,
+    pub move_diagonally: bool,
+    /// The name of the [`Heuristic`].
+    pub heuristic: String,
+    /// The name of the [`MazeAlgorithm`].
+    pub maze_algorithm: String,
+    /// The name of the [`RandomGraphKind`].
+    pub random_graph: String,
+    pub edge_probability: f64,
+    pub min_weight: f64,
+    pub max_weight: f64,
+    pub playback_time: f32,
+}
+impl Default for PathfindingSettings {
+    fn default() -> Self {
+        Self::from(&PathfindingConfig::<EdgeType>::default())
+    }
+}
+impl<E: Edge> From<&PathfindingConfig<E>> for PathfindingSettings {
+    fn from(config: &PathfindingConfig<E>) -> Self {
+        Self {
+            graph_width: config.graph_width,
+            graph_height: config.graph_height,
+            move_diagonally: config.move_diagonally,
+            heuristic: config.heuristic.name().to_string(),
+            maze_algorithm: config.maze_algorithm.name().to_string(),
+            random_graph: config.random_graph.name().to_string(),
+            edge_probability: config.edge_probability,
+            min_weight: config.min_weight,
+            max_weight: config.max_weight,
+            playback_time: config.playback_time,
+        }
+    }
+}
+
+/// Updates the pathfinding settings unless they are already `new_settings`, which would render every component that uses them.
+fn save_settings(settings: &SettingsHandle, new_settings: &PathfindingSettings) {
+    if settings.pathfinding != *new_settings {
+        settings.update(|settings| settings.pathfinding = new_settings.clone());
+    }
+}
+
 #[function_component(PathfindingPage)]
 pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
+    let settings = use_settings();
     let config = {
         let algorithm_name = props.algorithm.to_string();
+        let settings = settings.clone();
 
-        use_mut_ref(|| {
-            let mut config = PathfindingConfig::default();
+        use_mut_ref(move || {
+            let mut config = PathfindingConfig::<EdgeType>::default();
+            config.apply_settings(&settings.pathfinding);
             if let Some(algorithm) = get_pathfinding_algorithms().get(algorithm_name.as_str()) {
                 config.algorithm = algorithm.to_owned();
             }
@@ -144,26 +351,46 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
         })
     };
 
-    let start = use_state_eq(|| Coord::new(2, 2));
-    let end = use_state_eq(|| Coord::new(22, 22));
+    // The start and the end are kept on the grid, which can be smaller than the default one in the saved settings
+    let corner = |x: isize, y: isize| {
+        let config = config.borrow();
+        Coord::new(
+            x.min(config.graph_width as isize - 1),
+            y.min(config.graph_height as isize - 1),
+        )
+    };
+    let start = use_state_eq(|| corner(2, 2));
+    let end = use_state_eq(|| corner(22, 22));
 
     let active_tool = use_state_eq(|| PathTool::Wall);
 
     let path = use_mut_ref(Vec::<Coord>::new);
     let walls = use_mut_ref(BTreeSet::new);
+    let mud = use_mut_ref(BTreeSet::new);
+    // The drag over the grid that is in progress, if any
+    let drag = use_mut_ref(|| None::<GridDrag>);
 
     let graph = use_mut_ref(|| {
-        generate_graph::<EdgeType>(
-            config.borrow().graph_width,
-            config.borrow().graph_height,
-            config.borrow().move_diagonally,
-            &walls.borrow(),
-        )
+        config
+            .borrow()
+            .generate_graph(&walls.borrow(), &mud.borrow())
     });
 
-    let pathfinding_steps = use_mut_ref(|| PathfindingSteps::<Coord>::new(vec![]));
+    // Incremented whenever the graph changes, so that the graph editor doesn't need to compare the whole graph
+    let graph_version = use_mut_ref(|| 0u32);
+
+    let search_playback = use_mut_ref(SearchPlayback::<Coord>::default);
     let maze_gen_steps = use_mut_ref(Vec::<MazeGenerationStep>::new);
 
+    let history = use_mut_ref(|| GridHistory::<EdgeType>::new(MAX_HISTORY_DEPTH));
+    // The size of the grid that the edits in the history were made on
+    let history_grid_size = use_mut_ref(|| {
+        let config = config.borrow();
+        (config.graph_width, config.graph_height)
+    });
+    // Whether the graph was generated with diagonal moves, since the config has already changed by the time the graph is generated again
+    let graph_move_diagonally = use_mut_ref(|| config.borrow().move_diagonally);
+
     let graph_at_pathfinding_step = use_mut_ref(BTreeMap::<Coord, VertexState>::new);
     let walls_at_maze_gen_step = use_mut_ref(BTreeSet::new);
 
@@ -180,62 +407,100 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
         let graph = Rc::clone(&graph);
 
         move |start: Coord, end: Coord| {
+            let config = config.borrow();
             config
-                .borrow()
                 .algorithm
-                .find_path(&graph.borrow(), start, end)
-                .0
+                .search_playback(&graph.borrow(), start, end, config.heuristic)
         }
     };
 
     let update_pathfinding_step = {
-        let steps = Rc::clone(&pathfinding_steps);
+        let search_playback = Rc::clone(&search_playback);
         let path = Rc::clone(&path);
         let graph_at_step = graph_at_pathfinding_step.clone();
         let step_i = pathfinding_step_index.clone();
 
-        move |pathfinding_result: PathfindingResult<Coord, EdgeType>| {
-            let old_step_count = steps.borrow().len();
-
-            *path.borrow_mut() = pathfinding_result.path;
-            *steps.borrow_mut() = pathfinding_result.steps;
-
-            let old_step_i = *step_i;
-            let new_step_i = if old_step_i >= old_step_count {
-                steps.borrow().len()
+        move |playback: SearchPlayback<Coord>| {
+            // Stay at the end if the previous search was fully shown, so that editing the grid updates the result immediately
+            let new_step_i = if *step_i >= search_playback.borrow().len() {
+                playback.len()
             } else {
                 0
             };
+            *search_playback.borrow_mut() = playback;
 
-            update_graph_at_pathfinding_step(
+            show_search_frame(
                 &mut graph_at_step.borrow_mut(),
-                &steps.borrow().steps,
+                &mut path.borrow_mut(),
+                &search_playback.borrow(),
                 new_step_i,
-                old_step_i.min(steps.borrow().len()),
-                true,
             );
 
             step_i.set(new_step_i);
         }
     };
 
+    // Every edit of the grid or the graph goes through the history so that it can be undone.
+    // The walls and the mud are passed as they were before the edit if it changed them.
+    // Toggling diagonal moves is recorded along with the edit that connects the cells anew, so that undoing it also restores the config.
+    let apply_edit = {
+        let config = Rc::clone(&config);
+        let graph = Rc::clone(&graph);
+        let graph_version = Rc::clone(&graph_version);
+        let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
+        let history = Rc::clone(&history);
+        let graph_move_diagonally = Rc::clone(&graph_move_diagonally);
+
+        move |edit: GraphEdit<Coord, EdgeType>,
+              cells_before: Option<(BTreeSet<Coord>, BTreeSet<Coord>)>| {
+            let move_diagonally = config.borrow().move_diagonally;
+            let move_diagonally_before = graph_move_diagonally.replace(move_diagonally);
+            let cells = match cells_before {
+                Some(cells_before) => Some(cells_before),
+                None if move_diagonally != move_diagonally_before => {
+                    Some((walls.borrow().clone(), mud.borrow().clone()))
+                }
+                None => None,
+            }
+            .map(|(walls_before, mud_before)| CellsEdit {
+                walls: (walls_before, walls.borrow().clone()),
+                mud: (mud_before, mud.borrow().clone()),
+                move_diagonally: (move_diagonally_before, move_diagonally),
+            });
+            history
+                .borrow_mut()
+                .apply(&mut graph.borrow_mut(), edit, cells);
+            *graph_version.borrow_mut() += 1;
+        }
+    };
+
     // Config has been updated, update grah, path etc.
     let on_update_config = {
         let config = config.clone();
         let (start, end) = (*start, *end);
         let graph = Rc::clone(&graph);
         let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
+        let history = Rc::clone(&history);
+        let history_grid_size = Rc::clone(&history_grid_size);
+        let apply_edit = apply_edit.clone();
         let find_path = find_path.clone();
         let update_pathfinding_step = update_pathfinding_step.clone();
 
         Callback::from(move |_| {
-            let new_graph = generate_graph(
-                config.borrow().graph_width,
-                config.borrow().graph_height,
-                config.borrow().move_diagonally,
-                &walls.borrow(),
-            );
-            *graph.borrow_mut() = new_graph;
+            let config = config.borrow();
+            let new_graph = config.generate_graph(&walls.borrow(), &mud.borrow());
+            // Changing e.g. the heuristic doesn't change the graph, which shouldn't be recorded as an edit
+            if *graph.borrow() != new_graph {
+                apply_edit(GraphEdit::Replace(new_graph), None);
+            }
+            // The old edits were made on a grid of a different size, so they can't be undone on this one
+            let grid_size = (config.graph_width, config.graph_height);
+            if *history_grid_size.borrow() != grid_size {
+                history.borrow_mut().clear();
+                *history_grid_size.borrow_mut() = grid_size;
+            }
 
             update_pathfinding_step(find_path(start, end));
         })
@@ -268,18 +533,50 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
 
     use_title(format!("{} - Pathfinding", config.borrow().algorithm.name));
 
+    // Some of the controls change the config without rendering the page, so the settings are also saved when leaving the page.
+    // That uses the handle of the latest render, as an older one would overwrite the settings changed since with its own.
+    let latest_settings = use_mut_ref(|| settings.clone());
+    *latest_settings.borrow_mut() = settings.clone();
+    {
+        let settings = settings.clone();
+
+        use_effect_with_deps(
+            move |new_settings| {
+                save_settings(&settings, new_settings);
+                || ()
+            },
+            PathfindingSettings::from(&*config.borrow()),
+        );
+    }
+    {
+        let config = Rc::clone(&config);
+        let latest_settings = Rc::clone(&latest_settings);
+
+        use_effect_with_deps(
+            move |_| {
+                move || {
+                    save_settings(
+                        &latest_settings.borrow(),
+                        &PathfindingSettings::from(&*config.borrow()),
+                    )
+                }
+            },
+            (),
+        );
+    }
+
     let on_change_pathfinding_step = {
         let graph_at_pathfinding_step = graph_at_pathfinding_step.clone();
-        let pathfinding_steps = Rc::clone(&pathfinding_steps);
+        let path = Rc::clone(&path);
+        let search_playback = Rc::clone(&search_playback);
         let pathfinding_step_index = pathfinding_step_index.clone();
 
         Callback::from(move |val| {
-            update_graph_at_pathfinding_step(
+            show_search_frame(
                 &mut graph_at_pathfinding_step.borrow_mut(),
-                &pathfinding_steps.borrow().steps,
+                &mut path.borrow_mut(),
+                &search_playback.borrow(),
                 val,
-                *pathfinding_step_index,
-                false,
             );
             pathfinding_step_index.set(val);
         })
@@ -308,37 +605,75 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
     };
 
     let on_click_cell = {
+        let config = config.clone();
         let (start, end) = (start.clone(), end.clone());
         let active_tool = *active_tool;
         let graph = Rc::clone(&graph);
         let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
+        let drag = Rc::clone(&drag);
         let paused = paused.clone();
         let show_maze_gen_slider = show_maze_gen_slider.clone();
         let update_pathfinding_step = update_pathfinding_step.clone();
         let find_path = find_path.clone();
 
         Callback::from(move |vertex| {
-            if vertex != *start && vertex != *end {
-                if graph.borrow().hash_map.contains_key(&vertex) {
+            let mut drag = drag.borrow_mut();
+            let drag = drag.get_or_insert_with(|| {
+                // Dragging the start or the end moves it, whatever the active tool is
+                if vertex == *start {
+                    GridDrag::Move(PathTool::Start)
+                } else if vertex == *end {
+                    GridDrag::Move(PathTool::End)
+                } else {
                     match active_tool {
-                        PathTool::Start => {
+                        PathTool::Wall => GridDrag::Paint(
+                            PathTool::Wall,
+                            WallPainter::begin(vertex, &walls.borrow()),
+                            walls.borrow().clone(),
+                        ),
+                        PathTool::Mud => GridDrag::Paint(
+                            PathTool::Mud,
+                            WallPainter::begin(vertex, &mud.borrow()),
+                            mud.borrow().clone(),
+                        ),
+                        tool => GridDrag::Move(tool),
+                    }
+                }
+            });
+
+            match drag {
+                GridDrag::Paint(tool, painter, _) => {
+                    let cells = if *tool == PathTool::Wall {
+                        &walls
+                    } else {
+                        &mud
+                    };
+                    let config = config.borrow();
+                    if painter.paint_to(
+                        vertex,
+                        &mut cells.borrow_mut(),
+                        config.graph_width,
+                        config.graph_height,
+                        &[*start, *end],
+                    ) {
+                        paused.set(true);
+                        if *tool == PathTool::Wall {
+                            show_maze_gen_slider.set(false);
+                        }
+                    }
+                }
+                GridDrag::Move(tool) => {
+                    if vertex != *start && vertex != *end && graph.borrow().contains_vertex(&vertex)
+                    {
+                        if *tool == PathTool::Start {
                             start.set(vertex);
                             update_pathfinding_step(find_path(vertex, *end));
-                        }
-                        PathTool::End => {
+                        } else {
                             end.set(vertex);
                             update_pathfinding_step(find_path(*start, vertex));
                         }
-                        PathTool::Wall => {
-                            walls.borrow_mut().insert(vertex);
-                            paused.set(true);
-                            show_maze_gen_slider.set(false);
-                        }
                     }
-                } else if active_tool == PathTool::Wall {
-                    walls.borrow_mut().remove(&vertex);
-                    paused.set(true);
-                    show_maze_gen_slider.set(false);
                 }
             }
         })
@@ -347,24 +682,30 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
     let on_draw_end = {
         let config = config.clone();
         let (start, end) = (*start, *end);
-        let active_tool = *active_tool;
-        let graph = Rc::clone(&graph);
         let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
+        let drag = Rc::clone(&drag);
         let paused = paused.clone();
+        let apply_edit = apply_edit.clone();
         let update_pathfinding_step = update_pathfinding_step.clone();
         let find_path = find_path.clone();
 
         Callback::from(move |_| {
-            if active_tool == PathTool::Wall {
-                let new_graph = generate_graph(
-                    config.borrow().graph_width,
-                    config.borrow().graph_height,
-                    config.borrow().move_diagonally,
-                    &walls.borrow(),
-                );
-                *graph.borrow_mut() = new_graph;
+            // Moving the start or the end already searched again on every move
+            if let Some(GridDrag::Paint(tool, _, before)) = drag.borrow_mut().take() {
+                let cells_before = if tool == PathTool::Wall {
+                    (before, mud.borrow().clone())
+                } else {
+                    (walls.borrow().clone(), before)
+                };
+                if cells_before.0 != *walls.borrow() || cells_before.1 != *mud.borrow() {
+                    let new_graph = config
+                        .borrow()
+                        .generate_graph(&walls.borrow(), &mud.borrow());
+                    apply_edit(GraphEdit::Replace(new_graph), Some(cells_before));
 
-                update_pathfinding_step(find_path(start, end));
+                    update_pathfinding_step(find_path(start, end));
+                }
 
                 paused.set(false);
             }
@@ -374,26 +715,24 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
     let on_clear_walls = {
         let config = config.clone();
         let (start, end) = (*start, *end);
-        let graph = Rc::clone(&graph);
         let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
         let walls_at_maze_gen_step = Rc::clone(&walls_at_maze_gen_step);
         let maze_gen_steps = Rc::clone(&maze_gen_steps);
+        let apply_edit = apply_edit.clone();
         let update_pathfinding_step = update_pathfinding_step.clone();
         let find_path = find_path.clone();
         let show_maze_gen_slider = show_maze_gen_slider.clone();
 
         Callback::from(move |_| {
-            walls.borrow_mut().clear();
+            let cells_before = (walls.take(), mud.borrow().clone());
             walls_at_maze_gen_step.borrow_mut().clear();
             maze_gen_steps.borrow_mut().clear();
 
-            let new_graph = generate_graph(
-                config.borrow().graph_width,
-                config.borrow().graph_height,
-                config.borrow().move_diagonally,
-                &walls.borrow(),
-            );
-            *graph.borrow_mut() = new_graph;
+            let new_graph = config
+                .borrow()
+                .generate_graph(&walls.borrow(), &mud.borrow());
+            apply_edit(GraphEdit::Replace(new_graph), Some(cells_before));
 
             update_pathfinding_step(find_path(start, end));
 
@@ -401,19 +740,44 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
         })
     };
 
+    let on_clear_mud = {
+        let config = config.clone();
+        let (start, end) = (*start, *end);
+        let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
+        let apply_edit = apply_edit.clone();
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+
+        Callback::from(move |_| {
+            let cells_before = (walls.borrow().clone(), mud.take());
+
+            let new_graph = config
+                .borrow()
+                .generate_graph(&walls.borrow(), &mud.borrow());
+            apply_edit(GraphEdit::Replace(new_graph), Some(cells_before));
+
+            update_pathfinding_step(find_path(start, end));
+        })
+    };
+
     let on_generate_maze = {
         let config = config.clone();
         let (start, end) = (*start, *end);
         let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
         let show_maze_gen_slider = show_maze_gen_slider.clone();
         let walls_at_maze_gen_step = walls_at_maze_gen_step.clone();
         let maze_gen_steps = Rc::clone(&maze_gen_steps);
         let maze_gen_step_index = maze_gen_step_index.clone();
+        let apply_edit = apply_edit.clone();
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
 
         Callback::from(move |_| {
             // Generate maze
             let res = generate_maze(&config.borrow(), start, end);
-            *walls.borrow_mut() = res.walls;
+            let cells_before = (walls.replace(res.walls), mud.borrow().clone());
             *maze_gen_steps.borrow_mut() = res.steps;
 
             maze_gen_step_index.set(maze_gen_steps.borrow().len());
@@ -424,13 +788,10 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
             .walls
             .clone();
 
-            let new_graph = generate_graph(
-                config.borrow().graph_width,
-                config.borrow().graph_height,
-                config.borrow().move_diagonally,
-                &walls.borrow(),
-            );
-            *graph.borrow_mut() = new_graph;
+            let new_graph = config
+                .borrow()
+                .generate_graph(&walls.borrow(), &mud.borrow());
+            apply_edit(GraphEdit::Replace(new_graph), Some(cells_before));
 
             update_pathfinding_step(find_path(start, end));
 
@@ -438,6 +799,204 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
         })
     };
 
+    // Every cell is a vertex of the random graphs, so the walls and the mud are cleared. The generated weights are kept
+    // until the grid is edited, which generates the graph from the walls again.
+    let on_generate_graph = {
+        let config = config.clone();
+        let (start, end) = (*start, *end);
+        let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
+        let maze_gen_steps = Rc::clone(&maze_gen_steps);
+        let apply_edit = apply_edit.clone();
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+        let show_maze_gen_slider = show_maze_gen_slider.clone();
+
+        Callback::from(move |_| {
+            let cells_before = (walls.take(), mud.take());
+            maze_gen_steps.borrow_mut().clear();
+            let new_graph = config.borrow().generate_random_graph(rand::random());
+            apply_edit(GraphEdit::Replace(new_graph), Some(cells_before));
+
+            update_pathfinding_step(find_path(start, end));
+
+            show_maze_gen_slider.set(false);
+        })
+    };
+
+    // Like an imported graph, the edited graph is kept until the grid is edited
+    let edit_graph = {
+        let (start, end) = (*start, *end);
+        let apply_edit = apply_edit.clone();
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+
+        Callback::from(move |edit| {
+            apply_edit(edit, None);
+            update_pathfinding_step(find_path(start, end));
+        })
+    };
+
+    // Undoing or redoing restores the walls and the mud too, so the maze generation steps don't match them anymore
+    let step_history = |redo: bool| {
+        let (start, end) = (*start, *end);
+        let config = Rc::clone(&config);
+        let graph = Rc::clone(&graph);
+        let graph_move_diagonally = Rc::clone(&graph_move_diagonally);
+        let graph_version = Rc::clone(&graph_version);
+        let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
+        let history = Rc::clone(&history);
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+        let show_maze_gen_slider = show_maze_gen_slider.clone();
+
+        Callback::from(move |_| {
+            // The graph is borrowed only for the undo or redo, since searching it again borrows it too
+            let changed = {
+                let mut history = history.borrow_mut();
+                let mut config = config.borrow_mut();
+                let (graph, walls, mud) = (
+                    &mut graph.borrow_mut(),
+                    &mut walls.borrow_mut(),
+                    &mut mud.borrow_mut(),
+                );
+                let changed = if redo {
+                    history.redo(graph, walls, mud, &mut config.move_diagonally)
+                } else {
+                    history.undo(graph, walls, mud, &mut config.move_diagonally)
+                };
+                *graph_move_diagonally.borrow_mut() = config.move_diagonally;
+                changed
+            };
+            if changed {
+                *graph_version.borrow_mut() += 1;
+                update_pathfinding_step(find_path(start, end));
+                show_maze_gen_slider.set(false);
+            }
+        })
+    };
+    let undo = step_history(false);
+    let redo = step_history(true);
+
+    {
+        let (undo, redo) = (undo.clone(), redo.clone());
+
+        use_event_with_window("keydown", move |e: KeyboardEvent| {
+            // The inputs of the sidebar and any other editable element keep their own undo
+            let typing = e.target().is_some_and(|target| {
+                target.dyn_ref::<HtmlInputElement>().is_some()
+                    || target.dyn_ref::<HtmlTextAreaElement>().is_some()
+                    || target.dyn_ref::<HtmlSelectElement>().is_some()
+                    || target
+                        .dyn_ref::<HtmlElement>()
+                        .is_some_and(HtmlElement::is_content_editable)
+            });
+            if (e.ctrl_key() || e.meta_key()) && e.key().eq_ignore_ascii_case("z") && !typing {
+                e.prevent_default();
+                if e.shift_key() {
+                    redo.emit(());
+                } else {
+                    undo.emit(());
+                }
+            }
+        });
+    }
+
+    let select_start = {
+        let (start, end) = (start.clone(), *end);
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+
+        Callback::from(move |vertex| {
+            start.set(vertex);
+            update_pathfinding_step(find_path(vertex, end));
+        })
+    };
+
+    let select_end = {
+        let (start, end) = (*start, end.clone());
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+
+        Callback::from(move |vertex| {
+            end.set(vertex);
+            update_pathfinding_step(find_path(start, vertex));
+        })
+    };
+
+    let graph_format = use_state_eq(GraphFormat::default);
+
+    let change_graph_format = {
+        let graph_format = graph_format.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(format) = GraphFormat::ALL
+                .iter()
+                .copied()
+                .find(|format| format.name() == name)
+            {
+                graph_format.set(format);
+            }
+        })
+    };
+
+    let export_graph = {
+        let graph = Rc::clone(&graph);
+        let graph_format = *graph_format;
+
+        Callback::from(move |_| {
+            download_file(
+                &format!("graph.{}", graph_format.extension()),
+                &graph.borrow().to_format(graph_format),
+                graph_format.mime_type(),
+            );
+        })
+    };
+
+    let import_error: UseStateHandle<Option<String>> = use_state_eq(|| None);
+    // The cells of the grid that aren't in the imported graph become walls. The imported weights are kept
+    // until the grid is edited, which generates the graph from the walls again.
+    let import_graph = {
+        let config = config.clone();
+        let (start, end) = (*start, *end);
+        let walls = Rc::clone(&walls);
+        let mud = Rc::clone(&mud);
+        let maze_gen_steps = Rc::clone(&maze_gen_steps);
+        let apply_edit = apply_edit.clone();
+        let update_pathfinding_step = update_pathfinding_step.clone();
+        let find_path = find_path.clone();
+        let show_maze_gen_slider = show_maze_gen_slider.clone();
+        let import_error = import_error.clone();
+
+        Callback::from(move |text: String| {
+            let imported = match AdjacencyList::<Coord, EdgeType>::from_format(
+                GraphFormat::detect(&text),
+                &text,
+            ) {
+                Ok(imported) => imported,
+                Err(error) => {
+                    import_error.set(Some(error.to_string()));
+                    return;
+                }
+            };
+
+            let (width, height) = (config.borrow().graph_width, config.borrow().graph_height);
+            let new_walls = (0..height as isize)
+                .flat_map(|y| (0..width as isize).map(move |x| Coord::new(x, y)))
+                .filter(|cell| !imported.contains_vertex(cell))
+                .collect();
+            let cells_before = (walls.replace(new_walls), mud.take());
+            maze_gen_steps.borrow_mut().clear();
+            apply_edit(GraphEdit::Replace(imported), Some(cells_before));
+
+            update_pathfinding_step(find_path(start, end));
+
+            show_maze_gen_slider.set(false);
+            import_error.set(None);
+        })
+    };
+
     html! {
         <div class="page" id="Pathfinding">
             <Sidebar>
@@ -448,13 +1007,35 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
                 </Collapsible>
 
                 <Collapsible title="Walls" open={true} class="config-section">
-                    <WallControls {on_clear_walls} {on_generate_maze} />
+                    <WallControls<EdgeType> config={Rc::clone(&config)} {on_clear_walls} {on_clear_mud} {on_generate_maze} />
+                </Collapsible>
+
+                <Collapsible title="Random graph" open={false} class="config-section">
+                    <RandomGraphControls<EdgeType> config={Rc::clone(&config)} {on_generate_graph} />
+                </Collapsible>
+
+                <Collapsible title="Export and import" open={false} class="config-section">
+                    <SelectInput
+                        title="Graph format"
+                        options={GraphFormat::ALL.iter().map(|format| format.name().to_string()).collect::<Vec<String>>()}
+                        selected_value={graph_format.name().to_string()}
+                        onchange={change_graph_format}
+                    />
+                    <Button title="Export graph" onclick={export_graph} />
+                    <FileInput title="Import graph" accept=".dot,.gv,.graphml,.json" error={(*import_error).clone()} onload={import_graph} />
                 </Collapsible>
             </Sidebar>
 
             <main>
                 <div class="visualization">
-                    <PathToolbar active_tool={*active_tool} {on_tool_change} />
+                    <PathToolbar
+                        active_tool={*active_tool}
+                        {on_tool_change}
+                        can_undo={history.borrow().can_undo()}
+                        can_redo={history.borrow().can_redo()}
+                        on_undo={undo.clone()}
+                        on_redo={redo.clone()}
+                    />
                     <PathGrid
                         width={config.borrow().graph_width}
                         height={config.borrow().graph_height}
@@ -466,8 +1047,9 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
                                 Rc::clone(&walls)
                             }
                         }
+                        mud={Rc::clone(&mud)}
                         path={
-                            if *pathfinding_step_index >= pathfinding_steps.borrow().len() && path.borrow().len() > 0 {
+                            if !path.borrow().is_empty() {
                                 Some(Rc::clone(&path))
                             } else {
                                 None
@@ -480,9 +1062,16 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
                     />
 
                     <StepSlider
-                        label={format!("Pathfinding steps ({}/{})", *pathfinding_step_index, pathfinding_steps.borrow().len())}
+                        label={
+                            let phase = if search_playback.borrow().is_path_frame(*pathfinding_step_index) {
+                                "Path"
+                            } else {
+                                "Exploration"
+                            };
+                            format!("{} ({}/{})", phase, *pathfinding_step_index, search_playback.borrow().len())
+                        }
                         active_step_index={*pathfinding_step_index}
-                        max={pathfinding_steps.borrow().len()}
+                        max={search_playback.borrow().len()}
                         on_change={on_change_pathfinding_step}
                         playback_time={config.borrow().playback_time}
                         disabled={*paused}
@@ -506,68 +1095,71 @@ pub fn pathfinding_algorithms_page(props: &PathfindingPageProps) -> Html {
                     }
                 </div>
 
+                <Collapsible title="Graph editor" open={false} class="graph-editor-section">
+                    <GraphEditor<EdgeType>
+                        graph={VersionedGraph { graph: Rc::clone(&graph), version: *graph_version.borrow() }}
+                        vertex_states={graph_at_pathfinding_step.borrow().clone()}
+                        path={path.borrow().clone()}
+                        start={*start}
+                        end={*end}
+                        on_edit={edit_graph}
+                        on_select_start={select_start}
+                        on_select_end={select_end}
+                        can_undo={history.borrow().can_undo()}
+                        can_redo={history.borrow().can_redo()}
+                        on_undo={undo}
+                        on_redo={redo}
+                    />
+                </Collapsible>
+
+                <Collapsible title="Distance matrix (Floyd-Warshall)" open={false} class="distance-matrix-section">
+                    <DistanceMatrix<EdgeType> graph={graph.borrow().clone()} playback_time={config.borrow().playback_time} />
+                </Collapsible>
+
                 <AlgoDesc algorithm={config.borrow().algorithm.name.clone()} />
             </main>
         </div>
     }
 }
 
-fn update_graph_at_pathfinding_step<V: Vertex>(
-    graph: &mut BTreeMap<V, VertexState>,
-    steps: &[PathfindingStep<V>],
-    step_i: usize,
-    prev_step_i: usize,
-    force_from_start: bool,
-) {
-    // We use a reference to a slice to avoid cloning
-    let steps_to_execute: &[PathfindingStep<V>];
-    let run_from_start = force_from_start || step_i.abs_diff(0) < step_i.abs_diff(prev_step_i);
-    let mut execute_in_reverse = false;
-
-    // Get steps to execute
-    if !run_from_start {
-        // Execute steps between previous and current step indices
-        execute_in_reverse = step_i < prev_step_i;
-        if !execute_in_reverse {
-            // Going forwards in steps
-            steps_to_execute = &steps[prev_step_i..step_i];
-        } else {
-            // Going backwards in steps
-            steps_to_execute = &steps[step_i..prev_step_i];
-        }
-    } else {
-        // Execute all steps from start to current step index
-        graph.clear();
-        steps_to_execute = &steps[0..step_i];
-    }
-
-    execute_steps(graph, steps_to_execute, execute_in_reverse);
+/// What a drag over the grid does, which is decided by the cell where it starts.
+enum GridDrag {
+    /// Painting or erasing the walls or the mud, which were as given before the drag.
+    Paint(PathTool, WallPainter, BTreeSet<Coord>),
+    /// Moving the start or the end.
+    Move(PathTool),
 }
 
-fn execute_steps<V: Vertex>(
+/// Shows the frame at `index` of the playback, where the newest explored vertex is highlighted while exploring.
+/// The frontier of the search is shown around the explored vertices.
+fn show_search_frame<V: Vertex>(
     graph: &mut BTreeMap<V, VertexState>,
-    steps: &[PathfindingStep<V>],
-    reverse: bool,
+    path: &mut Vec<V>,
+    playback: &SearchPlayback<V>,
+    index: usize,
 ) {
-    if !reverse {
-        for step in steps.iter() {
-            for (vertex, state) in step.states.iter() {
-                graph.insert(*vertex, *state);
-            }
-        }
-    } else {
-        for step in steps.iter().rev() {
-            for (vertex, state) in step.states.iter() {
-                // Because we are going backwards in steps, we use the "previous" vertex states
-                let state = match *state {
-                    VertexState::NewVisited => VertexState::NotVisited,
-                    VertexState::NotVisited => VertexState::Visited,
-                    VertexState::Visited => VertexState::NewVisited,
-                };
-                graph.insert(*vertex, state);
-            }
+    let frame = playback.frame(index);
+
+    graph.clear();
+    graph.extend(
+        playback
+            .frontier(index)
+            .into_iter()
+            .map(|vertex| (vertex, VertexState::Frontier)),
+    );
+    graph.extend(
+        frame
+            .explored
+            .iter()
+            .map(|vertex| (*vertex, VertexState::Visited)),
+    );
+    if !playback.is_path_frame(index) {
+        if let Some(newest) = frame.explored.last() {
+            graph.insert(*newest, VertexState::NewVisited);
         }
     }
+
+    *path = frame.path.to_vec();
 }
 
 fn generate_maze<E: Edge>(
@@ -576,11 +1168,13 @@ fn generate_maze<E: Edge>(
     end: Coord,
 ) -> MazeGenerationResult {
     // Generate actual maze
-    let mut maze = recursive_division(
-        config.graph_width,
-        config.graph_height,
-        vec![MazeGenerationStep::new(BTreeSet::new())], // Empty initial step
-    );
+    let (width, height) = (config.graph_width, config.graph_height);
+    let mut maze = config
+        .maze_algorithm
+        .generate(width, height, rand::random());
+    // Empty initial step
+    maze.steps
+        .insert(0, MazeGenerationStep::new(BTreeSet::new()));
 
     // Remove walls around start and end cells to make sure the path isn't blocked.
 
@@ -620,3 +1214,68 @@ fn pathfinding_404_page(props: &Pathfinding404PageProps) -> Html {
         </>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_updates() {
+        let mut config = PathfindingConfig::<EdgeType>::default();
+
+        assert!(config.set_graph_width(10));
+        assert!(!config.set_graph_width(10));
+        assert!(!config.set_graph_width(1));
+        assert!(config.set_graph_height(4));
+        assert!(!config.set_graph_height(0));
+        assert_eq!((config.graph_width, config.graph_height), (10, 4));
+
+        let walls = BTreeSet::from([Coord::new(1, 1)]);
+        let mud = BTreeSet::from([Coord::new(2, 0)]);
+        let graph = config.generate_graph(&walls, &mud);
+        assert_eq!(graph.vertex_count(), 10 * 4 - 1);
+        assert!(!graph.is_neighbor(&Coord::new(0, 0), &Coord::new(1, 1)));
+        // Only entering the mud costs more, not leaving it
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(1, 0), &Coord::new(2, 0)),
+            Some(&5.0)
+        );
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(2, 0), &Coord::new(3, 0)),
+            Some(&1.0)
+        );
+
+        config.toggle_move_diagonally();
+        assert!(config.move_diagonally);
+        let graph = config.generate_graph(&BTreeSet::new(), &BTreeSet::new());
+        assert!(graph.is_neighbor(&Coord::new(0, 0), &Coord::new(1, 1)));
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let mut config = PathfindingConfig::<EdgeType>::default();
+        config.set_graph_width(12);
+        config.toggle_move_diagonally();
+        config.heuristic = Heuristic::ALL[1];
+        config.maze_algorithm = MazeAlgorithm::ALL[1];
+        config.random_graph = RandomGraphKind::ALL[1];
+        config.playback_time = 2.5;
+
+        let settings = PathfindingSettings::from(&config);
+        let mut restored = PathfindingConfig::<EdgeType>::default();
+        restored.apply_settings(&settings);
+        assert_eq!(restored, config);
+
+        // Unknown names and invalid values are ignored
+        let settings = PathfindingSettings {
+            graph_width: 1,
+            heuristic: "Teleport".to_string(),
+            edge_probability: 2.0,
+            ..settings
+        };
+        restored.apply_settings(&settings);
+        assert_eq!(restored.graph_width, 12);
+        assert_eq!(restored.heuristic, config.heuristic);
+        assert_eq!(restored.edge_probability, 1.0);
+    }
+}
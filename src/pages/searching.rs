@@ -0,0 +1,260 @@
+use rand::Rng;
+use sorting::{SearchAlgorithm, SearchState, TotalF64};
+use yew::prelude::*;
+use yew_hooks::use_title;
+
+use crate::{
+    components::{
+        collapsible::Collapsible,
+        input_items::{Button, IntInput, PlaybackTimeInput, SelectInput},
+        sidebar::Sidebar,
+        sorting::sort_graph::SortGraph,
+        step_slider::StepSlider,
+    },
+    hooks::{use_color_scheme::ColorScheme, use_settings::use_settings},
+    utils::palette::Palette,
+};
+
+/// How the values of the sorted items are spread out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchInput {
+    /// The values grow by the same amount from item to item, which is the best case of interpolation search.
+    Even,
+    /// Sorted random values.
+    Random,
+    /// The values grow as the cubes of the indices, so most of them are small and interpolation search guesses too low.
+    Skewed,
+}
+impl SearchInput {
+    pub const ALL: [SearchInput; 3] = [SearchInput::Even, SearchInput::Random, SearchInput::Skewed];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchInput::Even => "Evenly spread",
+            SearchInput::Random => "Random",
+            SearchInput::Skewed => "Skewed",
+        }
+    }
+    /// Generates `len` sorted whole numbers.
+    pub fn generate(&self, len: usize) -> Vec<TotalF64> {
+        let mut values: Vec<u32> = match self {
+            SearchInput::Even => (1..=len as u32).map(|i| i * 3).collect(),
+            SearchInput::Random => {
+                let mut rng = rand::thread_rng();
+                (0..len)
+                    .map(|_| rng.gen_range(1..=len as u32 * 3))
+                    .collect()
+            }
+            SearchInput::Skewed => (1..=len as u32).map(|i| i.pow(3)).collect(),
+        };
+        values.sort_unstable();
+        values.into_iter().map(TotalF64::from).collect()
+    }
+}
+
+/// Searches a value in sorted items and plays back how the search range shrinks around it.
+#[function_component(SearchingPage)]
+pub fn searching_page() -> Html {
+    let color_scheme = use_context::<ColorScheme>().expect("no color scheme context found");
+    let algorithm = use_state_eq(|| SearchAlgorithm::Binary);
+    let input = use_state_eq(|| SearchInput::Even);
+    let len = use_state_eq(|| 64);
+    let items = use_state_eq(|| SearchInput::Even.generate(64));
+    let value = use_state_eq(|| 150);
+    let palette = use_state_eq(Palette::default);
+    let step_index = use_state(|| 0);
+    let settings = use_settings();
+
+    use_title(format!("{} - Searching algorithms", algorithm.name()));
+
+    let change_algorithm = {
+        let algorithm = algorithm.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(new_algorithm) = SearchAlgorithm::ALL
+                .iter()
+                .copied()
+                .find(|algorithm| algorithm.name() == name)
+            {
+                algorithm.set(new_algorithm);
+                step_index.set(0);
+            }
+        })
+    };
+
+    let change_input = {
+        let input = input.clone();
+        let items = items.clone();
+        let step_index = step_index.clone();
+        let len = *len;
+
+        Callback::from(move |name: String| {
+            if let Some(new_input) = SearchInput::ALL
+                .iter()
+                .copied()
+                .find(|input| input.name() == name)
+            {
+                input.set(new_input);
+                items.set(new_input.generate(len));
+                step_index.set(0);
+            }
+        })
+    };
+
+    let change_len = {
+        let len = len.clone();
+        let items = items.clone();
+        let step_index = step_index.clone();
+        let input = *input;
+
+        Callback::from(move |new_len: usize| {
+            len.set(new_len);
+            items.set(input.generate(new_len));
+            step_index.set(0);
+        })
+    };
+
+    let change_value = {
+        let value = value.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |new_value| {
+            value.set(new_value);
+            step_index.set(0);
+        })
+    };
+
+    // Searches for one of the items, so that the search succeeds
+    let pick_random_item = {
+        let value = value.clone();
+        let items = items.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |_| {
+            if !items.is_empty() {
+                let index = rand::thread_rng().gen_range(0..items.len());
+                value.set(items[index].0 as u32);
+                step_index.set(0);
+            }
+        })
+    };
+
+    let change_palette = {
+        let palette = palette.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(new_palette) = Palette::ALL
+                .iter()
+                .copied()
+                .find(|palette| palette.name() == name)
+            {
+                palette.set(new_palette);
+            }
+        })
+    };
+
+    let on_change_step = {
+        let step_index = step_index.clone();
+        Callback::from(move |index| step_index.set(index))
+    };
+
+    let mut steps = vec![];
+    let found = algorithm.search_fn()(&items, TotalF64::from(*value), &mut steps);
+    let shown_steps = &steps[..(*step_index).min(steps.len())];
+    let state = SearchState::after(items.len(), shown_steps);
+    let total_comparisons = SearchState::after(items.len(), &steps).comparisons;
+
+    // The items that the value can't be between anymore are faded out
+    let colors = (0..items.len())
+        .map(|i| {
+            if state.probe.map(|(probe, _)| probe) == Some(i) {
+                palette.highlight_color(color_scheme)
+            } else if state
+                .range
+                .is_some_and(|(low, high)| (low..=high).contains(&i))
+            {
+                palette.base_color(color_scheme)
+            } else {
+                "hsla(0, 0%, 50%, 0.3)"
+            }
+            .to_string()
+        })
+        .collect::<Vec<String>>();
+
+    // The result is told once every step has been played
+    let narration = match shown_steps.last() {
+        Some(step) if shown_steps.len() < steps.len() => step.to_string(),
+        _ if shown_steps.is_empty() && !steps.is_empty() => {
+            format!("Searching for {}", *value)
+        }
+        _ => match found {
+            Some(index) => format!("{} is at index {}", *value, index),
+            None => format!("{} is not in the items", *value),
+        },
+    };
+
+    html! {
+        <div class="page" id="Searching">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <SelectInput
+                        title="Algorithm"
+                        options={SearchAlgorithm::ALL.iter().map(|algorithm| algorithm.name().to_string()).collect::<Vec<String>>()}
+                        selected_value={algorithm.name().to_string()}
+                        onchange={change_algorithm}
+                    />
+                    <SelectInput
+                        title="Values"
+                        options={SearchInput::ALL.iter().map(|input| input.name().to_string()).collect::<Vec<String>>()}
+                        selected_value={input.name().to_string()}
+                        onchange={change_input}
+                    />
+                    <IntInput<usize>
+                        title="Input length"
+                        value={*len}
+                        oninput={change_len}
+                        min={1}
+                        max={1000}
+                    />
+                    <IntInput<u32>
+                        title="Searched value"
+                        value={*value}
+                        oninput={change_value}
+                    />
+                    <Button title="Search for a random item" onclick={pick_random_item} />
+                    <SelectInput
+                        title="Palette"
+                        options={Palette::ALL.iter().map(|palette| palette.name().to_string()).collect::<Vec<String>>()}
+                        selected_value={palette.name().to_string()}
+                        onchange={change_palette}
+                    />
+                    <PlaybackTimeInput />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <div class="visualization">
+                    <span>
+                        {
+                            match state.range {
+                                Some((low, high)) => format!("Comparisons: {}/{}, range {}..{}", state.comparisons, total_comparisons, low, high),
+                                None => format!("Comparisons: {}/{}", state.comparisons, total_comparisons),
+                            }
+                        }
+                    </span>
+                    <SortGraph items={(*items).clone()} step={vec![]} palette={*palette} {colors} />
+                    <StepSlider
+                        active_step_index={*step_index}
+                        max={steps.len()}
+                        on_change={on_change_step}
+                        playback_time={settings.playback_time}
+                    />
+                    <p class="step-narration">{ narration }</p>
+                </div>
+            </main>
+        </div>
+    }
+}
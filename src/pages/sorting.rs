@@ -2,48 +2,217 @@ use crate::{
     components::{
         algo_desc::AlgoDesc,
         collapsible::Collapsible,
+        data_structures::heap_view::HeapView,
+        input_items::{Button, FileInput},
         sidebar::Sidebar,
         sorting::{
             audio_controls::{AudioConfig, AudioControls},
+            incremental_sort_graph::IncrementalSortGraph,
+            race_grid::RaceGrid,
+            race_leaderboard::{RaceLeaderboard, RaceStanding},
             sort_controls::SortControls,
             sort_graph::SortGraph,
+            sort_stats::SortStatsPanel,
         },
         step_slider::StepSlider,
     },
-    hooks::use_sort_audio::use_sort_audio,
-    utils::{gen_u32_vec, knuth_shuffle},
+    hooks::{use_settings::use_settings, use_sort_audio::use_sort_audio},
+    utils::{
+        download_file, format_duration_ms, gen_f64_vec, gen_mountain,
+        input_distribution::InputDistribution, now_ms, palette::Palette,
+        proportional_playback_time, sort_input::SortInput, sort_recording::SortRecording,
+        sort_renderer::SortRenderer, sort_visualization::SortVisualization,
+    },
+    workers::sort_worker::{SortJob, SortWorker, SortWorkerOutput},
 };
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use sorting::*;
-use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+use std::{cmp::Reverse, collections::BTreeMap, iter, rc::Rc, time::Duration};
 use yew::prelude::*;
+use yew_agent::use_bridge;
 use yew_hooks::use_title;
 use yew_router::prelude::*;
 
-type SortSteps = Vec<Vec<SortCommand<u32>>>;
+/// The algorithms sort [`Counted`] items so that their comparisons can be counted.
+/// Each value is tagged with its index in the input, so that equal values can be told apart for the stability demo.
+type SortItem = Counted<Keyed<TotalF64, usize>>;
+type ItemSortSteps = Vec<Vec<SortCommand<SortItem>>>;
+/// The steps that the indices of the items in the input take, which tell where every item of the input is at each step.
+type OriginSteps = Vec<Vec<SortCommand<usize>>>;
 
 #[derive(Clone)]
 pub struct SortingAlgorithm {
     pub name: String,
-    sort: fn(&mut Vec<u32>, &mut SortSteps),
+    sort: fn(&mut Vec<SortItem>, &mut ItemSortSteps),
+    /// Whether the algorithm only works on whole numbers, in which case it never gets a floating-point input.
+    pub whole_numbers_only: bool,
+    /// How the number of steps grows with the input length on average, for estimating the steps before sorting.
+    pub complexity: Complexity,
+    /// Generates an input of a given length that makes the algorithm do as much work as possible.
+    worst_case: fn(usize) -> Vec<u32>,
 }
 
 impl SortingAlgorithm {
-    fn new(name: &str, sort: fn(&mut Vec<u32>, &mut SortSteps)) -> Self {
+    fn new(
+        name: &str,
+        sort: fn(&mut Vec<SortItem>, &mut ItemSortSteps),
+        complexity: Complexity,
+        worst_case: fn(usize) -> Vec<u32>,
+    ) -> Self {
         Self {
             name: name.to_string(),
             sort,
+            whole_numbers_only: false,
+            complexity,
+            worst_case,
         }
     }
-    fn sort(&self, input: Rc<RefCell<Vec<u32>>>) -> SortResult<u32> {
-        run_sort(input, self.sort)
+    /// Sorts the input and measures how long only the sorting took, without rendering or playing back the steps.
+    pub(crate) fn sort(&self, items: &mut Vec<TotalF64>) -> SortResult<TotalF64> {
+        self.sort_items(items).1
+    }
+    /// Sorts the input like [`SortingAlgorithm::sort`], and also gets the [`OriginSteps`] of the items.\
+    /// There are none if the algorithm only works on whole numbers, since it writes numbers instead of moving the items.
+    pub(crate) fn sort_with_origins(
+        &self,
+        items: &mut Vec<TotalF64>,
+    ) -> (SortResult<TotalF64>, Option<OriginSteps>) {
+        let (item_steps, result) = self.sort_items(items);
+        let origins = if self.whole_numbers_only {
+            None
+        } else {
+            Some(map_item_steps(&item_steps, |Counted(item)| item.value))
+        };
+        (result, origins)
+    }
+    /// Sorts the input, returning the steps of the tagged items along with the result.
+    fn sort_items(&self, items: &mut Vec<TotalF64>) -> (ItemSortSteps, SortResult<TotalF64>) {
+        // Set up everything before starting the timer so that only the sorting itself is measured
+        let mut tagged = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                Counted(Keyed {
+                    key: *item,
+                    value: i,
+                })
+            })
+            .collect();
+        let mut item_steps = vec![];
+        let start = now_ms();
+        let ((), comparisons, step_comparisons) =
+            count_comparisons_by_step(|| (self.sort)(&mut tagged, &mut item_steps));
+        let duration = Duration::from_secs_f64((now_ms() - start).max(0.0) / 1000.0);
+
+        *items = tagged.into_iter().map(|Counted(item)| item.key).collect();
+        let steps = map_item_steps(&item_steps, |Counted(item)| item.key);
+        let result = SortResult {
+            comparisons: Some(comparisons),
+            step_comparisons: Some(step_comparisons),
+            ..SortResult::new(Some(duration), steps)
+        };
+        (item_steps, result)
+    }
+    /// Generates a pathological input of length `len` for the algorithm, e.g. a descending input for bubble sort.
+    pub fn worst_case_input(&self, len: usize) -> Vec<TotalF64> {
+        (self.worst_case)(len)
+            .into_iter()
+            .map(TotalF64::from)
+            .collect()
     }
 }
 
+/// Converts the values of the steps of the tagged items with `f`.
+fn map_item_steps<T>(
+    steps: &[Vec<SortCommand<SortItem>>],
+    f: fn(SortItem) -> T,
+) -> Vec<Vec<SortCommand<T>>> {
+    steps
+        .iter()
+        .map(|step| {
+            step.iter()
+                .map(|command| command.clone().map_value(f))
+                .collect()
+        })
+        .collect()
+}
+
+/// Sorts the items with an algorithm that distributes the items by their value, like bucket sort, so it only works on whole numbers.
+/// The values are converted to `u32`s for it, which doesn't change the whole numbers that it gets as input.
+fn sort_whole_numbers(
+    sort: fn(&mut Vec<u32>, &mut Vec<Vec<SortCommand<u32>>>),
+    items: &mut Vec<SortItem>,
+    steps: &mut ItemSortSteps,
+) {
+    let mut whole_numbers = items
+        .iter()
+        .map(|item| item.0.key.0 as u32)
+        .collect::<Vec<u32>>();
+    let mut whole_number_steps = vec![];
+    sort(&mut whole_numbers, &mut whole_number_steps);
+
+    // The numbers are written instead of moved, so where they came from in the input is unknown
+    let tagged = |value: u32| {
+        Counted(Keyed {
+            key: TotalF64::from(value),
+            value: usize::MAX,
+        })
+    };
+    *items = whole_numbers.into_iter().map(tagged).collect();
+    steps.extend(whole_number_steps.into_iter().map(|step| {
+        step.into_iter()
+            .map(|command| command.map_value(tagged))
+            .collect::<Vec<SortCommand<SortItem>>>()
+    }));
+}
+
+fn bucket_sort_whole_numbers(items: &mut Vec<SortItem>, steps: &mut ItemSortSteps) {
+    sort_whole_numbers(bucket_sort, items, steps);
+}
+
+fn counting_sort_whole_numbers(items: &mut Vec<SortItem>, steps: &mut ItemSortSteps) {
+    sort_whole_numbers(|items, steps| counting_sort(items, steps), items, steps);
+}
+
+fn radix_sort_whole_numbers(items: &mut Vec<SortItem>, steps: &mut ItemSortSteps) {
+    sort_whole_numbers(|items, steps| radix_sort(items, steps), items, steps);
+}
+
+/// Sorts the items in descending order with an algorithm that sorts in ascending order.
+/// The items are wrapped in [`Reverse`], which reverses the comparisons, and unwrapped again for the steps.
+fn sort_descending(
+    sort: SortFn<Reverse<SortItem>>,
+    items: &mut Vec<SortItem>,
+    steps: &mut ItemSortSteps,
+) {
+    let mut reversed = items.iter().copied().map(Reverse).collect::<Vec<_>>();
+    let mut reversed_steps = vec![];
+    sort(&mut reversed, &mut reversed_steps);
+
+    *items = reversed.into_iter().map(|Reverse(item)| item).collect();
+    steps.extend(reversed_steps.into_iter().map(|step| {
+        step.into_iter()
+            .map(|command| command.map_value(|Reverse(item)| item))
+            .collect::<Vec<SortCommand<SortItem>>>()
+    }));
+}
+
+fn insertion_sort_descending(items: &mut Vec<SortItem>, steps: &mut ItemSortSteps) {
+    sort_descending(insertion_sort, items, steps);
+}
+
+fn quicksort_descending(items: &mut Vec<SortItem>, steps: &mut ItemSortSteps) {
+    sort_descending(quicksort_with_events, items, steps);
+}
+
 impl Default for SortingAlgorithm {
     fn default() -> Self {
         Self {
             name: String::from("Bubble sort"),
-            sort: bubble_sort,
+            sort: bubble_sort_with_phases,
+            whole_numbers_only: false,
+            complexity: Complexity::Quadratic,
+            worst_case: descending_input,
         }
     }
 }
@@ -56,26 +225,159 @@ impl PartialEq for SortingAlgorithm {
     }
 }
 
+// The functions can't be serialized either, so a `SortingAlgorithm` is serialized as its name and looked up by it when deserializing.
+impl Serialize for SortingAlgorithm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name)
+    }
+}
+impl<'de> Deserialize<'de> for SortingAlgorithm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        get_sorting_algorithms()
+            .into_values()
+            .find(|algorithm| algorithm.name == name)
+            .ok_or_else(|| de::Error::custom(format!("unknown sorting algorithm \"{}\"", name)))
+    }
+}
+
+/// Turns the name of an algorithm into the slug of its route, e.g. `Insertion sort (descending)` into `insertion-sort-descending`.
+pub fn algorithm_slug(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase()
+}
+
 pub fn get_sorting_algorithms() -> BTreeMap<&'static str, SortingAlgorithm> {
     // `BTreeMap` because it keeps the order of the items.
     BTreeMap::from([
         (
             "bubble-sort",
-            SortingAlgorithm::new("Bubble sort", bubble_sort),
+            SortingAlgorithm::new(
+                "Bubble sort",
+                bubble_sort_with_phases,
+                Complexity::Quadratic,
+                descending_input,
+            ),
         ),
         (
             "insertion-sort",
-            SortingAlgorithm::new("Insertion sort", insertion_sort),
+            SortingAlgorithm::new(
+                "Insertion sort",
+                insertion_sort,
+                Complexity::Quadratic,
+                descending_input,
+            ),
+        ),
+        (
+            "insertion-sort-descending",
+            SortingAlgorithm::new(
+                "Insertion sort (descending)",
+                insertion_sort_descending,
+                Complexity::Quadratic,
+                ascending_input,
+            ),
         ),
         (
             "merge-sort",
-            SortingAlgorithm::new("Merge sort", merge_sort),
+            SortingAlgorithm::new(
+                "Merge sort",
+                merge_sort_with_events,
+                Complexity::Linearithmic,
+                interleaved_input,
+            ),
+        ),
+        (
+            "odd-even-sort",
+            SortingAlgorithm::new(
+                "Odd even sort",
+                |items, steps| odd_even_sort(items, steps),
+                Complexity::Quadratic,
+                descending_input,
+            ),
+        ),
+        (
+            "heapsort",
+            SortingAlgorithm::new(
+                "Heapsort",
+                heapsort_with_events,
+                Complexity::Linearithmic,
+                ascending_input,
+            ),
+        ),
+        (
+            "quicksort",
+            SortingAlgorithm::new(
+                "Quicksort",
+                quicksort_with_events,
+                Complexity::Linearithmic,
+                ascending_input,
+            ),
+        ),
+        (
+            "quicksort-descending",
+            SortingAlgorithm::new(
+                "Quicksort (descending)",
+                quicksort_descending,
+                Complexity::Linearithmic,
+                descending_input,
+            ),
+        ),
+        (
+            "shell-sort",
+            SortingAlgorithm::new(
+                "Shell sort",
+                |items, steps| shell_sort(items, steps),
+                Complexity::Linearithmic,
+                descending_input,
+            ),
+        ),
+        (
+            "comb-sort",
+            SortingAlgorithm::new(
+                "Comb sort",
+                |items, steps| comb_sort(items, steps),
+                Complexity::Linearithmic,
+                descending_input,
+            ),
         ),
-        ("heapsort", SortingAlgorithm::new("Heapsort", heapsort)),
-        ("quicksort", SortingAlgorithm::new("Quicksort", quicksort)),
         (
             "bucket-sort",
-            SortingAlgorithm::new("Bucket sort", bucket_sort),
+            SortingAlgorithm {
+                whole_numbers_only: true,
+                ..SortingAlgorithm::new(
+                    "Bucket sort",
+                    bucket_sort_whole_numbers,
+                    Complexity::Linear,
+                    clustered_input,
+                )
+            },
+        ),
+        (
+            "counting-sort",
+            SortingAlgorithm {
+                whole_numbers_only: true,
+                ..SortingAlgorithm::new(
+                    "Counting sort",
+                    counting_sort_whole_numbers,
+                    Complexity::Linear,
+                    clustered_input,
+                )
+            },
+        ),
+        (
+            "radix-sort",
+            SortingAlgorithm {
+                whole_numbers_only: true,
+                ..SortingAlgorithm::new(
+                    "Radix sort",
+                    radix_sort_whole_numbers,
+                    Complexity::Linear,
+                    clustered_input,
+                )
+            },
         ),
     ])
 }
@@ -107,23 +409,113 @@ pub fn switch_sorting(route: &SortingRoute) -> Html {
     }
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct SortConfig {
     pub input_len: usize,
+    /// The shape of the generated input.
+    pub distribution: InputDistribution,
+    /// How sorted a [`InputDistribution::Random`] input is, from 0.0 (random) to 1.0 (sorted).
+    pub presortedness: f64,
+    /// The seed of the generated input, so that the same config always generates the same input.
+    #[serde(default = "rand::random")]
+    pub seed: u64,
     pub sorting_algorithm: SortingAlgorithm,
     pub audio_enabled: bool,
     /// How long the playback of steps should take in seconds.
     pub playback_time: f32,
+    /// If set, `playback_time` is how long an `O(n log n)` number of steps takes instead,
+    /// and the playback time grows with the number of steps so that slower algorithms also play slower.
+    pub proportional_playback: bool,
+    /// The audio is up to whoever is listening, so it isn't part of an exported run.
+    #[serde(skip)]
     pub audio_config: AudioConfig,
+    pub palette: Palette,
+    pub renderer: SortRenderer,
+    /// Runs that were exported before the visualizations were added are shown as bars.
+    #[serde(default)]
+    pub visualization: SortVisualization,
+    /// If set, the generated input has floating-point numbers between the given minimum and maximum instead of the numbers from 1 to `input_len`.
+    pub float_range: Option<(f64, f64)>,
+    /// If set, the inversions that are left at the active step are drawn over the main graph.
+    pub highlight_inversions: bool,
+    /// If set, the generated input has only a few unique values, and the bars are colored by the original order of the equal values,
+    /// which shows whether the algorithm keeps equal values in their original order.
+    pub stability_demo: bool,
+    /// Other algorithms that sort the same input in lockstep with `sorting_algorithm`, each drawn in its own lane below it.
+    pub race_algorithms: Vec<SortingAlgorithm>,
 }
 impl Default for SortConfig {
     fn default() -> Self {
         Self {
             input_len: 100,
+            distribution: InputDistribution::default(),
+            presortedness: 0.0,
+            seed: rand::random(),
             sorting_algorithm: SortingAlgorithm::default(),
             audio_enabled: true,
             playback_time: 10.0,
+            proportional_playback: false,
             audio_config: AudioConfig::default(),
+            palette: Palette::default(),
+            renderer: SortRenderer::default(),
+            visualization: SortVisualization::default(),
+            float_range: None,
+            highlight_inversions: false,
+            stability_demo: false,
+            race_algorithms: vec![],
+        }
+    }
+}
+impl SortConfig {
+    /// Sets the parts of the config that are in the `query`.
+    pub fn with_query(self, query: SortQuery) -> Self {
+        Self {
+            input_len: query.len.clamp(2, MAX_INPUT_LEN),
+            distribution: query.distribution,
+            presortedness: query.presortedness.clamp(0.0, 1.0),
+            float_range: query.min.zip(query.max),
+            seed: query.seed,
+            ..self
+        }
+    }
+    /// Generates the input of the input length, distribution, presortedness, float range and seed.
+    pub fn gen_input(&self) -> Vec<TotalF64> {
+        let seed = self.seed;
+        // Stability is only visible with plenty of equal values
+        let distribution = if self.stability_demo {
+            InputDistribution::FewUnique
+        } else {
+            self.distribution
+        };
+        let order = distribution.generate(self.input_len, self.presortedness, seed);
+
+        match self.float_range {
+            Some((min, max)) if !self.sorting_algorithm.whole_numbers_only => {
+                // Use the generated numbers as the ranks of the random floats
+                let mut values = gen_f64_vec(self.input_len, min, max, seed);
+                values.sort_by(f64::total_cmp);
+                order
+                    .into_iter()
+                    .map(|rank| TotalF64(values[rank as usize - 1]))
+                    .collect()
+            }
+            _ => order.into_iter().map(TotalF64::from).collect(),
+        }
+    }
+    /// Generates an input that rises to a peak in the middle and falls back down, using the float range if there is one.
+    pub fn gen_mountain_input(&self) -> Vec<TotalF64> {
+        match self.float_range {
+            Some((min, max)) if !self.sorting_algorithm.whole_numbers_only => {
+                gen_mountain(self.input_len, min, max)
+                    .into_iter()
+                    .map(TotalF64)
+                    .collect()
+            }
+            // The numbers are at least 1 apart, so rounding them keeps the mountain strictly rising and falling
+            _ => gen_mountain(self.input_len, 1.0, self.input_len as f64)
+                .into_iter()
+                .map(|value| TotalF64(value.round()))
+                .collect(),
         }
     }
 }
@@ -134,13 +526,154 @@ pub struct SortingAlgorithmsPageProps {
     pub algorithm: String,
 }
 
+/// The parts of a [`SortConfig`] that decide the input, which are kept in the query string of the URL
+/// so that a link to the page generates the exact same input. The algorithm is already a part of the route.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SortQuery {
+    pub len: usize,
+    pub distribution: InputDistribution,
+    pub presortedness: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    pub seed: u64,
+}
+impl SortQuery {
+    pub fn new(config: &SortConfig) -> Self {
+        Self {
+            len: config.input_len,
+            distribution: config.distribution,
+            presortedness: config.presortedness,
+            min: config.float_range.map(|(min, _)| min),
+            max: config.float_range.map(|(_, max)| max),
+            seed: config.seed,
+        }
+    }
+}
+
+/// The most inversions that are highlighted at once, since a reversed input has `n(n - 1) / 2` of them.
+const INVERSION_OVERLAY_LIMIT: usize = 500;
+
+/// The longest input that can be generated, which the length in a link is limited to as well.
+pub const MAX_INPUT_LEN: usize = 100_000;
+
+/// The most algorithms that can race the selected one, so that at most four of them are sorting side by side.
+pub const MAX_RACERS: usize = 3;
+
+/// Inputs longer than this are sorted in a [`SortWorker`], since sorting them on the main thread would freeze the page.
+const WORKER_INPUT_LEN: usize = 5000;
+
+/// How far a [`SortWorker`] has gotten with the current sort, shown instead of the results until it's done.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SortProgress {
+    sorted: usize,
+    algorithms: usize,
+    steps: usize,
+}
+
+/// The results of the current [`SortJob`], put together from the output of the [`SortWorker`].
+struct PendingSort {
+    job: u32,
+    /// The results of the algorithm and the ones racing it, in that order.
+    results: Vec<SortResult<TotalF64>>,
+    sorted: usize,
+}
+impl PendingSort {
+    fn progress(&self) -> SortProgress {
+        SortProgress {
+            sorted: self.sorted,
+            algorithms: self.results.len(),
+            steps: self.results.iter().map(|result| result.steps.len()).sum(),
+        }
+    }
+}
+
+/// What is shown about the current sort at every step, worked out once per sort instead of on every render.
+struct SortDetails {
+    /// The work done after every number of played steps.
+    stats: Vec<SortStats>,
+    /// Only heapsort has a phase, during which its items are drawn as a heap too. Indexed by the number of played steps.
+    heap_phases: Vec<Option<(HeapsortPhase, usize)>>,
+    /// Only tracked for the stability demo
+    stability: Option<StabilityDemo>,
+}
+impl SortDetails {
+    fn new(
+        result: &SortResult<TotalF64>,
+        origin_steps: Option<OriginSteps>,
+        input: &[TotalF64],
+    ) -> Self {
+        Self {
+            stats: SortStats::after_each_step(
+                &result.steps,
+                result.step_comparisons.as_deref(),
+                result.comparisons.unwrap_or_default(),
+            ),
+            heap_phases: heapsort_phases(&result.steps),
+            stability: origin_steps.map(|steps| StabilityDemo::new(input, steps)),
+        }
+    }
+}
+
+/// Where the items of the input are at the played step, and whether the sort kept the order of equal values.
+struct StabilityDemo {
+    /// Plays the [`OriginSteps`] along with the sort, so that only the steps in between are applied when the step changes.
+    origins: SortPlayback<usize>,
+    /// The color of every item of the input, by its index in the input.
+    colors: Vec<String>,
+    info: String,
+}
+impl StabilityDemo {
+    fn new(input: &[TotalF64], origin_steps: OriginSteps) -> Self {
+        // The bars are colored by where they were in the input, and the stability is judged by where they end up
+        let mut sorted_origins = (0..input.len()).collect::<Vec<usize>>();
+        run_sort_steps(&mut sorted_origins, &origin_steps);
+        let sorted = sorted_origins
+            .iter()
+            .map(|origin| Keyed {
+                key: input[*origin],
+                value: *origin,
+            })
+            .collect::<Vec<_>>();
+        let info = match unstable_pairs(&sorted).len() {
+            0 => "Stable on this input: equal values keep their original order".to_string(),
+            unstable => format!(
+                "Not stable on this input: {} pairs of equal values end up out of their original order",
+                unstable
+            ),
+        };
+
+        Self {
+            origins: SortPlayback::new((0..input.len()).collect(), origin_steps),
+            colors: stability_colors(input),
+            info,
+        }
+    }
+    /// Gets the colors of the bars after the first `played` steps.
+    fn colors_at(&mut self, played: usize) -> Vec<String> {
+        self.origins.seek(played);
+        self.origins
+            .items()
+            .iter()
+            .map(|origin| self.colors[*origin].clone())
+            .collect()
+    }
+}
+
 #[function_component(SortingAlgorithmsPage)]
 pub fn sorting_algorithms_page(props: &SortingAlgorithmsPageProps) -> Html {
+    let location = use_location().unwrap();
+    let settings = use_settings();
     let config = {
         let algorithm_name = props.algorithm.to_string();
+        let saved_config = settings.sort.clone();
 
         use_state(|| {
-            let mut config = SortConfig::default();
+            let mut config = match location.query::<SortQuery>() {
+                Ok(query) => saved_config.with_query(query),
+                Err(_) => saved_config,
+            };
             if let Some(algorithm) = get_sorting_algorithms().get(algorithm_name.as_str()) {
                 config.sorting_algorithm = algorithm.to_owned();
             }
@@ -148,25 +681,33 @@ pub fn sorting_algorithms_page(props: &SortingAlgorithmsPageProps) -> Html {
         })
     };
 
-    let input = use_mut_ref(|| knuth_shuffle(gen_u32_vec(config.input_len)));
-    let output = use_mut_ref(|| input.borrow().clone());
-    let sort_result = use_mut_ref(|| config.sorting_algorithm.sort(Rc::clone(&output)));
+    let input = use_mut_ref(|| SortInput::new(config.gen_input()));
+    // The input is sorted once the page has mounted, so there is nothing to play before that
+    let sort_result = use_mut_ref(|| SortResult::new(None, vec![]));
+    let sort_details =
+        use_mut_ref(|| SortDetails::new(&sort_result.borrow(), None, input.borrow().original()));
+
+    // Plays the steps of the algorithm and the ones racing it on copies of the input, so that moving the slider only applies the steps in between.
+    // The algorithm itself is the first racer.
+    let race = use_mut_ref(|| {
+        SortRace::new(
+            input.borrow().original().to_vec(),
+            vec![vec![]; 1 + config.race_algorithms.len()],
+        )
+    });
 
     // The active step is empty at the input step, step 0.
-    let active_step = use_state(std::vec::Vec::<SortCommand<u32>>::new);
+    let active_step = use_state(std::vec::Vec::<SortCommand<TotalF64>>::new);
     let active_step_index: UseStateHandle<usize> = use_state_eq(|| 0);
 
     {
         let active_step = active_step.clone();
-        let sort_result = Rc::clone(&sort_result);
+        let race = Rc::clone(&race);
 
         use_effect_with_deps(
-            move |i| {
-                active_step.set(if **i == 0 {
-                    vec![]
-                } else {
-                    sort_result.borrow().steps[**i - 1].clone()
-                });
+            move |_| {
+                // The algorithm may have finished earlier than the ones racing it, in which case there is no active step
+                active_step.set(race.borrow().frame_steps(0).concat());
 
                 || ()
             },
@@ -174,37 +715,174 @@ pub fn sorting_algorithms_page(props: &SortingAlgorithmsPageProps) -> Html {
         );
     }
 
-    let output_at_active_step = use_state(|| {
-        get_output_at_step_index(
-            &input.borrow(),
-            &sort_result.borrow().steps,
-            *active_step_index,
-        )
-    });
+    let output_at_active_step = use_state(|| race.borrow().racers()[0].items().to_vec());
 
     let route = use_route::<SortingRoute>();
+    let history = use_history().unwrap();
 
-    let update_values = {
+    let progress: UseStateHandle<Option<SortProgress>> = use_state_eq(|| None);
+    let pending_sort = use_mut_ref(|| PendingSort {
+        job: 0,
+        results: vec![],
+        sorted: 0,
+    });
+
+    let sort_worker = {
         let input = input.clone();
         let sort_result = sort_result.clone();
+        let sort_details = sort_details.clone();
+        let race = Rc::clone(&race);
         let active_step_index = active_step_index.clone();
         let output_at_active_step = output_at_active_step.clone();
+        let progress = progress.clone();
+        let pending_sort = pending_sort.clone();
 
-        move |new_input: Vec<u32>, config: &SortConfig| {
-            // Cloning here is necessary if we want to keep the input
-            *input.borrow_mut() = new_input.clone();
-            *output.borrow_mut() = new_input;
-            *sort_result.borrow_mut() = config.sorting_algorithm.sort(Rc::clone(&output));
+        use_bridge::<SortWorker, _>(move |output| {
+            let mut pending = pending_sort.borrow_mut();
+            match output {
+                SortWorkerOutput::Steps {
+                    job,
+                    algorithm,
+                    steps,
+                } if job == pending.job => {
+                    pending.results[algorithm].steps.extend(steps);
+                }
+                SortWorkerOutput::Sorted {
+                    job,
+                    algorithm,
+                    items,
+                    duration,
+                    comparisons,
+                    step_comparisons,
+                } if job == pending.job => {
+                    let result = &mut pending.results[algorithm];
+                    result.duration = duration;
+                    result.comparisons = comparisons;
+                    result.step_comparisons = step_comparisons;
+                    if algorithm == 0 {
+                        *input.borrow_mut().working_mut() = items;
+                    }
+                    pending.sorted += 1;
+                }
+                // The output of a job that has been replaced by a newer one
+                _ => return,
+            }
+
+            if pending.sorted < pending.results.len() {
+                progress.set(Some(pending.progress()));
+                return;
+            }
+
+            let results = std::mem::take(&mut pending.results);
+            let original = input.borrow().original().to_vec();
+            *race.borrow_mut() = SortRace::new(
+                original.clone(),
+                results.iter().map(|result| result.steps.clone()).collect(),
+            );
+            *sort_result.borrow_mut() = results.into_iter().next().unwrap();
+            *sort_details.borrow_mut() = SortDetails::new(&sort_result.borrow(), None, &original);
             active_step_index.set(0);
+            output_at_active_step.set(original);
+            progress.set(None);
+        })
+    };
+
+    // Sorts the original input again and goes back to its first step
+    let replay_sort = {
+        let input = input.clone();
+        let sort_result = sort_result.clone();
+        let sort_details = sort_details.clone();
+        let race = Rc::clone(&race);
+        let active_step_index = active_step_index.clone();
+        let output_at_active_step = output_at_active_step.clone();
+        let progress = progress.clone();
+        let pending_sort = pending_sort.clone();
+
+        move |config: &SortConfig| {
+            let mut input = input.borrow_mut();
+            input.reset();
+
+            // Any output of an earlier worker job is ignored from now on
+            let mut pending = pending_sort.borrow_mut();
+            pending.job += 1;
+
+            if input.original().len() > WORKER_INPUT_LEN {
+                let algorithms = iter::once(&config.sorting_algorithm)
+                    .chain(&config.race_algorithms)
+                    .map(|algorithm| algorithm.name.clone())
+                    .collect::<Vec<String>>();
+                pending.results = algorithms
+                    .iter()
+                    .map(|_| SortResult::new(None, vec![]))
+                    .collect();
+                pending.sorted = 0;
+
+                // Nothing can be played until the worker is done
+                *sort_result.borrow_mut() = SortResult::new(None, vec![]);
+                *sort_details.borrow_mut() =
+                    SortDetails::new(&sort_result.borrow(), None, input.original());
+                *race.borrow_mut() =
+                    SortRace::new(input.original().to_vec(), vec![vec![]; algorithms.len()]);
+                progress.set(Some(pending.progress()));
+                sort_worker.send(SortJob {
+                    id: pending.job,
+                    algorithms,
+                    input: input.original().to_vec(),
+                });
+            } else {
+                let (result, origins) = if config.stability_demo {
+                    config
+                        .sorting_algorithm
+                        .sort_with_origins(input.working_mut())
+                } else {
+                    (config.sorting_algorithm.sort(input.working_mut()), None)
+                };
+                *sort_result.borrow_mut() = result;
+                *sort_details.borrow_mut() =
+                    SortDetails::new(&sort_result.borrow(), origins, input.original());
+                *race.borrow_mut() =
+                    start_race(config, input.original(), sort_result.borrow().steps.clone());
+                progress.set(None);
+            }
+            active_step_index.set(0);
+
+            output_at_active_step.set(input.original().to_vec());
+        }
+    };
+
+    // The first input is sorted like any later one, so that a long one is also left to the worker
+    {
+        let config = config.clone();
+        let replay_sort = replay_sort.clone();
 
-            output_at_active_step.set(get_output_at_step_index(
-                &input.borrow(),
-                &sort_result.borrow().steps,
-                0,
-            ));
+        use_effect_with_deps(
+            move |_| {
+                replay_sort(&config);
+                || ()
+            },
+            (),
+        );
+    }
+
+    let update_values = {
+        let input = input.clone();
+        let replay_sort = replay_sort.clone();
+
+        move |new_input: Vec<TotalF64>, config: &SortConfig| {
+            *input.borrow_mut() = SortInput::new(new_input);
+            replay_sort(config);
         }
     };
 
+    let reset_input = {
+        let config = config.clone();
+        let replay_sort = replay_sort.clone();
+
+        Callback::from(move |_| {
+            replay_sort(&config);
+        })
+    };
+
     let update_input = {
         let config = config.clone();
         let update_values = update_values.clone();
@@ -220,8 +898,10 @@ pub fn sorting_algorithms_page(props: &SortingAlgorithmsPageProps) -> Html {
 
         Callback::from(move |msg: (SortConfig, bool)| {
             if msg.1 {
-                let new_input = knuth_shuffle(gen_u32_vec(msg.0.input_len));
+                let new_input = msg.0.gen_input();
                 update_values(new_input, &msg.0);
+            } else if msg.0.race_algorithms != config.race_algorithms {
+                replay_sort(&msg.0);
             }
             config.set(msg.0);
         })
@@ -233,7 +913,7 @@ pub fn sorting_algorithms_page(props: &SortingAlgorithmsPageProps) -> Html {
 
         Callback::from(move |msg: (AudioConfig, bool)| {
             if msg.1 {
-                let new_input = knuth_shuffle(gen_u32_vec(config.input_len));
+                let new_input = config.gen_input();
                 update_values(new_input, &config);
             }
             config.set(SortConfig {
@@ -243,17 +923,161 @@ pub fn sorting_algorithms_page(props: &SortingAlgorithmsPageProps) -> Html {
         })
     };
 
+    let export_run = {
+        let config = config.clone();
+        let input = input.clone();
+        let sort_result = sort_result.clone();
+        let race = Rc::clone(&race);
+
+        Callback::from(move |_| {
+            let sort_result = sort_result.borrow();
+            let recording = SortRecording {
+                config: (*config).clone(),
+                input: input.borrow().original().to_vec(),
+                steps: race
+                    .borrow()
+                    .racers()
+                    .iter()
+                    .map(|racer| racer.steps().to_vec())
+                    .collect(),
+                stats: SortStats::new(
+                    &sort_result.steps,
+                    sort_result.comparisons.unwrap_or_default(),
+                ),
+                duration: sort_result.duration.unwrap_or_default(),
+            };
+            download_file(
+                &format!("{}.json", algorithm_slug(&config.sorting_algorithm.name)),
+                &recording.to_json(),
+                "application/json",
+            );
+        })
+    };
+
+    let import_error: UseStateHandle<Option<String>> = use_state_eq(|| None);
+    // Replays the steps of an exported run as they were recorded instead of sorting its input again
+    let import_run = {
+        let config = config.clone();
+        let input = input.clone();
+        let sort_result = sort_result.clone();
+        let sort_details = sort_details.clone();
+        let race = Rc::clone(&race);
+        let active_step_index = active_step_index.clone();
+        let output_at_active_step = output_at_active_step.clone();
+        let progress = progress.clone();
+        let pending_sort = pending_sort.clone();
+        let import_error = import_error.clone();
+        let history = history.clone();
+
+        Callback::from(move |json: String| {
+            let recording = match SortRecording::from_json(&json) {
+                Ok(recording) => recording,
+                Err(error) => {
+                    import_error.set(Some(error.to_string()));
+                    return;
+                }
+            };
+            // Any output of a worker job is ignored from now on
+            pending_sort.borrow_mut().job += 1;
+
+            let mut imported_input = SortInput::new(recording.input.clone());
+            run_sort_steps(imported_input.working_mut(), &recording.steps[0]);
+            *input.borrow_mut() = imported_input;
+
+            // The origins can only be followed if sorting again takes the exact same steps
+            let origins = if recording.config.stability_demo {
+                let (result, origins) = recording
+                    .config
+                    .sorting_algorithm
+                    .sort_with_origins(&mut recording.input.clone());
+                origins.filter(|_| result.steps == recording.steps[0])
+            } else {
+                None
+            };
+            *sort_result.borrow_mut() = SortResult {
+                comparisons: Some(recording.stats.comparisons),
+                ..SortResult::new(Some(recording.duration), recording.steps[0].clone())
+            };
+            *sort_details.borrow_mut() =
+                SortDetails::new(&sort_result.borrow(), origins, &recording.input);
+            *race.borrow_mut() = SortRace::new(recording.input.clone(), recording.steps);
+            active_step_index.set(0);
+            output_at_active_step.set(recording.input);
+            progress.set(None);
+            import_error.set(None);
+
+            let slug = algorithm_slug(&recording.config.sorting_algorithm.name);
+            config.set(SortConfig {
+                audio_config: config.audio_config.clone(),
+                ..recording.config
+            });
+            history.push(SortingRoute::SortingAlgorithm { algorithm: slug });
+        })
+    };
+
+    let current_input = input.borrow().shared_original();
+    let distinct_values = input.borrow().distinct_values();
+    let inversions = if config.highlight_inversions {
+        find_inversions(&output_at_active_step, INVERSION_OVERLAY_LIMIT)
+    } else {
+        vec![]
+    };
+    let (bar_colors, stability_info) = match (config.stability_demo, sort_details.borrow_mut().stability.as_mut()) {
+        (true, Some(stability)) => (
+            stability.colors_at(race.borrow().racers()[0].position()),
+            Some(stability.info.clone()),
+        ),
+        (true, None) if config.sorting_algorithm.whole_numbers_only => (
+            vec![],
+            Some("The stability of this algorithm can't be shown, since it writes numbers instead of moving the items".to_string()),
+        ),
+        (true, None) if current_input.len() > WORKER_INPUT_LEN => (
+            vec![],
+            Some(format!(
+                "The stability can only be shown for inputs of up to {} items",
+                WORKER_INPUT_LEN
+            )),
+        ),
+        // The input hasn't been sorted yet
+        (true, None) if sort_result.borrow().duration.is_none() => (
+            vec![],
+            Some("The stability is shown once the input has been sorted".to_string()),
+        ),
+        // An imported run whose steps don't match sorting its input again
+        (true, None) => (
+            vec![],
+            Some("The stability of this run can't be shown, since sorting its input again takes other steps".to_string()),
+        ),
+        (false, _) => (vec![], None),
+    };
+    // Only the full renderer can draw the inversions, the stability colors and the visualizations other than bars
+    let renderer = if config.highlight_inversions
+        || config.stability_demo
+        || config.visualization != SortVisualization::Bars
+    {
+        SortRenderer::Full
+    } else {
+        config.renderer.resolve(current_input.len())
+    };
+    let playback_time = if config.proportional_playback {
+        proportional_playback_time(
+            config.playback_time,
+            race.borrow().len(),
+            Complexity::Linearithmic.estimate(current_input.len()),
+        )
+    } else {
+        config.playback_time
+    };
+
     let change_step = {
         let active_step_index = active_step_index.clone();
-        let sort_result = Rc::clone(&sort_result);
         let output_at_active_step = output_at_active_step.clone();
+        let race = Rc::clone(&race);
 
         Callback::from(move |val: usize| {
-            output_at_active_step.set(get_output_at_step_index(
-                &input.borrow(),
-                &sort_result.borrow().steps,
-                val,
-            ));
+            let mut race = race.borrow_mut();
+            race.seek(val);
+            output_at_active_step.set(race.racers()[0].items().to_vec());
             active_step_index.set(val);
         })
     };
@@ -268,71 +1092,183 @@ pub fn sorting_algorithms_page(props: &SortingAlgorithmsPageProps) -> Html {
                     SortingRoute::SortingAlgorithm { algorithm } => algorithm,
                     _ => "bubble-sort",
                 };
-                if let Some(algorithm) = get_sorting_algorithms().get(algorithm_name) {
-                    update_config.emit((
+                match get_sorting_algorithms().get(algorithm_name) {
+                    // An imported run has already set the algorithm along with its own input
+                    Some(algorithm) if *algorithm == config.sorting_algorithm => (),
+                    Some(algorithm) => update_config.emit((
                         SortConfig {
                             sorting_algorithm: algorithm.to_owned(),
+                            // The algorithm can't race itself
+                            race_algorithms: config
+                                .race_algorithms
+                                .iter()
+                                .filter(|racer| *racer != algorithm)
+                                .cloned()
+                                .collect(),
                             ..(*config).clone()
                         },
                         true,
-                    ))
-                } else {
-                    update_config.emit(((*config).clone(), false));
-                };
+                    )),
+                    None => update_config.emit(((*config).clone(), false)),
+                }
                 || ()
             },
-            route,
+            route.clone(),
         );
     }
 
+    // Keeps the URL up to date with the config, so that it can be shared
+    use_effect_with_deps(
+        move |(route, query)| {
+            if let Some(route) = route {
+                history
+                    .replace_with_query(route.clone(), query.clone())
+                    .ok();
+            }
+            || ()
+        },
+        (route, SortQuery::new(&config)),
+    );
+
     use_title(format!(
         "{} - Sorting algorithms",
         config.sorting_algorithm.name
     ));
 
+    use_effect_with_deps(
+        move |config| {
+            settings.update(|settings| settings.sort = config.clone());
+            || ()
+        },
+        (*config).clone(),
+    );
+
     use_sort_audio(
         output_at_active_step.clone(),
         active_step.clone(),
         config.audio_config.clone(),
     );
 
+    let (stats_so_far, total_stats) = {
+        let stats = &sort_details.borrow().stats;
+        let steps_so_far = race.borrow().racers()[0].position().min(stats.len() - 1);
+        (stats[steps_so_far], stats[stats.len() - 1])
+    };
+    // An imported run only has the comparisons of the whole run
+    let comparisons_so_far = sort_result.borrow().step_comparisons.is_some();
+    // Racers other than the algorithm itself aren't narrated
+    let narration = narrate(
+        &sort_result.borrow().steps,
+        race.borrow().racers()[0].position(),
+    );
+    let heap_phase = sort_details
+        .borrow()
+        .heap_phases
+        .get(race.borrow().racers()[0].position())
+        .copied()
+        .flatten();
+
     html! {
         <div class="page" id="SortingAlgorithms">
             <Sidebar>
                 <h2>{"Config"}</h2>
 
                 <Collapsible title="General" open={true} class="config-section">
-                    <SortControls config={(*config).clone()} {update_input} {update_config} />
+                    <SortControls config={(*config).clone()} input={current_input} {update_input} {reset_input} {update_config} />
                 </Collapsible>
 
                 <Collapsible title="Audio" open={false} class="config-section">
                     <AudioControls config={config.audio_config.clone()} update_config={update_audio_config} />
                 </Collapsible>
+
+                <Collapsible title="Export and import" open={false} class="config-section">
+                    {
+                        // A run that is still sorting in the background has no steps to export yet
+                        if progress.is_none() {
+                            html! { <Button title="Export run as JSON" onclick={export_run} /> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    <FileInput title="Import run" accept=".json" error={(*import_error).clone()} onload={import_run} />
+                </Collapsible>
             </Sidebar>
 
             <main>
                 <div class="visualization">
-                    <span>{ format!("{} steps, {:?} ms", sort_result.borrow().steps.len(), &sort_result.borrow().duration.unwrap().as_millis()) }</span>
+                    {
+                        match *progress {
+                            Some(progress) => html! {
+                                <span class="sort-progress">
+                                    { format!("Sorting in the background: {} of {} algorithms done, {} steps received", progress.sorted, progress.algorithms, progress.steps) }
+                                </span>
+                            },
+                            None => html! {
+                                <span>{ format!("{} steps, {}", sort_result.borrow().steps.len(), format_duration_ms(sort_result.borrow().duration.unwrap_or_default().as_secs_f64() * 1000.0)) }</span>
+                            },
+                        }
+                    }
+                    <span>{ format!("Distinct values: {}", distinct_values) }</span>
+                    <SortStatsPanel so_far={stats_so_far} total={total_stats} {comparisons_so_far} />
+                    {
+                        match stability_info {
+                            Some(info) => html! { <span>{ info }</span> },
+                            None => html! {},
+                        }
+                    }
+                    {
+                        if config.race_algorithms.is_empty() {
+                            sort_graph(renderer, (*output_at_active_step).clone(), (*active_step).clone(), config.palette, config.visualization, inversions, bar_colors)
+                        } else {
+                            let race = race.borrow();
+                            // The first racer is the algorithm itself
+                            let names = iter::once(&config.sorting_algorithm)
+                                .chain(&config.race_algorithms)
+                                .map(|algorithm| algorithm.name.clone())
+                                .collect::<Vec<String>>();
 
-                    <SortGraph
-                        items={output_at_active_step.clone()}
-                        step={active_step.clone()}
-                    />
+                            html! {
+                                <>
+                                    <RaceLeaderboard standings={race_standings(&race, &names)} />
+                                    <RaceGrid>
+                                        <div class="race-lane">
+                                            <span>{ format!("{}: {}", names[0], race_progress(&race, 0)) }</span>
+                                            { sort_graph(renderer, (*output_at_active_step).clone(), (*active_step).clone(), config.palette, config.visualization, inversions, bar_colors) }
+                                        </div>
+                                        {
+                                            for race.racers().iter().zip(&names).enumerate().skip(1).map(|(i, (racer, name))| html! {
+                                                <div class="race-lane">
+                                                    <span>{ format!("{}: {}", name, race_progress(&race, i)) }</span>
+                                                    { sort_graph(renderer, racer.items().to_vec(), race.frame_steps(i).concat(), config.palette, config.visualization, vec![], vec![]) }
+                                                </div>
+                                            })
+                                        }
+                                    </RaceGrid>
+                                </>
+                            }
+                        }
+                    }
 
                     <StepSlider
                         active_step_index={*active_step_index}
-                        max={sort_result.borrow().steps.len()}
+                        max={race.borrow().len()}
                         on_change={change_step}
-                        playback_time={config.playback_time}
+                        {playback_time}
                     />
 
+                    {
+                        match narration {
+                            Some(narration) => html! { <p class="step-narration">{ narration }</p> },
+                            None => html! {},
+                        }
+                    }
                     <span class="step-info">
                         <label for="stepSlider">
                             {
                                 if *active_step_index == 0 {
-                                    format!("Step {} (input)", *active_step_index)
+                                    format!("Operation {} (input)", *active_step_index)
                                 } else {
-                                    format!("Step {}: ", *active_step_index)
+                                    format!("Operation {}: ", *active_step_index)
                                 }
                             }
                         </label>
@@ -340,26 +1276,155 @@ pub fn sorting_algorithms_page(props: &SortingAlgorithmsPageProps) -> Html {
                             active_step.iter().map(|command| match command {
                                 SortCommand::Swap(from, to) => format!("SWAP indices {} and {}", from, to),
                                 SortCommand::Set(i, val) => format!("SET value at index {} to {}", i, val),
+                                SortCommand::PhaseBoundary(label) => format!("START {}", label),
+                                SortCommand::Event(event) => format!("EVENT {}", event),
                             }).collect::<Vec<String>>().join(";")
                         }
                     </span>
                 </div>
 
+                {
+                    match heap_phase {
+                        Some((phase, heap_len)) => html! {
+                            <div class="heapsort-heap">
+                                <h3>{ format!("Heap: {} phase", phase.name()) }</h3>
+                                <p>
+                                    {
+                                        match phase {
+                                            HeapsortPhase::BuildHeap => "Every item that has children is sifted down, from the last one to the root, until the items form a max-heap.",
+                                            HeapsortPhase::Extract => "The root is the largest item of the heap, so it's swapped to the end of the heap, which is where it belongs. The heap then shrinks by one and the new root is sifted down.",
+                                        }
+                                    }
+                                </p>
+                                <HeapView
+                                    items={output_at_active_step.iter().map(heap_label).collect::<Vec<String>>()}
+                                    {heap_len}
+                                    highlighted={active_step.iter().flat_map(|command| match command {
+                                        SortCommand::Swap(from, to) => vec![*from, *to],
+                                        SortCommand::Event(SortEvent::Heapify(root, _)) => vec![*root],
+                                        _ => vec![],
+                                    }).collect::<Vec<usize>>()}
+                                />
+                            </div>
+                        },
+                        None => html! {},
+                    }
+                }
+
                 <AlgoDesc algorithm={config.sorting_algorithm.name.clone()} />
             </main>
         </div>
     }
 }
 
-/// Gets the output at a given step's index by running [`SortCommand`]s on the input.
-fn get_output_at_step_index(
-    input: &[u32],
-    steps: &[Vec<SortCommand<u32>>],
-    index: usize,
-) -> Vec<u32> {
-    let mut output = input.to_vec();
-    run_sort_steps(&mut output, &steps[0..index]);
-    output
+/// Labels an item of the heap of heapsort, rounding floats so that they fit in the nodes.
+fn heap_label(item: &TotalF64) -> String {
+    if item.0.fract() == 0.0 {
+        item.to_string()
+    } else {
+        format!("{:.1}", item.0)
+    }
+}
+
+/// Draws the items with the given renderer, which can't be [`SortRenderer::Auto`].
+/// Only the full renderer draws the `inversions`, the `colors` and the visualizations other than [`SortVisualization::Bars`].
+fn sort_graph(
+    renderer: SortRenderer,
+    items: Vec<TotalF64>,
+    step: Vec<SortCommand<TotalF64>>,
+    palette: Palette,
+    visualization: SortVisualization,
+    inversions: Vec<(usize, usize)>,
+    colors: Vec<String>,
+) -> Html {
+    match renderer {
+        SortRenderer::Incremental => html! {
+            <IncrementalSortGraph {items} {step} {palette} />
+        },
+        _ => html! {
+            <SortGraph {items} {step} {palette} {visualization} {inversions} {colors} />
+        },
+    }
+}
+
+/// Colors the items of the input by the order of the equal values, from light to dark,
+/// so after a stable sort every run of equal values goes from light to dark.
+fn stability_colors(input: &[TotalF64]) -> Vec<String> {
+    // How many of the equal values came before each value in the input, and how many of each value there are
+    let mut counts = BTreeMap::new();
+    let order = input
+        .iter()
+        .map(|value| {
+            let count = counts.entry(*value).or_insert(0);
+            *count += 1;
+            *count - 1
+        })
+        .collect::<Vec<usize>>();
+
+    input
+        .iter()
+        .zip(order)
+        .map(|(value, order)| {
+            let count = counts[value];
+            let lightness = if count > 1 {
+                75 - 50 * order / (count - 1)
+            } else {
+                50
+            };
+            format!("hsl(200, 80%, {}%)", lightness)
+        })
+        .collect()
+}
+
+/// Sorts the `input` with the algorithms racing the selected one and starts a race between them,
+/// where the first racer plays the `steps` of the selected algorithm.
+fn start_race(
+    config: &SortConfig,
+    input: &[TotalF64],
+    steps: Vec<Vec<SortCommand<TotalF64>>>,
+) -> SortRace<TotalF64> {
+    let mut race_steps = vec![steps];
+    race_steps.extend(
+        config
+            .race_algorithms
+            .iter()
+            .map(|algorithm| algorithm.sort(&mut input.to_vec()).steps),
+    );
+    SortRace::new(input.to_vec(), race_steps)
+}
+
+/// Describes how far the racer at `index` has gotten, e.g. `"operation 10 of 45"` or `"finished 2nd in 45 operations"`.
+fn race_progress<T: Clone>(race: &SortRace<T>, index: usize) -> String {
+    let operations = race.operations(index);
+    match race.place(index) {
+        Some(place) => {
+            let suffix = match (place % 10, place % 100) {
+                (1, 11) | (2, 12) | (3, 13) => "th",
+                (1, _) => "st",
+                (2, _) => "nd",
+                (3, _) => "rd",
+                _ => "th",
+            };
+            format!("finished {}{} in {} operations", place, suffix, operations)
+        }
+        None => format!("operation {} of {}", race.frame(), operations),
+    }
+}
+
+/// Gets the standings of the racers, which are named by `names` in the same order.
+/// Only the racers that are both named and in the race have a standing.
+fn race_standings<T: Clone>(race: &SortRace<T>, names: &[String]) -> Vec<RaceStanding> {
+    names
+        .iter()
+        .zip(race.racers())
+        .enumerate()
+        .map(|(index, (name, _))| RaceStanding {
+            algorithm: name.clone(),
+            place: race.place(index),
+            operations: race.frame().min(race.operations(index)),
+            total_operations: race.operations(index),
+        })
+        .collect()
 }
 
 #[derive(Clone, PartialEq, Properties)]
@@ -381,3 +1446,154 @@ fn sorting_404_page(props: &Sorting404PageProps) -> Html {
         </>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_are_the_slugs_of_the_names() {
+        for (slug, algorithm) in get_sorting_algorithms() {
+            assert_eq!(algorithm_slug(&algorithm.name), slug);
+        }
+    }
+
+    #[test]
+    fn library_algorithms_have_routes() {
+        let algorithms = get_sorting_algorithms();
+        for name in sorting::sorting_algorithm_names() {
+            assert!(algorithms.contains_key(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn descending_entries_sort_non_increasing() {
+        let algorithms = get_sorting_algorithms();
+        let input = vec![5_u32, 3, 8, 1, 9, 2, 8, 7]
+            .into_iter()
+            .map(TotalF64::from)
+            .collect::<Vec<_>>();
+
+        for slug in ["insertion-sort-descending", "quicksort-descending"] {
+            let algorithm = &algorithms[slug];
+            // The dropdown navigates to the name turned into a slug
+            assert_eq!(algorithm_slug(&algorithm.name), slug);
+
+            let mut items = input.clone();
+            let result = algorithm.sort(&mut items);
+            assert!(items.windows(2).all(|pair| pair[0] >= pair[1]));
+            assert!(result.comparisons.unwrap() >= input.len() - 1);
+
+            // The steps turn the input into the same descending order
+            let mut replayed = input.clone();
+            run_sort_steps(&mut replayed, &result.steps);
+            assert_eq!(replayed, items);
+        }
+    }
+
+    #[test]
+    fn origins_follow_the_items() {
+        let algorithms = get_sorting_algorithms();
+        let input = vec![3_u32, 1, 3, 2, 1, 3, 2, 1]
+            .into_iter()
+            .map(TotalF64::from)
+            .collect::<Vec<_>>();
+
+        for (slug, stable) in [("merge-sort", true), ("heapsort", false)] {
+            let mut items = input.clone();
+            let (_, origin_steps) = algorithms[slug].sort_with_origins(&mut items);
+
+            let mut origins = (0..input.len()).collect::<Vec<usize>>();
+            run_sort_steps(&mut origins, &origin_steps.unwrap());
+            for (item, origin) in items.iter().zip(origins.iter()) {
+                assert_eq!(input[*origin], *item);
+            }
+
+            let sorted = origins
+                .iter()
+                .map(|origin| Keyed {
+                    key: input[*origin],
+                    value: *origin,
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(unstable_pairs(&sorted).is_empty(), stable, "{}", slug);
+
+            // The colors move with the items, and are worked out once for the whole sort
+            let origin_steps = algorithms[slug]
+                .sort_with_origins(&mut input.clone())
+                .1
+                .unwrap();
+            let step_count = origin_steps.len();
+            let mut demo = StabilityDemo::new(&input, origin_steps);
+            assert_eq!(demo.info.starts_with("Stable"), stable, "{}", slug);
+            assert_eq!(demo.colors_at(0), stability_colors(&input));
+            let colors = demo.colors_at(step_count);
+            for (color, origin) in colors.iter().zip(origins.iter()) {
+                assert_eq!(*color, stability_colors(&input)[*origin]);
+            }
+        }
+
+        // Radix sort writes new numbers, so the items can't be followed
+        let (_, origin_steps) = algorithms["radix-sort"].sort_with_origins(&mut input.clone());
+        assert!(origin_steps.is_none());
+    }
+
+    #[test]
+    fn query_reproduces_the_input() {
+        let config = SortConfig {
+            input_len: 50,
+            distribution: InputDistribution::NearlySorted,
+            float_range: Some((2.5, 10.0)),
+            ..SortConfig::default()
+        };
+        let query = serde_urlencoded::to_string(SortQuery::new(&config)).unwrap();
+        assert!(query.contains("distribution=NearlySorted"));
+
+        let shared = SortConfig::default().with_query(serde_urlencoded::from_str(&query).unwrap());
+        assert!(shared == config);
+        assert_eq!(shared.gen_input(), config.gen_input());
+
+        // Whole numbers have no range
+        let query = serde_urlencoded::to_string(SortQuery::new(&SortConfig::default())).unwrap();
+        assert!(!query.contains("min") && !query.contains("max"));
+
+        // A link can't ask for a longer input than the sidebar allows
+        let query = SortQuery {
+            len: 100_000_000,
+            ..SortQuery::new(&config)
+        };
+        assert_eq!(
+            SortConfig::default().with_query(query).input_len,
+            MAX_INPUT_LEN
+        );
+    }
+
+    #[test]
+    fn restored_racers_join_the_first_race() {
+        let algorithms = get_sorting_algorithms();
+        let config = SortConfig {
+            input_len: 20,
+            race_algorithms: vec![
+                algorithms["merge-sort"].clone(),
+                algorithms["heapsort"].clone(),
+            ],
+            ..SortConfig::default()
+        };
+        let saved: SortConfig =
+            serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+        let names = iter::once(&saved.sorting_algorithm)
+            .chain(&saved.race_algorithms)
+            .map(|algorithm| algorithm.name.clone())
+            .collect::<Vec<_>>();
+
+        let input = saved.gen_input();
+        let steps = saved.sorting_algorithm.sort(&mut input.clone()).steps;
+        let race = start_race(&saved, &input, steps.clone());
+        assert_eq!(race.racers().len(), 3);
+        assert_eq!(race_standings(&race, &names).len(), 3);
+
+        // A race from before the racers were restored has no standings for them
+        let race = SortRace::new(input, vec![steps]);
+        assert_eq!(race_standings(&race, &names).len(), 1);
+    }
+}
@@ -0,0 +1,148 @@
+use string_algorithms::{MatchState, MatchStep, StringAlgorithm};
+use yew::prelude::*;
+use yew_hooks::use_title;
+
+use crate::{
+    components::{
+        collapsible::Collapsible,
+        input_items::{PlaybackTimeInput, SelectInput, TextInput},
+        sidebar::Sidebar,
+        step_slider::StepSlider,
+        strings::match_view::MatchView,
+    },
+    hooks::use_settings::use_settings,
+};
+
+/// The longest text that is searched, so that its characters fit on the page.
+const MAX_TEXT_LEN: usize = 200;
+
+/// Searches a pattern in a text with a string matching algorithm and plays back its comparisons step by step.
+#[function_component(StringsPage)]
+pub fn strings_page() -> Html {
+    let algorithm = use_state_eq(|| StringAlgorithm::Kmp);
+    let text = use_state_eq(|| "abracadabra cadabra abracadabra".to_string());
+    let pattern = use_state_eq(|| "abra".to_string());
+    let step_index = use_state(|| 0);
+    let settings = use_settings();
+
+    use_title(format!("{} - String algorithms", algorithm.name()));
+
+    let change_algorithm = {
+        let algorithm = algorithm.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |name: String| {
+            if let Some(new_algorithm) = StringAlgorithm::ALL
+                .iter()
+                .copied()
+                .find(|algorithm| algorithm.name() == name)
+            {
+                algorithm.set(new_algorithm);
+                step_index.set(0);
+            }
+        })
+    };
+
+    let change_text = {
+        let text = text.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |new_text: String| {
+            text.set(new_text);
+            step_index.set(0);
+        })
+    };
+
+    let change_pattern = {
+        let pattern = pattern.clone();
+        let step_index = step_index.clone();
+
+        Callback::from(move |new_pattern: String| {
+            pattern.set(new_pattern);
+            step_index.set(0);
+        })
+    };
+
+    let on_change_step = {
+        let step_index = step_index.clone();
+        Callback::from(move |index| step_index.set(index))
+    };
+
+    let text_chars = text.chars().take(MAX_TEXT_LEN).collect::<Vec<char>>();
+    let pattern_chars = pattern.chars().collect::<Vec<char>>();
+    let text_error = (text.chars().count() > MAX_TEXT_LEN)
+        .then(|| format!("Only the first {} characters are searched", MAX_TEXT_LEN));
+    let pattern_error = (pattern_chars.len() > text_chars.len())
+        .then(|| "The pattern is longer than the text".to_string());
+
+    let mut steps = vec![];
+    let matches = algorithm.search_fn()(&text_chars, &pattern_chars, &mut steps);
+    let shown_steps = &steps[..(*step_index).min(steps.len())];
+    let state = MatchState::after(shown_steps);
+    let step = shown_steps.last().cloned();
+    let comparisons = |steps: &[MatchStep]| {
+        steps
+            .iter()
+            .filter(|step| matches!(step, MatchStep::Compare(..)))
+            .count()
+    };
+
+    let narration = match &step {
+        Some(step) => step.to_string(),
+        None if pattern_chars.is_empty() => "Type a pattern to search for".to_string(),
+        None if matches.is_empty() => "The pattern is not in the text".to_string(),
+        None => format!(
+            "The pattern starts at these indices of the text: {}",
+            matches
+                .iter()
+                .map(|shift| shift.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    };
+
+    html! {
+        <div class="page" id="Strings">
+            <Sidebar>
+                <h2>{"Config"}</h2>
+
+                <Collapsible title="General" open={true} class="config-section">
+                    <SelectInput
+                        title="Algorithm"
+                        options={StringAlgorithm::ALL.iter().map(|algorithm| algorithm.name().to_string()).collect::<Vec<String>>()}
+                        selected_value={algorithm.name().to_string()}
+                        onchange={change_algorithm}
+                    />
+                    <TextInput
+                        title="Text"
+                        value={(*text).clone()}
+                        error={text_error}
+                        oninput={change_text}
+                    />
+                    <TextInput
+                        title="Pattern"
+                        value={(*pattern).clone()}
+                        error={pattern_error}
+                        oninput={change_pattern}
+                    />
+                    <PlaybackTimeInput />
+                </Collapsible>
+            </Sidebar>
+
+            <main>
+                <MatchView text={text_chars} pattern={pattern_chars} {state} {step} />
+                <p class="step-narration">{ narration }</p>
+                <span>
+                    { format!("Comparisons: {}/{}", comparisons(shown_steps), comparisons(&steps)) }
+                </span>
+                <StepSlider
+                    label={format!("Steps ({}/{})", *step_index, steps.len())}
+                    active_step_index={*step_index}
+                    max={steps.len()}
+                    on_change={on_change_step}
+                    playback_time={settings.playback_time}
+                />
+            </main>
+        </div>
+    }
+}
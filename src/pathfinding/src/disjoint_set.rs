@@ -0,0 +1,126 @@
+//! A union-find structure for tracking which items have been joined together, e.g. the trees of a spanning forest.
+use std::collections::{btree_map::Entry, BTreeMap};
+
+/// Splits items into disjoint sets that can be joined, and finds the set that an item belongs to in nearly constant time.\
+/// Every set is a tree whose root represents it. The smaller tree is always attached under the root of the larger one,
+/// and finding a root points everything on the way directly at it, so the trees stay flat.
+///
+/// ## Example
+///
+/// ```rust
+/// use pathfinding::disjoint_set::DisjointSet;
+///
+/// let mut sets = DisjointSet::from_iter([1, 2, 3, 4]);
+/// assert!(sets.union(1, 2));
+/// assert!(sets.union(3, 2));
+///
+/// // 1 and 3 were already joined through 2
+/// assert!(!sets.union(1, 3));
+/// assert!(sets.in_same_set(1, 3));
+/// assert_eq!(sets.set_count(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisjointSet<T: Ord + Copy> {
+    parents: BTreeMap<T, T>,
+    /// The number of items in the tree of every root.
+    sizes: BTreeMap<T, usize>,
+}
+impl<T: Ord + Copy> DisjointSet<T> {
+    pub fn new() -> Self {
+        Self {
+            parents: BTreeMap::new(),
+            sizes: BTreeMap::new(),
+        }
+    }
+    /// Adds `item` in a set of its own. Does nothing if the item already exists.
+    pub fn make_set(&mut self, item: T) {
+        if let Entry::Vacant(entry) = self.parents.entry(item) {
+            entry.insert(item);
+            self.sizes.insert(item, 1);
+        }
+    }
+    /// Gets the item that represents the set of `item`, adding `item` in a set of its own if it doesn't exist.
+    pub fn find(&mut self, item: T) -> T {
+        self.make_set(item);
+
+        let mut root = item;
+        while self.parents[&root] != root {
+            root = self.parents[&root];
+        }
+
+        // Point everything on the way directly at the root
+        let mut current = item;
+        while current != root {
+            let next = self.parents[&current];
+            self.parents.insert(current, root);
+            current = next;
+        }
+
+        root
+    }
+    /// Joins the sets of `a` and `b`. Returns `false` if they were already in the same set.
+    pub fn union(&mut self, a: T, b: T) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+
+        let (larger, smaller) = if self.sizes[&root_a] >= self.sizes[&root_b] {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.parents.insert(smaller, larger);
+        let smaller_size = self.sizes.remove(&smaller).unwrap_or_default();
+        *self.sizes.entry(larger).or_default() += smaller_size;
+
+        true
+    }
+    /// Returns `true` if `a` and `b` are in the same set.
+    pub fn in_same_set(&mut self, a: T, b: T) -> bool {
+        self.find(a) == self.find(b)
+    }
+    /// Gets the number of separate sets.
+    pub fn set_count(&self) -> usize {
+        self.sizes.len()
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<T> for DisjointSet<T> {
+    /// Puts every item in a set of its own.
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut sets = Self::new();
+        for item in items {
+            sets.make_set(item);
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unions_merge_sets() {
+        let mut sets = (0..10).collect::<DisjointSet<u32>>();
+        assert_eq!(sets.set_count(), 10);
+
+        // Join the even numbers together and the odd numbers together
+        for i in 2..10 {
+            assert!(sets.union(i, i - 2));
+        }
+        assert_eq!(sets.set_count(), 2);
+        assert!(sets.in_same_set(0, 8));
+        assert!(sets.in_same_set(1, 9));
+        assert!(!sets.in_same_set(0, 9));
+
+        assert!(sets.union(9, 4));
+        assert!(!sets.union(0, 1));
+        assert_eq!(sets.set_count(), 1);
+
+        // Finding a missing item adds it
+        assert_eq!(sets.find(20), 20);
+        assert_eq!(sets.set_count(), 2);
+    }
+}
@@ -0,0 +1,219 @@
+//! A single error type for all of the ways that the graph algorithms can fail.
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display},
+};
+
+use crate::{
+    graph::{CycleError, GraphTooLargeError, NegativeCycle, ParseGraphError},
+    pathfinding_algorithms::NegativeWeightError,
+    ParseCoordError,
+};
+
+/// Any error returned by the graph algorithms, so that callers running several of them can handle all failures the same way with `?`.\
+/// Each of the specific errors converts into it with [`From`]. `V` and `E` are the vertex and edge weight types of the graph,
+/// which are needed for [`NegativeCycle`] and [`NegativeWeightError`].
+///
+/// ## Example
+///
+/// ```rust
+/// use pathfinding::{error::GraphError, graph::AdjacencyList, Coord};
+///
+/// fn shortest_path_count(graph: &AdjacencyList<Coord, f32>) -> Result<usize, GraphError<Coord, f32>> {
+///     graph.topological_sort()?;
+///     Ok(graph.johnsons()?.len())
+/// }
+///
+/// let mut graph = AdjacencyList::<Coord, f32>::default();
+/// graph.add_edge_undirected(Coord::new(0, 0), Coord::new(1, 0), 1.0);
+/// let error = shortest_path_count(&graph).unwrap_err();
+///
+/// assert!(matches!(error, GraphError::Cycle(_)));
+/// assert_eq!(error.to_string(), "graph has a cycle: 0,0 1,0 0,0");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum GraphError<V, E> {
+    Cycle(CycleError<V>),
+    NegativeCycle(NegativeCycle<V>),
+    NegativeWeight(NegativeWeightError<V, E>),
+    TooLarge(GraphTooLargeError),
+    /// A graph or a vertex couldn't be read from text.
+    Parse(ParseGraphError),
+}
+impl<V: Display, E: Display> Display for GraphError<V, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::Cycle(error) => write!(f, "{}", error),
+            GraphError::NegativeCycle(error) => write!(f, "{}", error),
+            GraphError::NegativeWeight(error) => write!(f, "{}", error),
+            GraphError::TooLarge(error) => write!(f, "{}", error),
+            GraphError::Parse(error) => write!(f, "{}", error),
+        }
+    }
+}
+impl<V: Debug + Display + 'static, E: Debug + Display + 'static> Error for GraphError<V, E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GraphError::Cycle(error) => Some(error),
+            GraphError::NegativeCycle(error) => Some(error),
+            GraphError::NegativeWeight(error) => Some(error),
+            GraphError::TooLarge(error) => Some(error),
+            GraphError::Parse(error) => Some(error),
+        }
+    }
+}
+
+impl<V, E> From<CycleError<V>> for GraphError<V, E> {
+    fn from(error: CycleError<V>) -> Self {
+        GraphError::Cycle(error)
+    }
+}
+impl<V, E> From<NegativeCycle<V>> for GraphError<V, E> {
+    fn from(error: NegativeCycle<V>) -> Self {
+        GraphError::NegativeCycle(error)
+    }
+}
+impl<V, E> From<NegativeWeightError<V, E>> for GraphError<V, E> {
+    fn from(error: NegativeWeightError<V, E>) -> Self {
+        GraphError::NegativeWeight(error)
+    }
+}
+impl<V, E> From<GraphTooLargeError> for GraphError<V, E> {
+    fn from(error: GraphTooLargeError) -> Self {
+        GraphError::TooLarge(error)
+    }
+}
+impl<V, E> From<ParseGraphError> for GraphError<V, E> {
+    fn from(error: ParseGraphError) -> Self {
+        GraphError::Parse(error)
+    }
+}
+/// A coordinate that can't be parsed is an invalid vertex, like in a graph that can't be read.
+impl<V, E> From<ParseCoordError> for GraphError<V, E> {
+    fn from(error: ParseCoordError) -> Self {
+        GraphError::Parse(ParseGraphError::InvalidVertex(error.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{graph::AdjacencyList, Coord};
+
+    type CoordGraphError = GraphError<Coord, f32>;
+
+    #[test]
+    fn variants_format_like_their_errors() {
+        let negative_weight = NegativeWeightError {
+            from: Coord::new(0, 0),
+            to: Coord::new(1, 0),
+            weight: -2.0,
+        };
+        let too_large = GraphTooLargeError {
+            vertex_count: 20,
+            limit: 12,
+        };
+
+        let errors: [(CoordGraphError, &str); 5] = [
+            (
+                CycleError(vec![Coord::new(0, 0), Coord::new(1, 0)]).into(),
+                "graph has a cycle: 0,0 1,0 0,0",
+            ),
+            (
+                NegativeCycle {
+                    cycle: vec![Coord::new(0, 0), Coord::new(1, 0)],
+                }
+                .into(),
+                "graph has a negative cycle: 0,0 1,0 0,0",
+            ),
+            (negative_weight.into(), &negative_weight.to_string()),
+            (
+                too_large.into(),
+                "graph has 20 vertices, but at most 12 are supported",
+            ),
+            (
+                ParseGraphError::InvalidWeight("heavy".to_string()).into(),
+                "\"heavy\" is not a valid edge weight",
+            ),
+        ];
+        for (error, message) in errors {
+            assert_eq!(error.to_string(), message);
+            assert_eq!(error.source().unwrap().to_string(), message);
+        }
+        assert!(negative_weight.to_string().contains("-2"));
+    }
+
+    #[test]
+    fn question_mark_converts_each_error() {
+        fn fail_with<T>(error: T) -> Result<(), CoordGraphError>
+        where
+            CoordGraphError: From<T>,
+        {
+            Err(error)?;
+            Ok(())
+        }
+
+        let cycle = CycleError(vec![Coord::new(0, 0), Coord::new(1, 0)]);
+        assert_eq!(fail_with(cycle.clone()), Err(GraphError::Cycle(cycle)));
+        let parse_error = ParseGraphError::Syntax {
+            line: 2,
+            message: "expected }".to_string(),
+        };
+        assert_eq!(
+            fail_with(parse_error.clone()),
+            Err(GraphError::Parse(parse_error))
+        );
+        // A coordinate that can't be parsed is reported as the vertex that couldn't be read
+        assert_eq!(
+            fail_with("2;3".parse::<Coord>().unwrap_err()),
+            Err(GraphError::Parse(ParseGraphError::InvalidVertex(
+                "2;3".to_string()
+            )))
+        );
+        let negative_cycle = NegativeCycle {
+            cycle: vec![Coord::new(0, 0)],
+        };
+        assert_eq!(
+            fail_with(negative_cycle.clone()),
+            Err(GraphError::NegativeCycle(negative_cycle))
+        );
+        let too_large = GraphTooLargeError {
+            vertex_count: 13,
+            limit: 12,
+        };
+        assert_eq!(fail_with(too_large), Err(GraphError::TooLarge(too_large)));
+        let negative_weight = NegativeWeightError {
+            from: Coord::new(2, 0),
+            to: Coord::new(3, 0),
+            weight: -1.0,
+        };
+        assert_eq!(
+            fail_with(negative_weight),
+            Err(GraphError::NegativeWeight(negative_weight))
+        );
+    }
+
+    #[test]
+    fn cycles_survive_the_conversion() {
+        fn cheapest_cost(
+            graph: &AdjacencyList<Coord, f32>,
+        ) -> Result<Option<f32>, CoordGraphError> {
+            let tree = graph.bellman_ford(Coord::new(0, 0))?;
+            Ok(tree.cost(&Coord::new(2, 0)))
+        }
+
+        // 1 -> 2 -> 1 costs -1 around the loop
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(Coord::new(0, 0), Coord::new(1, 0), 1.0);
+        graph.add_edge_directed(Coord::new(1, 0), Coord::new(2, 0), 1.0);
+        assert_eq!(cheapest_cost(&graph), Ok(Some(2.0)));
+
+        graph.add_edge_directed(Coord::new(2, 0), Coord::new(1, 0), -2.0);
+        let Err(GraphError::NegativeCycle(NegativeCycle { mut cycle })) = cheapest_cost(&graph)
+        else {
+            panic!("the negative cycle should be returned");
+        };
+        cycle.sort();
+        assert_eq!(cycle, vec![Coord::new(1, 0), Coord::new(2, 0)]);
+    }
+}
@@ -0,0 +1,807 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fmt,
+};
+
+use super::AdjacencyList;
+use crate::{Edge, Vertex};
+
+/// An error that is returned when an algorithm that only works on directed acyclic graphs finds a cycle.\
+/// It holds the cycle from [`AdjacencyList::find_cycle_directed`], so the last vertex has an edge back to the first one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleError<V>(pub Vec<V>);
+impl<V: fmt::Display> fmt::Display for CycleError<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph has a cycle:")?;
+        for vertex in self.0.iter().chain(self.0.first()) {
+            write!(f, " {}", vertex)?;
+        }
+        Ok(())
+    }
+}
+impl<V: fmt::Debug + fmt::Display> Error for CycleError<V> {}
+
+/// An error that is returned when shortest paths aren't defined because the graph has a cycle whose weights sum to a negative number,
+/// so going around it again always makes a path cheaper.\
+/// The vertices of the cycle are in the order of the edges, so the last one has an edge back to the first one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegativeCycle<V> {
+    pub cycle: Vec<V>,
+}
+impl<V: fmt::Display> fmt::Display for NegativeCycle<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph has a negative cycle:")?;
+        for vertex in self.cycle.iter().chain(self.cycle.first()) {
+            write!(f, " {}", vertex)?;
+        }
+        Ok(())
+    }
+}
+impl<V: fmt::Debug + fmt::Display> Error for NegativeCycle<V> {}
+
+/// An error that is returned when a graph is too large for an algorithm that takes exponential time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GraphTooLargeError {
+    pub vertex_count: usize,
+    pub limit: usize,
+}
+impl fmt::Display for GraphTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "graph has {} vertices, but at most {} are supported",
+            self.vertex_count, self.limit
+        )
+    }
+}
+impl Error for GraphTooLargeError {}
+
+/// The largest number of vertices that [`AdjacencyList::is_isomorphic_to`] accepts.
+pub const ISOMORPHISM_VERTEX_LIMIT: usize = 12;
+
+impl<V: Vertex, E: Clone> AdjacencyList<V, E> {
+    /// Gets the neighbors of every vertex when the edges are interpreted as undirected, i.e. an edge in either direction connects the vertices.
+    fn undirected_neighbors(&self) -> BTreeMap<V, BTreeSet<V>> {
+        let mut neighbors: BTreeMap<V, BTreeSet<V>> = self
+            .hash_map
+            .keys()
+            .map(|vertex| (*vertex, BTreeSet::new()))
+            .collect();
+
+        for (vertex, edges) in self.hash_map.iter() {
+            for neighbor in edges.keys() {
+                neighbors.entry(*vertex).or_default().insert(*neighbor);
+                neighbors.entry(*neighbor).or_default().insert(*vertex);
+            }
+        }
+
+        neighbors
+    }
+    /// Gets the degrees of all vertices in descending order, interpreting the graph as undirected.\
+    /// A self-loop contributes 2 to the degree of its vertex.
+    pub fn degree_sequence(&self) -> Vec<usize> {
+        let mut degrees = self
+            .undirected_neighbors()
+            .iter()
+            .map(|(vertex, neighbors)| {
+                if neighbors.contains(vertex) {
+                    neighbors.len() + 1
+                } else {
+                    neighbors.len()
+                }
+            })
+            .collect::<Vec<usize>>();
+        degrees.sort_unstable_by(|a, b| b.cmp(a));
+        degrees
+    }
+    /// Gets the degree of `vertex` when interpreting the graph as undirected, like in [`AdjacencyList::degree_sequence`].\
+    /// This is the number of vertices with an edge to or from `vertex`, where a self-loop counts twice.
+    pub fn degree(&self, vertex: &V) -> usize {
+        let neighbors = self
+            .hash_map
+            .iter()
+            .filter(|(_, edges)| edges.contains_key(vertex))
+            .map(|(other, _)| other)
+            .chain(self.ordered_neighbors(vertex).map(|(neighbor, _)| neighbor))
+            .collect::<BTreeSet<&V>>();
+        if neighbors.contains(vertex) {
+            neighbors.len() + 1
+        } else {
+            neighbors.len()
+        }
+    }
+    /// Gets the number of edges leading to `vertex`. A self-loop counts once.\
+    /// Only the edges leaving a vertex are stored, so this goes through every vertex.
+    pub fn in_degree(&self, vertex: &V) -> usize {
+        self.hash_map
+            .values()
+            .filter(|edges| edges.contains_key(vertex))
+            .count()
+    }
+    /// Gets the number of edges leaving `vertex`. A self-loop counts once.
+    pub fn out_degree(&self, vertex: &V) -> usize {
+        self.ordered_neighbors(vertex).count()
+    }
+    /// Gets the number of edges leading to each vertex in descending order, interpreting the graph as directed.\
+    /// A self-loop contributes 1 to both the in-degree and the out-degree of its vertex.
+    pub fn in_degree_sequence(&self) -> Vec<usize> {
+        self.directed_degree_sequence(|_, neighbor| *neighbor)
+    }
+    /// Gets the number of edges leaving each vertex in descending order, interpreting the graph as directed.\
+    /// A self-loop contributes 1 to both the in-degree and the out-degree of its vertex.
+    pub fn out_degree_sequence(&self) -> Vec<usize> {
+        self.directed_degree_sequence(|vertex, _| *vertex)
+    }
+    /// Counts every edge `(vertex, neighbor)` for the vertex returned by `counted_end` and sorts the counts in descending order.
+    /// Vertices without an entry in the map still get a degree, so both directed sequences have the same length.
+    fn directed_degree_sequence(&self, counted_end: impl Fn(&V, &V) -> V) -> Vec<usize> {
+        let mut degrees: BTreeMap<V, usize> = self
+            .undirected_neighbors()
+            .keys()
+            .map(|vertex| (*vertex, 0))
+            .collect();
+
+        for (vertex, edges) in self.hash_map.iter() {
+            for neighbor in edges.keys() {
+                *degrees.entry(counted_end(vertex, neighbor)).or_default() += 1;
+            }
+        }
+
+        let mut degrees = degrees.into_values().collect::<Vec<usize>>();
+        degrees.sort_unstable_by(|a, b| b.cmp(a));
+        degrees
+    }
+    /// Checks if the graph has a cycle when the edges are interpreted as directed. A self-loop is a cycle.
+    pub fn has_cycle_directed(&self) -> bool {
+        self.find_cycle_directed().is_some()
+    }
+    /// Finds a cycle when the edges are interpreted as directed, e.g. to show why [`AdjacencyList::topological_sort`] failed.\
+    /// Every vertex of the cycle has an edge to the next one, and the last one has an edge back to the first one. A self-loop is a cycle of one vertex.
+    pub fn find_cycle_directed(&self) -> Option<Vec<V>> {
+        // Vertices on the current DFS path are `true`, finished vertices are `false`
+        let mut on_path: BTreeMap<V, bool> = BTreeMap::new();
+
+        for root in self.hash_map.keys() {
+            if on_path.contains_key(root) {
+                continue;
+            }
+            on_path.insert(*root, true);
+            let mut stack = vec![(*root, self.ordered_neighbors(root))];
+
+            while let Some((vertex, neighbors)) = stack.last_mut() {
+                if let Some((neighbor, _)) = neighbors.next() {
+                    match on_path.get(neighbor) {
+                        Some(true) => {
+                            // The cycle is the part of the path from the neighbor back to it
+                            let start = stack.iter().position(|(vertex, _)| vertex == neighbor)?;
+                            return Some(
+                                stack[start..].iter().map(|(vertex, _)| *vertex).collect(),
+                            );
+                        }
+                        Some(false) => (),
+                        None => {
+                            on_path.insert(*neighbor, true);
+                            stack.push((*neighbor, self.ordered_neighbors(neighbor)));
+                        }
+                    }
+                } else {
+                    on_path.insert(*vertex, false);
+                    stack.pop();
+                }
+            }
+        }
+
+        None
+    }
+    /// Orders the vertices so that every edge leads from an earlier vertex to a later one, using Kahn's algorithm.\
+    /// When several vertices could come next, the smallest one is picked, so the order is deterministic.
+    /// If there is no such order because of a cycle, the error holds the cycle found by [`AdjacencyList::find_cycle_directed`].
+    pub fn topological_sort(&self) -> Result<Vec<V>, CycleError<V>> {
+        let mut in_degrees: BTreeMap<V, usize> = self.hash_map.keys().map(|v| (*v, 0)).collect();
+        for edges in self.hash_map.values() {
+            for neighbor in edges.keys() {
+                *in_degrees.entry(*neighbor).or_default() += 1;
+            }
+        }
+
+        let mut ready = in_degrees
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(vertex, _)| *vertex)
+            .collect::<BTreeSet<V>>();
+        let mut order = Vec::with_capacity(in_degrees.len());
+
+        while let Some(vertex) = ready.pop_first() {
+            order.push(vertex);
+            for (neighbor, _) in self.ordered_neighbors(&vertex) {
+                let degree = in_degrees.get_mut(neighbor).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.insert(*neighbor);
+                }
+            }
+        }
+
+        // Vertices on a cycle never run out of incoming edges, and they all have edges, so the search finds one of the cycles
+        if order.len() < in_degrees.len() {
+            return Err(CycleError(self.find_cycle_directed().unwrap_or_default()));
+        }
+
+        Ok(order)
+    }
+    /// Checks if the graphs are the same apart from the labels of the vertices, i.e. if the vertices of this graph can be renamed to get `other`.\
+    /// Only the directed edges are compared, not their weights.
+    ///
+    /// The vertex mapping is found by backtracking, which takes exponential time in the worst case,
+    /// so an error is returned if either graph has more than [`ISOMORPHISM_VERTEX_LIMIT`] vertices.
+    pub fn is_isomorphic_to(&self, other: &Self) -> Result<bool, GraphTooLargeError> {
+        for graph in [self, other] {
+            if graph.hash_map.len() > ISOMORPHISM_VERTEX_LIMIT {
+                return Err(GraphTooLargeError {
+                    vertex_count: graph.hash_map.len(),
+                    limit: ISOMORPHISM_VERTEX_LIMIT,
+                });
+            }
+        }
+        if self.hash_map.len() != other.hash_map.len() || self.edges().len() != other.edges().len()
+        {
+            return Ok(false);
+        }
+
+        let vertices = self.ordered_vertices().copied().collect::<Vec<V>>();
+        let mut mapping = BTreeMap::new();
+        let mut used = BTreeSet::new();
+        Ok(self.extend_isomorphism(other, &vertices, &mut mapping, &mut used))
+    }
+    /// Tries every unused vertex of `other` for the first unmapped vertex in `vertices`, backtracking when the edges to the already mapped vertices don't match.
+    fn extend_isomorphism(
+        &self,
+        other: &Self,
+        vertices: &[V],
+        mapping: &mut BTreeMap<V, V>,
+        used: &mut BTreeSet<V>,
+    ) -> bool {
+        let Some(vertex) = vertices.get(mapping.len()).copied() else {
+            return true;
+        };
+        let has_edge = |graph: &Self, a: &V, b: &V| {
+            graph
+                .get_neighbors(a)
+                .is_some_and(|edges| edges.contains_key(b))
+        };
+        let in_degree = |graph: &Self, a: &V| {
+            graph
+                .hash_map
+                .values()
+                .filter(|edges| edges.contains_key(a))
+                .count()
+        };
+
+        for candidate in other.ordered_vertices() {
+            if used.contains(candidate)
+                || self.hash_map[&vertex].len() != other.hash_map[candidate].len()
+                || in_degree(self, &vertex) != in_degree(other, candidate)
+                || has_edge(self, &vertex, &vertex) != has_edge(other, candidate, candidate)
+            {
+                continue;
+            }
+            let consistent = mapping.iter().all(|(mapped, image)| {
+                has_edge(self, &vertex, mapped) == has_edge(other, candidate, image)
+                    && has_edge(self, mapped, &vertex) == has_edge(other, image, candidate)
+            });
+            if !consistent {
+                continue;
+            }
+
+            mapping.insert(vertex, *candidate);
+            used.insert(*candidate);
+            if self.extend_isomorphism(other, vertices, mapping, used) {
+                return true;
+            }
+            mapping.remove(&vertex);
+            used.remove(candidate);
+        }
+
+        false
+    }
+    /// Gets the connected components of the graph, interpreting the edges as undirected.\
+    /// The components are ordered by their smallest vertex.
+    pub fn connected_components(&self) -> Vec<BTreeSet<V>> {
+        let neighbors = self.undirected_neighbors();
+        let mut visited = BTreeSet::new();
+        let mut components = vec![];
+
+        for vertex in neighbors.keys() {
+            if !visited.insert(*vertex) {
+                continue;
+            }
+            let mut component = BTreeSet::from([*vertex]);
+            let mut stack = vec![*vertex];
+            while let Some(current) = stack.pop() {
+                for neighbor in neighbors[&current].iter() {
+                    if visited.insert(*neighbor) {
+                        component.insert(*neighbor);
+                        stack.push(*neighbor);
+                    }
+                }
+            }
+            components.push(component);
+        }
+
+        components
+    }
+    /// Suggests the fewest undirected edges that connect every [connected component](AdjacencyList::connected_components) into one,
+    /// which is one edge per component after the first.\
+    /// Each edge goes from the smallest vertex of the first component to the smallest vertex of another component,
+    /// so the suggestions are empty if the graph is already connected.
+    pub fn suggest_bridges_to_connect(&self) -> Vec<(V, V)> {
+        let representatives: Vec<V> = self
+            .connected_components()
+            .iter()
+            .filter_map(|component| component.first().copied())
+            .collect();
+        match representatives.split_first() {
+            Some((first, rest)) => rest.iter().map(|other| (*first, *other)).collect(),
+            None => vec![],
+        }
+    }
+    /// Gets the strongly connected components of the graph with Tarjan's algorithm, where every vertex in a component can reach
+    /// every other vertex of the component along the directed edges.\
+    /// The components are ordered by their smallest vertex, like in [`AdjacencyList::connected_components`].
+    pub fn strongly_connected_components(&self) -> Vec<BTreeSet<V>> {
+        // The order in which the DFS found each vertex, and the earliest vertex still on the stack that it can reach
+        let mut indices: BTreeMap<V, usize> = BTreeMap::new();
+        let mut low_links: BTreeMap<V, usize> = BTreeMap::new();
+        let mut stack = vec![];
+        let mut on_stack = BTreeSet::new();
+        let mut components = vec![];
+
+        for root in self.undirected_neighbors().keys() {
+            if indices.contains_key(root) {
+                continue;
+            }
+            indices.insert(*root, indices.len());
+            low_links.insert(*root, indices[root]);
+            stack.push(*root);
+            on_stack.insert(*root);
+            let mut path = vec![(*root, self.ordered_neighbors(root))];
+
+            while let Some((vertex, neighbors)) = path.last_mut() {
+                let vertex = *vertex;
+                if let Some((neighbor, _)) = neighbors.next() {
+                    if !indices.contains_key(neighbor) {
+                        indices.insert(*neighbor, indices.len());
+                        low_links.insert(*neighbor, indices[neighbor]);
+                        stack.push(*neighbor);
+                        on_stack.insert(*neighbor);
+                        path.push((*neighbor, self.ordered_neighbors(neighbor)));
+                    } else if on_stack.contains(neighbor) {
+                        let low_link = low_links[&vertex].min(indices[neighbor]);
+                        low_links.insert(vertex, low_link);
+                    }
+                } else {
+                    path.pop();
+                    if let Some((parent, _)) = path.last() {
+                        let low_link = low_links[parent].min(low_links[&vertex]);
+                        low_links.insert(*parent, low_link);
+                    }
+                    // Nothing on the stack above the vertex can reach further back, so they form a component
+                    if low_links[&vertex] == indices[&vertex] {
+                        let mut component = BTreeSet::new();
+                        while let Some(member) = stack.pop() {
+                            on_stack.remove(&member);
+                            component.insert(member);
+                            if member == vertex {
+                                break;
+                            }
+                        }
+                        components.push(component);
+                    }
+                }
+            }
+        }
+
+        components.sort();
+        components
+    }
+    /// Like [`AdjacencyList::strongly_connected_components`], but with Kosaraju's algorithm for comparison.\
+    /// The first DFS orders the vertices by when they finish, and the second DFS goes through the [transposed](AdjacencyList::transpose) graph
+    /// in the reverse of that order, so each search only reaches the vertices of one component.
+    pub fn strongly_connected_components_kosaraju(&self) -> Vec<BTreeSet<V>> {
+        let mut visited = BTreeSet::new();
+        let mut finished = vec![];
+
+        for root in self.undirected_neighbors().keys() {
+            if !visited.insert(*root) {
+                continue;
+            }
+            let mut path = vec![(*root, self.ordered_neighbors(root))];
+            while let Some((vertex, neighbors)) = path.last_mut() {
+                let vertex = *vertex;
+                if let Some((neighbor, _)) = neighbors.next() {
+                    if visited.insert(*neighbor) {
+                        path.push((*neighbor, self.ordered_neighbors(neighbor)));
+                    }
+                } else {
+                    finished.push(vertex);
+                    path.pop();
+                }
+            }
+        }
+
+        let transposed = self.transpose();
+        let mut assigned = BTreeSet::new();
+        let mut components = vec![];
+
+        for root in finished.into_iter().rev() {
+            if !assigned.insert(root) {
+                continue;
+            }
+            let mut component = BTreeSet::from([root]);
+            let mut stack = vec![root];
+            while let Some(vertex) = stack.pop() {
+                for (neighbor, _) in transposed.ordered_neighbors(&vertex) {
+                    if assigned.insert(*neighbor) {
+                        component.insert(*neighbor);
+                        stack.push(*neighbor);
+                    }
+                }
+            }
+            components.push(component);
+        }
+
+        components.sort();
+        components
+    }
+    /// Counts how many [connected components](AdjacencyList::connected_components) there are of each size.
+    pub fn component_size_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for component in self.connected_components() {
+            *histogram.entry(component.len()).or_default() += 1;
+        }
+        histogram
+    }
+    /// Ranks the vertices by importance with PageRank, e.g. for sizing them by how much the other vertices point to them.\
+    /// Every vertex starts with the same rank and then repeatedly passes it along its outgoing edges for `iterations` rounds.
+    /// With a chance of `1 - damping` the rank jumps to a random vertex instead, so `damping` is between 0 and 1 and usually 0.85.
+    ///
+    /// The rank of vertices without outgoing edges is spread evenly over all vertices, so the ranks always sum to 1.
+    /// The weights of the edges are ignored.
+    pub fn pagerank(&self, damping: f64, iterations: usize) -> BTreeMap<V, f64> {
+        let vertices: Vec<V> = self.undirected_neighbors().into_keys().collect();
+        let vertex_count = vertices.len() as f64;
+        let mut ranks: BTreeMap<V, f64> = vertices
+            .iter()
+            .map(|vertex| (*vertex, 1.0 / vertex_count))
+            .collect();
+
+        for _ in 0..iterations {
+            let dangling_rank: f64 = vertices
+                .iter()
+                .filter(|vertex| self.ordered_neighbors(vertex).next().is_none())
+                .map(|vertex| ranks[vertex])
+                .sum();
+            // What every vertex gets from random jumps and from the dangling vertices
+            let base_rank = ((1.0 - damping) + damping * dangling_rank) / vertex_count;
+            let mut next_ranks: BTreeMap<V, f64> =
+                vertices.iter().map(|vertex| (*vertex, base_rank)).collect();
+
+            for (vertex, edges) in self.hash_map.iter() {
+                let share = damping * ranks[vertex] / edges.len() as f64;
+                for neighbor in edges.keys() {
+                    *next_ranks.get_mut(neighbor).unwrap() += share;
+                }
+            }
+
+            ranks = next_ranks;
+        }
+
+        ranks
+    }
+    /// Measures how similar the outgoing edges of `a` and `b` are as the number of [common neighbors](AdjacencyList::common_neighbors)
+    /// divided by the number of vertices that either of them points to, e.g. for suggesting edges between similar vertices.\
+    /// The similarity is between 0 and 1, and 0 if neither vertex has any neighbors.
+    pub fn jaccard_similarity(&self, a: &V, b: &V) -> f64 {
+        let common = self.common_neighbors(a, b).len();
+        let union = self.ordered_neighbors(a).count() + self.ordered_neighbors(b).count() - common;
+        if union == 0 {
+            return 0.0;
+        }
+        common as f64 / union as f64
+    }
+}
+
+impl<V: Vertex, E: Edge> AdjacencyList<V, E> {
+    /// Gets the total weight of a minimum spanning tree, interpreting the graph as undirected.\
+    /// Returns `None` if the graph is disconnected, since it then only has a spanning forest. An empty graph has a total weight of 0.
+    pub fn mst_total_weight(&self) -> Option<E> {
+        let forest = self.kruskal_mst();
+
+        if forest.edges.len() + 1 < self.vertex_count() {
+            return None;
+        }
+
+        Some(forest.total_weight())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn v(i: isize) -> Coord {
+        Coord::new(i, 0)
+    }
+
+    #[test]
+    fn degree_sequence_of_known_graph() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b) in [(0, 1), (0, 2), (0, 3), (1, 2)] {
+            graph.add_edge_undirected(v(a), v(b), 1.0);
+        }
+        graph.add_edge_directed(v(4), v(4), 1.0);
+        graph.add_vertex(v(5));
+
+        assert_eq!(graph.degree_sequence(), vec![3, 2, 2, 2, 1, 0]);
+    }
+
+    #[test]
+    fn in_and_out_degree_sequences_of_directed_graph() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        // 0 points at 1, 2 and 3, and 3 points back at 1
+        for (a, b) in [(0, 1), (0, 2), (0, 3), (3, 1)] {
+            graph.add_edge_directed(v(a), v(b), 1.0);
+        }
+        graph.add_self_loop(v(4), 1.0);
+
+        // In-degrees: 0 -> 0, 1 -> 2, 2 -> 1, 3 -> 1, 4 -> 1
+        assert_eq!(graph.in_degree_sequence(), vec![2, 1, 1, 1, 0]);
+        // Out-degrees: 0 -> 3, 1 -> 0, 2 -> 0, 3 -> 1, 4 -> 1
+        assert_eq!(graph.out_degree_sequence(), vec![3, 1, 1, 0, 0]);
+
+        assert_eq!((graph.in_degree(&v(1)), graph.out_degree(&v(1))), (2, 0));
+        assert_eq!((graph.in_degree(&v(4)), graph.out_degree(&v(4))), (1, 1));
+        // 1 is connected to 0 and 3 in either direction, and the self-loop of 4 counts twice
+        assert_eq!(graph.degree(&v(1)), 2);
+        assert_eq!(graph.degree(&v(4)), 2);
+        assert_eq!(graph.degree(&v(5)), 0);
+    }
+
+    #[test]
+    fn mst_total_weight_matches_kruskal_edges() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b, weight) in [
+            (0, 1, 1.0),
+            (1, 2, 2.0),
+            (2, 3, 3.0),
+            (3, 0, 4.0),
+            (0, 2, 5.0),
+        ] {
+            graph.add_edge_undirected(v(a), v(b), weight);
+        }
+
+        let kruskal_sum: f32 = graph.kruskal_mst().edges.iter().map(|edge| edge.2).sum();
+        assert_eq!(kruskal_sum, 6.0);
+        assert_eq!(graph.mst_total_weight(), Some(kruskal_sum));
+
+        // Every spanning tree of a grid with unit weights connects its 9 cells with 8 edges
+        let grid = AdjacencyList::<Coord, f32>::grid_with_obstacles(3, 3, false, &BTreeSet::new());
+        assert_eq!(grid.mst_total_weight(), Some(8.0));
+
+        graph.add_vertex(v(9));
+        assert_eq!(graph.mst_total_weight(), None);
+        assert_eq!(
+            AdjacencyList::<Coord, f32>::default().mst_total_weight(),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn directed_cycles() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(v(0), v(1), 1.0);
+        graph.add_edge_directed(v(1), v(2), 1.0);
+        graph.add_edge_directed(v(0), v(2), 1.0);
+        assert!(!graph.has_cycle_directed());
+
+        graph.add_edge_directed(v(2), v(0), 1.0);
+        assert!(graph.has_cycle_directed());
+
+        assert_eq!(graph.find_cycle_directed(), Some(vec![v(0), v(1), v(2)]));
+
+        graph.remove_edge_directed(&v(2), &v(0));
+        graph.add_self_loop(v(3), 1.0);
+        assert!(graph.has_cycle_directed());
+        assert_eq!(graph.find_cycle_directed(), Some(vec![v(3)]));
+    }
+
+    #[test]
+    fn isomorphism_of_relabeled_and_different_graphs() {
+        // A directed triangle with a tail: 0 -> 1 -> 2 -> 0 and 2 -> 3
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3)] {
+            graph.add_edge_directed(v(a), v(b), 1.0);
+        }
+
+        // The same graph with the labels shuffled and different weights
+        let mut relabeled = AdjacencyList::<Coord, f32>::default();
+        for (a, b) in [(7, 5), (5, 9), (9, 7), (9, 4)] {
+            relabeled.add_edge_directed(v(a), v(b), 3.0);
+        }
+        assert_eq!(graph.is_isomorphic_to(&relabeled), Ok(true));
+        assert_eq!(relabeled.is_isomorphic_to(&graph), Ok(true));
+
+        // Also four vertices and four edges, but the tail points into the triangle
+        let mut different = AdjacencyList::<Coord, f32>::default();
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 2)] {
+            different.add_edge_directed(v(a), v(b), 1.0);
+        }
+        assert_eq!(graph.is_isomorphic_to(&different), Ok(false));
+
+        let large = AdjacencyList::<Coord, f32>::grid_with_obstacles(4, 4, false, &BTreeSet::new());
+        assert_eq!(
+            large.is_isomorphic_to(&large),
+            Err(GraphTooLargeError {
+                vertex_count: 16,
+                limit: ISOMORPHISM_VERTEX_LIMIT
+            })
+        );
+    }
+
+    #[test]
+    fn pagerank_favors_pointed_to_vertex() {
+        // Every other vertex points at 0, 0 points back at 1, and 4 has no outgoing edges
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b) in [(1, 0), (2, 0), (3, 0), (0, 1), (2, 3), (3, 4)] {
+            graph.add_edge_directed(v(a), v(b), 1.0);
+        }
+
+        let ranks = graph.pagerank(0.85, 50);
+        assert_eq!(ranks.len(), 5);
+        assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let highest = ranks.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+        assert_eq!(*highest.0, v(0));
+        // 1 gets everything that 0 has, while 2 only gets random jumps and a share of the dangling vertex 4
+        assert!(ranks[&v(1)] > ranks[&v(3)]);
+        assert!(ranks[&v(3)] > ranks[&v(2)]);
+        assert!(AdjacencyList::<Coord, f32>::default()
+            .pagerank(0.85, 10)
+            .is_empty());
+    }
+
+    #[test]
+    fn jaccard_similarity_of_overlapping_neighbors() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b) in [
+            (0, 2),
+            (0, 3),
+            (0, 4),
+            (1, 3),
+            (1, 4),
+            (1, 5),
+            (6, 2),
+            (7, 7),
+        ] {
+            graph.add_edge_directed(v(a), v(b), 1.0);
+        }
+
+        // {2, 3, 4} and {3, 4, 5} share 2 of 4 neighbors
+        assert_eq!(graph.jaccard_similarity(&v(0), &v(1)), 0.5);
+        assert_eq!(graph.jaccard_similarity(&v(1), &v(0)), 0.5);
+        assert_eq!(graph.jaccard_similarity(&v(0), &v(6)), 1.0 / 3.0);
+        assert_eq!(graph.jaccard_similarity(&v(0), &v(0)), 1.0);
+        assert_eq!(graph.jaccard_similarity(&v(1), &v(7)), 0.0);
+        // Neither has any neighbors
+        assert_eq!(graph.jaccard_similarity(&v(2), &v(9)), 0.0);
+    }
+
+    #[test]
+    fn suggested_bridges_connect_components() {
+        // {0, 1, 2}, {3, 4} and {5}
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(0), v(1), 1.0);
+        graph.add_edge_undirected(v(1), v(2), 1.0);
+        graph.add_edge_undirected(v(4), v(3), 1.0);
+        graph.add_vertex(v(5));
+
+        let bridges = graph.suggest_bridges_to_connect();
+        assert_eq!(bridges, vec![(v(0), v(3)), (v(0), v(5))]);
+
+        for (a, b) in bridges {
+            graph.add_edge_undirected(a, b, 1.0);
+        }
+        assert_eq!(graph.connected_components().len(), 1);
+        assert!(graph.suggest_bridges_to_connect().is_empty());
+        assert!(AdjacencyList::<Coord, f32>::default()
+            .suggest_bridges_to_connect()
+            .is_empty());
+    }
+
+    #[test]
+    fn strongly_connected_components_of_known_graph() {
+        // The cycles 0 -> 1 -> 2 -> 0 and 3 <-> 4, with 5 only reachable from them
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (4, 5)] {
+            graph.add_edge_directed(v(a), v(b), 1.0);
+        }
+
+        let expected = vec![
+            BTreeSet::from([v(0), v(1), v(2)]),
+            BTreeSet::from([v(3), v(4)]),
+            BTreeSet::from([v(5)]),
+        ];
+        assert_eq!(graph.strongly_connected_components(), expected);
+        assert_eq!(graph.strongly_connected_components_kosaraju(), expected);
+    }
+
+    #[test]
+    fn tarjan_and_kosaraju_agree_on_random_graphs() {
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut graph = AdjacencyList::<Coord, f32>::default();
+            for a in 0..15 {
+                graph.add_vertex(v(a));
+                for b in 0..15 {
+                    if rng.gen_bool(0.1) {
+                        graph.add_edge_directed(v(a), v(b), 1.0);
+                    }
+                }
+            }
+
+            let components = graph.strongly_connected_components();
+            assert_eq!(components, graph.strongly_connected_components_kosaraju());
+            // The components partition the vertices
+            assert_eq!(components.iter().map(BTreeSet::len).sum::<usize>(), 15);
+        }
+    }
+
+    #[test]
+    fn component_sizes() {
+        // {0}, {1, 2} and {3, 4}, where the direction of the edges doesn't matter
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_vertex(v(0));
+        graph.add_edge_directed(v(1), v(2), 1.0);
+        graph.add_edge_directed(v(4), v(3), 1.0);
+
+        assert_eq!(
+            graph.connected_components(),
+            vec![
+                BTreeSet::from([v(0)]),
+                BTreeSet::from([v(1), v(2)]),
+                BTreeSet::from([v(3), v(4)])
+            ]
+        );
+        assert_eq!(
+            graph.component_size_histogram(),
+            BTreeMap::from([(1, 1), (2, 2)])
+        );
+        assert!(AdjacencyList::<Coord, f32>::default()
+            .component_size_histogram()
+            .is_empty());
+    }
+
+    #[test]
+    fn topological_sort_respects_edges() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b) in [(3, 1), (1, 0), (3, 2), (2, 0), (4, 2)] {
+            graph.add_edge_directed(v(a), v(b), 1.0);
+        }
+
+        assert_eq!(
+            graph.topological_sort(),
+            Ok(vec![v(3), v(1), v(4), v(2), v(0)])
+        );
+
+        graph.add_edge_directed(v(0), v(4), 1.0);
+        // The cycle goes through the new edge
+        assert_eq!(
+            graph.topological_sort(),
+            Err(CycleError(vec![v(0), v(4), v(2)]))
+        );
+        assert_eq!(graph.find_cycle_directed(), Some(vec![v(0), v(4), v(2)]));
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use super::AdjacencyList;
+use crate::Vertex;
+
+/// A builder for constructing an [`AdjacencyList`] with chained method calls.
+///
+/// ## Example
+///
+/// ```rust
+/// use pathfinding::{graph::GraphBuilder, Coord};
+///
+/// let graph = GraphBuilder::new()
+///     .vertex(Coord::new(0, 0))
+///     .edge(Coord::new(0, 0), Coord::new(1, 0), 1.0)
+///     .undirected_edge(Coord::new(1, 0), Coord::new(2, 0), 2.0)
+///     .build();
+///
+/// assert_eq!(graph.hash_map.len(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GraphBuilder<V: Vertex, E: Clone> {
+    graph: AdjacencyList<V, E>,
+}
+impl<V: Vertex, E: Clone> GraphBuilder<V, E> {
+    pub fn new() -> Self {
+        Self {
+            graph: AdjacencyList::new(BTreeMap::new()),
+        }
+    }
+    /// Adds a vertex without any edges. Existing edges of the vertex are kept.
+    pub fn vertex(mut self, vertex: V) -> Self {
+        self.graph.hash_map.entry(vertex).or_default();
+        self
+    }
+    /// Adds a directed edge from `a` to `b`, creating missing vertices like [`AdjacencyList::add_edge_directed`].
+    pub fn edge(mut self, a: V, b: V, weight: E) -> Self {
+        self.graph.add_edge_directed(a, b, weight);
+        self
+    }
+    /// Adds an undirected edge between `a` and `b`, creating missing vertices like [`AdjacencyList::add_edge_undirected`].
+    pub fn undirected_edge(mut self, a: V, b: V, weight: E) -> Self {
+        self.graph.add_edge_undirected(a, b, weight);
+        self
+    }
+    pub fn build(self) -> AdjacencyList<V, E> {
+        self.graph
+    }
+}
+impl<V: Vertex, E: Clone> Default for GraphBuilder<V, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    #[test]
+    fn builder_matches_imperatively_built_graph() {
+        let (a, b, c, d) = (
+            Coord::new(0, 0),
+            Coord::new(1, 0),
+            Coord::new(1, 1),
+            Coord::new(5, 5),
+        );
+        let built = GraphBuilder::new()
+            .vertex(d)
+            .edge(a, b, 1.0)
+            .undirected_edge(b, c, 2.5)
+            .edge(c, a, 4.0)
+            // Adding a vertex again keeps its edges
+            .vertex(a)
+            .build();
+
+        let mut graph = AdjacencyList::new(BTreeMap::new());
+        graph.add_vertex(d);
+        graph.add_edge_directed(a, b, 1.0);
+        graph.add_edge_undirected(b, c, 2.5);
+        graph.add_edge_directed(c, a, 4.0);
+
+        assert_eq!(built, graph);
+        assert_eq!(built.hash_map[&a].get(&b), Some(&1.0));
+        assert_eq!(built.hash_map[&c].get(&b), Some(&2.5));
+        assert!(built
+            .get_neighbors(&d)
+            .is_some_and(|edges| edges.is_empty()));
+    }
+}
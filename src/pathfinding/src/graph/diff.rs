@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use super::AdjacencyList;
+use crate::Vertex;
+
+/// The differences between two states of an [`AdjacencyList`], found with [`AdjacencyList::diff`].\
+/// Everything is listed in ascending order of the vertices, and the edges of added or removed vertices are listed as added or removed edges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphDiff<V: Vertex, E: Clone> {
+    pub added_vertices: Vec<V>,
+    pub removed_vertices: Vec<V>,
+    /// Edges as `(from, to, weight)`.
+    pub added_edges: Vec<(V, V, E)>,
+    /// Edges as `(from, to, weight)` with the weight they had before they were removed.
+    pub removed_edges: Vec<(V, V, E)>,
+    /// Edges as `(from, to, old_weight, new_weight)`.
+    pub changed_edges: Vec<(V, V, E, E)>,
+}
+impl<V: Vertex, E: Clone> GraphDiff<V, E> {
+    /// Returns `true` if the two graphs were equal.
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+impl<V: Vertex, E: Clone + PartialEq> AdjacencyList<V, E> {
+    /// Finds what has to change to turn this graph into `other`. Can be used for animating the transition between the two.
+    pub fn diff(&self, other: &AdjacencyList<V, E>) -> GraphDiff<V, E> {
+        let mut diff = GraphDiff {
+            added_vertices: vec![],
+            removed_vertices: vec![],
+            added_edges: vec![],
+            removed_edges: vec![],
+            changed_edges: vec![],
+        };
+        let no_edges = BTreeMap::new();
+
+        for (vertex, edges) in self.hash_map.iter() {
+            let other_edges = other.hash_map.get(vertex).unwrap_or_else(|| {
+                diff.removed_vertices.push(*vertex);
+                &no_edges
+            });
+            for (neighbor, weight) in edges.iter() {
+                match other_edges.get(neighbor) {
+                    Some(other_weight) if other_weight != weight => diff.changed_edges.push((
+                        *vertex,
+                        *neighbor,
+                        weight.clone(),
+                        other_weight.clone(),
+                    )),
+                    Some(_) => (),
+                    None => diff
+                        .removed_edges
+                        .push((*vertex, *neighbor, weight.clone())),
+                }
+            }
+        }
+
+        for (vertex, other_edges) in other.hash_map.iter() {
+            let edges = self.hash_map.get(vertex).unwrap_or_else(|| {
+                diff.added_vertices.push(*vertex);
+                &no_edges
+            });
+            for (neighbor, weight) in other_edges.iter() {
+                if !edges.contains_key(neighbor) {
+                    diff.added_edges.push((*vertex, *neighbor, weight.clone()));
+                }
+            }
+        }
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    #[test]
+    fn diff_finds_added_removed_and_changed() {
+        let (a, b, c, d) = (
+            Coord::new(0, 0),
+            Coord::new(1, 0),
+            Coord::new(2, 0),
+            Coord::new(3, 0),
+        );
+        let mut before = AdjacencyList::<Coord, f32>::default();
+        before.add_edge_directed(a, b, 1.0);
+        before.add_edge_directed(b, c, 2.0);
+        before.add_edge_directed(c, a, 3.0);
+
+        let mut after = before.clone();
+        after.add_vertex(d);
+        after.remove_edge_directed(&b, &c);
+        after.add_edge_directed(c, a, 5.0);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added_vertices, vec![d]);
+        assert!(diff.removed_vertices.is_empty());
+        assert!(diff.added_edges.is_empty());
+        assert_eq!(diff.removed_edges, vec![(b, c, 2.0)]);
+        assert_eq!(diff.changed_edges, vec![(c, a, 3.0, 5.0)]);
+
+        let reverse = after.diff(&before);
+        assert_eq!(reverse.removed_vertices, vec![d]);
+        assert_eq!(reverse.added_edges, vec![(b, c, 2.0)]);
+        assert!(before.diff(&before).is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::AdjacencyList;
+use crate::Vertex;
+
+/// A single mutation of an [`AdjacencyList`] that can be recorded by an [`EditHistory`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum GraphEdit<V: Vertex, E: Clone> {
+    AddVertex(V),
+    RemoveVertex(V),
+    AddEdgeDirected(V, V, E),
+    AddEdgeUndirected(V, V, E),
+    RemoveEdgeDirected(V, V),
+    RemoveEdgeUndirected(V, V),
+    /// Replaces the whole graph, like generating a new one or importing one.
+    Replace(AdjacencyList<V, E>),
+}
+impl<V: Vertex, E: Clone> GraphEdit<V, E> {
+    /// Runs the edit on the given graph.
+    pub fn apply(&self, graph: &mut AdjacencyList<V, E>) {
+        match self {
+            GraphEdit::AddVertex(vertex) => graph.add_vertex(*vertex),
+            GraphEdit::RemoveVertex(vertex) => graph.remove_vertex(vertex),
+            GraphEdit::AddEdgeDirected(a, b, weight) => {
+                graph.add_edge_directed(*a, *b, weight.clone())
+            }
+            GraphEdit::AddEdgeUndirected(a, b, weight) => {
+                graph.add_edge_undirected(*a, *b, weight.clone())
+            }
+            GraphEdit::RemoveEdgeDirected(a, b) => {
+                graph.remove_edge_directed(a, b);
+            }
+            GraphEdit::RemoveEdgeUndirected(a, b) => {
+                graph.remove_edge_undirected(a, b);
+            }
+            GraphEdit::Replace(new_graph) => *graph = new_graph.clone(),
+        }
+    }
+    /// Gets the vertices whose neighbor maps can be changed by the edit.
+    fn affected_vertices(&self, graph: &AdjacencyList<V, E>) -> Vec<V> {
+        match self {
+            GraphEdit::AddVertex(vertex) => vec![*vertex],
+            // Removing a vertex also removes the edges that point to it from its neighbors
+            GraphEdit::RemoveVertex(vertex) => {
+                let mut vertices = vec![*vertex];
+                vertices.extend(
+                    graph
+                        .hash_map
+                        .iter()
+                        .filter(|(_, edges)| edges.contains_key(vertex))
+                        .map(|(neighbor, _)| *neighbor),
+                );
+                vertices
+            }
+            GraphEdit::AddEdgeDirected(a, b, _)
+            | GraphEdit::AddEdgeUndirected(a, b, _)
+            | GraphEdit::RemoveEdgeDirected(a, b)
+            | GraphEdit::RemoveEdgeUndirected(a, b) => vec![*a, *b],
+            GraphEdit::Replace(new_graph) => graph
+                .hash_map
+                .keys()
+                .chain(new_graph.hash_map.keys())
+                .copied()
+                .collect::<BTreeSet<V>>()
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// An edit that has been applied, along with the state of the affected vertices before it was applied.
+#[derive(Clone, Debug, PartialEq)]
+struct HistoryEntry<V: Vertex, E: Clone> {
+    edit: GraphEdit<V, E>,
+    /// The neighbor maps of the affected vertices before the edit. `None` means that the vertex didn't exist.
+    previous: BTreeMap<V, Option<BTreeMap<V, E>>>,
+}
+
+/// Records [`GraphEdit`]s applied to an [`AdjacencyList`] so that they can be undone and redone.
+///
+/// Undoing restores the affected vertices exactly as they were, so undoing a vertex removal also restores all of the vertex's former edges and their weights.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditHistory<V: Vertex, E: Clone> {
+    undo_stack: Vec<HistoryEntry<V, E>>,
+    redo_stack: Vec<HistoryEntry<V, E>>,
+    /// The most edits that can be undone. The oldest edits are forgotten once there are more.
+    max_depth: Option<usize>,
+}
+impl<V: Vertex, E: Clone> Default for EditHistory<V, E> {
+    fn default() -> Self {
+        Self {
+            undo_stack: vec![],
+            redo_stack: vec![],
+            max_depth: None,
+        }
+    }
+}
+impl<V: Vertex, E: Clone> EditHistory<V, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Creates a history that only keeps the latest `max_depth` edits.
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            max_depth: Some(max_depth),
+            ..Self::default()
+        }
+    }
+    /// Applies an edit to the graph and records it. Any previously undone edits can no longer be redone.
+    pub fn apply(&mut self, graph: &mut AdjacencyList<V, E>, edit: GraphEdit<V, E>) {
+        let entry = Self::record(graph, edit);
+        entry.edit.apply(graph);
+        self.undo_stack.push(entry);
+        self.redo_stack.clear();
+        if let Some(max_depth) = self.max_depth {
+            let forgotten = self.undo_stack.len().saturating_sub(max_depth);
+            self.undo_stack.drain(..forgotten);
+        }
+    }
+    /// Reverts the latest applied edit and returns it, or `None` if there is nothing to undo.
+    pub fn undo(&mut self, graph: &mut AdjacencyList<V, E>) -> Option<GraphEdit<V, E>> {
+        let entry = self.undo_stack.pop()?;
+        for (vertex, edges) in entry.previous.iter() {
+            match edges {
+                Some(edges) => {
+                    graph.hash_map.insert(*vertex, edges.clone());
+                }
+                None => {
+                    graph.hash_map.remove(vertex);
+                }
+            }
+        }
+        let edit = entry.edit.clone();
+        self.redo_stack.push(entry);
+        Some(edit)
+    }
+    /// Applies the latest undone edit again and returns it, or `None` if there is nothing to redo.
+    pub fn redo(&mut self, graph: &mut AdjacencyList<V, E>) -> Option<GraphEdit<V, E>> {
+        let entry = self.redo_stack.pop()?;
+        // The graph may have been mutated outside of the history, so the previous state is recorded again.
+        let entry = Self::record(graph, entry.edit);
+        let edit = entry.edit.clone();
+        edit.apply(graph);
+        self.undo_stack.push(entry);
+        Some(edit)
+    }
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+    /// The number of edits that can be undone.
+    pub fn undo_len(&self) -> usize {
+        self.undo_stack.len()
+    }
+    /// Removes all recorded edits.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+    fn record(graph: &AdjacencyList<V, E>, edit: GraphEdit<V, E>) -> HistoryEntry<V, E> {
+        let previous = edit
+            .affected_vertices(graph)
+            .into_iter()
+            .map(|vertex| (vertex, graph.get_neighbors(&vertex).cloned()))
+            .collect();
+        HistoryEntry { edit, previous }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    fn triangle() -> AdjacencyList<Coord, f32> {
+        let mut graph = AdjacencyList::default();
+        graph.add_edge_undirected(Coord::new(0, 0), Coord::new(1, 0), 1.0);
+        graph.add_edge_undirected(Coord::new(1, 0), Coord::new(1, 1), 2.0);
+        graph.add_edge_directed(Coord::new(1, 1), Coord::new(0, 0), 3.0);
+        graph
+    }
+
+    #[test]
+    fn undoing_vertex_removal_restores_edges_and_weights() {
+        let original = triangle();
+        let mut graph = original.clone();
+        let mut history = EditHistory::new();
+
+        history.apply(&mut graph, GraphEdit::RemoveVertex(Coord::new(1, 0)));
+        assert!(!graph.hash_map.contains_key(&Coord::new(1, 0)));
+        assert!(!graph.hash_map[&Coord::new(0, 0)].contains_key(&Coord::new(1, 0)));
+
+        history.undo(&mut graph);
+        assert_eq!(graph, original);
+        assert_eq!(graph.hash_map[&Coord::new(1, 1)][&Coord::new(1, 0)], 2.0);
+        assert!(!history.can_undo());
+        assert!(history.can_redo());
+    }
+
+    #[test]
+    fn redo_after_outside_mutation() {
+        let mut graph = triangle();
+        let mut history = EditHistory::new();
+
+        history.apply(
+            &mut graph,
+            GraphEdit::AddEdgeUndirected(Coord::new(0, 0), Coord::new(1, 1), 5.0),
+        );
+        history.undo(&mut graph);
+
+        // Changed without going through the history
+        graph.add_edge_directed(Coord::new(0, 0), Coord::new(2, 2), 4.0);
+        let mutated = graph.clone();
+
+        history.redo(&mut graph);
+        assert_eq!(graph.hash_map[&Coord::new(0, 0)][&Coord::new(1, 1)], 5.0);
+        assert_eq!(graph.hash_map[&Coord::new(0, 0)][&Coord::new(2, 2)], 4.0);
+
+        // Undoing the redone edit goes back to the mutated graph, not the one before the outside change
+        history.undo(&mut graph);
+        assert_eq!(graph, mutated);
+    }
+
+    #[test]
+    fn replacing_and_capped_depth() {
+        let original = triangle();
+        let mut graph = original.clone();
+        let mut history = EditHistory::with_max_depth(2);
+
+        let mut replacement = AdjacencyList::default();
+        replacement.add_edge_undirected(Coord::new(5, 5), Coord::new(6, 5), 1.0);
+        history.apply(&mut graph, GraphEdit::Replace(replacement.clone()));
+        assert_eq!(graph, replacement);
+        history.undo(&mut graph);
+        assert_eq!(graph, original);
+        history.redo(&mut graph);
+        assert_eq!(graph, replacement);
+
+        history.apply(&mut graph, GraphEdit::AddVertex(Coord::new(7, 7)));
+        history.apply(&mut graph, GraphEdit::AddVertex(Coord::new(8, 8)));
+        assert_eq!(history.undo_len(), 2);
+
+        // The replacement was forgotten, so it can't be undone anymore
+        while history.undo(&mut graph).is_some() {}
+        assert_eq!(graph, replacement);
+    }
+}
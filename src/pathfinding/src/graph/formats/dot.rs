@@ -0,0 +1,345 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use num_traits::One;
+
+use super::{line_at, ParseGraphError};
+use crate::{graph::AdjacencyList, Vertex};
+
+impl<V: Vertex, E: Clone + Display> AdjacencyList<V, E> {
+    /// Writes the graph in the DOT language of Graphviz as a `digraph`.\
+    /// Every vertex is listed before the edges so that vertices without edges are kept.
+    /// The weight of an edge is written as both its `weight` and its `label`, so Graphviz shows the weights.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+
+        for vertex in self.ordered_vertices() {
+            dot += format!("    {};\n", quote(vertex)).as_str();
+        }
+        for (from, to, weight) in self.edges_iter() {
+            dot += format!(
+                "    {} -> {} [weight={}, label={}];\n",
+                quote(from),
+                quote(to),
+                quote(weight),
+                quote(weight)
+            )
+            .as_str();
+        }
+
+        dot + "}\n"
+    }
+}
+
+impl<V: Vertex + FromStr, E: Clone + FromStr + One> AdjacencyList<V, E> {
+    /// Reads a graph written in the DOT language of Graphviz.\
+    /// The weight of an edge is read from its `weight` attribute, or from its `label` if that is a number.
+    /// Edges of a `graph` are undirected and edges of a `digraph` are directed.
+    /// Attributes of the graph and default attributes for nodes and edges are skipped, and subgraphs aren't supported.
+    pub fn from_dot(text: &str) -> Result<Self, ParseGraphError> {
+        let mut parser = Parser {
+            text,
+            tokens: tokenize(text)?,
+            position: 0,
+        };
+        let mut graph = Self::new(BTreeMap::new());
+
+        if parser.peek_keyword("strict") {
+            parser.position += 1;
+        }
+        let directed = if parser.peek_keyword("digraph") {
+            true
+        } else if parser.peek_keyword("graph") {
+            false
+        } else {
+            return Err(parser.error("expected \"graph\" or \"digraph\""));
+        };
+        parser.position += 1;
+        if let Some(Token::Id { .. }) = parser.peek() {
+            parser.position += 1;
+        }
+        parser.expect(Token::Punct('{'))?;
+
+        while parser.peek() != Some(&Token::Punct('}')) {
+            parser.statement(&mut graph, directed)?;
+        }
+        parser.position += 1;
+        if parser.peek().is_some() {
+            return Err(parser.error("expected the end of the graph"));
+        }
+
+        Ok(graph)
+    }
+}
+
+/// Quotes an id for DOT, escaping the characters that would end the quotes.
+fn quote(id: &impl Display) -> String {
+    format!(
+        "\"{}\"",
+        id.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// An identifier, a number or a string, which keywords are only if they aren't `quoted`.
+    Id { text: String, quoted: bool },
+    /// `->` if `directed`, otherwise `--`.
+    EdgeOp { directed: bool },
+    /// One of `{}[];,=:`.
+    Punct(char),
+}
+
+/// Splits DOT text into tokens along with the byte offsets where they start, skipping whitespace and comments.
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, ParseGraphError> {
+    let mut tokens = vec![];
+    let mut chars = text.char_indices().peekable();
+    let syntax_error = |offset: usize, message: &str| ParseGraphError::Syntax {
+        line: line_at(text, offset),
+        message: message.to_string(),
+    };
+
+    while let Some((offset, c)) = chars.next() {
+        let next = chars.peek().map(|(_, next)| *next);
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '#' => {
+                chars.find(|(_, c)| *c == '\n');
+                continue;
+            }
+            '/' if next == Some('/') => {
+                chars.find(|(_, c)| *c == '\n');
+                continue;
+            }
+            '/' if next == Some('*') => {
+                chars.next();
+                let mut previous = ' ';
+                if !chars.any(|(_, c)| std::mem::replace(&mut previous, c) == '*' && c == '/') {
+                    return Err(syntax_error(offset, "unclosed comment"));
+                }
+                continue;
+            }
+            '-' if next == Some('>') || next == Some('-') => {
+                chars.next();
+                Token::EdgeOp {
+                    directed: next == Some('>'),
+                }
+            }
+            '{' | '}' | '[' | ']' | ';' | ',' | '=' | ':' => Token::Punct(c),
+            '"' => {
+                let mut id = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c @ ('"' | '\\'))) => id.push(c),
+                            // A backslash before a line break continues the line
+                            Some((_, '\n')) => {}
+                            Some((_, c)) => {
+                                id.push('\\');
+                                id.push(c);
+                            }
+                            None => return Err(syntax_error(offset, "unclosed string")),
+                        },
+                        Some((_, c)) => id.push(c),
+                        None => return Err(syntax_error(offset, "unclosed string")),
+                    }
+                }
+                Token::Id {
+                    text: id,
+                    quoted: true,
+                }
+            }
+            _ if is_id_char(c) => {
+                let mut id = c.to_string();
+                while let Some((_, c)) = chars.next_if(|(_, c)| is_id_char(*c)) {
+                    id.push(c);
+                }
+                Token::Id {
+                    text: id,
+                    quoted: false,
+                }
+            }
+            _ => return Err(syntax_error(offset, &format!("unexpected \"{}\"", c))),
+        };
+        tokens.push((token, offset));
+    }
+
+    Ok(tokens)
+}
+
+/// Checks if `c` can be a part of an unquoted id or number.
+fn is_id_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '-'
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+    /// Checks if the next token is the unquoted `keyword`, which is case-insensitive like in Graphviz.
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Id { text, quoted: false }) if text.eq_ignore_ascii_case(keyword))
+    }
+    fn error(&self, message: &str) -> ParseGraphError {
+        let offset = self
+            .tokens
+            .get(self.position)
+            .map_or(self.text.len(), |(_, offset)| *offset);
+        ParseGraphError::Syntax {
+            line: line_at(self.text, offset),
+            message: message.to_string(),
+        }
+    }
+    fn expect(&mut self, token: Token) -> Result<(), ParseGraphError> {
+        if self.peek() == Some(&token) {
+            self.position += 1;
+            Ok(())
+        } else {
+            let expected = match token {
+                Token::Punct(c) => format!("expected \"{}\"", c),
+                _ => "expected an id".to_string(),
+            };
+            Err(self.error(&expected))
+        }
+    }
+    fn id(&mut self) -> Result<String, ParseGraphError> {
+        match self.peek() {
+            Some(Token::Id { text, .. }) => {
+                let text = text.clone();
+                self.position += 1;
+                Ok(text)
+            }
+            _ => Err(self.error("expected an id")),
+        }
+    }
+    /// Parses a node id, skipping its port if it has one.
+    fn node_id(&mut self) -> Result<String, ParseGraphError> {
+        let id = self.id()?;
+        while self.peek() == Some(&Token::Punct(':')) {
+            self.position += 1;
+            self.id()?;
+        }
+        Ok(id)
+    }
+    /// Parses any number of attribute lists like `[weight=2, label="a"]` into `(name, value)` pairs.
+    fn attributes(&mut self) -> Result<Vec<(String, String)>, ParseGraphError> {
+        let mut attributes = vec![];
+        while self.peek() == Some(&Token::Punct('[')) {
+            self.position += 1;
+            while self.peek() != Some(&Token::Punct(']')) {
+                let name = self.id()?;
+                self.expect(Token::Punct('='))?;
+                attributes.push((name, self.id()?));
+                if let Some(Token::Punct(';' | ',')) = self.peek() {
+                    self.position += 1;
+                }
+            }
+            self.position += 1;
+        }
+        Ok(attributes)
+    }
+    /// Parses one statement and adds the vertices and edges of it to the `graph`.
+    fn statement<V: Vertex + FromStr, E: Clone + FromStr + One>(
+        &mut self,
+        graph: &mut AdjacencyList<V, E>,
+        directed: bool,
+    ) -> Result<(), ParseGraphError> {
+        if self.peek_keyword("subgraph") || self.peek() == Some(&Token::Punct('{')) {
+            return Err(self.error("subgraphs aren't supported"));
+        }
+        if self.peek_keyword("graph") || self.peek_keyword("node") || self.peek_keyword("edge") {
+            // Default attributes don't change the vertices or the edges
+            self.position += 1;
+            self.attributes()?;
+        } else {
+            let mut ids = vec![self.node_id()?];
+            if self.peek() == Some(&Token::Punct('=')) {
+                // An attribute of the whole graph
+                self.position += 1;
+                self.id()?;
+            } else {
+                while let Some(Token::EdgeOp {
+                    directed: directed_edge,
+                }) = self.peek()
+                {
+                    if *directed_edge != directed {
+                        return Err(self.error(if directed {
+                            "a digraph can only have \"->\" edges"
+                        } else {
+                            "a graph can only have \"--\" edges"
+                        }));
+                    }
+                    self.position += 1;
+                    ids.push(self.node_id()?);
+                }
+
+                let attributes = self.attributes()?;
+                let attribute = |name: &str| {
+                    attributes
+                        .iter()
+                        .find(|(attribute, _)| attribute == name)
+                        .map(|(_, value)| value.as_str())
+                };
+                let weight = attribute("weight").or_else(|| {
+                    attribute("label").filter(|label| label.trim().parse::<E>().is_ok())
+                });
+
+                graph.add_parsed_vertex(&ids[0])?;
+                for pair in ids.windows(2) {
+                    graph.add_parsed_edge(&pair[0], &pair[1], weight, directed)?;
+                }
+            }
+        }
+
+        if let Some(Token::Punct(';' | ',')) = self.peek() {
+            self.position += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    #[test]
+    fn reads_graphviz_syntax() {
+        let dot = r#"
+            /* Written by hand */
+            strict graph roads {
+                rankdir = LR; node [shape=circle]
+                "0,0" -- "1,0" -- "2,0" [label="3"] // a chain of two edges
+                "2,0" -- "0,0" [weight=5 label="ignored"];
+                # a vertex without edges
+                "4,4":n
+            }
+        "#;
+        let graph = AdjacencyList::<Coord, f32>::from_dot(dot).unwrap();
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count_undirected(), 3);
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(1, 0), &Coord::new(0, 0)),
+            Some(&3.0)
+        );
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(0, 0), &Coord::new(2, 0)),
+            Some(&5.0)
+        );
+
+        assert_eq!(
+            AdjacencyList::<Coord, f32>::from_dot("digraph {\n \"0,0\" -- \"1,0\"\n}"),
+            Err(ParseGraphError::Syntax {
+                line: 2,
+                message: "a digraph can only have \"->\" edges".to_string()
+            })
+        );
+        assert!(AdjacencyList::<Coord, f32>::from_dot("digraph { subgraph { \"0,0\" } }").is_err());
+        assert!(AdjacencyList::<Coord, f32>::from_dot("digraph { \"0,0\" ").is_err());
+    }
+}
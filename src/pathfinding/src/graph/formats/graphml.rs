@@ -0,0 +1,271 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use num_traits::One;
+
+use super::{line_at, ParseGraphError};
+use crate::{graph::AdjacencyList, Vertex};
+
+impl<V: Vertex, E: Clone + Display> AdjacencyList<V, E> {
+    /// Writes the graph as GraphML with directed edges. The weights are stored in a `weight` attribute of the edges.
+    pub fn to_graphml(&self) -> String {
+        let mut graphml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n  \
+             <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n  \
+             <graph id=\"G\" edgedefault=\"directed\">\n",
+        );
+
+        for vertex in self.ordered_vertices() {
+            graphml += format!("    <node id=\"{}\"/>\n", escape(vertex)).as_str();
+        }
+        for (from, to, weight) in self.edges_iter() {
+            graphml += format!(
+                "    <edge source=\"{}\" target=\"{}\">\n      <data key=\"weight\">{}</data>\n    </edge>\n",
+                escape(from),
+                escape(to),
+                escape(weight)
+            )
+            .as_str();
+        }
+
+        graphml + "  </graph>\n</graphml>\n"
+    }
+}
+
+impl<V: Vertex + FromStr, E: Clone + FromStr + One> AdjacencyList<V, E> {
+    /// Reads a graph from GraphML.\
+    /// The weights are read from the edge attribute named `weight`. The edges are directed unless the graph has
+    /// `edgedefault="undirected"`, which an edge can override with its own `directed` attribute.
+    /// Other attributes and nested graphs are skipped.
+    pub fn from_graphml(text: &str) -> Result<Self, ParseGraphError> {
+        let mut graph = Self::new(BTreeMap::new());
+        let mut directed_by_default = true;
+        let mut weight_key = "weight".to_string();
+        // The source, target, direction and weight of the edge whose data is being read
+        let mut edge: Option<(String, String, bool, Option<String>)> = None;
+        // The key and the text so far of the data element being read
+        let mut data: Option<(String, String)> = None;
+        let mut rest = text;
+
+        while let Some(start) = rest.find('<') {
+            let offset = text.len() - rest.len() + start;
+            let syntax_error = |message: &str| ParseGraphError::Syntax {
+                line: line_at(text, offset),
+                message: message.to_string(),
+            };
+            if let Some((_, content)) = data.as_mut() {
+                content.push_str(&unescape(&rest[..start]));
+            }
+            rest = &rest[start..];
+
+            for (opening, closing) in [
+                ("<?", "?>"),
+                ("<!--", "-->"),
+                ("<![CDATA[", "]]>"),
+                ("<!", ">"),
+            ] {
+                if let Some(after) = rest.strip_prefix(opening) {
+                    let end = after
+                        .find(closing)
+                        .ok_or_else(|| syntax_error(&format!("expected \"{}\"", closing)))?;
+                    if let (Some((_, content)), "<![CDATA[") = (data.as_mut(), opening) {
+                        content.push_str(&after[..end]);
+                    }
+                    rest = &after[end + closing.len()..];
+                    break;
+                }
+            }
+            if !rest.starts_with('<') || rest.starts_with("<!") || rest.starts_with("<?") {
+                continue;
+            }
+
+            let end = rest
+                .find('>')
+                .ok_or_else(|| syntax_error("expected \">\""))?;
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                match name.trim() {
+                    "data" => {
+                        if let (Some((key, content)), Some((_, _, _, weight))) =
+                            (data.take(), edge.as_mut())
+                        {
+                            if key == weight_key {
+                                *weight = Some(content);
+                            }
+                        }
+                    }
+                    "edge" => {
+                        if let Some((source, target, directed, weight)) = edge.take() {
+                            graph.add_parsed_edge(&source, &target, weight.as_deref(), directed)?;
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let name = tag.split_whitespace().next().unwrap_or_default();
+            let attributes = parse_attributes(&tag[name.len()..])
+                .ok_or_else(|| syntax_error(&format!("invalid attributes in <{}>", name)))?;
+            let attribute = |name: &str| {
+                attributes
+                    .iter()
+                    .find(|(attribute, _)| attribute == name)
+                    .map(|(_, value)| value.clone())
+            };
+
+            match name {
+                "key" => {
+                    let for_edges = matches!(attribute("for").as_deref(), Some("edge" | "all"));
+                    if let (true, Some("weight"), Some(id)) = (
+                        for_edges,
+                        attribute("attr.name").as_deref(),
+                        attribute("id"),
+                    ) {
+                        weight_key = id;
+                    }
+                }
+                "graph" => {
+                    directed_by_default = attribute("edgedefault").as_deref() != Some("undirected");
+                }
+                "node" => {
+                    let id = attribute("id").ok_or_else(|| syntax_error("a node needs an id"))?;
+                    graph.add_parsed_vertex(&id)?;
+                }
+                "edge" => {
+                    let (source, target) = attribute("source")
+                        .zip(attribute("target"))
+                        .ok_or_else(|| syntax_error("an edge needs a source and a target"))?;
+                    let directed = attribute("directed")
+                        .map_or(directed_by_default, |directed| directed == "true");
+                    if self_closing {
+                        graph.add_parsed_edge(&source, &target, None, directed)?;
+                    } else {
+                        edge = Some((source, target, directed, None));
+                    }
+                }
+                "data" if edge.is_some() && !self_closing => {
+                    data = Some((attribute("key").unwrap_or_default(), String::new()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+/// Parses attributes like `id="a" source='b'` into `(name, value)` pairs, or `None` if they aren't valid.
+fn parse_attributes(mut text: &str) -> Option<Vec<(String, String)>> {
+    let mut attributes = vec![];
+
+    loop {
+        text = text.trim_start();
+        if text.is_empty() {
+            return Some(attributes);
+        }
+        let (name, rest) = text.split_once('=')?;
+        let rest = rest.trim_start();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let (value, rest) = rest[1..].split_once(quote)?;
+        attributes.push((name.trim().to_string(), unescape(value)));
+        text = rest;
+    }
+}
+
+/// Escapes the characters that XML gives a meaning to.
+fn escape(text: &impl Display) -> String {
+    text.to_string()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Replaces the XML entities and character references in `text` with the characters they stand for.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => break,
+        };
+        let character = match &rest[1..end] {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            reference => reference
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| reference.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match character {
+            Some(character) => {
+                unescaped.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    unescaped + rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    #[test]
+    fn reads_keys_and_edge_directions() {
+        let graphml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!-- Exported from another editor -->
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="node" attr.name="weight" attr.type="double"/>
+              <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+              <graph id="G" edgedefault="undirected">
+                <node id="0,0"><data key="d0">7</data></node>
+                <node id="1,0"/>
+                <edge source="0,0" target="1,0"><data key="d1"><![CDATA[2.5]]></data></edge>
+                <edge source="1,0" target="5,&#53;" directed="true"/>
+              </graph>
+            </graphml>"#;
+        let graph = AdjacencyList::<Coord, f32>::from_graphml(graphml).unwrap();
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(1, 0), &Coord::new(0, 0)),
+            Some(&2.5)
+        );
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(1, 0), &Coord::new(5, 5)),
+            Some(&1.0)
+        );
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(5, 5), &Coord::new(1, 0)),
+            None
+        );
+
+        assert_eq!(
+            AdjacencyList::<Coord, f32>::from_graphml("<graphml>\n<edge source=\"0,0\"/>"),
+            Err(ParseGraphError::Syntax {
+                line: 2,
+                message: "an edge needs a source and a target".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,372 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use num_traits::One;
+
+use super::{line_at, ParseGraphError};
+use crate::{graph::AdjacencyList, Vertex};
+
+impl<V: Vertex, E: Clone + Display> AdjacencyList<V, E> {
+    /// Writes the graph as JSON like `{ "directed": true, "vertices": ["0,0"], "edges": [{ "from": "0,0", "to": "0,1", "weight": 1 }] }`.\
+    /// The vertices are written as strings and the weights as numbers.
+    pub fn to_json(&self) -> String {
+        let vertices = self
+            .ordered_vertices()
+            .map(|vertex| format!("\n    {}", quote(vertex)))
+            .collect::<Vec<String>>();
+        let edges = self
+            .edges_iter()
+            .map(|(from, to, weight)| {
+                format!(
+                    "\n    {{ \"from\": {}, \"to\": {}, \"weight\": {} }}",
+                    quote(from),
+                    quote(to),
+                    weight
+                )
+            })
+            .collect::<Vec<String>>();
+        let list = |items: Vec<String>| {
+            if items.is_empty() {
+                "[]".to_string()
+            } else {
+                format!("[{}\n  ]", items.join(","))
+            }
+        };
+
+        format!(
+            "{{\n  \"directed\": true,\n  \"vertices\": {},\n  \"edges\": {}\n}}\n",
+            list(vertices),
+            list(edges)
+        )
+    }
+}
+
+impl<V: Vertex + FromStr, E: Clone + FromStr + One> AdjacencyList<V, E> {
+    /// Reads a graph from JSON in the form written by [`AdjacencyList::to_json`].\
+    /// Both lists are optional, and the vertices of the edges don't have to be listed. The edges are directed unless
+    /// `"directed"` is `false`, and an edge can override that with its own `"directed"`. An edge without a `"weight"` gets a weight of one.
+    /// The ends of an edge can also be called `"source"` and `"target"`.
+    pub fn from_json(text: &str) -> Result<Self, ParseGraphError> {
+        let mut parser = JsonParser { text, position: 0 };
+        let root = parser.value()?;
+        parser.skip_whitespace();
+        if parser.position < text.len() {
+            return Err(parser.error("expected the end of the JSON"));
+        }
+
+        let syntax_error = |value: &Value, message: &str| ParseGraphError::Syntax {
+            line: line_at(text, value.offset),
+            message: message.to_string(),
+        };
+        let fields = match &root.json {
+            Json::Object(fields) => fields,
+            _ => {
+                return Err(syntax_error(
+                    &root,
+                    "expected an object with the vertices and edges",
+                ))
+            }
+        };
+        let directed =
+            |fields: &[(String, Value)], default: bool| match field(fields, &["directed"]) {
+                Some(Value {
+                    json: Json::Bool(directed),
+                    ..
+                }) => Ok(*directed),
+                Some(value) => Err(syntax_error(
+                    value,
+                    "expected \"directed\" to be true or false",
+                )),
+                None => Ok(default),
+            };
+        let list = |name: &str| match field(fields, &[name]) {
+            Some(Value {
+                json: Json::Array(items),
+                ..
+            }) => Ok(items.as_slice()),
+            Some(value) => Err(syntax_error(
+                value,
+                &format!("expected \"{}\" to be a list", name),
+            )),
+            None => Ok(&[] as &[Value]),
+        };
+
+        let mut graph = Self::new(BTreeMap::new());
+        let directed_by_default = directed(fields, true)?;
+
+        for vertex in list("vertices")? {
+            let id = vertex
+                .id()
+                .ok_or_else(|| syntax_error(vertex, "expected a vertex to be a string"))?;
+            graph.add_parsed_vertex(id)?;
+        }
+        for edge in list("edges")? {
+            let edge_fields = match &edge.json {
+                Json::Object(fields) => fields,
+                _ => return Err(syntax_error(edge, "expected an edge to be an object")),
+            };
+            let end = |names: &[&str]| {
+                field(edge_fields, names)
+                    .and_then(Value::id)
+                    .ok_or_else(|| {
+                        syntax_error(edge, "expected an edge to have a \"from\" and a \"to\"")
+                    })
+            };
+            let (from, to) = (end(&["from", "source"])?, end(&["to", "target"])?);
+            let weight = match field(edge_fields, &["weight"]) {
+                Some(weight) => Some(
+                    weight
+                        .id()
+                        .ok_or_else(|| syntax_error(weight, "expected a weight to be a number"))?,
+                ),
+                None => None,
+            };
+            graph.add_parsed_edge(
+                from,
+                to,
+                weight,
+                directed(edge_fields, directed_by_default)?,
+            )?;
+        }
+
+        Ok(graph)
+    }
+}
+
+/// Quotes a string for JSON, escaping the characters that JSON doesn't allow in strings.
+fn quote(text: &impl Display) -> String {
+    let mut quoted = String::from('"');
+    for c in text.to_string().chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\t' => quoted += "\\t",
+            '\r' => quoted += "\\r",
+            _ if c.is_control() => quoted += format!("\\u{:04x}", c as u32).as_str(),
+            _ => quoted.push(c),
+        }
+    }
+    quoted + "\""
+}
+
+/// Gets the value of the first of the `fields` that has one of the `names`.
+fn field<'a>(fields: &'a [(String, Value)], names: &[&str]) -> Option<&'a Value> {
+    fields
+        .iter()
+        .find(|(name, _)| names.contains(&name.as_str()))
+        .map(|(_, value)| value)
+}
+
+/// A parsed JSON value and the byte offset where it starts, for showing the line of a value that doesn't fit the schema.
+struct Value {
+    json: Json,
+    offset: usize,
+}
+impl Value {
+    /// Gets the text of a string or a number, which is how vertices and weights are written.
+    fn id(&self) -> Option<&str> {
+        match &self.json {
+            Json::String(text) | Json::Number(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+enum Json {
+    Null,
+    Bool(bool),
+    /// The number as it was written, so it can be parsed into any weight type.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+struct JsonParser<'a> {
+    text: &'a str,
+    /// The byte offset of the next character.
+    position: usize,
+}
+impl JsonParser<'_> {
+    fn error(&self, message: &str) -> ParseGraphError {
+        ParseGraphError::Syntax {
+            line: line_at(self.text, self.position),
+            message: message.to_string(),
+        }
+    }
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+    /// Skips whitespace and then `c`, or returns an error if `c` isn't next.
+    fn expect(&mut self, c: char) -> Result<(), ParseGraphError> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected \"{}\"", c)))
+        }
+    }
+    fn value(&mut self) -> Result<Value, ParseGraphError> {
+        self.skip_whitespace();
+        let offset = self.position;
+        let rest = &self.text[offset..];
+
+        let json = match self.peek() {
+            Some('{') => {
+                self.position += 1;
+                let mut fields = vec![];
+                self.skip_whitespace();
+                if self.peek() == Some('}') {
+                    self.position += 1;
+                } else {
+                    loop {
+                        self.skip_whitespace();
+                        let name = self.string()?;
+                        self.expect(':')?;
+                        fields.push((name, self.value()?));
+                        self.skip_whitespace();
+                        if self.peek() == Some(',') {
+                            self.position += 1;
+                        } else {
+                            self.expect('}')?;
+                            break;
+                        }
+                    }
+                }
+                Json::Object(fields)
+            }
+            Some('[') => {
+                self.position += 1;
+                let mut items = vec![];
+                self.skip_whitespace();
+                if self.peek() == Some(']') {
+                    self.position += 1;
+                } else {
+                    loop {
+                        items.push(self.value()?);
+                        self.skip_whitespace();
+                        if self.peek() == Some(',') {
+                            self.position += 1;
+                        } else {
+                            self.expect(']')?;
+                            break;
+                        }
+                    }
+                }
+                Json::Array(items)
+            }
+            Some('"') => Json::String(self.string()?),
+            Some('-' | '0'..='9') => {
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+                    .unwrap_or(rest.len());
+                self.position += len;
+                Json::Number(rest[..len].to_string())
+            }
+            _ => {
+                let (json, keyword) = [
+                    (Json::Null, "null"),
+                    (Json::Bool(true), "true"),
+                    (Json::Bool(false), "false"),
+                ]
+                .into_iter()
+                .find(|(_, keyword)| rest.starts_with(keyword))
+                .ok_or_else(|| self.error("expected a value"))?;
+                self.position += keyword.len();
+                json
+            }
+        };
+
+        Ok(Value { json, offset })
+    }
+    fn string(&mut self) -> Result<String, ParseGraphError> {
+        if self.peek() != Some('"') {
+            return Err(self.error("expected a string"));
+        }
+        self.position += 1;
+        let mut string = String::new();
+
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("unclosed string"))?;
+            self.position += c.len_utf8();
+            match c {
+                '"' => return Ok(string),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("unclosed string"))?;
+                    self.position += escaped.len_utf8();
+                    string.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'u' => {
+                            let code = self
+                                .text
+                                .get(self.position..self.position + 4)
+                                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                                .ok_or_else(|| self.error("invalid unicode escape"))?;
+                            self.position += 4;
+                            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                        }
+                        _ => escaped,
+                    });
+                }
+                _ => string.push(c),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    #[test]
+    fn reads_other_json_graphs() {
+        let json = r#"{
+            "name": "roads",
+            "directed": false,
+            "edges": [
+                { "source": "0,0", "target": "1,0", "weight": 2.5, "color": [1, 2, 3] },
+                { "from": "1,0", "to": "2,0", "directed": true },
+                { "from": "2,0", "to": "3,0", "weight": "-1e1" }
+            ],
+            "extra": { "nested": [null, true] }
+        }"#;
+        let graph = AdjacencyList::<Coord, f32>::from_json(json).unwrap();
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(1, 0), &Coord::new(0, 0)),
+            Some(&2.5)
+        );
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(1, 0), &Coord::new(2, 0)),
+            Some(&1.0)
+        );
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(2, 0), &Coord::new(1, 0)),
+            None
+        );
+        assert_eq!(
+            graph.get_edge_weight(&Coord::new(3, 0), &Coord::new(2, 0)),
+            Some(&-10.0)
+        );
+
+        assert_eq!(
+            AdjacencyList::<Coord, f32>::from_json("{\n  \"edges\": [{ \"from\": \"0,0\" }]\n}"),
+            Err(ParseGraphError::Syntax {
+                line: 2,
+                message: "expected an edge to have a \"from\" and a \"to\"".to_string()
+            })
+        );
+        assert!(AdjacencyList::<Coord, f32>::from_json("{ \"edges\": [ }").is_err());
+        assert!(AdjacencyList::<Coord, f32>::from_json("[]").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+//! Writing graphs in the formats of other graph tools and reading them back, so graphs can be brought in from elsewhere and results taken out.
+mod dot;
+mod graphml;
+mod json;
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use num_traits::One;
+
+use super::AdjacencyList;
+use crate::Vertex;
+
+/// A text format that graphs can be written in and read from, e.g. for choosing the format from a dropdown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// The language of Graphviz.
+    #[default]
+    Dot,
+    /// The XML format understood by most graph editors, e.g. yEd, Gephi and Cytoscape.
+    GraphMl,
+    /// A list of vertices and a list of weighted edges.
+    Json,
+}
+
+impl GraphFormat {
+    pub const ALL: [GraphFormat; 3] = [GraphFormat::Dot, GraphFormat::GraphMl, GraphFormat::Json];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "DOT",
+            GraphFormat::GraphMl => "GraphML",
+            GraphFormat::Json => "JSON",
+        }
+    }
+    /// Gets the usual file extension of the format without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::GraphMl => "graphml",
+            GraphFormat::Json => "json",
+        }
+    }
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "text/vnd.graphviz",
+            GraphFormat::GraphMl => "application/graphml+xml",
+            GraphFormat::Json => "application/json",
+        }
+    }
+    /// Guesses the format of `text` from how it starts, since JSON starts with `{` and GraphML with `<`.
+    /// Anything else is assumed to be DOT.
+    pub fn detect(text: &str) -> Self {
+        match text.trim_start().chars().next() {
+            Some('{') => GraphFormat::Json,
+            Some('<') => GraphFormat::GraphMl,
+            _ => GraphFormat::Dot,
+        }
+    }
+}
+
+/// An error returned when a graph can't be read from text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseGraphError {
+    /// The text doesn't follow the format on the line, which starts from 1.
+    Syntax { line: usize, message: String },
+    /// A vertex id can't be parsed into a vertex.
+    InvalidVertex(String),
+    /// An edge weight can't be parsed into a weight.
+    InvalidWeight(String),
+}
+
+impl Display for ParseGraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGraphError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            ParseGraphError::InvalidVertex(id) => write!(f, "\"{}\" is not a valid vertex", id),
+            ParseGraphError::InvalidWeight(weight) => {
+                write!(f, "\"{}\" is not a valid edge weight", weight)
+            }
+        }
+    }
+}
+
+impl Error for ParseGraphError {}
+
+impl<V: Vertex, E: Clone + Display> AdjacencyList<V, E> {
+    /// Writes the graph in `format`. Every edge is written as directed, so an undirected edge is written in both directions.
+    pub fn to_format(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::GraphMl => self.to_graphml(),
+            GraphFormat::Json => self.to_json(),
+        }
+    }
+}
+
+impl<V: Vertex + FromStr, E: Clone + FromStr + One> AdjacencyList<V, E> {
+    /// Reads a graph written in `format`. Edges without a weight get a weight of one.
+    pub fn from_format(format: GraphFormat, text: &str) -> Result<Self, ParseGraphError> {
+        match format {
+            GraphFormat::Dot => Self::from_dot(text),
+            GraphFormat::GraphMl => Self::from_graphml(text),
+            GraphFormat::Json => Self::from_json(text),
+        }
+    }
+    /// Parses `id` and adds the vertex without edges if it doesn't exist yet, unlike [`AdjacencyList::add_vertex`] which would remove its edges.
+    fn add_parsed_vertex(&mut self, id: &str) -> Result<V, ParseGraphError> {
+        let vertex = id
+            .parse()
+            .map_err(|_| ParseGraphError::InvalidVertex(id.to_string()))?;
+        if !self.contains_vertex(&vertex) {
+            self.add_vertex(vertex);
+        }
+        Ok(vertex)
+    }
+    /// Adds an edge from `from` to `to`, and back if it's not `directed`, with a weight of one if there is no `weight`.
+    fn add_parsed_edge(
+        &mut self,
+        from: &str,
+        to: &str,
+        weight: Option<&str>,
+        directed: bool,
+    ) -> Result<(), ParseGraphError> {
+        let (from, to) = (self.add_parsed_vertex(from)?, self.add_parsed_vertex(to)?);
+        let weight = match weight {
+            Some(weight) => weight
+                .trim()
+                .parse()
+                .map_err(|_| ParseGraphError::InvalidWeight(weight.to_string()))?,
+            None => E::one(),
+        };
+        if directed {
+            self.add_edge_directed(from, to, weight);
+        } else {
+            self.add_edge_undirected(from, to, weight);
+        }
+        Ok(())
+    }
+}
+
+/// Gets the line of the byte at `offset` in `text`, starting from 1.
+fn line_at(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    /// A graph with an undirected edge, a directed edge, a self-loop, a fractional weight and a vertex without edges.
+    fn graph() -> AdjacencyList<Coord, f32> {
+        let mut graph = AdjacencyList::default();
+        graph.add_edge_undirected(Coord::new(0, 0), Coord::new(1, 0), 1.0);
+        graph.add_edge_directed(Coord::new(1, 0), Coord::new(-2, 3), 2.5);
+        graph.add_edge_directed(Coord::new(-2, 3), Coord::new(-2, 3), -1.0);
+        graph.add_vertex(Coord::new(9, 9));
+        graph
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        let graph = graph();
+        for format in GraphFormat::ALL {
+            let text = graph.to_format(format);
+            assert_eq!(GraphFormat::detect(&text), format, "{}", format.name());
+            assert_eq!(
+                AdjacencyList::from_format(format, &text),
+                Ok(graph.clone()),
+                "{}",
+                format.name()
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_vertices_and_weights_are_rejected() {
+        assert_eq!(
+            AdjacencyList::<Coord, f32>::from_format(GraphFormat::Dot, "digraph { a -> \"1,0\" }"),
+            Err(ParseGraphError::InvalidVertex("a".to_string()))
+        );
+        assert_eq!(
+            AdjacencyList::<Coord, f32>::from_format(
+                GraphFormat::Json,
+                r#"{ "edges": [{ "from": "0,0", "to": "1,0", "weight": "heavy" }] }"#
+            ),
+            Err(ParseGraphError::InvalidWeight("heavy".to_string()))
+        );
+    }
+}
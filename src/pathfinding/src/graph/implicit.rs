@@ -0,0 +1,81 @@
+use std::marker::PhantomData;
+
+use super::Graph;
+use crate::Vertex;
+
+/// A [`Graph`] whose neighbors are generated on demand by a closure instead of being stored.\
+/// This lets the pathfinding algorithms search enormous or even unbounded graphs, since only the vertices that are actually visited are ever created.
+/// The vertices can't be listed, so [`Graph::vertices`] is always empty.
+///
+/// ## Example
+///
+/// ```rust
+/// use pathfinding::{graph::ImplicitGraph, pathfinding_algorithms::a_star, Coord, PathfindingSteps};
+///
+/// // An infinite grid
+/// let graph = ImplicitGraph::new(|coord: &Coord| {
+///     coord.adjacent(false).into_iter().map(|neighbor| (neighbor, 1.0_f32)).collect()
+/// });
+///
+/// let result = a_star(graph, Coord::new(0, 0), Coord::new(3, -2), PathfindingSteps::new(vec![]));
+/// assert_eq!(result.path.len(), 6);
+/// ```
+pub struct ImplicitGraph<V, E, F: Fn(&V) -> Vec<(V, E)>> {
+    neighbors_fn: F,
+    _marker: PhantomData<(V, E)>,
+}
+impl<V, E, F: Fn(&V) -> Vec<(V, E)>> ImplicitGraph<V, E, F> {
+    /// Creates a graph where the neighbors of each vertex and the weights of the edges leading to them are given by `neighbors_fn`.
+    pub fn new(neighbors_fn: F) -> Self {
+        Self {
+            neighbors_fn,
+            _marker: PhantomData,
+        }
+    }
+}
+impl<V: Vertex, E: Clone, F: Fn(&V) -> Vec<(V, E)>> Graph<V, E> for ImplicitGraph<V, E, F> {
+    fn neighbors(&self, vertex: &V) -> Vec<(V, E)> {
+        (self.neighbors_fn)(vertex)
+    }
+    fn vertices(&self) -> Vec<V> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pathfinding_algorithms::a_star_to_any, Coord, PathfindingSteps};
+
+    #[test]
+    fn a_star_on_infinite_grid() {
+        // Moving along x costs 1 and along y costs 2
+        let graph = ImplicitGraph::new(|coord: &Coord| {
+            coord
+                .adjacent(false)
+                .into_iter()
+                .map(|neighbor| (neighbor, if neighbor.y == coord.y { 1.0 } else { 2.0 }))
+                .collect::<Vec<(Coord, f32)>>()
+        });
+        let (start, goal) = (Coord::new(-40, 25), Coord::new(1_000, -1_000));
+
+        let result = a_star_to_any(
+            &graph,
+            start,
+            |vertex| *vertex == goal,
+            |vertex| ((goal.x - vertex.x).abs() + 2 * (goal.y - vertex.y).abs()) as f32,
+            PathfindingSteps::new(vec![]),
+        );
+
+        assert_eq!(result.path.first(), Some(&start));
+        assert_eq!(result.path.last(), Some(&goal));
+        assert_eq!(result.path.len(), 1040 + 1025 + 1);
+        let cost: f32 = result
+            .path
+            .windows(2)
+            .map(|pair| graph.edge_weight(&pair[0], &pair[1]).unwrap())
+            .sum();
+        assert_eq!(cost, 1040.0 + 2.0 * 1025.0);
+        assert!(graph.vertices().is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::AdjacencyList;
+use crate::Vertex;
+
+/// A Fruchterman-Reingold spring layout of a graph that is advanced one step at a time, e.g. to animate it
+/// or to let vertices be dragged around while the rest of the graph makes room for them.\
+/// Every pair of vertices repels each other while the edges pull their ends together. The edges are treated as undirected
+/// and their weights are ignored. The vertices are positioned in the unit square.
+#[derive(Clone, Debug)]
+pub struct ForceLayout<V: Vertex> {
+    vertices: Vec<V>,
+    indices: BTreeMap<V, usize>,
+    /// The pairs of vertex indices that are connected, with the smaller index first.
+    edges: BTreeSet<(usize, usize)>,
+    positions: Vec<(f64, f64)>,
+    /// Whether the vertex at each index stays where it is.
+    pinned: Vec<bool>,
+    /// Gives the starting positions of vertices, including ones that are added later.
+    rng: StdRng,
+}
+
+impl<V: Vertex> ForceLayout<V> {
+    /// Places the vertices of `graph` at random positions. The same `seed` always gives the same layout.
+    pub fn new<E: Clone>(graph: &AdjacencyList<V, E>, seed: u64) -> Self {
+        let mut layout = Self {
+            vertices: vec![],
+            indices: BTreeMap::new(),
+            edges: BTreeSet::new(),
+            positions: vec![],
+            pinned: vec![],
+            rng: StdRng::seed_from_u64(seed),
+        };
+        layout.set_graph(graph);
+        layout
+    }
+    /// Changes the graph that is laid out, e.g. after it was edited. The vertices that are still in the graph keep their positions
+    /// and whether they are pinned, and new vertices start at random positions.
+    pub fn set_graph<E: Clone>(&mut self, graph: &AdjacencyList<V, E>) {
+        let previous: BTreeMap<V, ((f64, f64), bool)> = self
+            .vertices
+            .iter()
+            .zip(self.positions.iter().zip(&self.pinned))
+            .map(|(vertex, (position, pinned))| (*vertex, (*position, *pinned)))
+            .collect();
+
+        self.vertices = graph.ordered_vertices().copied().collect();
+        self.indices = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(index, vertex)| (*vertex, index))
+            .collect();
+        self.edges = graph
+            .edges_iter()
+            .filter_map(|(from, to, _)| Some((self.indices[from], *self.indices.get(to)?)))
+            .filter(|(from, to)| from != to)
+            .map(|(from, to)| (from.min(to), from.max(to)))
+            .collect();
+
+        let rng = &mut self.rng;
+        (self.positions, self.pinned) = self
+            .vertices
+            .iter()
+            .map(|vertex| {
+                previous
+                    .get(vertex)
+                    .copied()
+                    .unwrap_or_else(|| ((rng.gen::<f64>(), rng.gen::<f64>()), false))
+            })
+            .unzip();
+    }
+    /// Moves every vertex that isn't pinned along the forces on it by at most `temperature`.
+    /// Lowering the temperature over the steps lets the layout settle.
+    ///
+    /// Returns the furthest that a vertex moved, which is close to zero once the layout has settled.
+    pub fn step(&mut self, temperature: f64) -> f64 {
+        if self.vertices.is_empty() {
+            return 0.0;
+        }
+        // The ideal distance between vertices when they are spread evenly over the unit square
+        let k = (1.0 / self.vertices.len() as f64).sqrt();
+        let positions = &self.positions;
+        let mut displacements = vec![(0.0, 0.0); positions.len()];
+
+        // Pushes `a` away from `b` and `b` away from `a` by `force`, or pulls them together if it's negative
+        let mut push = |a: usize, b: usize, force: &dyn Fn(f64) -> f64| {
+            let (dx, dy) = (
+                positions[a].0 - positions[b].0,
+                positions[a].1 - positions[b].1,
+            );
+            let distance = dx.hypot(dy).max(f64::EPSILON);
+            let (fx, fy) = (
+                dx / distance * force(distance),
+                dy / distance * force(distance),
+            );
+            displacements[a].0 += fx;
+            displacements[a].1 += fy;
+            displacements[b].0 -= fx;
+            displacements[b].1 -= fy;
+        };
+
+        for a in 0..positions.len() {
+            for b in a + 1..positions.len() {
+                push(a, b, &|distance| k * k / distance);
+            }
+        }
+        for (a, b) in &self.edges {
+            push(*a, *b, &|distance| -distance * distance / k);
+        }
+
+        let mut furthest: f64 = 0.0;
+        for ((position, (dx, dy)), pinned) in self
+            .positions
+            .iter_mut()
+            .zip(displacements)
+            .zip(&self.pinned)
+        {
+            let length = dx.hypot(dy);
+            if length > 0.0 && !pinned {
+                let step = length.min(temperature) / length;
+                let moved = (
+                    (position.0 + dx * step).clamp(0.0, 1.0),
+                    (position.1 + dy * step).clamp(0.0, 1.0),
+                );
+                furthest = furthest.max((moved.0 - position.0).hypot(moved.1 - position.1));
+                *position = moved;
+            }
+        }
+        furthest
+    }
+    pub fn position(&self, vertex: &V) -> Option<(f64, f64)> {
+        self.indices.get(vertex).map(|index| self.positions[*index])
+    }
+    pub fn positions(&self) -> BTreeMap<V, (f64, f64)> {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.positions.iter().copied())
+            .collect()
+    }
+    /// Moves `vertex` to `position` and keeps it there until it's [unpinned](ForceLayout::unpin), e.g. while it's being dragged.
+    pub fn pin(&mut self, vertex: &V, position: (f64, f64)) {
+        if let Some(index) = self.indices.get(vertex) {
+            self.positions[*index] = (position.0.clamp(0.0, 1.0), position.1.clamp(0.0, 1.0));
+            self.pinned[*index] = true;
+        }
+    }
+    /// Lets the forces move `vertex` again.
+    pub fn unpin(&mut self, vertex: &V) {
+        if let Some(index) = self.indices.get(vertex) {
+            self.pinned[*index] = false;
+        }
+    }
+    /// Finds the vertex closest to `point` that is at most `radius` away from it, e.g. the vertex under the mouse.
+    pub fn vertex_at(&self, point: (f64, f64), radius: f64) -> Option<V> {
+        self.vertices
+            .iter()
+            .zip(&self.positions)
+            .map(|(vertex, (x, y))| (vertex, (x - point.0).hypot(y - point.1)))
+            .filter(|(_, distance)| *distance <= radius)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(vertex, _)| *vertex)
+    }
+}
+
+impl<V: Vertex, E: Clone> AdjacencyList<V, E> {
+    /// Positions the vertices in the unit square with the [`ForceLayout`], for drawing graphs that aren't grids.\
+    /// The vertices move less and less over the `iterations` until they settle.
+    ///
+    /// The starting positions are random, and the same `seed` always gives the same layout.
+    pub fn force_directed_layout(&self, iterations: usize, seed: u64) -> BTreeMap<V, (f64, f64)> {
+        let mut layout = ForceLayout::new(self, seed);
+        for iteration in 0..iterations {
+            // The furthest that a vertex can move on this iteration, cooling linearly to zero
+            layout.step(0.1 * (1.0 - iteration as f64 / iterations as f64));
+        }
+        layout.positions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+        (a.0 - b.0).hypot(a.1 - b.1)
+    }
+
+    #[test]
+    fn connected_vertices_end_up_closer() {
+        // A ring of 12 vertices
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for i in 0..12 {
+            graph.add_edge_undirected(Coord::new(i, 0), Coord::new((i + 1) % 12, 0), 1.0);
+        }
+
+        let layout = graph.force_directed_layout(200, 7);
+        assert_eq!(layout.len(), 12);
+        assert!(layout
+            .values()
+            .all(|(x, y)| (0.0..=1.0).contains(x) && (0.0..=1.0).contains(y)));
+        assert_eq!(layout, graph.force_directed_layout(200, 7));
+
+        let (mut connected, mut unconnected) = (vec![], vec![]);
+        for (a, position_a) in &layout {
+            for (b, position_b) in layout.range(a..).skip(1) {
+                let distance = distance(*position_a, *position_b);
+                if graph.is_neighbor(a, b) {
+                    connected.push(distance);
+                } else {
+                    unconnected.push(distance);
+                }
+            }
+        }
+        let average = |distances: &[f64]| distances.iter().sum::<f64>() / distances.len() as f64;
+        assert_eq!(connected.len(), 12);
+        assert!(average(&connected) < average(&unconnected));
+    }
+
+    #[test]
+    fn pinned_vertices_stay_when_the_graph_changes() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(Coord::new(0, 0), Coord::new(1, 0), 1.0);
+        graph.add_edge_undirected(Coord::new(1, 0), Coord::new(2, 0), 1.0);
+
+        let mut layout = ForceLayout::new(&graph, 3);
+        layout.pin(&Coord::new(1, 0), (0.5, 1.5));
+        for _ in 0..20 {
+            layout.step(0.05);
+        }
+        assert_eq!(layout.position(&Coord::new(1, 0)), Some((0.5, 1.0)));
+        assert_eq!(layout.vertex_at((0.49, 0.98), 0.05), Some(Coord::new(1, 0)));
+
+        graph.add_edge_undirected(Coord::new(2, 0), Coord::new(3, 0), 1.0);
+        graph.remove_vertex(&Coord::new(0, 0));
+        layout.set_graph(&graph);
+        assert_eq!(layout.positions().len(), 3);
+        assert_eq!(layout.position(&Coord::new(0, 0)), None);
+        assert!(layout.position(&Coord::new(3, 0)).is_some());
+
+        layout.unpin(&Coord::new(1, 0));
+        layout.step(0.05);
+        assert_ne!(layout.position(&Coord::new(1, 0)), Some((0.5, 1.0)));
+    }
+
+    #[test]
+    fn steps_move_less_as_the_layout_settles() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for i in 0..6 {
+            graph.add_edge_undirected(Coord::new(i, 0), Coord::new((i + 1) % 6, 0), 1.0);
+        }
+
+        let mut layout = ForceLayout::new(&graph, 5);
+        let first = layout.step(0.05);
+        assert!(first > 0.0 && first <= 0.05 + f64::EPSILON);
+
+        let mut temperature = 0.05;
+        let mut last = first;
+        for _ in 0..200 {
+            temperature *= 0.95;
+            last = layout.step(temperature);
+            assert!(last <= temperature + f64::EPSILON);
+        }
+        assert!(last < 0.001);
+        assert_eq!(
+            ForceLayout::new(&AdjacencyList::<Coord, f32>::default(), 0).step(0.05),
+            0.0
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+
+use super::{AdjacencyList, Graph, GraphMut};
+use crate::Vertex;
+
+/// A graph that stores the edge from every vertex to every other vertex in a matrix, with `None` where there is no edge.\
+/// Looking up an edge takes constant time after finding the vertices, but the matrix takes space for every pair of vertices,
+/// so it suits small and dense graphs better than an [`AdjacencyList`]. The two can be converted into each other with [`From`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdjacencyMatrix<V: Vertex, E: Clone> {
+    /// The vertices in ascending order. The index of a vertex is both its row and its column.
+    vertices: Vec<V>,
+    /// The edges as `rows[from][to]`.
+    rows: Vec<Vec<Option<E>>>,
+}
+impl<V: Vertex, E: Clone> Default for AdjacencyMatrix<V, E> {
+    fn default() -> Self {
+        Self {
+            vertices: vec![],
+            rows: vec![],
+        }
+    }
+}
+impl<V: Vertex, E: Clone> AdjacencyMatrix<V, E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Gets the row and column of `vertex`, or `None` if it doesn't exist.
+    pub fn index_of(&self, vertex: &V) -> Option<usize> {
+        self.vertices.binary_search(vertex).ok()
+    }
+    /// Gets the rows of the matrix in the order of [`Graph::vertices`], e.g. for showing the matrix as a table.
+    pub fn rows(&self) -> &[Vec<Option<E>>] {
+        &self.rows
+    }
+    /// Adds `vertex` without any edges, shifting the rows and columns of the vertices after it.
+    /// Does nothing if the vertex already exists.
+    pub fn add_vertex(&mut self, vertex: V) {
+        if let Err(index) = self.vertices.binary_search(&vertex) {
+            self.vertices.insert(index, vertex);
+            for row in self.rows.iter_mut() {
+                row.insert(index, None);
+            }
+            self.rows.insert(index, vec![None; self.vertices.len()]);
+        }
+    }
+    /// Removes `vertex` along with all edges leading to or from it.
+    pub fn remove_vertex(&mut self, vertex: &V) {
+        if let Some(index) = self.index_of(vertex) {
+            self.vertices.remove(index);
+            self.rows.remove(index);
+            for row in self.rows.iter_mut() {
+                row.remove(index);
+            }
+        }
+    }
+    /// Adds a new directed edge from `a` to `b` with a given `weight`, creating the vertices if they don't exist.\
+    /// If the edge already exists, its weight is replaced.
+    pub fn add_edge_directed(&mut self, a: V, b: V, weight: E) {
+        self.add_vertex(a);
+        self.add_vertex(b);
+        let (from, to) = (
+            self.vertices.binary_search(&a),
+            self.vertices.binary_search(&b),
+        );
+        if let (Ok(from), Ok(to)) = (from, to) {
+            self.rows[from][to] = Some(weight);
+        }
+    }
+    /// Adds new edges in both directions between `a` and `b` with a given `weight`, creating the vertices if they don't exist.
+    pub fn add_edge_undirected(&mut self, a: V, b: V, weight: E) {
+        self.add_edge_directed(a, b, weight.clone());
+        self.add_edge_directed(b, a, weight);
+    }
+    /// Removes the directed edge from `a` to `b` and returns its weight if it existed.
+    pub fn remove_edge_directed(&mut self, a: &V, b: &V) -> Option<E> {
+        let (from, to) = (self.index_of(a)?, self.index_of(b)?);
+        self.rows[from][to].take()
+    }
+    /// Removes the edges between `a` and `b` in both directions and returns the weight of the edge from `a` to `b` if it existed.
+    pub fn remove_edge_undirected(&mut self, a: &V, b: &V) -> Option<E> {
+        self.remove_edge_directed(b, a);
+        self.remove_edge_directed(a, b)
+    }
+}
+
+impl<V: Vertex, E: Clone> Graph<V, E> for AdjacencyMatrix<V, E> {
+    fn neighbors(&self, vertex: &V) -> Vec<(V, E)> {
+        self.index_of(vertex).map_or(vec![], |from| {
+            self.vertices
+                .iter()
+                .zip(&self.rows[from])
+                .filter_map(|(neighbor, weight)| Some((*neighbor, weight.clone()?)))
+                .collect()
+        })
+    }
+    fn vertices(&self) -> Vec<V> {
+        self.vertices.clone()
+    }
+    fn edge_weight(&self, from: &V, to: &V) -> Option<E> {
+        self.rows[self.index_of(from)?][self.index_of(to)?].clone()
+    }
+}
+
+impl<V: Vertex, E: Clone> GraphMut<V, E> for AdjacencyMatrix<V, E> {
+    fn add_edge(&mut self, from: V, to: V, weight: E) {
+        self.add_edge_directed(from, to, weight);
+    }
+    fn remove_edge(&mut self, from: &V, to: &V) -> Option<E> {
+        self.remove_edge_directed(from, to)
+    }
+}
+
+impl<V: Vertex, E: Clone> From<&AdjacencyList<V, E>> for AdjacencyMatrix<V, E> {
+    fn from(list: &AdjacencyList<V, E>) -> Self {
+        let vertices = list.ordered_vertices().copied().collect::<Vec<V>>();
+        let rows = vertices
+            .iter()
+            .map(|from| {
+                vertices
+                    .iter()
+                    .map(|to| list.get_edge_weight(from, to).cloned())
+                    .collect()
+            })
+            .collect();
+
+        Self { vertices, rows }
+    }
+}
+
+impl<V: Vertex, E: Clone> From<&AdjacencyMatrix<V, E>> for AdjacencyList<V, E> {
+    fn from(matrix: &AdjacencyMatrix<V, E>) -> Self {
+        AdjacencyList::new(
+            matrix
+                .vertices
+                .iter()
+                .map(|vertex| {
+                    let edges = matrix
+                        .neighbors(vertex)
+                        .into_iter()
+                        .collect::<BTreeMap<V, E>>();
+                    (*vertex, edges)
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pathfinding_algorithms::dijkstra, Coord, PathfindingSteps};
+
+    #[test]
+    fn conversions_keep_the_graph() {
+        let mut list =
+            AdjacencyList::<Coord, f32>::grid_with_obstacles(4, 3, true, &Default::default());
+        list.add_edge_directed(Coord::new(0, 0), Coord::new(3, 2), 10.0);
+        // A vertex without edges still gets a row and a column
+        list.add_vertex(Coord::new(9, 9));
+
+        let matrix = AdjacencyMatrix::from(&list);
+        assert_eq!(matrix.rows().len(), 13);
+        assert!(matrix.rows().iter().all(|row| row.len() == 13));
+        assert_eq!(
+            matrix.edge_weight(&Coord::new(0, 0), &Coord::new(3, 2)),
+            Some(10.0)
+        );
+        assert_eq!(
+            matrix.edge_weight(&Coord::new(3, 2), &Coord::new(0, 0)),
+            None
+        );
+        assert_eq!(AdjacencyList::from(&matrix), list);
+    }
+
+    #[test]
+    fn same_search_through_both_representations() {
+        let list = AdjacencyList::<Coord, f32>::grid_with_obstacles(
+            5,
+            5,
+            false,
+            &[
+                Coord::new(2, 0),
+                Coord::new(2, 1),
+                Coord::new(2, 2),
+                Coord::new(2, 3),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let matrix = AdjacencyMatrix::from(&list);
+        let (start, end) = (Coord::new(0, 0), Coord::new(4, 0));
+
+        let from_list = dijkstra(&list, start, end, PathfindingSteps::new(vec![]));
+        let from_matrix = dijkstra(&matrix, start, end, PathfindingSteps::new(vec![]));
+        assert_eq!(from_list.path, from_matrix.path);
+        assert_eq!(from_matrix.path.len(), 13);
+    }
+
+    #[test]
+    fn editing_edges_and_vertices() {
+        let mut matrix = AdjacencyMatrix::new();
+        matrix.add_edge_undirected(Coord::new(1, 0), Coord::new(0, 0), 2.0_f32);
+        matrix.add_edge(Coord::new(0, 0), Coord::new(2, 0), 3.0);
+        assert_eq!(
+            Graph::vertices(&matrix),
+            [Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0)]
+        );
+        assert_eq!(
+            matrix.neighbors(&Coord::new(0, 0)),
+            [(Coord::new(1, 0), 2.0), (Coord::new(2, 0), 3.0)]
+        );
+
+        assert_eq!(
+            matrix.remove_edge(&Coord::new(0, 0), &Coord::new(1, 0)),
+            Some(2.0)
+        );
+        assert_eq!(
+            matrix.edge_weight(&Coord::new(1, 0), &Coord::new(0, 0)),
+            Some(2.0)
+        );
+        assert_eq!(
+            matrix.remove_edge(&Coord::new(0, 0), &Coord::new(1, 0)),
+            None
+        );
+
+        matrix.remove_vertex(&Coord::new(1, 0));
+        assert_eq!(matrix.rows(), [vec![None, Some(3.0)], vec![None, None]]);
+    }
+}
@@ -0,0 +1,305 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use super::AdjacencyList;
+use crate::Vertex;
+
+/// The direction that a Mermaid flowchart is laid out in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MermaidDirection {
+    #[default]
+    LeftToRight,
+    TopToBottom,
+    RightToLeft,
+    BottomToTop,
+}
+
+impl MermaidDirection {
+    pub const ALL: [MermaidDirection; 4] = [
+        MermaidDirection::LeftToRight,
+        MermaidDirection::TopToBottom,
+        MermaidDirection::RightToLeft,
+        MermaidDirection::BottomToTop,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MermaidDirection::LeftToRight => "Left to right",
+            MermaidDirection::TopToBottom => "Top to bottom",
+            MermaidDirection::RightToLeft => "Right to left",
+            MermaidDirection::BottomToTop => "Bottom to top",
+        }
+    }
+    /// Gets the keyword of the direction that follows `flowchart`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            MermaidDirection::LeftToRight => "LR",
+            MermaidDirection::TopToBottom => "TB",
+            MermaidDirection::RightToLeft => "RL",
+            MermaidDirection::BottomToTop => "BT",
+        }
+    }
+}
+
+/// How [`AdjacencyList::into_mermaid`] writes a diagram. The default is a left to right flowchart without any labels or styles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MermaidOptions<V> {
+    pub direction: MermaidDirection,
+    /// Whether the edges are labeled with their weights like `a -->|3| b`.
+    pub weight_labels: bool,
+    /// The CSS class of every vertex that has one, assigned to its id with a `class` directive, e.g. to color the start and goal of a path.\
+    /// The classes themselves have to be defined separately, for example with `classDef` or in the page's CSS.
+    pub classes: BTreeMap<V, String>,
+    /// The vertices of a path whose edges are highlighted with a `linkStyle` directive.
+    pub path: Vec<V>,
+}
+impl<V> Default for MermaidOptions<V> {
+    fn default() -> Self {
+        Self {
+            direction: MermaidDirection::default(),
+            weight_labels: false,
+            classes: BTreeMap::new(),
+            path: vec![],
+        }
+    }
+}
+
+impl<V: Vertex, E: Clone + Display + PartialEq> AdjacencyList<V, E> {
+    /// Writes the graph as a Mermaid flowchart.\
+    /// Every vertex is declared first with an id that Mermaid can parse and the vertex as its label, like `v0_0["0,0"]`,
+    /// and the links then refer to the ids.
+    /// A pair of edges in both directions is written once as an undirected link from the smaller vertex to the larger one.
+    /// If the weights are shown and the two edges have different weights, both are written as directed links so that no weight is lost.
+    pub fn into_mermaid(&self, options: &MermaidOptions<V>) -> String {
+        let mut diagram = format!("flowchart {}", options.direction.code());
+        let ids = self.mermaid_ids();
+        for (vertex, id) in &ids {
+            diagram += format!(
+                "\n    {}[\"{}\"]",
+                id,
+                vertex.to_string().replace('"', "#quot;")
+            )
+            .as_str();
+        }
+
+        let path_edges: BTreeSet<(V, V)> = options
+            .path
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect();
+        let mut path_links = vec![];
+        let mut link_count = 0;
+
+        for (from, to, weight) in self.edges_iter() {
+            let reverse_weight = self.get_edge_weight(to, from);
+            // Graphs built by hand may have edges to vertices without an entry, so those are treated as directed
+            let undirected =
+                reverse_weight.is_some_and(|reverse| !options.weight_labels || reverse == weight);
+            if undirected && to < from {
+                continue;
+            }
+
+            let link = if undirected { "---" } else { "-->" };
+            let label = if options.weight_labels {
+                format!("|{}|", weight)
+            } else {
+                String::new()
+            };
+            if path_edges.contains(&(*from, *to))
+                || (undirected && path_edges.contains(&(*to, *from)))
+            {
+                // Mermaid numbers the links in the order they are written
+                path_links.push(link_count.to_string());
+            }
+            diagram += format!("\n    {} {}{} {}", ids[from], link, label, ids[to]).as_str();
+            link_count += 1;
+        }
+
+        for (vertex, class) in &options.classes {
+            // A class can be given to a vertex that isn't in the graph, which Mermaid would add as a new node
+            if let Some(id) = ids.get(vertex) {
+                diagram += format!("\n    class {} {}", id, class).as_str();
+            }
+        }
+        if !path_links.is_empty() {
+            diagram += format!(
+                "\n    linkStyle {} stroke:#f0a,stroke-width:3px",
+                path_links.join(",")
+            )
+            .as_str();
+        }
+
+        diagram
+    }
+    /// Gives every vertex, including the ones that only have edges to them, a unique Mermaid id made of the vertex
+    /// with everything other than letters and digits replaced with `_`, like `v0_0` for `0,0`.
+    fn mermaid_ids(&self) -> BTreeMap<V, String> {
+        let vertices = self
+            .ordered_vertices()
+            .copied()
+            .chain(self.edges_iter().map(|(_, to, _)| *to))
+            .collect::<BTreeSet<V>>();
+        let mut used = BTreeSet::new();
+
+        vertices
+            .into_iter()
+            .map(|vertex| {
+                let sanitized = vertex
+                    .to_string()
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                    .collect::<String>();
+                let mut id = format!("v{}", sanitized);
+                // Different vertices can only get the same id if they differ by the replaced characters
+                let mut duplicates = 1;
+                while !used.insert(id.clone()) {
+                    duplicates += 1;
+                    id = format!("v{}_{}", sanitized, duplicates);
+                }
+                (vertex, id)
+            })
+            .collect()
+    }
+    /// Like [`AdjacencyList::into_mermaid`] with the default options, but also assigns the CSS class returned by `class_of`
+    /// to each vertex. Vertices for which `class_of` returns `None` get no class.
+    pub fn into_mermaid_styled<'a>(&self, class_of: impl Fn(&V) -> Option<&'a str>) -> String {
+        self.into_mermaid(&MermaidOptions {
+            classes: self
+                .ordered_vertices()
+                .filter_map(|vertex| Some((*vertex, class_of(vertex)?.to_string())))
+                .collect(),
+            ..MermaidOptions::default()
+        })
+    }
+    /// Like [`AdjacencyList::into_mermaid`] with the default options, but highlights the edges along `path`
+    /// and gives its first vertex the `start` class and its last vertex the `goal` class.\
+    /// An empty path gives the same diagram as the default options.
+    pub fn into_mermaid_path(&self, path: &[V]) -> String {
+        let mut classes = BTreeMap::new();
+        if let (Some(start), Some(goal)) = (path.first(), path.last()) {
+            classes.insert(*goal, "goal".to_string());
+            classes.insert(*start, "start".to_string());
+        }
+
+        self.into_mermaid(&MermaidOptions {
+            classes,
+            path: path.to_vec(),
+            ..MermaidOptions::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    fn v(i: isize) -> Coord {
+        Coord::new(i, 0)
+    }
+
+    #[test]
+    fn mermaid_edge_to_vertex_without_entry() {
+        let graph = AdjacencyList::<Coord, f32>::new(BTreeMap::from([(
+            v(0),
+            BTreeMap::from([(v(1), 1.0)]),
+        )]));
+
+        assert_eq!(
+            graph.into_mermaid(&MermaidOptions::default()),
+            "flowchart LR\n    v0_0[\"0,0\"]\n    v1_0[\"1,0\"]\n    v0_0 --> v1_0"
+        );
+    }
+
+    #[test]
+    fn mermaid_undirected_edges_are_written_once() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(1), v(0), 3.0);
+        graph.add_edge_directed(v(1), v(2), 1.5);
+        graph.add_edge_directed(v(2), v(1), 2.0);
+        graph.add_vertex(v(4));
+        let declarations =
+            "\n    v0_0[\"0,0\"]\n    v1_0[\"1,0\"]\n    v2_0[\"2,0\"]\n    v4_0[\"4,0\"]";
+
+        assert_eq!(
+            graph.into_mermaid(&MermaidOptions::default()),
+            format!(
+                "flowchart LR{}\n    v0_0 --- v1_0\n    v1_0 --- v2_0",
+                declarations
+            )
+        );
+        // The edges between 1 and 2 have different weights, so both are kept
+        assert_eq!(
+            graph.into_mermaid(&MermaidOptions {
+                direction: MermaidDirection::TopToBottom,
+                weight_labels: true,
+                ..MermaidOptions::default()
+            }),
+            format!(
+                "flowchart TB{}\n    v0_0 ---|3| v1_0\n    v1_0 -->|1.5| v2_0\n    v2_0 -->|2| v1_0",
+                declarations
+            )
+        );
+    }
+
+    #[test]
+    fn mermaid_ids_are_unique_words() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(Coord::new(-1, 2), Coord::new(1, -2), 1.0);
+
+        assert_eq!(
+            graph.into_mermaid(&MermaidOptions::default()),
+            "flowchart LR\n    v_1_2[\"-1,2\"]\n    v1__2[\"1,-2\"]\n    v_1_2 --> v1__2"
+        );
+    }
+
+    #[test]
+    fn mermaid_classes_for_styled_vertices() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(0), v(1), 1.0);
+        graph.add_edge_directed(v(1), v(2), 1.0);
+
+        let diagram = graph.into_mermaid_styled(|vertex| match vertex.x {
+            0 => Some("start"),
+            2 => Some("goal"),
+            _ => None,
+        });
+
+        let plain = graph.into_mermaid(&MermaidOptions::default());
+        assert_eq!(
+            diagram,
+            plain.clone() + "\n    class v0_0 start\n    class v2_0 goal"
+        );
+        assert_eq!(graph.into_mermaid_styled(|_| None), plain);
+    }
+
+    #[test]
+    fn mermaid_path_styles_endpoints_and_edges() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(0), v(1), 1.0);
+        graph.add_edge_directed(v(1), v(2), 1.0);
+        graph.add_edge_undirected(v(0), v(3), 1.0);
+
+        // Links: 0,0---1,0 (0), 0,0---3,0 (1), 1,0-->2,0 (2)
+        let plain = graph.into_mermaid(&MermaidOptions::default());
+        assert_eq!(
+            graph.into_mermaid_path(&[v(3), v(0), v(1), v(2)]),
+            plain.clone()
+                + "\n    class v2_0 goal\n    class v3_0 start"
+                + "\n    linkStyle 0,1,2 stroke:#f0a,stroke-width:3px"
+        );
+        assert_eq!(graph.into_mermaid_path(&[]), plain);
+
+        // The endpoints are styled through their ids, which Mermaid can parse unlike `-1,0`
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(-1), v(0), 1.0);
+        assert_eq!(
+            graph.into_mermaid_path(&[v(-1), v(0)]),
+            "flowchart LR\n    v_1_0[\"-1,0\"]\n    v0_0[\"0,0\"]\n    v_1_0 --- v0_0".to_string()
+                + "\n    class v_1_0 start\n    class v0_0 goal"
+                + "\n    linkStyle 0 stroke:#f0a,stroke-width:3px"
+        );
+    }
+}
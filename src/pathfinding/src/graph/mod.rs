@@ -0,0 +1,1018 @@
+mod analysis;
+mod builder;
+mod diff;
+mod edit_history;
+mod formats;
+mod implicit;
+mod layout;
+mod matrix;
+mod mermaid;
+mod paths;
+mod random;
+mod render;
+mod spanning_tree;
+mod traits;
+mod traversal;
+mod vertex_weighted;
+
+pub use analysis::{CycleError, GraphTooLargeError, NegativeCycle, ISOMORPHISM_VERTEX_LIMIT};
+pub use builder::GraphBuilder;
+pub use diff::GraphDiff;
+pub use edit_history::{EditHistory, GraphEdit};
+pub use formats::{GraphFormat, ParseGraphError};
+pub use implicit::ImplicitGraph;
+pub use layout::ForceLayout;
+pub use matrix::AdjacencyMatrix;
+pub use mermaid::{MermaidDirection, MermaidOptions};
+pub use paths::{AllPairsShortestPaths, DistanceUpdate, ShortestPathTree};
+pub use random::RandomGraphKind;
+pub use render::{EdgeDirection, EdgeRenderInfo};
+pub use spanning_tree::{EdgeSelection, SpanningTree, SpanningTreeStep};
+pub use traits::{Graph, GraphMut};
+pub use vertex_weighted::VertexWeightedGraph;
+
+use num_traits::{One, ToPrimitive};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    ops::Add,
+};
+
+use crate::{Coord, Edge, Vertex};
+
+/// A graph that stores the outgoing edges of every vertex in a map from the neighbors to the edges.
+///
+/// The edges are usually weights, but `E` can be any type, such as a struct with a weight, a label and a color.
+/// The pathfinding algorithms need numeric weights, so such a graph can be projected to its weights with [`AdjacencyList::map_edges`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AdjacencyList<V: Vertex, E: Clone> {
+    pub hash_map: BTreeMap<V, BTreeMap<V, E>>,
+}
+impl<V: Vertex, E: Clone> AdjacencyList<V, E> {
+    pub fn new(hash_map: BTreeMap<V, BTreeMap<V, E>>) -> Self {
+        Self { hash_map }
+    }
+    pub fn add_vertex(&mut self, vertex: V) {
+        self.hash_map.insert(vertex, BTreeMap::new());
+    }
+    pub fn add_vertex_with_undirected_edges(&mut self, vertex: V, edges: BTreeMap<V, E>) {
+        for (neighbor, weight) in edges {
+            self.add_edge_undirected(vertex, neighbor, weight);
+        }
+    }
+    pub fn add_vertex_with_directed_edges(&mut self, vertex: V, edges: BTreeMap<V, E>) {
+        self.hash_map.insert(vertex, edges);
+    }
+    /// Adds a new directed edge from `a` to `b` with a given `weight`.\
+    /// If `a` doesn't exist, it is created along with the edge to `b`.\
+    /// If `b` doesn't exist, it is created.\
+    /// If the edge already exists, its weight is replaced. See [`AdjacencyList::add_edge_directed_with_policy`] for other ways to handle parallel edges.
+    pub fn add_edge_directed(&mut self, a: V, b: V, weight: E) {
+        if let Some(a) = self.get_neighbors_mut(&a) {
+            a.insert(b, weight);
+        } else {
+            self.add_vertex_with_directed_edges(a, BTreeMap::from([(b, weight)]));
+        }
+        if self.get_neighbors(&b).is_none() {
+            self.add_vertex_with_directed_edges(b, BTreeMap::new());
+        }
+    }
+    /// Adds a new undirected edge between `a` and `b` with a given `weight`.\
+    /// If `a` doesn't exist, it is created along with the edge to `b`.\
+    /// If `b` doesn't exist, it is created along with the edge to `a`.\
+    /// If the edges already exist, their weights are replaced.\
+    /// If `a` and `b` are the same vertex, a single self-loop is added like in [`AdjacencyList::add_self_loop`].
+    pub fn add_edge_undirected(&mut self, a: V, b: V, weight: E) {
+        if a == b {
+            self.add_self_loop(a, weight);
+            return;
+        }
+        if let Some(a) = self.get_neighbors_mut(&a) {
+            a.insert(b, weight.clone());
+        } else {
+            self.add_vertex_with_directed_edges(a, BTreeMap::from([(b, weight.clone())]));
+        }
+        if let Some(b) = self.get_neighbors_mut(&b) {
+            b.insert(a, weight);
+        } else {
+            self.add_vertex_with_directed_edges(b, BTreeMap::from([(a, weight)]));
+        }
+    }
+    /// Adds every edge from `edges` as `(from, to, weight)` like [`AdjacencyList::add_edge_directed`], in the given order.
+    pub fn add_edges_directed(&mut self, edges: impl IntoIterator<Item = (V, V, E)>) {
+        for (a, b, weight) in edges {
+            self.add_edge_directed(a, b, weight);
+        }
+    }
+    /// Adds every edge from `edges` as `(a, b, weight)` like [`AdjacencyList::add_edge_undirected`], in the given order.
+    pub fn add_edges_undirected(&mut self, edges: impl IntoIterator<Item = (V, V, E)>) {
+        for (a, b, weight) in edges {
+            self.add_edge_undirected(a, b, weight);
+        }
+    }
+    /// Adds an edge from `vertex` to itself with a given `weight`, creating the vertex if it doesn't exist.\
+    /// A self-loop is stored as a single edge, so it appears once in [`AdjacencyList::edges`],
+    /// but it contributes 2 to the undirected degree of the vertex since both of its ends are connected to it.
+    pub fn add_self_loop(&mut self, vertex: V, weight: E) {
+        self.hash_map
+            .entry(vertex)
+            .or_default()
+            .insert(vertex, weight);
+    }
+    /// Removes the directed edge from `a` to `b` and returns its weight if it existed.
+    pub fn remove_edge_directed(&mut self, a: &V, b: &V) -> Option<E> {
+        self.get_neighbors_mut(a)?.remove(b)
+    }
+    /// Removes the edges between `a` and `b` in both directions and returns the weight of the edge from `a` to `b` if it existed.
+    pub fn remove_edge_undirected(&mut self, a: &V, b: &V) -> Option<E> {
+        if let Some(edges) = self.get_neighbors_mut(b) {
+            edges.remove(a);
+        }
+        self.remove_edge_directed(a, b)
+    }
+    /// Removes the vertex along with all edges leading to or from it, including directed edges from vertices that it has no edge to.
+    pub fn remove_vertex(&mut self, vertex: &V) {
+        self.hash_map.remove(vertex);
+
+        for edges in self.hash_map.values_mut() {
+            edges.remove(vertex);
+        }
+    }
+    /// Removes every vertex for which `f` returns `false`, along with all edges leading to or from it.
+    pub fn retain_vertices(&mut self, f: impl Fn(&V) -> bool) {
+        self.hash_map.retain(|vertex, _| f(vertex));
+
+        for edges in self.hash_map.values_mut() {
+            edges.retain(|neighbor, _| f(neighbor));
+        }
+    }
+    /// Removes every edge but keeps all of the vertices, unlike [`AdjacencyList::clear`].
+    pub fn clear_edges(&mut self) {
+        for edges in self.hash_map.values_mut() {
+            edges.clear();
+        }
+    }
+    /// Removes every vertex and edge.
+    pub fn clear(&mut self) {
+        self.hash_map.clear();
+    }
+    pub fn toggle_vertex(&mut self, vertex: &V, edges: &BTreeMap<V, E>) {
+        if self.hash_map.contains_key(vertex) {
+            self.remove_vertex(vertex);
+        } else {
+            self.add_vertex_with_directed_edges(*vertex, edges.to_owned());
+        }
+    }
+    pub fn get_neighbors(&self, vertex: &V) -> Option<&BTreeMap<V, E>> {
+        self.hash_map.get(vertex)
+    }
+    pub fn get_neighbors_mut(&mut self, vertex: &V) -> Option<&mut BTreeMap<V, E>> {
+        self.hash_map.get_mut(vertex)
+    }
+    /// Returns `true` if there is a directed edge from `a` to `b`, without handing out the neighbor map of `a`.
+    pub fn is_neighbor(&self, a: &V, b: &V) -> bool {
+        self.hash_map
+            .get(a)
+            .is_some_and(|neighbors| neighbors.contains_key(b))
+    }
+    /// Returns `true` if there are edges in both directions between `a` and `b`. See [`AdjacencyList::is_neighbor`] for a single direction.
+    pub fn has_edge_undirected(&self, a: &V, b: &V) -> bool {
+        self.is_neighbor(a, b) && self.is_neighbor(b, a)
+    }
+    /// Returns `true` if `vertex` is in the graph, even if it has no edges.
+    pub fn contains_vertex(&self, vertex: &V) -> bool {
+        self.hash_map.contains_key(vertex)
+    }
+    /// Gets the weight of the directed edge from `a` to `b`, or `None` if there is no such edge.
+    pub fn get_edge_weight(&self, a: &V, b: &V) -> Option<&E> {
+        self.hash_map.get(a)?.get(b)
+    }
+    pub fn vertex_count(&self) -> usize {
+        self.hash_map.len()
+    }
+    /// Counts the directed edges. Undirected edges count once in each direction, like in [`AdjacencyList::edges`].
+    pub fn edge_count_directed(&self) -> usize {
+        self.hash_map.values().map(BTreeMap::len).sum()
+    }
+    /// Counts the pairs of vertices with an edge in either direction between them, interpreting the graph as undirected.\
+    /// A self-loop counts once.
+    pub fn edge_count_undirected(&self) -> usize {
+        self.edges_iter()
+            // Count each pair from its smaller vertex, or from the larger one if there's no edge back
+            .filter(|(from, to, _)| from <= to || !self.is_neighbor(to, from))
+            .count()
+    }
+    /// Returns `true` if every edge has a reverse edge, so the graph can be treated as undirected.
+    /// The weights of the two directions aren't compared.
+    pub fn is_undirected(&self) -> bool {
+        self.hash_map.iter().all(|(vertex, edges)| {
+            edges
+                .keys()
+                .all(|neighbor| self.is_neighbor(neighbor, vertex))
+        })
+    }
+    /// Adds a reverse edge with the same weight for every edge that doesn't have one, e.g. after importing a one-directional edge list,
+    /// so that [`AdjacencyList::is_undirected`] holds afterwards.\
+    /// If both directions already exist with different weights, both weights are kept as they are.
+    pub fn make_undirected(&mut self) {
+        for (from, to, weight) in self.edges() {
+            if !self.is_neighbor(&to, &from) {
+                self.hash_map.entry(to).or_default().insert(from, weight);
+            }
+        }
+    }
+    /// Gets the vertices that both `a` and `b` have a directed edge to in ascending order, e.g. for predicting links between them.
+    pub fn common_neighbors(&self, a: &V, b: &V) -> Vec<V> {
+        self.ordered_neighbors(a)
+            .map(|(neighbor, _)| *neighbor)
+            .filter(|neighbor| self.is_neighbor(b, neighbor))
+            .collect()
+    }
+    /// Iterates over the vertices in ascending order.\
+    /// Algorithms that need deterministic results rely on this order, so it must hold even if the underlying map changes.
+    pub fn ordered_vertices(&self) -> impl Iterator<Item = &V> + '_ {
+        self.hash_map.keys()
+    }
+    /// Iterates over the neighbors of `vertex` and the weights of the edges leading to them in ascending order of the neighbors.\
+    /// Unlike [`AdjacencyList::get_neighbors`], a missing vertex just yields nothing, so there's no `Option` to match on.
+    pub fn ordered_neighbors(&self, vertex: &V) -> impl Iterator<Item = (&V, &E)> + '_ {
+        self.get_neighbors(vertex).into_iter().flatten()
+    }
+    /// Gets every directed edge as `(from, to, weight)`. Undirected edges appear once in each direction.
+    pub fn edges(&self) -> Vec<(V, V, E)> {
+        self.edges_iter()
+            .map(|(from, to, weight)| (*from, *to, weight.clone()))
+            .collect()
+    }
+    /// Iterates over every directed edge as `(from, to, weight)` in ascending order, without cloning the weights like [`AdjacencyList::edges`].
+    pub fn edges_iter(&self) -> impl Iterator<Item = (&V, &V, &E)> + '_ {
+        self.hash_map.iter().flat_map(|(vertex, edges)| {
+            edges
+                .iter()
+                .map(move |(neighbor, weight)| (vertex, neighbor, weight))
+        })
+    }
+    /// Creates a graph with the same vertices and edges where every edge is transformed with `f`,
+    /// e.g. to get the weights of edges that also have other data.
+    pub fn map_edges<E2: Clone>(&self, f: impl Fn(&E) -> E2) -> AdjacencyList<V, E2> {
+        AdjacencyList::new(
+            self.hash_map
+                .iter()
+                .map(|(vertex, edges)| {
+                    let edges = edges
+                        .iter()
+                        .map(|(neighbor, edge)| (*neighbor, f(edge)))
+                        .collect();
+                    (*vertex, edges)
+                })
+                .collect(),
+        )
+    }
+    /// Creates a graph with the same vertices where every edge points the other way, keeping its weight.
+    pub fn transpose(&self) -> Self {
+        let mut transposed = Self::new(
+            self.hash_map
+                .keys()
+                .map(|vertex| (*vertex, BTreeMap::new()))
+                .collect(),
+        );
+        for (from, to, weight) in self.edges() {
+            transposed
+                .hash_map
+                .entry(to)
+                .or_default()
+                .insert(from, weight);
+        }
+        transposed
+    }
+    /// Creates a graph with only the edges for which `keep(from, to, edge)` returns `true`.\
+    /// Every vertex is kept, even if all of its edges are removed.
+    pub fn filter_edges(&self, keep: impl Fn(&V, &V, &E) -> bool) -> Self {
+        Self::new(
+            self.hash_map
+                .iter()
+                .map(|(vertex, edges)| {
+                    let edges = edges
+                        .iter()
+                        .filter(|(neighbor, edge)| keep(vertex, neighbor, edge))
+                        .map(|(neighbor, edge)| (*neighbor, edge.clone()))
+                        .collect();
+                    (*vertex, edges)
+                })
+                .collect(),
+        )
+    }
+    /// Checks if every vertex in the `path` has an edge to the next vertex.\
+    /// An empty path is never valid, and a path with a single vertex is valid if the vertex exists.
+    pub fn is_valid_path(&self, path: &[V]) -> bool {
+        match path {
+            [] => false,
+            [vertex] => self.hash_map.contains_key(vertex),
+            _ => path.windows(2).all(|pair| {
+                self.get_neighbors(&pair[0])
+                    .is_some_and(|edges| edges.contains_key(&pair[1]))
+            }),
+        }
+    }
+}
+
+/// Determines how the weight of a new edge is combined with an existing edge between the same vertices.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParallelEdgePolicy {
+    /// Replace the old weight with the new one.
+    #[default]
+    Replace,
+    /// Keep the smaller weight.
+    KeepMin,
+    /// Keep the larger weight.
+    KeepMax,
+    /// Sum the weights.
+    Sum,
+}
+impl ParallelEdgePolicy {
+    /// Combines an existing weight with a new one according to the policy.
+    pub fn combine<E: PartialOrd + Add<Output = E>>(&self, old: E, new: E) -> E {
+        match self {
+            ParallelEdgePolicy::Replace => new,
+            ParallelEdgePolicy::KeepMin => {
+                if new < old {
+                    new
+                } else {
+                    old
+                }
+            }
+            ParallelEdgePolicy::KeepMax => {
+                if new > old {
+                    new
+                } else {
+                    old
+                }
+            }
+            ParallelEdgePolicy::Sum => old + new,
+        }
+    }
+}
+
+impl<V: Vertex, E: Clone + PartialOrd + Add<Output = E>> AdjacencyList<V, E> {
+    /// Adds a directed edge from `a` to `b`. If the edge already exists, the weights are combined according to the `policy`.\
+    /// Missing vertices are created like in [`AdjacencyList::add_edge_directed`].
+    pub fn add_edge_directed_with_policy(
+        &mut self,
+        a: V,
+        b: V,
+        weight: E,
+        policy: ParallelEdgePolicy,
+    ) {
+        let weight = match self.get_neighbors(&a).and_then(|edges| edges.get(&b)) {
+            Some(old) => policy.combine(old.clone(), weight),
+            None => weight,
+        };
+        self.add_edge_directed(a, b, weight);
+    }
+    /// Adds an undirected edge between `a` and `b`. If the edge already exists, the weights are combined according to the `policy`.\
+    /// The combined weight of the `a` to `b` edge is used for both directions.
+    pub fn add_edge_undirected_with_policy(
+        &mut self,
+        a: V,
+        b: V,
+        weight: E,
+        policy: ParallelEdgePolicy,
+    ) {
+        let weight = match self.get_neighbors(&a).and_then(|edges| edges.get(&b)) {
+            Some(old) => policy.combine(old.clone(), weight),
+            None => weight,
+        };
+        self.add_edge_undirected(a, b, weight);
+    }
+    /// Adds a directed edge from `a` to `b`, keeping the smaller weight if the edge already exists.
+    pub fn add_edge_directed_or_min(&mut self, a: V, b: V, weight: E) {
+        self.add_edge_directed_with_policy(a, b, weight, ParallelEdgePolicy::KeepMin);
+    }
+    /// Adds a directed edge from `a` to `b`, keeping the larger weight if the edge already exists.
+    pub fn add_edge_directed_or_max(&mut self, a: V, b: V, weight: E) {
+        self.add_edge_directed_with_policy(a, b, weight, ParallelEdgePolicy::KeepMax);
+    }
+    /// Adds a directed edge from `a` to `b`, summing the weights if the edge already exists.
+    pub fn add_edge_directed_or_sum(&mut self, a: V, b: V, weight: E) {
+        self.add_edge_directed_with_policy(a, b, weight, ParallelEdgePolicy::Sum);
+    }
+    /// Merges all vertices for which `key` returns the same key into the smallest one of them, e.g. to collapse equivalent vertices.\
+    /// The edges of the merged vertices are moved to the vertex they were merged into. If that gives it several edges to the same neighbor,
+    /// the smallest weight is kept like in [`AdjacencyList::add_edge_directed_or_min`], so the shortest paths through the merged vertices stay the same.
+    /// Edges between vertices that are merged together are removed, but existing self-loops are kept.
+    pub fn merge_vertices_by<K: Ord>(&mut self, key: impl Fn(&V) -> K) {
+        let mut representatives: BTreeMap<K, V> = BTreeMap::new();
+        let vertices: BTreeSet<V> = self
+            .edges()
+            .into_iter()
+            .map(|(_, neighbor, _)| neighbor)
+            .chain(self.ordered_vertices().copied())
+            .collect();
+        // Visiting the vertices in ascending order makes the smallest vertex with each key its representative
+        let merged_into: BTreeMap<V, V> = vertices
+            .into_iter()
+            .map(|vertex| {
+                (
+                    vertex,
+                    *representatives.entry(key(&vertex)).or_insert(vertex),
+                )
+            })
+            .collect();
+
+        for (vertex, edges) in std::mem::take(&mut self.hash_map) {
+            let from = merged_into[&vertex];
+            self.hash_map.entry(from).or_default();
+            for (neighbor, weight) in edges {
+                let to = merged_into[&neighbor];
+                if from != to || vertex == neighbor {
+                    self.add_edge_directed_or_min(from, to, weight);
+                }
+            }
+        }
+    }
+}
+
+/// Adding edges with a weight of one for unweighted graphs. The weight type must implement [`One`], which `f32` and `f64` do.
+impl<V: Vertex, E: Clone + One> AdjacencyList<V, E> {
+    /// Adds a directed edge from `a` to `b` with a weight of one, like [`AdjacencyList::add_edge_directed`].
+    pub fn add_edge_directed_unit(&mut self, a: V, b: V) {
+        self.add_edge_directed(a, b, E::one());
+    }
+    /// Adds an undirected edge between `a` and `b` with a weight of one, like [`AdjacencyList::add_edge_undirected`].
+    pub fn add_edge_undirected_unit(&mut self, a: V, b: V) {
+        self.add_edge_undirected(a, b, E::one());
+    }
+}
+
+/// The extremes of the edge weights for weight types with a total order, such as integers. `f32` and `f64` aren't [`Ord`].
+impl<V: Vertex, E: Clone + Ord> AdjacencyList<V, E> {
+    /// Gets the smallest edge weight of the graph, or `None` if there are no edges.
+    pub fn min_edge_weight(&self) -> Option<&E> {
+        self.hash_map.values().flat_map(BTreeMap::values).min()
+    }
+    /// Gets the largest edge weight of the graph, or `None` if there are no edges.
+    pub fn max_edge_weight(&self) -> Option<&E> {
+        self.hash_map.values().flat_map(BTreeMap::values).max()
+    }
+    /// Maps every edge weight linearly into `[0, 1]`, where the smallest weight becomes `0.0` and the largest becomes `1.0`.
+    /// Useful for visualizing the weights, e.g. as the thickness or the opacity of the edges.\
+    /// If all edges have the same weight, they all become `1.0` so that they stay visible.
+    pub fn normalized_weights(&self) -> AdjacencyList<V, f64>
+    where
+        E: ToPrimitive,
+    {
+        let to_f64 = |weight: &E| weight.to_f64().unwrap_or_default();
+        let (min, max) = match (self.min_edge_weight(), self.max_edge_weight()) {
+            (Some(min), Some(max)) => (to_f64(min), to_f64(max)),
+            _ => (0.0, 0.0),
+        };
+        self.map_edges(|weight| {
+            if max > min {
+                (to_f64(weight) - min) / (max - min)
+            } else {
+                1.0
+            }
+        })
+    }
+}
+
+impl<E: Edge> AdjacencyList<Coord, E> {
+    /// Creates a `width` x `height` grid graph where every cell is connected to its adjacent cells with undirected edges.
+    /// Horizontal and vertical edges cost 1 and diagonal edges cost sqrt(2).
+    ///
+    /// The `blocked` cells are left out completely, so they don't appear as vertices at all and no edges lead to them.
+    pub fn grid_with_obstacles(
+        width: usize,
+        height: usize,
+        diagonals: bool,
+        blocked: &BTreeSet<Coord>,
+    ) -> Self {
+        let mut graph = Self::new(BTreeMap::new());
+
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                let vertex = Coord::new(x, y);
+                if blocked.contains(&vertex) {
+                    continue;
+                }
+                // Make sure that cells surrounded by obstacles are still added
+                graph.hash_map.entry(vertex).or_default();
+
+                let mut neighbors = BTreeMap::<Coord, E>::new();
+                for coord in vertex.adjacent(diagonals) {
+                    if blocked.contains(&coord) {
+                        continue;
+                    }
+                    if coord.x >= 0
+                        && coord.x < width as isize
+                        && coord.y >= 0
+                        && coord.y < height as isize
+                    {
+                        let a_diff = (coord.x - vertex.x).abs();
+                        let b_diff = (coord.y - vertex.y).abs();
+                        if a_diff == 0 || b_diff == 0 {
+                            // Horizontal or vertical costs 1
+                            neighbors.insert(coord, E::from(1).unwrap());
+                        } else {
+                            // Diagonal costs sqrt(2)
+                            neighbors.insert(coord, E::from(2.0_f32.sqrt()).unwrap());
+                        }
+                    }
+                }
+                graph.add_vertex_with_undirected_edges(vertex, neighbors);
+            }
+        }
+
+        graph
+    }
+    /// Finds the vertex closest to `point`, e.g. the cell that was clicked, where each vertex is at its `(x, y)` coordinates.\
+    /// Ties go to the smallest vertex. This scans every vertex, which is fine for graphs small enough to draw.
+    pub fn nearest_vertex(&self, point: (f64, f64)) -> Option<Coord> {
+        let distance =
+            |vertex: &Coord| (vertex.x as f64 - point.0).hypot(vertex.y as f64 - point.1);
+        self.ordered_vertices().copied().reduce(|nearest, vertex| {
+            if distance(&vertex) < distance(&nearest) {
+                vertex
+            } else {
+                nearest
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(i: isize) -> Coord {
+        Coord::new(i, 0)
+    }
+
+    /// Finds the vertices that can be reached from `start`.
+    fn region(graph: &AdjacencyList<Coord, f32>, start: Coord) -> BTreeSet<Coord> {
+        let mut region = BTreeSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(vertex) = stack.pop() {
+            for neighbor in graph.hash_map[&vertex].keys() {
+                if region.insert(*neighbor) {
+                    stack.push(*neighbor);
+                }
+            }
+        }
+        region
+    }
+
+    #[test]
+    fn wall_splits_grid_into_two_regions() {
+        let wall = (0..3)
+            .map(|y| Coord::new(2, y))
+            .collect::<BTreeSet<Coord>>();
+
+        for diagonals in [false, true] {
+            let graph = AdjacencyList::<Coord, f32>::grid_with_obstacles(5, 3, diagonals, &wall);
+            assert_eq!(graph.hash_map.len(), 12);
+            assert!(wall.iter().all(|coord| !graph.hash_map.contains_key(coord)));
+
+            let left = region(&graph, Coord::new(0, 0));
+            let right = region(&graph, Coord::new(4, 0));
+            assert!(left.iter().all(|coord| coord.x < 2));
+            assert!(right.iter().all(|coord| coord.x > 2));
+            assert_eq!(left.len(), 6);
+            assert_eq!(right.len(), 6);
+        }
+    }
+
+    #[test]
+    fn parallel_edge_policies() {
+        let (a, b) = (Coord::new(0, 0), Coord::new(1, 0));
+        let expected = [
+            (ParallelEdgePolicy::Replace, 2.0),
+            (ParallelEdgePolicy::KeepMin, 2.0),
+            (ParallelEdgePolicy::KeepMax, 5.0),
+            (ParallelEdgePolicy::Sum, 7.0),
+        ];
+
+        for (policy, weight) in expected {
+            let mut graph = AdjacencyList::<Coord, f32>::default();
+            graph.add_edge_directed_with_policy(a, b, 5.0, policy);
+            graph.add_edge_directed_with_policy(a, b, 2.0, policy);
+            assert_eq!(graph.hash_map[&a].get(&b), Some(&weight), "{:?}", policy);
+            assert_eq!(graph.hash_map.values().map(BTreeMap::len).sum::<usize>(), 1);
+
+            let mut graph = AdjacencyList::<Coord, f32>::default();
+            graph.add_edge_undirected_with_policy(a, b, 5.0, policy);
+            graph.add_edge_undirected_with_policy(a, b, 2.0, policy);
+            assert_eq!(graph.hash_map[&a].get(&b), Some(&weight), "{:?}", policy);
+            assert_eq!(graph.hash_map[&b].get(&a), Some(&weight), "{:?}", policy);
+        }
+    }
+
+    #[test]
+    fn valid_and_broken_paths() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        let (a, b, c) = (Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0));
+        graph.add_edge_undirected(a, b, 1.0);
+        graph.add_edge_directed(b, c, 1.0);
+
+        assert!(graph.is_valid_path(&[a, b, c]));
+        assert!(graph.is_valid_path(&[c]));
+        assert!(!graph.is_valid_path(&[]));
+        assert!(!graph.is_valid_path(&[c, b]));
+        assert!(!graph.is_valid_path(&[a, c]));
+        assert!(!graph.is_valid_path(&[Coord::new(5, 5)]));
+
+        // Edits can break a path that used to be valid
+        graph.remove_vertex(&b);
+        assert!(!graph.is_valid_path(&[a, b, c]));
+    }
+
+    #[test]
+    fn retain_even_vertices_drops_odd_edges() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for i in 0..6 {
+            graph.add_edge_undirected(v(i), v(i + 1), 1.0);
+        }
+        for (a, b, weight) in [(0, 2, 2.0), (4, 0, 3.0), (3, 5, 4.0)] {
+            graph.add_edge_directed(v(a), v(b), weight);
+        }
+
+        graph.retain_vertices(|vertex| vertex.x % 2 == 0);
+
+        let edges = graph
+            .hash_map
+            .iter()
+            .flat_map(|(from, edges)| edges.iter().map(move |(to, weight)| (*from, *to, *weight)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            graph.hash_map.keys().copied().collect::<Vec<_>>(),
+            vec![v(0), v(2), v(4), v(6)]
+        );
+        assert_eq!(edges, vec![(v(0), v(2), 2.0), (v(4), v(0), 3.0)]);
+    }
+
+    #[test]
+    fn self_loop_counts_twice_in_degree() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_self_loop(v(0), 2.0);
+        assert_eq!(graph.degree_sequence(), vec![2]);
+
+        graph.add_edge_undirected(v(0), v(1), 1.0);
+        assert_eq!(graph.degree_sequence(), vec![3, 1]);
+
+        // Adding an undirected edge from a vertex to itself is the same as adding a self-loop
+        let mut other = AdjacencyList::<Coord, f32>::default();
+        other.add_edge_undirected(v(0), v(0), 2.0);
+        other.add_edge_undirected(v(0), v(1), 1.0);
+        assert_eq!(other, graph);
+    }
+
+    #[test]
+    fn self_loop_appears_once_in_edges() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_self_loop(v(0), 2.0);
+        graph.add_edge_undirected(v(0), v(1), 1.0);
+
+        assert_eq!(
+            graph.edges(),
+            vec![(v(0), v(0), 2.0), (v(0), v(1), 1.0), (v(1), v(0), 1.0)]
+        );
+        assert!(graph.hash_map[&v(0)].contains_key(&v(0)));
+    }
+
+    #[test]
+    fn edge_queries_and_counts() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(0), v(1), 1.0);
+        graph.add_edge_directed(v(1), v(2), 2.0);
+        graph.add_self_loop(v(2), 3.0);
+        graph.add_vertex(v(3));
+
+        assert!(graph.has_edge_undirected(&v(1), &v(0)));
+        assert!(!graph.has_edge_undirected(&v(1), &v(2)));
+        assert_eq!(graph.get_edge_weight(&v(1), &v(2)), Some(&2.0));
+        assert_eq!(graph.get_edge_weight(&v(2), &v(1)), None);
+        assert!(graph.contains_vertex(&v(3)));
+        assert!(!graph.contains_vertex(&v(4)));
+
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count_directed(), 4);
+        assert_eq!(graph.edge_count_undirected(), 3);
+        assert_eq!(graph.edges_iter().count(), graph.edges().len());
+
+        assert_eq!(graph.remove_edge_undirected(&v(0), &v(1)), Some(1.0));
+        assert_eq!(graph.edge_count_undirected(), 2);
+    }
+
+    #[test]
+    fn transpose_reverses_edges() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(v(0), v(1), 1.0);
+        graph.add_edge_undirected(v(1), v(2), 2.0);
+        graph.add_self_loop(v(3), 3.0);
+
+        let transposed = graph.transpose();
+        assert_eq!(
+            transposed.edges(),
+            vec![
+                (v(1), v(0), 1.0),
+                (v(1), v(2), 2.0),
+                (v(2), v(1), 2.0),
+                (v(3), v(3), 3.0)
+            ]
+        );
+        assert!(transposed.get_neighbors(&v(0)).unwrap().is_empty());
+        assert_eq!(transposed.transpose(), graph);
+    }
+
+    #[test]
+    fn make_undirected_adds_missing_reverse_edges() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edges_directed([
+            (v(0), v(1), 1.0),
+            (v(1), v(2), 2.0),
+            (v(2), v(1), 5.0),
+            (v(3), v(3), 3.0),
+        ]);
+        // An edge to a vertex without an entry of its own
+        graph.hash_map.get_mut(&v(0)).unwrap().insert(v(4), 4.0);
+        assert!(!graph.is_undirected());
+
+        graph.make_undirected();
+        assert!(graph.is_undirected());
+        assert_eq!(graph.hash_map[&v(1)][&v(0)], 1.0);
+        assert_eq!(graph.hash_map[&v(4)][&v(0)], 4.0);
+        // Both directions already existed, so their weights are kept
+        assert_eq!(graph.hash_map[&v(1)][&v(2)], 2.0);
+        assert_eq!(graph.hash_map[&v(2)][&v(1)], 5.0);
+        assert_eq!(graph.edges().len(), 7);
+    }
+
+    #[test]
+    fn clear_edges_keeps_vertices() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(0), v(1), 1.0);
+        graph.add_edge_directed(v(1), v(2), 2.0);
+        graph.add_self_loop(v(3), 3.0);
+        graph.add_vertex(v(4));
+
+        graph.clear_edges();
+        assert_eq!(graph.ordered_vertices().count(), 5);
+        assert!(graph.edges().is_empty());
+
+        graph.clear();
+        assert_eq!(graph, AdjacencyList::default());
+    }
+
+    #[test]
+    fn ordered_neighbors_of_present_and_missing_vertices() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(v(0), v(1), 2.0);
+        graph.add_edge_directed(v(0), v(2), 3.0);
+        graph.add_vertex(v(3));
+
+        let neighbors = graph
+            .ordered_neighbors(&v(0))
+            .map(|(neighbor, weight)| (*neighbor, *weight))
+            .collect::<Vec<_>>();
+        assert_eq!(neighbors, vec![(v(1), 2.0), (v(2), 3.0)]);
+        assert_eq!(graph.ordered_neighbors(&v(1)).count(), 0);
+        assert_eq!(graph.ordered_neighbors(&v(3)).count(), 0);
+        assert_eq!(graph.ordered_neighbors(&v(9)).count(), 0);
+    }
+
+    #[test]
+    fn common_neighbors_of_two_vertices() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edges_directed([
+            (v(0), v(2), 1.0),
+            (v(0), v(3), 1.0),
+            (v(0), v(4), 1.0),
+            (v(1), v(4), 1.0),
+            (v(1), v(2), 1.0),
+            (v(1), v(5), 1.0),
+            (v(3), v(0), 1.0),
+        ]);
+
+        assert!(graph.is_neighbor(&v(0), &v(3)));
+        assert!(!graph.is_neighbor(&v(0), &v(1)));
+        assert!(!graph.is_neighbor(&v(9), &v(0)));
+
+        assert_eq!(graph.common_neighbors(&v(0), &v(1)), vec![v(2), v(4)]);
+        assert_eq!(graph.common_neighbors(&v(1), &v(0)), vec![v(2), v(4)]);
+        // Edges pointing into `a` or `b` don't count
+        assert!(graph.common_neighbors(&v(3), &v(1)).is_empty());
+        assert!(graph.common_neighbors(&v(0), &v(9)).is_empty());
+    }
+
+    #[test]
+    fn vertices_and_neighbors_are_ordered() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        let (a, b, c) = (Coord::new(0, 1), Coord::new(0, 0), Coord::new(-1, 2));
+        for (from, to) in [(a, v(3)), (b, v(-2)), (c, a), (a, v(-1)), (a, b), (b, c)] {
+            graph.add_edge_directed(from, to, 1.0);
+        }
+
+        let vertices = graph.ordered_vertices().copied().collect::<Vec<_>>();
+        assert_eq!(vertices.len(), 6);
+        assert!(vertices.windows(2).all(|pair| pair[0] < pair[1]));
+
+        for vertex in vertices {
+            let neighbors = graph
+                .ordered_neighbors(&vertex)
+                .map(|(neighbor, _)| *neighbor)
+                .collect::<Vec<_>>();
+            assert!(neighbors.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+        assert_eq!(
+            graph
+                .ordered_neighbors(&a)
+                .map(|(neighbor, _)| *neighbor)
+                .collect::<Vec<_>>(),
+            vec![v(-1), b, v(3)]
+        );
+        assert_eq!(graph.ordered_neighbors(&Coord::new(9, 9)).count(), 0);
+    }
+
+    #[test]
+    fn merge_vertices_combines_neighbors() {
+        // 1 and 2 are merged into 1 and both lead to 3, 4 and 5 are merged into 4
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(0), v(1), 2.0);
+        graph.add_edge_undirected(v(0), v(2), 1.0);
+        graph.add_edge_undirected(v(1), v(3), 5.0);
+        graph.add_edge_undirected(v(2), v(3), 4.0);
+        graph.add_edge_undirected(v(1), v(2), 1.0);
+        graph.add_edge_directed(v(5), v(6), 3.0);
+        graph.add_self_loop(v(4), 1.0);
+
+        graph.merge_vertices_by(|vertex| match vertex.x {
+            2 => 1,
+            5 => 4,
+            x => x,
+        });
+
+        assert_eq!(
+            graph.ordered_vertices().copied().collect::<Vec<Coord>>(),
+            vec![v(0), v(1), v(3), v(4), v(6)]
+        );
+        // The smaller weights of the parallel edges are kept and the edge between 1 and 2 is removed
+        assert_eq!(
+            graph.get_neighbors(&v(1)),
+            Some(&BTreeMap::from([(v(0), 1.0), (v(3), 4.0)]))
+        );
+        assert_eq!(
+            graph.get_neighbors(&v(0)),
+            Some(&BTreeMap::from([(v(1), 1.0)]))
+        );
+        assert_eq!(
+            graph.get_neighbors(&v(4)),
+            Some(&BTreeMap::from([(v(4), 1.0), (v(6), 3.0)]))
+        );
+    }
+
+    #[test]
+    fn map_edges_projects_metadata_to_weights() {
+        #[derive(Clone, Debug, PartialEq)]
+        struct Road {
+            dist: f32,
+            name: &'static str,
+        }
+        let road = |dist, name| Road { dist, name };
+
+        let mut roads = AdjacencyList::<Coord, Road>::new(BTreeMap::new());
+        roads.add_edge_undirected(v(0), v(1), road(2.0, "Main Street"));
+        roads.add_edge_directed(v(1), v(2), road(3.5, "One Way"));
+        roads.add_vertex(v(3));
+
+        let distances = roads.map_edges(|road| road.dist);
+        let mut expected = AdjacencyList::<Coord, f32>::default();
+        expected.add_edge_undirected(v(0), v(1), 2.0);
+        expected.add_edge_directed(v(1), v(2), 3.5);
+        expected.add_vertex(v(3));
+        assert_eq!(distances, expected);
+
+        let names = roads.map_edges(|road| road.name);
+        assert_eq!(names.hash_map[&v(1)][&v(2)], "One Way");
+    }
+
+    #[test]
+    fn filter_edges_by_weight() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(0), v(1), 20.0);
+        graph.add_edge_undirected(v(1), v(2), 80.0);
+        graph.add_edge_directed(v(2), v(3), 49.0);
+        graph.add_edge_directed(v(3), v(4), 50.0);
+
+        let short = graph.filter_edges(|_, _, weight| *weight < 50.0);
+        let mut expected = AdjacencyList::<Coord, f32>::default();
+        expected.add_edge_undirected(v(0), v(1), 20.0);
+        expected.add_edge_directed(v(2), v(3), 49.0);
+        expected.add_vertex(v(4));
+        assert_eq!(short, expected);
+
+        // The predicate also gets the vertices, so one direction of an undirected edge can be dropped
+        let forward = graph.filter_edges(|from, to, _| from < to);
+        assert!(forward.hash_map[&v(0)].contains_key(&v(1)));
+        assert!(!forward.hash_map[&v(1)].contains_key(&v(0)));
+    }
+
+    #[test]
+    fn unit_edges_have_weight_one() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed_unit(v(0), v(1));
+        graph.add_edge_undirected_unit(v(1), v(2));
+        graph.add_edge_undirected_unit(v(3), v(3));
+
+        assert_eq!(graph.edges().len(), 4);
+        assert!(graph.edges().iter().all(|(_, _, weight)| *weight == 1.0));
+        assert!(graph.hash_map[&v(2)].contains_key(&v(1)));
+        assert!(!graph.hash_map[&v(1)].contains_key(&v(0)));
+    }
+
+    #[test]
+    fn min_and_max_edge_weights() {
+        let mut graph = AdjacencyList::<Coord, i32>::default();
+        graph.add_vertex(v(5));
+        assert_eq!(graph.min_edge_weight(), None);
+        assert_eq!(graph.max_edge_weight(), None);
+
+        graph.add_edge_directed(v(0), v(1), 7);
+        graph.add_edge_undirected(v(1), v(2), -3);
+        graph.add_edge_directed(v(2), v(3), 12);
+        graph.add_edge_directed(v(3), v(0), 4);
+
+        assert_eq!(graph.min_edge_weight(), Some(&-3));
+        assert_eq!(graph.max_edge_weight(), Some(&12));
+    }
+
+    #[test]
+    fn normalized_weights_span_zero_to_one() {
+        let mut graph = AdjacencyList::<Coord, i32>::default();
+        graph.add_edge_directed(v(0), v(1), 10);
+        graph.add_edge_directed(v(1), v(2), 20);
+        graph.add_edge_directed(v(2), v(3), 50);
+        graph.add_vertex(v(4));
+
+        let normalized = graph.normalized_weights();
+        assert_eq!(normalized.hash_map[&v(0)][&v(1)], 0.0);
+        assert_eq!(normalized.hash_map[&v(1)][&v(2)], 0.25);
+        assert_eq!(normalized.hash_map[&v(2)][&v(3)], 1.0);
+        assert!(normalized.hash_map[&v(4)].is_empty());
+
+        let mut equal = AdjacencyList::<Coord, i32>::default();
+        equal.add_edge_undirected(v(0), v(1), 3);
+        assert!(equal
+            .normalized_weights()
+            .edges()
+            .iter()
+            .all(|(_, _, weight)| *weight == 1.0));
+    }
+
+    #[test]
+    fn nearest_vertex_to_a_click() {
+        let blocked = BTreeSet::from([Coord::new(2, 2)]);
+        let graph = AdjacencyList::<Coord, f32>::grid_with_obstacles(4, 4, false, &blocked);
+
+        // Just off the corner where the cells (1, 1), (2, 1), (1, 2) and (2, 2) meet
+        assert_eq!(graph.nearest_vertex((1.55, 1.45)), Some(Coord::new(2, 1)));
+        assert_eq!(graph.nearest_vertex((1.45, 1.55)), Some(Coord::new(1, 2)));
+        // Exactly on the corner, the smallest cell wins
+        assert_eq!(graph.nearest_vertex((1.5, 1.5)), Some(Coord::new(1, 1)));
+        // The blocked cell isn't a vertex, so a click on it selects one of its neighbors
+        assert_eq!(graph.nearest_vertex((2.0, 2.1)), Some(Coord::new(2, 3)));
+        // Clicks outside the grid select the closest cell on the edge
+        assert_eq!(graph.nearest_vertex((10.0, -3.0)), Some(Coord::new(3, 0)));
+        assert_eq!(
+            AdjacencyList::<Coord, f32>::default().nearest_vertex((0.0, 0.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn batch_edges_match_single_adds() {
+        // Includes a self-loop and an edge that is added twice, so the later weight wins
+        let edges = [
+            (v(0), v(1), 1.0),
+            (v(1), v(2), 2.0),
+            (v(3), v(3), 3.0),
+            (v(0), v(1), 4.0),
+            (v(2), v(0), 5.0),
+        ];
+
+        let mut directed = AdjacencyList::<Coord, f32>::default();
+        let mut undirected = AdjacencyList::<Coord, f32>::default();
+        for (a, b, weight) in edges {
+            directed.add_edge_directed(a, b, weight);
+            undirected.add_edge_undirected(a, b, weight);
+        }
+
+        let mut batch = AdjacencyList::<Coord, f32>::default();
+        batch.add_edges_directed(edges);
+        assert_eq!(batch, directed);
+
+        let mut batch = AdjacencyList::<Coord, f32>::default();
+        batch.add_edges_undirected(edges.to_vec());
+        assert_eq!(batch, undirected);
+        assert_eq!(batch.hash_map[&v(1)].get(&v(0)), Some(&4.0));
+    }
+}
@@ -0,0 +1,977 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{AdjacencyList, CycleError, NegativeCycle};
+use crate::{
+    pathfinding_algorithms::{BinaryHeapFrontier, PriorityFrontier},
+    Edge, Vertex,
+};
+
+/// The cheapest paths from one vertex to every vertex that can be reached from it, found by [`AdjacencyList::bellman_ford`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShortestPathTree<V: Vertex, E: Edge> {
+    pub start: V,
+    /// The cost of the cheapest path to every reached vertex and the vertex before it on the path.
+    pub paths: BTreeMap<V, (E, Option<V>)>,
+    /// The edges that made a path cheaper in each relaxation pass as `(from, to, new_cost)`, e.g. for animating the passes.
+    pub passes: Vec<Vec<(V, V, E)>>,
+}
+impl<V: Vertex, E: Edge> ShortestPathTree<V, E> {
+    /// Gets the cost of the cheapest path to `goal`, or `None` if it can't be reached.
+    pub fn cost(&self, goal: &V) -> Option<E> {
+        self.paths.get(goal).map(|(cost, _)| *cost)
+    }
+    /// Gets the cheapest path from the start to `goal`, or `None` if it can't be reached.
+    pub fn path(&self, goal: V) -> Option<Vec<V>> {
+        let mut path = vec![goal];
+        let mut vertex = goal;
+        while let (_, Some(previous)) = self.paths.get(&vertex)? {
+            vertex = *previous;
+            path.push(vertex);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// A cell of the distance matrix of [`AdjacencyList::floyd_warshall`] that got cheaper, as the rows and columns of the matrices.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DistanceUpdate<E: Edge> {
+    pub from: usize,
+    pub to: usize,
+    /// The distance before the update, or `None` if `to` couldn't be reached from `from` yet.
+    pub previous: Option<E>,
+    pub distance: E,
+}
+
+/// The cheapest paths between every pair of vertices, found by [`AdjacencyList::floyd_warshall`].\
+/// The matrices are indexed as `[from][to]` by the positions of the vertices in [`AllPairsShortestPaths::vertices`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllPairsShortestPaths<V: Vertex, E: Edge> {
+    /// The vertices in ascending order.
+    pub vertices: Vec<V>,
+    /// The cost of the cheapest path between every pair of vertices, or `None` if there is no path.
+    pub distances: Vec<Vec<Option<E>>>,
+    /// The vertex before `to` on the cheapest path from `from` to `to`, or `None` if there is no path or `from` is `to`.
+    pub predecessors: Vec<Vec<Option<usize>>>,
+    /// The cells that got cheaper in each pass, where the pass at index `k` allows paths through the `k`th vertex.
+    pub passes: Vec<Vec<DistanceUpdate<E>>>,
+}
+impl<V: Vertex, E: Edge> AllPairsShortestPaths<V, E> {
+    /// Gets the row and column of `vertex`, or `None` if it doesn't exist.
+    pub fn index_of(&self, vertex: &V) -> Option<usize> {
+        self.vertices.binary_search(vertex).ok()
+    }
+    /// Gets the cost of the cheapest path from `from` to `to`, or `None` if there is no path.
+    pub fn cost(&self, from: &V, to: &V) -> Option<E> {
+        self.distances[self.index_of(from)?][self.index_of(to)?]
+    }
+    /// Gets the cheapest path from `from` to `to`, or `None` if there is no path.
+    pub fn path(&self, from: &V, to: &V) -> Option<Vec<V>> {
+        let (from, mut to) = (self.index_of(from)?, self.index_of(to)?);
+        self.distances[from][to]?;
+
+        let mut path = vec![self.vertices[to]];
+        while let Some(previous) = self.predecessors[from][to] {
+            to = previous;
+            path.push(self.vertices[to]);
+        }
+        path.reverse();
+        Some(path)
+    }
+    /// Gets the distance matrix as it was after the first `pass_count` passes, e.g. for animating how the distances get cheaper.\
+    /// With no passes, the matrix only has the edges of the graph.
+    pub fn distances_after(&self, pass_count: usize) -> Vec<Vec<Option<E>>> {
+        let mut distances = self.distances.clone();
+        for update in self
+            .passes
+            .iter()
+            .skip(pass_count)
+            .rev()
+            .flat_map(|pass| pass.iter().rev())
+        {
+            distances[update.from][update.to] = update.previous;
+        }
+        distances
+    }
+}
+
+/// The cheapest known path to every vertex as `(cost, previous vertex, number of edges in the path)`.
+type HopLayer<V, E> = BTreeMap<V, (E, Option<V>, usize)>;
+
+impl<V: Vertex, E: Edge> AdjacencyList<V, E> {
+    /// Finds the cheapest path from `start` to `goal` that uses at most `max_hops` edges and returns it along with its cost.\
+    /// This is Bellman-Ford where the number of relaxation rounds is capped at `max_hops`, so it also works with negative weights.
+    ///
+    /// Returns `None` if the goal can't be reached within the hop limit, even if a longer path exists.
+    pub fn shortest_path_with_hop_limit(
+        &self,
+        start: V,
+        goal: V,
+        max_hops: usize,
+    ) -> Option<(Vec<V>, E)> {
+        if !self.hash_map.contains_key(&start) {
+            return None;
+        }
+
+        // `layers[k]` only has paths that use at most `k` edges
+        let mut layers: Vec<HopLayer<V, E>> = vec![BTreeMap::from([(start, (E::zero(), None, 0))])];
+
+        for hops in 1..=max_hops {
+            let previous = &layers[hops - 1];
+            let mut layer = previous.clone();
+
+            for (vertex, (cost, _, _)) in previous.iter() {
+                for (neighbor, weight) in self.ordered_neighbors(vertex) {
+                    let new_cost = *cost + *weight;
+                    if layer
+                        .get(neighbor)
+                        .is_none_or(|(old_cost, _, _)| new_cost < *old_cost)
+                    {
+                        layer.insert(*neighbor, (new_cost, Some(*vertex), hops));
+                    }
+                }
+            }
+
+            let unchanged = layer == *previous;
+            layers.push(layer);
+            if unchanged {
+                break;
+            }
+        }
+
+        let (cost, _, _) = *layers.last()?.get(&goal)?;
+
+        // Walk backwards through the layers, since the previous vertex was reached with one edge less
+        let mut path = vec![goal];
+        let mut vertex = goal;
+        let mut layer = layers.len() - 1;
+        while let Some((_, Some(previous), hops)) = layers[layer].get(&vertex) {
+            vertex = *previous;
+            layer = hops - 1;
+            path.push(vertex);
+        }
+        path.reverse();
+
+        Some((path, cost))
+    }
+    /// Finds the shortest distance from `start` to every vertex reachable from it with a single run of Dijkstra.\
+    /// This is much cheaper than searching for each vertex separately, e.g. for coloring a whole grid by distance.
+    /// Unreachable vertices are left out, and the weights must not be negative.
+    pub fn dijkstra_all(&self, start: V) -> BTreeMap<V, E> {
+        self.dijkstra_tree(
+            start,
+            None,
+            |distance, weight| distance + weight,
+            |_, _| true,
+        )
+        .into_iter()
+        .map(|(vertex, (distance, _))| (vertex, distance))
+        .collect()
+    }
+    /// Finds the shortest distance between every pair of vertices with Johnson's algorithm, which also works with negative weights.\
+    /// The key of each distance is `(from, to)`, and pairs where `to` can't be reached from `from` are left out.
+    ///
+    /// Bellman-Ford first finds a potential for every vertex, such that adding the difference of the potentials to each edge makes all weights non-negative
+    /// without changing which paths are the shortest. Then Dijkstra runs from every vertex on the reweighted graph, which is much faster than Floyd-Warshall
+    /// for sparse graphs. Returns a [`NegativeCycle`] if the graph has one, since then the shortest paths aren't defined.
+    pub fn johnsons(&self) -> Result<BTreeMap<(V, V), E>, NegativeCycle<V>> {
+        let edges = self.edges();
+        let vertices: BTreeSet<V> = edges
+            .iter()
+            .map(|(_, to, _)| *to)
+            .chain(self.ordered_vertices().copied())
+            .collect();
+
+        // Bellman-Ford from a virtual vertex with an edge of weight zero to every vertex, so every potential starts at zero.
+        // With the virtual vertex there are `vertices.len() + 1` vertices, so the potentials are final after `vertices.len()` rounds.
+        let mut potentials: BTreeMap<V, E> =
+            vertices.iter().map(|vertex| (*vertex, E::zero())).collect();
+        // The vertex whose potential was lowered last by each vertex, where the virtual vertex is left out
+        let mut previous = BTreeMap::new();
+        for round in 0..=vertices.len() {
+            let mut relaxed = None;
+            for (from, to, weight) in &edges {
+                let potential = potentials[from] + *weight;
+                if potential < potentials[to] {
+                    potentials.insert(*to, potential);
+                    previous.insert(*to, *from);
+                    relaxed = Some(*to);
+                }
+            }
+            let Some(relaxed) = relaxed else {
+                break;
+            };
+            // Anything that can still be relaxed after all rounds has a path through a negative cycle
+            if round == vertices.len() {
+                let cycle = walk_to_cycle(
+                    |vertex| previous.get(&vertex).copied().unwrap_or(vertex),
+                    relaxed,
+                    vertices.len(),
+                );
+                return Err(NegativeCycle { cycle });
+            }
+        }
+
+        let mut reweighted = AdjacencyList::new(BTreeMap::new());
+        for vertex in &vertices {
+            reweighted.add_vertex(*vertex);
+        }
+        for (from, to, weight) in edges {
+            // Rounding errors could leave a tiny negative weight, which Dijkstra can't handle
+            let weight = (weight + potentials[&from] - potentials[&to]).max(E::zero());
+            reweighted.add_edge_directed(from, to, weight);
+        }
+
+        let mut distances = BTreeMap::new();
+        for from in &vertices {
+            for (to, distance) in reweighted.dijkstra_all(*from) {
+                distances.insert((*from, to), distance - potentials[from] + potentials[&to]);
+            }
+        }
+
+        Ok(distances)
+    }
+    /// Finds a path from `start` to `goal` whose largest edge weight is as small as possible, and returns it along with that weight.\
+    /// This is the minimax problem: the total cost doesn't matter, only the worst edge on the way, like the narrowest link of a network.
+    /// It's Dijkstra where the key of a path is its largest edge instead of the sum of its edges.
+    ///
+    /// Returns `None` if the goal can't be reached. A path from a vertex to itself has no edges, so its bottleneck is zero.
+    pub fn minimum_bottleneck_path(&self, start: V, goal: V) -> Option<(Vec<V>, E)> {
+        let bottlenecks = self.dijkstra_tree(
+            start,
+            Some(goal),
+            |bottleneck, weight| weight.max(bottleneck),
+            |_, _| true,
+        );
+        tree_path(&bottlenecks, goal)
+    }
+    /// Finds the best backup route from `start` to `goal`: the cheapest path without repeated vertices that differs from the shortest path,
+    /// and returns it along with its cost. This is Yen's algorithm for the two shortest paths, so the weights must not be negative.\
+    /// If another path is exactly as cheap as the shortest one, that path is returned, so the cost can be equal to the optimal cost.
+    ///
+    /// Returns `None` if the goal can't be reached or if the shortest path is the only one.
+    pub fn second_shortest_path(&self, start: V, goal: V) -> Option<(Vec<V>, E)> {
+        let (shortest, _) = self.shortest_path_avoiding(start, goal, &BTreeSet::new(), None)?;
+        let mut best: Option<(Vec<V>, E)> = None;
+
+        // Branch off of the shortest path at every vertex by forbidding the edge that it takes next
+        for spur_index in 0..shortest.len() - 1 {
+            let root = &shortest[..=spur_index];
+            let spur = shortest[spur_index];
+            // The root vertices before the spur can't be used again, or the path would have a loop
+            let blocked = root[..spur_index].iter().copied().collect::<BTreeSet<V>>();
+            let forbidden_edge = (spur, shortest[spur_index + 1]);
+
+            let Some((spur_path, spur_cost)) =
+                self.shortest_path_avoiding(spur, goal, &blocked, Some(forbidden_edge))
+            else {
+                continue;
+            };
+            let root_cost = root.windows(2).fold(E::zero(), |cost, pair| {
+                cost + self.hash_map[&pair[0]][&pair[1]]
+            });
+            let cost = root_cost + spur_cost;
+
+            if best.as_ref().is_none_or(|(_, best_cost)| cost < *best_cost) {
+                let mut path = root[..spur_index].to_vec();
+                path.extend(spur_path);
+                best = Some((path, cost));
+            }
+        }
+
+        best
+    }
+    /// Finds the shortest path from `start` to `goal` with Dijkstra without entering the `blocked` vertices or using the `forbidden_edge`.
+    fn shortest_path_avoiding(
+        &self,
+        start: V,
+        goal: V,
+        blocked: &BTreeSet<V>,
+        forbidden_edge: Option<(V, V)>,
+    ) -> Option<(Vec<V>, E)> {
+        let distances = self.dijkstra_tree(
+            start,
+            Some(goal),
+            |distance, weight| distance + weight,
+            |from, to| !blocked.contains(&to) && forbidden_edge != Some((from, to)),
+        );
+        tree_path(&distances, goal)
+    }
+    /// Runs Dijkstra from `start` and returns the best key of the path to every reached vertex along with the vertex before it on the path.
+    /// The search stops once `goal` is finished, or goes through every reachable vertex without one.
+    ///
+    /// `extend` gives the key of a path that is extended by an edge of the given weight, like the sum of the weights for the cheapest paths.
+    /// It must never make the key smaller. Edges for which `allow_edge(from, to)` returns `false` are never used.
+    fn dijkstra_tree(
+        &self,
+        start: V,
+        goal: Option<V>,
+        extend: impl Fn(E, E) -> E,
+        allow_edge: impl Fn(V, V) -> bool,
+    ) -> PathTree<V, E> {
+        let mut tree = BTreeMap::new();
+        if !self.hash_map.contains_key(&start) {
+            return tree;
+        }
+
+        tree.insert(start, (E::zero(), None));
+        let mut finished = BTreeSet::new();
+        let mut to_visit = BinaryHeapFrontier::default();
+        to_visit.push(start, E::zero());
+
+        while let Some((vertex, key)) = to_visit.pop() {
+            // Only the first time a vertex is popped has its final key
+            if !finished.insert(vertex) {
+                continue;
+            }
+            if Some(vertex) == goal {
+                break;
+            }
+
+            for (neighbor, weight) in self.ordered_neighbors(&vertex) {
+                if !allow_edge(vertex, *neighbor) {
+                    continue;
+                }
+                let new_key = extend(key, *weight);
+                if tree
+                    .get(neighbor)
+                    .is_none_or(|(old_key, _)| new_key < *old_key)
+                {
+                    tree.insert(*neighbor, (new_key, Some(vertex)));
+                    to_visit.push(*neighbor, new_key);
+                }
+            }
+        }
+
+        tree
+    }
+    /// Finds the cheapest path from `start` to every vertex reachable from it with Bellman-Ford, which also works with negative weights.\
+    /// Every pass relaxes the edges out of every reached vertex, and the edges that made a path cheaper are recorded for the pass.
+    ///
+    /// The paths are final once a pass changes nothing, which happens within one pass per vertex.
+    /// If they still get cheaper after that, there is a negative cycle, so the shortest paths aren't defined and the cycle is returned instead.
+    pub fn bellman_ford(&self, start: V) -> Result<ShortestPathTree<V, E>, NegativeCycle<V>> {
+        let mut tree = ShortestPathTree {
+            start,
+            paths: BTreeMap::new(),
+            passes: vec![],
+        };
+        if !self.contains_vertex(&start) {
+            return Ok(tree);
+        }
+        tree.paths.insert(start, (E::zero(), None));
+        // Edges may lead to vertices without their own entry, which still count towards the passes that are needed
+        let vertex_count = self.vertices_and_targets().len();
+
+        for _ in 0..vertex_count {
+            let mut relaxed = vec![];
+            for (vertex, (cost, _)) in tree.paths.clone() {
+                for (neighbor, weight) in self.ordered_neighbors(&vertex) {
+                    let new_cost = cost + *weight;
+                    if tree
+                        .paths
+                        .get(neighbor)
+                        .is_none_or(|(current, _)| new_cost < *current)
+                    {
+                        tree.paths.insert(*neighbor, (new_cost, Some(vertex)));
+                        relaxed.push((vertex, *neighbor, new_cost));
+                    }
+                }
+            }
+            if relaxed.is_empty() {
+                return Ok(tree);
+            }
+            tree.passes.push(relaxed);
+        }
+
+        // Every vertex that was relaxed in the last pass has a path through the cycle.
+        // Only the start has no previous vertex, and it's on the cycle if it was relaxed, so the walk never leaves the tree.
+        let relaxed = tree.passes.last().and_then(|pass| pass.last()).unwrap().1;
+        let cycle = walk_to_cycle(
+            |vertex| tree.paths[&vertex].1.unwrap_or(vertex),
+            relaxed,
+            vertex_count,
+        );
+
+        Err(NegativeCycle { cycle })
+    }
+    /// Finds the cheapest paths between every pair of vertices with Floyd-Warshall, which also works with negative weights.\
+    /// The distance matrix starts with the edges of the graph. Every pass allows the paths to go through one more vertex,
+    /// and a path gets cheaper if going through the new vertex costs less. The updated cells are recorded for each pass.
+    ///
+    /// This takes cubic time in the number of vertices, so it's best for small and dense graphs,
+    /// and [`AdjacencyList::johnsons`] is faster for sparse ones.
+    /// If a vertex has a negative distance to itself, it's on a negative cycle, which is returned instead.
+    pub fn floyd_warshall(&self) -> Result<AllPairsShortestPaths<V, E>, NegativeCycle<V>> {
+        let vertices = self.vertices_and_targets().into_iter().collect::<Vec<V>>();
+        let n = vertices.len();
+        let mut distances = vec![vec![None; n]; n];
+        let mut predecessors = vec![vec![None; n]; n];
+
+        for (from, vertex) in vertices.iter().enumerate() {
+            distances[from][from] = Some(E::zero());
+            for (neighbor, weight) in self.ordered_neighbors(vertex) {
+                let Ok(to) = vertices.binary_search(neighbor) else {
+                    continue;
+                };
+                // A self-loop is only used if it's negative
+                if from != to || *weight < E::zero() {
+                    distances[from][to] = Some(*weight);
+                }
+                if from != to {
+                    predecessors[from][to] = Some(from);
+                }
+            }
+        }
+
+        let mut passes = Vec::with_capacity(n);
+        for via in 0..n {
+            let mut updates = vec![];
+            for from in 0..n {
+                let first = match distances[from][via] {
+                    Some(first) => first,
+                    None => continue,
+                };
+                for to in 0..n {
+                    if let Some(second) = distances[via][to] {
+                        let distance = first + second;
+                        let previous = distances[from][to];
+                        if previous.is_none_or(|previous| distance < previous) {
+                            distances[from][to] = Some(distance);
+                            predecessors[from][to] = predecessors[via][to];
+                            updates.push(DistanceUpdate {
+                                from,
+                                to,
+                                previous,
+                                distance,
+                            });
+                        }
+                    }
+                }
+            }
+            passes.push(updates);
+        }
+
+        // A vertex with a negative distance to itself is on a negative cycle, so Bellman-Ford from it finds the cycle
+        for vertex in
+            (0..n).filter(|i| distances[*i][*i].is_some_and(|distance| distance < E::zero()))
+        {
+            self.bellman_ford(vertices[vertex])?;
+        }
+
+        Ok(AllPairsShortestPaths {
+            vertices,
+            distances,
+            predecessors,
+            passes,
+        })
+    }
+    /// Finds the length of the longest path from `start` to every vertex reachable from it, processing the vertices in topological order.\
+    /// Longest paths are only well defined without cycles, so an error is returned if the graph has one.
+    pub fn longest_path_dag(&self, start: V) -> Result<BTreeMap<V, E>, CycleError<V>> {
+        Ok(self
+            .longest_path_tree(&[start])?
+            .into_iter()
+            .map(|(vertex, (distance, _))| (vertex, distance))
+            .collect())
+    }
+    /// Finds the critical path of a task graph, where the edge weights are the durations of the tasks.\
+    /// This is the longest path through the whole graph, and any delay on it delays the entire project.
+    /// Returns an error if the graph has a cycle, and an empty path if the graph is empty.
+    pub fn critical_path(&self) -> Result<Vec<V>, CycleError<V>> {
+        let starts = self.hash_map.keys().copied().collect::<Vec<V>>();
+        let tree = self.longest_path_tree(&starts)?;
+
+        // The first vertex with the longest distance wins ties
+        let mut end = None;
+        for (vertex, (distance, _)) in tree.iter() {
+            if end.is_none_or(|(_, longest)| *distance > longest) {
+                end = Some((*vertex, *distance));
+            }
+        }
+
+        let mut path = vec![];
+        let mut vertex = end.map(|(vertex, _)| vertex);
+        while let Some(current) = vertex {
+            path.push(current);
+            vertex = tree[&current].1;
+        }
+        path.reverse();
+
+        Ok(path)
+    }
+    /// Finds the longest path from any of the `starts` to every vertex reachable from them as `(distance, previous vertex)`.
+    fn longest_path_tree(&self, starts: &[V]) -> Result<PathTree<V, E>, CycleError<V>> {
+        let order = self.topological_sort()?;
+        let mut tree = starts
+            .iter()
+            .filter(|start| self.hash_map.contains_key(start))
+            .map(|start| (*start, (E::zero(), None)))
+            .collect::<PathTree<V, E>>();
+
+        for vertex in order {
+            // Vertices before the starts in the order or not reachable from them are skipped
+            let Some((distance, _)) = tree.get(&vertex).copied() else {
+                continue;
+            };
+            for (neighbor, weight) in self.ordered_neighbors(&vertex) {
+                let new_distance = distance + *weight;
+                if tree
+                    .get(neighbor)
+                    .is_none_or(|(old_distance, _)| new_distance > *old_distance)
+                {
+                    tree.insert(*neighbor, (new_distance, Some(vertex)));
+                }
+            }
+        }
+
+        Ok(tree)
+    }
+    /// Gets every vertex along with every vertex that an edge leads to, since an edge can lead to a vertex that was never added.
+    fn vertices_and_targets(&self) -> BTreeSet<V> {
+        self.hash_map
+            .iter()
+            .flat_map(|(vertex, edges)| std::iter::once(vertex).chain(edges.keys()))
+            .copied()
+            .collect()
+    }
+}
+
+/// The best path to every reached vertex as `(key, previous vertex)`, like the cost of the path or its longest distance.
+type PathTree<V, E> = BTreeMap<V, (E, Option<V>)>;
+
+/// Follows the previous vertices in the `tree` back from `goal`, and returns the path along with the key of the goal.
+fn tree_path<V: Vertex, E: Copy>(tree: &PathTree<V, E>, goal: V) -> Option<(Vec<V>, E)> {
+    let (key, _) = *tree.get(&goal)?;
+    let mut path = vec![goal];
+    while let Some((_, Some(previous))) = tree.get(path.last().unwrap()) {
+        path.push(*previous);
+    }
+    path.reverse();
+
+    Some((path, key))
+}
+
+/// Finds the negative cycle that `relaxed` has a path through, given the `previous` vertex of every vertex on the path.\
+/// The relaxed vertex may only lead to the cycle, but going back once for each of the `vertex_count` vertices is sure to end up on it.
+fn walk_to_cycle<V: Vertex>(previous: impl Fn(V) -> V, relaxed: V, vertex_count: usize) -> Vec<V> {
+    let mut vertex = relaxed;
+    for _ in 0..vertex_count {
+        vertex = previous(vertex);
+    }
+
+    let mut cycle = vec![vertex];
+    let mut current = previous(vertex);
+    while current != vertex {
+        cycle.push(current);
+        current = previous(current);
+    }
+    cycle.reverse();
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    fn v(i: isize) -> Coord {
+        Coord::new(i, 0)
+    }
+
+    #[test]
+    fn hop_limit_prefers_costlier_short_path() {
+        // 0 -> 1 -> 2 -> 3 costs 3, 0 -> 4 -> 3 costs 5 and 0 -> 3 costs 10
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b, weight) in [
+            (0, 1, 1.0),
+            (1, 2, 1.0),
+            (2, 3, 1.0),
+            (0, 4, 2.0),
+            (4, 3, 3.0),
+            (0, 3, 10.0),
+        ] {
+            graph.add_edge_directed(v(a), v(b), weight);
+        }
+
+        assert_eq!(
+            graph.shortest_path_with_hop_limit(v(0), v(3), 3),
+            Some((vec![v(0), v(1), v(2), v(3)], 3.0))
+        );
+        assert_eq!(
+            graph.shortest_path_with_hop_limit(v(0), v(3), 2),
+            Some((vec![v(0), v(4), v(3)], 5.0))
+        );
+        assert_eq!(
+            graph.shortest_path_with_hop_limit(v(0), v(3), 1),
+            Some((vec![v(0), v(3)], 10.0))
+        );
+        assert_eq!(
+            graph.shortest_path_with_hop_limit(v(0), v(0), 0),
+            Some((vec![v(0)], 0.0))
+        );
+
+        // A longer path exists, but it doesn't fit the hop limit
+        graph.remove_edge_directed(&v(0), &v(3));
+        assert_eq!(graph.shortest_path_with_hop_limit(v(0), v(3), 1), None);
+        assert_eq!(graph.shortest_path_with_hop_limit(v(3), v(0), 5), None);
+    }
+
+    #[test]
+    fn dijkstra_all_matches_bellman_ford() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b, weight) in [
+            (0, 1, 4.0),
+            (0, 2, 1.0),
+            (2, 1, 2.0),
+            (1, 3, 1.0),
+            (2, 3, 5.0),
+            (3, 4, 3.0),
+            (4, 1, 1.0),
+            (5, 0, 1.0),
+        ] {
+            graph.add_edge_directed(v(a), v(b), weight);
+        }
+        graph.add_vertex(v(6));
+
+        let distances = graph.dijkstra_all(v(0));
+        assert_eq!(
+            distances.keys().copied().collect::<Vec<_>>(),
+            vec![v(0), v(1), v(2), v(3), v(4)]
+        );
+        for vertex in graph.ordered_vertices() {
+            // With as many hops as vertices, the hop limited search is plain Bellman-Ford
+            let bellman_ford = graph
+                .shortest_path_with_hop_limit(v(0), *vertex, graph.hash_map.len())
+                .map(|(_, cost)| cost);
+            assert_eq!(distances.get(vertex).copied(), bellman_ford);
+        }
+        assert!(graph.dijkstra_all(v(7)).is_empty());
+    }
+
+    /// All-pairs shortest distances with Floyd-Warshall in the same form as [`AdjacencyList::johnsons`], to check it against.
+    fn floyd_warshall_distances(
+        graph: &AdjacencyList<Coord, f32>,
+    ) -> BTreeMap<(Coord, Coord), f32> {
+        let paths = graph.floyd_warshall().unwrap();
+        let mut distances = BTreeMap::new();
+        for (from, row) in paths.distances.iter().enumerate() {
+            for (to, distance) in row.iter().enumerate() {
+                if let Some(distance) = distance {
+                    distances.insert((paths.vertices[from], paths.vertices[to]), *distance);
+                }
+            }
+        }
+        distances
+    }
+
+    #[test]
+    fn johnsons_matches_floyd_warshall_with_negative_edges() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b, weight) in [
+            (0, 1, 3.0),
+            (0, 2, 8.0),
+            (0, 4, -4.0),
+            (1, 3, 1.0),
+            (1, 4, 7.0),
+            (2, 1, 4.0),
+            (3, 0, 2.0),
+            (3, 2, -5.0),
+            (4, 3, 6.0),
+            (5, 0, -1.0),
+        ] {
+            graph.add_edge_directed(v(a), v(b), weight);
+        }
+        graph.add_vertex(v(6));
+
+        let distances = graph.johnsons().unwrap();
+        assert_eq!(distances, floyd_warshall_distances(&graph));
+        assert_eq!(distances[&(v(0), v(2))], -3.0);
+        assert_eq!(distances[&(v(6), v(6))], 0.0);
+        assert!(!distances.contains_key(&(v(0), v(5))));
+
+        // 2 -> 1 -> 3 -> 2 now sums to -1
+        graph.add_edge_directed(v(2), v(1), 2.0);
+        graph.add_edge_directed(v(3), v(2), -4.0);
+        let mut cycle = graph.johnsons().unwrap_err().cycle;
+        cycle.sort();
+        assert_eq!(cycle, vec![v(1), v(2), v(3)]);
+    }
+
+    #[test]
+    fn floyd_warshall_records_passes() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b, weight) in [(0, 1, 4.0), (1, 2, 1.0), (0, 2, 7.0), (2, 0, -2.0)] {
+            graph.add_edge_directed(v(a), v(b), weight);
+        }
+        graph.add_vertex(v(3));
+
+        let paths = graph.floyd_warshall().unwrap();
+        assert_eq!(paths.cost(&v(0), &v(2)), Some(5.0));
+        assert_eq!(paths.path(&v(0), &v(2)), Some(vec![v(0), v(1), v(2)]));
+        assert_eq!(paths.path(&v(1), &v(0)), Some(vec![v(1), v(2), v(0)]));
+        assert_eq!(paths.path(&v(1), &v(1)), Some(vec![v(1)]));
+        assert_eq!(paths.path(&v(0), &v(3)), None);
+
+        // Going through 0 makes 2 -> 1 possible, and going through 1 makes 0 -> 2 cheaper
+        assert_eq!(paths.passes.len(), 4);
+        assert_eq!(
+            paths.passes[0],
+            [DistanceUpdate {
+                from: 2,
+                to: 1,
+                previous: None,
+                distance: 2.0
+            }]
+        );
+        assert_eq!(
+            paths.passes[1],
+            [DistanceUpdate {
+                from: 0,
+                to: 2,
+                previous: Some(7.0),
+                distance: 5.0
+            }]
+        );
+
+        let edges = paths.distances_after(0);
+        assert_eq!(edges[0], [Some(0.0), Some(4.0), Some(7.0), None]);
+        assert_eq!(edges[2][1], None);
+        assert_eq!(paths.distances_after(1)[2][1], Some(2.0));
+        assert_eq!(paths.distances_after(paths.passes.len()), paths.distances);
+
+        graph.add_edge_directed(v(2), v(0), -6.0);
+        assert_eq!(
+            graph.floyd_warshall().map(|_| ()),
+            Err(NegativeCycle {
+                cycle: vec![v(1), v(2), v(0)]
+            })
+        );
+    }
+
+    #[test]
+    fn floyd_warshall_with_edges_to_missing_vertices() {
+        // 1 has no entry of its own, but it still gets a row and a column
+        let graph = AdjacencyList::new(BTreeMap::from([(v(0), BTreeMap::from([(v(1), 2.0)]))]));
+        let paths = graph.floyd_warshall().unwrap();
+        assert_eq!(paths.vertices, vec![v(0), v(1)]);
+        assert_eq!(paths.cost(&v(0), &v(1)), Some(2.0));
+        assert_eq!(paths.cost(&v(1), &v(0)), None);
+
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(v(0), v(1), 1.0);
+        graph.add_edge_directed(v(2), v(1), 1.0);
+        graph.remove_vertex(&v(1));
+        let paths = graph.floyd_warshall().unwrap();
+        assert_eq!(paths.vertices, vec![v(0), v(2)]);
+        assert_eq!(paths.path(&v(0), &v(1)), None);
+    }
+
+    #[test]
+    fn bottleneck_path_avoids_heavy_edge() {
+        // 0 -> 1 -> 4 is the cheapest at 1 + 9 = 10, but 0 -> 2 -> 3 -> 4 never uses an edge heavier than 5
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b, weight) in [
+            (0, 1, 1.0),
+            (1, 4, 9.0),
+            (0, 2, 5.0),
+            (2, 3, 5.0),
+            (3, 4, 4.0),
+            (4, 5, 2.0),
+        ] {
+            graph.add_edge_undirected(v(a), v(b), weight);
+        }
+        graph.add_vertex(v(6));
+
+        assert_eq!(
+            graph.shortest_path_with_hop_limit(v(0), v(4), 10),
+            Some((vec![v(0), v(1), v(4)], 10.0))
+        );
+        assert_eq!(
+            graph.minimum_bottleneck_path(v(0), v(4)),
+            Some((vec![v(0), v(2), v(3), v(4)], 5.0))
+        );
+        assert_eq!(
+            graph
+                .minimum_bottleneck_path(v(0), v(5))
+                .map(|(_, bottleneck)| bottleneck),
+            Some(5.0)
+        );
+        assert_eq!(
+            graph.minimum_bottleneck_path(v(2), v(2)),
+            Some((vec![v(2)], 0.0))
+        );
+        assert_eq!(graph.minimum_bottleneck_path(v(0), v(6)), None);
+    }
+
+    #[test]
+    fn second_shortest_path_is_backup_route() {
+        // 0 -> 1 -> 3 costs 2, 0 -> 2 -> 3 costs 5 and 0 -> 1 -> 2 -> 3 costs 6
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b, weight) in [
+            (0, 1, 1.0),
+            (1, 3, 1.0),
+            (0, 2, 2.0),
+            (2, 3, 3.0),
+            (1, 2, 2.0),
+        ] {
+            graph.add_edge_directed(v(a), v(b), weight);
+        }
+
+        assert_eq!(
+            graph.second_shortest_path(v(0), v(3)),
+            Some((vec![v(0), v(2), v(3)], 5.0))
+        );
+
+        // A tie with the shortest path counts as the second shortest path
+        graph.add_edge_directed(v(0), v(4), 1.0);
+        graph.add_edge_directed(v(4), v(3), 1.0);
+        let (path, cost) = graph.second_shortest_path(v(0), v(3)).unwrap();
+        assert_eq!(cost, 2.0);
+        assert_eq!(path.len(), 3);
+
+        // With a single path there's no backup
+        assert_eq!(graph.second_shortest_path(v(2), v(3)), None);
+        assert_eq!(graph.second_shortest_path(v(3), v(0)), None);
+    }
+
+    #[test]
+    fn negative_cycle_is_returned() {
+        // 0 -> 1 -> 2 -> 3 -> 1 costs -1 around the loop, and 4 hangs off of it
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(v(0), v(1), 4.0);
+        graph.add_edge_directed(v(1), v(2), 2.0);
+        graph.add_edge_directed(v(2), v(3), -6.0);
+        graph.add_edge_directed(v(3), v(1), 3.0);
+        graph.add_edge_directed(v(3), v(4), 1.0);
+
+        let cycle = graph.bellman_ford(v(0)).unwrap_err().cycle;
+        assert_eq!(cycle.len(), 3);
+        let start = cycle.iter().position(|vertex| *vertex == v(1)).unwrap();
+        let rotated = [&cycle[start..], &cycle[..start]].concat();
+        assert_eq!(rotated, vec![v(1), v(2), v(3)]);
+        let weight: f32 = (0..cycle.len())
+            .map(|i| graph.hash_map[&cycle[i]][&cycle[(i + 1) % cycle.len()]])
+            .sum();
+        assert!(weight < 0.0);
+
+        // Johnson's algorithm finds the same cycle from its virtual vertex
+        let johnsons_cycle = graph.johnsons().unwrap_err().cycle;
+        let start = johnsons_cycle
+            .iter()
+            .position(|vertex| *vertex == v(1))
+            .unwrap();
+        let rotated = [&johnsons_cycle[start..], &johnsons_cycle[..start]].concat();
+        assert_eq!(rotated, vec![v(1), v(2), v(3)]);
+
+        // The cycle can't be reached from 4, and without it there's nothing to find
+        assert!(graph.bellman_ford(v(4)).is_ok());
+        graph.add_edge_directed(v(3), v(1), 5.0);
+        assert!(graph.bellman_ford(v(0)).is_ok());
+        assert!(graph.johnsons().is_ok());
+    }
+
+    #[test]
+    fn bellman_ford_with_negative_weights() {
+        // Going around through 2 is cheaper than the direct edge from 0 to 1
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(v(0), v(1), 4.0);
+        graph.add_edge_directed(v(0), v(2), 5.0);
+        graph.add_edge_directed(v(2), v(1), -3.0);
+        graph.add_edge_directed(v(1), v(3), 1.0);
+        graph.add_vertex(v(4));
+
+        let tree = graph.bellman_ford(v(0)).unwrap();
+        assert_eq!(tree.path(v(3)), Some(vec![v(0), v(2), v(1), v(3)]));
+        assert_eq!(tree.cost(&v(3)), Some(3.0));
+        assert_eq!(tree.path(v(4)), None);
+        // The second pass reaches 3 but also makes 1 cheaper, so the third pass makes 3 cheaper too
+        assert_eq!(
+            tree.passes,
+            vec![
+                vec![(v(0), v(1), 4.0), (v(0), v(2), 5.0)],
+                vec![(v(1), v(3), 5.0), (v(2), v(1), 2.0)],
+                vec![(v(1), v(3), 3.0)],
+            ]
+        );
+
+        graph.add_edge_directed(v(3), v(2), 1.0);
+        let error = graph.bellman_ford(v(0)).unwrap_err();
+        assert_eq!(error.cycle.len(), 3);
+        assert!(error.to_string().starts_with("graph has a negative cycle:"));
+    }
+
+    #[test]
+    fn bellman_ford_reaches_edges_to_missing_vertices() {
+        // 1 has no entry of its own, but the edge to it still takes a pass to relax
+        let graph = AdjacencyList::new(BTreeMap::from([(v(0), BTreeMap::from([(v(1), 2.0)]))]));
+        let tree = graph.bellman_ford(v(0)).unwrap();
+        assert_eq!(tree.path(v(1)), Some(vec![v(0), v(1)]));
+        assert_eq!(tree.cost(&v(1)), Some(2.0));
+
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(v(0), v(1), 1.0);
+        graph.remove_vertex(&v(1));
+        assert_eq!(graph.bellman_ford(v(0)).unwrap().path(v(1)), None);
+    }
+
+    #[test]
+    fn longest_path_to_sink() {
+        // Two routes from 0 to the sink 4: 0 -> 1 -> 4 costs 5 and 0 -> 2 -> 3 -> 4 costs 7
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b, weight) in [
+            (0, 1, 3.0),
+            (1, 4, 2.0),
+            (0, 2, 1.0),
+            (2, 3, 4.0),
+            (3, 4, 2.0),
+            (1, 3, 1.0),
+            (5, 0, 9.0),
+        ] {
+            graph.add_edge_directed(v(a), v(b), weight);
+        }
+
+        let distances = graph.longest_path_dag(v(0)).unwrap();
+        assert_eq!(distances[&v(4)], 7.0);
+        assert_eq!(distances[&v(3)], 5.0);
+        assert!(!distances.contains_key(&v(5)));
+
+        graph.add_edge_directed(v(4), v(0), 1.0);
+        assert_eq!(
+            graph.longest_path_dag(v(0)),
+            Err(CycleError(vec![v(0), v(1), v(3), v(4)]))
+        );
+    }
+
+    #[test]
+    fn critical_path_of_task_graph() {
+        // 0 is the project start and 5 is the end. The critical path 0 -> 2 -> 3 -> 5 takes 4 + 6 + 3 = 13,
+        // while 0 -> 1 -> 3 -> 5 takes 12, 0 -> 1 -> 4 -> 5 takes 10 and 0 -> 2 -> 4 -> 5 takes 11
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b, duration) in [
+            (0, 1, 3.0),
+            (0, 2, 4.0),
+            (1, 3, 6.0),
+            (2, 3, 6.0),
+            (1, 4, 2.0),
+            (2, 4, 2.0),
+            (3, 5, 3.0),
+            (4, 5, 5.0),
+        ] {
+            graph.add_edge_directed(v(a), v(b), duration);
+        }
+
+        assert_eq!(graph.critical_path(), Ok(vec![v(0), v(2), v(3), v(5)]));
+        assert_eq!(graph.longest_path_dag(v(0)).unwrap()[&v(5)], 13.0);
+        assert_eq!(
+            AdjacencyList::<Coord, f32>::default().critical_path(),
+            Ok(vec![])
+        );
+
+        graph.add_edge_directed(v(5), v(2), 1.0);
+        assert_eq!(
+            graph.critical_path(),
+            Err(CycleError(vec![v(3), v(5), v(2)]))
+        );
+    }
+}
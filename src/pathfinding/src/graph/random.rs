@@ -0,0 +1,237 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::AdjacencyList;
+use crate::{Coord, Edge, Vertex};
+
+/// A kind of random graph, e.g. for choosing which graph the "Generate graph" button generates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RandomGraphKind {
+    /// Every pair of vertices is connected with the same chance. See [`AdjacencyList::erdos_renyi`].
+    #[default]
+    ErdosRenyi,
+    /// Every pair of vertices is connected. See [`AdjacencyList::complete`].
+    Complete,
+    /// Every cell is connected to its adjacent cells. See [`AdjacencyList::random_grid`].
+    Grid,
+    /// Edges only lead forward in the order of the vertices. See [`AdjacencyList::random_dag_from`].
+    Dag,
+}
+
+impl RandomGraphKind {
+    pub const ALL: [RandomGraphKind; 4] = [
+        RandomGraphKind::ErdosRenyi,
+        RandomGraphKind::Complete,
+        RandomGraphKind::Grid,
+        RandomGraphKind::Dag,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RandomGraphKind::ErdosRenyi => "Erdős–Rényi",
+            RandomGraphKind::Complete => "Complete",
+            RandomGraphKind::Grid => "Grid",
+            RandomGraphKind::Dag => "Random DAG",
+        }
+    }
+    /// Checks if the edges of the graph are left to chance. Complete graphs and grids have every edge they can have.
+    pub fn uses_edge_probability(&self) -> bool {
+        matches!(self, RandomGraphKind::ErdosRenyi | RandomGraphKind::Dag)
+    }
+    /// Generates a graph of this kind whose vertices are the cells of a `width` x `height` grid, in row-major order for DAGs.
+    /// `diagonals` only affects grids, and `edge_probability` only the kinds that [use it](RandomGraphKind::uses_edge_probability).
+    pub fn generate<E: Edge>(
+        &self,
+        width: usize,
+        height: usize,
+        diagonals: bool,
+        edge_probability: f64,
+        weights: RangeInclusive<f64>,
+        seed: u64,
+    ) -> AdjacencyList<Coord, E> {
+        let cells =
+            (0..height as isize).flat_map(|y| (0..width as isize).map(move |x| Coord::new(x, y)));
+        match self {
+            RandomGraphKind::ErdosRenyi => {
+                AdjacencyList::erdos_renyi(cells, edge_probability, weights, seed)
+            }
+            RandomGraphKind::Complete => AdjacencyList::complete(cells, weights, seed),
+            RandomGraphKind::Grid => {
+                AdjacencyList::random_grid(width, height, diagonals, weights, seed)
+            }
+            RandomGraphKind::Dag => {
+                AdjacencyList::random_dag_from(cells, edge_probability, weights, seed)
+            }
+        }
+    }
+}
+
+/// Seeded random graphs, where the same arguments always give the same graph.
+/// The edge weights are drawn uniformly from a range, which can be a single value like `1.0..=1.0` for unweighted graphs.
+impl<V: Vertex, E: Edge> AdjacencyList<V, E> {
+    /// Creates an Erdős–Rényi G(n, p) graph on the `vertices`, where every pair of vertices is connected by an undirected edge
+    /// with a chance of `edge_probability`, independently of the other pairs.
+    pub fn erdos_renyi(
+        vertices: impl IntoIterator<Item = V>,
+        edge_probability: f64,
+        weights: RangeInclusive<f64>,
+        seed: u64,
+    ) -> Self {
+        Self::random_pairs(vertices, edge_probability, &weights, seed, false)
+    }
+    /// Creates a complete graph on the `vertices`, where every pair of vertices is connected by an undirected edge.
+    pub fn complete(
+        vertices: impl IntoIterator<Item = V>,
+        weights: RangeInclusive<f64>,
+        seed: u64,
+    ) -> Self {
+        Self::random_pairs(vertices, 1.0, &weights, seed, false)
+    }
+    /// Creates a random directed acyclic graph where each vertex has an edge to every vertex after it in `vertices`
+    /// with a chance of `edge_probability`.\
+    /// Edges only lead forward in the order of `vertices`, so the graph never has a cycle.
+    pub fn random_dag_from(
+        vertices: impl IntoIterator<Item = V>,
+        edge_probability: f64,
+        weights: RangeInclusive<f64>,
+        seed: u64,
+    ) -> Self {
+        Self::random_pairs(vertices, edge_probability, &weights, seed, true)
+    }
+    /// Adds all `vertices` and connects each of them to every later one with a chance of `edge_probability`.
+    /// Repeated vertices aren't connected to themselves.
+    fn random_pairs(
+        vertices: impl IntoIterator<Item = V>,
+        edge_probability: f64,
+        weights: &RangeInclusive<f64>,
+        seed: u64,
+        directed: bool,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut graph = Self::new(BTreeMap::new());
+        let vertices: Vec<V> = vertices.into_iter().collect();
+
+        for (i, a) in vertices.iter().enumerate() {
+            graph.hash_map.entry(*a).or_default();
+            for b in &vertices[i + 1..] {
+                if a != b && rng.gen_bool(edge_probability.clamp(0.0, 1.0)) {
+                    let weight = random_weight(&mut rng, weights);
+                    if directed {
+                        graph.add_edge_directed(*a, *b, weight);
+                    } else {
+                        graph.add_edge_undirected(*a, *b, weight);
+                    }
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+impl<E: Edge> AdjacencyList<Coord, E> {
+    /// Creates a `width` x `height` grid graph like [`AdjacencyList::grid_with_obstacles`] without obstacles,
+    /// but with the cost of every edge multiplied by a factor drawn from `weights`. Both directions of an edge cost the same.
+    pub fn random_grid(
+        width: usize,
+        height: usize,
+        diagonals: bool,
+        weights: RangeInclusive<f64>,
+        seed: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut graph = Self::grid_with_obstacles(width, height, diagonals, &BTreeSet::new());
+
+        for (from, to, step) in graph.edges() {
+            if from < to {
+                let weight = step * random_weight(&mut rng, &weights);
+                graph.add_edge_undirected(from, to, weight);
+            }
+        }
+
+        graph
+    }
+    /// Creates a random directed acyclic graph with `vertex_count` vertices along the x axis, each having an edge of weight 1
+    /// to every vertex on its right with a chance of `edge_probability`. See [`AdjacencyList::random_dag_from`].
+    pub fn random_dag(vertex_count: usize, edge_probability: f64, seed: u64) -> Self {
+        Self::random_dag_from(
+            (0..vertex_count as isize).map(|x| Coord::new(x, 0)),
+            edge_probability,
+            1.0..=1.0,
+            seed,
+        )
+    }
+}
+
+/// Draws a weight uniformly from `weights`, or takes its start if the range holds a single value or is empty.
+fn random_weight<E: Edge>(rng: &mut StdRng, weights: &RangeInclusive<f64>) -> E {
+    let weight = if weights.start() < weights.end() {
+        rng.gen_range(weights.clone())
+    } else {
+        *weights.start()
+    };
+    E::from(weight).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(i: isize) -> Coord {
+        Coord::new(i, 0)
+    }
+
+    #[test]
+    fn random_dag_is_acyclic() {
+        for seed in 0..20 {
+            let graph = AdjacencyList::<Coord, f32>::random_dag(12, 0.4, seed);
+            assert_eq!(graph.hash_map.len(), 12);
+            assert!(!graph.has_cycle_directed(), "seed {}", seed);
+            assert!(graph.edges().iter().all(|(from, to, _)| from.x < to.x));
+            assert_eq!(graph, AdjacencyList::random_dag(12, 0.4, seed));
+        }
+
+        let complete = AdjacencyList::<Coord, f32>::random_dag(6, 1.0, 0);
+        assert_eq!(complete.edges().len(), 15);
+        assert!(!complete.has_cycle_directed());
+    }
+
+    #[test]
+    fn random_graphs_have_the_expected_edges() {
+        let vertices = (0..10).map(v);
+        let complete = AdjacencyList::<Coord, f64>::complete(vertices.clone(), 2.0..=5.0, 1);
+        assert_eq!(complete.vertex_count(), 10);
+        assert_eq!(complete.edge_count_undirected(), 45);
+        assert!(complete.is_undirected());
+        assert!(complete
+            .edges()
+            .iter()
+            .all(|(_, _, weight)| (2.0..=5.0).contains(weight)));
+
+        let sparse = AdjacencyList::<Coord, f64>::erdos_renyi(vertices.clone(), 0.3, 1.0..=1.0, 7);
+        assert!(sparse.is_undirected());
+        assert!(sparse.edge_count_undirected() < 45);
+        assert_eq!(
+            sparse,
+            AdjacencyList::erdos_renyi(vertices.clone(), 0.3, 1.0..=1.0, 7)
+        );
+        assert_eq!(
+            AdjacencyList::<Coord, f64>::erdos_renyi(vertices, 0.0, 1.0..=1.0, 7)
+                .edge_count_undirected(),
+            0
+        );
+
+        // A grid with weights of exactly 1 is the plain grid
+        assert_eq!(
+            AdjacencyList::<Coord, f64>::random_grid(4, 3, true, 1.0..=1.0, 3),
+            AdjacencyList::grid_with_obstacles(4, 3, true, &BTreeSet::new())
+        );
+        let grid = AdjacencyList::<Coord, f64>::random_grid(4, 3, false, 1.0..=9.0, 3);
+        assert!(grid.is_undirected());
+        assert_eq!(grid.edge_count_undirected(), 17);
+    }
+}
@@ -0,0 +1,123 @@
+use num_traits::ToPrimitive;
+
+use super::AdjacencyList;
+use crate::Vertex;
+
+/// How an edge should be drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeDirection {
+    /// An edge that only goes from `from` to `to`, drawn with an arrow.
+    Directed,
+    /// A pair of edges in both directions, drawn as one line without arrows.
+    Undirected,
+    /// An edge from a vertex to itself.
+    SelfLoop,
+}
+
+/// Everything needed to draw one edge, see [`AdjacencyList::edge_render_data`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdgeRenderInfo<V> {
+    pub from: V,
+    pub to: V,
+    /// The weight mapped into `[0, 1]` like in [`AdjacencyList::normalized_weights`], for the thickness or the opacity of the edge.
+    pub thickness: f64,
+    pub direction: EdgeDirection,
+}
+
+impl<V: Vertex, E: Clone + ToPrimitive> AdjacencyList<V, E> {
+    /// Gets the endpoints, thickness and direction of every edge for drawing the graph, without parsing them back from [`AdjacencyList::into_mermaid`].\
+    /// Like in the Mermaid diagram, an edge is undirected if the reverse edge exists too. Undirected edges appear only once,
+    /// from the smaller vertex to the larger one and with the thickness of the edge in that direction.
+    ///
+    /// The smallest weight of the graph gets a thickness of `0.0` and the largest `1.0`, or all edges get `1.0` if their weights are the same.
+    pub fn edge_render_data(&self) -> Vec<EdgeRenderInfo<V>> {
+        let edges: Vec<(V, V, f64)> = self
+            .edges()
+            .into_iter()
+            .map(|(from, to, weight)| (from, to, weight.to_f64().unwrap_or_default()))
+            .collect();
+        let min = edges
+            .iter()
+            .map(|edge| edge.2)
+            .fold(f64::INFINITY, f64::min);
+        let max = edges
+            .iter()
+            .map(|edge| edge.2)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        edges
+            .into_iter()
+            .filter_map(|(from, to, weight)| {
+                let direction = if from == to {
+                    EdgeDirection::SelfLoop
+                } else if self.is_neighbor(&to, &from) {
+                    // The other direction of the pair is drawn by the same line
+                    if to < from {
+                        return None;
+                    }
+                    EdgeDirection::Undirected
+                } else {
+                    EdgeDirection::Directed
+                };
+                let thickness = if max > min {
+                    (weight - min) / (max - min)
+                } else {
+                    1.0
+                };
+                Some(EdgeRenderInfo {
+                    from,
+                    to,
+                    thickness,
+                    direction,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    fn v(i: isize) -> Coord {
+        Coord::new(i, 0)
+    }
+
+    #[test]
+    fn thickness_is_normalized_and_directions_classified() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(0), v(1), 3.0);
+        graph.add_edge_directed(v(2), v(1), 5.0);
+        graph.add_edge_directed(v(1), v(3), 1.0);
+        graph.add_self_loop(v(3), 2.0);
+
+        let info = |from, to, thickness, direction| EdgeRenderInfo {
+            from: v(from),
+            to: v(to),
+            thickness,
+            direction,
+        };
+        assert_eq!(
+            graph.edge_render_data(),
+            vec![
+                info(0, 1, 0.5, EdgeDirection::Undirected),
+                info(1, 3, 0.0, EdgeDirection::Directed),
+                info(2, 1, 1.0, EdgeDirection::Directed),
+                info(3, 3, 0.25, EdgeDirection::SelfLoop),
+            ]
+        );
+
+        // Equal weights stay visible
+        let mut graph = AdjacencyList::<Coord, u32>::default();
+        graph.add_edge_directed(v(0), v(1), 4);
+        graph.add_edge_directed(v(1), v(2), 4);
+        assert!(graph
+            .edge_render_data()
+            .iter()
+            .all(|edge| edge.thickness == 1.0));
+        assert!(AdjacencyList::<Coord, u32>::default()
+            .edge_render_data()
+            .is_empty());
+    }
+}
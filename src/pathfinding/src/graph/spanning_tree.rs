@@ -0,0 +1,259 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
+};
+
+use super::AdjacencyList;
+use crate::{disjoint_set::DisjointSet, Edge, Vertex};
+
+/// What a spanning tree algorithm did with an edge that it looked at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeSelection {
+    /// The edge was added to the tree.
+    Added,
+    /// The edge was skipped because both of its vertices were already in the same tree, so it would have formed a cycle.
+    Rejected,
+}
+
+/// An edge that a spanning tree algorithm looked at, in the order that it looked at them, for animating the tree being built.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpanningTreeStep<V: Vertex, E: Edge> {
+    pub from: V,
+    pub to: V,
+    pub weight: E,
+    pub selection: EdgeSelection,
+}
+
+/// The result of [`AdjacencyList::kruskal_mst`] or [`AdjacencyList::prim_mst`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanningTree<V: Vertex, E: Edge> {
+    /// The edges of the tree as `(from, to, weight)` in the order that they were added.
+    pub edges: Vec<(V, V, E)>,
+    pub steps: Vec<SpanningTreeStep<V, E>>,
+}
+impl<V: Vertex, E: Edge> SpanningTree<V, E> {
+    pub fn total_weight(&self) -> E {
+        self.edges
+            .iter()
+            .fold(E::zero(), |total, (_, _, weight)| total + *weight)
+    }
+    /// Records that the edge from `from` to `to` was looked at and adds it to the tree if it was [added](EdgeSelection::Added).
+    fn select(&mut self, from: V, to: V, weight: E, selection: EdgeSelection) {
+        if selection == EdgeSelection::Added {
+            self.edges.push((from, to, weight));
+        }
+        self.steps.push(SpanningTreeStep {
+            from,
+            to,
+            weight,
+            selection,
+        });
+    }
+}
+
+impl<V: Vertex, E: Edge> AdjacencyList<V, E> {
+    /// Finds a minimum spanning forest with Kruskal's algorithm, interpreting the graph as undirected.\
+    /// The edges are tried from the lightest to the heaviest, and an edge is added if it joins two separate trees of the forest.
+    /// If the graph is disconnected, there is a tree for every connected component.
+    pub fn kruskal_mst(&self) -> SpanningTree<V, E> {
+        let mut edges = self.undirected_edges();
+        edges.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal));
+
+        let mut trees = self.ordered_vertices().copied().collect::<DisjointSet<V>>();
+        let mut tree = SpanningTree {
+            edges: vec![],
+            steps: vec![],
+        };
+
+        for (a, b, weight) in edges {
+            // A spanning tree of a connected graph is done once it connects every vertex
+            if trees.set_count() == 1 {
+                break;
+            }
+            let selection = if trees.union(a, b) {
+                EdgeSelection::Added
+            } else {
+                EdgeSelection::Rejected
+            };
+            tree.select(a, b, weight, selection);
+        }
+
+        tree
+    }
+    /// Finds a minimum spanning tree of the connected component of `start` with Prim's algorithm, interpreting the graph as undirected.\
+    /// The tree grows from `start` by always adding the lightest edge that leads out of it.
+    /// Edges that lead to a vertex that was reached after they were found are rejected.
+    pub fn prim_mst(&self, start: V) -> SpanningTree<V, E> {
+        let mut neighbors: BTreeMap<V, Vec<(V, E)>> = BTreeMap::new();
+        for (a, b, weight) in self.undirected_edges() {
+            neighbors.entry(a).or_default().push((b, weight));
+            neighbors.entry(b).or_default().push((a, weight));
+        }
+
+        let mut tree = SpanningTree {
+            edges: vec![],
+            steps: vec![],
+        };
+        let mut reached = BTreeSet::new();
+        let mut candidates = BinaryHeap::new();
+        let reach = |vertex: V, reached: &mut BTreeSet<V>, candidates: &mut BinaryHeap<_>| {
+            reached.insert(vertex);
+            for (neighbor, weight) in neighbors.get(&vertex).into_iter().flatten() {
+                if !reached.contains(neighbor) {
+                    candidates.push(CandidateEdge {
+                        from: vertex,
+                        to: *neighbor,
+                        weight: *weight,
+                    });
+                }
+            }
+        };
+
+        if !self.contains_vertex(&start) {
+            return tree;
+        }
+        reach(start, &mut reached, &mut candidates);
+
+        while let Some(CandidateEdge { from, to, weight }) = candidates.pop() {
+            if reached.contains(&to) {
+                tree.select(from, to, weight, EdgeSelection::Rejected);
+            } else {
+                tree.select(from, to, weight, EdgeSelection::Added);
+                reach(to, &mut reached, &mut candidates);
+            }
+        }
+
+        tree
+    }
+    /// Gets every edge as `(a, b, weight)` with `a < b`, interpreting the graph as undirected.\
+    /// If there are edges in both directions between two vertices, the smaller weight is used. Self-loops are left out.
+    fn undirected_edges(&self) -> Vec<(V, V, E)> {
+        let mut undirected_edges: BTreeMap<(V, V), E> = BTreeMap::new();
+
+        for (vertex, neighbor, weight) in self.edges_iter() {
+            if vertex == neighbor {
+                continue;
+            }
+            let key = (*vertex.min(neighbor), *vertex.max(neighbor));
+            let weight = match undirected_edges.get(&key) {
+                Some(existing) => existing.min(*weight),
+                None => *weight,
+            };
+            undirected_edges.insert(key, weight);
+        }
+
+        undirected_edges
+            .into_iter()
+            .map(|((a, b), weight)| (a, b, weight))
+            .collect()
+    }
+}
+
+/// An edge leading out of the tree of Prim's algorithm, ordered so that the lightest edge is at the top of a [`BinaryHeap`].
+struct CandidateEdge<V, E> {
+    from: V,
+    to: V,
+    weight: E,
+}
+impl<V, E: PartialOrd> PartialEq for CandidateEdge<V, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<V, E: PartialOrd> Eq for CandidateEdge<V, E> {}
+impl<V, E: PartialOrd> PartialOrd for CandidateEdge<V, E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<V, E: PartialOrd> Ord for CandidateEdge<V, E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .weight
+            .partial_cmp(&self.weight)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    fn v(i: isize) -> Coord {
+        Coord::new(i, 0)
+    }
+
+    /// A square 0-1-2-3 with a diagonal from 0 to 2, where the heaviest edges close the cycles.
+    fn square() -> AdjacencyList<Coord, f32> {
+        let mut graph = AdjacencyList::default();
+        for (a, b, weight) in [
+            (0, 1, 1.0),
+            (1, 2, 2.0),
+            (2, 3, 3.0),
+            (3, 0, 4.0),
+            (0, 2, 5.0),
+        ] {
+            graph.add_edge_undirected(v(a), v(b), weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn kruskal_rejects_cycle_forming_edges() {
+        let mut graph = square();
+        // The 0-2 edge is tried after the 0-1 and 1-2 edges have already connected its vertices
+        graph.add_edge_undirected(v(0), v(2), 2.5);
+
+        let tree = graph.kruskal_mst();
+        assert_eq!(tree.total_weight(), 6.0);
+        assert_eq!(
+            tree.steps
+                .iter()
+                .map(|step| (step.from, step.to, step.selection))
+                .collect::<Vec<_>>(),
+            [
+                (v(0), v(1), EdgeSelection::Added),
+                (v(1), v(2), EdgeSelection::Added),
+                (v(0), v(2), EdgeSelection::Rejected),
+                (v(2), v(3), EdgeSelection::Added),
+            ]
+        );
+    }
+
+    #[test]
+    fn prim_grows_from_the_start() {
+        let tree = square().prim_mst(v(3));
+        assert_eq!(tree.total_weight(), 6.0);
+        assert_eq!(
+            tree.edges,
+            [(v(3), v(2), 3.0), (v(2), v(1), 2.0), (v(1), v(0), 1.0)]
+        );
+        // The edges from 3 and 2 to 0 were found before 0 was reached
+        assert_eq!(
+            tree.steps
+                .iter()
+                .filter(|step| step.selection == EdgeSelection::Rejected)
+                .count(),
+            2
+        );
+
+        assert!(square().prim_mst(v(9)).edges.is_empty());
+    }
+
+    #[test]
+    fn kruskal_and_prim_agree_on_grids() {
+        let mut graph =
+            AdjacencyList::<Coord, f32>::grid_with_obstacles(6, 5, true, &BTreeSet::new());
+        // Uneven weights with many ties
+        for (a, b, _) in graph.edges() {
+            let weight = (a.x * 7 + a.y * 13 + b.x * 5 + b.y * 3) % 11;
+            graph.add_edge_undirected(a, b, weight as f32);
+        }
+        let kruskal = graph.kruskal_mst();
+        let prim = graph.prim_mst(Coord::new(0, 0));
+        assert_eq!(kruskal.edges.len(), 6 * 5 - 1);
+        assert_eq!(prim.edges.len(), 6 * 5 - 1);
+        assert!((kruskal.total_weight() - prim.total_weight()).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,112 @@
+use super::AdjacencyList;
+use crate::Vertex;
+
+/// A graph that the pathfinding algorithms can search without knowing how it's stored.\
+/// [`AdjacencyList`] and [`AdjacencyMatrix`](super::AdjacencyMatrix) implement it, but the neighbors can also be computed on demand, for example for grids that are too large to build.
+pub trait Graph<V: Vertex, E: Clone> {
+    /// Gets the neighbors of `vertex` and the weights of the edges leading to them. Returns nothing if the vertex doesn't exist.
+    fn neighbors(&self, vertex: &V) -> Vec<(V, E)>;
+    /// Gets every vertex of the graph in ascending order.\
+    /// Graphs that can't list their vertices, such as unbounded ones, return an empty list, so algorithms shouldn't depend on it for correctness.
+    fn vertices(&self) -> Vec<V>;
+    /// Gets the weight of the edge from `from` to `to`, or `None` if there is no such edge.
+    fn edge_weight(&self, from: &V, to: &V) -> Option<E> {
+        self.neighbors(from)
+            .into_iter()
+            .find(|(neighbor, _)| neighbor == to)
+            .map(|(_, weight)| weight)
+    }
+}
+
+impl<V: Vertex, E: Clone> Graph<V, E> for AdjacencyList<V, E> {
+    fn neighbors(&self, vertex: &V) -> Vec<(V, E)> {
+        self.ordered_neighbors(vertex)
+            .map(|(neighbor, weight)| (*neighbor, weight.clone()))
+            .collect()
+    }
+    fn vertices(&self) -> Vec<V> {
+        self.ordered_vertices().copied().collect()
+    }
+    fn edge_weight(&self, from: &V, to: &V) -> Option<E> {
+        self.get_edge_weight(from, to).cloned()
+    }
+}
+
+/// A [`Graph`] whose edges can be changed, so that code that edits graphs also works with every representation.
+pub trait GraphMut<V: Vertex, E: Clone>: Graph<V, E> {
+    /// Adds a directed edge from `from` to `to`, creating the vertices if they don't exist. The weight of an existing edge is replaced.
+    fn add_edge(&mut self, from: V, to: V, weight: E);
+    /// Removes the directed edge from `from` to `to` and returns its weight, or `None` if there was no such edge.
+    fn remove_edge(&mut self, from: &V, to: &V) -> Option<E>;
+}
+
+impl<V: Vertex, E: Clone> GraphMut<V, E> for AdjacencyList<V, E> {
+    fn add_edge(&mut self, from: V, to: V, weight: E) {
+        self.add_edge_directed(from, to, weight);
+    }
+    fn remove_edge(&mut self, from: &V, to: &V) -> Option<E> {
+        self.remove_edge_directed(from, to)
+    }
+}
+
+/// Lets algorithms that take the graph by value borrow it instead of cloning it.
+impl<V: Vertex, E: Clone, G: Graph<V, E> + ?Sized> Graph<V, E> for &G {
+    fn neighbors(&self, vertex: &V) -> Vec<(V, E)> {
+        (**self).neighbors(vertex)
+    }
+    fn vertices(&self) -> Vec<V> {
+        (**self).vertices()
+    }
+    fn edge_weight(&self, from: &V, to: &V) -> Option<E> {
+        (**self).edge_weight(from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pathfinding_algorithms::bfs, Coord, PathfindingSteps};
+
+    /// A 3x3 grid whose neighbors are computed from the coordinates instead of being stored.
+    struct TinyGrid;
+    impl Graph<Coord, f32> for TinyGrid {
+        fn neighbors(&self, vertex: &Coord) -> Vec<(Coord, f32)> {
+            if !self.vertices().contains(vertex) {
+                return vec![];
+            }
+            vertex
+                .adjacent(false)
+                .into_iter()
+                .filter(|neighbor| (0..3).contains(&neighbor.x) && (0..3).contains(&neighbor.y))
+                .map(|neighbor| (neighbor, 1.0))
+                .collect()
+        }
+        fn vertices(&self) -> Vec<Coord> {
+            (0..3)
+                .flat_map(|x| (0..3).map(move |y| Coord::new(x, y)))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn bfs_through_custom_graph() {
+        let (start, end) = (Coord::new(0, 0), Coord::new(2, 2));
+        let result = bfs(TinyGrid, start, end, PathfindingSteps::new(vec![]));
+
+        assert_eq!(result.path.len(), 5);
+        assert_eq!(result.path.first(), Some(&start));
+        assert_eq!(result.path.last(), Some(&end));
+        assert!(result
+            .path
+            .windows(2)
+            .all(|pair| TinyGrid.edge_weight(&pair[0], &pair[1]) == Some(1.0)));
+        assert_eq!(TinyGrid.edge_weight(&start, &end), None);
+
+        // The same search through the trait on a stored graph
+        let graph =
+            AdjacencyList::<Coord, f32>::grid_with_obstacles(3, 3, false, &Default::default());
+        assert_eq!(Graph::vertices(&graph), TinyGrid.vertices());
+        let stored = bfs(&graph, start, end, PathfindingSteps::new(vec![]));
+        assert_eq!(stored.path.len(), 5);
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use super::AdjacencyList;
+use crate::Vertex;
+
+impl<V: Vertex, E: Clone> AdjacencyList<V, E> {
+    /// Gets all vertices that can be reached from `start` by following the edges.\
+    /// `start` itself is only included if `include_start` is true.
+    pub fn reachable_from(&self, start: &V, include_start: bool) -> BTreeSet<V> {
+        let mut reachable = BTreeSet::new();
+        let mut stack = vec![*start];
+
+        while let Some(vertex) = stack.pop() {
+            if let Some(neighbors) = self.get_neighbors(&vertex) {
+                for neighbor in neighbors.keys() {
+                    if reachable.insert(*neighbor) {
+                        stack.push(*neighbor);
+                    }
+                }
+            }
+        }
+
+        if include_start && self.hash_map.contains_key(start) {
+            reachable.insert(*start);
+        } else {
+            reachable.remove(start);
+        }
+
+        reachable
+    }
+    /// Runs a breadth-first search from all `sources` at the same time and returns the number of edges from each reachable vertex to its nearest source.\
+    /// All sources are at a distance of 0. Vertices that can't be reached from any source are left out.
+    pub fn multi_source_bfs(&self, sources: &[V]) -> BTreeMap<V, usize> {
+        let mut distances = BTreeMap::new();
+        let mut queue = VecDeque::new();
+
+        for source in sources {
+            if self.hash_map.contains_key(source) && !distances.contains_key(source) {
+                distances.insert(*source, 0);
+                queue.push_back(*source);
+            }
+        }
+
+        while let Some(vertex) = queue.pop_front() {
+            let distance = distances[&vertex];
+            if let Some(neighbors) = self.get_neighbors(&vertex) {
+                for neighbor in neighbors.keys() {
+                    if !distances.contains_key(neighbor) {
+                        distances.insert(*neighbor, distance + 1);
+                        queue.push_back(*neighbor);
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+    /// Groups the vertices that can be reached from `start` by their number of edges from it, so layer 0 is `start` itself,
+    /// layer 1 its neighbors and so on. Each layer is in ascending order.\
+    /// Returns no layers if `start` isn't in the graph.
+    pub fn bfs_layers(&self, start: &V) -> Vec<Vec<V>> {
+        let mut layers: Vec<Vec<V>> = vec![];
+
+        for (vertex, distance) in self.multi_source_bfs(&[*start]) {
+            if layers.len() <= distance {
+                layers.resize(distance + 1, vec![]);
+            }
+            layers[distance].push(vertex);
+        }
+
+        layers
+    }
+    /// Visits every vertex of the graph exactly once in breadth-first order, even if the graph is disconnected.\
+    /// This is the concatenation of [`AdjacencyList::full_traversal_parts`], so use that one if the boundaries between the parts matter.
+    pub fn full_traversal_order(&self) -> Vec<V> {
+        self.full_traversal_parts().into_iter().flatten().collect()
+    }
+    /// Runs a breadth-first search from every vertex that hasn't been visited yet, in ascending order,
+    /// and returns the vertices visited by each search as one part. Each part starts with the vertex that its search started from.\
+    /// For undirected graphs the parts are the connected components. With directed edges a search can't revisit vertices of earlier parts,
+    /// so a part may only be a piece of a component.
+    pub fn full_traversal_parts(&self) -> Vec<Vec<V>> {
+        let mut visited = BTreeSet::new();
+        let mut parts = vec![];
+
+        for root in self.ordered_vertices() {
+            if !visited.insert(*root) {
+                continue;
+            }
+
+            let mut part = vec![];
+            let mut queue = VecDeque::from([*root]);
+            while let Some(vertex) = queue.pop_front() {
+                part.push(vertex);
+                for (neighbor, _) in self.ordered_neighbors(&vertex) {
+                    if visited.insert(*neighbor) {
+                        queue.push_back(*neighbor);
+                    }
+                }
+            }
+            parts.push(part);
+        }
+
+        parts
+    }
+    /// Computes the transitive closure of the graph, which has an edge from `a` to `b` if and only if `b` can be reached from `a`.\
+    /// A vertex only has an edge to itself if it's on a cycle. Every vertex of the graph is kept, even if it can't reach anything.
+    pub fn transitive_closure(&self) -> AdjacencyList<V, bool> {
+        let mut closure = AdjacencyList::new(BTreeMap::new());
+
+        for vertex in self.ordered_vertices() {
+            let mut reached = BTreeMap::new();
+            // Unlike with `reachable_from`, the start is only reached again by going around a cycle
+            let mut stack = vec![*vertex];
+            while let Some(current) = stack.pop() {
+                for (neighbor, _) in self.ordered_neighbors(&current) {
+                    if reached.insert(*neighbor, true).is_none() {
+                        stack.push(*neighbor);
+                    }
+                }
+            }
+            closure.hash_map.insert(*vertex, reached);
+        }
+
+        closure
+    }
+    /// Finds a path from `start` to `goal` with the fewest edges using iterative deepening depth-first search,
+    /// or `None` if there is no path of at most `max_depth` edges.\
+    /// The depth limit is raised by one until the goal is found, so only the current path has to be stored like in DFS,
+    /// while the first path found is the shallowest one like in BFS.
+    pub fn iddfs(&self, start: V, goal: V, max_depth: usize) -> Option<Vec<V>> {
+        if !self.hash_map.contains_key(&start) {
+            return None;
+        }
+
+        let mut path = vec![start];
+        for depth in 0..=max_depth {
+            let mut cut_off = false;
+            if self.depth_limited_search(&mut path, goal, depth, &mut cut_off) {
+                return Some(path);
+            }
+            // Every path ended before the limit, so raising it won't find anything new
+            if !cut_off {
+                break;
+            }
+        }
+
+        None
+    }
+    /// Extends `path` depth-first by at most `depth` edges until it reaches `goal`. Vertices already on the path are skipped to avoid cycles.\
+    /// `cut_off` is set if some path was stopped by the depth limit.
+    fn depth_limited_search(
+        &self,
+        path: &mut Vec<V>,
+        goal: V,
+        depth: usize,
+        cut_off: &mut bool,
+    ) -> bool {
+        let vertex = *path.last().unwrap();
+        if vertex == goal {
+            return true;
+        }
+        if depth == 0 {
+            *cut_off = true;
+            return false;
+        }
+
+        for (neighbor, _) in self.ordered_neighbors(&vertex) {
+            if path.contains(neighbor) {
+                continue;
+            }
+            path.push(*neighbor);
+            if self.depth_limited_search(path, goal, depth - 1, cut_off) {
+                return true;
+            }
+            path.pop();
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    fn v(i: isize) -> Coord {
+        Coord::new(i, 0)
+    }
+
+    #[test]
+    fn reachable_from_follows_edge_directions() {
+        // 0 -> 1 -> 2, 3 -> 0 and an isolated 4
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(v(0), v(1), 1.0);
+        graph.add_edge_directed(v(1), v(2), 1.0);
+        graph.add_edge_directed(v(3), v(0), 1.0);
+        graph.add_vertex(v(4));
+
+        assert_eq!(
+            graph.reachable_from(&v(0), false),
+            BTreeSet::from([v(1), v(2)])
+        );
+        assert_eq!(
+            graph.reachable_from(&v(0), true),
+            BTreeSet::from([v(0), v(1), v(2)])
+        );
+        assert_eq!(graph.reachable_from(&v(2), false), BTreeSet::new());
+        assert_eq!(graph.reachable_from(&v(4), true), BTreeSet::from([v(4)]));
+    }
+
+    #[test]
+    fn full_traversal_covers_both_components() {
+        // The components 0 - 1 - 2 and 3 - 4, plus 5 -> 3 which is only reachable in the other direction
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_undirected(v(0), v(1), 1.0);
+        graph.add_edge_undirected(v(2), v(1), 1.0);
+        graph.add_edge_undirected(v(4), v(3), 1.0);
+        graph.add_edge_directed(v(5), v(3), 1.0);
+
+        let order = graph.full_traversal_order();
+        assert_eq!(order.len(), graph.hash_map.len());
+        assert_eq!(
+            order.iter().copied().collect::<BTreeSet<_>>().len(),
+            order.len()
+        );
+        assert_eq!(
+            graph.full_traversal_parts(),
+            vec![vec![v(0), v(1), v(2)], vec![v(3), v(4)], vec![v(5)]]
+        );
+        assert!(AdjacencyList::<Coord, f32>::default()
+            .full_traversal_order()
+            .is_empty());
+    }
+
+    #[test]
+    fn transitive_closure_of_chain() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for i in 0..4 {
+            graph.add_edge_directed(v(i), v(i + 1), 1.0);
+        }
+
+        let closure = graph.transitive_closure();
+        for i in 0..5 {
+            let reachable = closure.hash_map[&v(i)].keys().copied().collect::<Vec<_>>();
+            assert_eq!(reachable, (i + 1..5).map(v).collect::<Vec<_>>());
+        }
+
+        // Closing the chain into a cycle makes every vertex reach every vertex, including itself
+        graph.add_edge_directed(v(4), v(0), 1.0);
+        let closure = graph.transitive_closure();
+        assert!(closure.hash_map.values().all(|edges| edges.len() == 5));
+    }
+
+    #[test]
+    fn iddfs_finds_fewest_hops_within_limit() {
+        // A long way 0 -> 1 -> 2 -> 3 -> 4 that is visited first, and a shortcut 0 -> 5 -> 4
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for i in 0..4 {
+            graph.add_edge_directed(v(i), v(i + 1), 1.0);
+        }
+        graph.add_edge_directed(v(0), v(5), 10.0);
+        graph.add_edge_directed(v(5), v(4), 10.0);
+        graph.add_edge_directed(v(4), v(0), 1.0);
+        graph.add_vertex(v(6));
+
+        assert_eq!(graph.iddfs(v(0), v(4), 10), Some(vec![v(0), v(5), v(4)]));
+        assert_eq!(graph.iddfs(v(0), v(4), 2), Some(vec![v(0), v(5), v(4)]));
+        assert_eq!(graph.iddfs(v(0), v(4), 1), None);
+        assert_eq!(graph.iddfs(v(0), v(3), 2), None);
+        assert_eq!(
+            graph.iddfs(v(0), v(3), 3),
+            Some(vec![v(0), v(1), v(2), v(3)])
+        );
+        assert_eq!(graph.iddfs(v(0), v(0), 0), Some(vec![v(0)]));
+        assert_eq!(graph.iddfs(v(0), v(6), 100), None);
+    }
+
+    #[test]
+    fn multi_source_bfs_uses_nearest_source() {
+        // A line from 0 to 6 with the sources at both ends, and 7 hanging off of 2
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for i in 0..6 {
+            graph.add_edge_undirected(v(i), v(i + 1), 1.0);
+        }
+        graph.add_edge_undirected(v(2), v(7), 1.0);
+        graph.add_vertex(v(8));
+
+        let distances = graph.multi_source_bfs(&[v(0), v(6)]);
+        assert_eq!(
+            distances,
+            BTreeMap::from([
+                (v(0), 0),
+                (v(1), 1),
+                (v(2), 2),
+                (v(3), 3),
+                (v(4), 2),
+                (v(5), 1),
+                (v(6), 0),
+                (v(7), 3),
+            ])
+        );
+    }
+
+    #[test]
+    fn bfs_layers_group_by_distance() {
+        // A diamond 0 -> {1, 2} -> 3, with 4 after 3 only reachable in one direction and 5 disconnected
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        for (a, b) in [(0, 2), (0, 1), (1, 3), (2, 3)] {
+            graph.add_edge_undirected(v(a), v(b), 1.0);
+        }
+        graph.add_edge_directed(v(3), v(4), 1.0);
+        graph.add_vertex(v(5));
+
+        assert_eq!(
+            graph.bfs_layers(&v(0)),
+            vec![vec![v(0)], vec![v(1), v(2)], vec![v(3)], vec![v(4)]]
+        );
+        assert_eq!(graph.bfs_layers(&v(4)), vec![vec![v(4)]]);
+        assert!(graph.bfs_layers(&v(9)).is_empty());
+    }
+}
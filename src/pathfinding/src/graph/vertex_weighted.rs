@@ -0,0 +1,40 @@
+use std::collections::BTreeMap;
+
+use super::Graph;
+use crate::{Edge, Vertex};
+
+/// A [`Graph`] with costs on the vertices in addition to the edges, like terrain that is harder to cross on some cells of a grid.\
+/// The cost of a vertex is added to every edge leading into it, so a path pays for each vertex it enters but not for the one it starts from.
+/// Vertices without a weight cost nothing to enter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VertexWeightedGraph<G, V: Vertex, E: Edge> {
+    pub graph: G,
+    pub vertex_weights: BTreeMap<V, E>,
+}
+impl<G: Graph<V, E>, V: Vertex, E: Edge> VertexWeightedGraph<G, V, E> {
+    pub fn new(graph: G, vertex_weights: BTreeMap<V, E>) -> Self {
+        Self {
+            graph,
+            vertex_weights,
+        }
+    }
+    /// Gets the cost of entering `vertex`.
+    pub fn vertex_weight(&self, vertex: &V) -> E {
+        self.vertex_weights
+            .get(vertex)
+            .copied()
+            .unwrap_or_else(E::zero)
+    }
+}
+impl<G: Graph<V, E>, V: Vertex, E: Edge> Graph<V, E> for VertexWeightedGraph<G, V, E> {
+    fn neighbors(&self, vertex: &V) -> Vec<(V, E)> {
+        self.graph
+            .neighbors(vertex)
+            .into_iter()
+            .map(|(neighbor, weight)| (neighbor, weight + self.vertex_weight(&neighbor)))
+            .collect()
+    }
+    fn vertices(&self) -> Vec<V> {
+        self.graph.vertices()
+    }
+}
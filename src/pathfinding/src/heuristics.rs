@@ -0,0 +1,121 @@
+//! Ready-made heuristics for grids of [`Coord`]s, e.g. for [`a_star_to_any`](crate::pathfinding_algorithms::a_star_to_any).
+//!
+//! A heuristic only keeps A* optimal if it never overestimates the remaining cost, so pick the one that matches how the grid was built:
+//! - [`manhattan_distance`] for grids without diagonals
+//! - [`octile_distance`] for grids with diagonals that cost √2
+//! - [`chebyshev_distance`] for grids with diagonals that cost the same as straight moves
+//! - [`euclidean_distance`] for any of them, at the cost of expanding more vertices
+//!
+//! [`Heuristic`] picks one of them at runtime, and [`Heuristic::Zero`] turns A* into Dijkstra's algorithm.
+use crate::{Coord, Edge};
+
+/// The number of straight moves between `a` and `b`, `|dx| + |dy|`.
+pub fn manhattan_distance<E: Edge>(a: &Coord, b: &Coord) -> E {
+    let (dx, dy) = differences::<E>(a, b);
+    dx + dy
+}
+
+/// The straight-line distance between `a` and `b`, `sqrt(dx² + dy²)`.
+pub fn euclidean_distance<E: Edge>(a: &Coord, b: &Coord) -> E {
+    let (dx, dy) = differences::<E>(a, b);
+    dx.hypot(dy)
+}
+
+/// The number of moves between `a` and `b` when diagonal moves are allowed, `max(|dx|, |dy|)`.
+pub fn chebyshev_distance<E: Edge>(a: &Coord, b: &Coord) -> E {
+    let (dx, dy) = differences::<E>(a, b);
+    dx.max(dy)
+}
+
+/// The cost of moving from `a` to `b` when diagonal moves cost √2, `max(|dx|, |dy|) + (√2 - 1) * min(|dx|, |dy|)`.
+pub fn octile_distance<E: Edge>(a: &Coord, b: &Coord) -> E {
+    let (dx, dy) = differences::<E>(a, b);
+    let diagonal_extra = E::from(2.0).unwrap().sqrt() - E::one();
+    dx.max(dy) + diagonal_extra * dx.min(dy)
+}
+
+/// One of the heuristics of this module, e.g. for choosing the heuristic of [`a_star_with_heuristic`](crate::pathfinding_algorithms::a_star_with_heuristic) from a dropdown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Heuristic {
+    Manhattan,
+    #[default]
+    Euclidean,
+    Chebyshev,
+    Octile,
+    /// Estimates every remaining cost as zero, so A* expands the vertices in the same order as Dijkstra's algorithm.
+    Zero,
+}
+
+impl Heuristic {
+    pub const ALL: [Heuristic; 5] = [
+        Heuristic::Manhattan,
+        Heuristic::Euclidean,
+        Heuristic::Chebyshev,
+        Heuristic::Octile,
+        Heuristic::Zero,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Heuristic::Manhattan => "Manhattan",
+            Heuristic::Euclidean => "Euclidean",
+            Heuristic::Chebyshev => "Chebyshev",
+            Heuristic::Octile => "Octile",
+            Heuristic::Zero => "Zero (Dijkstra)",
+        }
+    }
+
+    /// Estimates the cost of moving from `a` to `b`.
+    pub fn estimate<E: Edge>(&self, a: &Coord, b: &Coord) -> E {
+        match self {
+            Heuristic::Manhattan => manhattan_distance(a, b),
+            Heuristic::Euclidean => euclidean_distance(a, b),
+            Heuristic::Chebyshev => chebyshev_distance(a, b),
+            Heuristic::Octile => octile_distance(a, b),
+            Heuristic::Zero => E::zero(),
+        }
+    }
+}
+
+/// Gets the absolute differences of the x and y coordinates.
+fn differences<E: Edge>(a: &Coord, b: &Coord) -> (E, E) {
+    (
+        E::from((a.x - b.x).abs()).unwrap(),
+        E::from((a.y - b.y).abs()).unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_on_known_pairs() {
+        let (a, b) = (Coord::new(1, -2), Coord::new(4, 2));
+
+        assert_eq!(manhattan_distance::<f64>(&a, &b), 7.0);
+        assert_eq!(euclidean_distance::<f64>(&a, &b), 5.0);
+        assert_eq!(chebyshev_distance::<f64>(&a, &b), 4.0);
+        assert!((octile_distance::<f64>(&a, &b) - (1.0 + 3.0 * 2.0_f64.sqrt())).abs() < 1e-12);
+
+        for heuristic in [
+            manhattan_distance::<f32>,
+            euclidean_distance::<f32>,
+            chebyshev_distance::<f32>,
+            octile_distance::<f32>,
+        ] {
+            assert_eq!(heuristic(&a, &a), 0.0);
+            assert_eq!(heuristic(&a, &b), heuristic(&b, &a));
+        }
+
+        // Straight lines are the same for every metric
+        let c = Coord::new(1, 6);
+        assert_eq!(manhattan_distance::<f32>(&a, &c), 8.0);
+        assert_eq!(euclidean_distance::<f32>(&a, &c), 8.0);
+        assert_eq!(chebyshev_distance::<f32>(&a, &c), 8.0);
+        assert_eq!(octile_distance::<f32>(&a, &c), 8.0);
+
+        assert_eq!(Heuristic::Manhattan.estimate::<f64>(&a, &b), 7.0);
+        assert_eq!(Heuristic::Zero.estimate::<f64>(&a, &b), 0.0);
+    }
+}
@@ -1,17 +1,23 @@
 //! This crate contains my implementations of pathfinding utilities and algorithms.
 //! I made them for my algorithm visualization website, so they most likely won't be suited for other projects.
+pub mod disjoint_set;
+pub mod error;
 pub mod graph;
+pub mod heuristics;
 pub mod maze_generation;
 pub mod pathfinding_algorithms;
+pub mod playback;
 
 use core::fmt;
 use graph::*;
 use num_traits::Float;
 use std::{
     collections::{BTreeMap, BTreeSet},
+    error::Error,
     fmt::{Debug, Display},
     hash::Hash,
     ops::Add,
+    str::FromStr,
 };
 
 /// A trait for structs that can calculate the distance from a to b.
@@ -167,6 +173,28 @@ impl fmt::Display for Coord {
         write!(f, "{},{}", self.x, self.y)
     }
 }
+impl FromStr for Coord {
+    type Err = ParseCoordError;
+
+    /// Parses a coordinate written like its [`Display`] output, e.g. `2,-3`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || ParseCoordError(text.to_string());
+        let (x, y) = text.split_once(',').ok_or_else(error)?;
+        match (x.trim().parse(), y.trim().parse()) {
+            (Ok(x), Ok(y)) => Ok(Self::new(x, y)),
+            _ => Err(error()),
+        }
+    }
+}
+/// An error returned when parsing a [`Coord`] from text that isn't two integers separated by a comma. It holds the text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseCoordError(pub String);
+impl Display for ParseCoordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a coordinate like 2,3, got \"{}\"", self.0)
+    }
+}
+impl Error for ParseCoordError {}
 impl Add for Coord {
     type Output = Self;
 
@@ -189,6 +217,8 @@ impl Distance for Coord {
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum VertexState {
     NotVisited,
+    /// Discovered as a neighbor of a visited vertex, but not visited yet.
+    Frontier,
     NewVisited,
     Visited,
 }
@@ -211,6 +241,18 @@ pub fn run_pathfinding<V: Vertex, E: Clone>(
     (res, duration)
 }
 
+/// Runs the algorithm and returns the found path along with the vertices that were expanded to find it, in the order of expansion.
+/// The explored region can be used to visualize how efficient a search is.
+pub fn search_with_exploration<V: Vertex, E: Clone>(
+    graph: &AdjacencyList<V, E>,
+    start: V,
+    end: V,
+    algorithm: PathfindingFunc<V, E>,
+) -> (Vec<V>, Vec<V>) {
+    let res = algorithm(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+    (res.path, res.explored)
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct PathfindingSteps<V: Vertex> {
     pub steps: Vec<PathfindingStep<V>>,
@@ -282,46 +324,14 @@ impl<V: Vertex> PathfindingStep<V> {
     }
 }
 
+/// Generates a grid graph where the `walls` are left out. See [`AdjacencyList::grid_with_obstacles`].
 pub fn generate_graph<E: Edge>(
     width: usize,
     height: usize,
     diagonals: bool,
     walls: &BTreeSet<Coord>,
 ) -> AdjacencyList<Coord, E> {
-    let mut graph = AdjacencyList::<Coord, E>::new(BTreeMap::new());
-
-    for y in 0..height as isize {
-        for x in 0..width as isize {
-            let vertex = Coord::new(x, y);
-            if walls.contains(&vertex) {
-                continue;
-            }
-            let mut neighbors = BTreeMap::<Coord, E>::new();
-            for coord in vertex.adjacent(diagonals) {
-                if walls.contains(&coord) {
-                    continue;
-                }
-                if coord.x >= 0
-                    && coord.x < width as isize
-                    && coord.y >= 0
-                    && coord.y < height as isize
-                {
-                    let a_diff = (coord.x - vertex.x).abs();
-                    let b_diff = (coord.y - vertex.y).abs();
-                    if a_diff == 0 || b_diff == 0 {
-                        // Horizontal or vertical costs 1
-                        neighbors.insert(coord, E::from(1).unwrap());
-                    } else {
-                        // Diagonal costs sqrt(2)
-                        neighbors.insert(coord, E::from(2.0_f32.sqrt()).unwrap());
-                    }
-                }
-            }
-            graph.add_vertex_with_undirected_edges(vertex, neighbors);
-        }
-    }
-
-    graph
+    AdjacencyList::grid_with_obstacles(width, height, diagonals, walls)
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -329,9 +339,45 @@ pub struct PathfindingResult<V: Vertex, E> {
     pub steps: PathfindingSteps<V>,
     pub path: Vec<V>,
     pub costs: GraphWeightMap<V, E>,
+    /// The expanded (closed) vertices in the order that the algorithm expanded them.
+    pub explored: Vec<V>,
+    /// The vertices that the algorithm added to its open set after each of the `explored` vertices was expanded, before the next one.
+    /// A vertex can be added again when a cheaper path to it is found.
+    pub discovered: Vec<Vec<V>>,
 }
 impl<V: Vertex, E> PathfindingResult<V, E> {
-    pub fn new(steps: PathfindingSteps<V>, path: Vec<V>, costs: GraphWeightMap<V, E>) -> Self {
-        Self { steps, path, costs }
+    pub fn new(
+        steps: PathfindingSteps<V>,
+        path: Vec<V>,
+        costs: GraphWeightMap<V, E>,
+        explored: Vec<V>,
+        discovered: Vec<Vec<V>>,
+    ) -> Self {
+        Self {
+            steps,
+            path,
+            costs,
+            explored,
+            discovered,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pathfinding_algorithms::{a_star, dijkstra};
+
+    #[test]
+    fn a_star_expands_fewer_vertices_than_dijkstra() {
+        let graph = generate_graph::<f32>(20, 20, false, &BTreeSet::new());
+        let (start, end) = (Coord::new(2, 3), Coord::new(17, 15));
+
+        let (a_star_path, a_star_explored) = search_with_exploration(&graph, start, end, a_star);
+        let (dijkstra_path, dijkstra_explored) =
+            search_with_exploration(&graph, start, end, dijkstra);
+
+        assert_eq!(a_star_path.len(), dijkstra_path.len());
+        assert!(a_star_explored.len() < dijkstra_explored.len());
     }
 }
@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{grid_neighbors, passages_to_graph};
+use crate::{graph::AdjacencyList, Coord};
+
+/// Carves a perfect maze with a randomized version of Kruskal's algorithm.
+///
+/// Every wall between two cells gets a random weight, and the passages are the edges of a minimum spanning tree of the grid.
+/// Unlike the other carving algorithms, the maze grows from everywhere at once as the passages join separate regions.
+/// The graph is laid out like the one of the [recursive backtracker](super::generate_maze).
+///
+/// The same `seed` always produces the same maze.
+pub fn randomized_kruskal(width: usize, height: usize, seed: u64) -> AdjacencyList<Coord, u32> {
+    passages_to_graph(width, height, &carve_passages(width, height, seed))
+}
+
+/// Gets the passages of the maze of [`randomized_kruskal`] in the order that they're carved.
+pub(super) fn carve_passages(width: usize, height: usize, seed: u64) -> Vec<(Coord, Coord)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut grid = AdjacencyList::new(BTreeMap::new());
+
+    let cells = (0..height as isize)
+        .flat_map(|y| (0..width as isize).map(move |x| Coord::new(x, y)))
+        .collect::<Vec<Coord>>();
+    for cell in &cells {
+        grid.add_vertex(*cell);
+    }
+    for cell in cells {
+        // Only the neighbors after the cell, so that every wall is weighted once
+        for next in grid_neighbors(cell, width, height).filter(|next| *next > cell) {
+            grid.add_edge_undirected(cell, next, rng.gen::<f64>());
+        }
+    }
+
+    // The spanning tree is built from the lightest edge up, so its edges are already in the order that they're carved
+    grid.kruskal_mst()
+        .edges
+        .into_iter()
+        .map(|(cell, next, _)| (cell, next))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_maze_is_a_spanning_tree() {
+        for seed in 0..20 {
+            let maze = randomized_kruskal(8, 5, seed);
+            assert_eq!(maze.hash_map.len(), 8 * 5);
+            assert_eq!(maze.edges().len() / 2, 8 * 5 - 1);
+            assert_eq!(maze.connected_components().len(), 1);
+        }
+        assert_eq!(randomized_kruskal(6, 6, 1), randomized_kruskal(6, 6, 1));
+    }
+}
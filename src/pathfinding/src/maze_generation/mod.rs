@@ -1,10 +1,17 @@
+mod kruskal;
+mod prim;
+mod recursive_backtracking;
 mod recursive_division;
+mod visited_set;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
+pub use kruskal::randomized_kruskal;
+pub use prim::randomized_prim;
+pub use recursive_backtracking::generate_maze;
 pub use recursive_division::recursive_division;
 
-use crate::Coord;
+use crate::{graph::AdjacencyList, Coord};
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct MazeGenerationStep {
@@ -26,3 +33,157 @@ impl MazeGenerationResult {
         Self { steps, walls }
     }
 }
+
+/// One of the maze generation algorithms of this module, e.g. for choosing the algorithm from a dropdown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MazeAlgorithm {
+    #[default]
+    RecursiveDivision,
+    RecursiveBacktracker,
+    Prim,
+    Kruskal,
+}
+
+impl MazeAlgorithm {
+    pub const ALL: [MazeAlgorithm; 4] = [
+        MazeAlgorithm::RecursiveDivision,
+        MazeAlgorithm::RecursiveBacktracker,
+        MazeAlgorithm::Prim,
+        MazeAlgorithm::Kruskal,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MazeAlgorithm::RecursiveDivision => "Recursive division",
+            MazeAlgorithm::RecursiveBacktracker => "Recursive backtracker",
+            MazeAlgorithm::Prim => "Prim's",
+            MazeAlgorithm::Kruskal => "Kruskal's",
+        }
+    }
+
+    /// Generates the walls of a maze on a `width` x `height` grid, recording the walls after every change as a step.\
+    /// The carving algorithms start from a grid full of walls and open the cells at odd coordinates and the passages between them,
+    /// so every open cell can reach every other one like in the mazes of [`recursive_division`].\
+    /// Recursive division isn't seeded, so only the carving algorithms always give the same maze for the same `seed`.
+    pub fn generate(&self, width: usize, height: usize, seed: u64) -> MazeGenerationResult {
+        let carve_passages = match self {
+            MazeAlgorithm::RecursiveDivision => return recursive_division(width, height, vec![]),
+            MazeAlgorithm::RecursiveBacktracker => recursive_backtracking::carve_passages,
+            MazeAlgorithm::Prim => prim::carve_passages,
+            MazeAlgorithm::Kruskal => kruskal::carve_passages,
+        };
+
+        let mut walls = (0..height as isize)
+            .flat_map(|y| (0..width as isize).map(move |x| Coord::new(x, y)))
+            .collect::<BTreeSet<Coord>>();
+        // Every cell of the maze takes two cells of the grid, one for itself and one for the wall or passage after it
+        let (maze_width, maze_height) = (width.saturating_sub(1) / 2, height.saturating_sub(1) / 2);
+        let to_grid = |cell: Coord| Coord::new(2 * cell.x + 1, 2 * cell.y + 1);
+
+        if maze_width > 0 && maze_height > 0 {
+            walls.remove(&to_grid(Coord::new(0, 0)));
+        }
+        let mut steps = vec![MazeGenerationStep::new(walls.clone())];
+
+        for (cell, next) in carve_passages(maze_width, maze_height, seed) {
+            let (cell, next) = (to_grid(cell), to_grid(next));
+            walls.remove(&cell);
+            walls.remove(&Coord::new((cell.x + next.x) / 2, (cell.y + next.y) / 2));
+            walls.remove(&next);
+            steps.push(MazeGenerationStep::new(walls.clone()));
+        }
+
+        MazeGenerationResult::new(steps, walls)
+    }
+}
+
+/// Gets the cells next to `cell` that are inside of a `width` x `height` grid, without the diagonal ones.
+fn grid_neighbors(cell: Coord, width: usize, height: usize) -> impl Iterator<Item = Coord> {
+    cell.adjacent(false).into_iter().filter(move |coord| {
+        coord.x >= 0 && coord.x < width as isize && coord.y >= 0 && coord.y < height as isize
+    })
+}
+
+/// Builds the graph of a maze where every cell of the `width` x `height` grid is a vertex
+/// and the `passages` are undirected edges with a weight of 1.
+fn passages_to_graph(
+    width: usize,
+    height: usize,
+    passages: &[(Coord, Coord)],
+) -> AdjacencyList<Coord, u32> {
+    let mut graph = AdjacencyList::new(BTreeMap::new());
+
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            graph.add_vertex(Coord::new(x, y));
+        }
+    }
+    for (cell, next) in passages {
+        graph.add_edge_undirected(*cell, *next, 1);
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets the open cells of the grid that can be reached from the first open cell.
+    fn reachable_cells(width: usize, height: usize, walls: &BTreeSet<Coord>) -> BTreeSet<Coord> {
+        let first = (0..height as isize)
+            .flat_map(|y| (0..width as isize).map(move |x| Coord::new(x, y)))
+            .find(|cell| !walls.contains(cell));
+        let mut reached = first.into_iter().collect::<BTreeSet<Coord>>();
+        let mut stack = reached.iter().copied().collect::<Vec<Coord>>();
+
+        while let Some(cell) = stack.pop() {
+            for next in grid_neighbors(cell, width, height) {
+                if !walls.contains(&next) && reached.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        reached
+    }
+
+    #[test]
+    fn every_open_cell_is_reachable() {
+        let (width, height) = (15, 11);
+        for algorithm in MazeAlgorithm::ALL {
+            for seed in 0..5 {
+                let maze = algorithm.generate(width, height, seed);
+                let open_cells = width * height - maze.walls.len();
+                assert!(open_cells > 0, "{}", algorithm.name());
+                assert_eq!(
+                    reachable_cells(width, height, &maze.walls).len(),
+                    open_cells,
+                    "{}",
+                    algorithm.name()
+                );
+                assert_eq!(maze.steps.last().unwrap().walls, maze.walls);
+            }
+        }
+    }
+
+    #[test]
+    fn carving_opens_one_passage_per_step() {
+        for algorithm in [
+            MazeAlgorithm::RecursiveBacktracker,
+            MazeAlgorithm::Prim,
+            MazeAlgorithm::Kruskal,
+        ] {
+            let maze = algorithm.generate(9, 7, 2);
+            // A 4 x 3 maze is a spanning tree with 11 passages, and every cell and passage is opened
+            assert_eq!(maze.steps.len(), 12);
+            assert_eq!(maze.walls.len(), 9 * 7 - 12 - 11);
+            assert_eq!(maze, algorithm.generate(9, 7, 2));
+        }
+
+        // A maze that only fits one cell still opens it
+        let maze = MazeAlgorithm::Prim.generate(3, 3, 0);
+        assert_eq!(maze.walls.len(), 8);
+        assert!(MazeAlgorithm::Kruskal.generate(0, 0, 0).walls.is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{grid_neighbors, passages_to_graph, visited_set::VisitedSet};
+use crate::{graph::AdjacencyList, Coord};
+
+/// Carves a perfect maze with a randomized version of Prim's algorithm.
+///
+/// The maze grows from the top left cell by carving a passage through a random wall on its edge at every step,
+/// which gives many short dead ends instead of the long corridors of the [recursive backtracker](super::generate_maze).
+/// The graph is laid out like the one of the recursive backtracker.
+///
+/// The same `seed` always produces the same maze.
+pub fn randomized_prim(width: usize, height: usize, seed: u64) -> AdjacencyList<Coord, u32> {
+    passages_to_graph(width, height, &carve_passages(width, height, seed))
+}
+
+/// Gets the passages of the maze of [`randomized_prim`] in the order that they're carved.
+pub(super) fn carve_passages(width: usize, height: usize, seed: u64) -> Vec<(Coord, Coord)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut passages = vec![];
+
+    if width == 0 || height == 0 {
+        return passages;
+    }
+
+    let index = |cell: &Coord| cell.y as usize * width + cell.x as usize;
+    let start = Coord::new(0, 0);
+    let mut visited = VisitedSet::new(width * height);
+    visited.insert(index(&start));
+    // The walls between the carved cells and their uncarved neighbors
+    let mut frontier = grid_neighbors(start, width, height)
+        .map(|next| (start, next))
+        .collect::<Vec<(Coord, Coord)>>();
+
+    while !frontier.is_empty() {
+        let (cell, next) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+
+        // The wall may lead to a cell that was carved after the wall was added
+        if visited.insert(index(&next)) {
+            passages.push((cell, next));
+            frontier.extend(
+                grid_neighbors(next, width, height)
+                    .filter(|coord| !visited.contains(index(coord)))
+                    .map(|coord| (next, coord)),
+            );
+        }
+    }
+
+    passages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_maze_is_a_spanning_tree() {
+        for seed in 0..20 {
+            let maze = randomized_prim(8, 5, seed);
+            assert_eq!(maze.hash_map.len(), 8 * 5);
+            assert_eq!(maze.edges().len() / 2, 8 * 5 - 1);
+            assert_eq!(maze.connected_components().len(), 1);
+        }
+        assert_eq!(randomized_prim(6, 6, 1), randomized_prim(6, 6, 1));
+    }
+}
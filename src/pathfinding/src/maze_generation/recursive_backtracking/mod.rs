@@ -0,0 +1,90 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use super::{grid_neighbors, passages_to_graph, visited_set::VisitedSet};
+use crate::{graph::AdjacencyList, Coord};
+
+/// Carves a perfect maze with a randomized depth-first search, also known as the recursive backtracker.
+///
+/// Every cell of the `width` x `height` grid is a vertex at `(x, y)`, and passages between adjacent cells are undirected edges with a weight of 1.
+/// Missing edges are walls. Because the maze is perfect, there is exactly one path between any two cells, so the graph is a spanning tree of the grid.
+///
+/// The same `seed` always produces the same maze.
+pub fn generate_maze(width: usize, height: usize, seed: u64) -> AdjacencyList<Coord, u32> {
+    passages_to_graph(width, height, &carve_passages(width, height, seed))
+}
+
+/// Gets the passages of the maze of [`generate_maze`] in the order that they're carved.
+pub(super) fn carve_passages(width: usize, height: usize, seed: u64) -> Vec<(Coord, Coord)> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut passages = vec![];
+
+    if width == 0 || height == 0 {
+        return passages;
+    }
+
+    // Every cell of the grid has an index, so the visited cells fit in a bit set
+    let index = |cell: &Coord| cell.y as usize * width + cell.x as usize;
+    let start = Coord::new(0, 0);
+    let mut visited = VisitedSet::new(width * height);
+    visited.insert(index(&start));
+    let mut stack = vec![start];
+
+    while let Some(&cell) = stack.last() {
+        let unvisited_neighbors = grid_neighbors(cell, width, height)
+            .filter(|coord| !visited.contains(index(coord)))
+            .collect::<Vec<Coord>>();
+
+        if let Some(&next) = unvisited_neighbors.choose(&mut rng) {
+            // Carve a passage to the next cell
+            passages.push((cell, next));
+            visited.insert(index(&next));
+            stack.push(next);
+        } else {
+            // Dead end, backtrack
+            stack.pop();
+        }
+    }
+
+    passages
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use super::*;
+
+    #[test]
+    fn generated_maze_is_a_spanning_tree() {
+        for seed in 0..20 {
+            let maze = generate_maze(9, 6, seed);
+            let vertex_count = maze.hash_map.len();
+            let edge_count = maze.hash_map.values().map(BTreeMap::len).sum::<usize>() / 2;
+
+            // A connected graph with one edge less than it has vertices has no cycles
+            assert_eq!(vertex_count, 9 * 6);
+            assert_eq!(edge_count, vertex_count - 1);
+
+            let mut reached = BTreeSet::from([Coord::new(0, 0)]);
+            let mut stack = vec![Coord::new(0, 0)];
+            while let Some(cell) = stack.pop() {
+                for (next, weight) in &maze.hash_map[&cell] {
+                    // Passages only lead to adjacent cells
+                    assert_eq!((cell.x - next.x).abs() + (cell.y - next.y).abs(), 1);
+                    assert_eq!(*weight, 1);
+                    if reached.insert(*next) {
+                        stack.push(*next);
+                    }
+                }
+            }
+            assert_eq!(reached.len(), vertex_count);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_maze() {
+        assert_eq!(generate_maze(7, 7, 3), generate_maze(7, 7, 3));
+        assert_ne!(generate_maze(7, 7, 3), generate_maze(7, 7, 4));
+        assert!(generate_maze(0, 5, 3).hash_map.is_empty());
+    }
+}
@@ -0,0 +1,65 @@
+//! A set for tracking the cells that the maze generators have visited, which map to a dense range of indices.
+
+/// A set of the indices `0..capacity` stored as one bit per index.\
+/// Compared to a `BTreeSet` or a `HashSet` of the vertices, this takes 1/64 of a word per vertex and needs no hashing or comparisons,
+/// which adds up for large mazes. The generators map every cell to the index `y * width + x`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct VisitedSet {
+    words: Vec<u64>,
+}
+impl VisitedSet {
+    /// Creates an empty set that can hold the indices `0..capacity`.
+    pub(super) fn new(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(64)],
+        }
+    }
+    /// Adds `index` to the set and returns `true` if it wasn't there before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is outside of the capacity.
+    pub(super) fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (&mut self.words[index / 64], 1 << (index % 64));
+        let inserted = *word & bit == 0;
+        *word |= bit;
+        inserted
+    }
+    /// Checks if `index` is in the set. Indices outside of the capacity are never in it.
+    pub(super) fn contains(&self, index: usize) -> bool {
+        self.words
+            .get(index / 64)
+            .is_some_and(|word| word & (1 << (index % 64)) != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn matches_hash_set_on_random_operations() {
+        let mut rng = StdRng::seed_from_u64(7);
+        // Not a multiple of 64, so the last word is only partly used
+        let capacity = 1_000;
+        let mut visited = VisitedSet::new(capacity);
+        let mut baseline = HashSet::new();
+
+        for _ in 0..5_000 {
+            let index = rng.gen_range(0..capacity);
+            if rng.gen_bool(0.5) {
+                assert_eq!(visited.insert(index), baseline.insert(index));
+            } else {
+                assert_eq!(visited.contains(index), baseline.contains(&index));
+            }
+        }
+
+        assert!((0..capacity).all(|index| visited.contains(index) == baseline.contains(&index)));
+        assert!(!visited.contains(capacity + 100));
+        assert!(!VisitedSet::new(0).contains(0));
+    }
+}
@@ -1,96 +1,158 @@
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BinaryHeap, HashMap},
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet},
     hash::Hash,
 };
 
-use crate::{graph::AdjacencyList, *};
+use crate::{graph::Graph, *};
 
 pub fn a_star<V: Vertex, E: Edge>(
-    adjacency_list: AdjacencyList<V, E>,
+    graph: impl Graph<V, E>,
     start: V,
     end: V,
+    steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    a_star_with_heuristic(graph, start, end, |vertex| vertex.distance::<E>(end), steps)
+}
+
+/// A* that estimates the remaining cost from a vertex to `end` with the given `heuristic`,
+/// e.g. one of the [`heuristics`](crate::heuristics) or a [`Heuristic`](crate::heuristics::Heuristic) chosen at runtime.\
+/// The `explored` vertices of the result are in the order that they were expanded, so a heuristic that never estimates anything
+/// expands the same vertices as Dijkstra's algorithm, and a closer estimate expands fewer of them.
+pub fn a_star_with_heuristic<V: Vertex, E: Edge>(
+    graph: impl Graph<V, E>,
+    start: V,
+    end: V,
+    heuristic: impl Fn(&V) -> E,
+    steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    a_star_to_any(graph, start, |vertex| *vertex == end, heuristic, steps)
+}
+
+/// A* with the `heuristic` multiplied by `epsilon`, which makes the search head more greedily towards the goal and usually expand far fewer vertices.\
+/// With an admissible heuristic, the cost of the returned path is at most `epsilon` times the cost of the shortest path,
+/// and `epsilon` of 1.0 is the same as ordinary A*.
+///
+/// ## Panics
+///
+/// Panics if `epsilon` is less than 1.0.
+pub fn a_star_weighted<V: Vertex, E: Edge>(
+    graph: impl Graph<V, E>,
+    start: V,
+    end: V,
+    heuristic: impl Fn(&V) -> E,
+    epsilon: f64,
+    steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    assert!(epsilon >= 1.0, "epsilon must be at least 1.0");
+    let epsilon = E::from(epsilon).unwrap();
+    a_star_to_any(
+        graph,
+        start,
+        |vertex| *vertex == end,
+        |vertex| heuristic(vertex) * epsilon,
+        steps,
+    )
+}
+
+/// A* that stops at the first vertex satisfying `is_goal`, e.g. the nearest of several exits.\
+/// The `heuristic` must be admissible relative to every goal, for example the distance to the closest goal.
+/// Otherwise the returned path isn't guaranteed to be the shortest one.
+///
+/// The path is ordered from `start` to the goal that was reached, like the paths of the other algorithms.
+pub fn a_star_to_any<V: Vertex, E: Edge>(
+    graph: impl Graph<V, E>,
+    start: V,
+    is_goal: impl Fn(&V) -> bool,
+    heuristic: impl Fn(&V) -> E,
     mut steps: PathfindingSteps<V>,
 ) -> PathfindingResult<V, E> {
     // Discovered vertices that may need to be expanded, beginning with source vertex
     let mut open_set = BinaryHeap::new();
-    open_set.push(VertexWithPriority::new(start, start.distance::<E>(end)));
+    open_set.push(VertexWithPriority::new(start, heuristic(&start)));
 
-    // Stores the cost values and "parents" of vertices
+    // Stores the cost values and "parents" of vertices.
+    // Cells are created when vertices are discovered, so the graph's vertices don't have to be known in advance
     let mut cells = HashMap::<V, Cell<V, E>>::new();
+    cells.insert(start, Cell::new(start, None, E::zero(), heuristic(&start)));
 
-    // Initialize "cells" with no parents and infinite distance values from source vertex
-    for vertex in adjacency_list.hash_map.keys() {
-        cells.insert(
-            *vertex,
-            Cell::new(*vertex, None, E::max_value(), vertex.distance::<E>(end)),
-        );
-    }
-
-    // Initialize source vertex costs correctly
-    cells.insert(
-        start,
-        Cell::new(start, None, E::zero(), start.distance::<E>(end)),
-    );
+    // Expanded vertices in the order of expansion
+    let mut explored = vec![];
+    let mut discovered: Vec<Vec<V>> = vec![];
+    let mut closed = HashSet::new();
 
     // At each step we get the vertex with the smallest estimated cost from the `open_set`
     while let Some(curr) = open_set.pop() {
-        // Return when target is found
-        if curr.vertex == end {
+        // Return when a target is found
+        if is_goal(&curr.vertex) {
             return PathfindingResult::new(
                 steps,
                 reconstruct_path::<V, E>(cells, curr.vertex),
                 BTreeMap::new(),
+                explored,
+                discovered,
             );
         }
 
-        if let Some(neighbors) = adjacency_list.get_neighbors(&curr.vertex) {
-            let curr_g = cells.get(&curr.vertex).unwrap().g;
+        if closed.insert(curr.vertex) {
+            explored.push(curr.vertex);
+            discovered.push(vec![]);
+        }
 
-            for (neighbor, cost) in neighbors.clone() {
-                // Distance from start to neighbor
-                let tentative_g_dist = curr_g + cost;
-                let neighbor_cell = cells.get_mut(&neighbor).unwrap();
+        let curr_g = cells.get(&curr.vertex).unwrap().g;
 
-                // Shorter path from start to neighbor found
-                if tentative_g_dist < neighbor_cell.g {
-                    // Update neighbor's `parent` and `g` values
-                    neighbor_cell.set_parent(Some(curr.vertex));
-                    neighbor_cell.set_g(tentative_g_dist.into());
+        for (neighbor, cost) in graph.neighbors(&curr.vertex) {
+            // Distance from start to neighbor
+            let tentative_g_dist = curr_g + cost;
+            // Undiscovered vertices have no parent and an infinite distance from the source vertex
+            let neighbor_cell = cells
+                .entry(neighbor)
+                .or_insert_with(|| Cell::new(neighbor, None, E::max_value(), heuristic(&neighbor)));
 
-                    steps.init_step();
-                    steps.insert_state_to_last_step(neighbor, VertexState::NewVisited);
+            // Shorter path from start to neighbor found
+            if tentative_g_dist < neighbor_cell.g {
+                // Update neighbor's `parent` and `g` values
+                neighbor_cell.set_parent(Some(curr.vertex));
+                neighbor_cell.set_g(tentative_g_dist.into());
 
-                    // Add neighbor to `open_set`
-                    open_set.push(VertexWithPriority::new(
-                        neighbor_cell.vertex,
-                        (tentative_g_dist.to_owned() + neighbor_cell.h.to_owned()).into(),
-                    ));
+                steps.init_step();
+                steps.insert_state_to_last_step(neighbor, VertexState::NewVisited);
+
+                // Add neighbor to `open_set`
+                open_set.push(VertexWithPriority::new(
+                    neighbor_cell.vertex,
+                    (tentative_g_dist.to_owned() + neighbor_cell.h.to_owned()).into(),
+                ));
+                // A vertex that is expanded again adds to the open set along with the latest expansion
+                if let Some(added) = discovered.last_mut() {
+                    added.push(neighbor);
                 }
             }
         }
     }
 
     // `open_set` is empty and target was never reached
-    PathfindingResult::new(steps, vec![], BTreeMap::new())
+    PathfindingResult::new(steps, vec![], BTreeMap::new(), explored, discovered)
 }
 
+/// Follows the parents back from `curr` and returns the path from the start to `curr`.
 fn reconstruct_path<V: Vertex, E: Edge>(cells: HashMap<V, Cell<V, E>>, mut curr: V) -> Vec<V> {
     let mut path = vec![curr];
     while let Some(parent) = cells.get(&curr).unwrap().parent {
         curr = parent;
         path.push(curr);
     }
+    path.reverse();
     path
 }
 
 #[derive(Clone, PartialEq)]
-struct VertexWithPriority<V: Ord + Eq + Hash, E: PartialOrd> {
-    vertex: V,
-    priority: E,
+pub(crate) struct VertexWithPriority<V: Ord + Eq + Hash, E: PartialOrd> {
+    pub vertex: V,
+    pub priority: E,
 }
 impl<V: Ord + Eq + Hash, E: PartialOrd> VertexWithPriority<V, E> {
-    fn new(vertex: V, priority: E) -> Self {
+    pub fn new(vertex: V, priority: E) -> Self {
         Self { vertex, priority }
     }
 }
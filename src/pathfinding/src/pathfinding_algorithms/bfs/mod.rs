@@ -0,0 +1,46 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::{graph::Graph, Edge, PathfindingResult, PathfindingSteps, Vertex, VertexState};
+
+/// Breadth-first search, which finds the path with the fewest edges and ignores the weights.
+pub fn bfs<V: Vertex, E: Edge>(
+    graph: impl Graph<V, E>,
+    start: V,
+    end: V,
+    mut steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    let mut queue = VecDeque::from([start]);
+    // The vertex that each discovered vertex was first reached from
+    let mut vertex_parents = HashMap::<V, V>::new();
+    let mut discovered = HashSet::from([start]);
+    let mut explored = vec![];
+    // The vertices queued after each expansion
+    let mut queued: Vec<Vec<V>> = vec![];
+
+    while let Some(vertex) = queue.pop_front() {
+        if vertex == end {
+            let mut path = vec![end];
+            while let Some(parent) = vertex_parents.get(path.last().unwrap()) {
+                path.push(*parent);
+            }
+            path.reverse();
+            return PathfindingResult::new(steps, path, BTreeMap::new(), explored, queued);
+        }
+
+        explored.push(vertex);
+        queued.push(vec![]);
+        steps.init_step();
+        steps.insert_state_to_last_step(vertex, VertexState::NewVisited);
+
+        for (neighbor, _) in graph.neighbors(&vertex) {
+            // Unlike in DFS, vertices are marked before they are queued, so each one is only queued once
+            if discovered.insert(neighbor) {
+                vertex_parents.insert(neighbor, vertex);
+                queue.push_back(neighbor);
+                queued.last_mut().unwrap().push(neighbor);
+            }
+        }
+    }
+
+    PathfindingResult::new(steps, vec![], BTreeMap::new(), explored, queued)
+}
@@ -0,0 +1,364 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use super::{BinaryHeapFrontier, PriorityFrontier};
+use crate::{graph::AdjacencyList, Edge, Vertex};
+
+type Edges<V, E> = BTreeMap<V, BTreeMap<V, E>>;
+
+/// A graph that has been preprocessed for answering many shortest path queries much faster than running Dijkstra for each of them.
+///
+/// The vertices are contracted one at a time from the least to the most important one.
+/// Contracting a vertex removes it from the graph and adds shortcut edges between its neighbors wherever it was on the only shortest path between them.
+/// A query then only has to search upwards in the order from both ends, which visits a small part of the graph.
+///
+/// The graph must not change after preprocessing, and like Dijkstra, the weights must not be negative.
+/// Queries give the same costs as Dijkstra, although the sums may be rounded differently with fractional weights,
+/// and when there are several shortest paths, the returned path may be a different one.
+///
+/// ## Example
+///
+/// ```rust
+/// use pathfinding::{graph::AdjacencyList, pathfinding_algorithms::ContractionHierarchy, Coord};
+///
+/// let mut graph = AdjacencyList::<Coord, f32>::default();
+/// graph.add_edge_undirected(Coord::new(0, 0), Coord::new(1, 0), 1.0);
+/// graph.add_edge_undirected(Coord::new(1, 0), Coord::new(2, 0), 2.0);
+///
+/// let hierarchy = ContractionHierarchy::preprocess(&graph);
+/// let (path, cost) = hierarchy.query(Coord::new(0, 0), Coord::new(2, 0)).unwrap();
+///
+/// assert_eq!(path, vec![Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0)]);
+/// assert_eq!(cost, 3.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ContractionHierarchy<V: Vertex, E: Edge> {
+    /// The edges, including shortcuts, that lead to a vertex that was contracted later.
+    upward: Edges<V, E>,
+    /// The reversed edges, including shortcuts, that come from a vertex that was contracted later.
+    downward: Edges<V, E>,
+    /// The contracted vertex that each shortcut `(from, to)` skips over.
+    shortcuts: BTreeMap<(V, V), V>,
+}
+
+impl<V: Vertex, E: Edge> ContractionHierarchy<V, E> {
+    /// Contracts every vertex of the `graph` and stores the resulting shortcuts.
+    pub fn preprocess(graph: &AdjacencyList<V, E>) -> Self {
+        // The remaining graph in both directions. Self-loops are never on a shortest path, so they are left out.
+        let mut outgoing: Edges<V, E> = BTreeMap::new();
+        let mut incoming: Edges<V, E> = BTreeMap::new();
+        for vertex in graph.ordered_vertices() {
+            outgoing.entry(*vertex).or_default();
+            incoming.entry(*vertex).or_default();
+        }
+        for (from, to, weight) in graph.edges() {
+            outgoing.entry(to).or_default();
+            incoming.entry(from).or_default();
+            if from != to {
+                outgoing.entry(from).or_default().insert(to, weight);
+                incoming.entry(to).or_default().insert(from, weight);
+            }
+        }
+
+        let mut edges = outgoing.clone();
+        let mut shortcuts = BTreeMap::new();
+        let mut rank = BTreeMap::new();
+        let mut contracted_neighbors = BTreeMap::<V, i64>::new();
+
+        let importance = |vertex: V,
+                          new_shortcuts: &[(V, V, E)],
+                          outgoing: &Edges<V, E>,
+                          incoming: &Edges<V, E>,
+                          contracted_neighbors: &BTreeMap<V, i64>| {
+            // Contracting vertices that add few shortcuts first keeps the hierarchy small,
+            // and preferring vertices with few contracted neighbors spreads the contraction evenly
+            new_shortcuts.len() as i64
+                - outgoing[&vertex].len() as i64
+                - incoming[&vertex].len() as i64
+                + contracted_neighbors.get(&vertex).copied().unwrap_or(0)
+        };
+
+        let mut queue = outgoing
+            .keys()
+            .map(|vertex| {
+                let new_shortcuts = find_shortcuts(&outgoing, &incoming, *vertex);
+                let priority = importance(
+                    *vertex,
+                    &new_shortcuts,
+                    &outgoing,
+                    &incoming,
+                    &contracted_neighbors,
+                );
+                (priority, *vertex)
+            })
+            .collect::<BTreeSet<(i64, V)>>();
+
+        while let Some((_, vertex)) = queue.pop_first() {
+            // The importance may have changed since the vertex was queued, so it's only contracted if it's still the least important one
+            let new_shortcuts = find_shortcuts(&outgoing, &incoming, vertex);
+            let priority = importance(
+                vertex,
+                &new_shortcuts,
+                &outgoing,
+                &incoming,
+                &contracted_neighbors,
+            );
+            if queue.first().is_some_and(|(next, _)| priority > *next) {
+                queue.insert((priority, vertex));
+                continue;
+            }
+
+            rank.insert(vertex, rank.len());
+
+            for (from, to, weight) in new_shortcuts {
+                outgoing.entry(from).or_default().insert(to, weight);
+                incoming.entry(to).or_default().insert(from, weight);
+                edges.entry(from).or_default().insert(to, weight);
+                shortcuts.insert((from, to), vertex);
+            }
+
+            let successors = outgoing.remove(&vertex).unwrap_or_default();
+            let predecessors = incoming.remove(&vertex).unwrap_or_default();
+            for neighbor in successors.keys() {
+                if let Some(edges) = incoming.get_mut(neighbor) {
+                    edges.remove(&vertex);
+                }
+            }
+            for neighbor in predecessors.keys() {
+                if let Some(edges) = outgoing.get_mut(neighbor) {
+                    edges.remove(&vertex);
+                }
+            }
+            for neighbor in successors.keys().chain(predecessors.keys()) {
+                *contracted_neighbors.entry(*neighbor).or_default() += 1;
+            }
+        }
+
+        let mut upward: Edges<V, E> = rank.keys().map(|v| (*v, BTreeMap::new())).collect();
+        let mut downward = upward.clone();
+        for (from, neighbors) in edges {
+            for (to, weight) in neighbors {
+                if rank[&from] < rank[&to] {
+                    upward.entry(from).or_default().insert(to, weight);
+                } else {
+                    downward.entry(to).or_default().insert(from, weight);
+                }
+            }
+        }
+
+        Self {
+            upward,
+            downward,
+            shortcuts,
+        }
+    }
+
+    /// Finds a shortest path from `start` to `goal` and returns it along with its cost, or `None` if the goal can't be reached.
+    pub fn query(&self, start: V, goal: V) -> Option<(Vec<V>, E)> {
+        if !self.upward.contains_key(&start) || !self.upward.contains_key(&goal) {
+            return None;
+        }
+
+        let (forward_distances, forward_parents) = upward_search(&self.upward, start);
+        let (backward_distances, backward_parents) = upward_search(&self.downward, goal);
+
+        // The shortest path goes up from the start and then down to the goal, so both searches reach its highest vertex
+        let mut meeting = None;
+        for (vertex, forward_distance) in forward_distances.iter() {
+            if let Some(backward_distance) = backward_distances.get(vertex) {
+                let cost = *forward_distance + *backward_distance;
+                if meeting.is_none_or(|(_, best)| cost < best) {
+                    meeting = Some((*vertex, cost));
+                }
+            }
+        }
+        let (meeting, cost) = meeting?;
+
+        let mut hierarchy_path = vec![meeting];
+        while let Some(parent) = forward_parents.get(hierarchy_path.last().unwrap()) {
+            hierarchy_path.push(*parent);
+        }
+        hierarchy_path.reverse();
+        while let Some(parent) = backward_parents.get(hierarchy_path.last().unwrap()) {
+            hierarchy_path.push(*parent);
+        }
+
+        let mut path = vec![start];
+        for pair in hierarchy_path.windows(2) {
+            self.unpack(pair[0], pair[1], &mut path);
+        }
+
+        Some((path, cost))
+    }
+
+    /// Replaces the shortcuts on the edge from `from` to `to` with the vertices that they skip over,
+    /// and pushes every vertex after `from` to the `path`.
+    fn unpack(&self, from: V, to: V, path: &mut Vec<V>) {
+        match self.shortcuts.get(&(from, to)) {
+            Some(middle) => {
+                self.unpack(from, *middle, path);
+                self.unpack(*middle, to, path);
+            }
+            None => path.push(to),
+        }
+    }
+}
+
+/// Finds the shortcuts that are needed to keep the shortest paths between the neighbors of `vertex` when it's removed.\
+/// A shortcut is only needed if there isn't another path, a witness, that is at most as long as the path through `vertex`.
+fn find_shortcuts<V: Vertex, E: Edge>(
+    outgoing: &Edges<V, E>,
+    incoming: &Edges<V, E>,
+    vertex: V,
+) -> Vec<(V, V, E)> {
+    let mut shortcuts = vec![];
+
+    for (from, to_vertex) in incoming[&vertex].iter() {
+        let targets = outgoing[&vertex]
+            .iter()
+            .filter(|(to, _)| *to != from)
+            .map(|(to, from_vertex)| (*to, *to_vertex + *from_vertex))
+            .collect::<Vec<(V, E)>>();
+        let Some(limit) = targets.iter().map(|(_, cost)| *cost).reduce(E::max) else {
+            continue;
+        };
+
+        let witnesses = witness_search(outgoing, *from, vertex, limit);
+        for (to, cost) in targets {
+            if witnesses.get(&to).is_none_or(|witness| *witness > cost) {
+                shortcuts.push((*from, to, cost));
+            }
+        }
+    }
+
+    shortcuts
+}
+
+/// Finds the distances from `start` to the vertices that are at most `limit` away without going through `skip`.
+fn witness_search<V: Vertex, E: Edge>(
+    outgoing: &Edges<V, E>,
+    start: V,
+    skip: V,
+    limit: E,
+) -> BTreeMap<V, E> {
+    let mut distances = BTreeMap::from([(start, E::zero())]);
+    let mut visited = HashSet::new();
+    let mut to_visit = BinaryHeapFrontier::default();
+    to_visit.push(start, E::zero());
+
+    while let Some((vertex, distance)) = to_visit.pop() {
+        if distance > limit {
+            break;
+        }
+        if !visited.insert(vertex) {
+            continue;
+        }
+        for (neighbor, weight) in outgoing[&vertex].iter() {
+            let new_distance = distance + *weight;
+            if *neighbor != skip
+                && distances
+                    .get(neighbor)
+                    .is_none_or(|current| new_distance < *current)
+            {
+                distances.insert(*neighbor, new_distance);
+                to_visit.push(*neighbor, new_distance);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Runs Dijkstra from `start` over the given edges and returns the distances and the parent of each reached vertex.
+fn upward_search<V: Vertex, E: Edge>(
+    edges: &Edges<V, E>,
+    start: V,
+) -> (BTreeMap<V, E>, BTreeMap<V, V>) {
+    let mut distances = BTreeMap::from([(start, E::zero())]);
+    let mut parents = BTreeMap::new();
+    let mut visited = HashSet::new();
+    let mut to_visit = BinaryHeapFrontier::default();
+    to_visit.push(start, E::zero());
+
+    while let Some((vertex, distance)) = to_visit.pop() {
+        if !visited.insert(vertex) {
+            continue;
+        }
+        for (neighbor, weight) in edges[&vertex].iter() {
+            let new_distance = distance + *weight;
+            if distances
+                .get(neighbor)
+                .is_none_or(|current| new_distance < *current)
+            {
+                distances.insert(*neighbor, new_distance);
+                parents.insert(*neighbor, vertex);
+                to_visit.push(*neighbor, new_distance);
+            }
+        }
+    }
+
+    (distances, parents)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{generate_graph, pathfinding_algorithms::dijkstra, Coord, PathfindingSteps};
+
+    #[test]
+    fn queries_match_dijkstra_on_medium_graph() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let walls = (0..60)
+            .map(|_| Coord::new(rng.gen_range(0..15), rng.gen_range(0..15)))
+            .collect::<BTreeSet<Coord>>();
+        let mut graph = generate_graph::<f32>(15, 15, true, &walls);
+        // Whole number weights that differ in each direction, so that the sums are exact and the graph is really directed
+        for neighbors in graph.hash_map.values_mut() {
+            for weight in neighbors.values_mut() {
+                *weight = rng.gen_range(1..=9) as f32;
+            }
+        }
+
+        let hierarchy = ContractionHierarchy::preprocess(&graph);
+        let vertices = graph.ordered_vertices().copied().collect::<Vec<Coord>>();
+
+        for _ in 0..50 {
+            let start = vertices[rng.gen_range(0..vertices.len())];
+            let goal = vertices[rng.gen_range(0..vertices.len())];
+            let expected = dijkstra(graph.clone(), start, goal, PathfindingSteps::new(vec![]));
+
+            match hierarchy.query(start, goal) {
+                Some((path, cost)) => {
+                    assert_eq!(Some(&cost), expected.costs.get(&goal));
+                    assert!(graph.is_valid_path(&path));
+                    assert_eq!(path.first(), Some(&start));
+                    assert_eq!(path.last(), Some(&goal));
+                    let path_cost: f32 = path
+                        .windows(2)
+                        .map(|pair| graph.hash_map[&pair[0]][&pair[1]])
+                        .sum();
+                    assert_eq!(path_cost, cost);
+                }
+                None => assert!(expected.path.is_empty() && start != goal),
+            }
+        }
+    }
+
+    #[test]
+    fn unreachable_and_missing_vertices() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        let (a, b, c) = (Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0));
+        graph.add_edge_directed(a, b, 1.0);
+        graph.add_vertex(c);
+
+        let hierarchy = ContractionHierarchy::preprocess(&graph);
+        assert_eq!(hierarchy.query(a, b), Some((vec![a, b], 1.0)));
+        assert_eq!(hierarchy.query(b, a), None);
+        assert_eq!(hierarchy.query(a, c), None);
+        assert_eq!(hierarchy.query(c, c), Some((vec![c], 0.0)));
+        assert_eq!(hierarchy.query(a, Coord::new(5, 5)), None);
+    }
+}
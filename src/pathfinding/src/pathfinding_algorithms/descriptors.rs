@@ -0,0 +1,114 @@
+use crate::{Edge, PathfindingFunc, Vertex};
+
+use super::{a_star, bfs, dfs, dijkstra};
+
+/// Information about a pathfinding algorithm along with the function that runs it, e.g. for listing the algorithms in a dropdown.
+#[derive(Clone, Debug)]
+pub struct AlgorithmDescriptor<V: Vertex, E: Edge> {
+    pub name: &'static str,
+    /// A lowercase identifier that can be used in URLs.
+    pub slug: &'static str,
+    /// The worst-case time complexity in terms of the number of vertices `V` and edges `E`.
+    pub time_complexity: &'static str,
+    /// The worst-case extra space needed in terms of the number of vertices `V`.
+    pub space_complexity: &'static str,
+    /// Whether the returned path is always the cheapest one.
+    pub optimal: bool,
+    /// Whether the algorithm still gives correct results when some edges have negative weights.
+    pub supports_negative_weights: bool,
+    /// Whether the algorithm works on directed graphs. All algorithms work on undirected graphs.
+    pub supports_directed: bool,
+    pub find_path: PathfindingFunc<V, E>,
+}
+
+/// Gets the descriptors of the pathfinding algorithms that work on any graph.
+pub fn algorithm_descriptors<V: Vertex, E: Edge>() -> Vec<AlgorithmDescriptor<V, E>> {
+    vec![
+        AlgorithmDescriptor {
+            name: "A*",
+            slug: "a*",
+            time_complexity: "O((V + E) log V)",
+            space_complexity: "O(V)",
+            optimal: true,
+            supports_negative_weights: false,
+            supports_directed: true,
+            find_path: a_star::<V, E>,
+        },
+        AlgorithmDescriptor {
+            name: "Dijkstra",
+            slug: "dijkstra",
+            time_complexity: "O((V + E) log V)",
+            space_complexity: "O(V)",
+            optimal: true,
+            supports_negative_weights: false,
+            supports_directed: true,
+            find_path: dijkstra::<V, E>,
+        },
+        AlgorithmDescriptor {
+            name: "BFS",
+            slug: "bfs",
+            time_complexity: "O(V + E)",
+            space_complexity: "O(V)",
+            // Only the number of edges is minimized, not the weights
+            optimal: false,
+            supports_negative_weights: true,
+            supports_directed: true,
+            find_path: bfs::<V, E>,
+        },
+        AlgorithmDescriptor {
+            name: "DFS",
+            slug: "dfs",
+            time_complexity: "O(V + E)",
+            space_complexity: "O(V)",
+            optimal: false,
+            supports_negative_weights: true,
+            supports_directed: true,
+            find_path: dfs::<V, E>,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::{generate_graph, Coord, PathfindingSteps};
+
+    #[test]
+    fn descriptors_are_consistent() {
+        let descriptors = algorithm_descriptors::<Coord, f32>();
+        let slugs = descriptors.iter().map(|d| d.slug).collect::<BTreeSet<_>>();
+        assert_eq!(slugs.len(), descriptors.len());
+
+        let dijkstra = descriptors.iter().find(|d| d.slug == "dijkstra").unwrap();
+        assert!(dijkstra.optimal && !dijkstra.supports_negative_weights);
+
+        // Every algorithm finds a path, and the optimal ones find one as cheap as Dijkstra's
+        let walls = BTreeSet::from([Coord::new(2, 0), Coord::new(2, 1), Coord::new(2, 2)]);
+        let graph = generate_graph::<f32>(6, 6, true, &walls);
+        let (start, end) = (Coord::new(0, 0), Coord::new(5, 1));
+        let cost = |path: &[Coord]| -> f32 {
+            path.windows(2)
+                .map(|pair| graph.hash_map[&pair[0]][&pair[1]])
+                .sum()
+        };
+        let shortest = cost(
+            &(dijkstra.find_path)(graph.clone(), start, end, PathfindingSteps::new(vec![])).path,
+        );
+
+        for descriptor in descriptors.iter() {
+            assert!(
+                !descriptor.time_complexity.is_empty() && !descriptor.space_complexity.is_empty()
+            );
+            let path =
+                (descriptor.find_path)(graph.clone(), start, end, PathfindingSteps::new(vec![]))
+                    .path;
+            assert!(graph.is_valid_path(&path), "{}", descriptor.name);
+            assert_eq!(path.last(), Some(&end));
+            if descriptor.optimal {
+                assert!((cost(&path) - shortest).abs() < 1e-4, "{}", descriptor.name);
+            }
+        }
+    }
+}
@@ -1,22 +1,36 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use crate::{graph::AdjacencyList, Edge, PathfindingResult, PathfindingSteps, Vertex, VertexState};
+use crate::{
+    graph::{AdjacencyList, Graph},
+    Edge, PathfindingResult, PathfindingSteps, Vertex, VertexState,
+};
 
 pub fn dfs<V: Vertex, E: Edge>(
-    adjacency_list: AdjacencyList<V, E>,
+    graph: impl Graph<V, E>,
     start: V,
     end: V,
     mut steps: PathfindingSteps<V>,
 ) -> PathfindingResult<V, E> {
-    let path = _iterative_dfs(adjacency_list, start, end, &mut steps);
-    PathfindingResult::new(steps, path, BTreeMap::new())
+    let mut explored = vec![];
+    let mut discovered = vec![];
+    let path = _iterative_dfs(
+        graph,
+        start,
+        end,
+        &mut steps,
+        &mut explored,
+        &mut discovered,
+    );
+    PathfindingResult::new(steps, path, BTreeMap::new(), explored, discovered)
 }
 
 fn _iterative_dfs<V: Vertex, E: Edge>(
-    adjacency_list: AdjacencyList<V, E>,
+    graph: impl Graph<V, E>,
     start: V,
     end: V,
     steps: &mut PathfindingSteps<V>,
+    explored: &mut Vec<V>,
+    discovered: &mut Vec<Vec<V>>,
 ) -> Vec<V> {
     let mut stack = vec![start];
     // Map of the path's vertices and their parents
@@ -27,24 +41,25 @@ fn _iterative_dfs<V: Vertex, E: Edge>(
         let vertex = stack.pop().unwrap();
 
         if vertex == end {
+            // The parents lead back to the start, so it's already the first vertex
             let mut path = get_path(vertex_parents, vertex);
-            path.insert(0, start);
             path.push(end);
             return path;
         }
 
         if !visited.contains(&vertex) {
             visited.insert(vertex);
+            explored.push(vertex);
+            discovered.push(vec![]);
 
             steps.init_step();
             steps.insert_state_to_last_step(vertex, VertexState::NewVisited);
 
-            if let Some(neighbors) = adjacency_list.get_neighbors(&vertex) {
-                for (neighbor, _) in neighbors.iter().rev() {
-                    if !visited.contains(neighbor) {
-                        stack.push(*neighbor);
-                        vertex_parents.insert(*neighbor, vertex);
-                    }
+            for (neighbor, _) in graph.neighbors(&vertex).into_iter().rev() {
+                if !visited.contains(&neighbor) {
+                    stack.push(neighbor);
+                    vertex_parents.insert(neighbor, vertex);
+                    discovered.last_mut().unwrap().push(neighbor);
                 }
             }
         }
@@ -97,3 +112,20 @@ fn _recursive_dfs<V: Vertex, E: Edge>(
 
     (visited, steps)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_graph, Coord};
+
+    #[test]
+    fn path_starts_with_start_once() {
+        let graph = generate_graph::<f32>(4, 4, false, &BTreeSet::new());
+        let (start, end) = (Coord::new(0, 0), Coord::new(3, 2));
+        let path = dfs(&graph, start, end, PathfindingSteps::new(vec![])).path;
+
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.iter().filter(|&&vertex| vertex == start).count(), 1);
+        assert_eq!(path.last(), Some(&end));
+    }
+}
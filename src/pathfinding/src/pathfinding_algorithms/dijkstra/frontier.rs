@@ -0,0 +1,160 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{Edge, Vertex};
+
+/// A min-priority queue of vertices that haven't been expanded yet.
+///
+/// Dijkstra can be run with different frontiers with [`dijkstra_with_frontier`](super::dijkstra_with_frontier) to compare their performance.
+pub trait PriorityFrontier<V: Vertex, E: Edge> {
+    /// Adds a vertex to the frontier with the given priority.
+    fn push(&mut self, vertex: V, priority: E);
+    /// Removes and returns the vertex with the smallest priority.
+    fn pop(&mut self) -> Option<(V, E)>;
+    fn is_empty(&self) -> bool;
+}
+
+/// The default frontier that is backed by a [`BinaryHeap`]. Pushing and popping are both `O(log n)`.
+///
+/// Vertices with equal priorities are popped in ascending order, so that ties are always broken the same way.
+#[derive(Clone, Debug)]
+pub struct BinaryHeapFrontier<V: Vertex, E: Edge> {
+    heap: BinaryHeap<Visit<V, E>>,
+}
+impl<V: Vertex, E: Edge> Default for BinaryHeapFrontier<V, E> {
+    fn default() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+impl<V: Vertex, E: Edge> PriorityFrontier<V, E> for BinaryHeapFrontier<V, E> {
+    fn push(&mut self, vertex: V, priority: E) {
+        self.heap.push(Visit {
+            vertex,
+            distance: priority,
+        });
+    }
+    fn pop(&mut self) -> Option<(V, E)> {
+        self.heap
+            .pop()
+            .map(|Visit { vertex, distance }| (vertex, distance))
+    }
+    fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+/// A frontier for integer weights that stores vertices in buckets indexed by their priority truncated to an integer.
+///
+/// Finding the first non-empty bucket is amortized `O(1)` when the priorities grow monotonically, like they do in Dijkstra.
+/// Each bucket is a small heap that pops in the same order as [`BinaryHeapFrontier`], including ties,
+/// so both frontiers give identical paths and costs. Pushing and popping are `O(log k)` where `k` is the size of the bucket.
+///
+/// Priorities with a fractional part share the bucket of their integer part and are still popped in order within it,
+/// so fractional weights give the same results too, but then the buckets no longer make popping any faster.
+#[derive(Clone, Debug)]
+pub struct BucketQueueFrontier<V: Vertex, E: Edge> {
+    buckets: Vec<BinaryHeap<Visit<V, E>>>,
+    /// The index of the first bucket that may contain vertices.
+    current: usize,
+    len: usize,
+}
+impl<V: Vertex, E: Edge> Default for BucketQueueFrontier<V, E> {
+    fn default() -> Self {
+        Self {
+            buckets: vec![],
+            current: 0,
+            len: 0,
+        }
+    }
+}
+impl<V: Vertex, E: Edge> PriorityFrontier<V, E> for BucketQueueFrontier<V, E> {
+    fn push(&mut self, vertex: V, priority: E) {
+        let i = priority.max(E::zero()).to_usize().unwrap_or(0);
+        if i >= self.buckets.len() {
+            self.buckets.resize_with(i + 1, BinaryHeap::new);
+        }
+        self.buckets[i].push(Visit {
+            vertex,
+            distance: priority,
+        });
+        self.current = self.current.min(i);
+        self.len += 1;
+    }
+    fn pop(&mut self) -> Option<(V, E)> {
+        while self.current < self.buckets.len() {
+            if let Some(Visit { vertex, distance }) = self.buckets[self.current].pop() {
+                self.len -= 1;
+                return Some((vertex, distance));
+            }
+            self.current += 1;
+        }
+        None
+    }
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Visit<V, E: PartialOrd> {
+    vertex: V,
+    distance: E,
+}
+
+// Reversed so that the max-heap pops the smallest distance, and then the smallest vertex
+impl<V: Ord, E: PartialOrd> Ord for Visit<V, E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.vertex.cmp(&self.vertex))
+    }
+}
+
+impl<V: Ord, E: PartialOrd> PartialOrd for Visit<V, E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V: Ord, E: PartialOrd> PartialEq for Visit<V, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<V: Ord, E: PartialOrd> Eq for Visit<V, E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coord;
+
+    fn pop_all(mut frontier: impl PriorityFrontier<Coord, f32>) -> Vec<(Coord, f32)> {
+        let mut popped = vec![];
+        while let Some(visit) = frontier.pop() {
+            popped.push(visit);
+        }
+        assert!(frontier.is_empty());
+        popped
+    }
+
+    #[test]
+    fn fractional_priorities_are_popped_in_order() {
+        let pushes = [(0, 2.5), (1, 1.9), (2, 1.2), (3, 0.0), (4, 1.2), (5, 2.0)];
+        let mut heap = BinaryHeapFrontier::default();
+        let mut buckets = BucketQueueFrontier::default();
+        for (x, priority) in pushes {
+            heap.push(Coord::new(x, 0), priority);
+            buckets.push(Coord::new(x, 0), priority);
+        }
+
+        // 1.9 shares a bucket with 1.2 but is still popped after it, and ties are popped by vertex
+        let expected = [(3, 0.0), (2, 1.2), (4, 1.2), (1, 1.9), (5, 2.0), (0, 2.5)]
+            .map(|(x, priority)| (Coord::new(x, 0), priority));
+        assert_eq!(pop_all(heap), expected);
+        assert_eq!(pop_all(buckets), expected);
+    }
+}
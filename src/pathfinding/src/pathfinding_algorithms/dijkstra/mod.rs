@@ -1,118 +1,383 @@
+mod frontier;
+
+pub use frontier::{BinaryHeapFrontier, BucketQueueFrontier, PriorityFrontier};
+
 use std::{
-    cmp::Ordering,
-    collections::{BTreeMap, BinaryHeap, HashSet},
-    fmt::Debug,
+    collections::{BTreeMap, HashMap, HashSet},
+    error::Error,
+    fmt::{self, Debug, Display},
 };
 
-use crate::{graph::AdjacencyList, Edge, PathfindingResult, PathfindingSteps, Vertex, VertexState};
+use crate::{
+    graph::{Graph, VertexWeightedGraph},
+    Edge, PathfindingResult, PathfindingSteps, Vertex, VertexState,
+};
 
 pub fn dijkstra<V: Vertex, E: Edge>(
-    adjacency_list: AdjacencyList<V, E>,
+    graph: impl Graph<V, E>,
+    start: V,
+    end: V,
+    steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    dijkstra_with_frontier(graph, start, end, steps, BinaryHeapFrontier::default())
+}
+
+/// Runs Dijkstra where entering a vertex also costs its weight in `vertex_weights`, e.g. the difficulty of the terrain on a grid cell.\
+/// The costs in the result include the vertex weights. Without any vertex weights this is the same as [`dijkstra`].
+pub fn dijkstra_with_node_costs<V: Vertex, E: Edge>(
+    graph: impl Graph<V, E>,
+    vertex_weights: BTreeMap<V, E>,
+    start: V,
+    end: V,
+    steps: PathfindingSteps<V>,
+) -> PathfindingResult<V, E> {
+    dijkstra(
+        VertexWeightedGraph::new(graph, vertex_weights),
+        start,
+        end,
+        steps,
+    )
+}
+
+/// Runs Dijkstra after making sure that none of the edges reachable from `start` have a negative weight.
+/// Dijkstra doesn't work correctly with negative weights, so an error is returned instead of a wrong result.
+pub fn dijkstra_checked<V: Vertex, E: Edge>(
+    graph: impl Graph<V, E>,
+    start: V,
+    end: V,
+    steps: PathfindingSteps<V>,
+) -> Result<PathfindingResult<V, E>, NegativeWeightError<V, E>> {
+    if let Some(error) = find_negative_edge(&graph, start) {
+        return Err(error);
+    }
+    Ok(dijkstra(graph, start, end, steps))
+}
+
+/// An error that is returned when an edge with a negative weight is found.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NegativeWeightError<V, E> {
+    pub from: V,
+    pub to: V,
+    pub weight: E,
+}
+impl<V: Display, E: Display> Display for NegativeWeightError<V, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "edge from {} to {} has a negative weight of {}",
+            self.from, self.to, self.weight
+        )
+    }
+}
+impl<V: Debug + Display, E: Debug + Display> Error for NegativeWeightError<V, E> {}
+
+/// Finds the first edge with a negative weight that can be reached from `start`.
+fn find_negative_edge<V: Vertex, E: Edge>(
+    graph: &impl Graph<V, E>,
+    start: V,
+) -> Option<NegativeWeightError<V, E>> {
+    let mut stack = vec![start];
+    let mut visited = HashSet::from([start]);
+
+    while let Some(vertex) = stack.pop() {
+        for (neighbor, weight) in graph.neighbors(&vertex) {
+            if weight < E::zero() {
+                return Some(NegativeWeightError {
+                    from: vertex,
+                    to: neighbor,
+                    weight,
+                });
+            }
+            if visited.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    None
+}
+
+/// Runs Dijkstra with a given [`PriorityFrontier`] implementation.
+/// All frontiers produce the same paths and costs, but their performance characteristics differ.
+pub fn dijkstra_with_frontier<V: Vertex, E: Edge, F: PriorityFrontier<V, E>>(
+    graph: impl Graph<V, E>,
     start: V,
     end: V,
     mut steps: PathfindingSteps<V>,
+    mut to_visit: F,
 ) -> PathfindingResult<V, E> {
     let mut distances = BTreeMap::<V, E>::new();
+    // The vertex before each discovered vertex on the shortest path found to it so far
+    let mut parents = HashMap::<V, V>::new();
     let mut visited = HashSet::new();
-    let mut to_visit = BinaryHeap::new();
+    let mut explored = vec![];
+    let mut discovered: Vec<Vec<V>> = vec![];
 
     distances.insert(start, E::zero());
-    to_visit.push(Visit {
-        vertex: start,
-        distance: 0,
-    });
+    to_visit.push(start, E::zero());
 
-    while let Some(Visit { vertex, distance }) = to_visit.pop() {
+    while let Some((vertex, distance)) = to_visit.pop() {
         steps.init_step();
         steps.insert_state_to_last_step(vertex, VertexState::NewVisited);
 
         if !visited.insert(vertex) {
             continue;
         }
+        // The distance of a vertex is final once it's popped, so the search can stop here
+        if vertex == end {
+            return PathfindingResult::new(
+                steps,
+                parent_path(&parents, end),
+                distances,
+                explored,
+                discovered,
+            );
+        }
+        explored.push(vertex);
+        discovered.push(vec![]);
 
-        if let Some(neighbors) = adjacency_list.get_neighbors(&vertex) {
-            for (neighbor, cost) in neighbors {
-                let new_distance = E::from(distance).unwrap() + cost.to_owned();
-                let is_shorter = distances
-                    .get(neighbor)
-                    .map_or(true, |current| new_distance < current.to_owned());
-
-                if *neighbor == end {
-                    distances.insert(*neighbor, new_distance);
-                    return PathfindingResult::new(
-                        steps,
-                        distance_map_shortest_path(&adjacency_list, &distances, start, end),
-                        distances,
-                    );
-                }
+        for (neighbor, cost) in graph.neighbors(&vertex) {
+            if visited.contains(&neighbor) {
+                continue;
+            }
 
-                if is_shorter {
-                    distances.insert(*neighbor, new_distance.to_owned().into());
-                    to_visit.push(Visit {
-                        vertex: *neighbor,
-                        distance: new_distance.to_isize().unwrap(),
-                    });
-                }
+            let new_distance = distance + cost;
+            let is_shorter = distances
+                .get(&neighbor)
+                .is_none_or(|current| new_distance < *current);
+
+            if is_shorter {
+                distances.insert(neighbor, new_distance);
+                parents.insert(neighbor, vertex);
+                to_visit.push(neighbor, new_distance);
+                discovered.last_mut().unwrap().push(neighbor);
             }
         }
     }
 
-    PathfindingResult::new(steps, vec![], distances)
-}
-#[derive(Debug)]
-struct Visit<V, E: Ord> {
-    vertex: V,
-    distance: E,
+    PathfindingResult::new(steps, vec![], distances, explored, discovered)
 }
 
-impl<V, E: Ord> Ord for Visit<V, E> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.distance.cmp(&self.distance)
+/// Follows the parents back from `end` and returns the path from the start to `end`.\
+/// Parents are only set for vertices that haven't been expanded yet, so following them always ends at the start.
+fn parent_path<V: Vertex>(parents: &HashMap<V, V>, end: V) -> Vec<V> {
+    let mut path = vec![end];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(*parent);
     }
+    path.reverse();
+    path
 }
 
-impl<V, E: Ord> PartialOrd for Visit<V, E> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::{generate_graph, graph::AdjacencyList, Coord};
+
+    /// A grid with walls where every edge gets a random whole number weight, so that it suits the bucket queue.
+    fn random_grid(seed: u64) -> AdjacencyList<Coord, f32> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let walls = (0..20)
+            .map(|_| Coord::new(rng.gen_range(0..8), rng.gen_range(0..8)))
+            .collect();
+        let mut graph = generate_graph(8, 8, seed.is_multiple_of(2), &walls);
+        for neighbors in graph.hash_map.values_mut() {
+            for weight in neighbors.values_mut() {
+                *weight = rng.gen_range(1..=9) as f32;
+            }
+        }
+        graph
     }
-}
 
-impl<V, E: Ord> PartialEq for Visit<V, E> {
-    fn eq(&self, other: &Self) -> bool {
-        self.distance.eq(&other.distance)
+    /// Finds the distances from `start` by relaxing every edge until nothing changes.
+    fn relaxed_distances(graph: &AdjacencyList<Coord, f32>, start: Coord) -> BTreeMap<Coord, f32> {
+        let mut distances = BTreeMap::from([(start, 0.0)]);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (vertex, neighbors) in &graph.hash_map {
+                let distance = match distances.get(vertex) {
+                    Some(distance) => *distance,
+                    None => continue,
+                };
+                for (neighbor, weight) in neighbors {
+                    let new_distance = distance + weight;
+                    if distances
+                        .get(neighbor)
+                        .is_none_or(|current| new_distance < *current)
+                    {
+                        distances.insert(*neighbor, new_distance);
+                        changed = true;
+                    }
+                }
+            }
+        }
+        distances
     }
-}
 
-impl<V, E: Ord> Eq for Visit<V, E> {}
+    fn path_cost(graph: &AdjacencyList<Coord, f32>, path: &[Coord]) -> f32 {
+        path.windows(2)
+            .map(|pair| graph.hash_map[&pair[0]][&pair[1]])
+            .sum()
+    }
 
-/// Finds the shortest path from start to end according to a given distance map.
-fn distance_map_shortest_path<V: Vertex, E: Edge>(
-    adjacency_list: &AdjacencyList<V, E>,
-    distances: &BTreeMap<V, E>,
-    start: V,
-    end: V,
-) -> Vec<V> {
-    let mut shortest_path = vec![end];
-    let mut curr_vertex = end;
-    // If neighbor is not found in distance map
-    let default_val = (&start, &E::max_value());
-
-    while curr_vertex != start {
-        if let Some(neighbors) = adjacency_list.get_neighbors(&curr_vertex) {
-            let unvisited_neighbors = neighbors.iter().filter(|n| !shortest_path.contains(n.0));
-            let closest_neighbor = unvisited_neighbors
-                .map(|n| distances.get_key_value(n.0).unwrap_or(default_val))
-                .min_by(|a, b| a.1.partial_cmp(b.1).unwrap());
-
-            if let Some((new_vertex, _)) = closest_neighbor {
-                shortest_path.push(*new_vertex);
-                curr_vertex = *new_vertex;
+    /// Runs Dijkstra with both frontiers, checks that they agree and returns the result.
+    fn dijkstra_with_both_frontiers(
+        graph: &AdjacencyList<Coord, f32>,
+        start: Coord,
+        end: Coord,
+    ) -> PathfindingResult<Coord, f32> {
+        let heap = dijkstra_with_frontier(
+            graph.clone(),
+            start,
+            end,
+            PathfindingSteps::new(vec![]),
+            BinaryHeapFrontier::default(),
+        );
+        let buckets = dijkstra_with_frontier(
+            graph.clone(),
+            start,
+            end,
+            PathfindingSteps::new(vec![]),
+            BucketQueueFrontier::default(),
+        );
+
+        assert_eq!(heap.path, buckets.path);
+        assert_eq!(heap.costs, buckets.costs);
+        assert_eq!(heap.explored, buckets.explored);
+        heap
+    }
+
+    #[test]
+    fn frontiers_find_shortest_paths_on_random_graphs() {
+        for seed in 0..10 {
+            let graph = random_grid(seed);
+            let start = *graph.hash_map.keys().next().unwrap();
+            let distances = relaxed_distances(&graph, start);
+
+            for &end in graph.hash_map.keys() {
+                let result = dijkstra_with_both_frontiers(&graph, start, end);
+
+                match distances.get(&end) {
+                    Some(distance) => {
+                        assert_eq!(result.path.first(), Some(&start));
+                        assert_eq!(result.path.last(), Some(&end));
+                        assert_eq!(path_cost(&graph, &result.path), *distance);
+                        assert_eq!(result.costs[&end], *distance);
+                    }
+                    None => assert!(result.path.is_empty()),
+                }
             }
         }
-        if curr_vertex == end {
-            break;
+    }
+
+    #[test]
+    fn node_costs_change_route() {
+        // Going straight along y = 0 is the shortest way, but the middle of it is expensive to enter
+        let graph = generate_graph::<f32>(5, 3, false, &BTreeSet::new());
+        let (start, end) = (Coord::new(0, 0), Coord::new(4, 0));
+        let plain = dijkstra(&graph, start, end, PathfindingSteps::new(vec![]));
+        assert_eq!(plain.path.len(), 5);
+
+        let without_weights = dijkstra_with_node_costs(
+            &graph,
+            BTreeMap::new(),
+            start,
+            end,
+            PathfindingSteps::new(vec![]),
+        );
+        assert_eq!(without_weights, plain);
+
+        let swamp = BTreeMap::from([(Coord::new(2, 0), 10.0), (Coord::new(2, 1), 1.0)]);
+        let result =
+            dijkstra_with_node_costs(&graph, swamp, start, end, PathfindingSteps::new(vec![]));
+        assert!(!result.path.contains(&Coord::new(2, 0)));
+        assert!(result.path.contains(&Coord::new(2, 1)));
+        // 6 edges around the swamp plus entering (2, 1)
+        assert_eq!(result.costs[&end], 7.0);
+        assert_eq!(result.path.len(), 7);
+    }
+
+    #[test]
+    fn frontiers_break_ties_the_same_way() {
+        // Uniform weights have lots of ties between equally distant vertices
+        let graph = generate_graph::<f32>(12, 12, false, &BTreeSet::new());
+        let result = dijkstra_with_both_frontiers(&graph, Coord::new(1, 2), Coord::new(10, 9));
+        assert_eq!(result.path.len(), 17);
+    }
+
+    #[test]
+    fn path_includes_start_and_end() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(Coord::new(0, 0), Coord::new(1, 0), 1.0);
+
+        let result = dijkstra_with_both_frontiers(&graph, Coord::new(0, 0), Coord::new(1, 0));
+        assert_eq!(result.path, vec![Coord::new(0, 0), Coord::new(1, 0)]);
+        let result = dijkstra_with_both_frontiers(&graph, Coord::new(0, 0), Coord::new(0, 0));
+        assert_eq!(result.path, vec![Coord::new(0, 0)]);
+        // The edge can't be followed backwards
+        let result = dijkstra_with_both_frontiers(&graph, Coord::new(1, 0), Coord::new(0, 0));
+        assert!(result.path.is_empty());
+    }
+
+    #[test]
+    fn negative_edge_is_an_error() {
+        let mut graph = AdjacencyList::<Coord, f32>::default();
+        graph.add_edge_directed(Coord::new(0, 0), Coord::new(1, 0), 2.0);
+        graph.add_edge_directed(Coord::new(1, 0), Coord::new(2, 0), -1.0);
+
+        let result = dijkstra_checked(
+            graph.clone(),
+            Coord::new(0, 0),
+            Coord::new(2, 0),
+            PathfindingSteps::new(vec![]),
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            NegativeWeightError {
+                from: Coord::new(1, 0),
+                to: Coord::new(2, 0),
+                weight: -1.0,
+            }
+        );
+
+        // The negative edge can't be reached from here
+        let result = dijkstra_checked(
+            graph,
+            Coord::new(2, 0),
+            Coord::new(0, 0),
+            PathfindingSteps::new(vec![]),
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn checked_dijkstra_finds_shortest_paths_on_random_graphs() {
+        for seed in 0..10 {
+            let graph = random_grid(seed);
+            let start = *graph.hash_map.keys().next().unwrap();
+            let distances = relaxed_distances(&graph, start);
+
+            for &end in graph.hash_map.keys() {
+                let checked =
+                    dijkstra_checked(graph.clone(), start, end, PathfindingSteps::new(vec![]))
+                        .unwrap();
+                let unchecked = dijkstra(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+                assert_eq!(checked, unchecked);
+
+                // The path that is drawn after the steps is a shortest one
+                match distances.get(&end) {
+                    Some(distance) => {
+                        assert_eq!(path_cost(&graph, &checked.path), *distance);
+                        assert_eq!(checked.path.first(), Some(&start));
+                    }
+                    None => assert!(checked.path.is_empty()),
+                }
+            }
         }
     }
-    shortest_path.reverse();
-    shortest_path
 }
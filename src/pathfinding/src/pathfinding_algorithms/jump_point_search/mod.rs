@@ -0,0 +1,327 @@
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+
+use super::a_star::VertexWithPriority;
+use crate::{graph::AdjacencyList, Coord, Edge, PathfindingResult, PathfindingSteps, VertexState};
+
+/// Finds the shortest path on a uniform-cost 8-connected grid with [jump point search](https://en.wikipedia.org/wiki/Jump_point_search).
+///
+/// JPS is an optimization of A* that skips over symmetric paths by "jumping" in straight lines until it reaches a cell with forced neighbors.
+/// Only these jump points are expanded, so far fewer vertices are visited than with A*, but the path is equally short.
+///
+/// The graph is expected to be a grid like the ones made with [`AdjacencyList::grid_with_obstacles`] using diagonal movement,
+/// where every vertex is a walkable cell and missing vertices are obstacles.
+pub fn jump_point_search<E: Edge>(
+    adjacency_list: AdjacencyList<Coord, E>,
+    start: Coord,
+    end: Coord,
+    mut steps: PathfindingSteps<Coord>,
+) -> PathfindingResult<Coord, E> {
+    let grid = Grid {
+        adjacency_list: &adjacency_list,
+        end,
+    };
+
+    if !grid.walkable(start) || !grid.walkable(end) {
+        return PathfindingResult::new(steps, vec![], BTreeMap::new(), vec![], vec![]);
+    }
+
+    let mut open_set = BinaryHeap::new();
+    open_set.push(VertexWithPriority::new(
+        start,
+        octile_distance::<E>(start, end),
+    ));
+
+    // The cheapest known cost from the start to each jump point
+    let mut g = HashMap::<Coord, E>::from([(start, E::zero())]);
+    let mut parents = HashMap::<Coord, Coord>::new();
+    // The jump points that have already been expanded. A jump point can be pushed several times with different costs,
+    // but only the cheapest one needs to be expanded.
+    let mut closed = HashSet::new();
+    let mut explored = vec![];
+    let mut discovered = vec![];
+
+    while let Some(VertexWithPriority { vertex: curr, .. }) = open_set.pop() {
+        if curr == end {
+            return PathfindingResult::new(
+                steps,
+                reconstruct_path(&parents, curr),
+                g.into_iter().collect(),
+                explored,
+                discovered,
+            );
+        }
+        if !closed.insert(curr) {
+            continue;
+        }
+
+        explored.push(curr);
+        discovered.push(vec![]);
+        steps.init_step();
+        steps.insert_state_to_last_step(curr, VertexState::NewVisited);
+
+        let curr_g = g[&curr];
+
+        for neighbor in grid.pruned_neighbors(curr, parents.get(&curr).copied()) {
+            if let Some(jump_point) = grid.jump(neighbor, curr) {
+                if closed.contains(&jump_point) {
+                    continue;
+                }
+                let tentative_g = curr_g + octile_distance::<E>(curr, jump_point);
+
+                if g.get(&jump_point).is_none_or(|old_g| tentative_g < *old_g) {
+                    g.insert(jump_point, tentative_g);
+                    parents.insert(jump_point, curr);
+                    open_set.push(VertexWithPriority::new(
+                        jump_point,
+                        tentative_g + octile_distance::<E>(jump_point, end),
+                    ));
+                    discovered.last_mut().unwrap().push(jump_point);
+                }
+            }
+        }
+    }
+
+    // `open_set` is empty and target was never reached
+    PathfindingResult::new(steps, vec![], g.into_iter().collect(), explored, discovered)
+}
+
+struct Grid<'a, E: Edge> {
+    adjacency_list: &'a AdjacencyList<Coord, E>,
+    end: Coord,
+}
+impl<'a, E: Edge> Grid<'a, E> {
+    fn walkable(&self, coord: Coord) -> bool {
+        self.adjacency_list.contains_vertex(&coord)
+    }
+    /// Gets the neighbors that are worth exploring when arriving at `coord` from `parent`.
+    fn pruned_neighbors(&self, coord: Coord, parent: Option<Coord>) -> Vec<Coord> {
+        let Coord { x, y } = coord;
+        let parent = match parent {
+            Some(parent) => parent,
+            // The start vertex has no parent, so all of its neighbors are explored
+            None => {
+                return coord
+                    .adjacent(true)
+                    .into_iter()
+                    .filter(|neighbor| self.walkable(*neighbor))
+                    .collect();
+            }
+        };
+        let dx = (x - parent.x).signum();
+        let dy = (y - parent.y).signum();
+        let mut neighbors = vec![];
+
+        if dx != 0 && dy != 0 {
+            // Diagonal movement
+            neighbors.push(Coord::new(x, y + dy));
+            neighbors.push(Coord::new(x + dx, y));
+            neighbors.push(Coord::new(x + dx, y + dy));
+            if !self.walkable(Coord::new(x - dx, y)) {
+                neighbors.push(Coord::new(x - dx, y + dy));
+            }
+            if !self.walkable(Coord::new(x, y - dy)) {
+                neighbors.push(Coord::new(x + dx, y - dy));
+            }
+        } else if dx != 0 {
+            // Horizontal movement
+            neighbors.push(Coord::new(x + dx, y));
+            if !self.walkable(Coord::new(x, y + 1)) {
+                neighbors.push(Coord::new(x + dx, y + 1));
+            }
+            if !self.walkable(Coord::new(x, y - 1)) {
+                neighbors.push(Coord::new(x + dx, y - 1));
+            }
+        } else {
+            // Vertical movement
+            neighbors.push(Coord::new(x, y + dy));
+            if !self.walkable(Coord::new(x + 1, y)) {
+                neighbors.push(Coord::new(x + 1, y + dy));
+            }
+            if !self.walkable(Coord::new(x - 1, y)) {
+                neighbors.push(Coord::new(x - 1, y + dy));
+            }
+        }
+
+        neighbors
+            .into_iter()
+            .filter(|neighbor| self.walkable(*neighbor))
+            .collect()
+    }
+    /// Moves from `parent` towards `coord` until a jump point is found.
+    /// Returns `None` if the jump hits an obstacle or the edge of the grid.
+    fn jump(&self, mut coord: Coord, mut parent: Coord) -> Option<Coord> {
+        loop {
+            let Coord { x, y } = coord;
+            let dx = x - parent.x;
+            let dy = y - parent.y;
+
+            if !self.walkable(coord) {
+                return None;
+            }
+            if coord == self.end {
+                return Some(coord);
+            }
+
+            let walkable = |x, y| self.walkable(Coord::new(x, y));
+
+            if dx != 0 && dy != 0 {
+                // Diagonal jump, check for forced neighbors
+                if (walkable(x - dx, y + dy) && !walkable(x - dx, y))
+                    || (walkable(x + dx, y - dy) && !walkable(x, y - dy))
+                {
+                    return Some(coord);
+                }
+                // A diagonal jump point is also found if a horizontal or vertical jump from here finds one
+                if self.jump(Coord::new(x + dx, y), coord).is_some()
+                    || self.jump(Coord::new(x, y + dy), coord).is_some()
+                {
+                    return Some(coord);
+                }
+            } else if dx != 0 {
+                // Horizontal jump
+                if (walkable(x + dx, y + 1) && !walkable(x, y + 1))
+                    || (walkable(x + dx, y - 1) && !walkable(x, y - 1))
+                {
+                    return Some(coord);
+                }
+            } else if (walkable(x + 1, y + dy) && !walkable(x + 1, y))
+                || (walkable(x - 1, y + dy) && !walkable(x - 1, y))
+            {
+                // Vertical jump
+                return Some(coord);
+            }
+
+            parent = coord;
+            coord = Coord::new(x + dx, y + dy);
+        }
+    }
+}
+
+/// The distance between two cells when moving in 8 directions, where diagonal steps cost sqrt(2).
+fn octile_distance<E: Edge>(a: Coord, b: Coord) -> E {
+    let dx = (a.x - b.x).abs();
+    let dy = (a.y - b.y).abs();
+    let (diagonal, straight) = (dx.min(dy), dx.max(dy) - dx.min(dy));
+    E::from(2.0_f32.sqrt()).unwrap() * E::from(diagonal).unwrap() + E::from(straight).unwrap()
+}
+
+/// Reconstructs the full path from the start to `curr` by filling in the cells between consecutive jump points.
+fn reconstruct_path(parents: &HashMap<Coord, Coord>, mut curr: Coord) -> Vec<Coord> {
+    let mut path = vec![curr];
+    while let Some(&parent) = parents.get(&curr) {
+        let dx = (parent.x - curr.x).signum();
+        let dy = (parent.y - curr.y).signum();
+        while curr != parent {
+            curr = Coord::new(curr.x + dx, curr.y + dy);
+            path.push(curr);
+        }
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+    use crate::pathfinding_algorithms::a_star;
+
+    /// Gets the cost of following `path`, which panics if the path leaves the edges of the graph.
+    fn path_cost(graph: &AdjacencyList<Coord, f64>, path: &[Coord]) -> f64 {
+        path.windows(2)
+            .map(|pair| graph.hash_map[&pair[0]][&pair[1]])
+            .sum()
+    }
+
+    #[test]
+    fn same_cost_as_a_star() {
+        let walls = (0..15)
+            .map(|y| Coord::new(10, y))
+            .chain((5..20).map(|y| Coord::new(5, y)))
+            .collect::<BTreeSet<Coord>>();
+        let graph = AdjacencyList::<Coord, f64>::grid_with_obstacles(20, 20, true, &walls);
+        let (start, end) = (Coord::new(0, 0), Coord::new(19, 19));
+
+        let jps = jump_point_search(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+        let a_star = a_star(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+
+        assert_eq!(jps.path.first(), Some(&start));
+        assert_eq!(jps.path.last(), Some(&end));
+        assert!((path_cost(&graph, &jps.path) - path_cost(&graph, &a_star.path)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn costs_of_the_jump_points() {
+        let walls = (0..8)
+            .map(|y| Coord::new(4, y))
+            .collect::<BTreeSet<Coord>>();
+        let graph = AdjacencyList::<Coord, f64>::grid_with_obstacles(10, 10, true, &walls);
+        let (start, end) = (Coord::new(0, 0), Coord::new(9, 2));
+
+        let jps = jump_point_search(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+        assert_eq!(jps.costs[&start], 0.0);
+        assert!((jps.costs[&end] - path_cost(&graph, &jps.path)).abs() < 1e-9);
+
+        // The costs of the jump points that were reached are kept even if the end isn't
+        let walls = (0..10)
+            .map(|y| Coord::new(4, y))
+            .collect::<BTreeSet<Coord>>();
+        let graph = AdjacencyList::<Coord, f64>::grid_with_obstacles(10, 10, true, &walls);
+        let jps = jump_point_search(graph, start, end, PathfindingSteps::new(vec![]));
+        assert!(jps.path.is_empty());
+        assert_eq!(jps.costs[&start], 0.0);
+        assert!(!jps.costs.contains_key(&end));
+    }
+
+    #[test]
+    fn same_cost_as_a_star_on_random_obstacle_grids() {
+        let (start, end) = (Coord::new(0, 0), Coord::new(19, 19));
+        let mut paths = 0;
+
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let walls = (0..20)
+                .flat_map(|x| (0..20).map(move |y| Coord::new(x, y)))
+                .filter(|&vertex| vertex != start && vertex != end && rng.gen_bool(0.25))
+                .collect::<BTreeSet<Coord>>();
+            let graph = AdjacencyList::<Coord, f64>::grid_with_obstacles(20, 20, true, &walls);
+
+            let jps = jump_point_search(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+            let a_star = a_star(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+
+            // Both find a path or neither does
+            assert_eq!(jps.path.is_empty(), a_star.path.is_empty(), "seed {}", seed);
+            if !jps.path.is_empty() {
+                paths += 1;
+                assert!(
+                    (path_cost(&graph, &jps.path) - path_cost(&graph, &a_star.path)).abs() < 1e-4,
+                    "seed {}",
+                    seed
+                );
+            }
+        }
+        // Most of the grids have a path, so the costs are actually compared
+        assert!(paths > 25, "{}", paths);
+    }
+
+    #[test]
+    fn expands_fewer_vertices_than_a_star_on_open_grid() {
+        let graph =
+            AdjacencyList::<Coord, f64>::grid_with_obstacles(30, 30, true, &BTreeSet::new());
+        let (start, end) = (Coord::new(0, 3), Coord::new(29, 24));
+
+        let jps = jump_point_search(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+        let a_star = a_star(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+
+        assert_eq!(jps.path.len(), a_star.path.len());
+        assert!(
+            jps.explored.len() < a_star.explored.len(),
+            "JPS expanded {} vertices, A* expanded {}",
+            jps.explored.len(),
+            a_star.explored.len()
+        );
+    }
+}
@@ -1,8 +1,129 @@
 //! A collection of pathfinding algorithms.
 mod a_star;
+mod bfs;
+mod contraction_hierarchy;
+mod descriptors;
 mod dfs;
 mod dijkstra;
+mod jump_point_search;
 
-pub use a_star::a_star;
+pub use a_star::{a_star, a_star_to_any, a_star_weighted, a_star_with_heuristic};
+pub use bfs::bfs;
+pub use contraction_hierarchy::ContractionHierarchy;
+pub use descriptors::{algorithm_descriptors, AlgorithmDescriptor};
 pub use dfs::dfs;
-pub use dijkstra::dijkstra;
+pub use dijkstra::{
+    dijkstra, dijkstra_checked, dijkstra_with_frontier, dijkstra_with_node_costs,
+    BinaryHeapFrontier, BucketQueueFrontier, NegativeWeightError, PriorityFrontier,
+};
+pub use jump_point_search::jump_point_search;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::{graph::AdjacencyList, heuristics::Heuristic, Coord, Distance, PathfindingSteps};
+
+    #[test]
+    fn a_star_to_any_reaches_nearest_goal() {
+        let graph =
+            AdjacencyList::<Coord, f32>::grid_with_obstacles(10, 10, false, &BTreeSet::new());
+        let start = Coord::new(0, 0);
+        let goals = [Coord::new(9, 0), Coord::new(3, 3), Coord::new(0, 8)];
+
+        let result = a_star_to_any(
+            graph.clone(),
+            start,
+            |vertex| goals.contains(vertex),
+            |vertex| {
+                goals
+                    .iter()
+                    .map(|goal| ((vertex.x - goal.x).abs() + (vertex.y - goal.y).abs()) as f32)
+                    .fold(f32::INFINITY, f32::min)
+            },
+            PathfindingSteps::new(vec![]),
+        );
+
+        assert_eq!(result.path.first(), Some(&start));
+        assert_eq!(result.path.last(), Some(&Coord::new(3, 3)));
+        assert_eq!(result.path.len(), 7);
+        assert!(result
+            .path
+            .windows(2)
+            .all(|pair| graph.hash_map[&pair[0]].contains_key(&pair[1])));
+    }
+
+    #[test]
+    fn heuristics_change_the_exploration_but_not_the_cost() {
+        let walls = (2..10).map(|y| Coord::new(6, y)).collect::<BTreeSet<_>>();
+        let graph = AdjacencyList::<Coord, f32>::grid_with_obstacles(12, 12, false, &walls);
+        let (start, end) = (Coord::new(1, 8), Coord::new(10, 6));
+        let path_cost = |path: &[Coord]| -> f32 {
+            path.windows(2)
+                .map(|pair| graph.hash_map[&pair[0]][&pair[1]])
+                .sum()
+        };
+        let search = |heuristic: Heuristic| {
+            a_star_with_heuristic(
+                graph.clone(),
+                start,
+                end,
+                |vertex| heuristic.estimate(vertex, &end),
+                PathfindingSteps::new(vec![]),
+            )
+        };
+
+        let shortest = dijkstra(graph.clone(), start, end, PathfindingSteps::new(vec![]));
+        for heuristic in [Heuristic::Manhattan, Heuristic::Euclidean, Heuristic::Zero] {
+            let result = search(heuristic);
+            assert_eq!(result.path.last(), Some(&end), "{:?}", heuristic);
+            assert_eq!(
+                path_cost(&result.path),
+                path_cost(&shortest.path),
+                "{:?}",
+                heuristic
+            );
+        }
+
+        // Manhattan distance is exact on an open grid without diagonals, so it expands the fewest vertices
+        let manhattan = search(Heuristic::Manhattan).explored.len();
+        let euclidean = search(Heuristic::Euclidean).explored.len();
+        let zero = search(Heuristic::Zero).explored.len();
+        assert!(manhattan <= euclidean && euclidean < zero);
+    }
+
+    #[test]
+    fn weighted_a_star_trades_cost_for_expansions() {
+        // A wall with a gap at the bottom between the start and the goal
+        let walls = (0..25).map(|y| Coord::new(15, y)).collect::<BTreeSet<_>>();
+        let graph = AdjacencyList::<Coord, f32>::grid_with_obstacles(30, 30, true, &walls);
+        let (start, end) = (Coord::new(2, 5), Coord::new(28, 3));
+        let path_cost = |path: &[Coord]| -> f32 {
+            path.windows(2)
+                .map(|pair| graph.hash_map[&pair[0]][&pair[1]])
+                .sum()
+        };
+
+        let search = |epsilon| {
+            a_star_weighted(
+                graph.clone(),
+                start,
+                end,
+                |vertex| vertex.distance::<f32>(end),
+                epsilon,
+                PathfindingSteps::new(vec![]),
+            )
+        };
+        let optimal = search(1.0);
+        let weighted = search(2.0);
+
+        assert_eq!(
+            optimal,
+            a_star(graph.clone(), start, end, PathfindingSteps::new(vec![]))
+        );
+        assert_eq!(weighted.path.last(), Some(&end));
+        assert!(path_cost(&weighted.path) <= 2.0 * path_cost(&optimal.path));
+        assert!(weighted.explored.len() < optimal.explored.len());
+    }
+}
@@ -0,0 +1,204 @@
+//! Splitting a search into frames for animating it in two phases:
+//! first the vertices are revealed in the order that the algorithm explored them, and then the found path is drawn on top.
+use std::collections::BTreeSet;
+
+use crate::{PathfindingResult, Vertex};
+
+/// What is visible at one frame of a [`SearchPlayback`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchFrame<'a, V> {
+    /// The explored vertices so far. The last one is the newest.
+    pub explored: &'a [V],
+    /// The part of the path that has been drawn so far, starting from the start vertex.
+    pub path: &'a [V],
+}
+
+/// The output of a search split into exploration frames followed by path frames.\
+/// Frame `0` is empty, each following frame adds one explored vertex until all of them are visible,
+/// and after that each frame adds one vertex of the path.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchPlayback<V: Vertex> {
+    pub path: Vec<V>,
+    pub explored: Vec<V>,
+    /// The vertices that were added to the open set after each explored vertex, see [`PathfindingResult::discovered`].
+    pub discovered: Vec<Vec<V>>,
+}
+impl<V: Vertex> SearchPlayback<V> {
+    /// Takes the path, the explored vertices and the vertices discovered after each of them, like in a [`PathfindingResult`].
+    pub fn new(path: Vec<V>, explored: Vec<V>, discovered: Vec<Vec<V>>) -> Self {
+        Self {
+            path,
+            explored,
+            discovered,
+        }
+    }
+    pub fn from_result<E>(result: PathfindingResult<V, E>) -> Self {
+        Self::new(result.path, result.explored, result.discovered)
+    }
+    /// The index of the last frame, where both the exploration and the path are fully visible.
+    pub fn len(&self) -> usize {
+        self.explored.len() + self.path.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns `true` if the frame at `index` is drawing the path instead of exploring.
+    pub fn is_path_frame(&self, index: usize) -> bool {
+        index > self.explored.len()
+    }
+    /// Gets what is visible at the frame at `index`. Indices past the last frame show everything.
+    pub fn frame(&self, index: usize) -> SearchFrame<'_, V> {
+        let explored_count = index.min(self.explored.len());
+        let path_count = index
+            .saturating_sub(self.explored.len())
+            .min(self.path.len());
+        SearchFrame {
+            explored: &self.explored[..explored_count],
+            path: &self.path[..path_count],
+        }
+    }
+    /// Gets the frontier at the frame at `index`: the vertices in the open set of the algorithm that haven't been explored yet,
+    /// which are the ones that it may expand next.
+    pub fn frontier(&self, index: usize) -> BTreeSet<V> {
+        let frame = self.frame(index);
+        let explored = frame.explored.iter().collect::<BTreeSet<&V>>();
+        self.discovered
+            .iter()
+            .take(frame.explored.len())
+            .flatten()
+            .filter(|vertex| !explored.contains(vertex))
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generate_graph,
+        graph::Graph,
+        pathfinding_algorithms::{bfs, dfs, jump_point_search},
+        search_with_exploration, Coord, PathfindingSteps,
+    };
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn exploration_frames_come_before_path_frames() {
+        let graph = generate_graph::<f32>(4, 4, false, &BTreeSet::new());
+        let (path, explored) =
+            search_with_exploration(&graph, Coord::new(0, 0), Coord::new(3, 0), bfs);
+        let playback = SearchPlayback::new(path.clone(), explored.clone(), vec![]);
+        assert_eq!(playback.len(), explored.len() + path.len());
+
+        let first = playback.frame(0);
+        assert!(first.explored.is_empty() && first.path.is_empty());
+
+        // Exploring reveals one vertex at a time in the order of exploration, without any path
+        for index in 1..=explored.len() {
+            let frame = playback.frame(index);
+            assert_eq!(frame.explored, &explored[..index]);
+            assert!(frame.path.is_empty());
+            assert!(!playback.is_path_frame(index));
+        }
+
+        // Then the path is drawn from the start while the exploration stays visible
+        for index in 1..=path.len() {
+            let frame = playback.frame(explored.len() + index);
+            assert_eq!(frame.explored, &explored[..]);
+            assert_eq!(frame.path, &path[..index]);
+            assert!(playback.is_path_frame(explored.len() + index));
+        }
+        assert_eq!(
+            playback.frame(playback.len() + 10),
+            playback.frame(playback.len())
+        );
+    }
+
+    #[test]
+    fn no_path_only_explores() {
+        let walls = (0..4).map(|y| Coord::new(1, y)).collect();
+        let graph = generate_graph::<f32>(4, 4, false, &walls);
+        let (path, explored) =
+            search_with_exploration(&graph, Coord::new(0, 0), Coord::new(3, 0), bfs);
+        let playback = SearchPlayback::new(path, explored, vec![]);
+
+        assert_eq!(playback.len(), 4);
+        assert!(!playback.is_path_frame(playback.len()));
+        assert!(playback.frame(playback.len()).path.is_empty());
+        assert!(SearchPlayback::<Coord>::default().is_empty());
+    }
+
+    #[test]
+    fn frontier_surrounds_the_explored_vertices() {
+        let graph = generate_graph::<f32>(4, 4, false, &BTreeSet::new());
+        let playback = SearchPlayback::from_result(bfs(
+            graph,
+            Coord::new(0, 0),
+            Coord::new(3, 3),
+            PathfindingSteps::default(),
+        ));
+
+        assert!(playback.frontier(0).is_empty());
+        assert_eq!(
+            playback.frontier(1),
+            BTreeSet::from([Coord::new(1, 0), Coord::new(0, 1)])
+        );
+        // BFS has explored the first diagonal, so the frontier is the next one
+        assert_eq!(
+            playback.frontier(3),
+            BTreeSet::from([Coord::new(2, 0), Coord::new(1, 1), Coord::new(0, 2)])
+        );
+    }
+
+    #[test]
+    fn frontier_is_the_open_set() {
+        let walls = BTreeSet::from([Coord::new(3, 1), Coord::new(1, 4)]);
+        let graph = generate_graph::<f32>(8, 8, true, &walls);
+        let (start, end) = (Coord::new(0, 0), Coord::new(7, 7));
+        let neighbors = |vertex| {
+            graph
+                .neighbors(&vertex)
+                .into_iter()
+                .map(|(neighbor, _)| neighbor)
+                .collect::<BTreeSet<Coord>>()
+        };
+
+        // DFS only stacks the neighbors that haven't been explored, and the frontier shrinks as they're explored
+        let playback = SearchPlayback::from_result(dfs(
+            graph.clone(),
+            start,
+            end,
+            PathfindingSteps::default(),
+        ));
+        assert_eq!(playback.frontier(1), neighbors(start));
+        for index in 1..=playback.explored.len() {
+            let frontier = playback.frontier(index);
+            assert!(playback.explored[..index]
+                .iter()
+                .all(|vertex| !frontier.contains(vertex)));
+        }
+
+        // Jump point search only adds the jump points to its open set, which aren't the neighbors of the expanded vertices
+        let playback = SearchPlayback::from_result(jump_point_search(
+            graph.clone(),
+            start,
+            end,
+            PathfindingSteps::default(),
+        ));
+        assert_eq!(
+            playback.frontier(1),
+            BTreeSet::from([Coord::new(3, 0), Coord::new(0, 4), Coord::new(2, 2)])
+        );
+        assert_eq!(
+            playback.frontier(2),
+            BTreeSet::from([
+                Coord::new(3, 0),
+                Coord::new(0, 4),
+                Coord::new(2, 4),
+                Coord::new(3, 2),
+                end
+            ])
+        );
+    }
+}
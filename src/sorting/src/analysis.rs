@@ -0,0 +1,272 @@
+//! Utilities for measuring how sorted a collection is.
+
+/// Counts the inversions in a slice, i.e. the pairs of indices `i < j` where `items[i] > items[j]`.
+///
+/// A sorted slice has 0 inversions and a reversed slice with unique items has `n(n - 1) / 2` of them.
+/// The inversions are counted with a modified merge sort in `O(n log n)` time.
+pub fn count_inversions<T: Clone + Copy + Ord>(items: &[T]) -> usize {
+    let mut items = items.to_vec();
+    let mut buffer = items.clone();
+    sort_and_count(&mut items, &mut buffer)
+}
+
+/// Finds the inversions in a slice as pairs of indices `(i, j)`, e.g. to show which pairs of items a sort still has to fix.
+///
+/// A reversed slice has `n(n - 1) / 2` inversions, so at most `limit` of them are returned, ordered by `i` and then by `j`.
+/// Every inversion is found directly with a segment tree, so this takes `O((n + limit) log n)` time instead of checking every pair.
+pub fn find_inversions<T: Ord>(items: &[T], limit: usize) -> Vec<(usize, usize)> {
+    let tree = MinimumTree::new(items);
+    let mut inversions = vec![];
+
+    for (i, item) in items.iter().enumerate() {
+        let mut j = i;
+        while inversions.len() < limit {
+            let Some(next) = tree.first_smaller(j + 1, item) else {
+                break;
+            };
+            inversions.push((i, next));
+            j = next;
+        }
+    }
+
+    inversions
+}
+
+/// Counts the items that are equal to an earlier item in the slice, i.e. the length minus the number of distinct values.
+///
+/// Duplicates are what make the difference between stable and unstable sorting algorithms visible.
+pub fn count_duplicates<T: Clone + Copy + Ord>(items: &[T]) -> usize {
+    let mut sorted = items.to_vec();
+    sorted.sort_unstable();
+    sorted.windows(2).filter(|pair| pair[0] == pair[1]).count()
+}
+
+/// Finds a longest strictly increasing subsequence of a slice with patience sorting in `O(n log n)` time.
+///
+/// The items of a sorted slice form a single increasing subsequence, and the length of the result tells how many items are already in order relative to each other.
+/// If there are several longest subsequences, the one that ends with the smallest item is returned.
+pub fn longest_increasing_subsequence<T: Clone + Copy + Ord>(items: &[T]) -> Vec<T> {
+    // `tails[k]` is the index of the smallest item that ends an increasing subsequence of length `k + 1`
+    let mut tails: Vec<usize> = vec![];
+    // The index of the item before each item in the subsequence that it ends
+    let mut previous: Vec<Option<usize>> = Vec::with_capacity(items.len());
+
+    for (i, item) in items.iter().enumerate() {
+        let pile = tails.partition_point(|tail| items[*tail] < *item);
+        previous.push(pile.checked_sub(1).map(|pile| tails[pile]));
+        if pile == tails.len() {
+            tails.push(i);
+        } else {
+            tails[pile] = i;
+        }
+    }
+
+    let mut subsequence = Vec::with_capacity(tails.len());
+    let mut index = tails.last().copied();
+    while let Some(i) = index {
+        subsequence.push(items[i]);
+        index = previous[i];
+    }
+    subsequence.reverse();
+
+    subsequence
+}
+
+/// A segment tree over a slice for finding the first item after an index that is smaller than a given item in `O(log n)` time.
+struct MinimumTree<'a, T> {
+    items: &'a [T],
+    /// The index of the smallest item in the range of every node. Node 1 is the whole slice, and node `k` is split into `2k` and `2k + 1`.
+    minimums: Vec<usize>,
+}
+impl<'a, T: Ord> MinimumTree<'a, T> {
+    fn new(items: &'a [T]) -> Self {
+        let mut tree = Self {
+            items,
+            minimums: vec![0; 4 * items.len()],
+        };
+        if !items.is_empty() {
+            tree.build(1, 0, items.len());
+        }
+        tree
+    }
+    /// Fills in the node for the items in `start..end` and the nodes below it.
+    fn build(&mut self, node: usize, start: usize, end: usize) {
+        if end - start == 1 {
+            self.minimums[node] = start;
+            return;
+        }
+        let middle = (start + end) / 2;
+        self.build(2 * node, start, middle);
+        self.build(2 * node + 1, middle, end);
+
+        let (left, right) = (self.minimums[2 * node], self.minimums[2 * node + 1]);
+        // The left one wins ties, although any of the equal items would do
+        self.minimums[node] = if self.items[right] < self.items[left] {
+            right
+        } else {
+            left
+        };
+    }
+    /// Finds the first index from `from` onwards whose item is smaller than `item`.
+    fn first_smaller(&self, from: usize, item: &T) -> Option<usize> {
+        if from >= self.items.len() {
+            return None;
+        }
+        self.search(1, 0, self.items.len(), from, item)
+    }
+    fn search(
+        &self,
+        node: usize,
+        start: usize,
+        end: usize,
+        from: usize,
+        item: &T,
+    ) -> Option<usize> {
+        // Ranges that end before `from` or have nothing smaller are skipped without going further down
+        if end <= from || self.items[self.minimums[node]] >= *item {
+            return None;
+        }
+        if end - start == 1 {
+            return Some(start);
+        }
+        let middle = (start + end) / 2;
+        self.search(2 * node, start, middle, from, item)
+            .or_else(|| self.search(2 * node + 1, middle, end, from, item))
+    }
+}
+
+/// Sorts `items` with merge sort and returns the number of inversions that were fixed.
+fn sort_and_count<T: Clone + Copy + Ord>(items: &mut [T], buffer: &mut [T]) -> usize {
+    if items.len() < 2 {
+        return 0;
+    }
+
+    let middle = items.len() / 2;
+    let mut inversions = {
+        let (left, right) = items.split_at_mut(middle);
+        let (left_buffer, right_buffer) = buffer.split_at_mut(middle);
+        sort_and_count(left, left_buffer) + sort_and_count(right, right_buffer)
+    };
+
+    let (mut i, mut j) = (0, middle);
+    for merged in buffer.iter_mut().take(items.len()) {
+        if j >= items.len() || (i < middle && items[i] <= items[j]) {
+            *merged = items[i];
+            i += 1;
+        } else {
+            // Every remaining item in the left half is greater than the item taken from the right half
+            inversions += middle - i;
+            *merged = items[j];
+            j += 1;
+        }
+    }
+    items.copy_from_slice(&buffer[..items.len()]);
+
+    inversions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inversions_of_sorted_and_reversed() {
+        let sorted = (1..=50).collect::<Vec<u32>>();
+        let reversed = sorted.iter().rev().copied().collect::<Vec<u32>>();
+
+        assert_eq!(count_inversions(&sorted), 0);
+        assert_eq!(count_inversions(&reversed), 50 * 49 / 2);
+        assert_eq!(count_inversions::<u32>(&[]), 0);
+    }
+
+    #[test]
+    fn inversions_of_hand_checked_input() {
+        // 3 is bigger than 1 and both 2s, 5 is bigger than 2, 4 and the last 2, and 4 is bigger than the last 2
+        let items = [3, 1, 5, 2, 4, 2];
+        assert_eq!(count_inversions(&items), 7);
+    }
+
+    #[test]
+    fn found_inversions_of_sorted_reversed_and_mixed() {
+        assert!(find_inversions(&[1, 2, 2, 3], usize::MAX).is_empty());
+        assert!(find_inversions::<u32>(&[], usize::MAX).is_empty());
+
+        let reversed = [4, 3, 2, 1];
+        assert_eq!(
+            find_inversions(&reversed, usize::MAX),
+            vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]
+        );
+
+        let items = [3, 1, 5, 2, 4, 2];
+        let inversions = find_inversions(&items, usize::MAX);
+        assert_eq!(
+            inversions,
+            vec![(0, 1), (0, 3), (0, 5), (2, 3), (2, 4), (2, 5), (4, 5)]
+        );
+        assert_eq!(inversions.len(), count_inversions(&items));
+        // The limit keeps the first pairs
+        assert_eq!(find_inversions(&items, 2), vec![(0, 1), (0, 3)]);
+    }
+
+    #[test]
+    fn found_inversions_match_checking_every_pair() {
+        for len in [1_usize, 2, 7, 64, 100] {
+            let items = (0..len)
+                .map(|i| ((i * 37 + 11) % 23) as u32)
+                .collect::<Vec<u32>>();
+            let every_pair = (0..len)
+                .flat_map(|i| (i + 1..len).map(move |j| (i, j)))
+                .filter(|(i, j)| items[*i] > items[*j])
+                .collect::<Vec<(usize, usize)>>();
+
+            for limit in [0, 1, 10, usize::MAX] {
+                let expected = every_pair.iter().copied().take(limit).collect::<Vec<_>>();
+                assert_eq!(
+                    find_inversions(&items, limit),
+                    expected,
+                    "{} {}",
+                    len,
+                    limit
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn duplicates_of_unique_same_and_mixed() {
+        assert_eq!(count_duplicates(&[4, 1, 3, 2]), 0);
+        assert_eq!(count_duplicates(&[7, 7, 7, 7, 7]), 4);
+        // Two extra 2s and one extra 5
+        assert_eq!(count_duplicates(&[2, 5, 1, 2, 5, 2, 3]), 3);
+        assert_eq!(count_duplicates::<u32>(&[]), 0);
+    }
+
+    #[test]
+    fn longest_increasing_subsequence_of_sorted_and_reversed() {
+        let sorted = (1..=20).collect::<Vec<u32>>();
+        let reversed = sorted.iter().rev().copied().collect::<Vec<u32>>();
+
+        assert_eq!(longest_increasing_subsequence(&sorted), sorted);
+        assert_eq!(longest_increasing_subsequence(&reversed), vec![1]);
+        assert_eq!(
+            longest_increasing_subsequence::<u32>(&[]),
+            Vec::<u32>::new()
+        );
+    }
+
+    #[test]
+    fn longest_increasing_subsequence_of_hand_checked_input() {
+        // 0, 2, 6, 9, 11, 15 and 0, 4, 6, 9, 13, 15 are both valid, and the smallest tails give the first one
+        let items = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+        assert_eq!(
+            longest_increasing_subsequence(&items),
+            vec![0, 2, 6, 9, 11, 15]
+        );
+
+        // Equal items don't count as increasing
+        assert_eq!(
+            longest_increasing_subsequence(&[3, 3, 1, 3, 4]),
+            vec![1, 3, 4]
+        );
+    }
+}
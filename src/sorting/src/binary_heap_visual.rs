@@ -0,0 +1,245 @@
+//! A binary heap that records its operations so that they can be visualized.
+
+use std::fmt;
+
+/// Whether the root of a [`BinaryHeapVisual`] is its smallest or largest item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeapOrder {
+    Min,
+    Max,
+}
+impl HeapOrder {
+    pub const ALL: [HeapOrder; 2] = [HeapOrder::Min, HeapOrder::Max];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HeapOrder::Min => "Min-heap",
+            HeapOrder::Max => "Max-heap",
+        }
+    }
+}
+
+/// A change to the array of a [`BinaryHeapVisual`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum HeapStep<T> {
+    /// Append an item to the end of the array: `(value)`
+    Push(T),
+    /// Swap items by index while sifting up or down: `(from_index, to_index)`
+    Swap(usize, usize),
+    /// Remove the last item of the array. The root has been swapped there before extracting it.
+    Pop,
+}
+
+impl<T: fmt::Display> fmt::Display for HeapStep<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeapStep::Push(value) => write!(f, "Appending {} to the end of the array", value),
+            HeapStep::Swap(from, to) => write!(f, "Swapping indices {} and {}", from, to),
+            HeapStep::Pop => write!(f, "Removing the last item, which was the root"),
+        }
+    }
+}
+
+/// Runs recorded heap operations on the array of a heap, like [`run_sort_steps`](crate::run_sort_steps) for sorting.
+pub fn run_heap_steps<T: Clone>(items: &mut Vec<T>, steps: &[Vec<HeapStep<T>>]) {
+    for step in steps {
+        for command in step {
+            match command {
+                HeapStep::Push(value) => items.push(value.clone()),
+                HeapStep::Swap(from, to) => items.swap(*from, *to),
+                HeapStep::Pop => {
+                    items.pop();
+                }
+            }
+        }
+    }
+}
+
+/// A binary min-heap or max-heap stored in an array, where the children of the item at `i` are at `2i + 1` and `2i + 2`.
+///
+/// Every swap that is made while sifting items up or down is recorded as a step,
+/// so the operations can be replayed with [`run_heap_steps`] to visualize how heapify, insert and extract work.
+///
+/// ## Example
+///
+/// ```rust
+/// use sorting::{BinaryHeapVisual, run_heap_steps};
+///
+/// let mut heap = BinaryHeapVisual::new_min();
+/// heap.push(3);
+/// heap.push(1);
+/// heap.push(2);
+///
+/// assert_eq!(heap.pop(), Some(1));
+///
+/// let mut items = vec![];
+/// run_heap_steps(&mut items, heap.steps());
+/// assert_eq!(items, heap.as_slice());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryHeapVisual<T: Clone + Ord> {
+    items: Vec<T>,
+    order: HeapOrder,
+    steps: Vec<Vec<HeapStep<T>>>,
+}
+impl<T: Clone + Ord> BinaryHeapVisual<T> {
+    pub fn new(order: HeapOrder) -> Self {
+        Self {
+            items: vec![],
+            order,
+            steps: vec![],
+        }
+    }
+    pub fn new_min() -> Self {
+        Self::new(HeapOrder::Min)
+    }
+    pub fn new_max() -> Self {
+        Self::new(HeapOrder::Max)
+    }
+    /// Builds a heap from `items` by sifting down every item that has children, starting from the last one.\
+    /// This takes *O(n)* time. The recorded steps start from `items` in their original order.
+    pub fn heapify(items: Vec<T>, order: HeapOrder) -> Self {
+        let mut heap = Self {
+            items,
+            order,
+            steps: vec![],
+        };
+        for i in (0..heap.items.len() / 2).rev() {
+            heap.sift_down(i);
+        }
+        heap
+    }
+    /// Adds an item to the end of the array and sifts it up to its place.
+    pub fn push(&mut self, item: T) {
+        self.items.push(item.clone());
+        self.steps.push(vec![HeapStep::Push(item)]);
+        self.sift_up(self.items.len() - 1);
+    }
+    /// Removes the root by swapping it with the last item and sifting that item down from the root.
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.items.len().checked_sub(1)?;
+        if last > 0 {
+            self.swap(0, last);
+        }
+        let root = self.items.pop();
+        self.steps.push(vec![HeapStep::Pop]);
+        self.sift_down(0);
+        root
+    }
+    /// Gets the root, which is the smallest item of a min-heap and the largest item of a max-heap.
+    pub fn peek(&self) -> Option<&T> {
+        self.items.first()
+    }
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    /// Gets the array that stores the heap.
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+    /// Gets the steps of every operation so far.
+    pub fn steps(&self) -> &[Vec<HeapStep<T>>] {
+        &self.steps
+    }
+    /// Removes and returns the recorded steps, e.g. after they have been visualized.
+    pub fn take_steps(&mut self) -> Vec<Vec<HeapStep<T>>> {
+        std::mem::take(&mut self.steps)
+    }
+
+    /// Checks if the item at `a` belongs above the item at `b`.
+    fn is_above(&self, a: usize, b: usize) -> bool {
+        match self.order {
+            HeapOrder::Min => self.items[a] < self.items[b],
+            HeapOrder::Max => self.items[a] > self.items[b],
+        }
+    }
+    fn swap(&mut self, a: usize, b: usize) {
+        self.items.swap(a, b);
+        self.steps.push(vec![HeapStep::Swap(a, b)]);
+    }
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.is_above(i, parent) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut top = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.items.len() && self.is_above(child, top) {
+                    top = child;
+                }
+            }
+            if top == i {
+                break;
+            }
+            self.swap(i, top);
+            i = top;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_heap<T: Clone + Ord>(heap: &BinaryHeapVisual<T>) -> bool {
+        (1..heap.len()).all(|i| !heap.is_above(i, (i - 1) / 2))
+    }
+
+    #[test]
+    fn extraction_order_is_sorted() {
+        let input = [5, 3, 8, 3, 1, 9, 0, 7, 2, 6];
+
+        for order in [HeapOrder::Min, HeapOrder::Max] {
+            let mut heap = BinaryHeapVisual::new(order);
+            for item in input.iter().copied() {
+                heap.push(item);
+                assert!(is_heap(&heap));
+            }
+            // Mix extracts with inserts
+            let mut extracted = vec![heap.pop().unwrap(), heap.pop().unwrap()];
+            heap.push(4);
+            while let Some(item) = heap.pop() {
+                assert!(is_heap(&heap));
+                extracted.push(item);
+            }
+
+            let mut expected = input.to_vec();
+            expected.push(4);
+            expected.sort();
+            if order == HeapOrder::Max {
+                expected.reverse();
+            }
+            // 4 was only inserted after two extractions, but it's neither among the two smallest nor the two largest
+            assert_eq!(extracted, expected);
+        }
+    }
+
+    #[test]
+    fn steps_replay_to_the_same_array() {
+        let input = vec![4, 10, 3, 5, 1, 8, 8, 2];
+        let mut heap = BinaryHeapVisual::heapify(input.clone(), HeapOrder::Max);
+        assert!(is_heap(&heap));
+        heap.push(11);
+        heap.pop();
+        heap.pop();
+        heap.push(0);
+
+        let mut items = input;
+        run_heap_steps(&mut items, heap.steps());
+        assert_eq!(items, heap.as_slice());
+
+        let steps = heap.take_steps();
+        assert!(steps.contains(&vec![HeapStep::Push(11)]));
+        assert!(heap.steps().is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+//! Estimating how much work a sorting algorithm does before running it.
+use std::fmt;
+
+use crate::UnknownAlgorithmError;
+
+/// How the number of steps of an algorithm grows with the length `n` of its input on average.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    /// `O(n)`, like bucket sort with evenly distributed values.
+    Linear,
+    /// `O(n log n)`, like merge sort.
+    Linearithmic,
+    /// `O(n²)`, like bubble sort.
+    Quadratic,
+}
+impl Complexity {
+    /// Roughly estimates the number of steps for an input of length `n`.\
+    /// The quadratic estimate is the `n(n - 1) / 4` inversions of a random input, since the quadratic sorts swap each inversion once.
+    /// The others leave out the constant factors, so they are only accurate to the order of magnitude.
+    pub fn estimate(&self, n: usize) -> usize {
+        match self {
+            Complexity::Linear => n,
+            Complexity::Linearithmic => (n as f64 * (n.max(1) as f64).log2()).round() as usize,
+            Complexity::Quadratic => n * n.saturating_sub(1) / 4,
+        }
+    }
+}
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Complexity::Linear => write!(f, "O(n)"),
+            Complexity::Linearithmic => write!(f, "O(n log n)"),
+            Complexity::Quadratic => write!(f, "O(n²)"),
+        }
+    }
+}
+
+/// Gets the average complexity of the algorithm with the given name from [`get_sorting_algorithms`](crate::get_sorting_algorithms),
+/// or of `"bucket-sort"`, `"counting-sort"` or `"radix-sort"`.\
+/// Shell sort and comb sort are somewhere between `O(n log n)` and `O(n²)` depending on their gaps, and they are treated as `O(n log n)`.
+pub fn average_complexity(algorithm: &str) -> Result<Complexity, UnknownAlgorithmError> {
+    match algorithm {
+        "bubble-sort" | "insertion-sort" | "odd-even-sort" => Ok(Complexity::Quadratic),
+        "comb-sort" | "heapsort" | "merge-sort" | "quicksort" | "shell-sort" => {
+            Ok(Complexity::Linearithmic)
+        }
+        "bucket-sort" | "counting-sort" | "radix-sort" => Ok(Complexity::Linear),
+        _ => Err(UnknownAlgorithmError {
+            name: algorithm.to_string(),
+        }),
+    }
+}
+
+/// Roughly estimates how many steps the algorithm with the given name takes for an input of length `input_len`,
+/// so that a huge run can be noticed before animating it. See [`Complexity::estimate`].
+pub fn estimate_steps(algorithm: &str, input_len: usize) -> Result<usize, UnknownAlgorithmError> {
+    Ok(average_complexity(algorithm)?.estimate(input_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bubble_sort, SortMetrics};
+
+    /// The ratio of the estimates when the input length doubles from `n`.
+    fn doubling_ratio(algorithm: &str, n: usize) -> f64 {
+        estimate_steps(algorithm, 2 * n).unwrap() as f64
+            / estimate_steps(algorithm, n).unwrap() as f64
+    }
+
+    #[test]
+    fn bubble_sort_estimate_grows_quadratically() {
+        for n in [100, 1_000, 10_000] {
+            assert!((doubling_ratio("bubble-sort", n) - 4.0).abs() < 0.05);
+        }
+
+        // Bubble sort swaps every inversion, so a shuffled input is close to the estimate
+        let mut items: Vec<u32> = (0..400).map(|i| (i * 7919 + 13) % 400).collect();
+        let mut steps = vec![];
+        bubble_sort(&mut items, &mut steps);
+        let estimate = estimate_steps("bubble-sort", 400).unwrap() as f64;
+        assert!((SortMetrics::from_steps(&steps).swaps as f64 / estimate - 1.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn merge_sort_estimate_grows_linearithmically() {
+        for n in [128, 1_024, 8_192] {
+            // Doubling `n` adds one level: 2n log(2n) / (n log n) = 2 (log n + 1) / log n
+            let log = (n as f64).log2();
+            let expected = 2.0 * (log + 1.0) / log;
+            assert!((doubling_ratio("merge-sort", n) - expected).abs() < 0.01);
+        }
+        assert_eq!(estimate_steps("merge-sort", 1_024), Ok(10_240));
+        assert_eq!(estimate_steps("merge-sort", 0), Ok(0));
+        assert!(estimate_steps("bogosort", 10).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Looking up and running sorting algorithms by their names, without depending on the UI.
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, error::Error, fmt};
+
+use crate::{
+    bubble_sort_with_phases, comb_sort, heapsort, insertion_sort, merge_sort, odd_even_sort,
+    quicksort, shell_sort, SortCommand,
+};
+
+/// A sorting algorithm that sorts the items in place and records its steps.\
+/// Algorithms that take a slice instead of the `Vec` are wrapped in a closure, which coerces to this type.
+pub type SortFn<T> = fn(&mut Vec<T>, &mut Vec<Vec<SortCommand<T>>>);
+
+/// An error that is returned when there is no sorting algorithm with the given name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownAlgorithmError {
+    pub name: String,
+}
+impl fmt::Display for UnknownAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown sorting algorithm \"{}\"", self.name)
+    }
+}
+impl Error for UnknownAlgorithmError {}
+
+/// How much work a sorting algorithm did, counted from the steps that it emitted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortMetrics {
+    /// The number of steps, including the ones that only mark the start of a phase or explain the next steps.
+    pub steps: usize,
+    pub swaps: usize,
+    pub sets: usize,
+}
+impl SortMetrics {
+    pub fn from_steps<T>(steps: &[Vec<SortCommand<T>>]) -> Self {
+        let mut metrics = Self {
+            steps: steps.len(),
+            ..Default::default()
+        };
+        for command in steps.iter().flatten() {
+            match command {
+                SortCommand::Swap(..) => metrics.swaps += 1,
+                SortCommand::Set(..) => metrics.sets += 1,
+                SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => (),
+            }
+        }
+        metrics
+    }
+}
+
+/// The sorting algorithms that [`sort_by_name`] can run by their names, which are in the same format as the sorting page's routes.\
+/// Bucket sort, counting sort and radix sort aren't included, since they only sort unsigned whole numbers.
+pub fn get_sorting_algorithms<T: Clone + Copy + Ord>() -> BTreeMap<&'static str, SortFn<T>> {
+    // `BTreeMap` because it keeps the order of the items.
+    BTreeMap::from([
+        ("bubble-sort", bubble_sort_with_phases as SortFn<T>),
+        ("comb-sort", |items, steps| comb_sort(items, steps)),
+        ("heapsort", heapsort),
+        ("insertion-sort", insertion_sort),
+        ("merge-sort", merge_sort),
+        ("odd-even-sort", |items, steps| odd_even_sort(items, steps)),
+        ("quicksort", quicksort),
+        ("shell-sort", |items, steps| shell_sort(items, steps)),
+    ])
+}
+
+/// Gets the names of the algorithms from [`get_sorting_algorithms`] in alphabetical order.
+pub fn sorting_algorithm_names() -> Vec<&'static str> {
+    get_sorting_algorithms::<u32>().into_keys().collect()
+}
+
+/// Gets the sorting algorithm with the given name from [`get_sorting_algorithms`].
+pub fn sorting_algorithm_by_name<T: Clone + Copy + Ord>(
+    name: &str,
+) -> Result<SortFn<T>, UnknownAlgorithmError> {
+    get_sorting_algorithms()
+        .remove(name)
+        .ok_or_else(|| UnknownAlgorithmError {
+            name: name.to_string(),
+        })
+}
+
+/// Sorts `data` in place with the algorithm with the given name from [`get_sorting_algorithms`] and counts the work it did.
+///
+/// ## Example
+///
+/// ```rust
+/// use sorting::sort_by_name;
+///
+/// let mut data = [3, -1, 2];
+/// let metrics = sort_by_name("bubble-sort", &mut data).unwrap();
+///
+/// assert_eq!(data, [-1, 2, 3]);
+/// assert_eq!(metrics.swaps, 2);
+/// assert!(sort_by_name("bogosort", &mut data).is_err());
+/// ```
+pub fn sort_by_name(name: &str, data: &mut [i32]) -> Result<SortMetrics, UnknownAlgorithmError> {
+    let sort = sorting_algorithm_by_name(name)?;
+
+    let mut items = data.to_vec();
+    let mut steps = vec![];
+    sort(&mut items, &mut steps);
+    data.copy_from_slice(&items);
+
+    Ok(SortMetrics::from_steps(&steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_algorithms_sort_and_count_work() {
+        let input = [5, -3, 0, 9, -3, 2, 7, -8];
+        let mut sorted = input;
+        sorted.sort_unstable();
+
+        for name in sorting_algorithm_names() {
+            let mut data = input;
+            let metrics = sort_by_name(name, &mut data).unwrap();
+            assert_eq!(data, sorted, "{}", name);
+            assert!(metrics.swaps + metrics.sets > 0, "{}", name);
+        }
+
+        let mut data = [2, 1, 3];
+        assert_eq!(
+            sort_by_name("insertion-sort", &mut data),
+            Ok(SortMetrics {
+                steps: 1,
+                swaps: 1,
+                sets: 0
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_name_is_an_error() {
+        let mut data = [2, 1, 3];
+        let error = sort_by_name("bogosort", &mut data).unwrap_err();
+
+        assert_eq!(error.name, "bogosort");
+        assert_eq!(error.to_string(), "unknown sorting algorithm \"bogosort\"");
+        // The data isn't touched
+        assert_eq!(data, [2, 1, 3]);
+    }
+}
@@ -0,0 +1,166 @@
+//! Sorting items by a key, and checking whether a sort kept equal keys in their original order.
+use std::cmp::Ordering;
+
+use crate::{SortCommand, SortFn};
+
+/// An item that is only compared by its `key`, so items with equal keys can carry different values.\
+/// Tagging the items with their original index as the value shows whether an algorithm is stable, see [`unstable_pairs`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Keyed<K, V> {
+    pub key: K,
+    pub value: V,
+}
+
+impl<K: Ord, V> PartialEq for Keyed<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+impl<K: Ord, V> Eq for Keyed<K, V> {}
+impl<K: Ord, V> PartialOrd for Keyed<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<K: Ord, V> Ord for Keyed<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Sorts the items by the keys that `key` gets from them with one of the sorting algorithms, like [`slice::sort_by_key`].
+///
+/// Neither the items nor the keys have to be `Copy`, so e.g. strings can be sorted too. The algorithm sorts the ranks of the keys
+/// tagged with the index of their item, and the sorted items and the values in the steps are cloned from the original items.
+///
+/// ## Example
+///
+/// ```rust
+/// use sorting::{merge_sort, sort_by_key};
+///
+/// let mut words = vec!["pear".to_string(), "fig".to_string(), "kiwi".to_string()];
+/// sort_by_key(merge_sort, &mut words, &mut vec![], |word| word.len());
+///
+/// // Merge sort is stable, so "pear" stays before "kiwi"
+/// assert_eq!(words, ["fig", "pear", "kiwi"]);
+/// ```
+pub fn sort_by_key<T: Clone, K: Ord>(
+    sort: SortFn<Keyed<usize, usize>>,
+    items: &mut Vec<T>,
+    steps: &mut Vec<Vec<SortCommand<T>>>,
+    key: impl Fn(&T) -> K,
+) {
+    let keys = items.iter().map(key).collect::<Vec<K>>();
+    let mut order = (0..items.len()).collect::<Vec<usize>>();
+    order.sort_by(|a, b| keys[*a].cmp(&keys[*b]));
+
+    // Equal keys get the same rank
+    let mut ranks = vec![0; items.len()];
+    for pair in order.windows(2) {
+        ranks[pair[1]] = ranks[pair[0]] + usize::from(keys[pair[0]] != keys[pair[1]]);
+    }
+
+    let mut keyed = ranks
+        .into_iter()
+        .enumerate()
+        .map(|(i, rank)| Keyed {
+            key: rank,
+            value: i,
+        })
+        .collect();
+    let mut keyed_steps = vec![];
+    sort(&mut keyed, &mut keyed_steps);
+
+    let original = std::mem::take(items);
+    *items = keyed
+        .iter()
+        .map(|item| original[item.value].clone())
+        .collect();
+    steps.extend(keyed_steps.into_iter().map(|step| {
+        step.into_iter()
+            .map(|command| command.map_value(|item| original[item.value].clone()))
+            .collect::<Vec<SortCommand<T>>>()
+    }));
+}
+
+/// Finds the neighbouring items that have equal keys but whose values are in descending order, as the index of the first item of each pair.
+///
+/// If the items were tagged with their original index before sorting them, this is empty exactly when the sort kept
+/// the items with equal keys in their original order, i.e. when it was stable on that input.
+pub fn unstable_pairs<K: Ord, V: PartialOrd>(items: &[Keyed<K, V>]) -> Vec<usize> {
+    (1..items.len())
+        .filter(|i| items[i - 1].key == items[*i].key && items[i - 1].value > items[*i].value)
+        .map(|i| i - 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bubble_sort, heapsort, insertion_sort, merge_sort, odd_even_sort, run_sort_steps,
+        shell_sort,
+    };
+
+    /// Sorts items with only a few different keys, tagged with their original index.
+    fn sort_tagged(sort: SortFn<Keyed<u32, usize>>) -> Vec<Keyed<u32, usize>> {
+        let mut items = (0..60)
+            .map(|i| Keyed {
+                key: (i * 7 + 3) % 4,
+                value: i as usize,
+            })
+            .collect();
+        sort(&mut items, &mut vec![]);
+        items
+    }
+
+    #[test]
+    fn stable_algorithms_keep_equal_keys_in_order() {
+        let odd_even_sort: SortFn<_> = |items, steps| odd_even_sort(items, steps);
+        for sort in [bubble_sort, insertion_sort, merge_sort, odd_even_sort] {
+            let items = sort_tagged(sort);
+            assert!(items.windows(2).all(|pair| pair[0].key <= pair[1].key));
+            assert!(unstable_pairs(&items).is_empty());
+        }
+    }
+
+    #[test]
+    fn unstable_algorithms_reorder_equal_keys() {
+        let shell_sort: SortFn<_> = |items, steps| shell_sort(items, steps);
+        for sort in [heapsort, shell_sort] {
+            let items = sort_tagged(sort);
+            assert!(items.windows(2).all(|pair| pair[0].key <= pair[1].key));
+            assert!(!unstable_pairs(&items).is_empty());
+        }
+    }
+
+    #[test]
+    fn steps_replay_the_sort_by_key() {
+        let input = ["cc", "a", "bbb", "b", "dd", ""].map(String::from).to_vec();
+        let mut items = input.clone();
+        let mut steps = vec![];
+        sort_by_key(heapsort, &mut items, &mut steps, |item| item.len());
+        assert!(items.windows(2).all(|pair| pair[0].len() <= pair[1].len()));
+
+        // The strings aren't `Copy`, so replay the indices of the items instead
+        let mut replayed = (0..input.len()).collect::<Vec<usize>>();
+        let index_steps = steps
+            .into_iter()
+            .map(|step| {
+                step.into_iter()
+                    .map(|command| {
+                        command.map_value(|item| input.iter().position(|i| *i == item).unwrap())
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<SortCommand<usize>>>>();
+        run_sort_steps(&mut replayed, &index_steps);
+        assert_eq!(
+            replayed
+                .iter()
+                .map(|i| input[*i].clone())
+                .collect::<Vec<String>>(),
+            items
+        );
+    }
+}
@@ -1,6 +1,37 @@
+pub mod analysis;
+pub mod binary_heap_visual;
+pub mod complexity;
+pub mod dispatch;
+pub mod keyed;
+pub mod narration;
+pub mod online_sorter;
+pub mod playback;
+pub mod race;
+pub mod run;
+pub mod searching;
+pub mod selection;
 pub mod sorting_algorithms;
+pub mod stats;
+pub mod total_f64;
+pub mod worst_case;
+pub use analysis::*;
+pub use binary_heap_visual::*;
+pub use complexity::*;
+pub use dispatch::*;
+pub use keyed::*;
+pub use narration::*;
+pub use online_sorter::*;
+pub use playback::*;
+pub use race::*;
+pub use run::*;
+pub use searching::*;
+pub use selection::*;
 pub use sorting_algorithms::*;
+pub use stats::*;
+pub use total_f64::*;
+pub use worst_case::*;
 
+use serde::{Deserialize, Serialize};
 use std::{cell::RefCell, rc::Rc};
 
 /// Runs a given sorting algorithm on given items.
@@ -35,12 +66,34 @@ pub fn run_sort<T: Clone + Copy + Ord>(
 ///
 /// assert_eq!(items, vec![1, 2, 3]);
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum SortCommand<T> {
     /// Swap items in a collection by index: `(from_index, to_index)`
     Swap(usize, usize),
     /// Set the value of an item in a collection by index: `(index, value)`
     Set(usize, T),
+    /// Mark the start of a new phase of the algorithm, like a pass of bubble sort: `(label)`.\
+    /// This doesn't change the collection, and it's only emitted by algorithms that opt in to it.
+    PhaseBoundary(String),
+    /// Explain the steps that follow, like choosing a pivot: `(event)`.\
+    /// This doesn't change the collection either, and it's only emitted by algorithms that opt in to it.
+    Event(SortEvent<T>),
+}
+
+impl<T> SortCommand<T> {
+    /// Converts the value of a [`SortCommand::Set`] with `f`, keeping the indices and labels of all commands.
+    pub fn map_value<U>(self, f: impl FnOnce(T) -> U) -> SortCommand<U> {
+        match self {
+            SortCommand::Swap(from, to) => SortCommand::Swap(from, to),
+            SortCommand::Set(index, value) => SortCommand::Set(index, f(value)),
+            SortCommand::PhaseBoundary(label) => SortCommand::PhaseBoundary(label),
+            SortCommand::Event(event) => SortCommand::Event(event.map_value(f)),
+        }
+    }
+    /// Checks if the command changes the collection, unlike phase boundaries and events.
+    pub fn is_operation(&self) -> bool {
+        matches!(self, SortCommand::Swap(..) | SortCommand::Set(..))
+    }
 }
 
 /// Runs given sorting operations on a vector of type T.
@@ -50,6 +103,7 @@ pub fn run_sort_steps<T: Clone + Copy>(items: &mut [T], steps: &[Vec<SortCommand
             match command {
                 SortCommand::Swap(from, to) => items.swap(*from, *to),
                 SortCommand::Set(index, value) => items[*index] = *value,
+                SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => (),
             }
         }
     }
@@ -59,9 +113,18 @@ pub fn run_sort_steps<T: Clone + Copy>(items: &mut [T], steps: &[Vec<SortCommand
 pub struct SortResult<T: Clone + Copy + PartialEq + PartialOrd> {
     pub duration: Option<instant::Duration>,
     pub steps: Vec<Vec<SortCommand<T>>>,
+    /// How many times the items were compared, if the sort was run with [`Counted`] items.
+    pub comparisons: Option<usize>,
+    /// How many comparisons had been made when each step was recorded, see [`count_comparisons_by_step`].
+    pub step_comparisons: Option<Vec<usize>>,
 }
 impl<T: Clone + Copy + PartialEq + PartialOrd> SortResult<T> {
     pub fn new(duration: Option<instant::Duration>, steps: Vec<Vec<SortCommand<T>>>) -> Self {
-        Self { duration, steps }
+        Self {
+            duration,
+            steps,
+            comparisons: None,
+            step_comparisons: None,
+        }
     }
 }
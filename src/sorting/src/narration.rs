@@ -0,0 +1,176 @@
+//! Describing what a sorting algorithm is doing at a step in words.
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::SortCommand;
+
+/// Something that a sorting algorithm is about to do, which explains the swaps and sets that follow it.\
+/// Events don't change the collection, and they're only emitted by the algorithms that opt in to them, like [`crate::quicksort_with_events`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SortEvent<T> {
+    /// A pivot was chosen for partitioning the items between two indices, both included: `(pivot, low, high)`
+    Pivot(T, usize, usize),
+    /// Two adjacent sorted runs are merged, as `(first, last)` indices of both runs: `(left, right)`
+    Merge((usize, usize), (usize, usize)),
+    /// The item at an index is sifted down the max-heap made of the first items: `(root, heap_len)`
+    Heapify(usize, usize),
+    /// The root of the max-heap made of the first items is swapped to the end of the heap, where it's in its sorted place: `(heap_len)`
+    Extract(usize),
+}
+
+impl<T> SortEvent<T> {
+    /// Converts the value of a [`SortEvent::Pivot`] with `f`, keeping the indices.
+    pub fn map_value<U>(self, f: impl FnOnce(T) -> U) -> SortEvent<U> {
+        match self {
+            SortEvent::Pivot(pivot, low, high) => SortEvent::Pivot(f(pivot), low, high),
+            SortEvent::Merge(left, right) => SortEvent::Merge(left, right),
+            SortEvent::Heapify(root, heap_len) => SortEvent::Heapify(root, heap_len),
+            SortEvent::Extract(heap_len) => SortEvent::Extract(heap_len),
+        }
+    }
+    /// Adds a given offset to the indices of the event, keeping the length of the heap.
+    pub fn add_offset(self, offset: usize) -> Self {
+        match self {
+            SortEvent::Pivot(pivot, low, high) => {
+                SortEvent::Pivot(pivot, low + offset, high + offset)
+            }
+            SortEvent::Merge(left, right) => SortEvent::Merge(
+                (left.0 + offset, left.1 + offset),
+                (right.0 + offset, right.1 + offset),
+            ),
+            SortEvent::Heapify(root, heap_len) => SortEvent::Heapify(root + offset, heap_len),
+            SortEvent::Extract(heap_len) => SortEvent::Extract(heap_len),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for SortEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortEvent::Pivot(pivot, low, high) => write!(
+                f,
+                "Pivot {} chosen; partitioning indices {}..{}",
+                pivot, low, high
+            ),
+            SortEvent::Merge(left, right) => write!(
+                f,
+                "Merging runs [{}..{}] and [{}..{}]",
+                left.0, left.1, right.0, right.1
+            ),
+            SortEvent::Heapify(root, heap_len) => write!(
+                f,
+                "Sifting index {} down the heap of the first {} items",
+                root, heap_len
+            ),
+            SortEvent::Extract(heap_len) => write!(
+                f,
+                "Moving the largest item of the heap of the first {} items to index {}",
+                heap_len,
+                heap_len - 1
+            ),
+        }
+    }
+}
+
+/// Describes what the algorithm is doing after the first `played` of its `steps` have been played, or `None` at the input.\
+/// The latest [`SortEvent`] or phase explains the operations after it. Algorithms that emit neither are narrated one operation at a time.
+pub fn narrate<T: fmt::Display>(steps: &[Vec<SortCommand<T>>], played: usize) -> Option<String> {
+    let played = &steps[..played.min(steps.len())];
+
+    let context = played
+        .iter()
+        .flatten()
+        .rev()
+        .find_map(|command| match command {
+            SortCommand::Event(event) => Some(event.to_string()),
+            SortCommand::PhaseBoundary(label) => Some(format!("Starting {}", label)),
+            _ => None,
+        });
+    if context.is_some() {
+        return context;
+    }
+
+    let operations = played
+        .last()?
+        .iter()
+        .map(|command| match command {
+            SortCommand::Swap(from, to) => format!("Swapping indices {} and {}", from, to),
+            SortCommand::Set(index, value) => format!("Writing {} to index {}", value, index),
+            // Neither of these can be in the steps without being found above
+            SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => unreachable!(),
+        })
+        .collect::<Vec<String>>();
+    Some(operations.join("; "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        heapsort, heapsort_with_events, insertion_sort, merge_sort, merge_sort_with_events,
+        quicksort, quicksort_with_events, run_sort_steps, SortFn,
+    };
+
+    #[test]
+    fn events_dont_change_the_other_steps() {
+        let input: Vec<u32> = (0..40).map(|i| (i * 37 + 11) % 23).collect();
+        let pairs: [(SortFn<u32>, SortFn<u32>); 3] = [
+            (quicksort, quicksort_with_events),
+            (merge_sort, merge_sort_with_events),
+            (heapsort, heapsort_with_events),
+        ];
+
+        for (plain, with_events) in pairs {
+            let mut plain_steps = vec![];
+            plain(&mut input.clone(), &mut plain_steps);
+
+            let mut steps = vec![];
+            with_events(&mut input.clone(), &mut steps);
+            let mut replayed = input.clone();
+            run_sort_steps(&mut replayed, &steps);
+            assert!(replayed.windows(2).all(|pair| pair[0] <= pair[1]));
+
+            assert!(steps.len() > plain_steps.len());
+            steps.retain(|step| !matches!(step[..], [SortCommand::Event(_)]));
+            assert_eq!(steps, plain_steps);
+        }
+    }
+
+    #[test]
+    fn narrates_the_latest_event() {
+        let mut steps = vec![];
+        quicksort_with_events(&mut vec![3, 5, 1, 4, 2], &mut steps);
+
+        assert_eq!(narrate(&steps, 0), None);
+        assert_eq!(
+            narrate(&steps, 1).unwrap(),
+            "Pivot 2 chosen; partitioning indices 0..4"
+        );
+        // The swaps of the partition are explained by the pivot
+        assert_eq!(narrate(&steps, 2), narrate(&steps, 1));
+
+        let mut steps = vec![];
+        merge_sort_with_events(&mut vec![2, 1, 4, 3], &mut steps);
+        assert_eq!(
+            narrate(&steps, steps.len()).unwrap(),
+            "Merging runs [0..1] and [2..3]"
+        );
+    }
+
+    #[test]
+    fn narrates_operations_without_events() {
+        let mut steps = vec![];
+        insertion_sort(&mut vec![2, 1], &mut steps);
+        assert_eq!(
+            narrate(&steps, steps.len()).unwrap(),
+            "Swapping indices 0 and 1"
+        );
+
+        let steps = vec![vec![SortCommand::Set(3, 7), SortCommand::Set(4, 8)]];
+        assert_eq!(
+            narrate(&steps, 1).unwrap(),
+            "Writing 7 to index 3; Writing 8 to index 4"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Sorting a collection that grows one element at a time.
+
+/// Keeps its items sorted as they are inserted one at a time, unlike the sorting algorithms which sort a whole collection at once.
+///
+/// The position of each item is found with binary search, but the items after it still have to be shifted,
+/// so an insertion takes *O(n)* time and inserting *n* items takes *O(n²)* time in the worst case, like insertion sort.
+///
+/// ## Example
+///
+/// ```rust
+/// use sorting::OnlineSorter;
+///
+/// let mut sorter = OnlineSorter::new();
+/// sorter.insert(3);
+/// sorter.insert(1);
+/// sorter.insert(2);
+///
+/// assert_eq!(sorter.as_slice(), &[1, 2, 3]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OnlineSorter<T: Ord> {
+    items: Vec<T>,
+}
+impl<T: Ord> OnlineSorter<T> {
+    pub fn new() -> Self {
+        Self { items: vec![] }
+    }
+    /// Inserts `item` at its sorted position and returns the position.\
+    /// Equal items stay in the order they were inserted in.
+    pub fn insert(&mut self, item: T) -> usize {
+        let index = self.items.partition_point(|other| *other <= item);
+        self.items.insert(index, item);
+        index
+    }
+    /// Gets the items in ascending order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stays_sorted_after_every_insert() {
+        let input = [5, 3, 8, 3, 1, 9, 0, 5, 7, 2];
+        let mut sorter = OnlineSorter::new();
+
+        for (count, item) in input.iter().copied().enumerate() {
+            let index = sorter.insert(item);
+            assert_eq!(sorter.as_slice()[index], item);
+            assert_eq!(sorter.len(), count + 1);
+            assert!(sorter.as_slice().windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+
+        let mut expected = input.to_vec();
+        expected.sort();
+        assert_eq!(sorter.into_vec(), expected);
+    }
+}
@@ -0,0 +1,171 @@
+//! Playing the steps of a sort forwards and backwards.
+use crate::SortCommand;
+
+/// Plays back the steps of a sort on a copy of its input, one step at a time in either direction.
+///
+/// Stepping backward applies the inverse of the step instead of replaying everything from the input.
+/// A [`SortCommand::Swap`] is its own inverse, but a [`SortCommand::Set`] overwrites a value,
+/// so the value it replaced is recorded when the step is played forwards.
+///
+/// ## Example
+///
+/// ```rust
+/// use sorting::{SortCommand, SortPlayback};
+///
+/// let steps = vec![vec![SortCommand::Swap(0, 2)], vec![SortCommand::Set(1, 2)]];
+/// let mut playback = SortPlayback::new(vec![3, 4, 1], steps);
+///
+/// playback.seek(2);
+/// assert_eq!(playback.items(), &[1, 2, 3]);
+///
+/// playback.step_backward();
+/// assert_eq!(playback.items(), &[1, 4, 3]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SortPlayback<T: Clone> {
+    items: Vec<T>,
+    steps: Vec<Vec<SortCommand<T>>>,
+    /// The inverse of every step that has been played, in the order they were played.
+    undo_steps: Vec<Vec<SortCommand<T>>>,
+}
+impl<T: Clone> SortPlayback<T> {
+    /// Starts the playback at the `input`, before the first step.
+    pub fn new(input: Vec<T>, steps: Vec<Vec<SortCommand<T>>>) -> Self {
+        Self {
+            items: input,
+            steps,
+            undo_steps: vec![],
+        }
+    }
+    /// Gets the items as they are after the steps played so far.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+    /// Gets the number of steps played so far.
+    pub fn position(&self) -> usize {
+        self.undo_steps.len()
+    }
+    /// Gets the total number of steps.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+    /// Gets all of the steps, including the ones that haven't been played yet.
+    pub fn steps(&self) -> &[Vec<SortCommand<T>>] {
+        &self.steps
+    }
+    /// Gets the step that was played last, or `None` if the playback is at the input.
+    pub fn last_step(&self) -> Option<&[SortCommand<T>]> {
+        let index = self.position().checked_sub(1)?;
+        Some(&self.steps[index])
+    }
+    /// Plays the next step. Returns `false` if all steps have already been played.
+    pub fn step_forward(&mut self) -> bool {
+        let Some(step) = self.steps.get(self.position()) else {
+            return false;
+        };
+
+        let mut undo_step = Vec::with_capacity(step.len());
+        for command in step {
+            match command {
+                SortCommand::Swap(from, to) => {
+                    self.items.swap(*from, *to);
+                    undo_step.push(SortCommand::Swap(*from, *to));
+                }
+                SortCommand::Set(index, value) => {
+                    let old = std::mem::replace(&mut self.items[*index], value.clone());
+                    undo_step.push(SortCommand::Set(*index, old));
+                }
+                SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => (),
+            }
+        }
+        // A step with several commands is undone by inverting them in the opposite order
+        undo_step.reverse();
+        self.undo_steps.push(undo_step);
+
+        true
+    }
+    /// Undoes the last played step. Returns `false` if the playback is already at the input.
+    pub fn step_backward(&mut self) -> bool {
+        let Some(undo_step) = self.undo_steps.pop() else {
+            return false;
+        };
+
+        for command in undo_step {
+            match command {
+                SortCommand::Swap(from, to) => self.items.swap(from, to),
+                SortCommand::Set(index, value) => self.items[index] = value,
+                SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => (),
+            }
+        }
+
+        true
+    }
+    /// Steps forwards or backwards until `position` steps have been played, or until the last step if there are fewer steps.
+    pub fn seek(&mut self, position: usize) {
+        while self.position() < position && self.step_forward() {}
+        while self.position() > position && self.step_backward() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{heapsort, merge_sort};
+
+    /// Plays all `steps` forwards and then backwards, checking that the items end up sorted and then back at the `input`.
+    fn play_there_and_back(input: &[u32], steps: Vec<Vec<SortCommand<u32>>>) {
+        let mut sorted = input.to_vec();
+        sorted.sort_unstable();
+
+        let mut playback = SortPlayback::new(input.to_vec(), steps);
+        let n = playback.len();
+        for _ in 0..n {
+            assert!(playback.step_forward());
+        }
+        assert!(!playback.step_forward());
+        assert_eq!(playback.items(), &sorted[..]);
+
+        for _ in 0..n {
+            assert!(playback.step_backward());
+        }
+        assert!(!playback.step_backward());
+        assert_eq!(playback.position(), 0);
+        assert_eq!(playback.items(), input);
+    }
+
+    #[test]
+    fn forward_then_backward_returns_to_input() {
+        let input = [5, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+
+        // Heapsort swaps and merge sort sets values
+        let mut heapsort_steps = vec![];
+        heapsort(&mut input.to_vec(), &mut heapsort_steps);
+        play_there_and_back(&input, heapsort_steps);
+
+        let mut merge_sort_steps = vec![];
+        merge_sort(&mut input.to_vec(), &mut merge_sort_steps);
+        play_there_and_back(&input, merge_sort_steps);
+    }
+
+    #[test]
+    fn seek_rewinds_a_step_with_several_commands() {
+        // Setting the same index twice in one step must restore the value from before the step
+        let steps = vec![
+            vec![SortCommand::Swap(0, 1)],
+            vec![SortCommand::Set(2, 7), SortCommand::Set(2, 8)],
+        ];
+        let mut playback = SortPlayback::new(vec![1, 2, 3], steps);
+
+        playback.seek(10);
+        assert_eq!((playback.position(), playback.items()), (2, &[2, 1, 8][..]));
+        playback.seek(1);
+        assert_eq!(playback.items(), &[2, 1, 3]);
+        assert_eq!(playback.last_step(), Some(&[SortCommand::Swap(0, 1)][..]));
+        playback.seek(0);
+        assert_eq!(playback.items(), &[1, 2, 3]);
+        assert_eq!(playback.last_step(), None);
+    }
+}
@@ -0,0 +1,250 @@
+//! Racing several sorts on the same input by playing their operations in lockstep.
+use std::iter;
+
+use crate::{SortCommand, SortPlayback};
+
+/// Plays the steps of several sorts of the same input side by side, synchronized by the number of operations.\
+/// Every frame plays one [`SortCommand::Swap`] or [`SortCommand::Set`] of each racer along with the [`SortCommand::PhaseBoundary`] and [`SortCommand::Event`] steps before it,
+/// so marking phases and explaining steps doesn't slow a racer down. A racer that runs out of operations is finished and stays sorted while the others keep going,
+/// so the racer with the fewest operations wins.
+///
+/// ## Example
+///
+/// ```rust
+/// use sorting::{bubble_sort, merge_sort, SortRace};
+///
+/// let input: Vec<u32> = (1..=16).rev().collect();
+/// let (mut bubble_steps, mut merge_steps) = (vec![], vec![]);
+/// bubble_sort(&mut input.clone(), &mut bubble_steps);
+/// merge_sort(&mut input.clone(), &mut merge_steps);
+///
+/// // Bubble sort swaps every pair of the reversed input, while merge sort only sets each item once per level
+/// let mut race = SortRace::new(input, vec![bubble_steps, merge_steps]);
+/// assert_eq!(race.winners(), vec![1]);
+/// assert_eq!((race.operations(0), race.operations(1)), (120, 64));
+///
+/// race.seek(race.len());
+/// assert_eq!(race.place(0), Some(2));
+/// assert!(race.racers()[0].items().windows(2).all(|pair| pair[0] <= pair[1]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SortRace<T: Clone> {
+    racers: Vec<SortPlayback<T>>,
+    /// For every racer, how many of its steps have been played after each number of operations.
+    positions: Vec<Vec<usize>>,
+    frame: usize,
+}
+impl<T: Clone> SortRace<T> {
+    /// Starts a race where every racer sorts its own copy of the `input` with its steps from `steps`.
+    pub fn new(input: Vec<T>, steps: Vec<Vec<Vec<SortCommand<T>>>>) -> Self {
+        Self {
+            positions: steps
+                .iter()
+                .map(|steps| operation_positions(steps))
+                .collect(),
+            racers: steps
+                .into_iter()
+                .map(|steps| SortPlayback::new(input.clone(), steps))
+                .collect(),
+            frame: 0,
+        }
+    }
+    /// Gets the playbacks of the racers in the order that their steps were given.
+    pub fn racers(&self) -> &[SortPlayback<T>] {
+        &self.racers
+    }
+    /// Gets the number of frames played so far.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+    /// Gets the number of swaps and sets that the racer at `index` does in total, which is the number of frames that it takes.
+    /// There is no racer at `index` if it's out of bounds, so it does none.
+    pub fn operations(&self, index: usize) -> usize {
+        self.positions
+            .get(index)
+            .map_or(0, |positions| positions.len() - 1)
+    }
+    /// Gets the number of frames until every racer has finished, i.e. the number of operations of the slowest racer.
+    pub fn len(&self) -> usize {
+        (0..self.racers.len())
+            .map(|index| self.operations(index))
+            .max()
+            .unwrap_or(0)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Plays one operation of every racer that hasn't finished yet. Returns `false` if all of them had already finished.
+    pub fn step(&mut self) -> bool {
+        if self.frame >= self.len() {
+            return false;
+        }
+        self.seek(self.frame + 1);
+        true
+    }
+    /// Moves every racer forwards or backwards to the given frame, or to the last frame if there are fewer frames.
+    pub fn seek(&mut self, frame: usize) {
+        self.frame = frame.min(self.len());
+        for (racer, positions) in self.racers.iter_mut().zip(&self.positions) {
+            racer.seek(positions[self.frame.min(positions.len() - 1)]);
+        }
+    }
+    /// Gets the steps that the racer at `index` played in the current frame.
+    /// There are none at the input and after the racer has finished.
+    pub fn frame_steps(&self, index: usize) -> &[Vec<SortCommand<T>>] {
+        let positions = &self.positions[index];
+        if self.frame == 0 || self.frame >= positions.len() {
+            return &[];
+        }
+        &self.racers[index].steps()[positions[self.frame - 1]..positions[self.frame]]
+    }
+    /// Returns `true` if the racer at `index` has played all of its operations by the current frame.
+    pub fn is_finished(&self, index: usize) -> bool {
+        self.operations(index) <= self.frame
+    }
+    /// Gets the place of the racer at `index` starting from 1, or `None` if it hasn't finished yet.
+    /// Racers with the same number of operations share the same place.
+    pub fn place(&self, index: usize) -> Option<usize> {
+        if !self.is_finished(index) {
+            return None;
+        }
+        let operations = self.operations(index);
+        Some(
+            1 + (0..self.racers.len())
+                .filter(|other| self.operations(*other) < operations)
+                .count(),
+        )
+    }
+    /// Gets the indices of the racers that finish first, which is several of them in case of a tie.
+    pub fn winners(&self) -> Vec<usize> {
+        let fewest_operations = (0..self.racers.len())
+            .map(|index| self.operations(index))
+            .min();
+        (0..self.racers.len())
+            .filter(|index| Some(self.operations(*index)) == fewest_operations)
+            .collect()
+    }
+}
+
+/// Gets how many of the `steps` have been played after each number of operations, from none to all of them.\
+/// A step that only marks a phase or explains the next steps is played along with the next operation, or with the last one at the end.
+fn operation_positions<T>(steps: &[Vec<SortCommand<T>>]) -> Vec<usize> {
+    let mut positions = vec![0];
+    for (i, step) in steps.iter().enumerate() {
+        let operations = step.iter().filter(|command| command.is_operation()).count();
+        positions.extend(iter::repeat_n(i + 1, operations));
+    }
+    *positions.last_mut().unwrap() = steps.len();
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bubble_sort, bubble_sort_with_phases, heapsort, insertion_sort, merge_sort, SortFn,
+    };
+
+    fn race(input: &[u32]) -> SortRace<u32> {
+        let sorts: [SortFn<u32>; 4] = [bubble_sort, insertion_sort, heapsort, merge_sort];
+        let steps = sorts
+            .iter()
+            .map(|sort| {
+                let mut steps = vec![];
+                sort(&mut input.to_vec(), &mut steps);
+                steps
+            })
+            .collect();
+        SortRace::new(input.to_vec(), steps)
+    }
+
+    #[test]
+    fn racers_advance_in_lockstep() {
+        let input = [9, 2, 7, 4, 1, 8, 3, 6, 5];
+        let mut race = race(&input);
+        let lens: Vec<usize> = race.racers().iter().map(SortPlayback::len).collect();
+        assert_eq!(race.len(), *lens.iter().max().unwrap());
+
+        let mut frames = 0;
+        while race.step() {
+            frames += 1;
+            // Every racer has played one step per frame until it ran out of steps
+            for (racer, len) in race.racers().iter().zip(&lens) {
+                assert_eq!(racer.position(), frames.min(*len));
+            }
+        }
+        assert_eq!((frames, race.frame()), (race.len(), race.len()));
+
+        let mut sorted = input.to_vec();
+        sorted.sort_unstable();
+        assert!(race.racers().iter().all(|racer| racer.items() == sorted));
+
+        // Seeking back rewinds every racer to the same frame
+        race.seek(3);
+        assert!(race.racers().iter().all(|racer| racer.position() == 3));
+        race.seek(0);
+        assert!(race.racers().iter().all(|racer| racer.items() == input));
+    }
+
+    #[test]
+    fn fewest_operations_finishes_first() {
+        let input = [1, 2, 3, 5, 4];
+        let mut race = race(&input);
+        // Bubble sort and insertion sort only need to swap the last two items
+        assert_eq!(race.winners(), vec![0, 1]);
+        assert_eq!(race.place(0), None);
+
+        race.seek(1);
+        assert_eq!((race.place(0), race.place(1)), (Some(1), Some(1)));
+        assert!(!race.is_finished(2) && !race.is_finished(3));
+
+        race.seek(race.len());
+        let places: Vec<usize> = (0..4).map(|index| race.place(index).unwrap()).collect();
+        assert_eq!(places[..2], [1, 1]);
+        assert!(places[2..].iter().all(|place| *place >= 3));
+    }
+
+    #[test]
+    fn phase_boundaries_take_no_frames() {
+        let input: Vec<u32> = vec![4, 3, 1, 2];
+        let steps = [bubble_sort, bubble_sort_with_phases]
+            .iter()
+            .map(|sort: &SortFn<u32>| {
+                let mut steps = vec![];
+                sort(&mut input.clone(), &mut steps);
+                steps
+            })
+            .collect::<Vec<_>>();
+        assert!(steps[1].len() > steps[0].len());
+
+        let mut race = SortRace::new(input, steps);
+        assert_eq!(race.winners(), vec![0, 1]);
+        assert_eq!(race.len(), race.operations(0));
+
+        // The first pass is marked along with its first swap
+        race.step();
+        assert_eq!(race.frame_steps(0), [vec![SortCommand::Swap(0, 1)]]);
+        assert_eq!(
+            race.frame_steps(1),
+            [
+                vec![SortCommand::PhaseBoundary("Pass 1".to_string())],
+                vec![SortCommand::Swap(0, 1)]
+            ]
+        );
+
+        while race.step() {
+            assert_eq!(race.racers()[0].items(), race.racers()[1].items());
+        }
+        assert_eq!(race.racers()[1].position(), race.racers()[1].len());
+    }
+
+    #[test]
+    fn missing_racers_have_no_operations() {
+        let race = race(&[3, 1, 2]);
+        assert_eq!(race.operations(4), 0);
+        assert_eq!(
+            race.len(),
+            (0..4).map(|index| race.operations(index)).max().unwrap()
+        );
+    }
+}
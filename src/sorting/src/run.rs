@@ -0,0 +1,87 @@
+//! Recording a complete sort so that it can be shared and replayed exactly.
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    sorting_algorithm_by_name, SortCommand, SortMetrics, SortPlayback, UnknownAlgorithmError,
+};
+
+/// Everything needed to replay a sort: the algorithm, its input and the steps that it took on that input.\
+/// It can be (de)serialized with serde, e.g. to JSON, so a run that behaves strangely can be exported and replayed by someone else.
+///
+/// ## Example
+///
+/// ```rust
+/// use sorting::SortRun;
+///
+/// let run = SortRun::record("insertion-sort", vec![3, 1, 2]).unwrap();
+///
+/// assert_eq!(run.metrics.swaps, 2);
+/// assert_eq!(run.replay(), vec![1, 2, 3]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SortRun<T> {
+    /// The name of the algorithm from [`get_sorting_algorithms`](crate::get_sorting_algorithms).
+    pub algorithm: String,
+    pub input: Vec<T>,
+    pub steps: Vec<Vec<SortCommand<T>>>,
+    pub metrics: SortMetrics,
+}
+impl<T: Clone + Copy + Ord> SortRun<T> {
+    /// Sorts a copy of the `input` with the algorithm with the given name and records its steps.
+    pub fn record(algorithm: &str, input: Vec<T>) -> Result<Self, UnknownAlgorithmError> {
+        let sort = sorting_algorithm_by_name(algorithm)?;
+        let mut steps = vec![];
+        sort(&mut input.clone(), &mut steps);
+
+        Ok(Self {
+            algorithm: algorithm.to_string(),
+            input,
+            metrics: SortMetrics::from_steps(&steps),
+            steps,
+        })
+    }
+}
+impl<T: Clone> SortRun<T> {
+    /// Plays all of the recorded steps on the input and returns the result, which is sorted if the run is intact.
+    pub fn replay(&self) -> Vec<T> {
+        let mut playback = SortPlayback::new(self.input.clone(), self.steps.clone());
+        playback.seek(playback.len());
+        playback.items().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_round_trip_keeps_the_run() {
+        let run = SortRun::record("bubble-sort", vec![5, -2, 9, 0, 3]).unwrap();
+        // Bubble sort marks its passes, so the labels of the phases go through JSON as well
+        assert!(run
+            .steps
+            .iter()
+            .flatten()
+            .any(|command| matches!(command, SortCommand::PhaseBoundary(_))));
+
+        let json = serde_json::to_string(&run).unwrap();
+        let deserialized: SortRun<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, run);
+    }
+
+    #[test]
+    fn deserialized_run_replays_to_sorted_output() {
+        let input = vec![8, 3, 5, 1, 9, 2, 7];
+        let mut sorted = input.clone();
+        sorted.sort_unstable();
+
+        for algorithm in ["merge-sort", "heapsort", "quicksort"] {
+            let json =
+                serde_json::to_string(&SortRun::record(algorithm, input.clone()).unwrap()).unwrap();
+            let run: SortRun<u32> = serde_json::from_str(&json).unwrap();
+            assert_eq!(run.input, input);
+            assert_eq!(run.replay(), sorted, "{}", algorithm);
+        }
+        assert!(SortRun::record("bogosort", input).is_err());
+    }
+}
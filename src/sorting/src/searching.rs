@@ -0,0 +1,296 @@
+//! Algorithms that search a sorted slice for a value, recording their probes so that they can be visualized.
+use std::{cmp::Ordering, fmt};
+
+/// A step of a search in a sorted slice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchStep {
+    /// The value can only be between two indices, both included: `(low, high)`
+    Range(usize, usize),
+    /// Compare the item at an index with the value, which tells if the item is less than, equal to or greater than it: `(index, ordering)`
+    Probe(usize, Ordering),
+}
+
+impl fmt::Display for SearchStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchStep::Range(low, high) => write!(f, "Searching indices {}..{}", low, high),
+            SearchStep::Probe(index, ordering) => write!(
+                f,
+                "The item at index {} is {} the value",
+                index,
+                match ordering {
+                    Ordering::Less => "less than",
+                    Ordering::Equal => "equal to",
+                    Ordering::Greater => "greater than",
+                }
+            ),
+        }
+    }
+}
+
+/// What a search has found out after some of its steps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchState {
+    /// The indices that the value can still be between, both included. This is `None` only for an empty slice.
+    pub range: Option<(usize, usize)>,
+    /// The latest probe.
+    pub probe: Option<(usize, Ordering)>,
+    /// How many items have been compared with the value.
+    pub comparisons: usize,
+    /// The index of the value, once it has been found.
+    pub found: Option<usize>,
+}
+
+impl SearchState {
+    /// Plays `steps` from the start of a search in `len` items.
+    pub fn after(len: usize, steps: &[SearchStep]) -> Self {
+        let mut state = Self {
+            range: len.checked_sub(1).map(|high| (0, high)),
+            ..Self::default()
+        };
+        for step in steps {
+            match step {
+                SearchStep::Range(low, high) => state.range = Some((*low, *high)),
+                SearchStep::Probe(index, ordering) => {
+                    state.probe = Some((*index, *ordering));
+                    state.comparisons += 1;
+                    if *ordering == Ordering::Equal {
+                        state.found = Some(*index);
+                        state.range = Some((*index, *index));
+                    }
+                }
+            }
+        }
+        state
+    }
+}
+
+/// A search that finds the index of a value in a sorted slice, recording its steps.\
+/// Any index of the value is returned if it's in the slice more than once.
+pub type SearchFn<T> = fn(&[T], T, &mut Vec<SearchStep>) -> Option<usize>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchAlgorithm {
+    Linear,
+    Binary,
+    Jump,
+    Interpolation,
+}
+
+impl SearchAlgorithm {
+    pub const ALL: [SearchAlgorithm; 4] = [
+        SearchAlgorithm::Linear,
+        SearchAlgorithm::Binary,
+        SearchAlgorithm::Jump,
+        SearchAlgorithm::Interpolation,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchAlgorithm::Linear => "Linear search",
+            SearchAlgorithm::Binary => "Binary search",
+            SearchAlgorithm::Jump => "Jump search",
+            SearchAlgorithm::Interpolation => "Interpolation search",
+        }
+    }
+    pub fn search_fn<T: Copy + Ord + Into<f64>>(&self) -> SearchFn<T> {
+        match self {
+            SearchAlgorithm::Linear => linear_search,
+            SearchAlgorithm::Binary => binary_search,
+            SearchAlgorithm::Jump => jump_search,
+            SearchAlgorithm::Interpolation => interpolation_search,
+        }
+    }
+}
+
+/// Compares every item from the start until it finds the value or passes where it would be. Takes *O(n)* time.
+pub fn linear_search<T: Copy + Ord>(
+    items: &[T],
+    value: T,
+    steps: &mut Vec<SearchStep>,
+) -> Option<usize> {
+    for (i, item) in items.iter().enumerate() {
+        let ordering = item.cmp(&value);
+        steps.push(SearchStep::Probe(i, ordering));
+        match ordering {
+            Ordering::Less if i + 1 < items.len() => {
+                steps.push(SearchStep::Range(i + 1, items.len() - 1))
+            }
+            Ordering::Less => (),
+            Ordering::Equal => return Some(i),
+            Ordering::Greater => return None,
+        }
+    }
+    None
+}
+
+/// Compares the middle item of the range and continues in the half that can have the value. Takes *O(log n)* time.
+pub fn binary_search<T: Copy + Ord>(
+    items: &[T],
+    value: T,
+    steps: &mut Vec<SearchStep>,
+) -> Option<usize> {
+    // The range is half-open here, so that it can be empty
+    let mut low = 0;
+    let mut high = items.len();
+    while low < high {
+        let middle = low + (high - low) / 2;
+        let ordering = items[middle].cmp(&value);
+        steps.push(SearchStep::Probe(middle, ordering));
+        match ordering {
+            Ordering::Less => low = middle + 1,
+            Ordering::Equal => return Some(middle),
+            Ordering::Greater => high = middle,
+        }
+        if low < high {
+            steps.push(SearchStep::Range(low, high - 1));
+        }
+    }
+    None
+}
+
+/// Compares the last item of blocks of *√n* items until it finds the block that can have the value, and searches it linearly.
+/// Takes *O(√n)* time.
+pub fn jump_search<T: Copy + Ord>(
+    items: &[T],
+    value: T,
+    steps: &mut Vec<SearchStep>,
+) -> Option<usize> {
+    if items.is_empty() {
+        return None;
+    }
+    let block_len = ((items.len() as f64).sqrt() as usize).max(1);
+
+    let mut low = 0;
+    let mut end = block_len.min(items.len()) - 1;
+    loop {
+        let ordering = items[end].cmp(&value);
+        steps.push(SearchStep::Probe(end, ordering));
+        match ordering {
+            Ordering::Less if end + 1 < items.len() => {
+                low = end + 1;
+                end = (end + block_len).min(items.len() - 1);
+                steps.push(SearchStep::Range(low, items.len() - 1));
+            }
+            Ordering::Less => return None,
+            Ordering::Equal => return Some(end),
+            Ordering::Greater => break,
+        }
+    }
+
+    // The value is before the end of the block, if it's in the block at all
+    if low == end {
+        return None;
+    }
+    steps.push(SearchStep::Range(low, end - 1));
+    let block = &items[low..end];
+    let mut block_steps = vec![];
+    let found = linear_search(block, value, &mut block_steps);
+    steps.extend(block_steps.into_iter().map(|step| match step {
+        SearchStep::Range(block_low, _) => SearchStep::Range(low + block_low, end - 1),
+        SearchStep::Probe(index, ordering) => SearchStep::Probe(low + index, ordering),
+    }));
+    found.map(|index| low + index)
+}
+
+/// Guesses where the value is from how far it is between the first and last item of the range, like looking up a word in a dictionary.
+/// Takes *O(log log n)* time for evenly spread values, but *O(n)* time in the worst case.
+pub fn interpolation_search<T: Copy + Ord + Into<f64>>(
+    items: &[T],
+    value: T,
+    steps: &mut Vec<SearchStep>,
+) -> Option<usize> {
+    if items.is_empty() {
+        return None;
+    }
+
+    let mut low = 0;
+    let mut high = items.len() - 1;
+    // The value can't be in the range if it's outside of the first and last item
+    while value >= items[low] && value <= items[high] {
+        let guess = if items[low] == items[high] {
+            low
+        } else {
+            let (low_value, high_value): (f64, f64) = (items[low].into(), items[high].into());
+            let fraction = (value.into() - low_value) / (high_value - low_value);
+            low + (fraction * (high - low) as f64) as usize
+        };
+        let ordering = items[guess].cmp(&value);
+        steps.push(SearchStep::Probe(guess, ordering));
+        match ordering {
+            Ordering::Less => low = guess + 1,
+            Ordering::Equal => return Some(guess),
+            Ordering::Greater => high = guess - 1,
+        }
+        if low > high {
+            break;
+        }
+        steps.push(SearchStep::Range(low, high));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn algorithms_find_the_values_that_are_in_the_items() {
+        let inputs: Vec<Vec<u32>> = vec![
+            vec![],
+            vec![4],
+            (0..50).map(|i| i * 3).collect(),
+            (0..40).map(|i| i * i).collect(),
+            vec![1, 1, 2, 2, 2, 5, 8, 8, 13, 13, 13, 13],
+        ];
+
+        for items in inputs {
+            for value in 0..=60 {
+                let expected = items.contains(&value);
+                for algorithm in SearchAlgorithm::ALL {
+                    let mut steps = vec![];
+                    let found = algorithm.search_fn()(&items, value, &mut steps);
+                    match found {
+                        Some(index) => assert_eq!(items[index], value, "{}", algorithm.name()),
+                        None => assert!(!expected, "{} missed {}", algorithm.name(), value),
+                    }
+
+                    // The range only ever shrinks and the probes stay in it
+                    let mut state = SearchState::after(items.len(), &[]);
+                    for step in &steps {
+                        let previous = state.range.unwrap();
+                        state = SearchState::after(
+                            items.len(),
+                            &[SearchStep::Range(previous.0, previous.1), *step],
+                        );
+                        let (low, high) = state.range.unwrap();
+                        assert!(previous.0 <= low && high <= previous.1);
+                        if let SearchStep::Probe(index, _) = step {
+                            assert!((previous.0..=previous.1).contains(index));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn state_counts_comparisons() {
+        let items: Vec<u32> = (0..100).collect();
+        let mut steps = vec![];
+        binary_search(&items, 80, &mut steps);
+
+        let state = SearchState::after(items.len(), &steps);
+        assert_eq!(state.found, Some(80));
+        assert_eq!(state.range, Some((80, 80)));
+        // Binary search takes at most ⌊log2 n⌋ + 1 comparisons
+        assert!(state.comparisons <= 7);
+        assert_eq!(
+            state.comparisons,
+            steps
+                .iter()
+                .filter(|step| matches!(step, SearchStep::Probe(..)))
+                .count()
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Utilities for selecting items by their position in sorted order without sorting the whole collection.
+
+/// Finds the median of a slice with the median of medians algorithm, which takes `O(n)` time even in the worst case.
+///
+/// For slices with an even length, the lower of the two middle items is returned, i.e. the item at index `(len - 1) / 2` in sorted order.
+/// The slice is reordered in the process.
+///
+/// # Panics
+///
+/// Panics if the slice is empty.
+pub fn median<T: Clone + Copy + Ord>(items: &mut [T]) -> T {
+    assert!(
+        !items.is_empty(),
+        "cannot find the median of an empty slice"
+    );
+    let middle = (items.len() - 1) / 2;
+    select(items, middle)
+}
+
+/// Finds the items at the quantiles `qs` of a slice, e.g. `[0.25, 0.5, 0.75]` for the quartiles, in the same order as `qs`.
+///
+/// The quantile `q` is the item at index `floor(q * (len - 1))` in sorted order, so the quantile `0.5` is the [`median`].
+/// Quantiles outside of `[0, 1]` are clamped to it. Each item is found with a selection instead of sorting the slice,
+/// and the slice is reordered in the process.
+///
+/// # Panics
+///
+/// Panics if the slice is empty.
+pub fn quantiles<T: Clone + Copy + Ord>(items: &mut [T], qs: &[f64]) -> Vec<T> {
+    assert!(
+        !items.is_empty(),
+        "cannot find the quantiles of an empty slice"
+    );
+    let max_index = items.len() - 1;
+    let indices: Vec<usize> = qs
+        .iter()
+        .map(|q| (q.clamp(0.0, 1.0) * max_index as f64) as usize)
+        .collect();
+
+    let mut order: Vec<usize> = (0..qs.len()).collect();
+    order.sort_by_key(|&i| indices[i]);
+
+    // Selecting an item leaves the bigger items after it, so the next bigger index can be found from there on
+    let mut found = vec![items[0]; qs.len()];
+    let mut start = 0;
+    for i in order {
+        found[i] = select(&mut items[start..], indices[i] - start);
+        start = indices[i];
+    }
+
+    found
+}
+
+/// Finds the item that would be at index `k` if the slice was sorted.
+fn select<T: Clone + Copy + Ord>(mut items: &mut [T], mut k: usize) -> T {
+    loop {
+        if items.len() <= 5 {
+            items.sort_unstable();
+            return items[k];
+        }
+
+        let pivot = median_of_medians(items);
+        let (less, equal) = partition(items, pivot);
+
+        if k < less {
+            items = &mut items[..less];
+        } else if k < less + equal {
+            return pivot;
+        } else {
+            items = &mut items[less + equal..];
+            k -= less + equal;
+        }
+    }
+}
+
+/// Finds the median of the medians of groups of 5 items.
+/// At least 30% of the items are smaller than it and at least 30% are bigger, which guarantees the linear time of [`select`].
+fn median_of_medians<T: Clone + Copy + Ord>(items: &mut [T]) -> T {
+    let group_count = items.len().div_ceil(5);
+
+    // Move the median of every group to the front
+    for group in 0..group_count {
+        let start = group * 5;
+        let end = (start + 5).min(items.len());
+        items[start..end].sort_unstable();
+        items.swap(group, start + (end - start - 1) / 2);
+    }
+
+    select(&mut items[..group_count], (group_count - 1) / 2)
+}
+
+/// Reorders the items so that the items less than the `pivot` come first, then the items equal to it, and then the rest.
+/// Returns the number of items less than the pivot and the number of items equal to it.
+fn partition<T: Clone + Copy + Ord>(items: &mut [T], pivot: T) -> (usize, usize) {
+    let mut less = 0;
+    let mut i = 0;
+    let mut greater = items.len();
+
+    while i < greater {
+        if items[i] < pivot {
+            items.swap(i, less);
+            less += 1;
+            i += 1;
+        } else if items[i] > pivot {
+            greater -= 1;
+            items.swap(i, greater);
+        } else {
+            i += 1;
+        }
+    }
+
+    (less, greater - less)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_of_odd_and_even_lengths() {
+        assert_eq!(median(&mut [7]), 7);
+        assert_eq!(median(&mut [9, 1, 5, 3, 7]), 5);
+        // The lower middle item is returned for even lengths
+        assert_eq!(median(&mut [4, 1, 3, 2]), 2);
+        assert_eq!(median(&mut [8, 2]), 2);
+        assert_eq!(median(&mut [6, 6, 1, 6, 1, 1]), 1);
+    }
+
+    #[test]
+    fn median_quantile_matches_median() {
+        for len in [1, 2, 9, 10, 1_000, 1_001] {
+            let input: Vec<u32> = (0..len).map(|i| (i * 7919 + 13) % 101).collect();
+            let mut sorted = input.clone();
+            sorted.sort_unstable();
+
+            assert_eq!(
+                quantiles(&mut input.clone(), &[0.5]),
+                vec![median(&mut input.clone())]
+            );
+
+            // Unordered and out of range quantiles
+            let max_index = (len - 1) as f64;
+            assert_eq!(
+                quantiles(&mut input.clone(), &[0.75, 2.0, 0.25, -1.0, 0.5]),
+                vec![
+                    sorted[(0.75 * max_index) as usize],
+                    sorted[len as usize - 1],
+                    sorted[(0.25 * max_index) as usize],
+                    sorted[0],
+                    sorted[(len as usize - 1) / 2],
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn median_of_large_adversarial_inputs() {
+        let len = 10_001;
+        let inputs: Vec<Vec<u32>> = vec![
+            (0..len).collect(),
+            (0..len).rev().collect(),
+            vec![42; len as usize],
+            // Organ pipe, i.e. increasing and then decreasing
+            (0..len).map(|i| i.min(len - i)).collect(),
+            // Many duplicates in a sawtooth pattern
+            (0..len).map(|i| i % 7).collect(),
+            (0..len).map(|i| (i * 7919 + 13) % len).collect(),
+        ];
+
+        for input in inputs {
+            for len in [input.len(), input.len() - 1] {
+                let mut sorted = input[..len].to_vec();
+                sorted.sort_unstable();
+
+                let mut items = input[..len].to_vec();
+                assert_eq!(median(&mut items), sorted[(len - 1) / 2]);
+            }
+        }
+    }
+}
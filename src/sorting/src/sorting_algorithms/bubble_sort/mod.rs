@@ -1,15 +1,37 @@
-use crate::SortCommand;
+use crate::{push_step, SortCommand};
 
 pub fn bubble_sort<T: Clone + Copy + Ord>(
     items: &mut Vec<T>,
     steps: &mut Vec<Vec<SortCommand<T>>>,
+) {
+    _bubble_sort(items, steps, false);
+}
+
+/// Bubble sort that also emits a [`SortCommand::PhaseBoundary`] at the start of every pass.
+pub fn bubble_sort_with_phases<T: Clone + Copy + Ord>(
+    items: &mut Vec<T>,
+    steps: &mut Vec<Vec<SortCommand<T>>>,
+) {
+    _bubble_sort(items, steps, true);
+}
+
+fn _bubble_sort<T: Clone + Copy + Ord>(
+    items: &mut Vec<T>,
+    steps: &mut Vec<Vec<SortCommand<T>>>,
+    emit_phases: bool,
 ) {
     for i in 0..items.len() {
+        if emit_phases && i + 1 < items.len() {
+            push_step(
+                steps,
+                vec![SortCommand::PhaseBoundary(format!("Pass {}", i + 1))],
+            );
+        }
         let mut swapped = false;
         for j in 0..items.len() - i - 1 {
             if items[j] > items[j + 1] {
                 items.swap(j, j + 1);
-                steps.push(vec![SortCommand::Swap(j, j + 1)]);
+                push_step(steps, vec![SortCommand::Swap(j, j + 1)]);
                 swapped = true;
             }
         }
@@ -18,3 +40,40 @@ pub fn bubble_sort<T: Clone + Copy + Ord>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phase_count(steps: &[Vec<SortCommand<u32>>]) -> usize {
+        steps
+            .iter()
+            .flatten()
+            .filter(|command| matches!(command, SortCommand::PhaseBoundary(_)))
+            .count()
+    }
+
+    #[test]
+    fn one_phase_boundary_per_pass() {
+        let cases = [
+            (vec![6, 5, 4, 3, 2, 1], 5),
+            (vec![1, 2, 3, 4, 5, 6], 1),
+            // The second pass finds nothing to swap, so the sort stops
+            (vec![2, 1, 3, 4], 2),
+            (vec![1], 0),
+        ];
+
+        for (input, expected) in cases {
+            let mut items = input.clone();
+            let mut steps = vec![];
+            bubble_sort_with_phases(&mut items, &mut steps);
+            assert_eq!(phase_count(&steps), expected, "{:?}", input);
+
+            // The phases don't change the other steps
+            let mut plain_steps = vec![];
+            bubble_sort(&mut input.clone(), &mut plain_steps);
+            steps.retain(|step| !matches!(step[..], [SortCommand::PhaseBoundary(_)]));
+            assert_eq!(steps, plain_steps);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{insertion_sort, SortCommand};
+use crate::{insertion_sort, push_step, SortCommand};
 
 pub fn bucket_sort(items: &mut Vec<u32>, steps: &mut Vec<Vec<SortCommand<u32>>>) {
     let size = items.len();
@@ -10,10 +10,14 @@ pub fn bucket_sort(items: &mut Vec<u32>, steps: &mut Vec<Vec<SortCommand<u32>>>)
     for i in 0..size {
         let item = items[i];
         let bucket_i = ((item as f32 - 1.0) / (max_val as f32 / k as f32)).floor() as usize;
-        steps.push(vec![SortCommand::Set(
-            (bucket_i as f32 * size as f32 / k as f32).ceil() as usize + buckets[bucket_i].len(),
-            item,
-        )]);
+        push_step(
+            steps,
+            vec![SortCommand::Set(
+                (bucket_i as f32 * size as f32 / k as f32).ceil() as usize
+                    + buckets[bucket_i].len(),
+                item,
+            )],
+        );
         buckets[bucket_i].push(item);
     }
 
@@ -51,6 +55,10 @@ fn add_offset_to_step_indices<T: Clone + Copy + Ord>(
                 .map(|command| match command {
                     SortCommand::Swap(from, to) => SortCommand::Swap(from + offset, to + offset),
                     SortCommand::Set(i, val) => SortCommand::Set(i + offset, *val),
+                    SortCommand::PhaseBoundary(label) => SortCommand::PhaseBoundary(label.clone()),
+                    SortCommand::Event(event) => {
+                        SortCommand::Event(event.clone().add_offset(offset))
+                    }
                 })
                 .collect::<Vec<SortCommand<T>>>()
         })
@@ -0,0 +1,67 @@
+use crate::{push_step, SortCommand};
+
+/// How much the gap shrinks after every pass of [`comb_sort`].
+const SHRINK_FACTOR: f64 = 1.3;
+
+/// Comb sort, which emits a [`SortCommand::PhaseBoundary`] at the start of every pass.
+///
+/// Every pass compares the items that are `gap` apart like bubble sort compares adjacent items, and the gap shrinks by a factor of 1.3
+/// after each pass. Small items at the end of the input get moved forwards early, so they don't have to crawl forward one index at a time.
+/// Once the gap is 1, the passes go on until one of them doesn't swap anything.
+pub fn comb_sort<T: Clone + Copy + Ord>(items: &mut [T], steps: &mut Vec<Vec<SortCommand<T>>>) {
+    if items.len() < 2 {
+        return;
+    }
+
+    let mut gap = items.len();
+    let mut sorted = false;
+
+    while !sorted {
+        gap = ((gap as f64 / SHRINK_FACTOR) as usize).max(1);
+        // A pass with a gap of 1 that swaps nothing is the last one
+        sorted = gap == 1;
+        push_step(
+            steps,
+            vec![SortCommand::PhaseBoundary(format!("Gap {}", gap))],
+        );
+
+        for i in 0..items.len() - gap {
+            if items[i] > items[i + gap] {
+                items.swap(i, i + gap);
+                push_step(steps, vec![SortCommand::Swap(i, i + gap)]);
+                sorted = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_sort_steps;
+
+    #[test]
+    fn sorts_and_ends_with_a_clean_pass() {
+        for input in [
+            vec![2, 1],
+            (1..=50).rev().collect(),
+            vec![5, 1, 4, 1, 5, 9, 2, 6, 5, 3],
+        ] {
+            let mut items: Vec<u32> = input.clone();
+            let mut steps = vec![];
+            comb_sort(&mut items, &mut steps);
+
+            let mut sorted = input.clone();
+            sorted.sort_unstable();
+            assert_eq!(items, sorted);
+
+            let mut replayed = input;
+            run_sort_steps(&mut replayed, &steps);
+            assert_eq!(replayed, sorted);
+            assert_eq!(
+                steps.last(),
+                Some(&vec![SortCommand::PhaseBoundary("Gap 1".to_string())])
+            );
+        }
+    }
+}
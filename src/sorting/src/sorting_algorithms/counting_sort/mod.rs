@@ -0,0 +1,50 @@
+use crate::{push_step, SortCommand};
+
+/// Counting sort, which counts how many times each value appears and then writes the values back in order with [`SortCommand::Set`].
+///
+/// The counts are kept for every value between the smallest and the largest item, so it's only fast when that range is small.
+pub fn counting_sort(items: &mut [u32], steps: &mut Vec<Vec<SortCommand<u32>>>) {
+    let (min, max) = match (items.iter().min(), items.iter().max()) {
+        (Some(min), Some(max)) => (*min, *max),
+        _ => return,
+    };
+
+    let mut counts = vec![0_usize; (max - min) as usize + 1];
+    for item in items.iter() {
+        counts[(item - min) as usize] += 1;
+    }
+
+    let mut i = 0;
+    for (offset, count) in counts.into_iter().enumerate() {
+        let value = min + offset as u32;
+        for _ in 0..count {
+            items[i] = value;
+            push_step(steps, vec![SortCommand::Set(i, value)]);
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_sort_steps;
+
+    #[test]
+    fn writes_every_item_once() {
+        let input = vec![105, 101, 104, 101, 100, 105, 102];
+        let mut items = input.clone();
+        let mut steps = vec![];
+        counting_sort(&mut items, &mut steps);
+
+        assert_eq!(items, [100, 101, 101, 102, 104, 105, 105]);
+        assert_eq!(steps.len(), input.len());
+        let mut replayed = input;
+        run_sort_steps(&mut replayed, &steps);
+        assert_eq!(replayed, items);
+
+        let mut empty = vec![];
+        counting_sort(&mut empty, &mut steps);
+        assert_eq!(steps.len(), 7);
+    }
+}
@@ -1,24 +1,100 @@
-use crate::SortCommand;
+use crate::{push_step, SortCommand, SortEvent};
 
 pub fn heapsort<T: Clone + Copy + Ord>(items: &mut Vec<T>, steps: &mut Vec<Vec<SortCommand<T>>>) {
+    _heapsort(items, steps, false);
+}
+
+/// Which part of heapsort the steps are in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeapsortPhase {
+    /// The items are turned into a max-heap by sifting down every item that has children, starting from the last one.
+    BuildHeap,
+    /// The root of the heap is moved to the end of the heap one item at a time, which sorts the items from the end.
+    Extract,
+}
+impl HeapsortPhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HeapsortPhase::BuildHeap => "Build heap",
+            HeapsortPhase::Extract => "Extract",
+        }
+    }
+}
+
+/// Heapsort that also emits a [`SortEvent::Heapify`] before sifting an item down the heap
+/// and a [`SortEvent::Extract`] before moving the root to the end of the heap.
+pub fn heapsort_with_events<T: Clone + Copy + Ord>(
+    items: &mut Vec<T>,
+    steps: &mut Vec<Vec<SortCommand<T>>>,
+) {
+    _heapsort(items, steps, true);
+}
+
+fn _heapsort<T: Clone + Copy + Ord>(
+    items: &mut Vec<T>,
+    steps: &mut Vec<Vec<SortCommand<T>>>,
+    emit_events: bool,
+) {
     let size = items.len();
 
     // Build heap
     for i in (0..=size / 2 - 1).rev() {
+        if emit_events {
+            push_step(steps, vec![SortCommand::Event(SortEvent::Heapify(i, size))]);
+        }
         heapify(items, size, i, steps);
     }
 
     // Extract elements from heap
     for i in (0..=size - 1).rev() {
         // Move root to end
+        if emit_events {
+            push_step(steps, vec![SortCommand::Event(SortEvent::Extract(i + 1))]);
+        }
         items.swap(0, i);
-        steps.push(vec![SortCommand::Swap(0, i)]);
+        push_step(steps, vec![SortCommand::Swap(0, i)]);
 
-        // Max heapify the reduced heap
+        // Max heapify the reduced heap, which has nothing to sift with fewer than 2 items
+        if emit_events && i > 1 {
+            push_step(steps, vec![SortCommand::Event(SortEvent::Heapify(0, i))]);
+        }
         heapify(items, i, 0, steps);
     }
 }
 
+/// Finds the phase of heapsort after every number of played steps of [`heapsort_with_events`], from none to all of them,
+/// and how many of the first items are still in the heap. The items after the heap are sorted.\
+/// The phase is `None` before the first event.
+pub fn heapsort_phases<T>(steps: &[Vec<SortCommand<T>>]) -> Vec<Option<(HeapsortPhase, usize)>> {
+    let mut phases = Vec::with_capacity(steps.len() + 1);
+    let mut phase = None;
+    // Whether the next swap moves the root to the end of the heap
+    let mut extracting = false;
+    phases.push(phase);
+    for step in steps {
+        for command in step {
+            match command {
+                SortCommand::Event(SortEvent::Heapify(_, heap_len)) => {
+                    let current = phase.map_or(HeapsortPhase::BuildHeap, |(current, _)| current);
+                    phase = Some((current, *heap_len));
+                }
+                SortCommand::Event(SortEvent::Extract(heap_len)) => {
+                    phase = Some((HeapsortPhase::Extract, *heap_len));
+                    extracting = true;
+                }
+                // The root leaves the heap as soon as it's swapped to the end
+                SortCommand::Swap(..) if extracting => {
+                    phase = phase.map(|(current, heap_len)| (current, heap_len - 1));
+                    extracting = false;
+                }
+                _ => (),
+            }
+        }
+        phases.push(phase);
+    }
+    phases
+}
+
 fn heapify<T: Clone + Copy + Ord>(
     mut items: &mut Vec<T>,
     size: usize,
@@ -42,9 +118,46 @@ fn heapify<T: Clone + Copy + Ord>(
     // If new largest found, swap previous largest with new largest
     if largest != i {
         items.swap(i, largest);
-        steps.push(vec![SortCommand::Swap(i, largest)]);
+        push_step(steps, vec![SortCommand::Swap(i, largest)]);
 
         // Recursively heapify the sub-trees
         heapify(&mut items, size, largest, &mut steps);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_sort_steps;
+
+    #[test]
+    fn phases_follow_the_heap() {
+        let input: Vec<u32> = (0..30).map(|i| (i * 37 + 11) % 23).collect();
+        let mut steps = vec![];
+        heapsort_with_events(&mut input.clone(), &mut steps);
+        let phases = heapsort_phases(&steps);
+        assert_eq!(phases.len(), steps.len() + 1);
+
+        assert_eq!(phases[0], None);
+        assert_eq!(phases[1], Some((HeapsortPhase::BuildHeap, input.len())));
+
+        let mut items = input.clone();
+        let mut previous_len = input.len();
+        for played in 1..=steps.len() {
+            run_sort_steps(&mut items, &steps[played - 1..played]);
+            let (phase, heap_len) = phases[played].unwrap();
+            assert!(heap_len <= previous_len);
+            previous_len = heap_len;
+
+            if phase == HeapsortPhase::Extract {
+                // The items after the heap are sorted and none of them is smaller than an item in the heap
+                let (heap, sorted) = items.split_at(heap_len);
+                assert!(sorted.windows(2).all(|pair| pair[0] <= pair[1]));
+                assert!(sorted
+                    .first()
+                    .is_none_or(|first| heap.iter().all(|item| item <= first)));
+            }
+        }
+        assert_eq!(phases[steps.len()], Some((HeapsortPhase::Extract, 0)));
+    }
+}
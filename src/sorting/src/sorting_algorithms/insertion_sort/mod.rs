@@ -1,4 +1,4 @@
-use crate::SortCommand;
+use crate::{push_step, SortCommand};
 
 pub fn insertion_sort<T: Clone + Copy + Ord>(
     items: &mut Vec<T>,
@@ -8,7 +8,7 @@ pub fn insertion_sort<T: Clone + Copy + Ord>(
         let mut j = i;
         while j > 0 && items[(j - 1)] > items[j] {
             items.swap(j - 1, j);
-            steps.push(vec![SortCommand::Swap(j - 1, j)]);
+            push_step(steps, vec![SortCommand::Swap(j - 1, j)]);
             j -= 1;
         }
     }
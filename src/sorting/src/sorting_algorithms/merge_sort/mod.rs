@@ -1,21 +1,73 @@
-use crate::SortCommand;
+use crate::{push_step, SortCommand, SortEvent};
+
+/// How many [`SortCommand`]s merge sort groups into one step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeGranularity {
+    /// Every item written to its merged position is its own step.
+    #[default]
+    PerMergedElement,
+    /// Every comparison between the two halves is its own step.
+    /// Items that are copied over after one half runs out are written in the step of the last comparison,
+    /// so this results in fewer steps, which keeps the animation watchable on big inputs.
+    PerComparison,
+}
 
 pub fn merge_sort<T: Copy + Clone + Ord>(items: &mut Vec<T>, steps: &mut Vec<Vec<SortCommand<T>>>) {
-    _merge_sort(items, steps, 0);
+    merge_sort_with_granularity(items, steps, MergeGranularity::default());
+}
+
+/// Merge sort that also emits a [`SortEvent::Merge`] at the start of every merge.
+pub fn merge_sort_with_events<T: Copy + Clone + Ord>(
+    items: &mut Vec<T>,
+    steps: &mut Vec<Vec<SortCommand<T>>>,
+) {
+    _merge_sort(items, steps, 0, MergeGranularity::default(), true);
+}
+
+/// Merge sort that groups the steps according to the given [`MergeGranularity`]. The sorted result is the same for every granularity.
+pub fn merge_sort_with_granularity<T: Copy + Clone + Ord>(
+    items: &mut Vec<T>,
+    steps: &mut Vec<Vec<SortCommand<T>>>,
+    granularity: MergeGranularity,
+) {
+    _merge_sort(items, steps, 0, granularity, false);
 }
 
 fn _merge_sort<T: Copy + Clone + PartialOrd>(
     items: &mut Vec<T>,
     mut steps: &mut Vec<Vec<SortCommand<T>>>,
     start_i: usize,
+    granularity: MergeGranularity,
+    emit_events: bool,
 ) {
     if items.len() > 1 {
         let middle = items.len() / 2;
         let mut left_half = items[0..middle].to_vec();
         let mut right_half = items[middle..].to_vec();
-        _merge_sort(&mut left_half, &mut steps, start_i);
-        _merge_sort(&mut right_half, &mut steps, start_i + middle);
-        *items = merge(left_half, right_half, &mut steps, start_i);
+        _merge_sort(
+            &mut left_half,
+            &mut steps,
+            start_i,
+            granularity,
+            emit_events,
+        );
+        _merge_sort(
+            &mut right_half,
+            &mut steps,
+            start_i + middle,
+            granularity,
+            emit_events,
+        );
+        if emit_events {
+            push_step(
+                steps,
+                vec![SortCommand::Event(SortEvent::Merge(
+                    (start_i, start_i + middle - 1),
+                    (start_i + middle, start_i + items.len() - 1),
+                ))],
+            );
+        }
+        *items = merge(left_half, right_half, &mut steps, start_i, granularity);
     }
 }
 
@@ -24,6 +76,7 @@ fn merge<T: Copy + Clone + PartialOrd>(
     b: Vec<T>,
     steps: &mut Vec<Vec<SortCommand<T>>>,
     start_i: usize,
+    granularity: MergeGranularity,
 ) -> Vec<T> {
     let size = a.len() + b.len();
     let mut merged: Vec<T> = Vec::with_capacity(size);
@@ -31,30 +84,94 @@ fn merge<T: Copy + Clone + PartialOrd>(
     let mut i = 0; // Idx for a
     let mut j = 0; // Idx for b
 
-    // Loop through a and b, adding the smallest values between them to `merged`
+    // Loop through a and b, adding the smallest values between them to `merged`.
+    // Ties are taken from a, so that equal values keep their order and the sort is stable.
     while i < a.len() && j < b.len() {
-        if a[i] < b[j] {
+        if a[i] <= b[j] {
             merged.push(a[i]);
-            steps.push(vec![SortCommand::Set(start_i + merged.len() - 1, a[i])]);
+            push_step(
+                steps,
+                vec![SortCommand::Set(start_i + merged.len() - 1, a[i])],
+            );
             i += 1;
         } else {
             merged.push(b[j]);
-            steps.push(vec![SortCommand::Set(start_i + merged.len() - 1, b[j])]);
+            push_step(
+                steps,
+                vec![SortCommand::Set(start_i + merged.len() - 1, b[j])],
+            );
             j += 1;
         }
     }
 
     // Add all remaining values
+    let mut remaining = vec![];
     while i < a.len() {
         merged.push(a[i]);
-        steps.push(vec![SortCommand::Set(start_i + merged.len() - 1, a[i])]);
+        remaining.push(SortCommand::Set(start_i + merged.len() - 1, a[i]));
         i += 1;
     }
     while j < b.len() {
         merged.push(b[j]);
-        steps.push(vec![SortCommand::Set(start_i + merged.len() - 1, b[j])]);
+        remaining.push(SortCommand::Set(start_i + merged.len() - 1, b[j]));
         j += 1;
     }
+    match granularity {
+        MergeGranularity::PerMergedElement => {
+            for command in remaining {
+                push_step(steps, vec![command]);
+            }
+        }
+        MergeGranularity::PerComparison => {
+            if let Some(last_step) = steps.last_mut() {
+                last_step.extend(remaining);
+            }
+        }
+    }
 
     merged
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_sort_steps;
+
+    #[test]
+    fn granularities_sort_the_same() {
+        let inputs: Vec<Vec<u32>> = vec![
+            vec![1],
+            vec![5, 3, 8, 1, 9, 2, 7],
+            vec![4, 4, 2, 4, 1, 2],
+            (1..=20).collect(),
+            (1..=20).rev().collect(),
+            (0..50).map(|i| (i * 37 + 11) % 23).collect(),
+        ];
+
+        for input in inputs {
+            let mut sorted = input.clone();
+            sorted.sort_unstable();
+
+            let mut step_counts = vec![];
+            for granularity in [
+                MergeGranularity::PerMergedElement,
+                MergeGranularity::PerComparison,
+            ] {
+                let mut items = input.clone();
+                let mut steps = vec![];
+                merge_sort_with_granularity(&mut items, &mut steps, granularity);
+                assert_eq!(items, sorted);
+
+                let mut replayed = input.clone();
+                run_sort_steps(&mut replayed, &steps);
+                assert_eq!(replayed, sorted, "{:?}", granularity);
+
+                step_counts.push(steps.len());
+            }
+
+            if input.len() > 1 {
+                assert!(step_counts[1] < step_counts[0], "{:?}", input);
+            }
+        }
+    }
+}
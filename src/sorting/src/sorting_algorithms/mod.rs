@@ -1,13 +1,55 @@
 pub mod bubble_sort;
 pub mod bucket_sort;
+pub mod comb_sort;
+pub mod counting_sort;
 pub mod heapsort;
 pub mod insertion_sort;
 pub mod merge_sort;
+pub mod odd_even_sort;
 pub mod quicksort;
+pub mod radix_sort;
+pub mod shell_sort;
 
-pub use bubble_sort::bubble_sort;
+pub use bubble_sort::{bubble_sort, bubble_sort_with_phases};
 pub use bucket_sort::bucket_sort;
-pub use heapsort::heapsort;
+pub use comb_sort::comb_sort;
+pub use counting_sort::counting_sort;
+pub use heapsort::{heapsort, heapsort_phases, heapsort_with_events, HeapsortPhase};
 pub use insertion_sort::insertion_sort;
-pub use merge_sort::merge_sort;
-pub use quicksort::quicksort;
+pub use merge_sort::{
+    merge_sort, merge_sort_with_events, merge_sort_with_granularity, MergeGranularity,
+};
+pub use odd_even_sort::odd_even_sort;
+pub use quicksort::{quicksort, quicksort_steps, quicksort_with_events, QuicksortSteps};
+pub use radix_sort::radix_sort;
+pub use shell_sort::shell_sort;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lazy_quicksort_steps_equal_eager_steps() {
+        let inputs: Vec<Vec<u32>> = vec![
+            vec![],
+            vec![1],
+            vec![5, 3, 8, 1, 9, 2, 7],
+            vec![4, 4, 2, 4, 1, 2],
+            (1..=20).collect(),
+            (1..=20).rev().collect(),
+            (0..50).map(|i| (i * 37 + 11) % 23).collect(),
+        ];
+
+        for input in inputs {
+            let mut items = input.clone();
+            let mut steps = vec![];
+            quicksort(&mut items, &mut steps);
+
+            let mut lazy = quicksort_steps(&input);
+            let lazy_steps = lazy.by_ref().collect::<Vec<_>>();
+
+            assert_eq!(lazy_steps, steps, "{:?}", input);
+            assert_eq!(lazy.items(), items.as_slice());
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{push_step, SortCommand};
+
+/// Odd-even transposition sort, which emits a [`SortCommand::PhaseBoundary`] at the start of every phase.
+///
+/// The phases alternate between comparing the pairs that start at even indices and the pairs that start at odd indices.
+/// The pairs of a phase don't overlap, so they could all be compared in parallel, and the items are sorted after at most `n` phases.
+pub fn odd_even_sort<T: Clone + Copy + Ord>(items: &mut [T], steps: &mut Vec<Vec<SortCommand<T>>>) {
+    let mut previous_swapped = true;
+
+    for phase in 0..items.len() {
+        if items.len() < 2 {
+            break;
+        }
+        let first = phase % 2;
+        push_step(
+            steps,
+            vec![SortCommand::PhaseBoundary(format!(
+                "{} phase {}",
+                if first == 0 { "Even" } else { "Odd" },
+                phase + 1
+            ))],
+        );
+
+        let mut swapped = false;
+        for i in (first..items.len() - 1).step_by(2) {
+            if items[i] > items[i + 1] {
+                items.swap(i, i + 1);
+                push_step(steps, vec![SortCommand::Swap(i, i + 1)]);
+                swapped = true;
+            }
+        }
+
+        // Both kinds of pairs are in order, so every adjacent pair is
+        if !swapped && !previous_swapped {
+            break;
+        }
+        previous_swapped = swapped;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every permutation of `0..len`.
+    fn permutations(len: u32) -> Vec<Vec<u32>> {
+        if len == 0 {
+            return vec![vec![]];
+        }
+        let mut permutations = vec![];
+        for permutation in self::permutations(len - 1) {
+            for i in 0..len as usize {
+                let mut permutation = permutation.clone();
+                permutation.insert(i, len - 1);
+                permutations.push(permutation);
+            }
+        }
+        permutations
+    }
+
+    #[test]
+    fn sorts_every_permutation_within_n_phases() {
+        let mut inputs = (0..=7).flat_map(permutations).collect::<Vec<Vec<u32>>>();
+        inputs.push((0..100).rev().collect());
+        inputs.push((0..100).map(|i| (i * 37 + 11) % 23).collect());
+
+        for input in inputs {
+            let mut sorted = input.clone();
+            sorted.sort_unstable();
+
+            let mut items = input.clone();
+            let mut steps = vec![];
+            odd_even_sort(&mut items, &mut steps);
+            assert_eq!(items, sorted);
+
+            let phases = steps
+                .iter()
+                .flatten()
+                .filter(|command| matches!(command, SortCommand::PhaseBoundary(_)))
+                .count();
+            assert!(phases <= input.len(), "{} phases for {:?}", phases, input);
+        }
+    }
+}
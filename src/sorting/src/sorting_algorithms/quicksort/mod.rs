@@ -1,8 +1,17 @@
-use crate::SortCommand;
+use crate::{push_step, SortCommand, SortEvent};
 
 pub fn quicksort<T: Clone + Copy + Ord>(items: &mut Vec<T>, steps: &mut Vec<Vec<SortCommand<T>>>) {
     let high = items.len() as isize - 1;
-    _quicksort(items, steps, 0, high);
+    _quicksort(items, steps, 0, high, false);
+}
+
+/// Quicksort that also emits a [`SortEvent::Pivot`] at the start of every partition.
+pub fn quicksort_with_events<T: Clone + Copy + Ord>(
+    items: &mut Vec<T>,
+    steps: &mut Vec<Vec<SortCommand<T>>>,
+) {
+    let high = items.len() as isize - 1;
+    _quicksort(items, steps, 0, high, true);
 }
 
 fn _quicksort<T: Clone + Copy + Ord>(
@@ -10,11 +19,22 @@ fn _quicksort<T: Clone + Copy + Ord>(
     mut steps: &mut Vec<Vec<SortCommand<T>>>,
     low: isize,
     high: isize,
+    emit_events: bool,
 ) {
     if low < high {
+        if emit_events {
+            push_step(
+                steps,
+                vec![SortCommand::Event(SortEvent::Pivot(
+                    items[high as usize],
+                    low as usize,
+                    high as usize,
+                ))],
+            );
+        }
         let pivot_i = partition(&mut items, &mut steps, low, high);
-        _quicksort(&mut items, &mut steps, low, pivot_i - 1);
-        _quicksort(&mut items, &mut steps, pivot_i + 1, high);
+        _quicksort(&mut items, &mut steps, low, pivot_i - 1, emit_events);
+        _quicksort(&mut items, &mut steps, pivot_i + 1, high, emit_events);
     }
 }
 
@@ -39,12 +59,95 @@ fn partition<T: Clone + Copy + Ord>(
         }
         if i < j {
             items.swap(i as usize, j as usize);
-            steps.push(vec![SortCommand::Swap(i as usize, j as usize)]);
+            push_step(steps, vec![SortCommand::Swap(i as usize, j as usize)]);
         } else {
             break;
         }
     }
     items.swap(i as usize, high as usize);
-    steps.push(vec![SortCommand::Swap(i as usize, high as usize)]);
+    push_step(steps, vec![SortCommand::Swap(i as usize, high as usize)]);
     i
 }
+
+/// Lazily sorts a copy of given items with quicksort, yielding the same steps as [`quicksort`] one at a time.
+pub fn quicksort_steps<T: Clone + Copy + Ord>(items: &[T]) -> QuicksortSteps<T> {
+    QuicksortSteps {
+        items: items.to_vec(),
+        ranges: vec![(0, items.len() as isize - 1)],
+        partition: None,
+    }
+}
+
+/// An iterator over the steps of quicksort, created by [`quicksort_steps`].
+pub struct QuicksortSteps<T> {
+    items: Vec<T>,
+    /// Ranges that are yet to be partitioned: `(low, high)`
+    ranges: Vec<(isize, isize)>,
+    /// The partition currently in progress, if any.
+    partition: Option<PartitionState<T>>,
+}
+
+struct PartitionState<T> {
+    low: isize,
+    high: isize,
+    pivot: T,
+    i: isize,
+    j: isize,
+}
+
+impl<T: Clone + Copy + Ord> QuicksortSteps<T> {
+    /// Gets the items in their current, possibly partially sorted, order.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T: Clone + Copy + Ord> Iterator for QuicksortSteps<T> {
+    type Item = Vec<SortCommand<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(state) = self.partition.as_mut() {
+                let items = &mut self.items;
+
+                state.i += 1;
+                while items[state.i as usize] < state.pivot {
+                    state.i += 1;
+                }
+                state.j -= 1;
+                while state.j >= 0 && items[state.j as usize] > state.pivot {
+                    state.j -= 1;
+                }
+
+                let (i, j) = (state.i as usize, state.j as usize);
+
+                if state.i < state.j {
+                    items.swap(i, j);
+                    return Some(vec![SortCommand::Swap(i, j)]);
+                }
+
+                let (low, high, pivot_i) = (state.low, state.high, state.i);
+                items.swap(i, high as usize);
+                self.partition = None;
+
+                // Push the right side first so that the left side is sorted first like in the recursive version
+                self.ranges.push((pivot_i + 1, high));
+                self.ranges.push((low, pivot_i - 1));
+
+                return Some(vec![SortCommand::Swap(i, high as usize)]);
+            }
+
+            let (low, high) = self.ranges.pop()?;
+
+            if low < high {
+                self.partition = Some(PartitionState {
+                    low,
+                    high,
+                    pivot: self.items[high as usize],
+                    i: low - 1,
+                    j: high,
+                });
+            }
+        }
+    }
+}
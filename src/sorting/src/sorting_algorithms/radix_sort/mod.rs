@@ -0,0 +1,60 @@
+use crate::{push_step, SortCommand};
+
+/// Least significant digit radix sort in base 10, which emits a [`SortCommand::PhaseBoundary`] at the start of every digit.
+///
+/// Every pass distributes the items into buckets by one decimal digit, starting from the ones, and writes the buckets back in order
+/// with [`SortCommand::Set`]. The buckets keep the order from the previous pass, so the items are sorted after the largest item's last digit.
+pub fn radix_sort(items: &mut [u32], steps: &mut Vec<Vec<SortCommand<u32>>>) {
+    let max = match items.iter().max() {
+        Some(max) => *max as u64,
+        None => return,
+    };
+
+    // `u64` so that the place after the largest `u32` digit doesn't overflow
+    let mut place: u64 = 1;
+    let mut digit = 1;
+
+    while place <= max {
+        push_step(
+            steps,
+            vec![SortCommand::PhaseBoundary(format!("Digit {}", digit))],
+        );
+
+        let mut buckets: Vec<Vec<u32>> = vec![vec![]; 10];
+        for item in items.iter() {
+            buckets[(*item as u64 / place % 10) as usize].push(*item);
+        }
+        for (i, item) in buckets.into_iter().flatten().enumerate() {
+            items[i] = item;
+            push_step(steps, vec![SortCommand::Set(i, item)]);
+        }
+
+        place *= 10;
+        digit += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_sort_steps;
+
+    #[test]
+    fn sorts_one_digit_per_pass() {
+        let input = vec![170, 45, 75, 90, 802, 24, 2, 66, u32::MAX];
+        let mut items = input.clone();
+        let mut steps = vec![];
+        radix_sort(&mut items, &mut steps);
+
+        let mut sorted = input.clone();
+        sorted.sort_unstable();
+        assert_eq!(items, sorted);
+
+        let mut replayed = input.clone();
+        run_sort_steps(&mut replayed, &steps);
+        assert_eq!(replayed, sorted);
+
+        // `u32::MAX` has 10 digits, and every pass writes every item
+        assert_eq!(steps.len(), 10 * (1 + input.len()));
+    }
+}
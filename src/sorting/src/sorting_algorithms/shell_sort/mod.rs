@@ -0,0 +1,64 @@
+use crate::{push_step, SortCommand};
+
+/// Shell sort with Knuth's gap sequence 1, 4, 13, 40, ..., which emits a [`SortCommand::PhaseBoundary`] at the start of every gap.
+///
+/// Each phase is an insertion sort of the items that are `gap` apart, so items move far in few swaps while the gaps are large,
+/// and the last phase with a gap of 1 is a plain insertion sort of an almost sorted input.
+pub fn shell_sort<T: Clone + Copy + Ord>(items: &mut [T], steps: &mut Vec<Vec<SortCommand<T>>>) {
+    if items.len() < 2 {
+        return;
+    }
+
+    let mut gap = 1;
+    while gap < items.len() / 3 {
+        gap = 3 * gap + 1;
+    }
+
+    while gap > 0 {
+        push_step(
+            steps,
+            vec![SortCommand::PhaseBoundary(format!("Gap {}", gap))],
+        );
+        for i in gap..items.len() {
+            let mut j = i;
+            while j >= gap && items[j - gap] > items[j] {
+                items.swap(j - gap, j);
+                push_step(steps, vec![SortCommand::Swap(j - gap, j)]);
+                j -= gap;
+            }
+        }
+        gap /= 3;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_sort_steps;
+
+    #[test]
+    fn sorts_with_decreasing_gaps() {
+        let input: Vec<u32> = (0..100).map(|i| (i * 37 + 11) % 23).collect();
+        let mut items = input.clone();
+        let mut steps = vec![];
+        shell_sort(&mut items, &mut steps);
+
+        let mut sorted = input.clone();
+        sorted.sort_unstable();
+        assert_eq!(items, sorted);
+
+        let mut replayed = input;
+        run_sort_steps(&mut replayed, &steps);
+        assert_eq!(replayed, sorted);
+
+        let gaps = steps
+            .iter()
+            .flatten()
+            .filter_map(|command| match command {
+                SortCommand::PhaseBoundary(label) => Some(label.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<&str>>();
+        assert_eq!(gaps, ["Gap 40", "Gap 13", "Gap 4", "Gap 1"]);
+    }
+}
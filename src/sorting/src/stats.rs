@@ -0,0 +1,215 @@
+//! Counting the comparisons, swaps, reads and writes that a sorting algorithm does.
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{SortCommand, SortMetrics};
+
+thread_local! {
+    static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+    /// The comparisons counted when each step was recorded, while [`count_comparisons_by_step`] runs.
+    static STEP_COMPARISONS: RefCell<Option<Vec<usize>>> = const { RefCell::new(None) };
+}
+
+/// An item that counts every time that it's compared with another item, see [`count_comparisons`].\
+/// The algorithms only see the items through their [`Ord`] implementation, so any of them can be instrumented
+/// by sorting `Counted` items instead of changing the algorithm itself.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Counted<T>(pub T);
+
+impl<T: Ord> PartialEq for Counted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl<T: Ord> Eq for Counted<T> {}
+impl<T: Ord> PartialOrd for Counted<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T: Ord> Ord for Counted<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        COMPARISONS.with(|comparisons| comparisons.set(comparisons.get() + 1));
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Runs `f` and counts how many times [`Counted`] items were compared while it ran.
+///
+/// ## Example
+///
+/// ```rust
+/// use sorting::{count_comparisons, insertion_sort, Counted};
+///
+/// let mut items = vec![Counted(3), Counted(1), Counted(2)];
+/// let ((), comparisons) = count_comparisons(|| insertion_sort(&mut items, &mut vec![]));
+///
+/// // 3 > 1, 3 > 2 and 1 < 2
+/// assert_eq!(comparisons, 3);
+/// ```
+pub fn count_comparisons<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = COMPARISONS.with(Cell::get);
+    let result = f();
+    (result, COMPARISONS.with(Cell::get) - before)
+}
+
+/// Runs `f` like [`count_comparisons`], and also counts how many comparisons had been made when each step was recorded.
+/// Only the steps that the algorithms of this crate record are counted.
+///
+/// ## Example
+///
+/// ```rust
+/// use sorting::{count_comparisons_by_step, insertion_sort, Counted};
+///
+/// let mut items = vec![Counted(3), Counted(1), Counted(2)];
+/// let mut steps = vec![];
+/// let ((), comparisons, step_comparisons) =
+///     count_comparisons_by_step(|| insertion_sort(&mut items, &mut steps));
+///
+/// // 3 > 1 swaps them, and 3 > 2 swaps those before 1 < 2 ends the sort
+/// assert_eq!(step_comparisons, vec![1, 2]);
+/// assert_eq!(comparisons, 3);
+/// ```
+pub fn count_comparisons_by_step<R>(f: impl FnOnce() -> R) -> (R, usize, Vec<usize>) {
+    let outer = STEP_COMPARISONS.with(|log| log.replace(Some(vec![])));
+    let before = COMPARISONS.with(Cell::get);
+    let (result, comparisons) = count_comparisons(f);
+    let step_comparisons = STEP_COMPARISONS
+        .with(|log| log.replace(outer))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|count| count - before)
+        .collect();
+    (result, comparisons, step_comparisons)
+}
+
+/// Records a step of a sorting algorithm, along with the comparisons made before it if they're being counted.
+pub(crate) fn push_step<T>(steps: &mut Vec<Vec<SortCommand<T>>>, step: Vec<SortCommand<T>>) {
+    steps.push(step);
+    STEP_COMPARISONS.with(|log| {
+        if let Some(log) = log.borrow_mut().as_mut() {
+            log.push(COMPARISONS.with(Cell::get));
+        }
+    });
+}
+
+/// The work that a sorting algorithm did, for comparing algorithms by more than the number of steps.\
+/// A swap reads and writes both of its items and a set writes one item, while a comparison reads both of the compared items.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SortStats {
+    pub comparisons: usize,
+    pub swaps: usize,
+    pub writes: usize,
+    pub reads: usize,
+}
+impl SortStats {
+    /// Counts the swaps, reads and writes of the `steps` on top of the given number of `comparisons`.
+    pub fn new<T>(steps: &[Vec<SortCommand<T>>], comparisons: usize) -> Self {
+        Self::from_metrics(&SortMetrics::from_steps(steps), comparisons)
+    }
+    /// Counts the work done after every number of played `steps`, from none to all of them, in one pass.\
+    /// `step_comparisons` are the comparisons made when each step was recorded, one for every step, see [`count_comparisons_by_step`].
+    /// Without them, only the swaps and writes are counted before the last step, which has all of the `comparisons`.
+    pub fn after_each_step<T>(
+        steps: &[Vec<SortCommand<T>>],
+        step_comparisons: Option<&[usize]>,
+        comparisons: usize,
+    ) -> Vec<Self> {
+        let mut stats = Vec::with_capacity(steps.len() + 1);
+        let mut metrics = SortMetrics::default();
+        stats.push(Self::from_metrics(&metrics, 0));
+        for (i, step) in steps.iter().enumerate() {
+            let step_metrics = SortMetrics::from_steps(std::slice::from_ref(step));
+            metrics.swaps += step_metrics.swaps;
+            metrics.sets += step_metrics.sets;
+            let comparisons_so_far = if i + 1 == steps.len() {
+                comparisons
+            } else {
+                step_comparisons.map_or(0, |counts| counts[i])
+            };
+            stats.push(Self::from_metrics(&metrics, comparisons_so_far));
+        }
+        stats
+    }
+    fn from_metrics(metrics: &SortMetrics, comparisons: usize) -> Self {
+        Self {
+            comparisons,
+            swaps: metrics.swaps,
+            writes: 2 * metrics.swaps + metrics.sets,
+            reads: 2 * comparisons + 2 * metrics.swaps,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bubble_sort, merge_sort, quicksort, SortFn};
+
+    /// Sorts `input` with the algorithm and counts its work.
+    fn stats(sort: SortFn<Counted<u32>>, input: &[u32]) -> SortStats {
+        let mut items = input.iter().copied().map(Counted).collect();
+        let mut steps = vec![];
+        let ((), comparisons) = count_comparisons(|| sort(&mut items, &mut steps));
+        assert!(items.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        SortStats::new(&steps, comparisons)
+    }
+
+    #[test]
+    fn bubble_sort_does_quadratic_work() {
+        let input: Vec<u32> = (1..=10).rev().collect();
+        // Every pass compares the unsorted pairs and swaps all of them
+        assert_eq!(
+            stats(bubble_sort, &input),
+            SortStats {
+                comparisons: 45,
+                swaps: 45,
+                writes: 90,
+                reads: 180
+            }
+        );
+    }
+
+    #[test]
+    fn comparisons_are_counted_at_every_step() {
+        let input: Vec<u32> = (0..50).map(|i| (i * 17 + 5) % 50).collect();
+        for sort in [bubble_sort, merge_sort, quicksort] {
+            let mut items = input.iter().copied().map(Counted).collect();
+            let mut steps = vec![];
+            let ((), comparisons, step_comparisons) =
+                count_comparisons_by_step(|| sort(&mut items, &mut steps));
+            assert_eq!(step_comparisons.len(), steps.len());
+            assert!(step_comparisons.windows(2).all(|pair| pair[0] <= pair[1]));
+
+            let stats = SortStats::after_each_step(&steps, Some(&step_comparisons), comparisons);
+            assert_eq!(stats.len(), steps.len() + 1);
+            assert_eq!(stats[0], SortStats::default());
+            assert_eq!(stats[steps.len()], SortStats::new(&steps, comparisons));
+            for played in 1..steps.len() {
+                assert_eq!(
+                    stats[played],
+                    SortStats::new(&steps[..played], step_comparisons[played - 1])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn quicksort_and_merge_sort_compare_less_than_bubble_sort() {
+        let input: Vec<u32> = (0..200).map(|i| (i * 7919 + 13) % 200).collect();
+        let bubble = stats(bubble_sort, &input);
+        for sort in [quicksort, merge_sort] {
+            let stats = stats(sort, &input);
+            assert!(stats.comparisons * 5 < bubble.comparisons, "{:?}", stats);
+        }
+
+        // Merge sort only sets items
+        let merge = stats(merge_sort, &input);
+        assert_eq!((merge.swaps, merge.reads), (0, 2 * merge.comparisons));
+        assert!(merge.writes > 0);
+    }
+}
@@ -0,0 +1,113 @@
+//! A float type that can be sorted by the sorting algorithms.
+
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, fmt};
+
+/// An `f64` that is ordered with [`f64::total_cmp`], so that floats can be sorted with the algorithms that require [`Ord`].
+///
+/// Unlike the usual float comparisons, this orders NaN consistently: positive NaN comes after positive infinity and negative NaN before negative infinity.
+/// Negative zero comes before positive zero, and two values are only equal if they have the same bits.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct TotalF64(pub f64);
+
+impl PartialEq for TotalF64 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for TotalF64 {}
+impl PartialOrd for TotalF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for TotalF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl fmt::Display for TotalF64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<f64> for TotalF64 {
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+impl From<u32> for TotalF64 {
+    fn from(value: u32) -> Self {
+        Self(value as f64)
+    }
+}
+impl From<TotalF64> for f64 {
+    fn from(value: TotalF64) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bubble_sort, heapsort, insertion_sort, merge_sort, quicksort, SortCommand};
+
+    type Sort = fn(&mut Vec<TotalF64>, &mut Vec<Vec<SortCommand<TotalF64>>>);
+
+    #[test]
+    fn algorithms_sort_floats_with_nan() {
+        let input = [
+            3.5,
+            f64::NAN,
+            -0.0,
+            f64::INFINITY,
+            -2.25,
+            0.0,
+            -f64::NAN,
+            1e-9,
+            3.5,
+            f64::NEG_INFINITY,
+            -1e9,
+            f64::NAN,
+        ];
+        let mut expected = input.to_vec();
+        expected.sort_by(f64::total_cmp);
+        let expected_bits = expected
+            .iter()
+            .map(|item| item.to_bits())
+            .collect::<Vec<_>>();
+
+        let sorts: [(&str, Sort); 5] = [
+            ("bubble sort", bubble_sort),
+            ("insertion sort", insertion_sort),
+            ("merge sort", merge_sort),
+            ("heapsort", heapsort),
+            ("quicksort", quicksort),
+        ];
+
+        for (name, sort) in sorts {
+            let mut items = input.iter().copied().map(TotalF64).collect::<Vec<_>>();
+            sort(&mut items, &mut vec![]);
+            let bits = items
+                .iter()
+                .map(|item| item.0.to_bits())
+                .collect::<Vec<_>>();
+            assert_eq!(bits, expected_bits, "{}", name);
+        }
+
+        // Negative NaN is first and positive NaN is last
+        assert!(expected[0].is_nan() && expected[0].is_sign_negative());
+        assert!(expected[10].is_nan() && expected[11].is_nan());
+    }
+
+    #[test]
+    fn total_order_edge_cases() {
+        assert!(TotalF64(-0.0) < TotalF64(0.0));
+        assert!(TotalF64(f64::INFINITY) < TotalF64(f64::NAN));
+        assert_eq!(TotalF64(f64::NAN), TotalF64(f64::NAN));
+        assert_eq!(TotalF64::from(3_u32), TotalF64(3.0));
+        assert_eq!(TotalF64(2.5).to_string(), "2.5");
+    }
+}
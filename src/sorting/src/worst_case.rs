@@ -0,0 +1,154 @@
+//! Generators for inputs that make specific sorting algorithms do as much work as possible.
+
+/// Generates the numbers from `len` down to 1.
+///
+/// Every pair of items is an inversion, so bubble sort and insertion sort need the most comparisons and swaps with this input.
+pub fn descending_input(len: usize) -> Vec<u32> {
+    (1..=len as u32).rev().collect()
+}
+
+/// Generates the numbers from 1 to `len`, i.e. an already sorted input.
+///
+/// Quicksort that always picks the last item as the pivot only splits off the pivot itself in every partition, which takes `O(n^2)` time.
+/// Heapsort also has to move every item all the way down when building the heap.
+pub fn ascending_input(len: usize) -> Vec<u32> {
+    (1..=len as u32).collect()
+}
+
+/// Generates the numbers from 1 to `len` in an order where the halves are always interleaved.
+///
+/// Merge sort has to compare the halves until the very last item in every merge, since neither half runs out early.
+pub fn interleaved_input(len: usize) -> Vec<u32> {
+    let mut items = ascending_input(len);
+    interleave(&mut items);
+    items
+}
+
+/// Splits the sorted `items` so that every other item goes to each half like merge sort splits them, and does the same for the halves.
+fn interleave(items: &mut [u32]) {
+    if items.len() < 2 {
+        return;
+    }
+
+    // Merge sort's left half is the smaller one, so it gets the items at odd indices
+    let odd = items
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .copied()
+        .collect::<Vec<u32>>();
+    let even = items.iter().step_by(2).copied().collect::<Vec<u32>>();
+    let middle = odd.len();
+    items[..middle].copy_from_slice(&odd);
+    items[middle..].copy_from_slice(&even);
+
+    let (left, right) = items.split_at_mut(middle);
+    interleave(left);
+    interleave(right);
+}
+
+/// Generates an input where every item except the largest one lands in the first bucket of bucket sort, in descending order.
+///
+/// The largest item is so much bigger than the others that bucket sort degrades into a single insertion sort of a descending input.
+pub fn clustered_input(len: usize) -> Vec<u32> {
+    if len < 2 {
+        return ascending_input(len);
+    }
+
+    let bucket_count = (len as f32).sqrt().ceil() as u32;
+    let mut items = descending_input(len - 1);
+    items.push(bucket_count * len as u32 * 2);
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bubble_sort, bucket_sort, heapsort, insertion_sort, merge_sort, quicksort, SortCommand,
+    };
+    use std::{cell::Cell, cmp::Ordering};
+
+    thread_local! {
+        static COMPARISONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// A number that counts how many times it has been compared.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct Counted(u32);
+    impl PartialOrd for Counted {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Counted {
+        fn cmp(&self, other: &Self) -> Ordering {
+            COMPARISONS.with(|count| count.set(count.get() + 1));
+            self.0.cmp(&other.0)
+        }
+    }
+
+    type Sort = fn(&mut Vec<Counted>, &mut Vec<Vec<SortCommand<Counted>>>);
+
+    fn comparisons(sort: Sort, input: &[u32]) -> usize {
+        let mut items = input.iter().map(|item| Counted(*item)).collect::<Vec<_>>();
+        COMPARISONS.with(|count| count.set(0));
+        sort(&mut items, &mut vec![]);
+        assert!(items.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        COMPARISONS.with(Cell::get)
+    }
+
+    /// Shuffles the numbers from 1 to `len` with a Fisher-Yates shuffle driven by a linear congruential generator.
+    fn random_inputs(len: usize) -> Vec<Vec<u32>> {
+        (0..10_u64)
+            .map(|seed| {
+                let mut state = seed;
+                let mut items = ascending_input(len);
+                for i in (1..len).rev() {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    items.swap(i, (state >> 33) as usize % (i + 1));
+                }
+                items
+            })
+            .collect()
+    }
+
+    #[test]
+    fn worst_cases_need_more_comparisons_than_random_inputs() {
+        let len = 64;
+        let cases: [(&str, Sort, Vec<u32>); 5] = [
+            ("bubble sort", bubble_sort, descending_input(len)),
+            ("insertion sort", insertion_sort, descending_input(len)),
+            ("merge sort", merge_sort, interleaved_input(len)),
+            ("heapsort", heapsort, ascending_input(len)),
+            ("quicksort", quicksort, ascending_input(len)),
+        ];
+
+        for (name, sort, worst_case) in cases {
+            let worst = comparisons(sort, &worst_case);
+            for input in random_inputs(len) {
+                assert!(worst >= comparisons(sort, &input), "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn clustered_input_degrades_bucket_sort() {
+        // Bucket sort only works on numbers, so count its steps instead of comparisons
+        let len = 64;
+        let step_count = |input: &[u32]| {
+            let mut steps = vec![];
+            bucket_sort(&mut input.to_vec(), &mut steps);
+            steps.len()
+        };
+
+        let worst = step_count(&clustered_input(len));
+        // Every item is set into a bucket, and then every pair in the first bucket is swapped
+        assert_eq!(worst, len + (len - 1) * (len - 2) / 2);
+        for input in random_inputs(len) {
+            assert!(worst > step_count(&input));
+        }
+    }
+}
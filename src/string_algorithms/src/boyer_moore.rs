@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use crate::{MatchStep, ShiftRule};
+
+/// Compares the pattern with the text from right to left, and on a mismatch shifts the pattern by the larger shift
+/// of the bad character rule and the good suffix rule.\
+/// This often skips most of the text, but takes *O(nm)* time in the worst case for a text of length *n* and a pattern of length *m*.
+pub fn boyer_moore_search(
+    text: &[char],
+    pattern: &[char],
+    steps: &mut Vec<MatchStep>,
+) -> Vec<usize> {
+    let mut matches = vec![];
+    if pattern.is_empty() || pattern.len() > text.len() {
+        return matches;
+    }
+
+    let last_occurrences = last_occurrences(pattern);
+    let good_suffix_shifts = good_suffix_shifts(pattern);
+
+    let mut shift = 0;
+    while shift + pattern.len() <= text.len() {
+        steps.push(MatchStep::Align(shift));
+
+        // Find the last mismatch, if there is one
+        let mut mismatch = None;
+        for i in (0..pattern.len()).rev() {
+            let matched = text[shift + i] == pattern[i];
+            steps.push(MatchStep::Compare(shift + i, i, matched));
+            if !matched {
+                mismatch = Some(i);
+                break;
+            }
+        }
+
+        let (distance, rule) = match mismatch {
+            None => {
+                steps.push(MatchStep::Match(shift));
+                matches.push(shift);
+                (good_suffix_shifts[0], ShiftRule::GoodSuffix)
+            }
+            Some(i) => {
+                // Line the mismatched character up with its last occurrence before `i`, or move past it
+                let bad_character = match last_occurrences.get(&text[shift + i]) {
+                    Some(&last) if last < i => i - last,
+                    Some(_) => 1,
+                    None => i + 1,
+                };
+                let good_suffix = good_suffix_shifts[i + 1];
+                if bad_character >= good_suffix {
+                    (bad_character, ShiftRule::BadCharacter)
+                } else {
+                    (good_suffix, ShiftRule::GoodSuffix)
+                }
+            }
+        };
+        steps.push(MatchStep::Shift(distance, rule));
+        shift += distance;
+    }
+    matches
+}
+
+/// Finds the last index of every character in the pattern.
+fn last_occurrences(pattern: &[char]) -> HashMap<char, usize> {
+    pattern
+        .iter()
+        .enumerate()
+        .map(|(i, character)| (*character, i))
+        .collect()
+}
+
+/// Computes how far the pattern can be shifted when the suffix `pattern[i..]` matched, for every `i` from 0 to the length of the pattern.\
+/// The shift lines the suffix up with its next occurrence in the pattern that is preceded by a different character,
+/// or with the longest prefix of the pattern that is a suffix of it.
+fn good_suffix_shifts(pattern: &[char]) -> Vec<usize> {
+    let len = pattern.len();
+    let mut shifts = vec![0; len + 1];
+    // `borders[i]` is where the widest border of `pattern[i..]` starts
+    let mut borders = vec![0; len + 1];
+
+    let mut i = len;
+    let mut j = len + 1;
+    borders[i] = j;
+    while i > 0 {
+        while j <= len && pattern[i - 1] != pattern[j - 1] {
+            if shifts[j] == 0 {
+                shifts[j] = j - i;
+            }
+            j = borders[j];
+        }
+        i -= 1;
+        j -= 1;
+        borders[i] = j;
+    }
+
+    // The suffixes without an occurrence shift to the widest border of the whole pattern that fits
+    let mut j = borders[0];
+    for (i, shift) in shifts.iter_mut().enumerate() {
+        if *shift == 0 {
+            *shift = j;
+        }
+        if i == j {
+            j = borders[j];
+        }
+    }
+    shifts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_characters_that_are_not_in_the_pattern() {
+        let text: Vec<char> = "xxxxxxxxxabc".chars().collect();
+        let pattern: Vec<char> = "abc".chars().collect();
+        let mut steps = vec![];
+        assert_eq!(boyer_moore_search(&text, &pattern, &mut steps), vec![9]);
+
+        let comparisons = steps
+            .iter()
+            .filter(|step| matches!(step, MatchStep::Compare(..)))
+            .count();
+        // One comparison for each of the three skips, and three for the match
+        assert_eq!(comparisons, 6);
+        assert!(steps.contains(&MatchStep::Shift(3, ShiftRule::BadCharacter)));
+    }
+}
@@ -0,0 +1,297 @@
+//! Dynamic programming over two strings, where the cell at row `i` and column `j` of the table
+//! is the answer for the first `i` characters of `a` and the first `j` characters of `b`.
+use std::fmt;
+
+/// The neighbor that a cell of the table was computed from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbor {
+    /// The characters at the cell are lined up with each other.
+    Diagonal,
+    /// The character of `a` is lined up with a gap.
+    Up,
+    /// The character of `b` is lined up with a gap.
+    Left,
+}
+
+impl Neighbor {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Neighbor::Diagonal => "diagonal",
+            Neighbor::Up => "upper",
+            Neighbor::Left => "left",
+        }
+    }
+    /// Gets the cell of this neighbor of `(row, column)`.
+    pub fn of(&self, (row, column): (usize, usize)) -> (usize, usize) {
+        match self {
+            Neighbor::Diagonal => (row - 1, column - 1),
+            Neighbor::Up => (row - 1, column),
+            Neighbor::Left => (row, column - 1),
+        }
+    }
+}
+
+/// A step of filling a dynamic programming table and tracing back the optimal alignment in it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DpStep {
+    /// Compute a cell, which is `None` only for the top left one that isn't computed from anything: `(row, column, value, from)`
+    Fill(usize, usize, usize, Option<Neighbor>),
+    /// The optimal alignment goes through a cell, starting from the bottom right one: `(row, column)`
+    Trace(usize, usize),
+}
+
+impl fmt::Display for DpStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DpStep::Fill(row, column, value, Some(from)) => write!(
+                f,
+                "Row {}, column {} is {}, from the {} neighbor",
+                row,
+                column,
+                value,
+                from.name()
+            ),
+            DpStep::Fill(row, column, value, None) => {
+                write!(f, "Row {}, column {} is {}", row, column, value)
+            }
+            DpStep::Trace(row, column) => {
+                write!(f, "Tracing back through row {}, column {}", row, column)
+            }
+        }
+    }
+}
+
+/// A dynamic programming table after some of its steps.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DpTable {
+    /// The values of the cells that have been filled by row and column.
+    pub values: Vec<Vec<Option<usize>>>,
+    /// The neighbors that the filled cells were computed from.
+    pub from: Vec<Vec<Option<Neighbor>>>,
+    /// The cells of the optimal alignment that have been traced, from the bottom right one.
+    pub path: Vec<(usize, usize)>,
+}
+
+impl DpTable {
+    /// Plays `steps` on an empty table for strings of lengths `a_len` and `b_len`.
+    pub fn after(a_len: usize, b_len: usize, steps: &[DpStep]) -> Self {
+        let mut table = Self {
+            values: vec![vec![None; b_len + 1]; a_len + 1],
+            from: vec![vec![None; b_len + 1]; a_len + 1],
+            path: vec![],
+        };
+        for step in steps {
+            match step {
+                DpStep::Fill(row, column, value, from) => {
+                    table.values[*row][*column] = Some(*value);
+                    table.from[*row][*column] = *from;
+                }
+                DpStep::Trace(row, column) => table.path.push((*row, *column)),
+            }
+        }
+        table
+    }
+}
+
+/// Fills the table of two strings and returns the value of its bottom right cell, recording its steps.
+pub type DpFn = fn(&[char], &[char], &mut Vec<DpStep>) -> usize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DpProblem {
+    EditDistance,
+    LongestCommonSubsequence,
+}
+
+impl DpProblem {
+    pub const ALL: [DpProblem; 2] = [DpProblem::EditDistance, DpProblem::LongestCommonSubsequence];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DpProblem::EditDistance => "Edit distance",
+            DpProblem::LongestCommonSubsequence => "Longest common subsequence",
+        }
+    }
+    pub fn dp_fn(&self) -> DpFn {
+        match self {
+            DpProblem::EditDistance => edit_distance,
+            DpProblem::LongestCommonSubsequence => longest_common_subsequence,
+        }
+    }
+}
+
+/// Fills a table row by row, computing every cell that isn't on the first row or column from its three neighbors with `best`,
+/// and then traces the neighbors back from the bottom right cell.\
+/// The cells on the first row and column are their distance from the top left cell times `edge_cost`.
+fn fill_table(
+    a: &[char],
+    b: &[char],
+    edge_cost: usize,
+    best: impl Fn(char, char, usize, usize, usize) -> (usize, Neighbor),
+    steps: &mut Vec<DpStep>,
+) -> usize {
+    let mut values = vec![vec![0; b.len() + 1]; a.len() + 1];
+    let mut from = vec![vec![None; b.len() + 1]; a.len() + 1];
+
+    for row in 0..=a.len() {
+        for column in 0..=b.len() {
+            let (value, neighbor) = match (row, column) {
+                (0, 0) => (0, None),
+                (_, 0) => (row * edge_cost, Some(Neighbor::Up)),
+                (0, _) => (column * edge_cost, Some(Neighbor::Left)),
+                _ => {
+                    let (value, neighbor) = best(
+                        a[row - 1],
+                        b[column - 1],
+                        values[row - 1][column - 1],
+                        values[row - 1][column],
+                        values[row][column - 1],
+                    );
+                    (value, Some(neighbor))
+                }
+            };
+            values[row][column] = value;
+            from[row][column] = neighbor;
+            steps.push(DpStep::Fill(row, column, value, neighbor));
+        }
+    }
+
+    let mut cell = (a.len(), b.len());
+    steps.push(DpStep::Trace(cell.0, cell.1));
+    while let Some(neighbor) = from[cell.0][cell.1] {
+        cell = neighbor.of(cell);
+        steps.push(DpStep::Trace(cell.0, cell.1));
+    }
+    values[a.len()][b.len()]
+}
+
+/// Computes the Levenshtein distance, which is the fewest insertions, deletions and substitutions of characters that turn `a` into `b`.\
+/// Ties prefer the diagonal neighbor, then the upper one. This takes *O(nm)* time for strings of lengths *n* and *m*.
+///
+/// ## Example
+///
+/// ```rust
+/// use string_algorithms::edit_distance;
+///
+/// let kitten = "kitten".chars().collect::<Vec<char>>();
+/// let sitting = "sitting".chars().collect::<Vec<char>>();
+///
+/// assert_eq!(edit_distance(&kitten, &sitting, &mut vec![]), 3);
+/// ```
+pub fn edit_distance(a: &[char], b: &[char], steps: &mut Vec<DpStep>) -> usize {
+    fill_table(
+        a,
+        b,
+        1,
+        |a_char, b_char, diagonal, up, left| {
+            let substitution = diagonal + usize::from(a_char != b_char);
+            if substitution <= up + 1 && substitution <= left + 1 {
+                (substitution, Neighbor::Diagonal)
+            } else if up <= left {
+                (up + 1, Neighbor::Up)
+            } else {
+                (left + 1, Neighbor::Left)
+            }
+        },
+        steps,
+    )
+}
+
+/// Computes the length of the longest sequence of characters that is in both `a` and `b` in the same order, but not necessarily next to each other.\
+/// Ties prefer the upper neighbor. This takes *O(nm)* time for strings of lengths *n* and *m*.
+pub fn longest_common_subsequence(a: &[char], b: &[char], steps: &mut Vec<DpStep>) -> usize {
+    fill_table(
+        a,
+        b,
+        0,
+        |a_char, b_char, diagonal, up, left| {
+            if a_char == b_char {
+                (diagonal + 1, Neighbor::Diagonal)
+            } else if up >= left {
+                (up, Neighbor::Up)
+            } else {
+                (left, Neighbor::Left)
+            }
+        },
+        steps,
+    )
+}
+
+/// Lines the characters of `a` and `b` up along a traced path, from the first characters to the last ones.
+/// `None` is a gap.
+pub fn alignment(
+    a: &[char],
+    b: &[char],
+    path: &[(usize, usize)],
+) -> Vec<(Option<char>, Option<char>)> {
+    let mut columns = path
+        .windows(2)
+        .map(|cells| {
+            let ((row, column), (previous_row, previous_column)) = (cells[0], cells[1]);
+            (
+                (row != previous_row).then(|| a[row - 1]),
+                (column != previous_column).then(|| b[column - 1]),
+            )
+        })
+        .collect::<Vec<_>>();
+    columns.reverse();
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(string: &str) -> Vec<char> {
+        string.chars().collect()
+    }
+
+    #[test]
+    fn problems_have_known_answers() {
+        let cases = [
+            ("kitten", "sitting", 3, 4),
+            ("flaw", "lawn", 2, 3),
+            ("ABCBDAB", "BDCABA", 5, 4),
+            ("", "abc", 3, 0),
+            ("same", "same", 0, 4),
+        ];
+        for (a, b, distance, lcs_len) in cases {
+            let (a, b) = (chars(a), chars(b));
+            assert_eq!(edit_distance(&a, &b, &mut vec![]), distance);
+            assert_eq!(longest_common_subsequence(&a, &b, &mut vec![]), lcs_len);
+        }
+    }
+
+    #[test]
+    fn traced_alignments_match_the_answers() {
+        let (a, b) = (chars("intention"), chars("execution"));
+        for problem in DpProblem::ALL {
+            let mut steps = vec![];
+            let answer = problem.dp_fn()(&a, &b, &mut steps);
+            let table = DpTable::after(a.len(), b.len(), &steps);
+            assert!(table.values.iter().flatten().all(Option::is_some));
+            assert_eq!(table.path.first(), Some(&(a.len(), b.len())));
+            assert_eq!(table.path.last(), Some(&(0, 0)));
+
+            let columns = alignment(&a, &b, &table.path);
+            // Every character is in the alignment once, in order
+            let aligned_a = columns
+                .iter()
+                .filter_map(|(a_char, _)| *a_char)
+                .collect::<Vec<_>>();
+            let aligned_b = columns
+                .iter()
+                .filter_map(|(_, b_char)| *b_char)
+                .collect::<Vec<_>>();
+            assert_eq!((aligned_a, aligned_b), (a.clone(), b.clone()));
+
+            let matched = columns
+                .iter()
+                .filter(|(a_char, b_char)| a_char.is_some() && a_char == b_char)
+                .count();
+            match problem {
+                DpProblem::EditDistance => assert_eq!(columns.len() - matched, answer),
+                DpProblem::LongestCommonSubsequence => assert_eq!(matched, answer),
+            }
+        }
+    }
+}
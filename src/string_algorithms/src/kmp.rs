@@ -0,0 +1,94 @@
+use crate::{MatchStep, ShiftRule};
+
+/// Computes the failure function of the Knuth-Morris-Pratt algorithm,
+/// which is the length of the longest proper prefix of `pattern[..=i]` that is also its suffix for every `i`.\
+/// Every value is recorded as a [`MatchStep::Failure`].
+pub fn failure_function(pattern: &[char], steps: &mut Vec<MatchStep>) -> Vec<usize> {
+    let mut failure = vec![0; pattern.len()];
+    if pattern.is_empty() {
+        return failure;
+    }
+
+    steps.push(MatchStep::Failure(0, 0));
+    let mut length = 0;
+    for i in 1..pattern.len() {
+        // Fall back to shorter borders until one of them can be extended
+        while length > 0 && pattern[i] != pattern[length] {
+            length = failure[length - 1];
+        }
+        if pattern[i] == pattern[length] {
+            length += 1;
+        }
+        failure[i] = length;
+        steps.push(MatchStep::Failure(i, length));
+    }
+    failure
+}
+
+/// Reads the text from left to right once, and on a mismatch shifts the pattern with the [`failure_function`]
+/// so that the characters known to match don't have to be compared again.\
+/// This takes *O(n + m)* time for a text of length *n* and a pattern of length *m*.
+pub fn kmp_search(text: &[char], pattern: &[char], steps: &mut Vec<MatchStep>) -> Vec<usize> {
+    let mut matches = vec![];
+    if pattern.is_empty() || pattern.len() > text.len() {
+        return matches;
+    }
+    let failure = failure_function(pattern, steps);
+
+    // How many characters of the pattern match the text before `i`
+    let mut matched = 0;
+    let mut aligned = None;
+    for i in 0..text.len() {
+        loop {
+            let shift = i - matched;
+            // The pattern would run past the end of the text
+            if shift + pattern.len() > text.len() {
+                return matches;
+            }
+            if aligned != Some(shift) {
+                steps.push(MatchStep::Align(shift));
+                aligned = Some(shift);
+            }
+
+            let is_match = text[i] == pattern[matched];
+            steps.push(MatchStep::Compare(i, matched, is_match));
+            if is_match {
+                matched += 1;
+                break;
+            }
+            if matched == 0 {
+                break;
+            }
+            let border = failure[matched - 1];
+            steps.push(MatchStep::Shift(matched - border, ShiftRule::Failure));
+            matched = border;
+        }
+
+        if matched == pattern.len() {
+            let shift = i + 1 - matched;
+            steps.push(MatchStep::Match(shift));
+            matches.push(shift);
+            let border = failure[matched - 1];
+            steps.push(MatchStep::Shift(matched - border, ShiftRule::Failure));
+            matched = border;
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failure_function_finds_borders() {
+        let pattern: Vec<char> = "abacabab".chars().collect();
+        let mut steps = vec![];
+        assert_eq!(
+            failure_function(&pattern, &mut steps),
+            vec![0, 0, 1, 0, 1, 2, 3, 2]
+        );
+        assert_eq!(steps.len(), pattern.len());
+        assert_eq!(steps[6], MatchStep::Failure(6, 3));
+    }
+}
@@ -0,0 +1,236 @@
+//! String matching and dynamic programming algorithms that record their steps so that they can be visualized.
+pub mod boyer_moore;
+pub mod dynamic_programming;
+pub mod kmp;
+pub mod naive;
+pub mod rabin_karp;
+
+pub use boyer_moore::*;
+pub use dynamic_programming::*;
+pub use kmp::*;
+pub use naive::*;
+pub use rabin_karp::*;
+
+use std::fmt;
+
+/// Why the pattern moves more than one character to the right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShiftRule {
+    /// KMP keeps the longest prefix of the pattern that is also a suffix of the matched characters.
+    Failure,
+    /// Boyer-Moore aligns the mismatched character of the text with its last occurrence in the pattern.
+    BadCharacter,
+    /// Boyer-Moore aligns the matched suffix with its next occurrence in the pattern.
+    GoodSuffix,
+}
+
+impl ShiftRule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShiftRule::Failure => "failure function",
+            ShiftRule::BadCharacter => "bad character rule",
+            ShiftRule::GoodSuffix => "good suffix rule",
+        }
+    }
+}
+
+/// A step of a string search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MatchStep {
+    /// Line the start of the pattern up with an index of the text: `(shift)`
+    Align(usize),
+    /// Compare a character of the text with a character of the pattern: `(text_index, pattern_index, matched)`
+    Compare(usize, usize, bool),
+    /// The whole pattern is in the text at a shift: `(shift)`
+    Match(usize),
+    /// Move the pattern to the right by more than the naive search would: `(distance, rule)`
+    Shift(usize, ShiftRule),
+    /// The failure function of KMP for a prefix of the pattern, which is the length of the longest proper prefix of it that is also its suffix:
+    /// `(pattern_index, length)`
+    Failure(usize, usize),
+    /// The rolling hash of the text under the pattern and the hash of the pattern in Rabin-Karp: `(window_hash, pattern_hash)`
+    Hash(u64, u64),
+}
+
+impl fmt::Display for MatchStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchStep::Align(shift) => write!(f, "Aligning the pattern with index {}", shift),
+            MatchStep::Compare(text_index, pattern_index, matched) => write!(
+                f,
+                "Comparing index {} of the text with index {} of the pattern: {}",
+                text_index,
+                pattern_index,
+                if *matched { "match" } else { "mismatch" }
+            ),
+            MatchStep::Match(shift) => write!(f, "Found the pattern at index {}", shift),
+            MatchStep::Shift(distance, rule) => write!(
+                f,
+                "Shifting the pattern by {} with the {}",
+                distance,
+                rule.name()
+            ),
+            MatchStep::Failure(pattern_index, length) => write!(
+                f,
+                "The longest proper prefix that is also a suffix of the first {} characters has length {}",
+                pattern_index + 1,
+                length
+            ),
+            MatchStep::Hash(window_hash, pattern_hash) => write!(
+                f,
+                "The window hashes to {} and the pattern to {}{}",
+                window_hash,
+                pattern_hash,
+                if window_hash == pattern_hash {
+                    ", so the characters are checked"
+                } else {
+                    ", so the window is skipped"
+                }
+            ),
+        }
+    }
+}
+
+/// What a string search has found out after some of its steps.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MatchState {
+    /// The index of the text that the start of the pattern is lined up with, if the search has started.
+    pub shift: Option<usize>,
+    /// The indices of the pattern that have been compared at the current shift, and whether they matched.
+    pub compared: Vec<(usize, bool)>,
+    /// The shifts at which the whole pattern has been found.
+    pub matches: Vec<usize>,
+    /// The values of the failure function of KMP that have been computed, by the index of the pattern.
+    pub failure: Vec<usize>,
+    /// The latest hashes of Rabin-Karp: `(window_hash, pattern_hash)`
+    pub hashes: Option<(u64, u64)>,
+}
+
+impl MatchState {
+    /// Plays `steps` from the start of a search.
+    pub fn after(steps: &[MatchStep]) -> Self {
+        let mut state = Self::default();
+        for step in steps {
+            match step {
+                MatchStep::Align(shift) => {
+                    state.shift = Some(*shift);
+                    state.compared.clear();
+                    state.hashes = None;
+                }
+                MatchStep::Compare(_, pattern_index, matched) => {
+                    state.compared.push((*pattern_index, *matched))
+                }
+                MatchStep::Match(shift) => state.matches.push(*shift),
+                MatchStep::Shift(..) => (),
+                MatchStep::Failure(_, length) => state.failure.push(*length),
+                MatchStep::Hash(window_hash, pattern_hash) => {
+                    state.hashes = Some((*window_hash, *pattern_hash))
+                }
+            }
+        }
+        state
+    }
+}
+
+/// A string search that finds every index of `text` where `pattern` starts, recording its steps.\
+/// Nothing is found for an empty pattern.
+pub type SearchFn = fn(&[char], &[char], &mut Vec<MatchStep>) -> Vec<usize>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringAlgorithm {
+    Naive,
+    Kmp,
+    RabinKarp,
+    BoyerMoore,
+}
+
+impl StringAlgorithm {
+    pub const ALL: [StringAlgorithm; 4] = [
+        StringAlgorithm::Naive,
+        StringAlgorithm::Kmp,
+        StringAlgorithm::RabinKarp,
+        StringAlgorithm::BoyerMoore,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StringAlgorithm::Naive => "Naive search",
+            StringAlgorithm::Kmp => "Knuth-Morris-Pratt",
+            StringAlgorithm::RabinKarp => "Rabin-Karp",
+            StringAlgorithm::BoyerMoore => "Boyer-Moore",
+        }
+    }
+    pub fn search_fn(&self) -> SearchFn {
+        match self {
+            StringAlgorithm::Naive => naive_search,
+            StringAlgorithm::Kmp => kmp_search,
+            StringAlgorithm::RabinKarp => rabin_karp_search,
+            StringAlgorithm::BoyerMoore => boyer_moore_search,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(string: &str) -> Vec<char> {
+        string.chars().collect()
+    }
+
+    #[test]
+    fn algorithms_find_the_same_matches() {
+        // Small alphabets make partial matches and overlapping matches common
+        let mut seed = 7u64;
+        let mut random_string = |len: usize| {
+            (0..len)
+                .map(|_| {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    (b'a' + (seed >> 33) as u8 % 3) as char
+                })
+                .collect::<Vec<char>>()
+        };
+
+        for _ in 0..200 {
+            let text = random_string(30);
+            let pattern = random_string(1 + text.len() % 4);
+            let expected = (0..=text.len() - pattern.len())
+                .filter(|shift| text[*shift..].starts_with(&pattern))
+                .collect::<Vec<usize>>();
+
+            for algorithm in StringAlgorithm::ALL {
+                let mut steps = vec![];
+                let matches = algorithm.search_fn()(&text, &pattern, &mut steps);
+                assert_eq!(matches, expected, "{}", algorithm.name());
+                assert_eq!(MatchState::after(&steps).matches, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn comparisons_are_inside_the_text() {
+        let text = chars("abracadabra");
+        for pattern in ["abra", "cad", "x", "abracadabra", "abracadabrab", ""] {
+            let pattern = chars(pattern);
+            for algorithm in StringAlgorithm::ALL {
+                let mut steps = vec![];
+                algorithm.search_fn()(&text, &pattern, &mut steps);
+
+                let mut shift = None;
+                for step in &steps {
+                    match step {
+                        MatchStep::Align(new_shift) => {
+                            assert!(new_shift + pattern.len() <= text.len());
+                            shift = Some(*new_shift);
+                        }
+                        MatchStep::Compare(text_index, pattern_index, matched) => {
+                            assert_eq!(shift.unwrap() + pattern_index, *text_index);
+                            assert_eq!(*matched, text[*text_index] == pattern[*pattern_index]);
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::MatchStep;
+
+/// Compares the pattern with the text at every shift from left to right, moving on at the first mismatch.\
+/// This takes *O(nm)* time for a text of length *n* and a pattern of length *m*.
+pub fn naive_search(text: &[char], pattern: &[char], steps: &mut Vec<MatchStep>) -> Vec<usize> {
+    let mut matches = vec![];
+    if pattern.is_empty() || pattern.len() > text.len() {
+        return matches;
+    }
+
+    for shift in 0..=text.len() - pattern.len() {
+        steps.push(MatchStep::Align(shift));
+        let mut matched = true;
+        for (i, character) in pattern.iter().enumerate() {
+            matched = text[shift + i] == *character;
+            steps.push(MatchStep::Compare(shift + i, i, matched));
+            if !matched {
+                break;
+            }
+        }
+        if matched {
+            steps.push(MatchStep::Match(shift));
+            matches.push(shift);
+        }
+    }
+    matches
+}
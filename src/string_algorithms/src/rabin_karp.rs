@@ -0,0 +1,90 @@
+use crate::MatchStep;
+
+/// The base of the polynomial hash of Rabin-Karp.
+pub const RABIN_KARP_BASE: u64 = 256;
+/// The modulus of the hash of Rabin-Karp.
+/// It's small so that the hashes are easy to read and spurious hits, where the hashes are equal but the characters aren't, happen now and then.
+pub const RABIN_KARP_MODULUS: u64 = 101;
+
+/// Compares the hash of the pattern with a rolling hash of the text under it, and only compares the characters when the hashes are equal.\
+/// Rolling the hash to the next shift takes *O(1)* time, so this takes *O(n + m)* time on average for a text of length *n*
+/// and a pattern of length *m*, but *O(nm)* time if every shift is a hit.
+pub fn rabin_karp_search(
+    text: &[char],
+    pattern: &[char],
+    steps: &mut Vec<MatchStep>,
+) -> Vec<usize> {
+    let mut matches = vec![];
+    if pattern.is_empty() || pattern.len() > text.len() {
+        return matches;
+    }
+
+    let pattern_hash = hash(pattern);
+    let mut window_hash = hash(&text[..pattern.len()]);
+    // The weight of the first character of the window, which is removed when the window rolls
+    let first_weight =
+        (1..pattern.len()).fold(1, |weight, _| weight * RABIN_KARP_BASE % RABIN_KARP_MODULUS);
+
+    for shift in 0..=text.len() - pattern.len() {
+        if shift > 0 {
+            let removed = text[shift - 1] as u64 % RABIN_KARP_MODULUS * first_weight;
+            let added = text[shift + pattern.len() - 1] as u64;
+            window_hash = ((window_hash + RABIN_KARP_MODULUS * RABIN_KARP_MODULUS - removed)
+                * RABIN_KARP_BASE
+                + added)
+                % RABIN_KARP_MODULUS;
+        }
+        steps.push(MatchStep::Align(shift));
+        steps.push(MatchStep::Hash(window_hash, pattern_hash));
+        if window_hash != pattern_hash {
+            continue;
+        }
+
+        // The hashes can be equal for different characters, so the hit has to be checked
+        let mut matched = true;
+        for (i, character) in pattern.iter().enumerate() {
+            matched = text[shift + i] == *character;
+            steps.push(MatchStep::Compare(shift + i, i, matched));
+            if !matched {
+                break;
+            }
+        }
+        if matched {
+            steps.push(MatchStep::Match(shift));
+            matches.push(shift);
+        }
+    }
+    matches
+}
+
+/// Hashes characters as the digits of a number in base [`RABIN_KARP_BASE`], modulo [`RABIN_KARP_MODULUS`].
+fn hash(characters: &[char]) -> u64 {
+    characters.iter().fold(0, |hash, character| {
+        (hash * RABIN_KARP_BASE + *character as u64) % RABIN_KARP_MODULUS
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolling_hash_equals_the_hash_of_the_window() {
+        let text: Vec<char> = "the quick brown fox jumps".chars().collect();
+        let pattern: Vec<char> = "brown".chars().collect();
+        let mut steps = vec![];
+        rabin_karp_search(&text, &pattern, &mut steps);
+
+        let mut shift = 0;
+        for step in steps {
+            match step {
+                MatchStep::Align(new_shift) => shift = new_shift,
+                MatchStep::Hash(window_hash, pattern_hash) => {
+                    assert_eq!(window_hash, hash(&text[shift..shift + pattern.len()]));
+                    assert_eq!(pattern_hash, hash(&pattern));
+                }
+                _ => (),
+            }
+        }
+    }
+}
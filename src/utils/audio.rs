@@ -2,6 +2,24 @@ use instant::Duration;
 use std::rc::Rc;
 use web_sys::{AudioContext, DynamicsCompressorNode, GainNode, OscillatorNode, OscillatorType};
 
+/// Maps a value between `min` and `max` linearly to a frequency between `min_frequency` and `max_frequency`.
+/// The `min` value maps to `min_frequency` and the `max` value maps to `max_frequency`.
+/// If `min` and `max` are equal, `min_frequency` is returned.
+pub fn value_to_frequency(
+    value: f32,
+    min: f32,
+    max: f32,
+    min_frequency: f32,
+    max_frequency: f32,
+) -> f32 {
+    let ratio = if max > min {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    min_frequency + (max_frequency - min_frequency) * ratio
+}
+
 /// Used for playing `Note`s.
 #[derive(Clone, PartialEq)]
 pub struct Synth {
@@ -26,12 +44,12 @@ impl Synth {
             notes: vec![],
         }
     }
-    /// Play `Note`s for a given duration.
-    pub fn play(&mut self, notes: Vec<Note>, duration: Duration) {
+    /// Play `Note`s for a given duration at a volume between 0.0 (silent) and 1.0.
+    pub fn play(&mut self, notes: Vec<Note>, duration: Duration, volume: f32) {
         for note in notes {
             note.gain
                 .gain()
-                .linear_ramp_to_value_at_time(1.0, self.ctx.current_time() + 0.05)
+                .linear_ramp_to_value_at_time(volume, self.ctx.current_time() + 0.05)
                 .unwrap();
 
             note.osc.connect_with_audio_node(&note.gain).unwrap();
@@ -88,3 +106,31 @@ impl Note {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequencies_are_interpolated_between_the_bounds() {
+        assert_eq!(value_to_frequency(1.0, 1.0, 101.0, 200.0, 1200.0), 200.0);
+        assert_eq!(value_to_frequency(51.0, 1.0, 101.0, 200.0, 1200.0), 700.0);
+        assert_eq!(value_to_frequency(101.0, 1.0, 101.0, 200.0, 1200.0), 1200.0);
+    }
+
+    #[test]
+    fn values_outside_the_range_are_clamped() {
+        // Below the smallest value
+        assert_eq!(value_to_frequency(0.0, 1.0, 101.0, 200.0, 1200.0), 200.0);
+        assert_eq!(value_to_frequency(-50.0, 1.0, 101.0, 200.0, 1200.0), 200.0);
+        // Above the largest value
+        assert_eq!(value_to_frequency(102.0, 1.0, 101.0, 200.0, 1200.0), 1200.0);
+        assert_eq!(value_to_frequency(1e9, 1.0, 101.0, 200.0, 1200.0), 1200.0);
+    }
+
+    #[test]
+    fn empty_range_gives_the_lowest_frequency() {
+        assert_eq!(value_to_frequency(5.0, 5.0, 5.0, 200.0, 1200.0), 200.0);
+        assert_eq!(value_to_frequency(5.0, 10.0, 1.0, 200.0, 1200.0), 200.0);
+    }
+}
@@ -0,0 +1,121 @@
+//! Timing the sorting algorithms over a sweep of input sizes, without visualizing them.
+use sorting::TotalF64;
+
+use super::input_distribution::InputDistribution;
+use crate::pages::sorting::SortingAlgorithm;
+
+/// Algorithms that are estimated to take more steps than this for an input size are skipped for it,
+/// since they would freeze the page and use a lot of memory for their steps.
+pub const MAX_BENCHMARK_STEPS: usize = 5_000_000;
+
+/// Gets up to `count` evenly spaced input sizes up to and including `max_len`.
+pub fn benchmark_sizes(max_len: usize, count: usize) -> Vec<usize> {
+    let mut sizes: Vec<usize> = (1..=count)
+        .map(|i| max_len * i / count)
+        .filter(|len| *len > 0)
+        .collect();
+    // There are fewer distinct sizes than `count` for small inputs
+    sizes.dedup();
+    sizes
+}
+
+/// Times every algorithm on a random input of every size, one measurement at a time so that the page can update in between.\
+/// All of the algorithms sort the same input for each size. The times include recording the steps,
+/// which takes a constant time per step, so it doesn't change how the times grow with the size.
+#[derive(Clone, PartialEq)]
+pub struct Benchmark {
+    pub algorithms: Vec<SortingAlgorithm>,
+    pub sizes: Vec<usize>,
+    /// The time in milliseconds that each algorithm took for each size measured so far,
+    /// or `None` if the algorithm was skipped for the size because of [`MAX_BENCHMARK_STEPS`].
+    pub times: Vec<Vec<Option<f64>>>,
+    seed: u64,
+    measured: usize,
+}
+impl Benchmark {
+    pub fn new(algorithms: Vec<SortingAlgorithm>, sizes: Vec<usize>, seed: u64) -> Self {
+        Self {
+            times: vec![vec![]; algorithms.len()],
+            algorithms,
+            sizes,
+            seed,
+            measured: 0,
+        }
+    }
+    /// Gets the number of measurements done so far.
+    pub fn measured(&self) -> usize {
+        self.measured
+    }
+    /// Gets the total number of measurements, one for every algorithm and size.
+    pub fn len(&self) -> usize {
+        self.algorithms.len() * self.sizes.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn is_done(&self) -> bool {
+        self.measured >= self.len()
+    }
+    /// Sorts the input of the next size with the next algorithm and records how long it took.
+    /// Returns `false` if every measurement has already been done.
+    pub fn measure_next(&mut self) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        // Every algorithm is measured for a size before moving on to the next one, so the chart grows from left to right
+        let index = self.measured % self.algorithms.len();
+        let len = self.sizes[self.measured / self.algorithms.len()];
+        let algorithm = &self.algorithms[index];
+
+        let time = if algorithm.complexity.estimate(len) > MAX_BENCHMARK_STEPS {
+            None
+        } else {
+            let mut input = InputDistribution::Random
+                .generate(len, 0.0, self.seed)
+                .into_iter()
+                .map(TotalF64::from)
+                .collect();
+            let duration = algorithm.sort(&mut input).duration.unwrap_or_default();
+            Some(duration.as_secs_f64() * 1000.0)
+        };
+        self.times[index].push(time);
+        self.measured += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::sorting::get_sorting_algorithms;
+
+    #[test]
+    fn sizes_are_evenly_spaced() {
+        assert_eq!(benchmark_sizes(1000, 4), [250, 500, 750, 1000]);
+        // Sizes that round down to nothing or to the same size are left out
+        assert_eq!(benchmark_sizes(2, 4), [1, 2]);
+    }
+
+    #[test]
+    fn measures_every_algorithm_for_every_size() {
+        let algorithms = get_sorting_algorithms();
+        let mut benchmark = Benchmark::new(
+            vec![
+                algorithms["bubble-sort"].clone(),
+                algorithms["merge-sort"].clone(),
+            ],
+            vec![10, 20, 5000],
+            7,
+        );
+        assert_eq!(benchmark.len(), 6);
+
+        while benchmark.measure_next() {}
+        assert!(benchmark.is_done());
+        assert_eq!(benchmark.measured(), 6);
+
+        // Bubble sort would take millions of steps for the largest input, so it's skipped
+        assert!(benchmark.times[0][..2].iter().all(Option::is_some));
+        assert_eq!(benchmark.times[0][2], None);
+        assert!(benchmark.times[1].iter().all(Option::is_some));
+    }
+}
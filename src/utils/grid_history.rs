@@ -0,0 +1,224 @@
+//! Undo and redo for the edits of the pathfinding grid and the graph that is searched on it.
+
+use std::collections::BTreeSet;
+
+use pathfinding::{
+    graph::{AdjacencyList, EditHistory, GraphEdit},
+    Coord,
+};
+
+/// The most edits of the grid that can be undone.
+pub const MAX_HISTORY_DEPTH: usize = 100;
+
+/// The walls, the mud and whether the cells are connected diagonally before and after an edit that changed them: `(before, after)`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CellsEdit {
+    pub walls: (BTreeSet<Coord>, BTreeSet<Coord>),
+    pub mud: (BTreeSet<Coord>, BTreeSet<Coord>),
+    pub move_diagonally: (bool, bool),
+}
+impl CellsEdit {
+    pub fn is_empty(&self) -> bool {
+        self.walls.0 == self.walls.1
+            && self.mud.0 == self.mud.1
+            && self.move_diagonally.0 == self.move_diagonally.1
+    }
+}
+
+/// Records the edits of the grid and the graph so that they can be undone and redone.
+///
+/// The graph is edited through an [`EditHistory`]. Edits like painting walls, generating a maze or moving diagonally also change the walls,
+/// the mud or the config that the graph is generated from, so those are kept along with the edits of the graph.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridHistory<E: Clone> {
+    graph: EditHistory<Coord, E>,
+    /// The cells that every edit that can be undone changed, in the order of the edits of the graph.
+    undo_cells: Vec<Option<CellsEdit>>,
+    /// The cells that every edit that can be redone changed, in the order of the undone edits of the graph.
+    redo_cells: Vec<Option<CellsEdit>>,
+    max_depth: usize,
+}
+impl<E: Clone> GridHistory<E> {
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            graph: EditHistory::with_max_depth(max_depth),
+            undo_cells: vec![],
+            redo_cells: vec![],
+            max_depth,
+        }
+    }
+    /// Applies an edit to the graph and records it along with the change of the `cells`, which has already been made.
+    /// Any previously undone edits can no longer be redone.
+    pub fn apply(
+        &mut self,
+        graph: &mut AdjacencyList<Coord, E>,
+        edit: GraphEdit<Coord, E>,
+        cells: Option<CellsEdit>,
+    ) {
+        self.graph.apply(graph, edit);
+        self.undo_cells.push(cells);
+        self.redo_cells.clear();
+        let forgotten = self.undo_cells.len().saturating_sub(self.max_depth);
+        self.undo_cells.drain(..forgotten);
+    }
+    /// Reverts the latest edit, restoring the walls, the mud and the diagonal moves from before it. Returns `false` if there is nothing to undo.
+    pub fn undo(
+        &mut self,
+        graph: &mut AdjacencyList<Coord, E>,
+        walls: &mut BTreeSet<Coord>,
+        mud: &mut BTreeSet<Coord>,
+        move_diagonally: &mut bool,
+    ) -> bool {
+        if self.graph.undo(graph).is_none() {
+            return false;
+        }
+        let cells = self.undo_cells.pop().flatten();
+        if let Some(CellsEdit {
+            walls: (walls_before, _),
+            mud: (mud_before, _),
+            move_diagonally: (move_diagonally_before, _),
+        }) = &cells
+        {
+            *walls = walls_before.clone();
+            *mud = mud_before.clone();
+            *move_diagonally = *move_diagonally_before;
+        }
+        self.redo_cells.push(cells);
+        true
+    }
+    /// Applies the latest undone edit again, along with its walls, mud and diagonal moves. Returns `false` if there is nothing to redo.
+    pub fn redo(
+        &mut self,
+        graph: &mut AdjacencyList<Coord, E>,
+        walls: &mut BTreeSet<Coord>,
+        mud: &mut BTreeSet<Coord>,
+        move_diagonally: &mut bool,
+    ) -> bool {
+        if self.graph.redo(graph).is_none() {
+            return false;
+        }
+        let cells = self.redo_cells.pop().flatten();
+        if let Some(CellsEdit {
+            walls: (_, walls_after),
+            mud: (_, mud_after),
+            move_diagonally: (_, move_diagonally_after),
+        }) = &cells
+        {
+            *walls = walls_after.clone();
+            *mud = mud_after.clone();
+            *move_diagonally = *move_diagonally_after;
+        }
+        self.undo_cells.push(cells);
+        true
+    }
+    pub fn can_undo(&self) -> bool {
+        self.graph.can_undo()
+    }
+    pub fn can_redo(&self) -> bool {
+        self.graph.can_redo()
+    }
+    /// Forgets every edit, like when the grid is resized and the old edits don't fit it anymore.
+    pub fn clear(&mut self) {
+        self.graph.clear();
+        self.undo_cells.clear();
+        self.redo_cells.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pathfinding::generate_graph;
+
+    #[test]
+    fn undo_and_redo_restore_cells_and_graph() {
+        let mut walls = BTreeSet::new();
+        let mut mud = BTreeSet::new();
+        let mut move_diagonally = false;
+        let mut graph = generate_graph::<f32>(4, 4, false, &walls);
+        let original = graph.clone();
+        let mut history = GridHistory::new(2);
+
+        // Painting a wall regenerates the graph from the new walls
+        let walls_before = walls.clone();
+        walls.insert(Coord::new(1, 1));
+        let painted = generate_graph(4, 4, false, &walls);
+        history.apply(
+            &mut graph,
+            GraphEdit::Replace(painted.clone()),
+            Some(CellsEdit {
+                walls: (walls_before, walls.clone()),
+                mud: (mud.clone(), mud.clone()),
+                move_diagonally: (false, false),
+            }),
+        );
+        // Editing the graph doesn't touch the cells
+        history.apply(
+            &mut graph,
+            GraphEdit::AddEdgeUndirected(Coord::new(0, 0), Coord::new(3, 3), 2.0),
+            None,
+        );
+
+        assert!(history.undo(&mut graph, &mut walls, &mut mud, &mut move_diagonally));
+        assert_eq!(graph, painted);
+        assert!(walls.contains(&Coord::new(1, 1)));
+        assert!(history.undo(&mut graph, &mut walls, &mut mud, &mut move_diagonally));
+        assert_eq!(graph, original);
+        assert!(walls.is_empty());
+        assert!(!history.undo(&mut graph, &mut walls, &mut mud, &mut move_diagonally));
+
+        assert!(history.redo(&mut graph, &mut walls, &mut mud, &mut move_diagonally));
+        assert_eq!(graph, painted);
+        assert!(walls.contains(&Coord::new(1, 1)));
+
+        // A new edit can't be redone past, and only the latest edits are kept
+        for x in 0..3 {
+            history.apply(&mut graph, GraphEdit::AddVertex(Coord::new(x, 9)), None);
+        }
+        assert!(!history.can_redo());
+        assert!(history.undo(&mut graph, &mut walls, &mut mud, &mut move_diagonally));
+        assert!(history.undo(&mut graph, &mut walls, &mut mud, &mut move_diagonally));
+        assert!(!history.undo(&mut graph, &mut walls, &mut mud, &mut move_diagonally));
+        assert!(walls.contains(&Coord::new(1, 1)));
+    }
+
+    #[test]
+    fn undo_and_redo_restore_diagonal_moves() {
+        let walls = BTreeSet::new();
+        let mud = BTreeSet::new();
+        let (mut undone_walls, mut undone_mud) = (walls.clone(), mud.clone());
+        let mut move_diagonally = true;
+        let mut graph = generate_graph::<f32>(3, 3, false, &walls);
+        let mut history = GridHistory::new(2);
+
+        // Moving diagonally connects the cells anew, without touching the walls or the mud
+        let diagonal = generate_graph(3, 3, true, &walls);
+        history.apply(
+            &mut graph,
+            GraphEdit::Replace(diagonal.clone()),
+            Some(CellsEdit {
+                walls: (walls.clone(), walls.clone()),
+                mud: (mud.clone(), mud.clone()),
+                move_diagonally: (false, true),
+            }),
+        );
+
+        assert!(history.undo(
+            &mut graph,
+            &mut undone_walls,
+            &mut undone_mud,
+            &mut move_diagonally
+        ));
+        assert_eq!(graph, generate_graph(3, 3, false, &walls));
+        assert!(!move_diagonally);
+        assert!(history.redo(
+            &mut graph,
+            &mut undone_walls,
+            &mut undone_mud,
+            &mut move_diagonally
+        ));
+        assert_eq!(graph, diagonal);
+        assert!(move_diagonally);
+        assert_eq!((undone_walls, undone_mud), (walls, mud));
+    }
+}
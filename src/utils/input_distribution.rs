@@ -0,0 +1,121 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::{gen_presorted_u32_vec, gen_u32_vec};
+
+/// How many different values a [`InputDistribution::FewUnique`] input has.
+const FEW_UNIQUE_VALUES: usize = 5;
+/// How many rising ramps a [`InputDistribution::Sawtooth`] input has.
+const SAWTOOTH_TEETH: usize = 4;
+
+/// The shape of a generated sorting input. The algorithms behave very differently on some of them,
+/// e.g. insertion sort is fast on nearly sorted inputs and a naive quicksort is slow on inputs with few unique values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputDistribution {
+    /// The numbers from 1 to the input length, shuffled according to the presortedness.
+    #[default]
+    Random,
+    /// The numbers from 1 to the input length in order, except for a few swapped neighbours.
+    NearlySorted,
+    /// The numbers from the input length down to 1.
+    Reversed,
+    /// Random numbers that only have a few different values.
+    FewUnique,
+    /// A few ramps that each rise from low to high numbers.
+    Sawtooth,
+}
+
+impl InputDistribution {
+    pub const ALL: [InputDistribution; 5] = [
+        InputDistribution::Random,
+        InputDistribution::NearlySorted,
+        InputDistribution::Reversed,
+        InputDistribution::FewUnique,
+        InputDistribution::Sawtooth,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputDistribution::Random => "Random",
+            InputDistribution::NearlySorted => "Nearly sorted",
+            InputDistribution::Reversed => "Reversed",
+            InputDistribution::FewUnique => "Few unique values",
+            InputDistribution::Sawtooth => "Sawtooth",
+        }
+    }
+
+    /// Generates `len` numbers between 1 and `len` with this distribution. The `presortedness` is only used by [`InputDistribution::Random`].\
+    /// The same `seed` always produces the same vector.
+    pub fn generate(&self, len: usize, presortedness: f64, seed: u64) -> Vec<u32> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        match self {
+            InputDistribution::Random => gen_presorted_u32_vec(len, presortedness, seed),
+            InputDistribution::NearlySorted => {
+                let mut items = gen_u32_vec(len);
+                // One swap for every 20 numbers
+                for _ in 0..len / 20 {
+                    let i = rng.gen_range(0..len - 1);
+                    items.swap(i, i + 1);
+                }
+                items
+            }
+            InputDistribution::Reversed => (1..=len as u32).rev().collect(),
+            InputDistribution::FewUnique => (0..len)
+                .map(|_| {
+                    let level = rng.gen_range(1..=FEW_UNIQUE_VALUES);
+                    // Spread the values evenly between 1 and `len`
+                    (level * len).div_ceil(FEW_UNIQUE_VALUES).max(1) as u32
+                })
+                .collect(),
+            InputDistribution::Sawtooth => {
+                let tooth_len = len.div_ceil(SAWTOOTH_TEETH).max(1);
+                (0..len)
+                    .map(|i| ((i % tooth_len + 1) * len / tooth_len).max(1) as u32)
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_stay_between_one_and_the_length() {
+        for distribution in InputDistribution::ALL {
+            for len in [0, 1, 2, 3, 10, 101] {
+                let items = distribution.generate(len, 0.0, 7);
+                assert_eq!(items.len(), len, "{:?}", distribution);
+                assert!(
+                    items.iter().all(|item| (1..=len as u32).contains(item)),
+                    "{:?} {:?}",
+                    distribution,
+                    items
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn distributions_have_their_shape() {
+        let nearly_sorted = InputDistribution::NearlySorted.generate(100, 0.0, 1);
+        let descents = |items: &[u32]| items.windows(2).filter(|pair| pair[0] > pair[1]).count();
+        assert!((1..=5).contains(&descents(&nearly_sorted)));
+
+        assert_eq!(
+            InputDistribution::Reversed.generate(4, 0.0, 1),
+            [4, 3, 2, 1]
+        );
+
+        let mut few_unique = InputDistribution::FewUnique.generate(100, 0.0, 1);
+        few_unique.sort_unstable();
+        few_unique.dedup();
+        assert!(few_unique.len() <= FEW_UNIQUE_VALUES);
+
+        let sawtooth = InputDistribution::Sawtooth.generate(100, 0.0, 1);
+        assert_eq!(descents(&sawtooth), SAWTOOTH_TEETH - 1);
+        assert_eq!(&sawtooth[..3], [4, 8, 12]);
+    }
+}
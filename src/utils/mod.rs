@@ -1,6 +1,19 @@
 pub mod audio;
+pub mod benchmark;
+pub mod grid_history;
+pub mod input_distribution;
+pub mod palette;
+pub mod sort_input;
+pub mod sort_recording;
+pub mod sort_renderer;
+pub mod sort_visualization;
+pub mod wall_painting;
 
-use rand::{thread_rng, Rng};
+use std::{error::Error, fmt};
+
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
 
 /// Generate a sorted `Vec<u32>` with a given length.
 /// The numbers start from 1, and are in order, e.g. 1, 2, 3...
@@ -8,6 +21,52 @@ pub fn gen_u32_vec(len: usize) -> Vec<u32> {
     (1..=len as u32).collect::<Vec<u32>>()
 }
 
+/// Generate a `Vec<u32>` with the numbers from 1 to `len` in an order controlled by `presortedness`.
+///
+/// The numbers are shuffled with a partial Fisher-Yates shuffle, aka Knuth shuffle, that stops early based on `presortedness`.
+/// At 0.0 the whole vector is shuffled uniformly at random, at 1.0 it stays sorted, and values in between interpolate between the two.
+/// The same `seed` always produces the same vector.
+pub fn gen_presorted_u32_vec(len: usize, presortedness: f64, seed: u64) -> Vec<u32> {
+    let mut items = gen_u32_vec(len);
+    partial_shuffle(&mut items, presortedness, &mut StdRng::seed_from_u64(seed));
+    items
+}
+
+/// Generate a `Vec<i32>` with `len` numbers between `min` and `max` in an order controlled by `presortedness`.
+///
+/// The numbers are picked at random and sorted, and then shuffled like in [`gen_presorted_u32_vec`]:
+/// at 0.0 they're in a uniformly random order, at 1.0 they stay sorted, and values in between interpolate between the two.
+/// The same `seed` always produces the same vector.
+pub fn gen_i32_vec_presortedness(
+    len: usize,
+    min: i32,
+    max: i32,
+    presortedness: f64,
+    seed: u64,
+) -> Vec<i32> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+    let mut items = (0..len)
+        .map(|_| rng.gen_range(min..=max))
+        .collect::<Vec<i32>>();
+    items.sort_unstable();
+    partial_shuffle(&mut items, presortedness, &mut rng);
+    items
+}
+
+/// Shuffle the end of `items` with a Fisher-Yates shuffle that stops early based on `presortedness`,
+/// from a full shuffle at 0.0 to no swaps at all at 1.0.
+fn partial_shuffle<T>(items: &mut [T], presortedness: f64, rng: &mut StdRng) {
+    let len = items.len();
+    let swap_count =
+        ((1.0 - presortedness.clamp(0.0, 1.0)) * len.saturating_sub(1) as f64).round() as usize;
+
+    for curr_i in (1..len).rev().take(swap_count) {
+        let rand_i = rng.gen_range(0..=curr_i);
+        items.swap(curr_i, rand_i);
+    }
+}
+
 /// Shuffle a vector with the Fisher-Yates shuffle, aka Knuth shuffle.
 pub fn knuth_shuffle<T>(mut items: Vec<T>) -> Vec<T> {
     let mut curr_i = items.len();
@@ -23,6 +82,137 @@ pub fn knuth_shuffle<T>(mut items: Vec<T>) -> Vec<T> {
     items
 }
 
+/// Generate a permutation of the numbers from 0 to `len - 1` with `target_inversions` inversions,
+/// i.e. pairs of numbers where the bigger one comes first, as counted by [`sorting::count_inversions`].
+///
+/// The count is exact, so there's no tolerance, except that a target above the `len * (len - 1) / 2` inversions of a descending permutation
+/// is clamped to it. The permutation is built from a random inversion table, where the number at each index is chosen by how many of
+/// the smaller numbers still remaining come after it. The same `seed` always produces the same vector.
+pub fn gen_with_inversions(len: usize, target_inversions: usize, seed: u64) -> Vec<u32> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut remaining_inversions = target_inversions.min(len * len.saturating_sub(1) / 2);
+    let mut remaining_numbers = (0..len as u32).collect::<Vec<u32>>();
+
+    (0..len)
+        .map(|i| {
+            // The most inversions that the numbers after this one can still have between themselves
+            let later_inversions = (len - 1 - i) * len.saturating_sub(2 + i) / 2;
+            let min = remaining_inversions.saturating_sub(later_inversions);
+            let max = remaining_inversions.min(len - 1 - i);
+            let inversions = rng.gen_range(min..=max);
+
+            remaining_inversions -= inversions;
+            remaining_numbers.remove(inversions)
+        })
+        .collect()
+}
+
+/// Generate a `Vec<f64>` with `len` numbers picked uniformly at random between `min` and `max`.
+/// The same `seed` always produces the same vector.
+pub fn gen_f64_vec(len: usize, min: f64, max: f64, seed: u64) -> Vec<f64> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+    (0..len)
+        .map(|_| min + rng.gen::<f64>() * (max - min))
+        .collect()
+}
+
+/// Generate a "mountain" `Vec<f64>` with `len` numbers that rise evenly from `min` to `max` at the middle and fall back evenly towards `min`.
+/// If `max > min`, the numbers strictly increase up to the peak and strictly decrease after it.
+pub fn gen_mountain(len: usize, min: f64, max: f64) -> Vec<f64> {
+    let peak = len / 2;
+    (0..len)
+        .map(|i| {
+            // How far up the mountain `i` is, from 0.0 at the foot to 1.0 at the peak
+            let height = if i <= peak {
+                if peak == 0 {
+                    1.0
+                } else {
+                    i as f64 / peak as f64
+                }
+            } else {
+                (len - i) as f64 / (len - peak) as f64
+            };
+            min + height * (max - min)
+        })
+        .collect()
+}
+
+/// Shuffle a slice in place with a Fisher-Yates shuffle, aka Knuth shuffle.
+/// Unlike generating a new input, this keeps the same values, and the same `seed` always produces the same order.
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for curr_i in (1..items.len()).rev() {
+        let rand_i = rng.gen_range(0..=curr_i);
+        items.swap(curr_i, rand_i);
+    }
+}
+
+/// Get the current time in milliseconds with sub-millisecond precision where the platform supports it.
+/// Only differences between two calls are meaningful.
+#[cfg(target_arch = "wasm32")]
+pub fn now_ms() -> f64 {
+    // `std::time::Instant` isn't supported on WASM, so use the browser's high resolution timer
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or(0.0, |performance| performance.now())
+}
+
+/// Get the current time in milliseconds with sub-millisecond precision where the platform supports it.
+/// Only differences between two calls are meaningful.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now_ms() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |duration| duration.as_secs_f64() * 1000.0)
+}
+
+/// Scale the playback time of `steps` steps so that each step plays as long as it would if `reference_steps` steps took `playback_time` seconds.\
+/// Algorithms that take more steps then take longer to play, instead of every algorithm finishing in the same time.
+pub fn proportional_playback_time(playback_time: f32, steps: usize, reference_steps: usize) -> f32 {
+    playback_time * steps as f32 / reference_steps.max(1) as f32
+}
+
+/// Format a duration given in milliseconds for display, e.g. `"0.25 ms"`, `"12.3 ms"` or `"1.23 s"`.
+pub fn format_duration_ms(ms: f64) -> String {
+    if ms < 1.0 {
+        format!("{:.2} ms", ms)
+    } else if ms < 1000.0 {
+        format!("{:.1} ms", ms)
+    } else {
+        format!("{:.2} s", ms / 1000.0)
+    }
+}
+
+/// Parse a list of whole numbers separated by commas and/or whitespace, e.g. `"3, -1 2,"`.
+/// Empty tokens, like the one created by a trailing comma, are ignored.
+pub fn parse_i32_list(input: &str) -> Result<Vec<i32>, ParseError> {
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token.parse::<i32>().map_err(|_| ParseError {
+                token: token.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// An error returned by [`parse_i32_list`] when a token isn't a valid number.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub token: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" is not a valid number", self.token)
+    }
+}
+
+impl Error for ParseError {}
+
 /// Fetch from a given url.
 pub async fn fetch(url: String, content_type: &str) -> Result<String, String> {
     let resp = reqwest::get(url.to_string()).await;
@@ -38,3 +228,212 @@ pub async fn fetch(url: String, content_type: &str) -> Result<String, String> {
         Err(_) => format!("Could not get {}", url),
     })
 }
+
+/// Download a file with the given name, contents and MIME type by clicking a temporary link to it.
+pub fn download_file(name: &str, contents: &str, mime_type: &str) {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let mut options = BlobPropertyBag::new();
+    options.type_(mime_type);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options).unwrap();
+    let url = Url::create_object_url_with_blob(&blob).unwrap();
+
+    let window = web_sys::window().unwrap();
+    let link: HtmlAnchorElement = window
+        .document()
+        .unwrap()
+        .create_element("a")
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+    link.set_href(&url);
+    link.set_download(name);
+    link.click();
+
+    // The browser may not have started the download when `click` returns, so the URL is only revoked on the next tick
+    let revoke = Closure::once_into_js(move || {
+        Url::revoke_object_url(&url).ok();
+    });
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), 0)
+        .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sorting::count_inversions;
+
+    #[test]
+    fn presortedness_extremes_and_middle() {
+        let len = 200;
+        let max_inversions = len * (len - 1) / 2;
+
+        assert_eq!(gen_presorted_u32_vec(len, 1.0, 3), gen_u32_vec(len));
+
+        for seed in 0..5 {
+            let shuffled = gen_presorted_u32_vec(len, 0.0, seed);
+            let halfway = gen_presorted_u32_vec(len, 0.5, seed);
+
+            let mut sorted = shuffled.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, gen_u32_vec(len));
+
+            // A uniform shuffle has about half of the possible inversions on average
+            let shuffled_inversions = count_inversions(&shuffled);
+            assert!(shuffled_inversions > max_inversions / 3);
+            assert!(shuffled_inversions < max_inversions * 2 / 3);
+
+            let halfway_inversions = count_inversions(&halfway);
+            assert!(halfway_inversions > 0);
+            assert!(halfway_inversions < shuffled_inversions);
+        }
+    }
+
+    #[test]
+    fn i32_presortedness_extremes_and_middle() {
+        let len = 200;
+        let max_inversions = len * (len - 1) / 2;
+
+        let sorted = gen_i32_vec_presortedness(len, -500, 500, 1.0, 3);
+        assert_eq!(sorted.len(), len);
+        assert!(sorted.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(sorted.iter().all(|value| (-500..=500).contains(value)));
+
+        for seed in 0..5 {
+            let shuffled = gen_i32_vec_presortedness(len, -500, 500, 0.0, seed);
+            let halfway = gen_i32_vec_presortedness(len, -500, 500, 0.5, seed);
+
+            let shuffled_inversions = count_inversions(&shuffled);
+            assert!(shuffled_inversions > max_inversions / 3);
+            assert!(shuffled_inversions < max_inversions * 2 / 3);
+
+            let halfway_inversions = count_inversions(&halfway);
+            assert!(halfway_inversions > 0);
+            assert!(halfway_inversions < shuffled_inversions);
+        }
+    }
+
+    #[test]
+    fn inversions_hit_the_target() {
+        for len in [0_usize, 1, 2, 7, 100] {
+            let max_inversions = len * len.saturating_sub(1) / 2;
+            for target in [0, 1, max_inversions / 3, max_inversions, max_inversions + 5] {
+                let items = gen_with_inversions(len, target, target as u64);
+
+                let mut sorted = items.clone();
+                sorted.sort_unstable();
+                assert_eq!(sorted, (0..len as u32).collect::<Vec<u32>>());
+                assert_eq!(count_inversions(&items), target.min(max_inversions));
+            }
+        }
+    }
+
+    #[test]
+    fn f64_vec_stays_in_range() {
+        let items = gen_f64_vec(100, 2.5, 7.5, 1);
+        assert_eq!(items.len(), 100);
+        assert!(items.iter().all(|item| (2.5..=7.5).contains(item)));
+        assert_eq!(items, gen_f64_vec(100, 2.5, 7.5, 1));
+
+        // The range can be given in either order
+        assert!(gen_f64_vec(100, 7.5, 2.5, 1)
+            .iter()
+            .all(|item| (2.5..=7.5).contains(item)));
+    }
+
+    #[test]
+    fn mountain_rises_to_peak_and_falls() {
+        for len in [2, 3, 10, 11] {
+            let items = gen_mountain(len, 1.0, 50.0);
+            let peak = items
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(i, _)| i)
+                .unwrap();
+
+            assert_eq!(items.len(), len);
+            assert_eq!((items[0], items[peak]), (1.0, 50.0));
+            assert!(items[..=peak].windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(items[peak..].windows(2).all(|pair| pair[0] > pair[1]));
+            assert!(items.iter().all(|item| (1.0..=50.0).contains(item)));
+        }
+
+        assert_eq!(gen_mountain(1, 1.0, 50.0), vec![50.0]);
+        assert!(gen_mountain(0, 1.0, 50.0).is_empty());
+    }
+
+    #[test]
+    fn shuffle_is_seeded_permutation() {
+        let items = vec![5, 3, 3, 9, 1, 7, 2, 8, 2, 6];
+
+        for seed in 0..5 {
+            let mut shuffled = items.clone();
+            shuffle(&mut shuffled, seed);
+
+            let mut sorted = shuffled.clone();
+            sorted.sort_unstable();
+            let mut expected = items.clone();
+            expected.sort_unstable();
+            assert_eq!(sorted, expected);
+
+            let mut again = items.clone();
+            shuffle(&mut again, seed);
+            assert_eq!(again, shuffled);
+        }
+
+        let mut empty: Vec<u32> = vec![];
+        shuffle(&mut empty, 0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn proportional_playback_time_scales_with_steps() {
+        // 10 seconds for 100 steps is 0.1 seconds per step however many steps there are
+        for steps in [0, 1, 100, 250, 10_000] {
+            let time = proportional_playback_time(10.0, steps, 100);
+            assert!((time - steps as f32 * 0.1).abs() < 1e-3);
+        }
+        // Twice the steps takes twice as long
+        let (fast, slow) = (
+            proportional_playback_time(10.0, 664, 664),
+            proportional_playback_time(10.0, 1328, 664),
+        );
+        assert_eq!((fast, slow), (10.0, 20.0));
+        assert_eq!(proportional_playback_time(10.0, 5, 0), 50.0);
+    }
+
+    #[test]
+    fn format_durations() {
+        assert_eq!(format_duration_ms(1234.5), "1.23 s");
+        assert_eq!(format_duration_ms(999.94), "999.9 ms");
+        assert_eq!(format_duration_ms(12.345), "12.3 ms");
+        assert_eq!(format_duration_ms(0.25), "0.25 ms");
+        assert_eq!(format_duration_ms(0.0), "0.00 ms");
+    }
+
+    #[test]
+    fn parse_valid_lists() {
+        assert_eq!(parse_i32_list("3, 1, 2"), Ok(vec![3, 1, 2]));
+        assert_eq!(parse_i32_list("3 1\t2\n10"), Ok(vec![3, 1, 2, 10]));
+        assert_eq!(parse_i32_list("-3, 0, -12"), Ok(vec![-3, 0, -12]));
+        assert_eq!(parse_i32_list(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn parse_ignores_trailing_and_repeated_commas() {
+        assert_eq!(parse_i32_list("3, 1 2,"), Ok(vec![3, 1, 2]));
+        assert_eq!(parse_i32_list(",,4,,-5,"), Ok(vec![4, -5]));
+    }
+
+    #[test]
+    fn parse_rejects_non_numeric_tokens() {
+        let error = ParseError {
+            token: "two".to_string(),
+        };
+        assert_eq!(parse_i32_list("1, two, 3"), Err(error.clone()));
+        assert_eq!(error.to_string(), "\"two\" is not a valid number");
+        assert!(parse_i32_list("1, - 2").is_err());
+        assert!(parse_i32_list("1.5").is_err());
+    }
+}
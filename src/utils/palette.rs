@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use crate::hooks::use_color_scheme::ColorScheme;
+
+/// The colors used for the bars of the sorting visualization.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Palette {
+    /// Blue highlights on green bars.
+    #[default]
+    Default,
+    /// Orange highlights on blue bars from the Okabe-Ito palette, which stay distinguishable with all common types of color blindness.
+    ColorBlindSafe,
+    /// Shades of gray, where the highlights have the highest contrast against the background.
+    Monochrome,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 3] = [
+        Palette::Default,
+        Palette::ColorBlindSafe,
+        Palette::Monochrome,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Palette::Default => "Default",
+            Palette::ColorBlindSafe => "Color-blind safe",
+            Palette::Monochrome => "Monochrome",
+        }
+    }
+
+    /// Gets the color of the bars that are compared, swapped or set in the current step.
+    ///
+    /// | Palette          | Light     | Dark      |
+    /// |------------------|-----------|-----------|
+    /// | `Default`        | `#059ada` | `#00aaff` |
+    /// | `ColorBlindSafe` | `#d55e00` | `#e69f00` |
+    /// | `Monochrome`     | `#111111` | `#ffffff` |
+    pub fn highlight_color(&self, color_scheme: ColorScheme) -> &'static str {
+        match (self, color_scheme) {
+            (Palette::Default, ColorScheme::Light) => "#059ada",
+            (Palette::Default, ColorScheme::Dark) => "#00aaff",
+            (Palette::ColorBlindSafe, ColorScheme::Light) => "#d55e00",
+            (Palette::ColorBlindSafe, ColorScheme::Dark) => "#e69f00",
+            (Palette::Monochrome, ColorScheme::Light) => "#111111",
+            (Palette::Monochrome, ColorScheme::Dark) => "#ffffff",
+        }
+    }
+
+    /// Gets the color of the bars that aren't touched in the current step.
+    ///
+    /// | Palette          | Light     | Dark      |
+    /// |------------------|-----------|-----------|
+    /// | `Default`        | `#7abc05` | `#adff2f` |
+    /// | `ColorBlindSafe` | `#0072b2` | `#56b4e9` |
+    /// | `Monochrome`     | `#aaaaaa` | `#777777` |
+    pub fn base_color(&self, color_scheme: ColorScheme) -> &'static str {
+        match (self, color_scheme) {
+            (Palette::Default, ColorScheme::Light) => "#7abc05",
+            (Palette::Default, ColorScheme::Dark) => "#adff2f",
+            (Palette::ColorBlindSafe, ColorScheme::Light) => "#0072b2",
+            (Palette::ColorBlindSafe, ColorScheme::Dark) => "#56b4e9",
+            (Palette::Monochrome, ColorScheme::Light) => "#aaaaaa",
+            (Palette::Monochrome, ColorScheme::Dark) => "#777777",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documented_palette_colors() {
+        let expected = [
+            (
+                Palette::Default,
+                ["#059ada", "#7abc05", "#00aaff", "#adff2f"],
+            ),
+            (
+                Palette::ColorBlindSafe,
+                ["#d55e00", "#0072b2", "#e69f00", "#56b4e9"],
+            ),
+            (
+                Palette::Monochrome,
+                ["#111111", "#aaaaaa", "#ffffff", "#777777"],
+            ),
+        ];
+
+        for (palette, [light_highlight, light_base, dark_highlight, dark_base]) in expected {
+            assert_eq!(palette.highlight_color(ColorScheme::Light), light_highlight);
+            assert_eq!(palette.base_color(ColorScheme::Light), light_base);
+            assert_eq!(palette.highlight_color(ColorScheme::Dark), dark_highlight);
+            assert_eq!(palette.base_color(ColorScheme::Dark), dark_base);
+        }
+    }
+
+    #[test]
+    fn highlights_stand_out_from_base() {
+        for palette in Palette::ALL {
+            for color_scheme in [ColorScheme::Light, ColorScheme::Dark] {
+                assert_ne!(
+                    palette.highlight_color(color_scheme),
+                    palette.base_color(color_scheme),
+                    "{:?}",
+                    palette
+                );
+            }
+        }
+
+        let highlights = Palette::ALL.map(|palette| palette.highlight_color(ColorScheme::Light));
+        assert!(highlights
+            .iter()
+            .enumerate()
+            .all(|(i, color)| !highlights[i + 1..].contains(color)));
+    }
+}
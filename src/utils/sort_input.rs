@@ -0,0 +1,72 @@
+//! The input of the sorting page, kept apart from the copy that the sort works on.
+
+use std::rc::Rc;
+
+use sorting::count_duplicates;
+
+/// The input of a sort and the working copy that the sort mutates.
+///
+/// Sorting never changes the original, so the sort can always be replayed from the exact same array with [`SortInput::reset`],
+/// even after the working copy has been fully sorted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SortInput<T: Clone> {
+    original: Rc<Vec<T>>,
+    working: Vec<T>,
+    /// Counted once for the input instead of every time that it's shown.
+    distinct_values: usize,
+}
+impl<T: Clone + Copy + Ord> SortInput<T> {
+    pub fn new(original: Vec<T>) -> Self {
+        Self {
+            working: original.clone(),
+            distinct_values: original.len() - count_duplicates(&original),
+            original: Rc::new(original),
+        }
+    }
+}
+impl<T: Clone> SortInput<T> {
+    pub fn original(&self) -> &[T] {
+        &self.original
+    }
+    /// Gets the original without copying it, e.g. for passing it to a component.
+    pub fn shared_original(&self) -> Rc<Vec<T>> {
+        Rc::clone(&self.original)
+    }
+    pub fn distinct_values(&self) -> usize {
+        self.distinct_values
+    }
+    /// Gets the working copy for sorting it. The original is left untouched.
+    pub fn working_mut(&mut self) -> &mut Vec<T> {
+        &mut self.working
+    }
+    /// Restores the working copy to the original input.
+    pub fn reset(&mut self) {
+        self.working.clone_from(&*self.original);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sorting::{bubble_sort, SortCommand};
+
+    #[test]
+    fn reset_restores_original_after_sort() {
+        let generated = vec![5, 3, 8, 1, 9, 2];
+        let mut input = SortInput::new(generated.clone());
+
+        let mut steps: Vec<Vec<SortCommand<i32>>> = vec![];
+        bubble_sort(input.working_mut(), &mut steps);
+        assert_eq!(*input.working_mut(), vec![1, 2, 3, 5, 8, 9]);
+        assert_eq!(input.original(), &generated[..]);
+        assert_eq!(input.distinct_values(), 6);
+
+        input.reset();
+        assert_eq!(*input.working_mut(), generated);
+
+        // Sorting again from the reset input takes the exact same steps
+        let mut replayed_steps = vec![];
+        bubble_sort(input.working_mut(), &mut replayed_steps);
+        assert_eq!(replayed_steps, steps);
+    }
+}
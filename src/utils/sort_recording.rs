@@ -0,0 +1,163 @@
+//! Exporting a finished sort as JSON and importing it again to replay it exactly.
+use std::{error::Error, fmt};
+
+use instant::Duration;
+use serde::{Deserialize, Serialize};
+use sorting::{SortCommand, SortStats, TotalF64};
+
+use crate::pages::sorting::SortConfig;
+
+/// A finished sort with everything that's needed to replay it exactly, so interesting cases can be shared
+/// and recorded steps can be compared against in tests.\
+/// The steps are replayed as they were recorded, so the algorithms don't run again when a recording is imported.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct SortRecording {
+    pub config: SortConfig,
+    pub input: Vec<TotalF64>,
+    /// The steps of the algorithm, followed by the steps of the algorithms racing it in the order of [`SortConfig::race_algorithms`].
+    pub steps: Vec<Vec<Vec<SortCommand<TotalF64>>>>,
+    /// The work that the algorithm did.
+    pub stats: SortStats,
+    /// How long the sorting took when the run was recorded.
+    pub duration: Duration,
+}
+impl SortRecording {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("sort recordings only have serializable fields")
+    }
+    /// Parses a recording and checks that its steps can be played on its input.
+    pub fn from_json(json: &str) -> Result<Self, ImportRecordingError> {
+        let recording: Self = serde_json::from_str(json)
+            .map_err(|error| ImportRecordingError::Json(error.to_string()))?;
+
+        let algorithms = 1 + recording.config.race_algorithms.len();
+        if recording.steps.len() != algorithms {
+            return Err(ImportRecordingError::StepCount {
+                algorithms,
+                steps: recording.steps.len(),
+            });
+        }
+        let len = recording.input.len();
+        for (algorithm, steps) in recording.steps.iter().enumerate() {
+            for (step, command) in steps
+                .iter()
+                .enumerate()
+                .flat_map(|(step, commands)| commands.iter().map(move |command| (step, command)))
+            {
+                let in_bounds = match command {
+                    SortCommand::Swap(from, to) => *from < len && *to < len,
+                    SortCommand::Set(index, _) => *index < len,
+                    SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => true,
+                };
+                if !in_bounds {
+                    return Err(ImportRecordingError::IndexOutOfBounds { algorithm, step });
+                }
+            }
+        }
+
+        Ok(recording)
+    }
+}
+
+/// An error returned by [`SortRecording::from_json`] when the recording can't be replayed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportRecordingError {
+    /// The file isn't a recording, e.g. because it isn't JSON or it has an unknown algorithm.
+    Json(String),
+    /// There isn't a list of steps for every algorithm.
+    StepCount { algorithms: usize, steps: usize },
+    /// A step of the algorithm at the index uses an index outside of the input.
+    IndexOutOfBounds { algorithm: usize, step: usize },
+}
+
+impl fmt::Display for ImportRecordingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportRecordingError::Json(error) => write!(f, "not a sorting run: {}", error),
+            ImportRecordingError::StepCount { algorithms, steps } => write!(
+                f,
+                "the run has {} algorithms but steps for {}",
+                algorithms, steps
+            ),
+            ImportRecordingError::IndexOutOfBounds { algorithm, step } => write!(
+                f,
+                "step {} of algorithm {} is outside of the input",
+                step + 1,
+                algorithm + 1
+            ),
+        }
+    }
+}
+
+impl Error for ImportRecordingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::sorting::get_sorting_algorithms;
+
+    fn recording() -> SortRecording {
+        let algorithms = get_sorting_algorithms();
+        let config = SortConfig {
+            sorting_algorithm: algorithms["heapsort"].clone(),
+            race_algorithms: vec![algorithms["merge-sort"].clone()],
+            ..SortConfig::default()
+        };
+        let input = config.gen_input();
+
+        let results = [&config.sorting_algorithm, &config.race_algorithms[0]]
+            .iter()
+            .map(|algorithm| algorithm.sort(&mut input.clone()))
+            .collect::<Vec<_>>();
+        SortRecording {
+            stats: SortStats::new(&results[0].steps, results[0].comparisons.unwrap()),
+            duration: results[0].duration.unwrap(),
+            steps: results.into_iter().map(|result| result.steps).collect(),
+            config,
+            input,
+        }
+    }
+
+    #[test]
+    fn json_round_trip_replays_the_same_steps() {
+        let recording = recording();
+        let imported = SortRecording::from_json(&recording.to_json()).unwrap();
+        assert!(imported == recording);
+        assert_eq!(imported.config.sorting_algorithm.name, "Heapsort");
+
+        let mut replayed = imported.input.clone();
+        sorting::run_sort_steps(&mut replayed, &imported.steps[0]);
+        assert!(replayed.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn broken_recordings_are_rejected() {
+        let mut recording = recording();
+        let json = recording.to_json();
+
+        let unknown = json.replace("\"Heapsort\"", "\"Bogosort\"");
+        assert!(matches!(
+            SortRecording::from_json(&unknown),
+            Err(ImportRecordingError::Json(error)) if error.contains("Bogosort")
+        ));
+
+        recording.steps.pop();
+        assert_eq!(
+            SortRecording::from_json(&recording.to_json()).err(),
+            Some(ImportRecordingError::StepCount {
+                algorithms: 2,
+                steps: 1
+            })
+        );
+
+        recording.config.race_algorithms.clear();
+        recording.steps[0][3] = vec![SortCommand::Swap(0, recording.input.len())];
+        assert_eq!(
+            SortRecording::from_json(&recording.to_json()).err(),
+            Some(ImportRecordingError::IndexOutOfBounds {
+                algorithm: 0,
+                step: 3
+            })
+        );
+    }
+}
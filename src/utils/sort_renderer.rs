@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use sorting::SortCommand;
+
+/// Inputs longer than this are drawn with [`SortRenderer::Incremental`] when the renderer is [`SortRenderer::Auto`].
+pub const INCREMENTAL_RENDER_LEN: usize = 2000;
+
+/// How the bars of the sorting visualization are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortRenderer {
+    /// [`SortRenderer::Incremental`] for inputs longer than [`INCREMENTAL_RENDER_LEN`], [`SortRenderer::Full`] otherwise.
+    #[default]
+    Auto,
+    /// Redraws every bar on every step, and can draw overlays like the inversions.
+    Full,
+    /// Only redraws the bars that changed since the previous step, which keeps large inputs responsive.
+    Incremental,
+}
+
+impl SortRenderer {
+    pub const ALL: [SortRenderer; 3] = [
+        SortRenderer::Auto,
+        SortRenderer::Full,
+        SortRenderer::Incremental,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortRenderer::Auto => "Automatic",
+            SortRenderer::Full => "Full redraw",
+            SortRenderer::Incremental => "Incremental",
+        }
+    }
+
+    /// Picks the renderer for an input of length `input_len`, which is never [`SortRenderer::Auto`].
+    pub fn resolve(self, input_len: usize) -> Self {
+        match self {
+            SortRenderer::Auto if input_len > INCREMENTAL_RENDER_LEN => SortRenderer::Incremental,
+            SortRenderer::Auto => SortRenderer::Full,
+            renderer => renderer,
+        }
+    }
+}
+
+/// Gets the indices that the commands of a step touch, which are drawn highlighted.
+pub fn step_indices<T>(step: &[SortCommand<T>]) -> Vec<usize> {
+    step.iter()
+        .flat_map(|command| match command {
+            SortCommand::Swap(from, to) => vec![*from, *to],
+            SortCommand::Set(index, _) => vec![*index],
+            SortCommand::PhaseBoundary(_) | SortCommand::Event(_) => vec![],
+        })
+        .collect()
+}
+
+/// Gets the sorted indices of the bars that have to be redrawn when going from `prev_items` with `prev_highlighted` bars
+/// to `items` with `highlighted` bars: the ones whose value changed and the ones that were or are highlighted.
+///
+/// Both inputs have to be the same length, otherwise everything has to be redrawn anyway.
+pub fn dirty_indices<T: PartialEq>(
+    prev_items: &[T],
+    items: &[T],
+    prev_highlighted: &[usize],
+    highlighted: &[usize],
+) -> Vec<usize> {
+    let mut dirty = (0..items.len())
+        .filter(|i| prev_items[*i] != items[*i])
+        .chain(prev_highlighted.iter().copied())
+        .chain(highlighted.iter().copied())
+        .filter(|i| *i < items.len())
+        .collect::<Vec<usize>>();
+    dirty.sort_unstable();
+    dirty.dedup();
+    dirty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_switches_to_incremental_for_large_inputs() {
+        assert_eq!(SortRenderer::Auto.resolve(100), SortRenderer::Full);
+        assert_eq!(
+            SortRenderer::Auto.resolve(INCREMENTAL_RENDER_LEN + 1),
+            SortRenderer::Incremental
+        );
+        assert_eq!(SortRenderer::Full.resolve(1_000_000), SortRenderer::Full);
+    }
+
+    #[test]
+    fn changed_and_highlighted_bars_are_dirty() {
+        let step = vec![
+            SortCommand::Swap(1, 3),
+            SortCommand::<u32>::PhaseBoundary("Pass 2".to_string()),
+        ];
+        assert_eq!(step_indices(&step), [1, 3]);
+
+        // Swapping 1 and 3 changes both, and 5 was highlighted in the previous step
+        let dirty = dirty_indices(
+            &[1, 2, 3, 4, 5, 6],
+            &[1, 4, 3, 2, 5, 6],
+            &[5],
+            &step_indices(&step),
+        );
+        assert_eq!(dirty, [1, 3, 5]);
+
+        assert!(dirty_indices(&[1, 2], &[1, 2], &[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use serde::{Deserialize, Serialize};
+
+/// The shape that the items of the sorting visualization are drawn as, independent of the algorithm and the [`SortRenderer`](super::sort_renderer::SortRenderer).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortVisualization {
+    /// A vertical bar for every item, as high as its value.
+    #[default]
+    Bars,
+    /// A dot for every item, at the height of its value.
+    Scatter,
+    /// A slice of a circle for every item, where the hue encodes the value, so a sorted input is a rainbow.
+    ColorWheel,
+    /// A dot for every item around the center, as far out as its value, so a sorted input is a spiral.
+    Spiral,
+}
+
+impl SortVisualization {
+    pub const ALL: [SortVisualization; 4] = [
+        SortVisualization::Bars,
+        SortVisualization::Scatter,
+        SortVisualization::ColorWheel,
+        SortVisualization::Spiral,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortVisualization::Bars => "Bars",
+            SortVisualization::Scatter => "Scatter plot",
+            SortVisualization::ColorWheel => "Color wheel",
+            SortVisualization::Spiral => "Spiral",
+        }
+    }
+
+    /// Gets the center of the dot of the item at `index` in a canvas of the given size,
+    /// where `value` is the item's value as a fraction of the largest value.\
+    /// Only [`SortVisualization::Scatter`] and [`SortVisualization::Spiral`] are drawn as dots.
+    pub fn dot_position(
+        &self,
+        index: usize,
+        len: usize,
+        value: f64,
+        (width, height): (f64, f64),
+    ) -> (f64, f64) {
+        match self {
+            SortVisualization::Spiral => {
+                let radius = value * width.min(height) * 0.5;
+                let angle = item_angle(index, len);
+                (
+                    width * 0.5 + radius * angle.cos(),
+                    height * 0.5 + radius * angle.sin(),
+                )
+            }
+            _ => (
+                width * (index as f64 + 0.5) / len as f64,
+                height - value * height,
+            ),
+        }
+    }
+}
+
+/// Gets the angle of the item at `index` in the circular visualizations, clockwise from the top.
+pub fn item_angle(index: usize, len: usize) -> f64 {
+    index as f64 / len.max(1) as f64 * TAU - FRAC_PI_2
+}
+
+/// Gets the color of an item in the color wheel, where `value` is the item's value as a fraction of the largest value.\
+/// The hues stop short of the full circle so that the smallest and the largest values don't get the same color.
+pub fn value_color(value: f64) -> String {
+    format!("hsl({:.0}, 80%, 50%)", value.clamp(0.0, 1.0) * 300.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close((x, y): (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (x - expected.0).abs() < 1e-9 && (y - expected.1).abs() < 1e-9,
+            "{:?}",
+            (x, y)
+        );
+    }
+
+    #[test]
+    fn dots_are_placed_by_index_and_value() {
+        let size = (100.0, 50.0);
+
+        // The scatter plot has the largest value at the top and the smallest one at the bottom
+        assert_close(
+            SortVisualization::Scatter.dot_position(0, 4, 1.0, size),
+            (12.5, 0.0),
+        );
+        assert_close(
+            SortVisualization::Scatter.dot_position(3, 4, 0.0, size),
+            (87.5, 50.0),
+        );
+
+        // The spiral starts from the top and goes around clockwise
+        assert_close(
+            SortVisualization::Spiral.dot_position(0, 4, 1.0, size),
+            (50.0, 0.0),
+        );
+        assert_close(
+            SortVisualization::Spiral.dot_position(1, 4, 1.0, size),
+            (75.0, 25.0),
+        );
+        assert_close(
+            SortVisualization::Spiral.dot_position(2, 4, 0.0, size),
+            (50.0, 25.0),
+        );
+    }
+
+    #[test]
+    fn smallest_and_largest_values_have_different_colors() {
+        assert_eq!(value_color(0.0), "hsl(0, 80%, 50%)");
+        assert_eq!(value_color(1.0), "hsl(300, 80%, 50%)");
+        assert_eq!(value_color(f64::INFINITY), value_color(1.0));
+    }
+}
@@ -0,0 +1,129 @@
+//! The state of drawing or erasing walls by dragging over the pathfinding grid.
+
+use std::collections::BTreeSet;
+
+use pathfinding::{Coord, Line, Line2D};
+
+/// Whether a drag adds or removes walls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaintMode {
+    Draw,
+    Erase,
+}
+
+/// Paints walls along the cells that the mouse is dragged over.
+///
+/// The mode is decided by the cell where the drag starts: starting on a wall erases walls and starting on an empty cell draws them,
+/// so the rest of the drag doesn't toggle cells back and forth.
+/// Mouse events can skip cells when the mouse moves quickly, so the line between consecutive cells is painted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WallPainter {
+    mode: PaintMode,
+    last_cell: Coord,
+}
+impl WallPainter {
+    /// Starts a drag at `cell`. The cell itself is painted by the first call to [`WallPainter::paint_to`].
+    pub fn begin(cell: Coord, walls: &BTreeSet<Coord>) -> Self {
+        let mode = if walls.contains(&cell) {
+            PaintMode::Erase
+        } else {
+            PaintMode::Draw
+        };
+        Self {
+            mode,
+            last_cell: cell,
+        }
+    }
+    /// Continues the drag to `cell`, painting every cell on the line from the previous cell.\
+    /// Cells outside the `width` x `height` grid and `protected` cells like the start and the end are left alone.
+    /// Returns `true` if any wall was added or removed.
+    pub fn paint_to(
+        &mut self,
+        cell: Coord,
+        walls: &mut BTreeSet<Coord>,
+        width: usize,
+        height: usize,
+        protected: &[Coord],
+    ) -> bool {
+        let line = Line2D::new(self.last_cell, cell).get_points();
+        self.last_cell = cell;
+
+        let mut changed = false;
+        for cell in line {
+            let in_grid =
+                (0..width as isize).contains(&cell.x) && (0..height as isize).contains(&cell.y);
+            if !in_grid || protected.contains(&cell) {
+                continue;
+            }
+            changed |= match self.mode {
+                PaintMode::Draw => walls.insert(cell),
+                PaintMode::Erase => walls.remove(&cell),
+            };
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Drags over the given cells and returns the walls afterwards.
+    fn drag(path: &[Coord], walls: &BTreeSet<Coord>, protected: &[Coord]) -> BTreeSet<Coord> {
+        let mut walls = walls.clone();
+        let mut painter = WallPainter::begin(path[0], &walls);
+        for cell in path {
+            painter.paint_to(*cell, &mut walls, 10, 10, protected);
+        }
+        walls
+    }
+
+    #[test]
+    fn drag_paints_skipped_cells() {
+        // The mouse jumps from x = 1 to x = 4
+        let path = [Coord::new(1, 2), Coord::new(4, 2), Coord::new(4, 3)];
+        let walls = drag(&path, &BTreeSet::new(), &[]);
+
+        assert_eq!(
+            walls,
+            BTreeSet::from([
+                Coord::new(1, 2),
+                Coord::new(2, 2),
+                Coord::new(3, 2),
+                Coord::new(4, 2),
+                Coord::new(4, 3),
+            ])
+        );
+    }
+
+    #[test]
+    fn drag_from_wall_erases() {
+        let walls = (0..5).map(|x| Coord::new(x, 0)).collect::<BTreeSet<_>>();
+        let painter = WallPainter::begin(Coord::new(1, 0), &walls);
+        assert_eq!(painter.mode, PaintMode::Erase);
+
+        // Crossing an empty cell doesn't draw a wall on it
+        let path = [Coord::new(1, 0), Coord::new(3, 0), Coord::new(3, 1)];
+        assert_eq!(
+            drag(&path, &walls, &[]),
+            BTreeSet::from([Coord::new(0, 0), Coord::new(4, 0)])
+        );
+    }
+
+    #[test]
+    fn protected_and_outside_cells_are_skipped() {
+        let (start, end) = (Coord::new(2, 0), Coord::new(9, 9));
+        let path = [Coord::new(0, 0), Coord::new(3, 0), Coord::new(12, 9)];
+        let walls = drag(&path, &BTreeSet::new(), &[start, end]);
+
+        assert!(!walls.contains(&start) && !walls.contains(&end));
+        assert!(walls.contains(&Coord::new(1, 0)) && walls.contains(&Coord::new(3, 0)));
+        assert!(walls.iter().all(|cell| cell.x < 10 && cell.y < 10));
+
+        // A drag that stays outside of the grid changes nothing
+        let mut unchanged = walls.clone();
+        let mut painter = WallPainter::begin(Coord::new(-1, 3), &unchanged);
+        assert!(!painter.paint_to(Coord::new(-5, 0), &mut unchanged, 10, 10, &[]));
+        assert_eq!(unchanged, walls);
+    }
+}
@@ -0,0 +1,99 @@
+//! Sorting in a web worker, so that the page doesn't freeze while a large input is sorted.
+use crate::pages::sorting::get_sorting_algorithms;
+use serde::{Deserialize, Serialize};
+use sorting::{SortCommand, SortResult, TotalF64};
+use std::time::Duration;
+use yew_agent::{Agent, AgentLink, HandlerId, Public};
+
+/// How many steps are sent to the page in one message once an algorithm is done, so that the steps of a long sort
+/// are sent in chunks instead of one huge message.
+const STEPS_PER_MESSAGE: usize = 10_000;
+
+/// A request to sort the same input with one or more algorithms.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SortJob {
+    /// Sent back with the output, so that the output of a job that has been replaced by a newer one can be ignored.
+    pub id: u32,
+    /// The names of the algorithms, which are sorted one after another in this order.
+    pub algorithms: Vec<String>,
+    pub input: Vec<TotalF64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum SortWorkerOutput {
+    /// The next steps of the algorithm at index `algorithm` of the job.
+    Steps {
+        job: u32,
+        algorithm: usize,
+        steps: Vec<Vec<SortCommand<TotalF64>>>,
+    },
+    /// The algorithm at index `algorithm` of the job is done, and all of its steps have been sent.
+    Sorted {
+        job: u32,
+        algorithm: usize,
+        items: Vec<TotalF64>,
+        duration: Option<Duration>,
+        comparisons: Option<usize>,
+        step_comparisons: Option<Vec<usize>>,
+    },
+}
+
+/// Sorts [`SortJob`]s and sends the steps of each algorithm back in chunks as [`SortWorkerOutput`]s after it's done.
+pub struct SortWorker {
+    link: AgentLink<Self>,
+}
+
+impl Agent for SortWorker {
+    type Reach = Public<Self>;
+    type Message = ();
+    type Input = SortJob;
+    type Output = SortWorkerOutput;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        Self { link }
+    }
+
+    fn update(&mut self, _msg: Self::Message) {}
+
+    fn handle_input(&mut self, job: Self::Input, id: HandlerId) {
+        let algorithms = get_sorting_algorithms();
+
+        for (i, name) in job.algorithms.iter().enumerate() {
+            let mut items = job.input.clone();
+            // An unknown algorithm is still reported as sorted, so that the page doesn't wait for it forever
+            let result = match algorithms
+                .values()
+                .find(|algorithm| algorithm.name == *name)
+            {
+                Some(algorithm) => algorithm.sort(&mut items),
+                None => SortResult::new(None, vec![]),
+            };
+
+            for steps in result.steps.chunks(STEPS_PER_MESSAGE) {
+                self.link.respond(
+                    id,
+                    SortWorkerOutput::Steps {
+                        job: job.id,
+                        algorithm: i,
+                        steps: steps.to_vec(),
+                    },
+                );
+            }
+            self.link.respond(
+                id,
+                SortWorkerOutput::Sorted {
+                    job: job.id,
+                    algorithm: i,
+                    items,
+                    duration: result.duration,
+                    comparisons: result.comparisons,
+                    step_comparisons: result.step_comparisons,
+                },
+            );
+        }
+    }
+
+    fn name_of_resource() -> &'static str {
+        "sort_worker.js"
+    }
+}